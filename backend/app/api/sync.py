@@ -533,6 +533,28 @@ def _apply_change(
         if k in model_fields and k not in SYNC_PROTECTED_FIELDS and k not in derived
     }
 
+    # Aidat: üye + dönem tekildir. Zamanlanmış tahakkuk kimliği dönemden türettiği için
+    # iki cihazın aynı dönemi işlemesi aynı kayda düşer; farklı kimlikle gelen aynı dönem
+    # aidatı (ör. bir cihazda elle açılmış) mükerrer borç olur, reddedilir.
+    if model is AidatTakip:
+        donem = {
+            k: payload.get(k, getattr(row, k, None)) for k in ("uye_id", "yil", "ay")
+        }
+        cakisan = session.exec(
+            select(AidatTakip).where(
+                AidatTakip.tenant_id == tenant_id,
+                AidatTakip.uye_id == donem["uye_id"],
+                AidatTakip.yil == donem["yil"],
+                AidatTakip.ay == donem["ay"],
+                AidatTakip.id != item.id,
+            )
+        ).first()
+        if cakisan is not None:
+            return SyncItemResult(
+                table=item.table, id=item.id, status="rejected",
+                reason=f"donem_mukerrer: {cakisan.id}", change_id=item.change_id,
+            )
+
     if row is None:
         # Yeni kayıt (update gelse bile sunucuda yoksa insert edilir —
         # sunucu DB'si sıfırlanmış olabilir, veri kaybetmeyiz).
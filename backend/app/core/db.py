@@ -16,6 +16,27 @@ engine = create_engine(DATABASE_URL, connect_args=connect_args)
 def create_db_and_tables():
     SQLModel.metadata.create_all(engine)
     ensure_model_columns()
+    ensure_aidat_donem_index()
+
+
+def ensure_aidat_donem_index():
+    """Mevcut veritabanlarına aidat_takip üye + dönem tekil indeksini ekler.
+
+    create_all var olan tablolara indeks eklemez. Daha önce oluşmuş mükerrer
+    kayıtlar varsa indeks kurulamaz; uyarı yazılır, sync yine de aynı dönem
+    aidatını farklı kimlikle kabul etmez (bkz. app.api.sync).
+    """
+    from sqlalchemy import text
+    from sqlalchemy.exc import IntegrityError
+
+    try:
+        with engine.begin() as conn:
+            conn.execute(text(
+                "CREATE UNIQUE INDEX IF NOT EXISTS uq_aidat_takip_donem "
+                "ON aidat_takip (tenant_id, uye_id, yil, ay)"
+            ))
+    except IntegrityError as e:
+        print(f"⚠️ aidat_takip dönem indeksi kurulamadı (mükerrer kayıt var): {e}")
 
 
 def ensure_model_columns():
@@ -1,6 +1,7 @@
 from typing import Optional, List
 from datetime import datetime
 from sqlmodel import SQLModel, Field, Relationship
+from sqlalchemy import Index
 from enum import Enum
 import uuid
 
@@ -124,6 +125,7 @@ class Kasa(SQLModel, table=True):
     bakiye: float = Field(default=0.0)
     para_birimi: str = Field(default="TRY")
     devir_bakiye: float = Field(default=0.0)
+    devir_tarihi: Optional[str] = None  # devir_bakiye bu tarihten önceki hareketleri kapsar
     toplam_gelir: float = Field(default=0.0)
     toplam_gider: float = Field(default=0.0)
     virman_giris: float = Field(default=0.0)
@@ -262,6 +264,10 @@ class Uye(SQLModel, table=True):
 # --- AIDAT TAKIP (DUES TRACKING) ---
 class AidatTakip(SQLModel, table=True):
     __tablename__ = "aidat_takip"
+    # Masaüstündeki UNIQUE(tenant_id, uye_id, yil, ay) ile aynı: üye + dönem başına tek aidat
+    __table_args__ = (
+        Index("uq_aidat_takip_donem", "tenant_id", "uye_id", "yil", "ay", unique=True),
+    )
     id: str = Field(default_factory=lambda: str(uuid.uuid4()), primary_key=True)
     tenant_id: str = Field(index=True)
     uye_id: str
@@ -43,6 +43,7 @@ CREATE TABLE kasa (
     bakiye REAL NOT NULL DEFAULT 0,
     para_birimi TEXT NOT NULL DEFAULT 'TRY',
     devir_bakiye REAL DEFAULT 0,
+    devir_tarihi TEXT,
     toplam_gelir REAL DEFAULT 0,
     toplam_gider REAL DEFAULT 0,
     virman_giris REAL DEFAULT 0,
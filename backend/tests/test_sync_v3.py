@@ -6,6 +6,7 @@ Kapsanan garanti: hiçbir koşulda sessiz veri kaybı olmaz —
 - silinmiş kaydın update ile dirilmesi -> conflict (deleted_on_server)
 - tombstone delta'da taşınır
 - push edilen kayıt aynı isteğin delta'sında geri yankılanmaz
+- aynı üye + dönem için farklı kimlikle gelen aidat reddedilir
 """
 import os
 import tempfile
@@ -151,6 +152,7 @@ def test_kasa_turetilmis_alanlar_uygulanmaz(client):
         "data": {
             "id": "k1", "tenant_id": TENANT_ID, "kasa_adi": "Ana Kasa",
             "para_birimi": "TRY", "devir_bakiye": 100.0,
+            "devir_tarihi": "2026-01-01",
             "bakiye": 99999.0,  # türetilmiş — uygulanmamalı
         },
         "version": 1,
@@ -163,4 +165,29 @@ def test_kasa_turetilmis_alanlar_uygulanmaz(client):
     with Session(engine) as s:
         k = s.get(Kasa, "k1")
         assert k.devir_bakiye == 100.0
+        assert k.devir_tarihi == "2026-01-01"  # kesim tarihi devir bakiyesiyle birlikte taşınır
         assert k.bakiye == 0.0  # payload'daki 99999 uygulanmadı
+
+
+def test_ayni_donem_aidati_farkli_kimlikle_reddedilir(client):
+    aidat = {"tenant_id": TENANT_ID, "uye_id": "u-aidat", "yil": 2026, "ay": 3, "tutar": 100.0}
+    r = sync(client, [{
+        "table": "aidat_takip", "id": "aidat-1", "operation": "insert",
+        "data": {"id": "aidat-1", **aidat}, "version": 1,
+    }])
+    assert len(r["applied"]) == 1
+
+    # Başka cihazın aynı dönem için açtığı kayıt mükerrer borç olurdu
+    r = sync(client, [{
+        "table": "aidat_takip", "id": "aidat-2", "operation": "insert",
+        "data": {"id": "aidat-2", **aidat}, "version": 1,
+    }])
+    assert len(r["applied"]) == 0
+    assert r["rejected"][0]["reason"] == "donem_mukerrer: aidat-1"
+
+    # Sonraki dönem serbest
+    r = sync(client, [{
+        "table": "aidat_takip", "id": "aidat-3", "operation": "insert",
+        "data": {"id": "aidat-3", **aidat, "ay": 4}, "version": 1,
+    }])
+    assert len(r["applied"]) == 1
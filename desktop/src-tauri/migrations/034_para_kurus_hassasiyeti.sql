@@ -0,0 +1,99 @@
+-- Migration: Para tutarlarını kuruş hassasiyetine dönüştürme
+-- Date: 2026-10-18
+-- Description: Tüm tutar kolonları tam kuruşa yuvarlanır; uygulama tarafında tutarlar
+-- db::para::Para (i64 kuruş) ile taşınır ve toplamlar INTEGER kuruş olarak alınır.
+-- Kolonlar REAL kalır (sync payload sözleşmesi TL cinsinden sayı), ancak artık her
+-- değer tam kuruşun en yakın temsili olduğundan REAL toplam kayması oluşmaz.
+-- Dönüşüm öncesi kasa bakiyeleri para_donusum_kontrol tablosuna alınır;
+-- check_kasa_bakiye_tutarliligi komutu öncesi/sonrası karşılaştırmasını raporlar.
+-- Yuvarlama her cihazda aynı migration ile yapıldığı için outbox'a yazılmaz.
+
+-- 1. Dönüşüm öncesi kasa bakiyeleri (tutarlılık denetimi için)
+CREATE TABLE IF NOT EXISTS para_donusum_kontrol (
+    kasa_id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    bakiye REAL,
+    fiziksel_bakiye REAL,
+    toplam_gelir REAL,
+    toplam_gider REAL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+INSERT OR IGNORE INTO para_donusum_kontrol (kasa_id, tenant_id, bakiye, fiziksel_bakiye, toplam_gelir, toplam_gider)
+SELECT id, tenant_id, bakiye, COALESCE(fiziksel_bakiye, bakiye), toplam_gelir, toplam_gider FROM kasalar;
+
+-- 2. Devir kesim tarihi: devir_bakiye bu tarihten önceki hareketleri kapsar
+ALTER TABLE kasalar ADD COLUMN devir_tarihi TEXT;
+
+-- 3. Ana muhasebe tabloları
+UPDATE gelirler SET tutar = ROUND(tutar, 2) WHERE tutar IS NOT NULL;
+UPDATE giderler SET tutar = ROUND(tutar, 2) WHERE tutar IS NOT NULL;
+UPDATE virmanlar SET tutar = ROUND(tutar, 2), kaynak_tutar = ROUND(kaynak_tutar, 2), hedef_tutar = ROUND(hedef_tutar, 2);
+UPDATE kasalar SET
+    bakiye = ROUND(COALESCE(bakiye, 0), 2),
+    devir_bakiye = ROUND(devir_bakiye, 2),
+    toplam_gelir = ROUND(toplam_gelir, 2),
+    toplam_gider = ROUND(toplam_gider, 2),
+    virman_giris = ROUND(virman_giris, 2),
+    virman_cikis = ROUND(virman_cikis, 2),
+    fiziksel_bakiye = ROUND(fiziksel_bakiye, 2),
+    tahakkuk_tutari = ROUND(tahakkuk_tutari, 2),
+    serbest_bakiye = ROUND(serbest_bakiye, 2);
+
+-- 4. Aidat
+UPDATE aidat_takip SET tutar = ROUND(tutar, 2), odenen = ROUND(odenen, 2), kalan = ROUND(kalan, 2), gecikme_faiz = ROUND(gecikme_faiz, 2);
+UPDATE aidat_tanimlari SET tutar = ROUND(tutar, 2);
+UPDATE uyeler SET ozel_aidat_tutari = ROUND(ozel_aidat_tutari, 2) WHERE ozel_aidat_tutari IS NOT NULL;
+
+-- 5. Cariler
+UPDATE cariler SET borc_bakiye = ROUND(borc_bakiye, 2), alacak_bakiye = ROUND(alacak_bakiye, 2), kredi_limiti = ROUND(kredi_limiti, 2);
+UPDATE cari_hareketler SET tutar = ROUND(tutar, 2), odenen = ROUND(odenen, 2), kalan = ROUND(kalan, 2);
+
+-- 6. Köy muhasebesi
+UPDATE koy_kasalar SET bakiye = ROUND(bakiye, 2);
+UPDATE koy_gelirler SET tutar = ROUND(tutar, 2);
+UPDATE koy_giderler SET tutar = ROUND(tutar, 2);
+UPDATE koy_virmanlar SET tutar = ROUND(tutar, 2);
+
+-- 7. Eski devirler için kesim tarihi
+-- Önceki devir işlemi devir_bakiye = fiziksel_bakiye yazıp tarih tutmuyordu; devir_tarihi
+-- boş kalırsa bakiye hesabı önceki yılların hareketlerini ikinci kez sayar. Kesim, devir
+-- bakiyesine sonraki hareketler eklendiğinde dönüşüm öncesi fiziksel bakiyeyi veren en erken
+-- hareket günüdür. Tutmazsa kesim son hareketin ertesi günü olur ve devir_bakiye dönüşüm
+-- öncesi fiziksel bakiyeye eşitlenir; iki durumda da kasa bakiyesi değişmez.
+CREATE TEMP TABLE devir_hareket AS
+SELECT kasa_id, substr(tarih, 1, 10) AS gun, CAST(ROUND(tutar * 100) AS INTEGER) AS kurus
+  FROM gelirler WHERE is_deleted IS NULL OR is_deleted = 0
+UNION ALL
+SELECT kasa_id, substr(tarih, 1, 10), -CAST(ROUND(tutar * 100) AS INTEGER)
+  FROM giderler WHERE is_deleted IS NULL OR is_deleted = 0
+UNION ALL
+SELECT hedef_kasa_id, substr(tarih, 1, 10), CAST(ROUND(COALESCE(hedef_tutar, tutar) * 100) AS INTEGER)
+  FROM virmanlar WHERE is_deleted IS NULL OR is_deleted = 0
+UNION ALL
+SELECT kaynak_kasa_id, substr(tarih, 1, 10), -CAST(ROUND(tutar * 100) AS INTEGER)
+  FROM virmanlar WHERE is_deleted IS NULL OR is_deleted = 0;
+
+UPDATE kasalar SET devir_tarihi = (
+    SELECT MIN(a.gun) FROM (
+        SELECT DISTINCT kasa_id, gun FROM devir_hareket
+        UNION
+        SELECT kasa_id, date(MAX(gun), '+1 day') FROM devir_hareket GROUP BY kasa_id
+    ) a
+    WHERE a.kasa_id = kasalar.id
+      AND CAST(ROUND(kasalar.devir_bakiye * 100) AS INTEGER)
+          + (SELECT COALESCE(SUM(h.kurus), 0) FROM devir_hareket h WHERE h.kasa_id = a.kasa_id AND h.gun >= a.gun)
+          = (SELECT CAST(ROUND(p.fiziksel_bakiye * 100) AS INTEGER) FROM para_donusum_kontrol p WHERE p.kasa_id = kasalar.id)
+)
+WHERE devir_tarihi IS NULL AND ROUND(COALESCE(devir_bakiye, 0), 2) != 0;
+
+UPDATE kasalar SET
+    devir_tarihi = COALESCE(
+        (SELECT date(MAX(h.gun), '+1 day') FROM devir_hareket h WHERE h.kasa_id = kasalar.id),
+        date(COALESCE(created_at, 'now'))),
+    devir_bakiye = COALESCE(
+        (SELECT ROUND(p.fiziksel_bakiye, 2) FROM para_donusum_kontrol p WHERE p.kasa_id = kasalar.id),
+        devir_bakiye)
+WHERE devir_tarihi IS NULL AND ROUND(COALESCE(devir_bakiye, 0), 2) != 0;
+
+DROP TABLE devir_hareket;
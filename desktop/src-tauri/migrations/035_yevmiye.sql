@@ -0,0 +1,58 @@
+-- Migration: Çift taraflı kayıt (yevmiye) altyapısı
+-- Date: 2026-10-18
+-- Description: Hesap planı, yevmiye fişleri ve fiş satırları.
+-- Fişler kaynak kayıtlardan (gelirler, giderler, virmanlar, aidat_takip, cari_hareketler,
+-- kasalar açılışı) db::yevmiye tarafından aynı transaction içinde üretilir.
+-- Kaynak değiştiğinde eski fiş silinmez, durum = 'iptal' olarak işaretlenir (denetim izi).
+-- Tablolar sync yüzeyinde değildir: her cihaz kendi yevmiyesini baz kayıtlardan üretir.
+
+CREATE TABLE IF NOT EXISTS hesap_plani (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    kod TEXT NOT NULL,
+    ad TEXT NOT NULL,
+    hesap_turu TEXT NOT NULL,
+    ust_kod TEXT,
+    bagli_tablo TEXT,
+    bagli_id TEXT,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (tenant_id, kod)
+);
+
+CREATE INDEX IF NOT EXISTS idx_hesap_plani_bagli ON hesap_plani(tenant_id, bagli_tablo, bagli_id);
+
+CREATE TABLE IF NOT EXISTS yevmiye_fisleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    yil INTEGER NOT NULL,
+    fis_no INTEGER NOT NULL,
+    tarih TEXT NOT NULL,
+    aciklama TEXT,
+    kaynak_tablo TEXT NOT NULL,
+    kaynak_id TEXT NOT NULL,
+    durum TEXT NOT NULL DEFAULT 'aktif',
+    iptal_tarihi TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_yevmiye_fisleri_kaynak ON yevmiye_fisleri(tenant_id, kaynak_tablo, kaynak_id, durum);
+CREATE INDEX IF NOT EXISTS idx_yevmiye_fisleri_tarih ON yevmiye_fisleri(tenant_id, tarih);
+
+CREATE TABLE IF NOT EXISTS yevmiye_satirlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    fis_id TEXT NOT NULL,
+    sira INTEGER NOT NULL,
+    hesap_id TEXT NOT NULL,
+    hesap_kodu TEXT NOT NULL,
+    borc REAL NOT NULL DEFAULT 0,
+    alacak REAL NOT NULL DEFAULT 0,
+    aciklama TEXT,
+    FOREIGN KEY (fis_id) REFERENCES yevmiye_fisleri(id),
+    FOREIGN KEY (hesap_id) REFERENCES hesap_plani(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_yevmiye_satirlari_fis ON yevmiye_satirlari(fis_id);
+CREATE INDEX IF NOT EXISTS idx_yevmiye_satirlari_hesap ON yevmiye_satirlari(tenant_id, hesap_id);
@@ -0,0 +1,76 @@
+-- Migration: Muhasebe dönemi kilitleri
+-- Date: 2026-10-18
+-- Description: Tenant bazında yıl (ay = NULL) veya ay kilitleri ve kilitli döneme
+-- düşen sync değişikliklerinin kaydı. Kilit açılınca satır silinmez; durum = 'acik'
+-- olur ve açan kullanıcı ile gerekçe saklanır.
+-- Tablolar sync yüzeyinde değildir: kilit her cihazda yerel olarak uygulanır, kilitli
+-- döneme gelen uzak değişiklikler donem_kilidi_ihlalleri'ne düşer.
+
+CREATE TABLE IF NOT EXISTS donem_kilitleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    yil INTEGER NOT NULL,
+    ay INTEGER,
+    durum TEXT NOT NULL DEFAULT 'kilitli',
+    aciklama TEXT,
+    kilitleyen TEXT,
+    kilit_tarihi TEXT NOT NULL,
+    acan TEXT,
+    acilis_tarihi TEXT,
+    acilis_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_donem_kilitleri_donem ON donem_kilitleri(tenant_id, yil, durum);
+
+CREATE TABLE IF NOT EXISTS donem_kilidi_ihlalleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    kilit_id TEXT NOT NULL,
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    islem TEXT NOT NULL,
+    degisiklik TEXT NOT NULL,
+    durum TEXT NOT NULL DEFAULT 'bekliyor',
+    karar_veren TEXT,
+    karar_tarihi TEXT,
+    karar_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (kilit_id) REFERENCES donem_kilitleri(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_donem_kilidi_ihlalleri_durum ON donem_kilidi_ihlalleri(tenant_id, durum);
+
+-- Yıl sonu devri daha önce uygulanmış yılları kilitle. Eski sürümün devirlerinde kesim
+-- tarihi tutulmuyordu; 034 bu kasalar için devir_tarihi'ni devir_bakiye ve hareket
+-- geçmişinden türettiği için burada her devirli kasanın tarihi doludur. Son kesimden önce
+-- kalan (yani devir_bakiye'ye girmiş) her yıl kilitlenir: tenant'ın ilk hareket yılından
+-- son kesimden önceki yıla kadar, hareket yoksa yalnızca o yıl. Kilit yıl bazında
+-- eşleştiği için (kilit_bul) her yıl ayrı satırdır.
+INSERT INTO donem_kilitleri (id, tenant_id, yil, ay, durum, aciklama, kilit_tarihi, created_at, updated_at)
+WITH RECURSIVE devir AS (
+    SELECT tenant_id, CAST(strftime('%Y', MAX(devir_tarihi)) AS INTEGER) - 1 AS son_yil
+    FROM kasalar
+    WHERE devir_tarihi IS NOT NULL
+    GROUP BY tenant_id
+),
+hareket AS (
+    SELECT tenant_id, tarih FROM gelirler WHERE is_deleted IS NULL OR is_deleted = 0
+    UNION ALL
+    SELECT tenant_id, tarih FROM giderler WHERE is_deleted IS NULL OR is_deleted = 0
+    UNION ALL
+    SELECT tenant_id, tarih FROM virmanlar WHERE is_deleted IS NULL OR is_deleted = 0
+),
+yillar (tenant_id, yil, son_yil) AS (
+    SELECT d.tenant_id,
+           MIN(d.son_yil, COALESCE((SELECT MIN(CAST(substr(h.tarih, 1, 4) AS INTEGER)) FROM hareket h
+                                     WHERE h.tenant_id = d.tenant_id AND h.tarih GLOB '[0-9][0-9][0-9][0-9]*'), d.son_yil)),
+           d.son_yil
+    FROM devir d
+    UNION ALL
+    SELECT tenant_id, yil + 1, son_yil FROM yillar WHERE yil < son_yil
+)
+SELECT lower(hex(randomblob(16))), tenant_id, yil, NULL,
+       'kilitli', 'Yıl sonu devri', datetime('now'), datetime('now'), datetime('now')
+FROM yillar;
@@ -0,0 +1,25 @@
+-- Migration: Değiştirilemez denetim izi
+-- Date: 2026-10-18
+-- Description: Her yazım komutunun kullanıcı, komut adı, tablo, kayıt id ve
+-- önceki/sonraki satır görüntüsü (JSON) ile kaydı. Satırlar db::denetim tarafından
+-- değişiklikle aynı transaction'da eklenir; UPDATE/DELETE açılışta kurulan
+-- tetikleyicilerle engellenir (yalnızca ekleme).
+-- Tablo sync yüzeyinde değildir: her cihaz kendi yaptığı değişikliklerin izini tutar.
+
+CREATE TABLE IF NOT EXISTS denetim_kayitlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    kullanici_id TEXT,
+    kullanici_adi TEXT,
+    komut TEXT NOT NULL,
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    islem TEXT NOT NULL,
+    once TEXT,
+    sonra TEXT,
+    degisen_alanlar TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_denetim_kayit ON denetim_kayitlari(tenant_id, table_name, record_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_denetim_kullanici ON denetim_kayitlari(tenant_id, kullanici_id, created_at);
@@ -0,0 +1,61 @@
+-- Migration: Banka ekstresi aktarımı
+-- Date: 2026-10-18
+-- Description: CSV / camt.053 / MT940 banka ekstrelerinin bir banka kasasına aktarımı.
+-- Her aktarımın satırları mevcut gelir/gider kayıtlarıyla eşleştirilir (öneri),
+-- kullanıcı inceledikten sonra eşleşmeyenler gelir/gider olarak oluşturulur.
+-- Satır durumları: oneri, eslesti, yeni, mukerrer, atlandi, olusturuldu.
+-- Aktarım tabloları yerel çalışma kayıtlarıdır, sync yüzeyinde değildir;
+-- oluşturulan gelir/gider kayıtları her zamanki gibi outbox'a girer.
+
+CREATE TABLE IF NOT EXISTS banka_ekstre_aktarimlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    kasa_id TEXT NOT NULL,
+    dosya_adi TEXT,
+    bicim TEXT NOT NULL,
+    hesap TEXT,
+    donem_baslangic TEXT,
+    donem_bitis TEXT,
+    acilis_bakiyesi REAL,
+    kapanis_bakiyesi REAL,
+    satir_sayisi INTEGER NOT NULL DEFAULT 0,
+    csv_eslestirme TEXT,
+    tarih_toleransi INTEGER NOT NULL DEFAULT 3,
+    durum TEXT NOT NULL DEFAULT 'inceleniyor',
+    kullanici_id TEXT,
+    uygulama_tarihi TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (kasa_id) REFERENCES kasalar(id)
+);
+
+CREATE TABLE IF NOT EXISTS banka_ekstre_satirlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    aktarim_id TEXT NOT NULL,
+    sira INTEGER NOT NULL,
+    tarih TEXT NOT NULL,
+    valor_tarihi TEXT,
+    tutar REAL NOT NULL,
+    aciklama TEXT,
+    dekont_no TEXT,
+    karsi_taraf TEXT,
+    karsi_iban TEXT,
+    parmak_izi TEXT NOT NULL,
+    durum TEXT NOT NULL,
+    eslesen_tablo TEXT,
+    eslesen_id TEXT,
+    eslesme_puani INTEGER,
+    tur_id TEXT,
+    uye_id TEXT,
+    olusan_tablo TEXT,
+    olusan_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (aktarim_id) REFERENCES banka_ekstre_aktarimlari(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_banka_aktarim_kasa ON banka_ekstre_aktarimlari(tenant_id, kasa_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_banka_satir_aktarim ON banka_ekstre_satirlari(aktarim_id, sira);
+CREATE INDEX IF NOT EXISTS idx_banka_satir_parmak ON banka_ekstre_satirlari(tenant_id, parmak_izi);
+CREATE INDEX IF NOT EXISTS idx_banka_satir_eslesen ON banka_ekstre_satirlari(tenant_id, eslesen_id);
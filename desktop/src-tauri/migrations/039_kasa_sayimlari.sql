@@ -0,0 +1,32 @@
+-- Migration: Kasa sayımı ve mutabakat
+-- Date: 2026-10-18
+-- Description: Nakit kasalar için küpür bazında sayım, banka kasaları için ekstre
+-- bakiyesi kaydı. Sayım anındaki defter bakiyesi ve fark saklanır; onaylayan kullanıcı
+-- farkı açıklar ve isterse düzeltme kaydı (gelir: sayım fazlası, gider: sayım açığı)
+-- oluşturulur. Durumlar: sayildi, onaylandi, iptal.
+-- Tablo sync yüzeyinde değildir; oluşan düzeltme gelir/gider kaydı outbox'a girer.
+
+CREATE TABLE IF NOT EXISTS kasa_sayimlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    kasa_id TEXT NOT NULL,
+    sayim_tarihi TEXT NOT NULL,
+    sayim_tipi TEXT NOT NULL,
+    kupurler TEXT,
+    sayilan_tutar REAL NOT NULL,
+    defter_bakiyesi REAL NOT NULL,
+    fark REAL NOT NULL,
+    aciklama TEXT,
+    durum TEXT NOT NULL DEFAULT 'sayildi',
+    sayan TEXT,
+    onaylayan TEXT,
+    onay_tarihi TEXT,
+    fark_aciklamasi TEXT,
+    duzeltme_tablo TEXT,
+    duzeltme_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (kasa_id) REFERENCES kasalar(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_kasa_sayimlari_kasa ON kasa_sayimlari(tenant_id, kasa_id, sayim_tarihi);
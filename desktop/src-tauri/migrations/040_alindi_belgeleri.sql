@@ -0,0 +1,51 @@
+-- Migration: Alındı belgesi ciltleri
+-- Date: 2026-10-18
+-- Description: Matbaada bastırılan alındı belgesi ciltleri (seri, sıra no aralığı, matbaa
+-- faturası), teslim alan görevli ve yetki belgesi. Kesilen ve iptal edilen her sıra numarası
+-- alindi_belgesi_kullanimlari'nda tutulur; (cilt_id, sira_no) tekildir, aynı numara iki kez
+-- verilemez. Cilt durumları: aktif, iade, kapali. Numara durumları: kullanildi, iptal.
+-- Tablolar sync yüzeyinde değildir; numara gelirler.makbuz_no'ya yazılır ve onunla senkronlanır.
+
+CREATE TABLE IF NOT EXISTS alindi_belgesi_ciltleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    seri TEXT NOT NULL DEFAULT '',
+    cilt_no TEXT,
+    baslangic_no INTEGER NOT NULL,
+    bitis_no INTEGER NOT NULL,
+    matbaa_adi TEXT,
+    matbaa_fatura_no TEXT,
+    matbaa_fatura_tarihi TEXT,
+    teslim_alan TEXT,
+    teslim_tarihi TEXT,
+    yetki_belgesi_no TEXT,
+    yetki_baslangic TEXT,
+    yetki_bitis TEXT,
+    iade_tarihi TEXT,
+    durum TEXT NOT NULL DEFAULT 'aktif',
+    aciklama TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_alindi_ciltleri_teslim ON alindi_belgesi_ciltleri(tenant_id, teslim_alan, durum);
+
+CREATE TABLE IF NOT EXISTS alindi_belgesi_kullanimlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    cilt_id TEXT NOT NULL,
+    sira_no INTEGER NOT NULL,
+    makbuz_no TEXT NOT NULL,
+    durum TEXT NOT NULL,
+    gelir_id TEXT,
+    tarih TEXT NOT NULL,
+    kullanici_id TEXT,
+    iptal_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (cilt_id) REFERENCES alindi_belgesi_ciltleri(id),
+    UNIQUE (cilt_id, sira_no)
+);
+
+CREATE INDEX IF NOT EXISTS idx_alindi_kullanim_gelir ON alindi_belgesi_kullanimlari(gelir_id);
+CREATE INDEX IF NOT EXISTS idx_alindi_kullanim_makbuz ON alindi_belgesi_kullanimlari(tenant_id, makbuz_no);
@@ -0,0 +1,6 @@
+-- Migration: Tenant logosu
+-- Date: 2026-10-18
+-- Description: Yazdırılabilir belgelerin (makbuz, üye hesap ekstresi, cari ekstre) başlığında
+-- kullanılan dernek logosu. Base64 kodlu JPEG ya da PNG olarak saklanır.
+
+ALTER TABLE tenants ADD COLUMN logo TEXT;
@@ -0,0 +1,29 @@
+-- Migration: Aidat fiyat kuralları
+-- Date: 2026-10-18
+-- Description: Toplu ve kişi bazlı aidat tahakkukunda taban tutara (üye türüne göre aidat
+-- tanımı) sırayla uygulanan kurallar. Koşullar (hepsi isteğe bağlı, birlikte sağlanmalı):
+-- yil, uye_turu, yas_min/yas_max (yıl başındaki yaş), ozel_durum (ogrenci, emekli, aile).
+-- İşlemler: sabit (tutarı değer yap), yuzde_indirim, tutar_indirim, ust_sinir, alt_sinir,
+-- kist_giris (yıl içinde katılanlara kalan ay oranında). durdur = 1 ise eşleşen kuraldan sonra
+-- başka kural uygulanmaz. Tablo sync yüzeyinde değildir; sonuç aidat_takip.tutar ile senkronlanır.
+
+CREATE TABLE IF NOT EXISTS aidat_fiyat_kurallari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    ad TEXT NOT NULL,
+    sira INTEGER NOT NULL DEFAULT 100,
+    yil INTEGER,
+    uye_turu TEXT,
+    yas_min INTEGER,
+    yas_max INTEGER,
+    ozel_durum TEXT CHECK (ozel_durum IS NULL OR ozel_durum IN ('ogrenci', 'emekli', 'aile')),
+    islem TEXT NOT NULL CHECK (islem IN ('sabit', 'yuzde_indirim', 'tutar_indirim', 'ust_sinir', 'alt_sinir', 'kist_giris')),
+    deger REAL NOT NULL DEFAULT 0,
+    durdur INTEGER NOT NULL DEFAULT 0,
+    aciklama TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_aidat_fiyat_kurallari_tenant ON aidat_fiyat_kurallari(tenant_id, is_active, sira);
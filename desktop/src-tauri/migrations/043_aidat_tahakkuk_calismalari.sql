@@ -0,0 +1,30 @@
+-- Migration: Zamanlanmış aidat tahakkuku çalışmaları
+-- Date: 2026-10-18
+-- Description: Uygulama içi zamanlayıcının (ve elle tetiklenen çalıştırmanın) her aidat tipi
+-- (Yıllık, Üç aylık, Aylık) ve dönem için yaptığı tahakkuk çalışmasının kaydı. yil/ay dönem
+-- başlangıcıdır (Yıllık: ay = 1, Üç aylık: 1/4/7/10). Son başarılı dönemden sonraki dönemler
+-- uygulama kapalıyken kaçırılmış sayılır ve sırayla tamamlanır. durum: basarili, kismi (bazı
+-- üyelerde hata), hatali (dönem kilitli vb.). Tablo sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS aidat_tahakkuk_calismalari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    aidat_tipi TEXT NOT NULL,
+    donem TEXT NOT NULL,
+    yil INTEGER NOT NULL,
+    ay INTEGER NOT NULL,
+    tetikleyen TEXT NOT NULL CHECK (tetikleyen IN ('zamanlayici', 'manuel')),
+    kullanici_id TEXT,
+    durum TEXT NOT NULL CHECK (durum IN ('basarili', 'kismi', 'hatali')),
+    olusturulan INTEGER NOT NULL DEFAULT 0,
+    atlanan INTEGER NOT NULL DEFAULT 0,
+    hatali INTEGER NOT NULL DEFAULT 0,
+    toplam_tutar REAL NOT NULL DEFAULT 0,
+    hata TEXT,
+    baslangic TEXT NOT NULL,
+    bitis TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_aidat_tahakkuk_calismalari_donem ON aidat_tahakkuk_calismalari(tenant_id, aidat_tipi, durum, yil, ay);
+
+CREATE INDEX IF NOT EXISTS idx_aidat_tahakkuk_calismalari_tarih ON aidat_tahakkuk_calismalari(tenant_id, baslangic);
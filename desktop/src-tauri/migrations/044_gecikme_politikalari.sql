@@ -0,0 +1,61 @@
+-- Migration: Gecikme faizi politikaları ve gecikme faizi alacakları
+-- Date: 2026-10-18
+-- Description: Vadesi geçmiş aidatlar için gecikme faizi kuralları ve tahakkuk edilen faizler.
+-- Politika üye türüne özel olabilir (uye_turu boşsa varsayılan). Vade, aidat tanımının
+-- son_odeme_gunu ile aidat dönemi ayından bulunur; odemesiz_gun dolana kadar faiz işlemez,
+-- dolduktan sonra vadeden itibaren hesaplanır. hesaplama: basit veya bilesik; oran_birimi:
+-- aylik (30 gün) veya gunluk; oran boşsa aidat tanımının gecikme_faiz_orani kullanılır.
+-- tavan_yuzde faizi anaparanın yüzdesiyle sınırlar; asgari_tutar altındaki tahakkuklar yazılmaz.
+-- feragat_yonetici = 1 ise faizden vazgeçmeyi yalnızca yönetici yapabilir.
+-- gecikme_faizleri aidattan ayrı bir alacaktır: yevmiyede 127 (B) / Gecikme Faizi geliri (A),
+-- net tutar = tutar - feragat_tutari. Tahsilat gelir kaydıyla yapılır (gelir_id).
+-- Tablolar sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS gecikme_politikalari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    ad TEXT NOT NULL,
+    uye_turu TEXT,
+    odemesiz_gun INTEGER NOT NULL DEFAULT 0,
+    hesaplama TEXT NOT NULL DEFAULT 'basit' CHECK (hesaplama IN ('basit', 'bilesik')),
+    oran_birimi TEXT NOT NULL DEFAULT 'aylik' CHECK (oran_birimi IN ('aylik', 'gunluk')),
+    oran REAL,
+    tavan_yuzde REAL,
+    asgari_tutar REAL NOT NULL DEFAULT 0,
+    feragat_yonetici INTEGER NOT NULL DEFAULT 1,
+    aciklama TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_gecikme_politikalari_tenant ON gecikme_politikalari(tenant_id, is_active);
+
+CREATE TABLE IF NOT EXISTS gecikme_faizleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    aidat_id TEXT NOT NULL,
+    uye_id TEXT NOT NULL,
+    politika_id TEXT,
+    hesap_tarihi TEXT NOT NULL,
+    vade_tarihi TEXT NOT NULL,
+    gecikme_gun INTEGER NOT NULL,
+    anapara REAL NOT NULL,
+    tutar REAL NOT NULL,
+    feragat_tutari REAL NOT NULL DEFAULT 0,
+    odenen REAL NOT NULL DEFAULT 0,
+    durum TEXT NOT NULL DEFAULT 'acik' CHECK (durum IN ('acik', 'odendi', 'feragat')),
+    gelir_id TEXT,
+    feragat_nedeni TEXT,
+    feragat_eden TEXT,
+    feragat_tarihi TEXT,
+    aciklama TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_gecikme_faizleri_aidat ON gecikme_faizleri(tenant_id, aidat_id);
+
+CREATE INDEX IF NOT EXISTS idx_gecikme_faizleri_uye ON gecikme_faizleri(tenant_id, uye_id, durum);
+
+CREATE INDEX IF NOT EXISTS idx_gecikme_faizleri_gelir ON gecikme_faizleri(gelir_id);
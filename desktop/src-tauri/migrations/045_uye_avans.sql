@@ -0,0 +1,26 @@
+-- Migration: Üye avans (alacak bakiyesi) hareketleri
+-- Date: 2026-10-18
+-- Description: Üyenin borcundan fazla ödediği tutar avans olarak tutulur. Bakiye hareketlerin
+-- toplamıdır: giris (fazla ödeme veya peşin ödeme, gelir_id) artırır; mahsup (açık aidata
+-- uygulanan, aidat_id) ve iade (gider kaydıyla geri ödenen, gider_id) azaltır (negatif tutar).
+-- Avans açık aidatlara en eskiden başlayarak (FIFO) otomatik mahsup edilir. Yevmiyede avans
+-- 127 Üye Aidat Alacakları'nın alacak bakiyesidir; iade gideri 127'ye borç yazılır.
+-- Tablo sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS uye_avans_hareketleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    tarih TEXT NOT NULL,
+    tur TEXT NOT NULL CHECK (tur IN ('giris', 'mahsup', 'iade')),
+    tutar REAL NOT NULL,
+    aidat_id TEXT,
+    gelir_id TEXT,
+    gider_id TEXT,
+    aciklama TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_uye_avans_hareketleri_uye ON uye_avans_hareketleri(tenant_id, uye_id, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_uye_avans_hareketleri_gider ON uye_avans_hareketleri(gider_id);
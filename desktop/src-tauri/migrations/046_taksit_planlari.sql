@@ -0,0 +1,72 @@
+-- Migration: Aidat borcu taksitlendirme
+-- Date: 2026-10-18
+-- Description: Üyenin açık aidat borçlarının taksit planına bağlanması. Plan oluşturulurken
+-- seçilen aidatların o anki kalan borcu (taksit_plani_aidatlari.tutar) toplanır ve taksit
+-- sayısına kuruş kaybı olmadan bölünür; vadeler ilk vadeden itibaren aralik_ay ay arayla
+-- verilir. Bir aidat aynı anda yalnızca bir aktif planda olabilir. Plana bağlı aidatlara
+-- add_aidat_odeme_with_gelir ile yapılan ödemeler taksitlere sırayla işlenir
+-- (taksit_odemeleri, gelir_id). Vadesinden tolerans_gun sonra hâlâ ödenmemiş taksiti olan
+-- aktif plan bozuk sayılır. durum: aktif, tamamlandi, bozuldu (yönetici kararıyla), iptal.
+-- Tablolar sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS taksit_planlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    ilk_vade TEXT NOT NULL,
+    taksit_sayisi INTEGER NOT NULL,
+    aralik_ay INTEGER NOT NULL DEFAULT 1,
+    toplam_tutar REAL NOT NULL,
+    tolerans_gun INTEGER NOT NULL DEFAULT 0,
+    durum TEXT NOT NULL DEFAULT 'aktif' CHECK (durum IN ('aktif', 'tamamlandi', 'bozuldu', 'iptal')),
+    aciklama TEXT,
+    olusturan TEXT,
+    kapanis_tarihi TEXT,
+    kapanis_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_taksit_planlari_uye ON taksit_planlari(tenant_id, uye_id, durum);
+
+CREATE TABLE IF NOT EXISTS taksit_plani_aidatlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    plan_id TEXT NOT NULL REFERENCES taksit_planlari(id) ON DELETE CASCADE,
+    aidat_id TEXT NOT NULL,
+    tutar REAL NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_taksit_plani_aidatlari_plan ON taksit_plani_aidatlari(plan_id);
+
+CREATE INDEX IF NOT EXISTS idx_taksit_plani_aidatlari_aidat ON taksit_plani_aidatlari(tenant_id, aidat_id);
+
+CREATE TABLE IF NOT EXISTS taksitler (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    plan_id TEXT NOT NULL REFERENCES taksit_planlari(id) ON DELETE CASCADE,
+    sira INTEGER NOT NULL,
+    vade_tarihi TEXT NOT NULL,
+    tutar REAL NOT NULL,
+    odenen REAL NOT NULL DEFAULT 0,
+    odeme_tarihi TEXT,
+    durum TEXT NOT NULL DEFAULT 'bekliyor' CHECK (durum IN ('bekliyor', 'kismi', 'odendi')),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (plan_id, sira)
+);
+
+CREATE TABLE IF NOT EXISTS taksit_odemeleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    taksit_id TEXT NOT NULL REFERENCES taksitler(id) ON DELETE CASCADE,
+    aidat_id TEXT NOT NULL,
+    gelir_id TEXT NOT NULL,
+    tarih TEXT NOT NULL,
+    tutar REAL NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_taksit_odemeleri_taksit ON taksit_odemeleri(taksit_id);
+
+CREATE INDEX IF NOT EXISTS idx_taksit_odemeleri_gelir ON taksit_odemeleri(gelir_id);
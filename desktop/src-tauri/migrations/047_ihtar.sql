@@ -0,0 +1,69 @@
+-- Migration: Aidat borcu ihtar akışı
+-- Date: 2026-10-18
+-- Description: Kademeli ihtar mektupları. ihtar_sablonlari seviye başına başlık, metin
+-- ({ad_soyad}, {uye_no}, {borc_tutari}, {en_eski_donem}, {tarih}, {cevap_suresi_gun},
+-- {dernek_adi}, {seviye} yer tutucularıyla) ve tebliğden sonraki cevap süresini tutar.
+-- ihtarlar üyeye kesilen her mektubu, o anki borç dökümünü (ihtar_kalemleri) ve tebliğ
+-- bilgisini (bildirim_tarihi, bildirim_kanali, sure_bitis_tarihi) saklar. Bir üst seviye
+-- ancak önceki ihtarın cevap süresi dolunca kesilebilir. ihrac_kurallari tüzüğün ödenmeyen
+-- aidat hükmünü tutar: en eski açık borcun gecikmesi asgari_gecikme_gun'ü aşmış ve
+-- gerekli_seviye'deki ihtarın süresi dolmuş üye ihraç adayıdır.
+-- Tablolar sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS ihtar_sablonlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    seviye INTEGER NOT NULL CHECK (seviye >= 1),
+    baslik TEXT NOT NULL,
+    metin TEXT NOT NULL,
+    cevap_suresi_gun INTEGER NOT NULL DEFAULT 30,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (tenant_id, seviye)
+);
+
+CREATE TABLE IF NOT EXISTS ihtarlar (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    seviye INTEGER NOT NULL,
+    sablon_id TEXT,
+    tarih TEXT NOT NULL,
+    baslik TEXT NOT NULL,
+    metin TEXT NOT NULL,
+    borc_tutari REAL NOT NULL,
+    en_eski_vade TEXT NOT NULL,
+    cevap_suresi_gun INTEGER NOT NULL,
+    bildirim_tarihi TEXT,
+    bildirim_kanali TEXT CHECK (bildirim_kanali IS NULL OR bildirim_kanali IN ('elden', 'posta', 'iadeli_taahhutlu', 'eposta', 'noter')),
+    sure_bitis_tarihi TEXT,
+    belge_yolu TEXT,
+    olusturan TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ihtarlar_uye ON ihtarlar(tenant_id, uye_id, tarih);
+
+CREATE TABLE IF NOT EXISTS ihtar_kalemleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    ihtar_id TEXT NOT NULL REFERENCES ihtarlar(id) ON DELETE CASCADE,
+    aidat_id TEXT NOT NULL,
+    yil INTEGER NOT NULL,
+    ay INTEGER NOT NULL,
+    vade_tarihi TEXT NOT NULL,
+    kalan REAL NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ihtar_kalemleri_ihtar ON ihtar_kalemleri(ihtar_id);
+
+CREATE TABLE IF NOT EXISTS ihrac_kurallari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL UNIQUE REFERENCES tenants(id) ON DELETE CASCADE,
+    asgari_gecikme_gun INTEGER NOT NULL DEFAULT 365,
+    gerekli_seviye INTEGER,
+    tuzuk_maddesi TEXT,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,47 @@
+-- Migration: Aile (hane) üyelikleri
+-- Date: 2026-10-18
+-- Description: Eş ve çocukları tek aile aidatıyla kapsayan hane grupları. haneler bir ödeyen
+-- üye (odeyen_uye_id) tutar; hane_uyeleri haneye bağlı üye kayıtlarıdır (ödeyen dahil, bir üye
+-- en fazla bir hanede). aidat_tanimlari.aile_tutari doluysa o yıl ve ödeyenin üye türü için
+-- hane başına tek aidat ödeyene tahakkuk eder, diğer hane üyelerine aidat açılmaz; boşsa hane
+-- üyeleri tek tek borçlandırılır. hane_aidat_paylari hane aidatının tahakkuk anındaki üyelere
+-- pay_agirligi oranında dağılımıdır; ödemeler raporlamada aynı oranla paylaştırılır.
+-- Yeni tablolar sync yüzeyinde değildir.
+
+ALTER TABLE aidat_tanimlari ADD COLUMN aile_tutari REAL;
+
+CREATE TABLE IF NOT EXISTS haneler (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    ad TEXT NOT NULL,
+    odeyen_uye_id TEXT NOT NULL,
+    aciklama TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS hane_uyeleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    hane_id TEXT NOT NULL REFERENCES haneler(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    yakinlik TEXT,
+    pay_agirligi INTEGER NOT NULL DEFAULT 1 CHECK (pay_agirligi > 0),
+    created_at TEXT NOT NULL,
+    UNIQUE (tenant_id, uye_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_hane_uyeleri_hane ON hane_uyeleri(hane_id);
+
+CREATE TABLE IF NOT EXISTS hane_aidat_paylari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    hane_id TEXT NOT NULL,
+    aidat_id TEXT NOT NULL,
+    uye_id TEXT NOT NULL,
+    pay REAL NOT NULL,
+    UNIQUE (aidat_id, uye_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_hane_aidat_paylari_uye ON hane_aidat_paylari(tenant_id, uye_id);
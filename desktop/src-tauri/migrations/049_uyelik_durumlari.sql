@@ -0,0 +1,49 @@
+-- Migration: Üyelik durum geçmişi
+-- Date: 2026-10-18
+-- Description: Üyenin aday, asil, askıda, ayrıldı ve ihraç durumlarını yürürlük tarihleriyle
+-- tutar. Her satır baslangic gününden bitis gününe kadar (bitis hariç) geçerlidir; açık satırın
+-- bitis'i boştur ve üyenin şu anki durumudur. karar_no/karar_tarihi durumu değiştiren yönetim
+-- kurulu kararını gösterir. uyeler.durum, cikis_tarihi ve ayrilma_nedeni açık satırın kopyası
+-- olarak güncellenir. Otomatik aidat tahakkuku dönemle kesişen asil satırı olan üyelere yapılır.
+-- Tablo sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS uyelik_durumlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    durum TEXT NOT NULL CHECK (durum IN ('aday', 'asil', 'askida', 'ayrildi', 'ihrac')),
+    baslangic TEXT NOT NULL,
+    bitis TEXT,
+    karar_no TEXT,
+    karar_tarihi TEXT,
+    neden TEXT,
+    aciklama TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_uyelik_durumlari_uye ON uyelik_durumlari(tenant_id, uye_id, baslangic);
+
+-- Mevcut üyeler: giriş tarihinden itibaren asil (Bekleyen ise aday); ayrılmış ya da pasif
+-- üyelerde bu satır ayrılma tarihinde kapanır ve yerine ayrıldı/askıda satırı açılır
+INSERT INTO uyelik_durumlari (id, tenant_id, uye_id, durum, baslangic, bitis, created_at)
+SELECT lower(hex(randomblob(16))), tenant_id, id,
+       CASE WHEN durum = 'Bekleyen' THEN 'aday' ELSE 'asil' END,
+       substr(giris_tarihi, 1, 10),
+       CASE WHEN kapali THEN MAX(kapanis, substr(giris_tarihi, 1, 10)) END,
+       datetime('now')
+FROM (
+    SELECT *,
+           (cikis_tarihi IS NOT NULL AND cikis_tarihi != '') OR durum IN ('Ayrıldı', 'Pasif') AS kapali,
+           substr(COALESCE(NULLIF(cikis_tarihi, ''), pasife_alinma_tarihi, updated_at, giris_tarihi), 1, 10) AS kapanis
+    FROM uyeler
+);
+
+INSERT INTO uyelik_durumlari (id, tenant_id, uye_id, durum, baslangic, neden, created_at)
+SELECT lower(hex(randomblob(16))), d.tenant_id, d.uye_id,
+       CASE WHEN u.durum = 'Pasif' AND (u.cikis_tarihi IS NULL OR u.cikis_tarihi = '') THEN 'askida' ELSE 'ayrildi' END,
+       d.bitis,
+       CASE WHEN u.durum = 'Pasif' AND (u.cikis_tarihi IS NULL OR u.cikis_tarihi = '') THEN u.pasife_alma_nedeni ELSE u.ayrilma_nedeni END,
+       datetime('now')
+FROM uyelik_durumlari d
+JOIN uyeler u ON u.id = d.uye_id
+WHERE d.bitis IS NOT NULL;
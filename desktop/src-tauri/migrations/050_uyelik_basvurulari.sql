@@ -0,0 +1,39 @@
+-- Migration: Üyelik başvuruları
+-- Date: 2026-10-18
+-- Description: Yönetim kurulunun karara bağlayacağı üyelik başvuruları. Başvuru 'bekliyor'
+-- durumunda açılır ve karar_son_tarihi (başvurudan 30 gün sonrası) ile izlenir. Karar
+-- verildiğinde 'onaylandi' ya da 'reddedildi' olur; karar_no, karar_tarihi ve kararın alındığı
+-- toplantı (toplanti_id) yazılır. Onayda üye kaydı açılır (uye_id) ve ilk aidat tahakkuk eder;
+-- eski üyenin yeniden başvurusunda uye_id başvuruda doludur ve onayda o üye yeniden kabul
+-- edilir. Tablo sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS uyelik_basvurulari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    tc_no TEXT NOT NULL,
+    ad TEXT NOT NULL,
+    soyad TEXT NOT NULL,
+    telefon TEXT,
+    email TEXT,
+    dogum_tarihi TEXT,
+    cinsiyet TEXT,
+    meslek TEXT,
+    adres TEXT,
+    il TEXT,
+    ilce TEXT,
+    uyelik_tipi TEXT,
+    referans_uye_id TEXT,
+    basvuru_tarihi TEXT NOT NULL,
+    karar_son_tarihi TEXT NOT NULL,
+    durum TEXT NOT NULL DEFAULT 'bekliyor' CHECK (durum IN ('bekliyor', 'onaylandi', 'reddedildi')),
+    toplanti_id TEXT,
+    karar_no TEXT,
+    karar_tarihi TEXT,
+    red_nedeni TEXT,
+    uye_id TEXT,
+    notlar TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_uyelik_basvurulari_durum ON uyelik_basvurulari(tenant_id, durum, karar_son_tarihi);
@@ -0,0 +1,97 @@
+-- Migration: Genel kurul
+-- Date: 2026-10-18
+-- Description: Genel kurul toplantıları (toplantilar kaydına bağlı), hazirun listesi, gündem
+-- oylamaları ve organ seçimleri. Hazirun, hazirun_tarihi itibarıyla asil üyelerden çıkarılır;
+-- tüzük gereği vadesi geçmiş aidat borcu olan üye listede katilim_hakki = 0 ve engel nedeniyle
+-- tutulur, yeter sayıya girmez. imza_zamani dolu satırlar toplantıya katılanlardır. Toplantı ilk
+-- çağrıda üye tamsayısının salt çoğunluğuyla (tüzük değişikliği/fesih gündeminde üçte ikisiyle),
+-- ikinci çağrıda yönetim ve denetim kurulları üye tamsayısının iki katıyla açılır (TMK 86).
+-- Seçimlerde adaylar oy sırasına göre asil ve yedek olur; sınırdaki eşitlik kura sırasıyla
+-- çözülür. Tablolar sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS genel_kurullar (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    toplanti_id TEXT NOT NULL,
+    tur TEXT NOT NULL DEFAULT 'olagan' CHECK (tur IN ('olagan', 'olaganustu')),
+    hazirun_tarihi TEXT NOT NULL,
+    ikinci_toplanti_tarihi TEXT,
+    yk_dk_uye_sayisi INTEGER NOT NULL,
+    borc_engeli INTEGER NOT NULL DEFAULT 1,
+    borc_tolerans_gun INTEGER NOT NULL DEFAULT 0,
+    durum TEXT NOT NULL DEFAULT 'hazirlik' CHECK (durum IN ('hazirlik', 'ertelendi', 'acildi', 'tamamlandi')),
+    toplanti_cagrisi INTEGER CHECK (toplanti_cagrisi IN (1, 2)),
+    acilis_zamani TEXT,
+    acilista_katilan INTEGER,
+    divan_baskani TEXT,
+    divan_katipleri TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (tenant_id, toplanti_id)
+);
+
+CREATE TABLE IF NOT EXISTS genel_kurul_hazirun (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    genel_kurul_id TEXT NOT NULL REFERENCES genel_kurullar(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    sira INTEGER NOT NULL,
+    uye_no TEXT,
+    ad_soyad TEXT NOT NULL,
+    tc_no TEXT,
+    katilim_hakki INTEGER NOT NULL DEFAULT 1,
+    engel_nedeni TEXT,
+    borc_tutari REAL NOT NULL DEFAULT 0,
+    imza_zamani TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE (genel_kurul_id, uye_id)
+);
+
+CREATE TABLE IF NOT EXISTS genel_kurul_gundem (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    genel_kurul_id TEXT NOT NULL REFERENCES genel_kurullar(id) ON DELETE CASCADE,
+    sira INTEGER NOT NULL,
+    baslik TEXT NOT NULL,
+    aciklama TEXT,
+    nitelikli INTEGER NOT NULL DEFAULT 0,
+    katilan INTEGER,
+    kabul INTEGER,
+    ret INTEGER,
+    cekimser INTEGER,
+    sonuc TEXT CHECK (sonuc IN ('kabul', 'ret')),
+    karar_metni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS genel_kurul_secimleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    genel_kurul_id TEXT NOT NULL REFERENCES genel_kurullar(id) ON DELETE CASCADE,
+    organ TEXT NOT NULL,
+    asil_sayisi INTEGER NOT NULL,
+    yedek_sayisi INTEGER NOT NULL DEFAULT 0,
+    kullanilan_oy INTEGER,
+    gecersiz_oy INTEGER,
+    sonuclandi INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (genel_kurul_id, organ)
+);
+
+CREATE TABLE IF NOT EXISTS genel_kurul_adaylari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    secim_id TEXT NOT NULL REFERENCES genel_kurul_secimleri(id) ON DELETE CASCADE,
+    uye_id TEXT NOT NULL,
+    oy INTEGER NOT NULL DEFAULT 0,
+    kura_sirasi INTEGER,
+    sira INTEGER,
+    sonuc TEXT CHECK (sonuc IN ('asil', 'yedek', 'kazanamadi')),
+    UNIQUE (secim_id, uye_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_genel_kurul_hazirun ON genel_kurul_hazirun(genel_kurul_id, sira);
+CREATE INDEX IF NOT EXISTS idx_genel_kurul_gundem ON genel_kurul_gundem(genel_kurul_id, sira);
+CREATE INDEX IF NOT EXISTS idx_genel_kurul_adaylari ON genel_kurul_adaylari(secim_id);
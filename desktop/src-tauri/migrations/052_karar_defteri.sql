@@ -0,0 +1,53 @@
+-- Migration: Karar defteri
+-- Date: 2026-10-18
+-- Description: Organ kararları (yönetim kurulu, denetim kurulu, genel kurul) tek tek kayıt
+-- olarak tutulur. Karar numarası organ ve karar yılı içinde 1'den başlayarak sıralıdır ve
+-- değişmez; karar silinmez, gerekçesiyle iptal edilir. Kararda toplantıya katılanlar ve
+-- oyları (karar_katilimcilari), kararın dayanak olduğu gelir/gider/demirbaş/üye kayıtları
+-- (karar_baglantilari) tutulur. Tablolar sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS kararlar (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    organ TEXT NOT NULL CHECK (organ IN ('yonetim_kurulu', 'denetim_kurulu', 'genel_kurul')),
+    yil INTEGER NOT NULL,
+    karar_no INTEGER NOT NULL,
+    karar_tarihi TEXT NOT NULL,
+    toplanti_id TEXT,
+    konu TEXT NOT NULL,
+    metin TEXT NOT NULL,
+    kabul INTEGER NOT NULL DEFAULT 0,
+    ret INTEGER NOT NULL DEFAULT 0,
+    cekimser INTEGER NOT NULL DEFAULT 0,
+    sonuc TEXT NOT NULL CHECK (sonuc IN ('kabul', 'ret')),
+    durum TEXT NOT NULL DEFAULT 'gecerli' CHECK (durum IN ('gecerli', 'iptal')),
+    iptal_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (tenant_id, organ, yil, karar_no)
+);
+
+CREATE TABLE IF NOT EXISTS karar_katilimcilari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    karar_id TEXT NOT NULL REFERENCES kararlar(id) ON DELETE CASCADE,
+    uye_id TEXT,
+    ad_soyad TEXT NOT NULL,
+    oy TEXT NOT NULL CHECK (oy IN ('kabul', 'ret', 'cekimser', 'katilmadi')),
+    muhalefet_serhi TEXT
+);
+
+CREATE TABLE IF NOT EXISTS karar_baglantilari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    karar_id TEXT NOT NULL REFERENCES kararlar(id) ON DELETE CASCADE,
+    tablo TEXT NOT NULL CHECK (tablo IN ('gelirler', 'giderler', 'demirbaslar', 'uyeler')),
+    kayit_id TEXT NOT NULL,
+    aciklama TEXT,
+    created_at TEXT NOT NULL,
+    UNIQUE (karar_id, tablo, kayit_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_kararlar_tarih ON kararlar(tenant_id, organ, karar_tarihi);
+CREATE INDEX IF NOT EXISTS idx_karar_katilimcilari ON karar_katilimcilari(karar_id);
+CREATE INDEX IF NOT EXISTS idx_karar_baglantilari_kayit ON karar_baglantilari(tenant_id, tablo, kayit_id);
@@ -0,0 +1,30 @@
+-- Migration: Organ görevleri
+-- Date: 2026-10-18
+-- Description: Yönetim, denetim ve disiplin kurulları ile komisyonlarda görev alan üyeler.
+-- Her satır bir üyenin bir organdaki görev dönemidir (başkan, başkan yardımcısı, sayman,
+-- sekreter, asil üye, yedek üye); bitis_tarihi görevin son günüdür, boşsa görev sürer.
+-- Görev değişikliği (ör. yedeğin asile geçmesi) eski satırı kapatıp yeni satır açar, böylece
+-- geçmiş korunur. genel_kurul_id üyeyi seçen genel kuruldur. Kurul toplantılarının yeter
+-- sayısı, tahsilat yetkilisi ve beyannamenin organ bölümü bu tablodan okunur.
+-- Tablo sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS organ_gorevleri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    organ TEXT NOT NULL CHECK (organ IN ('yonetim_kurulu', 'denetim_kurulu', 'disiplin_kurulu', 'komisyon')),
+    komisyon_adi TEXT,
+    uye_id TEXT NOT NULL,
+    gorev TEXT NOT NULL CHECK (gorev IN ('baskan', 'baskan_yardimcisi', 'sayman', 'sekreter', 'uye', 'yedek_uye')),
+    -- Yedek üyelerin göreve çağrılma sırası
+    sira INTEGER,
+    baslangic_tarihi TEXT NOT NULL,
+    bitis_tarihi TEXT,
+    genel_kurul_id TEXT,
+    tahsilat_yetkisi INTEGER NOT NULL DEFAULT 0,
+    ayrilma_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_organ_gorevleri_organ ON organ_gorevleri(tenant_id, organ, baslangic_tarihi);
+CREATE INDEX IF NOT EXISTS idx_organ_gorevleri_uye ON organ_gorevleri(uye_id);
@@ -0,0 +1,31 @@
+-- Migration: Toplantı davetlileri ve katılım
+-- Date: 2026-10-18
+-- Description: Toplantıya çağrılan üyeler ve misafirler, çağrının tarihi ve kanalı, katılım
+-- durumu. Davetli listesi toplantı türünden çıkarılır: kurul toplantılarına o gün görevde olan
+-- asil kurul üyeleri (organ_gorevleri), genel kurula hazirun listesindeki katılma hakkı olan
+-- üyeler çağrılır. Çağrı, toplantıdan asgari bildirim süresi (genel kurulda 15 gün, TMK 75)
+-- önce yapılmış olmalıdır. Genel kurulun katılımı hazirun imzalarından işlenir.
+-- toplantilar.katilimci_sayisi katildi durumundaki davetlilerden güncellenir.
+-- Tablo sync yüzeyinde değildir.
+
+CREATE TABLE IF NOT EXISTS toplanti_davetlileri (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    toplanti_id TEXT NOT NULL,
+    -- Misafirlerde boş
+    uye_id TEXT,
+    ad_soyad TEXT NOT NULL,
+    -- Çağrı anındaki kurul görevi (ör. "Başkan")
+    gorev TEXT,
+    davet_tarihi TEXT,
+    davet_kanali TEXT CHECK (davet_kanali IS NULL OR davet_kanali IN ('elden', 'posta', 'iadeli_taahhutlu', 'eposta', 'sms', 'ilan')),
+    katilim TEXT NOT NULL DEFAULT 'bekleniyor' CHECK (katilim IN ('bekleniyor', 'katildi', 'katilmadi', 'mazeretli')),
+    katilim_zamani TEXT,
+    mazeret TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    UNIQUE (toplanti_id, uye_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_toplanti_davetlileri_toplanti ON toplanti_davetlileri(tenant_id, toplanti_id);
+CREATE INDEX IF NOT EXISTS idx_toplanti_davetlileri_uye ON toplanti_davetlileri(tenant_id, uye_id);
@@ -0,0 +1,37 @@
+-- Migration: Etkinlik kayıtları, bilet ve giriş
+-- Date: 2026-10-18
+-- Description: Etkinliğe kayıt olan üyeler ve misafirler. kapasite doluysa yeni kayıt yedek
+-- listeye sırayla (yedek_sira) alınır; kayıt iptal edildikçe ya da kapasite artırıldıkça
+-- yedekler kesin kayda geçer. Ücretli etkinlikte kaydın bilet ücreti kayıt anındaki
+-- fiyattır (üyeye uye_bilet_ucreti, misafire bilet_ucreti); tahsilat etkinlik_id ile
+-- bağlı gelir kaydıdır (gelir_id, bkz. 033). Girişte QR kodundaki qr_kodu değeri okunur.
+-- etkinlikler.katilimci_sayisi giriş yapan kayıtlardan güncellenir.
+-- Yeni etkinlikler kolonları sunucu modelinde yoktur, sync payload'ında sunucuda süzülür;
+-- etkinlik_kayitlari sync yüzeyinde değildir.
+
+ALTER TABLE etkinlikler ADD COLUMN kapasite INTEGER;
+ALTER TABLE etkinlikler ADD COLUMN bilet_ucreti REAL;
+ALTER TABLE etkinlikler ADD COLUMN uye_bilet_ucreti REAL;
+
+CREATE TABLE IF NOT EXISTS etkinlik_kayitlari (
+    id TEXT PRIMARY KEY,
+    tenant_id TEXT NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    etkinlik_id TEXT NOT NULL,
+    -- Misafirlerde boş
+    uye_id TEXT,
+    ad_soyad TEXT NOT NULL,
+    telefon TEXT,
+    email TEXT,
+    durum TEXT NOT NULL DEFAULT 'kayitli' CHECK (durum IN ('kayitli', 'yedek', 'iptal')),
+    yedek_sira INTEGER,
+    bilet_ucreti REAL NOT NULL DEFAULT 0,
+    gelir_id TEXT,
+    qr_kodu TEXT NOT NULL UNIQUE,
+    giris_zamani TEXT,
+    iptal_nedeni TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_etkinlik_kayitlari_etkinlik ON etkinlik_kayitlari(tenant_id, etkinlik_id, durum);
+CREATE INDEX IF NOT EXISTS idx_etkinlik_kayitlari_uye ON etkinlik_kayitlari(tenant_id, uye_id);
@@ -0,0 +1,7 @@
+-- Migration: Zamanlanmış aidat tahakkuku tercihi
+-- Date: 2026-10-18
+-- Description: Uygulama içi zamanlayıcı yalnızca yöneticinin açtığı tenant'larda çalışır.
+-- Varsayılan kapalıdır; güncellemeden sonraki ilk açılışta kendiliğinden tahakkuk yapılmaz.
+-- Elle çalıştırma (aidat_tahakkukunu_calistir) bu tercihten bağımsızdır.
+
+ALTER TABLE tenants ADD COLUMN otomatik_aidat_tahakkuku INTEGER NOT NULL DEFAULT 0;
@@ -6,29 +6,33 @@ use tauri::State;
 use uuid::Uuid;
 use crate::db::models::AidatTakip;
 use crate::db::outbox::{self, TxError};
+use crate::db::yevmiye;
+use crate::db::donem_kilidi;
+use crate::db::alindi_belgesi;
+use crate::db::para::Para;
+use crate::db::aidat_fiyat::FiyatMotoru;
+use crate::db::hane::{self, HaneKarari, HaneTahakkuku};
+use crate::db::avans;
+use crate::db::denetim::Denetim;
+use crate::db::taksit;
+use crate::db::uyelik_durumu;
+use crate::commands::aidat_fiyat::{self, UyeAidatFiyati};
 
 type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
-// Tutar sorgusundan dönecek row için struct
-#[derive(Debug, QueryableByName)]
-pub struct TutarRow {
-    #[diesel(sql_type = diesel::sql_types::Double)]
-    pub tutar: f64,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct CreateAidatRequest {
     pub uye_id: String,
     pub yil: i32,
     pub ay: i32,
-    pub tutar: f64,
+    pub tutar: Para,
     pub notlar: Option<String>,
     pub kasa_id: Option<String>,  // Aidat oluştururken kasa belirle
 }
 
 #[derive(Debug, Deserialize)]
 pub struct OdemeRequest {
-    pub tutar: f64,
+    pub tutar: Para,
     pub odeme_tarihi: String,
     pub tahsilat_turu: Option<String>,
     pub banka_sube: Option<String>,
@@ -39,9 +43,9 @@ pub struct OdemeRequest {
 
 #[derive(Debug, Serialize)]
 pub struct AidatOzet {
-    pub toplam_tutar: f64,
-    pub toplam_odenen: f64,
-    pub toplam_kalan: f64,
+    pub toplam_tutar: Para,
+    pub toplam_odenen: Para,
+    pub toplam_kalan: Para,
     pub odenen_adet: i32,
     pub geciken_adet: i32,
 }
@@ -60,11 +64,11 @@ pub struct AidatWithUye {
     #[diesel(sql_type = diesel::sql_types::Integer)]
     pub ay: i32,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub tutar: f64,
+    pub tutar: Para,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub odenen: f64,
+    pub odenen: Para,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub kalan: Option<f64>,
+    pub kalan: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub odeme_tarihi: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Text)]
@@ -72,7 +76,7 @@ pub struct AidatWithUye {
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
     pub gecikme_gun: Option<i32>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub gecikme_faiz: Option<f64>,
+    pub gecikme_faiz: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub notlar: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
@@ -91,9 +95,9 @@ pub struct AidatWithUye {
 #[derive(QueryableByName)]
 struct AidatOzetQuery {
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub toplam_tutar: f64,
+    pub toplam_tutar: Para,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub toplam_odenen: f64,
+    pub toplam_odenen: Para,
     #[diesel(sql_type = diesel::sql_types::Integer)]
     pub odenen_adet: i32,
     #[diesel(sql_type = diesel::sql_types::Integer)]
@@ -232,9 +236,12 @@ pub async fn create_aidat(
     let new_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let mut denetim = state.denetim("create_aidat");
     // Durum değeri standardı: 'odenmedi' | 'kismi_odendi' | 'odendi' | 'iptal'
     // Yazım + outbox kaydı aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &new_id).map_err(TxError::Msg)?;
+        donem_kilidi::donemi_dogrula(conn, &tenant_id_param, data.yil, data.ay).map_err(TxError::Msg)?;
         diesel::sql_query(
             "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, odenen, gecikme_gun, gecikme_faiz, durum, notlar, created_at, updated_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0.0, 0, 0.0, 'odenmedi', ?7, ?8, ?9)"
@@ -252,7 +259,12 @@ pub async fn create_aidat(
 
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &new_id, "create")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &new_id)
+            .map_err(TxError::Msg)?;
+        avans::mahsup_et(conn, &mut denetim, &tenant_id_param, &data.uye_id, &bugun())
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -297,8 +309,12 @@ pub async fn kaydet_odeme(
         "kismi_odendi"
     };
 
+    let mut denetim = state.denetim("kaydet_odeme");
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
     // Transaction ile aidat + gelir + kasa güncellemelerini yap
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &odeme.odeme_tarihi).map_err(TxError::Msg)?;
         // 1. Aidat kaydını güncelle
         diesel::sql_query(
             "UPDATE aidat_takip SET odenen = ?1, kalan = ?2, odeme_tarihi = ?3, durum = ?4, updated_at = ?5 WHERE id = ?6 AND tenant_id = ?7"
@@ -314,10 +330,13 @@ pub async fn kaydet_odeme(
 
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+            .map_err(TxError::Msg)?;
 
         // 2. Kasa ID kontrolü - varsa gelir ve kasa güncelle
         if let Some(ref kasa_id_val) = odeme.kasa_id {
             let new_gelir_id = Uuid::new_v4().to_string();
+            denetim.yeni_kayit(&tenant_id_param, "gelirler", &new_gelir_id);
             let makbuz_no = format!("AIDAT-{}", &new_gelir_id[..8]);
             
             // Gelir kaydı oluştur
@@ -338,26 +357,18 @@ pub async fn kaydet_odeme(
             .bind::<diesel::sql_types::Text, _>(&now)
             .execute(conn)?;
 
+            alindi_belgesi::gelire_makbuz_kes(conn, &tenant_id_param, kullanici_id.as_deref(), &new_gelir_id)
+                .map_err(TxError::Msg)?;
             outbox::queue_change(conn, &tenant_id_param, "gelirler", &new_gelir_id, "create")
                 .map_err(TxError::Msg)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &new_gelir_id)
+                .map_err(TxError::Msg)?;
 
-            // Kasa bakiyesini güncelle (türetilmiş alanlar — kasalar için outbox kaydı atılmaz)
-            diesel::sql_query(
-                "UPDATE kasalar 
-                 SET toplam_gelir = COALESCE(toplam_gelir, 0.0) + ?1,
-                     fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) + ?2,
-                     bakiye = bakiye + ?3,
-                     updated_at = ?4
-                 WHERE id = ?5"
-            )
-            .bind::<diesel::sql_types::Double, _>(odeme.tutar)
-            .bind::<diesel::sql_types::Double, _>(odeme.tutar)
-            .bind::<diesel::sql_types::Double, _>(odeme.tutar)
-            .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(kasa_id_val)
-            .execute(conn)?;
+            // Kasa bakiyesini yeniden hesapla (türetilmiş alanlar — kasalar için outbox kaydı atılmaz)
+            crate::commands::mali::update_kasa_bakiye(conn, kasa_id_val)?;
         }
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     }).map_err(|e: TxError| e.to_string())?;
 
@@ -369,14 +380,16 @@ pub async fn kaydet_odeme(
     Ok(result)
 }
 
+/// gun_sayisi ve faiz_orani verilmezse bugün itibarıyla gecikme politikasına göre
+/// hesaplanır (bkz. db::gecikme); sonuç aidat kaydında bilgi amaçlı saklanır.
 #[tauri::command]
 pub async fn hesapla_gecikme(
     state: State<'_, crate::AppState>,
     _tenant_id_param: String,
     aidat_id: String,
-    gun_sayisi: i32,
-    faiz_orani: f64,
-) -> Result<f64, String> {
+    gun_sayisi: Option<i32>,
+    faiz_orani: Option<f64>,
+) -> Result<Para, String> {
     let pool = state.db.lock().unwrap();
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
@@ -387,12 +400,28 @@ pub async fn hesapla_gecikme(
         .first::<AidatTakip>(&mut conn)
         .map_err(|e| e.to_string())?;
 
-    let kalan_tutar = aidat.tutar - aidat.odenen;
-    let hesaplanan_faiz = kalan_tutar * (faiz_orani / 100.0) * (gun_sayisi as f64 / 30.0);
+    let (gun_sayisi, hesaplanan_faiz) = match (gun_sayisi, faiz_orani) {
+        (Some(gun), Some(oran)) => {
+            let kalan_tutar = aidat.tutar - aidat.odenen;
+            (gun, kalan_tutar.carp((oran / 100.0) * (gun as f64 / 30.0)))
+        }
+        _ => {
+            let onizleme = crate::db::gecikme::onizle(&mut conn, &aidat.tenant_id, chrono::Local::now().date_naive())?;
+            onizleme
+                .satirlar
+                .into_iter()
+                .find(|h| h.aidat_id == aidat_id)
+                .map(|h| (h.gecikme_gun as i32, h.hesaplanan))
+                .unwrap_or((0, Para::SIFIR))
+        }
+    };
 
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut denetim = state.denetim("hesapla_gecikme");
     // Yazım + outbox kaydı aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &aidat.tenant_id, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
+        donem_kilidi::kaydi_dogrula(conn, &aidat.tenant_id, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
         diesel::sql_query(
             "UPDATE aidat_takip SET gecikme_gun = ?1, gecikme_faiz = ?2, updated_at = ?3 WHERE id = ?4"
         )
@@ -405,7 +434,10 @@ pub async fn hesapla_gecikme(
         // Not: tenant_id parametresi kullanılmıyor (mevcut davranış); kaydın kendi tenant_id'si kullanılır.
         outbox::queue_change(conn, &aidat.tenant_id, "aidat_takip", &aidat_id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &aidat.tenant_id, "aidat_takip", &aidat_id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -431,8 +463,8 @@ pub async fn get_aidat_ozet(
 
     let result = diesel::sql_query(
         "SELECT
-            COALESCE(SUM(tutar), 0.0) as toplam_tutar,
-            COALESCE(SUM(odenen), 0.0) as toplam_odenen,
+            COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) / 100.0 as toplam_tutar,
+            COALESCE(SUM(CAST(ROUND(odenen * 100) AS INTEGER)), 0) / 100.0 as toplam_odenen,
             COUNT(CASE WHEN durum = 'odendi' THEN 1 END) as odenen_adet,
             COUNT(CASE WHEN (durum IS NULL OR durum = 'odenmedi' OR durum = 'kismi_odendi' OR durum = 'Bekliyor' OR durum = 'beklemede' OR durum = 'bekliyor')
                            AND (yil * 100 + ay) < ?3 THEN 1 END) as geciken_adet
@@ -463,7 +495,7 @@ pub async fn get_aidat_ozet(
 #[derive(Debug, Deserialize)]
 pub struct TopluAidatRequest {
     pub yil: i32,
-    pub varsayilan_tutar: f64,
+    pub varsayilan_tutar: Para,
     pub sadece_aktif_uyeler: bool,
     pub kasa_id: String,
     pub otomatik_gelir_olustur: bool,
@@ -473,7 +505,7 @@ pub struct TopluAidatRequest {
 pub struct TopluAidatResult {
     pub success: bool,
     pub olusturulan_adet: i32,
-    pub toplam_tutar: f64,
+    pub toplam_tutar: Para,
     pub mesaj: String,
 }
 
@@ -482,8 +514,8 @@ pub struct TopluAidatResult {
 pub struct UyelikTuruDagilim {
     pub uye_turu: String,
     pub adet: i32,
-    pub ortalama_tutar: f64,
-    pub toplam_tutar: f64,
+    pub ortalama_tutar: Para,
+    pub toplam_tutar: Para,
 }
 
 // Toplu aidat önizleme sonucu
@@ -497,9 +529,37 @@ pub struct TopluAidatOnizleme {
     pub ozel_tutarli_uyeler: i32,
     pub tanim_tutarli_uyeler: i32,
     pub varsayilan_tutarli_uyeler: i32,
-    pub toplam_borclandirilacak_tutar: f64,
-    pub ortalama_tutar: f64,
+    pub toplam_borclandirilacak_tutar: Para,
+    pub ortalama_tutar: Para,
     pub uyarilar: Vec<String>,
+    /// En az bir fiyat kuralının uygulandığı üye sayısı
+    pub kural_uygulanan_uyeler: i32,
+    /// Borçlandırılacak her üye için tutar ve uygulanan kurallar
+    pub uye_fiyatlari: Vec<UyeAidatFiyati>,
+    /// Aile tutarıyla ödeyene açılacak hane aidatı sayısı
+    pub hane_aidati_adedi: i32,
+    /// Aidatı hane ödeyenince karşılandığı için borçlandırılmayacak üye sayısı
+    pub hane_kapsamindaki_uyeler: i32,
+}
+
+/// Toplu aidat üyeleri; sadece_aktif ise yılın bir kısmında asil olanlar
+fn toplu_aidat_uyeleri(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+    sadece_aktif: bool,
+) -> Result<Vec<crate::db::models::Uye>, String> {
+    let uyeler: Vec<crate::db::models::Uye> = diesel::sql_query("SELECT * FROM uyeler WHERE tenant_id = ?1")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    if !sadece_aktif {
+        return Ok(uyeler);
+    }
+    let baslangic = chrono::NaiveDate::from_ymd_opt(yil, 1, 1).ok_or("Geçersiz yıl")?;
+    let bitis = chrono::NaiveDate::from_ymd_opt(yil, 12, 31).ok_or("Geçersiz yıl")?;
+    let asiller = uyelik_durumu::donemde_asil_uyeler(conn, tenant_id, baslangic, bitis)?;
+    Ok(uyeler.into_iter().filter(|u| asiller.contains(&u.id)).collect())
 }
 
 #[tauri::command]
@@ -516,17 +576,15 @@ pub async fn toplu_aidat_onizleme(
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
     // 1. Aktif üyeleri getir
-    let uyeler: Vec<crate::db::models::Uye> = if data.sadece_aktif_uyeler {
-        diesel::sql_query("SELECT * FROM uyeler WHERE tenant_id = ?1 AND cikis_tarihi IS NULL")
-            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-            .load(&mut conn)
-            .map_err(|e| e.to_string())?
-    } else {
-        diesel::sql_query("SELECT * FROM uyeler WHERE tenant_id = ?1")
-            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-            .load(&mut conn)
-            .map_err(|e| e.to_string())?
-    };
+    let uyeler = toplu_aidat_uyeleri(&mut conn, &tenant_id_param, data.yil, data.sadece_aktif_uyeler)?;
+
+    let motor = FiyatMotoru::yukle(&mut conn, &tenant_id_param, data.yil, Some(data.varsayilan_tutar))?;
+    let haneler = HaneTahakkuku::yukle(
+        &mut conn,
+        &tenant_id_param,
+        data.yil,
+        uyeler.iter().map(|u| (u.id.as_str(), u.uyelik_tipi.as_deref().unwrap_or("Asil"))),
+    )?;
 
     let toplam_uye_sayisi = uyeler.len() as i32;
     let mut borçlandirilacak = 0;
@@ -534,11 +592,15 @@ pub async fn toplu_aidat_onizleme(
     let mut ozel_tutarli = 0;
     let mut tanim_tutarli = 0;
     let mut varsayilan_tutarli = 0;
-    let mut toplam_tutar = 0.0;
+    let mut kural_uygulanan = 0;
+    let mut hane_aidati = 0;
+    let mut hane_kapsaminda = 0;
+    let mut toplam_tutar = Para::SIFIR;
     let mut uyarilar = Vec::new();
+    let mut uye_fiyatlari = Vec::new();
 
     // Üyelik türü bazında istatistikler
-    let mut uyelik_turu_map: HashMap<String, (i32, f64)> = HashMap::new(); // (adet, toplam_tutar)
+    let mut uyelik_turu_map: HashMap<String, (i32, Para)> = HashMap::new(); // (adet, toplam_tutar)
 
     for uye in uyeler {
         // Bu üyenin bu yıl için aidatı var mı kontrol et
@@ -555,37 +617,34 @@ pub async fn toplu_aidat_onizleme(
             continue;
         }
 
-        // Tutar hesaplama mantığı (toplu_aidat_olustur ile aynı)
-        let (uye_aidat_tutari, tutar_kaynak) = if let Some(ozel_tutar) = uye.ozel_aidat_tutari {
-            ozel_tutarli += 1;
-            (ozel_tutar, "ozel")
-        } else {
-            let uye_tipi = uye.uyelik_tipi.clone().unwrap_or_else(|| "Asil".to_string());
-            let tanim_tutar: Option<f64> = diesel::sql_query(
-                "SELECT tutar FROM aidat_tanimlari WHERE tenant_id = ?1 AND yil = ?2 AND uye_turu = ?3 AND is_active = 1"
-            )
-            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-            .bind::<diesel::sql_types::Integer, _>(data.yil)
-            .bind::<diesel::sql_types::Text, _>(&uye_tipi)
-            .get_result::<TutarRow>(&mut conn)
-            .ok()
-            .map(|r| r.tutar);
-
-            if let Some(tutar) = tanim_tutar {
-                tanim_tutarli += 1;
-                (tutar, "tanim")
-            } else {
-                varsayilan_tutarli += 1;
-                (data.varsayilan_tutar, "varsayilan")
+        // Tutar hesaplama: toplu_aidat_olustur ile aynı fiyat motoru ve hane kararı
+        let mut fiyat = aidat_fiyat::uye_fiyati(&motor, &uye);
+        match haneler.karar(&uye.id) {
+            HaneKarari::Bireysel => {}
+            HaneKarari::Odeyen(h) => fiyat.fiyat = h.fiyat(),
+            HaneKarari::Kapsanan(_) => {
+                hane_kapsaminda += 1;
+                continue;
             }
-        };
+        }
+        match fiyat.fiyat.kaynak.as_str() {
+            "ozel" => ozel_tutarli += 1,
+            "tanim" => tanim_tutarli += 1,
+            "hane" => hane_aidati += 1,
+            _ => varsayilan_tutarli += 1,
+        }
+        if fiyat.fiyat.kural_uygulandi() {
+            kural_uygulanan += 1;
+        }
+        let uye_aidat_tutari = fiyat.fiyat.tutar;
+        uye_fiyatlari.push(fiyat);
 
         borçlandirilacak += 1;
         toplam_tutar += uye_aidat_tutari;
 
         // Üyelik türü istatistiği
         let uye_turu = uye.uyelik_tipi.clone().unwrap_or_else(|| "Asil".to_string());
-        let entry = uyelik_turu_map.entry(uye_turu).or_insert((0, 0.0));
+        let entry = uyelik_turu_map.entry(uye_turu).or_insert((0, Para::SIFIR));
         entry.0 += 1;
         entry.1 += uye_aidat_tutari;
     }
@@ -597,6 +656,9 @@ pub async fn toplu_aidat_onizleme(
     if varsayilan_tutarli > 0 {
         uyarilar.push(format!("{} üye için varsayılan tutar (₺{}) kullanılacak", varsayilan_tutarli, data.varsayilan_tutar));
     }
+    if hane_kapsaminda > 0 {
+        uyarilar.push(format!("{} üyenin aidatı {} hane aidatına dahil, ayrıca borçlandırılmayacak", hane_kapsaminda, hane_aidati));
+    }
     if borçlandirilacak == 0 {
         uyarilar.push("Hiçbir üye borçlandırılmayacak!".to_string());
     }
@@ -608,7 +670,7 @@ pub async fn toplu_aidat_onizleme(
             UyelikTuruDagilim {
                 uye_turu,
                 adet,
-                ortalama_tutar: if adet > 0 { toplam / adet as f64 } else { 0.0 },
+                ortalama_tutar: if adet > 0 { Para::from_kurus(toplam.kurus() / adet as i64) } else { Para::SIFIR },
                 toplam_tutar: toplam,
             }
         })
@@ -627,8 +689,12 @@ pub async fn toplu_aidat_onizleme(
         tanim_tutarli_uyeler: tanim_tutarli,
         varsayilan_tutarli_uyeler: varsayilan_tutarli,
         toplam_borclandirilacak_tutar: toplam_tutar,
-        ortalama_tutar: if borçlandirilacak > 0 { toplam_tutar / borçlandirilacak as f64 } else { 0.0 },
+        ortalama_tutar: if borçlandirilacak > 0 { Para::from_kurus(toplam_tutar.kurus() / borçlandirilacak as i64) } else { Para::SIFIR },
         uyarilar,
+        kural_uygulanan_uyeler: kural_uygulanan,
+        uye_fiyatlari,
+        hane_aidati_adedi: hane_aidati,
+        hane_kapsamindaki_uyeler: hane_kapsaminda,
     })
 }
 
@@ -645,21 +711,23 @@ pub async fn toplu_aidat_olustur(
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
     // Aktif üyeleri getir
-    let uyeler: Vec<crate::db::models::Uye> = if data.sadece_aktif_uyeler {
-        diesel::sql_query("SELECT * FROM uyeler WHERE tenant_id = ?1 AND cikis_tarihi IS NULL")
-            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-            .load(&mut conn)
-            .map_err(|e| e.to_string())?
-    } else {
-        diesel::sql_query("SELECT * FROM uyeler WHERE tenant_id = ?1")
-            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-            .load(&mut conn)
-            .map_err(|e| e.to_string())?
-    };
+    let uyeler = toplu_aidat_uyeleri(&mut conn, &tenant_id_param, data.yil, data.sadece_aktif_uyeler)?;
+
+    // Kilitli yıla toplu tahakkuk yapılmaz
+    donem_kilidi::donemi_dogrula(&mut conn, &tenant_id_param, data.yil, 1)?;
+
+    let motor = FiyatMotoru::yukle(&mut conn, &tenant_id_param, data.yil, Some(data.varsayilan_tutar))?;
+    let haneler = HaneTahakkuku::yukle(
+        &mut conn,
+        &tenant_id_param,
+        data.yil,
+        uyeler.iter().map(|u| (u.id.as_str(), u.uyelik_tipi.as_deref().unwrap_or("Asil"))),
+    )?;
 
     let mut olusturulan = 0;
-    let mut toplam = 0.0;
+    let mut toplam = Para::SIFIR;
 
+    let mut denetim = state.denetim("toplu_aidat_olustur");
     for uye in uyeler {
         // Bu üyenin bu yıl için aidatı var mı kontrol et
         let mevcut = aidat_dsl::aidat_takip
@@ -674,27 +742,21 @@ pub async fn toplu_aidat_olustur(
             let aidat_id = Uuid::new_v4().to_string();
             let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
             
-            // Öncelik sırası: 1. Üyenin özel aidat tutarı, 2. Üyelik tipine göre tanım, 3. Varsayılan tutar
-            let uye_aidat_tutari = if let Some(ozel_tutar) = uye.ozel_aidat_tutari {
-                ozel_tutar
-            } else {
-                // Üyelik tipine göre aidat tanımından tutar çek
-                let uye_tipi = uye.uyelik_tipi.clone().unwrap_or_else(|| "Asil".to_string());
-                let tanim_tutar: Option<f64> = diesel::sql_query(
-                    "SELECT tutar FROM aidat_tanimlari WHERE tenant_id = ?1 AND yil = ?2 AND uye_turu = ?3 AND is_active = 1"
-                )
-                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-                .bind::<diesel::sql_types::Integer, _>(data.yil)
-                .bind::<diesel::sql_types::Text, _>(&uye_tipi)
-                .get_result::<TutarRow>(&mut conn)
-                .ok()
-                .map(|r| r.tutar);
-                
-                tanim_tutar.unwrap_or(data.varsayilan_tutar)
+            // Özel tutar, üye türü tanımı veya varsayılan + fiyat kuralları; hanede aile tutarı ödeyene
+            let (fiyat, hane_aidati) = match haneler.karar(&uye.id) {
+                HaneKarari::Bireysel => (motor.hesapla(&uye), None),
+                HaneKarari::Odeyen(h) => (h.fiyat(), Some(h)),
+                HaneKarari::Kapsanan(_) => continue,
             };
-            
+            let uye_aidat_tutari = fiyat.tutar;
+            let notlar = match fiyat.ozet() {
+                Some(ozet) => format!("Toplu oluşturuldu · {}", ozet),
+                None => "Toplu oluşturuldu".to_string(),
+            };
+
             // Transaction içinde aidat + outbox kaydı oluştur
             conn.transaction::<_, TxError, _>(|conn| {
+                denetim.yeni_kayit(&tenant_id_param, "aidat_takip", &aidat_id);
                 // Aidat kaydı oluştur
                 diesel::sql_query(
                     "INSERT INTO aidat_takip (
@@ -709,20 +771,28 @@ pub async fn toplu_aidat_olustur(
                 .bind::<diesel::sql_types::Integer, _>(data.yil)
                 .bind::<diesel::sql_types::Integer, _>(1)
                 .bind::<diesel::sql_types::Double, _>(uye_aidat_tutari)
-                .bind::<diesel::sql_types::Double, _>(0.0)
+                .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
                 .bind::<diesel::sql_types::Double, _>(uye_aidat_tutari)
                 .bind::<diesel::sql_types::Text, _>("beklemede")
                 .bind::<diesel::sql_types::Integer, _>(0)
-                .bind::<diesel::sql_types::Double, _>(0.0)
-                .bind::<diesel::sql_types::Text, _>("Toplu oluşturuldu")
+                .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
+                .bind::<diesel::sql_types::Text, _>(&notlar)
                 .bind::<diesel::sql_types::Text, _>("Bekliyor")
                 .bind::<diesel::sql_types::Text, _>(&now)
                 .bind::<diesel::sql_types::Text, _>(&now)
                 .execute(conn)?;
 
+                if let Some(h) = hane_aidati {
+                    hane::paylari_yaz(conn, &tenant_id_param, &aidat_id, h).map_err(TxError::Msg)?;
+                }
                 outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "create")
                     .map_err(TxError::Msg)?;
+                yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+                    .map_err(TxError::Msg)?;
+                avans::mahsup_et(conn, &mut denetim, &tenant_id_param, &uye.id, &bugun())
+                    .map_err(TxError::Msg)?;
 
+                denetim.kaydet(conn).map_err(TxError::Msg)?;
                 Ok(())
             }).map_err(|e| format!("Aidat oluşturulamadı: {}", e))?;
 
@@ -761,9 +831,14 @@ pub async fn toplu_aidat_kisi_bazli(
         .get_result(&mut conn)
         .map_err(|_| "Üye bulunamadı!")?;
 
+    for yil in baslangic_yili..=bitis_yili {
+        donem_kilidi::donemi_dogrula(&mut conn, &tenant_id_param, yil, 1)?;
+    }
+
     let mut olusturulan = 0;
-    let mut toplam = 0.0;
+    let mut toplam = Para::SIFIR;
 
+    let mut denetim = state.denetim("toplu_aidat_kisi_bazli");
     for yil in baslangic_yili..=bitis_yili {
         // Bu üyenin bu yıl için aidatı var mı kontrol et
         let mevcut = aidat_dsl::aidat_takip
@@ -778,27 +853,23 @@ pub async fn toplu_aidat_kisi_bazli(
             let aidat_id = Uuid::new_v4().to_string();
             let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
             
-            // Öncelik sırası: 1. Üyenin özel aidat tutarı, 2. Üyelik tipine göre tanım, 3. Varsayılan 0
-            let uye_aidat_tutari = if let Some(ozel_tutar) = uye.ozel_aidat_tutari {
-                ozel_tutar
-            } else {
-                // Üyelik tipine göre aidat tanımından tutar çek
-                let uye_tipi = uye.uyelik_tipi.clone().unwrap_or_else(|| "Asil".to_string());
-                let tanim_tutar: Option<f64> = diesel::sql_query(
-                    "SELECT tutar FROM aidat_tanimlari WHERE tenant_id = ?1 AND yil = ?2 AND uye_turu = ?3 AND is_active = 1"
-                )
-                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-                .bind::<diesel::sql_types::Integer, _>(yil)
-                .bind::<diesel::sql_types::Text, _>(&uye_tipi)
-                .get_result::<TutarRow>(&mut conn)
-                .ok()
-                .map(|r| r.tutar);
-                
-                tanim_tutar.unwrap_or(0.0)
+            // Özel tutar veya üye türü tanımı (yoksa 0) + o yılın fiyat kuralları;
+            // hanede aile tutarı ödeyene açılır, diğer hane üyeleri atlanır
+            let haneler = HaneTahakkuku::aktif_uyelerle(&mut conn, &tenant_id_param, yil)?;
+            let (fiyat, hane_aidati) = match haneler.karar(&uye.id) {
+                HaneKarari::Bireysel => (FiyatMotoru::yukle(&mut conn, &tenant_id_param, yil, None)?.hesapla(&uye), None),
+                HaneKarari::Odeyen(h) => (h.fiyat(), Some(h)),
+                HaneKarari::Kapsanan(_) => continue,
             };
-            
+            let uye_aidat_tutari = fiyat.tutar;
+            let notlar = match fiyat.ozet() {
+                Some(ozet) => format!("Toplu oluşturuldu ({}) · {}", yil, ozet),
+                None => format!("Toplu oluşturuldu ({})", yil),
+            };
+
             // Aidat kaydı + outbox kaydı aynı transaction'da (doküman merkezi deseni)
             conn.transaction::<_, TxError, _>(|conn| {
+                denetim.yeni_kayit(&tenant_id_param, "aidat_takip", &aidat_id);
                 diesel::sql_query(
                     "INSERT INTO aidat_takip (
                         id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan,
@@ -812,20 +883,28 @@ pub async fn toplu_aidat_kisi_bazli(
                 .bind::<diesel::sql_types::Integer, _>(yil)
                 .bind::<diesel::sql_types::Integer, _>(1)
                 .bind::<diesel::sql_types::Double, _>(uye_aidat_tutari)
-                .bind::<diesel::sql_types::Double, _>(0.0)
+                .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
                 .bind::<diesel::sql_types::Double, _>(uye_aidat_tutari)
                 .bind::<diesel::sql_types::Text, _>("beklemede")
                 .bind::<diesel::sql_types::Integer, _>(0)
-                .bind::<diesel::sql_types::Double, _>(0.0)
-                .bind::<diesel::sql_types::Text, _>(format!("Toplu oluşturuldu ({})", yil))
+                .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
+                .bind::<diesel::sql_types::Text, _>(&notlar)
                 .bind::<diesel::sql_types::Text, _>("aktarilmadi")
                 .bind::<diesel::sql_types::Text, _>(&now)
                 .bind::<diesel::sql_types::Text, _>(&now)
                 .execute(conn)?;
 
+                if let Some(h) = hane_aidati {
+                    hane::paylari_yaz(conn, &tenant_id_param, &aidat_id, h).map_err(TxError::Msg)?;
+                }
                 outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "create")
                     .map_err(TxError::Msg)?;
+                yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+                    .map_err(TxError::Msg)?;
+                avans::mahsup_et(conn, &mut denetim, &tenant_id_param, &uye.id, &bugun())
+                    .map_err(TxError::Msg)?;
 
+                denetim.kaydet(conn).map_err(TxError::Msg)?;
                 Ok(())
             })
             .map_err(|e| e.to_string())?;
@@ -848,7 +927,7 @@ pub struct CokluYilOdemeRequest {
     pub uye_id: String,
     pub baslangic_yili: i32,
     pub bitis_yili: i32,
-    pub toplam_tutar: f64,
+    pub toplam_tutar: Para,
     pub odeme_tarihi: String,
     pub kasa_id: String,
 }
@@ -858,7 +937,7 @@ pub struct CokluYilOdemeResult {
     pub success: bool,
     pub odenen_yil_sayisi: i32,
     pub yillar: Vec<i32>,
-    pub yillik_tutar: f64,
+    pub yillik_tutar: Para,
 }
 
 #[tauri::command]
@@ -873,15 +952,21 @@ pub async fn coklu_yil_odeme(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    let yil_sayisi = (data.bitis_yili - data.baslangic_yili + 1) as f64;
-    let yillik_tutar = data.toplam_tutar / yil_sayisi;
+    if data.bitis_yili < data.baslangic_yili {
+        return Err("Bitiş yılı başlangıç yılından önce olamaz".to_string());
+    }
+    let yil_sayisi = (data.bitis_yili - data.baslangic_yili + 1) as usize;
+    // Toplam tutar yıllara kuruş kaybı olmadan paylaştırılır (artan kuruşlar ilk yıllara)
+    let yillik_tutarlar = data.toplam_tutar.paylastir(yil_sayisi);
+    let yillik_tutar = yillik_tutarlar[0];
 
+    let mut denetim = state.denetim("coklu_yil_odeme");
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
     // Yazımlar + outbox kayıtları aynı transaction'da (doküman merkezi deseni)
     let yillar = conn.transaction::<_, TxError, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &data.odeme_tarihi).map_err(TxError::Msg)?;
         let mut yillar = Vec::new();
-        let mut toplam_odenen: f64 = 0.0;
-
-        for y in data.baslangic_yili..=data.bitis_yili {
+        for (y, yillik_tutar) in (data.baslangic_yili..=data.bitis_yili).zip(yillik_tutarlar.iter().copied()) {
             // Bu yıl için aidat var mı kontrol et
             let mevcut = aidat_takip
                 .filter(tenant_id.eq(&tenant_id_param))
@@ -892,26 +977,40 @@ pub async fn coklu_yil_odeme(
 
             let aidat_id_for_gelir: String;
             let aidat_op: &str;
+            // Mevcut aidatın kalan borcunu aşan kısım üye avansına alınır
+            let mut fazla = Para::SIFIR;
 
             if let Some(aidat_rec) = mevcut {
                 aidat_id_for_gelir = aidat_rec.id.clone();
                 aidat_op = "update";
+                denetim.izle(conn, &tenant_id_param, "aidat_takip", &aidat_rec.id).map_err(TxError::Msg)?;
+
+                let acik = aidat_rec
+                    .kalan
+                    .unwrap_or(aidat_rec.tutar - aidat_rec.odenen)
+                    .sifirdan_kucukse_sifir();
+                let uygulanan = yillik_tutar.min(acik);
+                fazla = yillik_tutar - uygulanan;
+                let yeni_kalan = acik - uygulanan;
+                let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else { "kismi_odendi" };
 
                 // Mevcut aidatı güncelle
                 diesel::update(aidat_takip.filter(id.eq(&aidat_rec.id)))
                     .set((
-                        odenen.eq(yillik_tutar),
-                        kalan.eq(0.0),
-                        durum.eq("odendi"),
+                        odenen.eq(aidat_rec.odenen + uygulanan),
+                        kalan.eq(yeni_kalan),
+                        durum.eq(yeni_durum),
                         odeme_tarihi.eq(Some(&data.odeme_tarihi)),
                         updated_at.eq(chrono::Utc::now().to_rfc3339()),
                     ))
                     .execute(conn)?;
             } else {
-                // Yeni aidat kaydı oluştur
+                // Yeni aidat kaydı oluştur (tahakkuk dönemi de kilitli olmamalı)
+                donem_kilidi::donemi_dogrula(conn, &tenant_id_param, y, 1).map_err(TxError::Msg)?;
                 let new_id = Uuid::new_v4().to_string();
                 aidat_id_for_gelir = new_id.clone();
                 aidat_op = "create";
+                denetim.yeni_kayit(&tenant_id_param, "aidat_takip", &aidat_id_for_gelir);
 
                 let new_aidat = AidatTakip {
                     id: new_id,
@@ -921,10 +1020,10 @@ pub async fn coklu_yil_odeme(
                     ay: 1,
                     tutar: yillik_tutar,
                     odenen: yillik_tutar,
-                    kalan: Some(0.0),
+                    kalan: Some(Para::SIFIR),
                     durum: "odendi".to_string(),
                     gecikme_gun: Some(0),
-                    gecikme_faiz: Some(0.0),
+                    gecikme_faiz: Some(Para::SIFIR),
                     tahsilat_turu: None,
                     banka_sube: None,
                     dekont_no: None,
@@ -946,10 +1045,13 @@ pub async fn coklu_yil_odeme(
 
             outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id_for_gelir, aidat_op)
                 .map_err(TxError::Msg)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id_for_gelir)
+                .map_err(TxError::Msg)?;
 
             // Her yıl için gelir kaydı oluştur ve kasa güncelle
             let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
             let new_gelir_id = Uuid::new_v4().to_string();
+            denetim.yeni_kayit(&tenant_id_param, "gelirler", &new_gelir_id);
             let makbuz_no = format!("AIDAT-{}", &new_gelir_id[..8]);
 
             diesel::sql_query(
@@ -970,24 +1072,31 @@ pub async fn coklu_yil_odeme(
             .execute(conn)
             .map_err(|e| TxError::Msg(format!("Gelir kaydı oluşturulamadı: {}", e)))?;
 
+            alindi_belgesi::gelire_makbuz_kes(conn, &tenant_id_param, kullanici_id.as_deref(), &new_gelir_id)
+                .map_err(TxError::Msg)?;
             outbox::queue_change(conn, &tenant_id_param, "gelirler", &new_gelir_id, "create")
                 .map_err(TxError::Msg)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &new_gelir_id)
+                .map_err(TxError::Msg)?;
+            avans::fazla_odemeyi_kaydet(
+                conn,
+                &mut denetim,
+                &tenant_id_param,
+                &data.uye_id,
+                &data.odeme_tarihi,
+                fazla,
+                &new_gelir_id,
+            )
+            .map_err(TxError::Msg)?;
 
-            toplam_odenen += yillik_tutar;
             yillar.push(y);
         }
 
-        // Toplam tutarı kasaya ekle (tek seferde) — türetilmiş alan, kasalar için outbox kaydı atılmaz
-        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
-        diesel::sql_query(
-            "UPDATE kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3"
-        )
-        .bind::<diesel::sql_types::Double, _>(toplam_odenen)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .execute(conn)
-        .map_err(|e| TxError::Msg(format!("Kasa güncellenemedi: {}", e)))?;
+        // Kasa bakiyesini gelirler SUM'ından yeniden hesapla — türetilmiş alan, kasalar için outbox kaydı atılmaz
+        crate::commands::mali::update_kasa_bakiye(conn, &data.kasa_id)
+            .map_err(|e| TxError::Msg(format!("Kasa güncellenemedi: {}", e)))?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(yillar)
     })
     .map_err(|e| e.to_string())?;
@@ -1004,6 +1113,11 @@ pub async fn coklu_yil_odeme(
 // HELPER FUNCTIONS - Aidat → Gelir → Kasa Entegrasyonu
 // ============================================================================
 
+/// Avans mahsubunun tarihi: tahakkukun yapıldığı gün
+fn bugun() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
 /// Aidat ödemesi için otomatik gelir kaydı oluşturur ve kasa bakiyesini günceller
 fn create_gelir_from_aidat(
     conn: &mut SqliteConnection,
@@ -1011,7 +1125,7 @@ fn create_gelir_from_aidat(
     kasa_id: &str,
     aidat: &AidatTakip,
     uye_id: &str,
-    tutar: f64,
+    tutar: Para,
     tarih: &str,
 ) -> Result<String, String> {
     let gelir_id = Uuid::new_v4().to_string();
@@ -1056,93 +1170,14 @@ fn create_gelir_from_aidat(
     Ok(gelir_id)
 }
 
-/// Kasa bakiyesini yeniden hesaplar (gelir/gider/virman toplamları)
+/// Kasa bakiyesini yeniden hesaplar (gelir/gider/virman toplamları).
+/// Hesap mali modülündeki tek kaynaktan yapılır; burada yalnızca hata tipi uyarlanır.
 fn update_kasa_bakiye(
     conn: &mut SqliteConnection,
     kasa_id: &str,
 ) -> Result<(), String> {
-    #[derive(QueryableByName)]
-    struct TotalQuery {
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        total: f64,
-    }
-    
-    #[derive(QueryableByName)]
-    struct KasaInfoQuery {
-        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        devir_bakiye: Option<f64>,
-        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        virman_giris: Option<f64>,
-        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        virman_cikis: Option<f64>,
-        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        tahakkuk_tutari: Option<f64>,
-    }
-    
-    // Toplam gelir hesapla
-    let toplam_gelir: f64 = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM gelirler WHERE kasa_id = ?1"
-    )
-    .bind::<diesel::sql_types::Text, _>(kasa_id)
-    .get_result::<TotalQuery>(conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
-    
-    // Toplam gider hesapla
-    let toplam_gider: f64 = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM giderler WHERE kasa_id = ?1"
-    )
-    .bind::<diesel::sql_types::Text, _>(kasa_id)
-    .get_result::<TotalQuery>(conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
-    
-    // Kasa bilgilerini çek (devir_bakiye için)
-    let kasa_info = diesel::sql_query(
-        "SELECT devir_bakiye, virman_giris, virman_cikis, tahakkuk_tutari FROM kasalar WHERE id = ?1"
-    )
-    .bind::<diesel::sql_types::Text, _>(kasa_id)
-    .get_result::<KasaInfoQuery>(conn)
-    .unwrap_or(KasaInfoQuery {
-        devir_bakiye: Some(0.0),
-        virman_giris: Some(0.0),
-        virman_cikis: Some(0.0),
-        tahakkuk_tutari: Some(0.0),
-    });
-    
-    let devir_bakiye = kasa_info.devir_bakiye.unwrap_or(0.0);
-    let virman_giris = kasa_info.virman_giris.unwrap_or(0.0);
-    let virman_cikis = kasa_info.virman_cikis.unwrap_or(0.0);
-    let tahakkuk_tutari = kasa_info.tahakkuk_tutari.unwrap_or(0.0);
-    
-    // Fiziksel bakiye = devir + gelir - gider + virman_giris - virman_cikis
-    let fiziksel_bakiye = devir_bakiye + toplam_gelir - toplam_gider + virman_giris - virman_cikis;
-    
-    // Serbest bakiye = fiziksel_bakiye - tahakkuk
-    let serbest_bakiye = fiziksel_bakiye - tahakkuk_tutari;
-    
-    // Kasa tablosunu güncelle
-    diesel::sql_query(
-        "UPDATE kasalar 
-         SET toplam_gelir = ?1,
-             toplam_gider = ?2,
-             fiziksel_bakiye = ?3,
-             serbest_bakiye = ?4,
-             bakiye = ?5,
-             updated_at = ?6
-         WHERE id = ?7"
-    )
-    .bind::<diesel::sql_types::Double, _>(toplam_gelir)
-    .bind::<diesel::sql_types::Double, _>(toplam_gider)
-    .bind::<diesel::sql_types::Double, _>(fiziksel_bakiye)
-    .bind::<diesel::sql_types::Double, _>(serbest_bakiye)
-    .bind::<diesel::sql_types::Double, _>(fiziksel_bakiye) // bakiye = fiziksel_bakiye
-    .bind::<diesel::sql_types::Text, _>(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
-    .bind::<diesel::sql_types::Text, _>(kasa_id)
-    .execute(conn)
-    .map_err(|e| format!("Kasa bakiyesi güncellenemedi: {}", e))?;
-    
-    Ok(())
+    crate::commands::mali::update_kasa_bakiye(conn, kasa_id)
+        .map_err(|e| format!("Kasa bakiyesi güncellenemedi: {}", e))
 }
 
 /// Aidat ödemesi kaydet + Gelir oluştur + Kasa güncelle (Basit versiyon - transaction eklenecek)
@@ -1150,7 +1185,7 @@ fn update_kasa_bakiye(
 pub struct AidatOdemeRequest {
     pub aidat_id: String,
     pub kasa_id: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub odeme_tarihi: String,
 }
 
@@ -1166,8 +1201,12 @@ pub async fn kaydet_aidat_odeme_with_gelir(
 
     use crate::db::schema::aidat_takip::dsl::*;
 
+    let mut denetim = state.denetim("kaydet_aidat_odeme_with_gelir");
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
     // Transaction + optimistic locking — iki kullanıcı aynı anda ödeme yaparsa ikisi de sağlam olur.
     let result: Result<String, String> = conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &data.aidat_id).map_err(TxError::Msg)?;
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &data.odeme_tarihi).map_err(TxError::Msg)?;
         // 1. Mevcut aidat kaydını çek (version dahil)
         let current_aidat = aidat_takip
             .filter(id.eq(&data.aidat_id))
@@ -1181,7 +1220,7 @@ pub async fn kaydet_aidat_odeme_with_gelir(
 
         let yeni_odenen = current_aidat.odenen + data.tutar;
         let yeni_kalan = current_aidat.tutar - yeni_odenen;
-        let yeni_durum = if yeni_kalan <= 0.01 { "odendi" } else { "kismi_odendi" };
+        let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else { "kismi_odendi" };
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
         // 3. Aidat kaydını güncelle (version check ile)
@@ -1218,11 +1257,19 @@ pub async fn kaydet_aidat_odeme_with_gelir(
         // 5. Outbox: gelir "create" + aidat "update" (create_gelir_from_aidat aidat üzerinde
         //    gelir_id/aktarim_durumu da güncellediği için snapshot burada, en sonda alınır).
         //    Kasa bakiyesi türetilmiş — kasalar için outbox kaydı atılmaz.
+        denetim.yeni_kayit(&tenant_id_param, "gelirler", &created_gelir_id);
+        alindi_belgesi::gelire_makbuz_kes(conn, &tenant_id_param, kullanici_id.as_deref(), &created_gelir_id)
+            .map_err(TxError::Msg)?;
         outbox::queue_change(conn, &tenant_id_param, "gelirler", &created_gelir_id, "create")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &created_gelir_id)
+            .map_err(TxError::Msg)?;
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &data.aidat_id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &data.aidat_id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(created_gelir_id)
     })
     .map_err(|e| match e {
@@ -1242,7 +1289,7 @@ pub async fn kaydet_aidat_odeme_with_gelir(
 pub struct AidatOdeme {
     pub id: String,
     pub aidat_id: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub odeme_tarihi: String,
     pub created_at: String,
 }
@@ -1253,7 +1300,7 @@ pub async fn add_aidat_odeme(
     state: State<'_, crate::AppState>,
     tenant_id_param: String,
     aidat_id: String,
-    odeme_tutari: f64,
+    odeme_tutari: Para,
 ) -> Result<String, String> {
     let pool = state.db.lock().unwrap();
     let pool = pool.as_ref().ok_or("Database not initialized")?;
@@ -1271,13 +1318,16 @@ pub async fn add_aidat_odeme(
     // Yeni ödenen miktarı hesapla
     let yeni_odenen = current_aidat.odenen + odeme_tutari;
     let yeni_kalan = current_aidat.tutar - yeni_odenen;
-    let yeni_durum = if yeni_kalan <= 0.01 { "odendi" } else { "kismi_odendi" };
+    let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else { "kismi_odendi" };
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
     let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
     
+    let mut denetim = state.denetim("add_aidat_odeme");
     // Aidat kaydını güncelle (version check ile — concurrent ödeme çakışmalarını yakala)
     // Yazım + outbox kaydı aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &today).map_err(TxError::Msg)?;
         let affected = diesel::sql_query(
             "UPDATE aidat_takip
              SET odenen = ?1, kalan = ?2, durum = ?3, odeme_tarihi = ?4, version = version + 1, updated_at = ?5
@@ -1299,7 +1349,10 @@ pub async fn add_aidat_odeme(
 
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -1307,88 +1360,128 @@ pub async fn add_aidat_odeme(
     Ok("Ödeme kaydedildi".to_string())
 }
 
+/// Tek aidata gelir kayıtlı ödeme: gelir + makbuz, aidat güncellemesi, kasa bakiyesi,
+/// outbox ve yevmiye. Aidat aktif bir taksit planındaysa ödeme planın taksitlerine de
+/// işlenir. Çağıranın transaction'ında çalışır; gelir kimliğini döner.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn aidat_odemesi_gelirle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id_param: &str,
+    aidat_id: &str,
+    odeme_tutari: Para,
+    kasa_id: &str,
+    kullanici_id: Option<&str>,
+) -> Result<String, TxError> {
+    denetim.izle(conn, tenant_id_param, "aidat_takip", aidat_id).map_err(TxError::Msg)?;
+    // 1. Mevcut aidat kaydını çek
+    let current_aidat: AidatTakip = {
+        use crate::db::schema::aidat_takip::dsl::*;
+        aidat_takip
+            .filter(id.eq(aidat_id))
+            .filter(tenant_id.eq(tenant_id_param))
+            .first(conn)?
+    };
+
+    // Yeni ödenen miktarı hesapla
+    let yeni_odenen = current_aidat.odenen + odeme_tutari;
+    let yeni_kalan = current_aidat.tutar - yeni_odenen;
+    let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else { "kismi_odendi" };
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    donem_kilidi::tarihi_dogrula(conn, tenant_id_param, &today).map_err(TxError::Msg)?;
+    let gelir_id = Uuid::new_v4().to_string();
+    denetim.yeni_kayit(tenant_id_param, "gelirler", &gelir_id);
+
+    // 2. Gelir kaydı oluştur
+    diesel::sql_query(
+        "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, tutar, tarih, aciklama, aidat_id, makbuz_no, is_active, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 1, ?10, ?10)"
+    )
+    .bind::<diesel::sql_types::Text, _>(&gelir_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id_param)
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .bind::<diesel::sql_types::Text, _>("Aidat")
+    .bind::<diesel::sql_types::Double, _>(odeme_tutari)
+    .bind::<diesel::sql_types::Text, _>(&today)
+    .bind::<diesel::sql_types::Text, _>(format!("Aidat ödemesi - {} {}", current_aidat.yil, current_aidat.ay))
+    .bind::<diesel::sql_types::Text, _>(aidat_id)
+    .bind::<diesel::sql_types::Text, _>(format!("AIDAT-{}", &gelir_id[..8]))
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)?;
+
+    // 3. Aidat kaydını güncelle (gelir_id ile ilişkilendir) + version check
+    let affected = diesel::sql_query(
+        "UPDATE aidat_takip
+         SET odenen = ?1, kalan = ?2, durum = ?3, odeme_tarihi = ?4, gelir_id = ?5, version = version + 1, updated_at = ?6
+         WHERE id = ?7 AND version = ?8"
+    )
+    .bind::<diesel::sql_types::Double, _>(yeni_odenen)
+    .bind::<diesel::sql_types::Double, _>(yeni_kalan)
+    .bind::<diesel::sql_types::Text, _>(yeni_durum)
+    .bind::<diesel::sql_types::Text, _>(&today)
+    .bind::<diesel::sql_types::Text, _>(&gelir_id)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(aidat_id)
+    .bind::<diesel::sql_types::Integer, _>(current_aidat.version)
+    .execute(conn)?;
+
+    if affected == 0 {
+        return Err(TxError::Diesel(diesel::result::Error::RollbackTransaction));
+    }
+
+    // 4. Kasa bakiyesini tek kaynaktan (gelirler SUM) yeniden hesapla.
+    // NOT: Eski kod burada hem `bakiye += ?` hem `toplam_gelir += ?` yapıyordu ve ayrıca
+    // gelirler tablosuna kayıt ekliyordu — bu çift sayım demekti.
+    update_kasa_bakiye(conn, kasa_id)
+        .map_err(|_| TxError::Diesel(diesel::result::Error::RollbackTransaction))?;
+
+    // 5. Outbox: gelir "create" + aidat "update" aynı transaction'da.
+    //    Kasa bakiyesi türetilmiş — kasalar için outbox kaydı atılmaz.
+    alindi_belgesi::gelire_makbuz_kes(conn, tenant_id_param, kullanici_id, &gelir_id)
+        .map_err(TxError::Msg)?;
+    outbox::queue_change(conn, tenant_id_param, "gelirler", &gelir_id, "create")
+        .map_err(TxError::Msg)?;
+    yevmiye::kaydi_isle(conn, tenant_id_param, "gelirler", &gelir_id)
+        .map_err(TxError::Msg)?;
+    outbox::queue_change(conn, tenant_id_param, "aidat_takip", aidat_id, "update")
+        .map_err(TxError::Msg)?;
+    yevmiye::kaydi_isle(conn, tenant_id_param, "aidat_takip", aidat_id)
+        .map_err(TxError::Msg)?;
+
+    // 6. Aidat taksit planındaysa ödeme sıradaki taksitlere işlenir
+    taksit::odemeyi_isle(conn, denetim, tenant_id_param, aidat_id, odeme_tutari, &today, &gelir_id)
+        .map_err(TxError::Msg)?;
+    Ok(gelir_id)
+}
+
 /// Aidat ödeme + Gelir kaydı + Kasa güncellemesi (Tam entegrasyon)
 #[tauri::command]
 pub async fn add_aidat_odeme_with_gelir(
     state: State<'_, crate::AppState>,
     tenant_id_param: String,
     aidat_id: String,
-    odeme_tutari: f64,
+    odeme_tutari: Para,
     kasa_id: String,
 ) -> Result<String, String> {
     let pool = state.db.lock().unwrap();
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("add_aidat_odeme_with_gelir");
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
     // TRANSACTION START - Critical for data consistency
     conn.transaction::<_, TxError, _>(|conn| {
-        // 1. Mevcut aidat kaydını çek
-        let current_aidat: AidatTakip = {
-            use crate::db::schema::aidat_takip::dsl::*;
-            aidat_takip
-                .filter(id.eq(&aidat_id))
-                .filter(tenant_id.eq(&tenant_id_param))
-                .first(conn)?
-        };
-
-        // Yeni ödenen miktarı hesapla
-        let yeni_odenen = current_aidat.odenen + odeme_tutari;
-        let yeni_kalan = current_aidat.tutar - yeni_odenen;
-        let yeni_durum = if yeni_kalan <= 0.01 { "odendi" } else { "kismi_odendi" };
-        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
-        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let gelir_id = Uuid::new_v4().to_string();
-
-        // 2. Gelir kaydı oluştur
-        diesel::sql_query(
-            "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, tutar, tarih, aciklama, aidat_id, makbuz_no, is_active, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 1, ?10, ?10)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&gelir_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&kasa_id)
-        .bind::<diesel::sql_types::Text, _>("Aidat")
-        .bind::<diesel::sql_types::Double, _>(odeme_tutari)
-        .bind::<diesel::sql_types::Text, _>(&today)
-        .bind::<diesel::sql_types::Text, _>(format!("Aidat ödemesi - {} {}", current_aidat.yil, current_aidat.ay))
-        .bind::<diesel::sql_types::Text, _>(&aidat_id)
-        .bind::<diesel::sql_types::Text, _>(format!("AIDAT-{}", &gelir_id[..8]))
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(conn)?;
-
-        // 3. Aidat kaydını güncelle (gelir_id ile ilişkilendir) + version check
-        let affected = diesel::sql_query(
-            "UPDATE aidat_takip
-             SET odenen = ?1, kalan = ?2, durum = ?3, odeme_tarihi = ?4, gelir_id = ?5, version = version + 1, updated_at = ?6
-             WHERE id = ?7 AND version = ?8"
-        )
-        .bind::<diesel::sql_types::Double, _>(yeni_odenen)
-        .bind::<diesel::sql_types::Double, _>(yeni_kalan)
-        .bind::<diesel::sql_types::Text, _>(yeni_durum)
-        .bind::<diesel::sql_types::Text, _>(&today)
-        .bind::<diesel::sql_types::Text, _>(&gelir_id)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&aidat_id)
-        .bind::<diesel::sql_types::Integer, _>(current_aidat.version)
-        .execute(conn)?;
-
-        if affected == 0 {
-            return Err(TxError::Diesel(diesel::result::Error::RollbackTransaction));
-        }
-
-        // 4. Kasa bakiyesini tek kaynaktan (gelirler SUM) yeniden hesapla.
-        // NOT: Eski kod burada hem `bakiye += ?` hem `toplam_gelir += ?` yapıyordu ve ayrıca
-        // gelirler tablosuna kayıt ekliyordu — bu çift sayım demekti.
-        update_kasa_bakiye(conn, &kasa_id)
-            .map_err(|_| TxError::Diesel(diesel::result::Error::RollbackTransaction))?;
-
-        // 5. Outbox: gelir "create" + aidat "update" aynı transaction'da.
-        //    Kasa bakiyesi türetilmiş — kasalar için outbox kaydı atılmaz.
-        outbox::queue_change(conn, &tenant_id_param, "gelirler", &gelir_id, "create")
-            .map_err(TxError::Msg)?;
-        outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "update")
-            .map_err(TxError::Msg)?;
-
+        aidat_odemesi_gelirle(
+            conn,
+            &mut denetim,
+            &tenant_id_param,
+            &aidat_id,
+            odeme_tutari,
+            &kasa_id,
+            kullanici_id.as_deref(),
+        )?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| format!("Transaction failed: {}", e))?;
@@ -1419,7 +1512,7 @@ pub async fn get_aidat_odemeleri(
             gelirler::tarih,
             gelirler::created_at,
         ))
-        .load::<(String, Option<String>, f64, String, String)>(&mut conn)
+        .load::<(String, Option<String>, Para, String, String)>(&mut conn)
         .map_err(|e| e.to_string())?;
 
     let result: Vec<AidatOdeme> = odemeler
@@ -1440,7 +1533,7 @@ pub async fn get_aidat_odemeleri(
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateAidatOdemeRequest {
-    pub tutar: Option<f64>,
+    pub tutar: Option<Para>,
     pub odeme_tarihi: Option<String>,
 }
 
@@ -1472,7 +1565,16 @@ pub async fn update_aidat_odeme(
     let yeni_tutar = request.tutar.unwrap_or(eski_tutar);
     let tutar_farki = yeni_tutar - eski_tutar;
 
+    let mut denetim = state.denetim("update_aidat_odeme");
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gelirler", &id).map_err(TxError::Msg)?;
+        if let Some(aid_id) = &current.aidat_id {
+            denetim.izle(conn, &tenant_id_param, "aidat_takip", aid_id).map_err(TxError::Msg)?;
+        }
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &current.tarih).map_err(TxError::Msg)?;
+        if let Some(ref yeni_tarih) = request.odeme_tarihi {
+            donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, yeni_tarih).map_err(TxError::Msg)?;
+        }
         // Gelir kaydını güncelle (tenant filtreli)
         diesel::update(
             gelir_dsl::gelirler
@@ -1488,23 +1590,12 @@ pub async fn update_aidat_odeme(
 
         outbox::queue_change(conn, &tenant_id_param, "gelirler", &id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &id)
+            .map_err(TxError::Msg)?;
 
-        // Kasa bakiyesini güncelle (türetilmiş alanlar — kasalar için outbox kaydı atılmaz)
-        if tutar_farki.abs() > 0.01 {
-            diesel::sql_query(
-                "UPDATE kasalar 
-                 SET toplam_gelir = COALESCE(toplam_gelir, 0.0) + ?1,
-                     fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) + ?2,
-                     bakiye = bakiye + ?3,
-                     updated_at = ?4
-                 WHERE id = ?5"
-            )
-            .bind::<diesel::sql_types::Double, _>(tutar_farki)
-            .bind::<diesel::sql_types::Double, _>(tutar_farki)
-            .bind::<diesel::sql_types::Double, _>(tutar_farki)
-            .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(&current.kasa_id)
-            .execute(conn)?;
+        // Kasa bakiyesini yeniden hesapla (türetilmiş alanlar — kasalar için outbox kaydı atılmaz)
+        if !tutar_farki.is_zero() {
+            crate::commands::mali::update_kasa_bakiye(conn, &current.kasa_id)?;
 
             // Aidat takip tablosunu da güncelle
             if let Some(aid_id) = &current.aidat_id {
@@ -1515,7 +1606,7 @@ pub async fn update_aidat_odeme(
 
                 let yeni_odenen = aidat.odenen + tutar_farki;
                 let yeni_kalan = aidat.tutar - yeni_odenen;
-                let yeni_durum = if yeni_kalan <= 0.01 { "odendi" } else { "kismi_odendi" };
+                let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else { "kismi_odendi" };
 
                 diesel::update(aidat_takip::table.find(aid_id))
                     .set((
@@ -1528,9 +1619,12 @@ pub async fn update_aidat_odeme(
 
                 outbox::queue_change(conn, &tenant_id_param, "aidat_takip", aid_id, "update")
                     .map_err(TxError::Msg)?;
+                yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", aid_id)
+                    .map_err(TxError::Msg)?;
             }
         }
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     }).map_err(|e: TxError| e.to_string())?;
 
@@ -1558,7 +1652,13 @@ pub async fn delete_aidat_odeme(
         .first::<crate::db::models::Gelir>(&mut conn)
         .map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("delete_aidat_odeme");
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gelirler", &id).map_err(TxError::Msg)?;
+        if let Some(aid_id) = &gelir.aidat_id {
+            denetim.izle(conn, &tenant_id_param, "aidat_takip", aid_id).map_err(TxError::Msg)?;
+        }
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &gelir.tarih).map_err(TxError::Msg)?;
         // Gelir kaydını sil (tenant filtreli)
         diesel::delete(
             gelir_dsl::gelirler
@@ -1569,22 +1669,11 @@ pub async fn delete_aidat_odeme(
 
         outbox::queue_change(conn, &tenant_id_param, "gelirler", &id, "delete")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &id)
+            .map_err(TxError::Msg)?;
 
-        // Kasa bakiyesini güncelle (geliri geri çıkar; türetilmiş alanlar — kasalar için outbox kaydı atılmaz)
-        diesel::sql_query(
-            "UPDATE kasalar 
-             SET toplam_gelir = COALESCE(toplam_gelir, 0.0) - ?1,
-                 fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) - ?2,
-                 bakiye = bakiye - ?3,
-                 updated_at = ?4
-             WHERE id = ?5"
-        )
-        .bind::<diesel::sql_types::Double, _>(gelir.tutar)
-        .bind::<diesel::sql_types::Double, _>(gelir.tutar)
-        .bind::<diesel::sql_types::Double, _>(gelir.tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&gelir.kasa_id)
-        .execute(conn)?;
+        // Kasa bakiyesini yeniden hesapla (silinen gelir SUM'dan düşer; türetilmiş alanlar — kasalar için outbox kaydı atılmaz)
+        crate::commands::mali::update_kasa_bakiye(conn, &gelir.kasa_id)?;
 
         // Aidat takip tablosunu güncelle (ödenen tutarı düşür)
         if let Some(aid_id) = &gelir.aidat_id {
@@ -1593,9 +1682,9 @@ pub async fn delete_aidat_odeme(
                 .filter(aidat_takip::id.eq(aid_id))
                 .first::<AidatTakip>(conn)?;
 
-            let yeni_odenen = aidat.odenen - gelir.tutar;
+            let yeni_odenen = (aidat.odenen - gelir.tutar).sifirdan_kucukse_sifir();
             let yeni_kalan = aidat.tutar - yeni_odenen;
-            let yeni_durum = if yeni_odenen <= 0.01 { "odenmedi" } else { "kismi_odendi" };
+            let yeni_durum = if yeni_odenen.is_zero() { "odenmedi" } else { "kismi_odendi" };
 
             diesel::update(aidat_takip::table.find(aid_id))
                 .set((
@@ -1608,8 +1697,11 @@ pub async fn delete_aidat_odeme(
 
             outbox::queue_change(conn, &tenant_id_param, "aidat_takip", aid_id, "update")
                 .map_err(TxError::Msg)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", aid_id)
+                .map_err(TxError::Msg)?;
         }
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     }).map_err(|e: TxError| e.to_string())?;
 
@@ -1618,7 +1710,7 @@ pub async fn delete_aidat_odeme(
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateAidatTanimlamaRequest {
-    pub tutar: Option<f64>,
+    pub tutar: Option<Para>,
     pub notlar: Option<String>,
 }
 
@@ -1646,11 +1738,14 @@ pub async fn update_aidat_tanimlama(
 
     let yeni_tutar = request.tutar.unwrap_or(current.tutar);
     let yeni_kalan = yeni_tutar - current.odenen;
-    let yeni_durum = if yeni_kalan <= 0.01 { "odendi" } else if current.odenen > 0.01 { "kismi_odendi" } else { "odenmedi" };
+    let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else if current.odenen.is_positive() { "kismi_odendi" } else { "odenmedi" };
 
+    let mut denetim = state.denetim("update_aidat_tanimlama");
     // Not: Bu komut adına rağmen aidat_takip tablosuna yazar — sync yüzeyindedir.
     // Yazım + outbox kaydı aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &id).map_err(TxError::Msg)?;
+        donem_kilidi::kaydi_dogrula(conn, &tenant_id_param, "aidat_takip", &id).map_err(TxError::Msg)?;
         diesel::update(
             aidat_dsl::aidat_takip
                 .filter(aidat_dsl::id.eq(&id))
@@ -1667,7 +1762,10 @@ pub async fn update_aidat_tanimlama(
 
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -1700,14 +1798,17 @@ pub async fn delete_aidat_tanimlama(
         .first::<AidatTakip>(&mut conn)
         .map_err(|e| e.to_string())?;
 
-    if aidat_record.odenen > 0.01 {
+    if aidat_record.odenen.is_positive() {
         return Err("Bu aidatın ödemeleri var, silinemez! Önce ödemeleri silin.".to_string());
     }
 
+    let mut denetim = state.denetim("delete_aidat_tanimlama");
     // Hard delete (ödeme yoksa)
     // Not: Bu komut adına rağmen aidat_takip tablosundan siler — sync yüzeyindedir.
     // Yazım + outbox kaydı aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &id).map_err(TxError::Msg)?;
+        donem_kilidi::kaydi_dogrula(conn, &tenant_id_param, "aidat_takip", &id).map_err(TxError::Msg)?;
         diesel::delete(
             aidat_dsl::aidat_takip
                 .filter(aidat_dsl::id.eq(&id))
@@ -1717,7 +1818,10 @@ pub async fn delete_aidat_tanimlama(
 
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &id, "delete")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -1731,9 +1835,9 @@ pub async fn delete_aidat_tanimlama(
 #[derive(Debug, Serialize)]
 pub struct UyeBorcDurumu {
     pub uye_id: String,
-    pub toplam_borc: f64,
-    pub odenen: f64,
-    pub kalan_borc: f64,
+    pub toplam_borc: Para,
+    pub odenen: Para,
+    pub kalan_borc: Para,
 }
 
 #[derive(Debug, QueryableByName)]
@@ -1741,9 +1845,9 @@ struct BorcRow {
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub uye_id: String,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub toplam_borc: f64,
+    pub toplam_borc: Para,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub odenen: f64,
+    pub odenen: Para,
 }
 
 /// Birden fazla üye için borç durumlarını toplu getir
@@ -1768,8 +1872,8 @@ pub async fn get_uye_borc_durumlari(
     let placeholders: Vec<String> = uye_ids.iter().enumerate().map(|(i, _)| format!("?{}", i + 2)).collect();
     let query = format!(
         "SELECT uye_id, 
-                COALESCE(SUM(tutar), 0.0) as toplam_borc, 
-                COALESCE(SUM(odenen), 0.0) as odenen
+                COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) / 100.0 as toplam_borc, 
+                COALESCE(SUM(CAST(ROUND(odenen * 100) AS INTEGER)), 0) / 100.0 as odenen
          FROM aidat_takip 
          WHERE tenant_id = ?1 AND uye_id IN ({})
          GROUP BY uye_id",
@@ -1782,8 +1886,8 @@ pub async fn get_uye_borc_durumlari(
     for uye_id in &uye_ids {
         let row = diesel::sql_query(
             "SELECT uye_id,
-                    COALESCE(SUM(tutar), 0.0) as toplam_borc,
-                    COALESCE(SUM(odenen), 0.0) as odenen
+                    COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) / 100.0 as toplam_borc,
+                    COALESCE(SUM(CAST(ROUND(odenen * 100) AS INTEGER)), 0) / 100.0 as odenen
              FROM aidat_takip
              WHERE tenant_id = ?1 AND uye_id = ?2
                AND (is_deleted IS NULL OR is_deleted = 0)
@@ -1806,9 +1910,9 @@ pub async fn get_uye_borc_durumlari(
             // Aidat kaydı olmayan üyeler için 0 borç
             results.push(UyeBorcDurumu {
                 uye_id: uye_id.clone(),
-                toplam_borc: 0.0,
-                odenen: 0.0,
-                kalan_borc: 0.0,
+                toplam_borc: Para::SIFIR,
+                odenen: Para::SIFIR,
+                kalan_borc: Para::SIFIR,
             });
         }
     }
@@ -1820,64 +1924,20 @@ pub async fn get_uye_borc_durumlari(
 // HELPER: Aidat tutar hesaplama
 // ============================================================================
 
-/// Üye için aidat tutarını hesapla (tanım + üye özel durumu)
+/// Üye için aidat tutarını hesapla (özel tutar, üye türü tanımı ve fiyat kuralları)
 pub fn calculate_uye_aidat_tutari(
     conn: &mut diesel::sqlite::SqliteConnection,
     tenant_id: &str,
     uye_id: &str,
     yil: i32,
-) -> Result<f64, diesel::result::Error> {
-    use crate::db::schema::uyeler_basic;
-
-    // 1. Üyeyi al
-    #[derive(QueryableByName)]
-    struct UyeTutarRow {
-        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        ozel_aidat_tutari: Option<f64>,
-        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        aidat_indirimi_yuzde: Option<f64>,
-    }
-
-    let uye = diesel::sql_query(
-        "SELECT ozel_aidat_tutari, aidat_indirimi_yuzde FROM uyeler WHERE id = ?1 AND tenant_id = ?2"
-    )
-    .bind::<diesel::sql_types::Text, _>(uye_id)
-    .bind::<diesel::sql_types::Text, _>(tenant_id)
-    .get_result::<UyeTutarRow>(conn)?;
-
-    // 2. Üyenin özel tutarı varsa onu kullan
-    if let Some(ozel_tutar) = uye.ozel_aidat_tutari {
-        if ozel_tutar > 0.0 {
-            return Ok(ozel_tutar);
-        }
-    }
-
-    // 3. Aidat tanımından genel tutarı al
-    #[derive(QueryableByName)]
-    struct TanimRow {
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        tutar: f64,
-    }
-
-    let tanim_tutar = diesel::sql_query(
-        "SELECT tutar FROM aidat_tanimlari WHERE tenant_id = ?1 AND yil = ?2 AND is_active = 1"
-    )
-    .bind::<diesel::sql_types::Text, _>(tenant_id)
-    .bind::<diesel::sql_types::Integer, _>(yil)
-    .get_result::<TanimRow>(conn)
-    .map(|r| r.tutar)
-    .unwrap_or(0.0);
-
-    // 4. İndirim varsa uygula
-    if let Some(indirim_yuzde) = uye.aidat_indirimi_yuzde {
-        if indirim_yuzde > 0.0 {
-            let indirim_carpani = 1.0 - (indirim_yuzde / 100.0);
-            return Ok(tanim_tutar * indirim_carpani);
-        }
-    }
+) -> Result<Para, String> {
+    let uye: crate::db::models::Uye = diesel::sql_query("SELECT * FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .map_err(|e| e.to_string())?;
 
-    // 5. Normal tutar
-    Ok(tanim_tutar)
+    Ok(FiyatMotoru::yukle(conn, tenant_id, yil, None)?.hesapla(&uye).tutar)
 }
 
 // ============================================================================
@@ -1889,11 +1949,11 @@ pub struct AidatBorcDetay {
     #[diesel(sql_type = diesel::sql_types::Integer)]
     pub yil: i32,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub tutar: f64,
+    pub tutar: Para,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub odenen: f64,
+    pub odenen: Para,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub kalan: f64,
+    pub kalan: Para,
 }
 
 /// Üyenin aidat borçlarını detaylı getir (yıl bazında)
@@ -1909,23 +1969,30 @@ pub async fn get_uye_aidat_borclari(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    let result = diesel::sql_query(
+    uye_aidat_borclari(&mut conn, &tenant_id_param, &uye_id).map_err(|e| e.to_string())
+}
+
+/// Üyenin kalan borcu olan aidatları, yıl bazında toplanmış (eskiden yeniye).
+/// Üye hesap ekstresi PDF'i de bunu kullanır.
+pub(crate) fn uye_aidat_borclari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: &str,
+) -> QueryResult<Vec<AidatBorcDetay>> {
+    diesel::sql_query(
         "SELECT yil,
-                SUM(tutar) as tutar,
-                SUM(odenen) as odenen,
-                SUM(kalan) as kalan
+                SUM(CAST(ROUND(tutar * 100) AS INTEGER)) / 100.0 as tutar,
+                SUM(CAST(ROUND(odenen * 100) AS INTEGER)) / 100.0 as odenen,
+                SUM(CAST(ROUND(kalan * 100) AS INTEGER)) / 100.0 as kalan
          FROM aidat_takip
          WHERE tenant_id = ?1 AND uye_id = ?2 AND kalan > 0
            AND (is_deleted IS NULL OR is_deleted = 0)
          GROUP BY yil
          ORDER BY yil ASC"
     )
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .bind::<diesel::sql_types::Text, _>(&uye_id)
-    .load::<AidatBorcDetay>(&mut conn)
-    .map_err(|e| e.to_string())?;
-
-    Ok(result)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .load::<AidatBorcDetay>(conn)
 }
 
 /// Seçili üyelere özel tutar ile borçlandırma
@@ -1935,7 +2002,7 @@ pub async fn ozel_tutar_borclandir(
     tenant_id_param: String,
     uye_ids: Vec<String>,
     yil: i32,
-    tutar: f64,
+    tutar: Para,
 ) -> Result<serde_json::Value, String> {
     use crate::db::schema::aidat_takip::dsl as aidat_dsl;
 
@@ -1945,9 +2012,12 @@ pub async fn ozel_tutar_borclandir(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    donem_kilidi::donemi_dogrula(&mut conn, &tenant_id_param, yil, 1)?;
+
     let mut olusturulan = 0;
     let mut zaten_var = 0;
 
+    let mut denetim = state.denetim("ozel_tutar_borclandir");
     for uye_id in uye_ids {
         // Zaten kayıt var mı kontrol et
         let mevcut = aidat_dsl::aidat_takip
@@ -1968,6 +2038,7 @@ pub async fn ozel_tutar_borclandir(
         let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
         conn.transaction::<_, TxError, _>(|conn| {
+            denetim.yeni_kayit(&tenant_id_param, "aidat_takip", &aidat_id);
             diesel::sql_query(
                 "INSERT INTO aidat_takip (
                     id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan,
@@ -1980,18 +2051,23 @@ pub async fn ozel_tutar_borclandir(
             .bind::<diesel::sql_types::Integer, _>(yil)
             .bind::<diesel::sql_types::Integer, _>(1)
             .bind::<diesel::sql_types::Double, _>(tutar)
-            .bind::<diesel::sql_types::Double, _>(0.0)
+            .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
             .bind::<diesel::sql_types::Double, _>(tutar)
             .bind::<diesel::sql_types::Text, _>("beklemede")
             .bind::<diesel::sql_types::Integer, _>(0)
-            .bind::<diesel::sql_types::Double, _>(0.0)
+            .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
             .bind::<diesel::sql_types::Text, _>(&now)
             .bind::<diesel::sql_types::Text, _>(&now)
             .execute(conn)?;
 
             outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "create")
                 .map_err(TxError::Msg)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+                .map_err(TxError::Msg)?;
+            avans::mahsup_et(conn, &mut denetim, &tenant_id_param, &uye_id, &bugun())
+                .map_err(TxError::Msg)?;
 
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
             Ok(())
         })
         .map_err(|e| e.to_string())?;
@@ -2003,7 +2079,7 @@ pub async fn ozel_tutar_borclandir(
         "success": true,
         "olusturulan_adet": olusturulan,
         "zaten_var": zaten_var,
-        "toplam_tutar": tutar * olusturulan as f64,
+        "toplam_tutar": Para::from_kurus(tutar.kurus() * olusturulan as i64),
         "mesaj": format!("{} üye için aidat borçlandırıldı", olusturulan)
     }))
 }
@@ -2015,7 +2091,7 @@ pub async fn coklu_donem_tahsilat(
     tenant_id_param: String,
     uye_id: String,
     yillar: Vec<i32>,
-    odeme_tutari: f64,
+    odeme_tutari: Para,
     odeme_tarihi: String,
     kasa_id: String,
 ) -> Result<serde_json::Value, String> {
@@ -2030,22 +2106,25 @@ pub async fn coklu_donem_tahsilat(
         #[diesel(sql_type = diesel::sql_types::Text)]
         id: String,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        tutar: f64,
+        tutar: Para,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        odenen: f64,
+        odenen: Para,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        kalan: f64,
+        kalan: Para,
         #[diesel(sql_type = diesel::sql_types::Integer)]
         version: i32,
     }
 
-    conn.transaction::<_, TxError, _>(|conn| {
+    let mut denetim = state.denetim("coklu_donem_tahsilat");
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let avansa_alinan = conn.transaction::<_, TxError, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &odeme_tarihi).map_err(TxError::Msg)?;
         let mut kalan_odeme = odeme_tutari;
         let mut odenen_yillar = Vec::new();
 
         // Yılları sırayla işle ve kalan borcu öde
         for yil in yillar {
-            if kalan_odeme <= 0.0 {
+            if !kalan_odeme.is_positive() {
                 break;
             }
 
@@ -2062,12 +2141,13 @@ pub async fn coklu_donem_tahsilat(
 
             if let Some(aidat_row) = aidat_opt {
                 let aidat_id = aidat_row.id;
+                denetim.izle(conn, &tenant_id_param, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
                 let odenen = aidat_row.odenen;
                 let kalan = aidat_row.kalan;
                 let odeme_miktari = kalan.min(kalan_odeme);
                 let yeni_odenen = odenen + odeme_miktari;
                 let yeni_kalan = kalan - odeme_miktari;
-                let yeni_durum = if yeni_kalan <= 0.01 { "odendi" } else { "kismi_odendi" };
+                let yeni_durum = if !yeni_kalan.is_positive() { "odendi" } else { "kismi_odendi" };
 
                 // Aidat kaydını güncelle (version check — concurrent ödeme yakala)
                 let affected = diesel::sql_query(
@@ -2091,6 +2171,8 @@ pub async fn coklu_donem_tahsilat(
 
                 outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "update")
                     .map_err(TxError::Msg)?;
+                yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+                    .map_err(TxError::Msg)?;
 
                 kalan_odeme -= odeme_miktari;
                 odenen_yillar.push(yil);
@@ -2099,37 +2181,43 @@ pub async fn coklu_donem_tahsilat(
 
         // Gelir kaydı oluştur
         let gelir_id = Uuid::new_v4().to_string();
+        denetim.yeni_kayit(&tenant_id_param, "gelirler", &gelir_id);
         diesel::sql_query(
-            "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar, aciklama, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, uye_id, tarih, tutar, aciklama, created_at, updated_at)
+             VALUES (?1, ?2, ?3, 'AİDAT', ?4, ?5, ?6, ?7, ?8, ?8)"
         )
         .bind::<diesel::sql_types::Text, _>(&gelir_id)
         .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
         .bind::<diesel::sql_types::Text, _>(&kasa_id)
+        .bind::<diesel::sql_types::Text, _>(&uye_id)
         .bind::<diesel::sql_types::Text, _>(&odeme_tarihi)
         .bind::<diesel::sql_types::Double, _>(odeme_tutari)
         .bind::<diesel::sql_types::Text, _>(&format!("Çoklu dönem aidat tahsilatı - Yıllar: {}", odenen_yillar.iter().map(|y| y.to_string()).collect::<Vec<_>>().join(", ")))
         .bind::<diesel::sql_types::Text, _>(&chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
         .execute(conn)?;
 
+        alindi_belgesi::gelire_makbuz_kes(conn, &tenant_id_param, kullanici_id.as_deref(), &gelir_id)
+            .map_err(TxError::Msg)?;
         outbox::queue_change(conn, &tenant_id_param, "gelirler", &gelir_id, "create")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &gelir_id)
+            .map_err(TxError::Msg)?;
 
-        // Kasa bakiyesini güncelle (türetilmiş alan — kasalar için outbox kaydı atılmaz)
-        diesel::sql_query(
-            "UPDATE kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3"
-        )
-        .bind::<diesel::sql_types::Double, _>(odeme_tutari)
-        .bind::<diesel::sql_types::Text, _>(&chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
-        .bind::<diesel::sql_types::Text, _>(&kasa_id)
-        .execute(conn)?;
+        // Seçilen yılların borcunu aşan kısım üye avansına alınır, varsa diğer açık borçlara mahsup edilir
+        avans::fazla_odemeyi_kaydet(conn, &mut denetim, &tenant_id_param, &uye_id, &odeme_tarihi, kalan_odeme, &gelir_id)
+            .map_err(TxError::Msg)?;
 
-        Ok(())
+        // Kasa bakiyesini yeniden hesapla (türetilmiş alan — kasalar için outbox kaydı atılmaz)
+        crate::commands::mali::update_kasa_bakiye(conn, &kasa_id)?;
+
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(kalan_odeme)
     }).map_err(|e| e.to_string())?;
 
     Ok(serde_json::json!({
         "success": true,
         "odenen_tutar": odeme_tutari,
+        "avansa_alinan": avansa_alinan,
         "mesaj": "Çoklu dönem tahsilatı başarıyla kaydedildi"
     }))
 }
@@ -2164,11 +2252,14 @@ pub async fn delete_aidat_borclandirma(
         .map_err(|e| format!("Aidat bulunamadı: {}", e))?;
 
     // Eğer ödeme yapılmışsa iptal edemeyiz
-    if aidat.odenen > 0.01 {
+    if aidat.odenen.is_positive() {
         return Err("Bu aidata ödeme yapılmış. Önce ödemeleri iptal etmelisiniz.".to_string());
     }
 
+    let mut denetim = state.denetim("delete_aidat_borclandirma");
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
+        donem_kilidi::kaydi_dogrula(conn, &tenant_id_param, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
         // SOFT DELETE: Aidat kaydını iptal et
         diesel::sql_query(
             "UPDATE aidat_takip SET durum = 'iptal', notlar = COALESCE(notlar, '') || ' [İPTAL: ' || ?1 || ']', updated_at = ?2 WHERE id = ?3"
@@ -2183,7 +2274,10 @@ pub async fn delete_aidat_borclandirma(
         // şekilde 'iptal' görünsün (tombstone'a dönüşüp kaybolmasın).
         outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &aidat_id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &aidat_id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     }).map_err(|e: TxError| e.to_string())?;
 
@@ -2275,9 +2369,14 @@ pub async fn toplu_aidat_iptal(
         id: String,
     }
 
+    let mut denetim = state.denetim("toplu_aidat_iptal");
     // Yazım + outbox kayıtları aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
         let etkilenen_ids: Vec<IdRow> = diesel::sql_query(&id_query).load(conn)?;
+        for row in &etkilenen_ids {
+            donem_kilidi::kaydi_dogrula(conn, &tenant_id_param, "aidat_takip", &row.id).map_err(TxError::Msg)?;
+            denetim.izle(conn, &tenant_id_param, "aidat_takip", &row.id).map_err(TxError::Msg)?;
+        }
 
         diesel::sql_query(&update_query).execute(conn)?;
 
@@ -2286,8 +2385,11 @@ pub async fn toplu_aidat_iptal(
         for row in &etkilenen_ids {
             outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &row.id, "update")
                 .map_err(TxError::Msg)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &row.id)
+                .map_err(TxError::Msg)?;
         }
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -2334,10 +2436,10 @@ pub struct AidatTutarlilikSorunu {
     pub aidat_id: String,
     pub uye_id: String,
     pub yil: i32,
-    pub tutar: f64,
-    pub kayitli_odenen: f64,
-    pub gelirlerden_hesaplanan: f64,
-    pub fark: f64,
+    pub tutar: Para,
+    pub kayitli_odenen: Para,
+    pub gelirlerden_hesaplanan: Para,
+    pub fark: Para,
     pub durum: String,
     pub onarildi: bool,
 }
@@ -2351,13 +2453,13 @@ struct TutarlilikRow {
     #[diesel(sql_type = diesel::sql_types::Integer)]
     yil: i32,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    tutar: f64,
+    tutar: Para,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    odenen: f64,
+    odenen: Para,
     #[diesel(sql_type = diesel::sql_types::Text)]
     durum: String,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    gelir_toplam: f64,
+    gelir_toplam: Para,
 }
 
 #[tauri::command]
@@ -2379,9 +2481,9 @@ pub async fn check_aidat_gelir_tutarliligi(
     // ama onarımda EZİLMEZ (gelir kaydı hiç yoksa dokunma).
     let rows: Vec<TutarlilikRow> = diesel::sql_query(
         "SELECT a.id, a.uye_id, a.yil, a.tutar, a.odenen, a.durum, \
-                COALESCE((SELECT SUM(g.tutar) FROM gelirler g \
+                COALESCE((SELECT SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)) FROM gelirler g \
                           WHERE g.aidat_id = a.id \
-                            AND (g.is_deleted IS NULL OR g.is_deleted = 0)), 0.0) AS gelir_toplam \
+                            AND (g.is_deleted IS NULL OR g.is_deleted = 0)), 0) / 100.0 AS gelir_toplam \
          FROM aidat_takip a \
          WHERE a.tenant_id = ?1 \
            AND (a.is_deleted IS NULL OR a.is_deleted = 0) \
@@ -2394,29 +2496,31 @@ pub async fn check_aidat_gelir_tutarliligi(
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
     let mut sorunlar = Vec::new();
 
+    let mut denetim = state.denetim("check_aidat_gelir_tutarliligi");
     for r in rows {
-        let fark = (r.odenen - r.gelir_toplam).abs();
-        if fark <= 0.01 {
+        let fark = r.odenen - r.gelir_toplam;
+        if fark.is_zero() {
             continue;
         }
 
         // Gelir kaydı hiç yoksa (gelir_toplam == 0, odenen > 0) manuel ödeme
         // olabilir — raporla ama onarma.
-        let gelir_kaydi_var = r.gelir_toplam > 0.01;
+        let gelir_kaydi_var = r.gelir_toplam.is_positive();
         let mut onarildi = false;
 
         if onar && gelir_kaydi_var {
             let yeni_odenen = r.gelir_toplam;
             let yeni_kalan = r.tutar - yeni_odenen;
-            let yeni_durum = if yeni_kalan <= 0.01 {
+            let yeni_durum = if !yeni_kalan.is_positive() {
                 "odendi"
-            } else if yeni_odenen > 0.01 {
+            } else if yeni_odenen.is_positive() {
                 "kismi_odendi"
             } else {
                 "odenmedi"
             };
 
             conn.transaction::<_, TxError, _>(|conn| {
+                denetim.izle(conn, &tenant_id_param, "aidat_takip", &r.id).map_err(TxError::Msg)?;
                 diesel::sql_query(
                     "UPDATE aidat_takip SET odenen = ?1, kalan = ?2, durum = ?3, updated_at = ?4 \
                      WHERE id = ?5 AND tenant_id = ?6",
@@ -2431,7 +2535,10 @@ pub async fn check_aidat_gelir_tutarliligi(
 
                 outbox::queue_change(conn, &tenant_id_param, "aidat_takip", &r.id, "update")
                     .map_err(TxError::Msg)?;
+                yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", &r.id)
+                    .map_err(TxError::Msg)?;
 
+                denetim.kaydet(conn).map_err(TxError::Msg)?;
                 Ok(())
             })
             .map_err(|e| e.to_string())?;
@@ -2446,7 +2553,7 @@ pub async fn check_aidat_gelir_tutarliligi(
             tutar: r.tutar,
             kayitli_odenen: r.odenen,
             gelirlerden_hesaplanan: r.gelir_toplam,
-            fark: r.odenen - r.gelir_toplam,
+            fark,
             durum: r.durum,
             onarildi,
         });
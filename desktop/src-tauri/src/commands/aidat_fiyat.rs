@@ -0,0 +1,201 @@
+// Aidat fiyat kuralları (bkz. db::aidat_fiyat)
+use tauri::State;
+use diesel::prelude::*;
+use chrono::Utc;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use crate::state::AppState;
+use crate::db::aidat_fiyat::{self, AidatFiyatKurali, AidatFiyati, FiyatMotoru};
+use crate::db::denetim;
+use crate::db::models::Uye;
+use crate::db::para::Para;
+
+#[derive(Debug, Deserialize)]
+pub struct AidatFiyatKuraliRequest {
+    pub ad: String,
+    pub sira: Option<i32>,
+    pub yil: Option<i32>,
+    pub uye_turu: Option<String>,
+    pub yas_min: Option<i32>,
+    pub yas_max: Option<i32>,
+    pub ozel_durum: Option<String>,
+    pub islem: String,
+    pub deger: f64,
+    pub durdur: Option<bool>,
+    pub aciklama: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UyeAidatFiyati {
+    pub uye_id: String,
+    pub uye_no: String,
+    pub ad_soyad: String,
+    pub uye_turu: String,
+    #[serde(flatten)]
+    pub fiyat: AidatFiyati,
+}
+
+fn bos_degil(s: Option<String>) -> Option<String> {
+    s.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+#[tauri::command]
+pub fn get_aidat_fiyat_kurallari(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<AidatFiyatKurali>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    aidat_fiyat::kurallari_getir(&mut conn, &tenant_id_param, None)
+}
+
+/// Kural ekler (kural_id yoksa) veya günceller; yalnızca yönetici
+#[tauri::command]
+pub fn aidat_fiyat_kurali_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    kural_id: Option<String>,
+    data: AidatFiyatKuraliRequest,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let mut kural = AidatFiyatKurali {
+        id: kural_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string()),
+        ad: data.ad.trim().to_string(),
+        sira: data.sira.unwrap_or(100),
+        yil: data.yil,
+        uye_turu: bos_degil(data.uye_turu),
+        yas_min: data.yas_min,
+        yas_max: data.yas_max,
+        ozel_durum: bos_degil(data.ozel_durum),
+        islem: data.islem.trim().to_string(),
+        deger: data.deger,
+        durdur: data.durdur.unwrap_or(false),
+        aciklama: bos_degil(data.aciklama),
+    };
+    aidat_fiyat::kural_dogrula(&mut kural)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let yeni = kural_id.is_none();
+    let etkilenen = denetim::kayitli_yazim(
+        &mut conn,
+        state.denetim("aidat_fiyat_kurali_kaydet"),
+        &tenant_id_param,
+        "aidat_fiyat_kurallari",
+        &kural.id,
+        |conn| {
+            let sql = if yeni {
+                "INSERT INTO aidat_fiyat_kurallari
+                    (id, tenant_id, ad, sira, yil, uye_turu, yas_min, yas_max, ozel_durum,
+                     islem, deger, durdur, aciklama, is_active, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 1, ?14, ?14)"
+            } else {
+                "UPDATE aidat_fiyat_kurallari
+                 SET ad = ?3, sira = ?4, yil = ?5, uye_turu = ?6, yas_min = ?7, yas_max = ?8,
+                     ozel_durum = ?9, islem = ?10, deger = ?11, durdur = ?12, aciklama = ?13, updated_at = ?14
+                 WHERE id = ?1 AND tenant_id = ?2 AND is_active = 1"
+            };
+            diesel::sql_query(sql)
+                .bind::<diesel::sql_types::Text, _>(&kural.id)
+                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+                .bind::<diesel::sql_types::Text, _>(&kural.ad)
+                .bind::<diesel::sql_types::Integer, _>(kural.sira)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(kural.yil)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kural.uye_turu)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(kural.yas_min)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(kural.yas_max)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kural.ozel_durum)
+                .bind::<diesel::sql_types::Text, _>(&kural.islem)
+                .bind::<diesel::sql_types::Double, _>(kural.deger)
+                .bind::<diesel::sql_types::Bool, _>(kural.durdur)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kural.aciklama)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .execute(conn)
+                .map_err(|e| e.to_string())
+        },
+    )?;
+
+    if etkilenen == 0 {
+        return Err("Kural bulunamadı".to_string());
+    }
+    Ok(kural.id)
+}
+
+#[tauri::command]
+pub fn aidat_fiyat_kurali_sil(
+    state: State<AppState>,
+    tenant_id_param: String,
+    kural_id: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim::kayitli_yazim(
+        &mut conn,
+        state.denetim("aidat_fiyat_kurali_sil"),
+        &tenant_id_param,
+        "aidat_fiyat_kurallari",
+        &kural_id,
+        |conn| {
+            diesel::sql_query(
+                "UPDATE aidat_fiyat_kurallari SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+            )
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&kural_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Tek üye için tutarı ve hangi kuralların uygulandığını gösterir
+#[tauri::command]
+pub fn aidat_fiyat_hesapla(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+    yil: i32,
+    varsayilan_tutar: Option<Para>,
+) -> Result<UyeAidatFiyati, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let uye: Uye = diesel::sql_query("SELECT * FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(&uye_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .get_result(&mut conn)
+        .map_err(|_| "Üye bulunamadı")?;
+
+    let motor = FiyatMotoru::yukle(&mut conn, &tenant_id_param, yil, varsayilan_tutar)?;
+    Ok(uye_fiyati(&motor, &uye))
+}
+
+pub(crate) fn uye_fiyati(motor: &FiyatMotoru, uye: &Uye) -> UyeAidatFiyati {
+    UyeAidatFiyati {
+        uye_id: uye.id.clone(),
+        uye_no: uye.uye_no.clone(),
+        ad_soyad: uye.ad_soyad.clone(),
+        uye_turu: uye.uyelik_tipi.clone().unwrap_or_else(|| "Asil".to_string()),
+        fiyat: motor.hesapla(uye),
+    }
+}
@@ -0,0 +1,150 @@
+// Zamanlanmış aidat tahakkuku (bkz. db::aidat_tahakkuku)
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+use chrono::Local;
+use crate::state::AppState;
+use crate::db::denetim;
+use crate::db::aidat_tahakkuku::{self, BekleyenDonem, TahakkukCalismasi, Tetikleyen};
+
+/// Uygulama açıldıktan sonra ilk kontrol (açılıştaki senkronizasyon vb. için pay)
+const ILK_GECIKME: Duration = Duration::from_secs(30);
+/// Sonraki kontroller; dönem başı gece yarısı gelse de en geç bu süre içinde işlenir
+const KONTROL_ARALIGI: Duration = Duration::from_secs(60 * 60);
+
+/// Arka plan zamanlayıcısı: her kontrolde otomatik tahakkuku açık tenant'ların bekleyen
+/// dönemlerini işler. Veritabanı kilidi tenant başına alınır; komutlar tenant'lar arasında
+/// çalışabilir, elle çalıştırmayla aynı tenant'ta çakışmaz.
+pub fn zamanlayiciyi_baslat(app: AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(ILK_GECIKME);
+        loop {
+            if let Err(e) = tum_tenantlari_isle(&app) {
+                eprintln!("❌ Aidat tahakkuku çalışmadı: {}", e);
+            }
+            std::thread::sleep(KONTROL_ARALIGI);
+        }
+    });
+}
+
+fn tum_tenantlari_isle(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let tenantlar = {
+        let db = state.db.lock().unwrap();
+        let pool = db.as_ref().ok_or("Database not initialized")?;
+        let mut conn = pool.get().map_err(|e| e.to_string())?;
+        aidat_tahakkuku::zamanlanmis_kiracilar(&mut conn)?
+    };
+
+    let bugun = Local::now().date_naive();
+    for tenant_id in tenantlar {
+        let sonuc = {
+            let db = state.db.lock().unwrap();
+            let pool = db.as_ref().ok_or("Database not initialized")?;
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            aidat_tahakkuku::calistir(&mut conn, &tenant_id, bugun, &Tetikleyen::zamanlayici())
+        };
+        match sonuc {
+            Ok(calismalar) => {
+                for c in calismalar {
+                    println!(
+                        "🗓️ Aidat tahakkuku {} {} {}: {} oluşturuldu, {} atlandı ({})",
+                        tenant_id, c.aidat_tipi, c.donem, c.olusturulan, c.atlanan, c.durum
+                    );
+                }
+            }
+            Err(e) => eprintln!("❌ Aidat tahakkuku ({}): {}", tenant_id, e),
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_aidat_tahakkuk_calismalari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    limit: Option<i64>,
+) -> Result<Vec<TahakkukCalismasi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    aidat_tahakkuku::calismalari_getir(&mut conn, &tenant_id_param, limit.unwrap_or(100))
+}
+
+/// Zamanlayıcı bir sonraki kontrolde hangi dönemleri işleyecek
+#[tauri::command]
+pub fn get_bekleyen_aidat_donemleri(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<BekleyenDonem>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    aidat_tahakkuku::bekleyenleri_getir(&mut conn, &tenant_id_param, Local::now().date_naive())
+}
+
+/// Bekleyen dönemleri beklemeden işler; yalnızca yönetici
+#[tauri::command]
+pub fn aidat_tahakkukunu_calistir(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<TahakkukCalismasi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let yonetici = state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    aidat_tahakkuku::calistir(
+        &mut conn,
+        &tenant_id_param,
+        Local::now().date_naive(),
+        &Tetikleyen::manuel(Some(yonetici.id), Some(yonetici.full_name)),
+    )
+}
+
+/// Zamanlayıcının bu tenant'ta otomatik tahakkuk yapıp yapmadığı
+#[tauri::command]
+pub fn get_otomatik_aidat_tahakkuku(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<bool, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    aidat_tahakkuku::otomatik_tahakkuk_acik_mi(&mut conn, &tenant_id_param)
+}
+
+/// Zamanlanmış tahakkuku açar/kapatır; yalnızca yönetici. Açıldığında cari dönemden başlar.
+#[tauri::command]
+pub fn set_otomatik_aidat_tahakkuku(
+    state: State<AppState>,
+    tenant_id_param: String,
+    acik: bool,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    denetim::kayitli_yazim(
+        &mut conn,
+        state.denetim("set_otomatik_aidat_tahakkuku"),
+        &tenant_id_param,
+        "tenants",
+        &tenant_id_param,
+        |conn| aidat_tahakkuku::otomatik_tahakkuk_ayarla(conn, &tenant_id_param, acik),
+    )
+}
@@ -3,6 +3,8 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
+use crate::db::aidat_tahakkuku::AidatTipi;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize, QueryableByName)]
@@ -25,6 +27,9 @@ pub struct AidatTanimi {
     pub son_odeme_gunu: Option<i32>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub aciklama: Option<String>,
+    /// Hane başına tek aidat tutarı (bkz. db::hane); boşsa hane üyeleri tek tek borçlandırılır
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub aile_tutari: Option<f64>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
     pub is_active: Option<i32>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
@@ -101,6 +106,7 @@ pub fn get_aidat_tanimi_by_yil_uye_turu(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn set_aidat_tanimi(
     state: State<AppState>,
     tenant_id_param: String,
@@ -109,9 +115,24 @@ pub fn set_aidat_tanimi(
     gecikme_faiz_orani: Option<f64>,
     aciklama: Option<String>,
     uye_turu: Option<String>,
+    aidat_tipi: Option<String>,
+    aile_tutari: Option<f64>,
 ) -> Result<String, String> {
+    // aile_tutari: verilmezse mevcut değer korunur, 0 verilirse kaldırılır
+    if aile_tutari.is_some_and(|t| t < 0.0) {
+        return Err("Aile tutarı negatif olamaz".to_string());
+    }
     let now = Utc::now().naive_utc().to_string();
     let uye_turu_val = uye_turu.unwrap_or_else(|| "Asil".to_string());
+    // Tahakkuk zamanlayıcısı dönemleri bu tipe göre açar (bkz. db::aidat_tahakkuku)
+    let aidat_tipi_val = match aidat_tipi.as_deref() {
+        Some(t) => Some(
+            AidatTipi::coz(t)
+                .ok_or_else(|| format!("Geçersiz aidat tipi: {} (Yıllık, Üç aylık veya Aylık)", t))?
+                .ad(),
+        ),
+        None => None,
+    };
 
     let db = state.db.lock().unwrap();
     let pool = db.as_ref().ok_or("Database not initialized")?;
@@ -129,16 +150,20 @@ pub fn set_aidat_tanimi(
     match existing {
         Ok(tanim) => {
             // Güncelle
-            diesel::sql_query(
-                "UPDATE aidat_tanimlari SET tutar = ?1, gecikme_faiz_orani = ?2, aciklama = ?3, is_active = 1, updated_at = ?4 WHERE id = ?5"
-            )
-            .bind::<diesel::sql_types::Double, _>(tutar)
-            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&gecikme_faiz_orani)
-            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&aciklama)
-            .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(&tanim.id)
-            .execute(&mut conn)
-            .map_err(|e| e.to_string())?;
+            denetim::kayitli_yazim(&mut conn, state.denetim("set_aidat_tanimi"), &tenant_id_param, "aidat_tanimlari", &tanim.id, |conn| {
+                diesel::sql_query(
+                    "UPDATE aidat_tanimlari SET tutar = ?1, gecikme_faiz_orani = ?2, aciklama = ?3, is_active = 1, updated_at = ?4, aidat_tipi = COALESCE(?6, aidat_tipi), aile_tutari = CASE WHEN ?7 IS NULL THEN aile_tutari ELSE NULLIF(?7, 0) END WHERE id = ?5"
+                )
+                .bind::<diesel::sql_types::Double, _>(tutar)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&gecikme_faiz_orani)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&aciklama)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&tanim.id)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aidat_tipi_val)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(aile_tutari)
+                .execute(conn)
+                .map_err(|e| e.to_string())
+            })?;
             
             Ok(tanim.id)
         }
@@ -146,20 +171,24 @@ pub fn set_aidat_tanimi(
             // Yeni kayıt oluştur
             let new_id = Uuid::new_v4().to_string();
             
-            diesel::sql_query(
-                "INSERT INTO aidat_tanimlari (id, tenant_id, yil, aidat_tipi, uye_turu, tutar, gecikme_faiz_orani, aciklama, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, 'Yıllık', ?4, ?5, ?6, ?7, 1, ?8, ?9)"
-            )
-            .bind::<diesel::sql_types::Text, _>(&new_id)
-            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-            .bind::<diesel::sql_types::Integer, _>(yil)
-            .bind::<diesel::sql_types::Text, _>(&uye_turu_val)
-            .bind::<diesel::sql_types::Double, _>(tutar)
-            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&gecikme_faiz_orani)
-            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&aciklama)
-            .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(&now)
-            .execute(&mut conn)
-            .map_err(|e| e.to_string())?;
+            denetim::kayitli_yazim(&mut conn, state.denetim("set_aidat_tanimi"), &tenant_id_param, "aidat_tanimlari", &new_id, |conn| {
+                diesel::sql_query(
+                    "INSERT INTO aidat_tanimlari (id, tenant_id, yil, aidat_tipi, uye_turu, tutar, gecikme_faiz_orani, aciklama, aile_tutari, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?10, ?4, ?5, ?6, ?7, NULLIF(?11, 0), 1, ?8, ?9)"
+                )
+                .bind::<diesel::sql_types::Text, _>(&new_id)
+                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+                .bind::<diesel::sql_types::Integer, _>(yil)
+                .bind::<diesel::sql_types::Text, _>(&uye_turu_val)
+                .bind::<diesel::sql_types::Double, _>(tutar)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&gecikme_faiz_orani)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&aciklama)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(aidat_tipi_val.unwrap_or("Yıllık"))
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(aile_tutari)
+                .execute(conn)
+                .map_err(|e| e.to_string())
+            })?;
             
             Ok(new_id)
         }
@@ -179,14 +208,16 @@ pub fn delete_aidat_tanimi(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE aidat_tanimlari SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&aidat_tanimi_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_aidat_tanimi"), &tenant_id_param, "aidat_tanimlari", &aidat_tanimi_id, |conn| {
+        diesel::sql_query(
+            "UPDATE aidat_tanimlari SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&aidat_tanimi_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
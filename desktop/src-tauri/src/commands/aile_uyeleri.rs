@@ -3,6 +3,7 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use crate::db::models::AileUyesi;
 use crate::db::schema::uye_aile_uyeleri;
 
@@ -76,26 +77,28 @@ pub fn create_aile_uyesi(
     let id = Uuid::new_v4().to_string();
     let sync_id = Uuid::new_v4().to_string();
 
-    diesel::insert_into(uye_aile_uyeleri::table)
-        .values((
-            uye_aile_uyeleri::id.eq(&id),
-            uye_aile_uyeleri::tenant_id.eq(&tenant_id_param),
-            uye_aile_uyeleri::uye_id.eq(&request.uye_id),
-            uye_aile_uyeleri::yakinlik.eq(&request.yakinlik),
-            uye_aile_uyeleri::ad_soyad.eq(&request.ad_soyad),
-            uye_aile_uyeleri::dogum_tarihi.eq(&request.dogum_tarihi),
-            uye_aile_uyeleri::telefon.eq(&request.telefon),
-            uye_aile_uyeleri::meslek.eq(&request.meslek),
-            uye_aile_uyeleri::egitim_durumu.eq(&request.egitim_durumu),
-            uye_aile_uyeleri::notlar.eq(&request.notlar),
-            uye_aile_uyeleri::sync_id.eq(&sync_id),
-            uye_aile_uyeleri::version.eq(1),
-            uye_aile_uyeleri::is_deleted.eq(0),
-            uye_aile_uyeleri::created_at.eq(&now),
-            uye_aile_uyeleri::updated_at.eq(&now),
-        ))
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("create_aile_uyesi"), &tenant_id_param, "uye_aile_uyeleri", &id, |conn| {
+        diesel::insert_into(uye_aile_uyeleri::table)
+            .values((
+                uye_aile_uyeleri::id.eq(&id),
+                uye_aile_uyeleri::tenant_id.eq(&tenant_id_param),
+                uye_aile_uyeleri::uye_id.eq(&request.uye_id),
+                uye_aile_uyeleri::yakinlik.eq(&request.yakinlik),
+                uye_aile_uyeleri::ad_soyad.eq(&request.ad_soyad),
+                uye_aile_uyeleri::dogum_tarihi.eq(&request.dogum_tarihi),
+                uye_aile_uyeleri::telefon.eq(&request.telefon),
+                uye_aile_uyeleri::meslek.eq(&request.meslek),
+                uye_aile_uyeleri::egitim_durumu.eq(&request.egitim_durumu),
+                uye_aile_uyeleri::notlar.eq(&request.notlar),
+                uye_aile_uyeleri::sync_id.eq(&sync_id),
+                uye_aile_uyeleri::version.eq(1),
+                uye_aile_uyeleri::is_deleted.eq(0),
+                uye_aile_uyeleri::created_at.eq(&now),
+                uye_aile_uyeleri::updated_at.eq(&now),
+            ))
+            .execute(conn)
+            .map_err(|e| e.to_string())
+    })?;
 
     let created = uye_aile_uyeleri::table
         .find(&id)
@@ -125,20 +128,22 @@ pub fn update_aile_uyesi(
         .first::<AileUyesi>(&mut conn)
         .map_err(|e| e.to_string())?;
 
-    diesel::update(uye_aile_uyeleri::table.find(&id))
-        .set((
-            uye_aile_uyeleri::yakinlik.eq(&request.yakinlik),
-            uye_aile_uyeleri::ad_soyad.eq(&request.ad_soyad.unwrap_or(current.ad_soyad)),
-            uye_aile_uyeleri::dogum_tarihi.eq(&request.dogum_tarihi),
-            uye_aile_uyeleri::telefon.eq(&request.telefon),
-            uye_aile_uyeleri::meslek.eq(&request.meslek),
-            uye_aile_uyeleri::egitim_durumu.eq(&request.egitim_durumu),
-            uye_aile_uyeleri::notlar.eq(&request.notlar),
-            uye_aile_uyeleri::version.eq(current.version + 1),
-            uye_aile_uyeleri::updated_at.eq(&now),
-        ))
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("update_aile_uyesi"), &tenant_id_param, "uye_aile_uyeleri", &id, |conn| {
+        diesel::update(uye_aile_uyeleri::table.find(&id))
+            .set((
+                uye_aile_uyeleri::yakinlik.eq(&request.yakinlik),
+                uye_aile_uyeleri::ad_soyad.eq(&request.ad_soyad.unwrap_or(current.ad_soyad)),
+                uye_aile_uyeleri::dogum_tarihi.eq(&request.dogum_tarihi),
+                uye_aile_uyeleri::telefon.eq(&request.telefon),
+                uye_aile_uyeleri::meslek.eq(&request.meslek),
+                uye_aile_uyeleri::egitim_durumu.eq(&request.egitim_durumu),
+                uye_aile_uyeleri::notlar.eq(&request.notlar),
+                uye_aile_uyeleri::version.eq(current.version + 1),
+                uye_aile_uyeleri::updated_at.eq(&now),
+            ))
+            .execute(conn)
+            .map_err(|e| e.to_string())
+    })?;
 
     let updated = uye_aile_uyeleri::table
         .find(&id)
@@ -168,14 +173,16 @@ pub fn delete_aile_uyesi(
         .map_err(|e| e.to_string())?;
 
     // Soft delete
-    diesel::update(uye_aile_uyeleri::table.find(&id))
-        .set((
-            uye_aile_uyeleri::is_deleted.eq(1),
-            uye_aile_uyeleri::version.eq(current.version + 1),
-            uye_aile_uyeleri::updated_at.eq(&now),
-        ))
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_aile_uyesi"), &tenant_id_param, "uye_aile_uyeleri", &id, |conn| {
+        diesel::update(uye_aile_uyeleri::table.find(&id))
+            .set((
+                uye_aile_uyeleri::is_deleted.eq(1),
+                uye_aile_uyeleri::version.eq(current.version + 1),
+                uye_aile_uyeleri::updated_at.eq(&now),
+            ))
+            .execute(conn)
+            .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -0,0 +1,584 @@
+// Alındı belgesi cilt yönetimi komutları
+// Ciltlerin kaydı, görevliye teslimi ve yetki belgesi; kesilmemiş numaraların
+// iptali; boşluk/mükerrer/iptal kontrolü ve alındı belgesi kayıt defteri.
+// Numara verme db::alindi_belgesi'ndedir (create_gelir ve aidat tahsilatları).
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::db::alindi_belgesi;
+use crate::db::denetim;
+use crate::db::para::Para;
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct AlindiBelgesiCildi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub seri: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub cilt_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub baslangic_no: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub bitis_no: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub matbaa_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub matbaa_fatura_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub matbaa_fatura_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub teslim_alan: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub teslim_alan_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub teslim_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub yetki_belgesi_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub yetki_baslangic: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub yetki_bitis: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub iade_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub son_no: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub kullanilan_sayisi: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub iptal_sayisi: i64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub tahsil_edilen: Para,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlindiBelgesiCildiRequest {
+    #[serde(default)]
+    pub seri: String,
+    pub cilt_no: Option<String>,
+    pub baslangic_no: i64,
+    pub bitis_no: i64,
+    pub matbaa_adi: Option<String>,
+    pub matbaa_fatura_no: Option<String>,
+    pub matbaa_fatura_tarihi: Option<String>,
+    pub teslim_alan: Option<String>,
+    pub teslim_tarihi: Option<String>,
+    pub yetki_belgesi_no: Option<String>,
+    pub yetki_baslangic: Option<String>,
+    pub yetki_bitis: Option<String>,
+    pub iade_tarihi: Option<String>,
+    /// aktif | iade | kapali (eklemede verilmezse aktif)
+    pub durum: Option<String>,
+    pub aciklama: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NumaraBoslugu {
+    pub cilt_id: String,
+    pub seri: String,
+    pub cilt_no: Option<String>,
+    pub baslangic_no: i64,
+    pub bitis_no: i64,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct MukerrerMakbuz {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub makbuz_no: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub adet: i64,
+    /// Virgülle ayrılmış gelir id'leri
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub gelir_idleri: String,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct MakbuzKaydi {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub cilt_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub makbuz_no: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gelir_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub tutar: Option<Para>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+}
+
+/// Kontrol raporu:
+/// - bosluklar: ciltte son verilen numaraya kadar hiç kaydı olmayan numaralar
+/// - mukerrerler: aynı makbuz_no ile birden fazla (silinmemiş) gelir
+/// - iptaller: iptal edilen numaralar ve gerekçeleri
+/// - silinmis_gelire_bagli: kesilmiş ama geliri silinmiş numaralar (iptal edilmeli)
+/// - kayitsiz: cilt kullanımı başladıktan sonra ciltlerde karşılığı olmayan makbuz no'lu gelirler
+#[derive(Debug, Serialize)]
+pub struct AlindiBelgesiKontrolu {
+    pub bosluklar: Vec<NumaraBoslugu>,
+    pub mukerrerler: Vec<MukerrerMakbuz>,
+    pub iptaller: Vec<MakbuzKaydi>,
+    pub silinmis_gelire_bagli: Vec<MakbuzKaydi>,
+    pub kayitsiz: Vec<MakbuzKaydi>,
+}
+
+const CILT_SORGUSU: &str = "SELECT c.id, c.seri, c.cilt_no, c.baslangic_no, c.bitis_no, c.matbaa_adi,
+        c.matbaa_fatura_no, c.matbaa_fatura_tarihi, c.teslim_alan, u.full_name as teslim_alan_adi,
+        c.teslim_tarihi, c.yetki_belgesi_no, c.yetki_baslangic, c.yetki_bitis, c.iade_tarihi,
+        c.durum, c.aciklama,
+        (SELECT MAX(k.sira_no) FROM alindi_belgesi_kullanimlari k WHERE k.cilt_id = c.id) as son_no,
+        (SELECT COUNT(*) FROM alindi_belgesi_kullanimlari k WHERE k.cilt_id = c.id AND k.durum = 'kullanildi') as kullanilan_sayisi,
+        (SELECT COUNT(*) FROM alindi_belgesi_kullanimlari k WHERE k.cilt_id = c.id AND k.durum = 'iptal') as iptal_sayisi,
+        (SELECT COALESCE(SUM(g.tutar), 0) FROM alindi_belgesi_kullanimlari k
+          JOIN gelirler g ON g.id = k.gelir_id AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+          WHERE k.cilt_id = c.id AND k.durum = 'kullanildi') as tahsil_edilen
+     FROM alindi_belgesi_ciltleri c
+     LEFT JOIN users u ON u.id = c.teslim_alan";
+
+fn ciltleri_getir(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<AlindiBelgesiCildi>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE c.tenant_id = ?1 ORDER BY c.seri, c.baslangic_no",
+        CILT_SORGUSU
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+fn cilt_dogrula(conn: &mut SqliteConnection, tenant_id: &str, data: &AlindiBelgesiCildiRequest, cilt_id: Option<&str>) -> Result<(), String> {
+    if data.baslangic_no <= 0 || data.bitis_no < data.baslangic_no {
+        return Err("Sıra numarası aralığı geçersiz".to_string());
+    }
+    if let Some(d) = data.durum.as_deref() {
+        if !["aktif", "iade", "kapali"].contains(&d) {
+            return Err(format!("Geçersiz cilt durumu: {}", d));
+        }
+    }
+    if let (Some(b), Some(s)) = (&data.yetki_baslangic, &data.yetki_bitis) {
+        if s < b {
+            return Err("Yetki belgesi bitiş tarihi başlangıçtan önce olamaz".to_string());
+        }
+    }
+    if alindi_belgesi::aralik_cakisiyor(conn, tenant_id, &data.seri, data.baslangic_no, data.bitis_no, cilt_id)
+        .map_err(|e| e.to_string())?
+    {
+        return Err(format!(
+            "{} serisinde {}-{} aralığı kayıtlı başka bir cilde çakışıyor",
+            if data.seri.trim().is_empty() { "Serisiz ciltler" } else { data.seri.trim() },
+            data.baslangic_no,
+            data.bitis_no
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_alindi_belgesi_ciltleri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<AlindiBelgesiCildi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ciltleri_getir(&mut conn, &tenant_id_param)
+}
+
+/// Matbaadan gelen cildi kaydeder (yalnızca yönetici)
+#[tauri::command]
+pub async fn alindi_belgesi_cildi_ekle(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    data: AlindiBelgesiCildiRequest,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    cilt_dogrula(&mut conn, &tenant_id_param, &data, None)?;
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim::kayitli_yazim(&mut conn, state.denetim("alindi_belgesi_cildi_ekle"), &tenant_id_param, "alindi_belgesi_ciltleri", &id, |conn| {
+        diesel::sql_query(
+            "INSERT INTO alindi_belgesi_ciltleri
+                (id, tenant_id, seri, cilt_no, baslangic_no, bitis_no, matbaa_adi, matbaa_fatura_no,
+                 matbaa_fatura_tarihi, teslim_alan, teslim_tarihi, yetki_belgesi_no, yetki_baslangic,
+                 yetki_bitis, iade_tarihi, durum, aciklama, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?18)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Text, _>(data.seri.trim())
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.cilt_no)
+        .bind::<diesel::sql_types::BigInt, _>(data.baslangic_no)
+        .bind::<diesel::sql_types::BigInt, _>(data.bitis_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.matbaa_adi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.matbaa_fatura_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.matbaa_fatura_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.teslim_alan)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.teslim_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetki_belgesi_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetki_baslangic)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetki_bitis)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.iade_tarihi)
+        .bind::<diesel::sql_types::Text, _>(data.durum.as_deref().unwrap_or("aktif"))
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
+
+    Ok(id)
+}
+
+/// Teslim, yetki belgesi, iade ve durum bilgilerini günceller (yalnızca yönetici).
+/// Numara kesilmiş ciltte aralık, kesilen numaraları dışarıda bırakacak şekilde daraltılamaz.
+#[tauri::command]
+pub async fn alindi_belgesi_cildi_guncelle(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    cilt_id: String,
+    data: AlindiBelgesiCildiRequest,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    cilt_dogrula(&mut conn, &tenant_id_param, &data, Some(&cilt_id))?;
+
+    #[derive(QueryableByName)]
+    struct Aralik {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        seri: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+        en_kucuk: Option<i64>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+        en_buyuk: Option<i64>,
+    }
+    let mevcut: Aralik = diesel::sql_query(
+        "SELECT c.seri,
+                (SELECT MIN(sira_no) FROM alindi_belgesi_kullanimlari WHERE cilt_id = c.id) as en_kucuk,
+                (SELECT MAX(sira_no) FROM alindi_belgesi_kullanimlari WHERE cilt_id = c.id) as en_buyuk
+         FROM alindi_belgesi_ciltleri c WHERE c.id = ?1 AND c.tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(&cilt_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Alındı belgesi cildi bulunamadı")?;
+
+    if let (Some(min), Some(max)) = (mevcut.en_kucuk, mevcut.en_buyuk) {
+        if mevcut.seri != data.seri.trim() || data.baslangic_no > min || data.bitis_no < max {
+            return Err(format!(
+                "Bu ciltte {}-{} arası numaralar kullanılmış; seri ve aralık bunları kapsamalıdır",
+                min, max
+            ));
+        }
+    }
+
+    denetim::kayitli_yazim(&mut conn, state.denetim("alindi_belgesi_cildi_guncelle"), &tenant_id_param, "alindi_belgesi_ciltleri", &cilt_id, |conn| {
+        diesel::sql_query(
+            "UPDATE alindi_belgesi_ciltleri
+             SET seri = ?1, cilt_no = ?2, baslangic_no = ?3, bitis_no = ?4, matbaa_adi = ?5, matbaa_fatura_no = ?6,
+                 matbaa_fatura_tarihi = ?7, teslim_alan = ?8, teslim_tarihi = ?9, yetki_belgesi_no = ?10,
+                 yetki_baslangic = ?11, yetki_bitis = ?12, iade_tarihi = ?13, durum = COALESCE(?14, durum),
+                 aciklama = ?15, updated_at = ?16
+             WHERE id = ?17 AND tenant_id = ?18",
+        )
+        .bind::<diesel::sql_types::Text, _>(data.seri.trim())
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.cilt_no)
+        .bind::<diesel::sql_types::BigInt, _>(data.baslangic_no)
+        .bind::<diesel::sql_types::BigInt, _>(data.bitis_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.matbaa_adi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.matbaa_fatura_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.matbaa_fatura_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.teslim_alan)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.teslim_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetki_belgesi_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetki_baslangic)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetki_bitis)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.iade_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.durum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+        .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(&cilt_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    })
+}
+
+/// Numarayı iptal eder (yırtılan/hatalı doldurulan belge). Numara kesilmemişse
+/// iptal kaydı eklenir; kesilmişse ancak bağlı gelir silinmişse iptal edilebilir.
+/// Cildin sahibi veya yönetici yapabilir; gerekçe zorunludur.
+#[tauri::command]
+pub async fn alindi_belgesi_iptal(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    cilt_id: String,
+    sira_no: i64,
+    neden: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let kullanici = state
+        .current_user
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or("Unauthorized: No active session. Please login first.")?;
+
+    let neden = neden.trim();
+    if neden.is_empty() {
+        return Err("İptal gerekçesi yazılmalıdır".to_string());
+    }
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    #[derive(QueryableByName)]
+    struct Cilt {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        seri: String,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        baslangic_no: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        bitis_no: i64,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        teslim_alan: Option<String>,
+    }
+    let cilt: Cilt = diesel::sql_query(
+        "SELECT seri, baslangic_no, bitis_no, teslim_alan FROM alindi_belgesi_ciltleri WHERE id = ?1 AND tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(&cilt_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Alındı belgesi cildi bulunamadı")?;
+
+    if cilt.teslim_alan.as_deref() != Some(kullanici.id.as_str()) {
+        state.verify_admin()?;
+    }
+    if sira_no < cilt.baslangic_no || sira_no > cilt.bitis_no {
+        return Err(format!(
+            "{} numarası bu cildin aralığında değil ({}-{})",
+            sira_no, cilt.baslangic_no, cilt.bitis_no
+        ));
+    }
+
+    #[derive(QueryableByName)]
+    struct Kullanim {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        durum: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        gelir_aktif: i32,
+    }
+    let mevcut: Option<Kullanim> = diesel::sql_query(
+        "SELECT k.id, k.durum,
+                CASE WHEN g.id IS NOT NULL AND (g.is_deleted IS NULL OR g.is_deleted = 0) THEN 1 ELSE 0 END as gelir_aktif
+         FROM alindi_belgesi_kullanimlari k
+         LEFT JOIN gelirler g ON g.id = k.gelir_id
+         WHERE k.cilt_id = ?1 AND k.sira_no = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(&cilt_id)
+    .bind::<diesel::sql_types::BigInt, _>(sira_no)
+    .get_result(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let kullanim_id = match &mevcut {
+        Some(k) if k.durum == "iptal" => return Err("Bu numara zaten iptal edilmiş".to_string()),
+        Some(k) if k.gelir_aktif == 1 => {
+            return Err("Bu numarayla kesilmiş bir gelir kaydı var; önce gelir kaydı silinmelidir".to_string())
+        }
+        Some(k) => k.id.clone(),
+        None => Uuid::new_v4().to_string(),
+    };
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim::kayitli_yazim(&mut conn, state.denetim("alindi_belgesi_iptal"), &tenant_id_param, "alindi_belgesi_kullanimlari", &kullanim_id, |conn| {
+        if mevcut.is_some() {
+            diesel::sql_query(
+                "UPDATE alindi_belgesi_kullanimlari SET durum = 'iptal', iptal_nedeni = ?1, updated_at = ?2 WHERE id = ?3",
+            )
+            .bind::<diesel::sql_types::Text, _>(neden)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&kullanim_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        } else {
+            diesel::sql_query(
+                "INSERT INTO alindi_belgesi_kullanimlari
+                    (id, tenant_id, cilt_id, sira_no, makbuz_no, durum, tarih, kullanici_id, iptal_nedeni, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'iptal', ?6, ?7, ?8, ?9, ?9)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&kullanim_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Text, _>(&cilt_id)
+            .bind::<diesel::sql_types::BigInt, _>(sira_no)
+            .bind::<diesel::sql_types::Text, _>(alindi_belgesi::makbuz_no_bicimle(&cilt.seri, sira_no))
+            .bind::<diesel::sql_types::Text, _>(&now[..10])
+            .bind::<diesel::sql_types::Text, _>(&kullanici.id)
+            .bind::<diesel::sql_types::Text, _>(neden)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn get_alindi_belgesi_kontrolu(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+) -> Result<AlindiBelgesiKontrolu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    #[derive(QueryableByName)]
+    struct SiraNo {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sira_no: i64,
+    }
+    let mut bosluklar = Vec::new();
+    for cilt in ciltleri_getir(&mut conn, &tenant_id_param)? {
+        let numaralar: Vec<i64> = diesel::sql_query(
+            "SELECT sira_no FROM alindi_belgesi_kullanimlari WHERE cilt_id = ?1 ORDER BY sira_no",
+        )
+        .bind::<diesel::sql_types::Text, _>(&cilt.id)
+        .load::<SiraNo>(&mut conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|s| s.sira_no)
+        .collect();
+        for (bas, bit) in alindi_belgesi::bosluklari_bul(cilt.baslangic_no, &numaralar) {
+            bosluklar.push(NumaraBoslugu {
+                cilt_id: cilt.id.clone(),
+                seri: cilt.seri.clone(),
+                cilt_no: cilt.cilt_no.clone(),
+                baslangic_no: bas,
+                bitis_no: bit,
+            });
+        }
+    }
+
+    let mukerrerler = diesel::sql_query(
+        "SELECT makbuz_no, COUNT(*) as adet, GROUP_CONCAT(id) as gelir_idleri
+         FROM gelirler
+         WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)
+           AND makbuz_no IS NOT NULL AND TRIM(makbuz_no) != ''
+         GROUP BY makbuz_no HAVING COUNT(*) > 1
+         ORDER BY makbuz_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    let iptaller = diesel::sql_query(
+        "SELECT k.cilt_id, k.makbuz_no, k.tarih, k.gelir_id, g.tutar, k.iptal_nedeni as aciklama
+         FROM alindi_belgesi_kullanimlari k
+         LEFT JOIN gelirler g ON g.id = k.gelir_id
+         WHERE k.tenant_id = ?1 AND k.durum = 'iptal'
+         ORDER BY k.makbuz_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    let silinmis_gelire_bagli = diesel::sql_query(
+        "SELECT k.cilt_id, k.makbuz_no, k.tarih, k.gelir_id, g.tutar, g.aciklama
+         FROM alindi_belgesi_kullanimlari k
+         LEFT JOIN gelirler g ON g.id = k.gelir_id
+         WHERE k.tenant_id = ?1 AND k.durum = 'kullanildi'
+           AND (g.id IS NULL OR g.is_deleted = 1)
+         ORDER BY k.makbuz_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    let kayitsiz = diesel::sql_query(
+        "SELECT NULL as cilt_id, g.makbuz_no, g.tarih, g.id as gelir_id, g.tutar, g.aciklama
+         FROM gelirler g
+         WHERE g.tenant_id = ?1 AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+           AND g.makbuz_no IS NOT NULL AND TRIM(g.makbuz_no) != ''
+           AND g.tarih >= (SELECT MIN(COALESCE(teslim_tarihi, substr(created_at, 1, 10)))
+                           FROM alindi_belgesi_ciltleri WHERE tenant_id = ?1)
+           AND NOT EXISTS (SELECT 1 FROM alindi_belgesi_kullanimlari k
+                           WHERE k.tenant_id = g.tenant_id AND k.makbuz_no = g.makbuz_no AND k.durum = 'kullanildi')
+         ORDER BY g.tarih, g.makbuz_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(AlindiBelgesiKontrolu {
+        bosluklar,
+        mukerrerler,
+        iptaller,
+        silinmis_gelire_bagli,
+        kayitsiz,
+    })
+}
+
+/// Alındı Belgesi Kayıt Defteri satırları: her cilt bir satır, kayıt (eklenme) sırasıyla.
+/// yil verilirse o yıl teslim edilen (teslim tarihi yoksa kaydedilen) ciltler.
+pub(crate) fn kayit_defteri_hazirla(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: Option<i32>,
+) -> Result<Vec<AlindiBelgesiCildi>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE c.tenant_id = ?1
+           AND (?2 IS NULL OR substr(COALESCE(c.teslim_tarihi, c.created_at), 1, 4) = CAST(?2 AS TEXT))
+         ORDER BY c.created_at, c.seri, c.baslangic_no",
+        CILT_SORGUSU
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(yil)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_alindi_belgesi_kayit_defteri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    yil: Option<i32>,
+) -> Result<Vec<AlindiBelgesiCildi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    kayit_defteri_hazirla(&mut conn, &tenant_id_param, yil)
+}
@@ -0,0 +1,219 @@
+// Üye avansı: bakiye/hareketler, peşin tahsilat ve gider kaydıyla iade (bkz. db::avans)
+use tauri::State;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use crate::state::AppState;
+use crate::commands::mali::{self, outbox_err, CreateGelirRequest, CreateGiderRequest};
+use crate::db::alindi_belgesi;
+use crate::db::avans::{self, AvansHareketi, UyeAvansBakiyesi};
+use crate::db::para::Para;
+
+#[derive(Debug, Serialize)]
+pub struct UyeAvansOzeti {
+    pub uye_id: String,
+    pub bakiye: Para,
+    pub hareketler: Vec<AvansHareketi>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvansTahsilatRequest {
+    pub kasa_id: String,
+    pub tarih: String,
+    pub tutar: Para,
+    pub gelir_turu_id: Option<String>,
+    pub tahsil_eden: Option<String>,
+    pub makbuz_no: Option<String>,
+    pub aciklama: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvansIadeRequest {
+    pub kasa_id: String,
+    pub tarih: String,
+    pub tutar: Para,
+    pub gider_turu_id: Option<String>,
+    pub islem_no: Option<String>,
+    pub aciklama: Option<String>,
+}
+
+fn bos_degil(s: Option<String>) -> Option<String> {
+    s.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn ozet(conn: &mut SqliteConnection, tenant_id: &str, uye_id: &str) -> Result<UyeAvansOzeti, String> {
+    Ok(UyeAvansOzeti {
+        uye_id: uye_id.to_string(),
+        bakiye: avans::bakiye(conn, tenant_id, uye_id)?,
+        hareketler: avans::hareketleri_getir(conn, tenant_id, uye_id)?,
+    })
+}
+
+#[tauri::command]
+pub fn get_uye_avans(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+) -> Result<UyeAvansOzeti, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ozet(&mut conn, &tenant_id_param, &uye_id)
+}
+
+#[tauri::command]
+pub fn get_avans_bakiyeleri(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<UyeAvansBakiyesi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    avans::bakiyeleri_getir(&mut conn, &tenant_id_param)
+}
+
+/// Borca bağlanmamış peşin ödeme: gelir kaydı avans girişi olur ve açık aidatlara hemen mahsup edilir
+#[tauri::command]
+pub fn uye_avans_tahsil_et(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+    data: AvansTahsilatRequest,
+) -> Result<UyeAvansOzeti, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    if !data.tutar.is_positive() {
+        return Err("Avans tutarı sıfırdan büyük olmalıdır".to_string());
+    }
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let gelir_id = Uuid::new_v4().to_string();
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let mut denetim = state.denetim("uye_avans_tahsil_et");
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        // Avans girişi gelirden önce yazılır: gelirin yevmiye fişi 127'yi buradan bulur
+        avans::hareket_ekle(
+            conn,
+            &mut denetim,
+            &tenant_id_param,
+            &uye_id,
+            &data.tarih,
+            "giris",
+            data.tutar,
+            (None, Some(&gelir_id), None),
+            "Peşin ödeme",
+        )
+        .map_err(outbox_err)?;
+
+        denetim.yeni_kayit(&tenant_id_param, "gelirler", &gelir_id);
+        mali::gelir_ekle(conn, &tenant_id_param, &gelir_id, &CreateGelirRequest {
+            kasa_id: data.kasa_id.clone(),
+            gelir_turu_id: data.gelir_turu_id.clone(),
+            tarih: data.tarih.clone(),
+            tutar: data.tutar,
+            aciklama: Some(bos_degil(data.aciklama.clone()).unwrap_or_else(|| "Üye avans tahsilatı".to_string())),
+            makbuz_no: bos_degil(data.makbuz_no.clone()),
+            alt_kategori: Some("Avans".to_string()),
+            tahakkuk_durumu: None,
+            belge_no: None,
+            tahsil_eden: bos_degil(data.tahsil_eden.clone()),
+            belge_id: None,
+            uye_id: Some(uye_id.clone()),
+            aidat_id: None,
+            ait_oldugu_yil: None,
+            etkinlik_id: None,
+        })?;
+        alindi_belgesi::gelire_makbuz_kes(conn, &tenant_id_param, kullanici_id.as_deref(), &gelir_id)
+            .map_err(outbox_err)?;
+        mali::update_kasa_bakiye(conn, &data.kasa_id)?;
+
+        avans::mahsup_et(conn, &mut denetim, &tenant_id_param, &uye_id, &data.tarih).map_err(outbox_err)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    ozet(&mut conn, &tenant_id_param, &uye_id)
+}
+
+/// Avans bakiyesini üyeye geri öder: gider kaydı 127'yi borçlandırır (gider hesabına yazılmaz)
+#[tauri::command]
+pub fn uye_avans_iade_et(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+    data: AvansIadeRequest,
+) -> Result<UyeAvansOzeti, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let kullanici = state.verify_admin()?;
+    if !data.tutar.is_positive() {
+        return Err("İade tutarı sıfırdan büyük olmalıdır".to_string());
+    }
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let gider_id = Uuid::new_v4().to_string();
+    let mut denetim = state.denetim("uye_avans_iade_et");
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let bakiye = avans::bakiye(conn, &tenant_id_param, &uye_id).map_err(outbox_err)?;
+        if data.tutar > bakiye {
+            return Err(outbox_err(format!(
+                "İade tutarı avans bakiyesini aşıyor (bakiye: {})",
+                bakiye.tr_format()
+            )));
+        }
+
+        // İade hareketi giderden önce yazılır: giderin yevmiye fişi 127'yi buradan bulur
+        avans::hareket_ekle(
+            conn,
+            &mut denetim,
+            &tenant_id_param,
+            &uye_id,
+            &data.tarih,
+            "iade",
+            -data.tutar,
+            (None, None, Some(&gider_id)),
+            "Avans iadesi",
+        )
+        .map_err(outbox_err)?;
+
+        denetim.yeni_kayit(&tenant_id_param, "giderler", &gider_id);
+        mali::gider_ekle(conn, &tenant_id_param, &gider_id, &CreateGiderRequest {
+            kasa_id: data.kasa_id.clone(),
+            gider_turu_id: data.gider_turu_id.clone(),
+            tarih: data.tarih.clone(),
+            tutar: data.tutar,
+            aciklama: Some(bos_degil(data.aciklama.clone()).unwrap_or_else(|| "Üye avans iadesi".to_string())),
+            fatura_no: None,
+            alt_kategori: Some("Avans İadesi".to_string()),
+            islem_no: bos_degil(data.islem_no.clone()),
+            odeyen: Some(kullanici.full_name.clone()),
+            notlar: None,
+            belge_id: None,
+            uye_id: Some(uye_id.clone()),
+            demirbas_id: None,
+            etkinlik_id: None,
+        })?;
+        mali::update_kasa_bakiye(conn, &data.kasa_id)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    ozet(&mut conn, &tenant_id_param, &uye_id)
+}
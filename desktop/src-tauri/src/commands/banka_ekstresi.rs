@@ -0,0 +1,878 @@
+// Banka ekstresi aktarım komutları
+// Akış: banka_ekstresi_yukle (oku + eşleşme öner) → satır satır inceleme
+// (banka_ekstre_satiri_karar) → banka_ekstresi_uygula (eşleşmeleri onayla,
+// eşleşmeyenleri gelir/gider olarak oluştur). Okuma ve eşleştirme kuralları
+// db::banka_ekstresi'ndedir.
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::commands::mali::{self, outbox_err, CreateGelirRequest, CreateGiderRequest};
+use crate::db::banka_ekstresi::{self as ekstre, CsvEslestirme, EkstreHareketi, EslesmeAdayi};
+use crate::db::denetim;
+use crate::db::outbox::TxError;
+use crate::db::para::Para;
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct BankaEkstreAktarimi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kasa_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kasa_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub dosya_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub bicim: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub hesap: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub donem_baslangic: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub donem_bitis: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub acilis_bakiyesi: Option<Para>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub kapanis_bakiyesi: Option<Para>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub satir_sayisi: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub tarih_toleransi: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uygulama_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub oneri_sayisi: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub yeni_sayisi: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub mukerrer_sayisi: i64,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct BankaEkstreSatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub sira: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub valor_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub dekont_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karsi_taraf: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karsi_iban: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub eslesen_tablo: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub eslesen_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub eslesme_puani: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub eslesen_tarih: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub eslesen_aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub tur_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub olusan_tablo: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub olusan_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BankaAktarimDetayi {
+    pub aktarim: BankaEkstreAktarimi,
+    pub satirlar: Vec<BankaEkstreSatiri>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EslesmeOnerisi {
+    pub aday: EslesmeAdayi,
+    /// Tarih toleransı dışındaysa None (yine de elle seçilebilir)
+    pub puan: Option<i32>,
+}
+
+/// İnceleme kararı: durum 'eslesti' (eslesen_tablo + eslesen_id ile),
+/// 'yeni' (tur_id: gelir_turu_id / gider_turu_id, isteğe bağlı uye_id) veya 'atlandi'
+#[derive(Debug, Deserialize)]
+pub struct SatirKarari {
+    pub durum: String,
+    pub eslesen_tablo: Option<String>,
+    pub eslesen_id: Option<String>,
+    pub tur_id: Option<String>,
+    pub uye_id: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BankaUygulamaSonucu {
+    pub eslesen: usize,
+    pub olusturulan_gelir: usize,
+    pub olusturulan_gider: usize,
+    pub atlanan: usize,
+    pub mukerrer: usize,
+}
+
+const AKTARIM_SORGUSU: &str = "SELECT a.id, a.kasa_id, k.kasa_adi, a.dosya_adi, a.bicim, a.hesap,
+        a.donem_baslangic, a.donem_bitis, a.acilis_bakiyesi, a.kapanis_bakiyesi, a.satir_sayisi,
+        a.tarih_toleransi, a.durum, a.uygulama_tarihi, a.created_at,
+        (SELECT COUNT(*) FROM banka_ekstre_satirlari s WHERE s.aktarim_id = a.id AND s.durum = 'oneri') as oneri_sayisi,
+        (SELECT COUNT(*) FROM banka_ekstre_satirlari s WHERE s.aktarim_id = a.id AND s.durum = 'yeni') as yeni_sayisi,
+        (SELECT COUNT(*) FROM banka_ekstre_satirlari s WHERE s.aktarim_id = a.id AND s.durum = 'mukerrer') as mukerrer_sayisi
+     FROM banka_ekstre_aktarimlari a
+     LEFT JOIN kasalar k ON k.id = a.kasa_id";
+
+fn aktarim_getir(conn: &mut SqliteConnection, tenant_id: &str, aktarim_id: &str) -> Result<BankaEkstreAktarimi, String> {
+    diesel::sql_query(format!("{} WHERE a.id = ?1 AND a.tenant_id = ?2", AKTARIM_SORGUSU))
+        .bind::<diesel::sql_types::Text, _>(aktarim_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Ekstre aktarımı bulunamadı".to_string())
+}
+
+fn aktarim_detayi(conn: &mut SqliteConnection, tenant_id: &str, aktarim_id: &str) -> Result<BankaAktarimDetayi, String> {
+    let aktarim = aktarim_getir(conn, tenant_id, aktarim_id)?;
+    let satirlar = diesel::sql_query(
+        "SELECT s.id, s.sira, s.tarih, s.valor_tarihi, s.tutar, s.aciklama, s.dekont_no, s.karsi_taraf,
+                s.karsi_iban, s.durum, s.eslesen_tablo, s.eslesen_id, s.eslesme_puani,
+                COALESCE(g.tarih, d.tarih) as eslesen_tarih,
+                COALESCE(g.aciklama, d.aciklama) as eslesen_aciklama,
+                s.tur_id, s.uye_id, s.olusan_tablo, s.olusan_id
+         FROM banka_ekstre_satirlari s
+         LEFT JOIN gelirler g ON s.eslesen_tablo = 'gelirler' AND g.id = s.eslesen_id
+         LEFT JOIN giderler d ON s.eslesen_tablo = 'giderler' AND d.id = s.eslesen_id
+         WHERE s.aktarim_id = ?1 AND s.tenant_id = ?2
+         ORDER BY s.sira",
+    )
+    .bind::<diesel::sql_types::Text, _>(aktarim_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(BankaAktarimDetayi { aktarim, satirlar })
+}
+
+#[derive(QueryableByName)]
+struct AdaySatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tablo: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    aidat_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    belge1: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    belge2: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    belge3: Option<String>,
+}
+
+/// Kasadaki, tarih aralığına düşen ve başka bir ekstre satırına bağlanmamış
+/// gelir/gider kayıtları (aidat tahsilatı gelirlerinde aidat dekont_no'su da belge sayılır).
+/// Gider tutarları ekstreyle aynı işaret kuralı için eksiye çevrilir.
+fn eslesme_adaylari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kasa_id: &str,
+    baslangic: &str,
+    bitis: &str,
+    haric_aktarim_id: Option<&str>,
+) -> Result<Vec<EslesmeAdayi>, String> {
+    let satirlar: Vec<AdaySatiri> = diesel::sql_query(
+        "WITH bagli AS (
+            SELECT s.eslesen_id FROM banka_ekstre_satirlari s
+            JOIN banka_ekstre_aktarimlari a ON a.id = s.aktarim_id
+            WHERE s.tenant_id = ?1 AND s.eslesen_id IS NOT NULL
+              AND s.durum IN ('oneri', 'eslesti') AND a.durum != 'iptal'
+              AND (?5 IS NULL OR s.aktarim_id != ?5)
+         )
+         SELECT 'gelirler' as tablo, g.id, g.tarih, g.tutar, g.aciklama, g.aidat_id,
+                g.makbuz_no as belge1, g.belge_no as belge2, at.dekont_no as belge3
+         FROM gelirler g
+         LEFT JOIN aidat_takip at ON at.id = g.aidat_id
+         WHERE g.tenant_id = ?1 AND g.kasa_id = ?2 AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+           AND g.tarih >= ?3 AND substr(g.tarih, 1, 10) <= ?4
+           AND g.id NOT IN (SELECT eslesen_id FROM bagli)
+         UNION ALL
+         SELECT 'giderler', d.id, d.tarih, -d.tutar, d.aciklama, NULL,
+                d.fatura_no, d.islem_no, NULL
+         FROM giderler d
+         WHERE d.tenant_id = ?1 AND d.kasa_id = ?2 AND (d.is_deleted IS NULL OR d.is_deleted = 0)
+           AND d.tarih >= ?3 AND substr(d.tarih, 1, 10) <= ?4
+           AND d.id NOT IN (SELECT eslesen_id FROM bagli)
+         ORDER BY 3, 2",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .bind::<diesel::sql_types::Text, _>(baslangic)
+    .bind::<diesel::sql_types::Text, _>(bitis)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(haric_aktarim_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(satirlar
+        .into_iter()
+        .map(|s| EslesmeAdayi {
+            tablo: s.tablo,
+            id: s.id,
+            tarih: s.tarih.chars().take(10).collect(),
+            tutar: s.tutar,
+            belge_nolari: [s.belge1, s.belge2, s.belge3].into_iter().flatten().filter(|b| !b.trim().is_empty()).collect(),
+            aciklama: s.aciklama,
+            aidat_id: s.aidat_id,
+        })
+        .collect())
+}
+
+fn gun_kaydir(tarih: &str, gun: i64) -> String {
+    chrono::NaiveDate::parse_from_str(tarih, "%Y-%m-%d")
+        .map(|d| (d + chrono::Duration::days(gun)).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| tarih.to_string())
+}
+
+/// Ekstre dosyasını okur, aktarımı ve satırlarını kaydeder, eşleşme önerir.
+/// bicim verilmezse içerikten tahmin edilir ("csv" | "camt053" | "mt940").
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn banka_ekstresi_yukle(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kasa_id: String,
+    dosya_adi: Option<String>,
+    icerik: String,
+    bicim: Option<String>,
+    csv_eslestirme: Option<CsvEslestirme>,
+    tarih_toleransi: Option<i64>,
+) -> Result<BankaAktarimDetayi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let bicim = bicim.unwrap_or_else(|| ekstre::bicim_tahmin_et(&icerik).to_string());
+    let okunan = ekstre::ekstre_oku(&bicim, &icerik, csv_eslestirme.as_ref())?;
+    if okunan.hareketler.is_empty() {
+        return Err("Ekstrede aktarılacak hareket bulunamadı".to_string());
+    }
+    let tolerans = tarih_toleransi.unwrap_or(ekstre::VARSAYILAN_TARIH_TOLERANSI).clamp(0, 30);
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    #[derive(QueryableByName)]
+    struct KasaTipi {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        kasa_tipi: Option<String>,
+    }
+    let kasa: Option<KasaTipi> = diesel::sql_query(
+        "SELECT kasa_tipi FROM kasalar WHERE id = ?1 AND tenant_id = ?2 AND is_active = 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(&kasa_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+    match kasa {
+        None => return Err("Kasa bulunamadı".to_string()),
+        Some(k) if k.kasa_tipi.as_deref() != Some("Banka") => {
+            return Err("Ekstre yalnızca banka tipindeki kasaya aktarılabilir".to_string())
+        }
+        Some(_) => {}
+    }
+
+    let donem_baslangic = okunan.hareketler.iter().map(|h| h.tarih.clone()).min().unwrap_or_default();
+    let donem_bitis = okunan.hareketler.iter().map(|h| h.tarih.clone()).max().unwrap_or_default();
+    let aktarim_id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let eslestirme_json = csv_eslestirme
+        .as_ref()
+        .map(|e| serde_json::to_string(e).unwrap_or_default());
+
+    conn.transaction::<_, TxError, _>(|conn| {
+        diesel::sql_query(
+            "INSERT INTO banka_ekstre_aktarimlari
+                (id, tenant_id, kasa_id, dosya_adi, bicim, hesap, donem_baslangic, donem_bitis,
+                 acilis_bakiyesi, kapanis_bakiyesi, satir_sayisi, csv_eslestirme, tarih_toleransi,
+                 durum, kullanici_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 'inceleniyor', ?14, ?15, ?15)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&aktarim_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Text, _>(&kasa_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&dosya_adi)
+        .bind::<diesel::sql_types::Text, _>(&bicim)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&okunan.hesap)
+        .bind::<diesel::sql_types::Text, _>(&donem_baslangic)
+        .bind::<diesel::sql_types::Text, _>(&donem_bitis)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(okunan.acilis_bakiyesi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(okunan.kapanis_bakiyesi)
+        .bind::<diesel::sql_types::Integer, _>(okunan.hareketler.len() as i32)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&eslestirme_json)
+        .bind::<diesel::sql_types::Integer, _>(tolerans as i32)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kullanici_id)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)?;
+
+        let adaylar = eslesme_adaylari(
+            conn,
+            &tenant_id_param,
+            &kasa_id,
+            &gun_kaydir(&donem_baslangic, -ekstre::DEKONT_TARIH_TOLERANSI),
+            &gun_kaydir(&donem_bitis, ekstre::DEKONT_TARIH_TOLERANSI),
+            None,
+        )
+        .map_err(TxError::Msg)?;
+        let eslesmeler = ekstre::eslestir(&okunan.hareketler, &adaylar, tolerans);
+
+        for (h, eslesme) in okunan.hareketler.iter().zip(eslesmeler) {
+            let parmak_izi = ekstre::parmak_izi(h);
+            let mukerrer = diesel::sql_query(
+                "SELECT COUNT(*) as count FROM banka_ekstre_satirlari s
+                 JOIN banka_ekstre_aktarimlari a ON a.id = s.aktarim_id
+                 WHERE s.tenant_id = ?1 AND s.parmak_izi = ?2 AND a.kasa_id = ?3
+                   AND a.durum != 'iptal' AND a.id != ?4 AND s.durum NOT IN ('atlandi', 'mukerrer')",
+            )
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Text, _>(&parmak_izi)
+            .bind::<diesel::sql_types::Text, _>(&kasa_id)
+            .bind::<diesel::sql_types::Text, _>(&aktarim_id)
+            .get_result::<CountRow>(conn)?
+            .count;
+
+            let (durum, aday) = match eslesme {
+                _ if mukerrer > 0 => ("mukerrer", None),
+                Some(e) => ("oneri", Some((&adaylar[e.aday], e.puan))),
+                None => ("yeni", None),
+            };
+            satir_ekle(conn, &tenant_id_param, &aktarim_id, h, &parmak_izi, durum, aday, &now)?;
+        }
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    aktarim_detayi(&mut conn, &tenant_id_param, &aktarim_id)
+}
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    count: i64,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn satir_ekle(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    aktarim_id: &str,
+    h: &EkstreHareketi,
+    parmak_izi: &str,
+    durum: &str,
+    aday: Option<(&EslesmeAdayi, i32)>,
+    now: &str,
+) -> Result<(), diesel::result::Error> {
+    diesel::sql_query(
+        "INSERT INTO banka_ekstre_satirlari
+            (id, tenant_id, aktarim_id, sira, tarih, valor_tarihi, tutar, aciklama, dekont_no,
+             karsi_taraf, karsi_iban, parmak_izi, durum, eslesen_tablo, eslesen_id, eslesme_puani,
+             created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?17)",
+    )
+    .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(aktarim_id)
+    .bind::<diesel::sql_types::Integer, _>(h.sira as i32)
+    .bind::<diesel::sql_types::Text, _>(&h.tarih)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&h.valor_tarihi)
+    .bind::<diesel::sql_types::Double, _>(h.tutar)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&h.aciklama)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&h.dekont_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&h.karsi_taraf)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&h.karsi_iban)
+    .bind::<diesel::sql_types::Text, _>(parmak_izi)
+    .bind::<diesel::sql_types::Text, _>(durum)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aday.map(|(a, _)| a.tablo.as_str()))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aday.map(|(a, _)| a.id.as_str()))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(aday.map(|(_, p)| p))
+    .bind::<diesel::sql_types::Text, _>(now)
+    .execute(conn)?;
+    Ok(())
+}
+
+/// Aktarımlar, en yeni önce (kasa verilirse yalnızca o kasanınkiler)
+#[tauri::command]
+pub async fn get_banka_ekstre_aktarimlari(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kasa_id: Option<String>,
+) -> Result<Vec<BankaEkstreAktarimi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    diesel::sql_query(format!(
+        "{} WHERE a.tenant_id = ?1 AND (?2 IS NULL OR a.kasa_id = ?2) ORDER BY a.created_at DESC",
+        AKTARIM_SORGUSU
+    ))
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kasa_id)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Aktarım ve satırları (eşleşen kaydın tarih/açıklamasıyla)
+#[tauri::command]
+pub async fn get_banka_ekstre_aktarimi(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    aktarim_id: String,
+) -> Result<BankaAktarimDetayi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    aktarim_detayi(&mut conn, &tenant_id_param, &aktarim_id)
+}
+
+/// Bu kasaya son CSV aktarımında kullanılan sütun eşleştirmesi (formu doldurmak için)
+#[tauri::command]
+pub async fn get_son_csv_eslestirmesi(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kasa_id: String,
+) -> Result<Option<CsvEslestirme>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    #[derive(QueryableByName)]
+    struct Eslestirme {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        csv_eslestirme: String,
+    }
+    let son: Option<Eslestirme> = diesel::sql_query(
+        "SELECT csv_eslestirme FROM banka_ekstre_aktarimlari
+         WHERE tenant_id = ?1 AND kasa_id = ?2 AND csv_eslestirme IS NOT NULL
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Text, _>(&kasa_id)
+    .get_result(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    Ok(son.and_then(|s| serde_json::from_str(&s.csv_eslestirme).ok()))
+}
+
+#[derive(QueryableByName)]
+struct SatirBaglami {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    aktarim_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    kasa_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    aktarim_durumu: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    tarih_toleransi: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    dekont_no: Option<String>,
+}
+
+fn satir_baglami(conn: &mut SqliteConnection, tenant_id: &str, satir_id: &str) -> Result<SatirBaglami, String> {
+    diesel::sql_query(
+        "SELECT s.aktarim_id, a.kasa_id, a.durum as aktarim_durumu, a.tarih_toleransi,
+                s.tarih, s.tutar, s.dekont_no
+         FROM banka_ekstre_satirlari s
+         JOIN banka_ekstre_aktarimlari a ON a.id = s.aktarim_id
+         WHERE s.id = ?1 AND s.tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(satir_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Ekstre satırı bulunamadı".to_string())
+}
+
+impl SatirBaglami {
+    fn hareket(&self) -> EkstreHareketi {
+        EkstreHareketi {
+            sira: 0,
+            tarih: self.tarih.clone(),
+            valor_tarihi: None,
+            tutar: self.tutar,
+            aciklama: None,
+            dekont_no: self.dekont_no.clone(),
+            karsi_taraf: None,
+            karsi_iban: None,
+        }
+    }
+}
+
+/// Satır için elle seçilebilecek adaylar: aynı tutarlı, en fazla 30 gün uzaktaki
+/// ve başka satıra bağlanmamış kayıtlar, puana göre
+#[tauri::command]
+pub async fn get_banka_ekstre_satiri_adaylari(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    satir_id: String,
+) -> Result<Vec<EslesmeOnerisi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let satir = satir_baglami(&mut conn, &tenant_id_param, &satir_id)?;
+    let hareket = satir.hareket();
+    let adaylar = eslesme_adaylari(
+        &mut conn,
+        &tenant_id_param,
+        &satir.kasa_id,
+        &gun_kaydir(&satir.tarih, -ekstre::DEKONT_TARIH_TOLERANSI),
+        &gun_kaydir(&satir.tarih, ekstre::DEKONT_TARIH_TOLERANSI),
+        Some(&satir.aktarim_id),
+    )?;
+
+    let mut oneriler: Vec<EslesmeOnerisi> = adaylar
+        .into_iter()
+        .filter(|a| a.tutar == satir.tutar)
+        .map(|a| {
+            let puan = ekstre::eslesme_puani(&hareket, &a, satir.tarih_toleransi as i64);
+            EslesmeOnerisi { aday: a, puan }
+        })
+        .collect();
+    oneriler.sort_by(|a, b| b.puan.cmp(&a.puan).then(a.aday.tarih.cmp(&b.aday.tarih)));
+    Ok(oneriler)
+}
+
+/// İnceleme kararını satıra yazar (aktarım uygulanmadan önce)
+#[tauri::command]
+pub async fn banka_ekstre_satiri_karar(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    satir_id: String,
+    karar: SatirKarari,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let satir = satir_baglami(&mut conn, &tenant_id_param, &satir_id)?;
+    if satir.aktarim_durumu != "inceleniyor" {
+        return Err("Uygulanmış veya iptal edilmiş aktarımın satırı değiştirilemez".to_string());
+    }
+
+    let (eslesen_tablo, eslesen_id, puan) = match karar.durum.as_str() {
+        "eslesti" => {
+            let tablo = karar.eslesen_tablo.as_deref().ok_or("Eşleşen kayıt seçilmelidir")?;
+            let id = karar.eslesen_id.as_deref().ok_or("Eşleşen kayıt seçilmelidir")?;
+            let adaylar = eslesme_adaylari(
+                &mut conn,
+                &tenant_id_param,
+                &satir.kasa_id,
+                "0000-00-00",
+                "9999-12-31",
+                Some(&satir.aktarim_id),
+            )?;
+            let aday = adaylar
+                .iter()
+                .find(|a| a.tablo == tablo && a.id == id)
+                .ok_or("Seçilen kayıt bu kasada yok veya başka bir ekstre satırına bağlı")?;
+            if aday.tutar != satir.tutar {
+                return Err(format!(
+                    "Tutarlar tutmuyor: ekstre {} ₺, kayıt {} ₺",
+                    satir.tutar.tr_format(),
+                    aday.tutar.tr_format()
+                ));
+            }
+            let puan = ekstre::eslesme_puani(&satir.hareket(), aday, ekstre::DEKONT_TARIH_TOLERANSI * 12);
+            (Some(tablo.to_string()), Some(id.to_string()), puan)
+        }
+        "yeni" | "atlandi" => (None, None, None),
+        d => return Err(format!("Geçersiz karar: {}", d)),
+    };
+
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut denetim = state.denetim("banka_ekstre_satiri_karar");
+    conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "banka_ekstre_satirlari", &satir_id).map_err(TxError::Msg)?;
+
+        // Aynı kayıt bu aktarımda başka satıra önerilmişse o satır yeniye döner
+        if let Some(id) = &eslesen_id {
+            let digerleri: Vec<IdRow> = diesel::sql_query(
+                "SELECT id FROM banka_ekstre_satirlari
+                 WHERE aktarim_id = ?1 AND eslesen_id = ?2 AND id != ?3 AND durum IN ('oneri', 'eslesti')",
+            )
+            .bind::<diesel::sql_types::Text, _>(&satir.aktarim_id)
+            .bind::<diesel::sql_types::Text, _>(id)
+            .bind::<diesel::sql_types::Text, _>(&satir_id)
+            .load(conn)?;
+            for d in &digerleri {
+                denetim.izle(conn, &tenant_id_param, "banka_ekstre_satirlari", &d.id).map_err(TxError::Msg)?;
+                diesel::sql_query(
+                    "UPDATE banka_ekstre_satirlari
+                     SET durum = 'yeni', eslesen_tablo = NULL, eslesen_id = NULL, eslesme_puani = NULL, updated_at = ?1
+                     WHERE id = ?2",
+                )
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&d.id)
+                .execute(conn)?;
+            }
+        }
+
+        diesel::sql_query(
+            "UPDATE banka_ekstre_satirlari
+             SET durum = ?1, eslesen_tablo = ?2, eslesen_id = ?3, eslesme_puani = ?4,
+                 tur_id = ?5, uye_id = ?6, updated_at = ?7
+             WHERE id = ?8 AND tenant_id = ?9",
+        )
+        .bind::<diesel::sql_types::Text, _>(&karar.durum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&eslesen_tablo)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&eslesen_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(puan)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&karar.tur_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&karar.uye_id)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&satir_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)?;
+
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[derive(QueryableByName)]
+struct UygulanacakSatir {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    dekont_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    karsi_taraf: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    tur_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    uye_id: Option<String>,
+}
+
+/// İncelenmiş aktarımı uygular (tek transaction):
+/// - 'oneri' ve 'eslesti' satırlar eşleşmiş sayılır, kayıtlara dokunulmaz
+/// - 'yeni' satırlar gelir (giriş) veya gider (çıkış) olarak oluşturulur;
+///   tür satırda seçilmemişse varsayılan tür kullanılır, dekont no gelirde
+///   belge_no'ya, giderde islem_no'ya yazılır
+/// - 'mukerrer' ve 'atlandi' satırlar olduğu gibi kalır
+#[tauri::command]
+pub async fn banka_ekstresi_uygula(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    aktarim_id: String,
+    varsayilan_gelir_turu_id: Option<String>,
+    varsayilan_gider_turu_id: Option<String>,
+) -> Result<BankaUygulamaSonucu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let aktarim = aktarim_getir(&mut conn, &tenant_id_param, &aktarim_id)?;
+    if aktarim.durum != "inceleniyor" {
+        return Err("Aktarım zaten uygulanmış veya iptal edilmiş".to_string());
+    }
+
+    let satirlar: Vec<UygulanacakSatir> = diesel::sql_query(
+        "SELECT id, tarih, tutar, aciklama, dekont_no, karsi_taraf, durum, tur_id, uye_id
+         FROM banka_ekstre_satirlari WHERE aktarim_id = ?1 AND tenant_id = ?2 ORDER BY sira",
+    )
+    .bind::<diesel::sql_types::Text, _>(&aktarim_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    let mut sonuc = BankaUygulamaSonucu::default();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut denetim = state.denetim("banka_ekstresi_uygula");
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        for s in &satirlar {
+            let (yeni_durum, olusan) = match s.durum.as_str() {
+                "oneri" | "eslesti" => {
+                    sonuc.eslesen += 1;
+                    ("eslesti", None)
+                }
+                "yeni" => {
+                    let yeni_id = Uuid::new_v4().to_string();
+                    denetim.yeni_kayit(&tenant_id_param, if s.tutar.is_positive() { "gelirler" } else { "giderler" }, &yeni_id);
+                    let aciklama = match (&s.karsi_taraf, &s.aciklama) {
+                        (Some(k), Some(a)) => Some(format!("{} - {}", k, a)),
+                        (k, a) => a.clone().or_else(|| k.clone()),
+                    };
+                    let tablo = if s.tutar.is_positive() {
+                        mali::gelir_ekle(conn, &tenant_id_param, &yeni_id, &CreateGelirRequest {
+                            kasa_id: aktarim.kasa_id.clone(),
+                            gelir_turu_id: s.tur_id.clone().or_else(|| varsayilan_gelir_turu_id.clone()),
+                            tarih: s.tarih.clone(),
+                            tutar: s.tutar,
+                            aciklama,
+                            makbuz_no: None,
+                            alt_kategori: None,
+                            tahakkuk_durumu: None,
+                            belge_no: s.dekont_no.clone(),
+                            tahsil_eden: None,
+                            belge_id: None,
+                            uye_id: s.uye_id.clone(),
+                            aidat_id: None,
+                            ait_oldugu_yil: None,
+                            etkinlik_id: None,
+                        })?;
+                        sonuc.olusturulan_gelir += 1;
+                        "gelirler"
+                    } else {
+                        mali::gider_ekle(conn, &tenant_id_param, &yeni_id, &CreateGiderRequest {
+                            kasa_id: aktarim.kasa_id.clone(),
+                            gider_turu_id: s.tur_id.clone().or_else(|| varsayilan_gider_turu_id.clone()),
+                            tarih: s.tarih.clone(),
+                            tutar: s.tutar.abs(),
+                            aciklama,
+                            fatura_no: None,
+                            alt_kategori: None,
+                            islem_no: s.dekont_no.clone(),
+                            odeyen: None,
+                            notlar: None,
+                            belge_id: None,
+                            uye_id: s.uye_id.clone(),
+                            demirbas_id: None,
+                            etkinlik_id: None,
+                        })?;
+                        sonuc.olusturulan_gider += 1;
+                        "giderler"
+                    };
+                    ("olusturuldu", Some((tablo, yeni_id)))
+                }
+                "mukerrer" => {
+                    sonuc.mukerrer += 1;
+                    continue;
+                }
+                _ => {
+                    sonuc.atlanan += 1;
+                    continue;
+                }
+            };
+
+            diesel::sql_query(
+                "UPDATE banka_ekstre_satirlari SET durum = ?1, olusan_tablo = ?2, olusan_id = ?3, updated_at = ?4
+                 WHERE id = ?5",
+            )
+            .bind::<diesel::sql_types::Text, _>(yeni_durum)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(olusan.as_ref().map(|(t, _)| *t))
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(olusan.as_ref().map(|(_, id)| id.as_str()))
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&s.id)
+            .execute(conn)?;
+        }
+
+        if sonuc.olusturulan_gelir + sonuc.olusturulan_gider > 0 {
+            mali::update_kasa_bakiye(conn, &aktarim.kasa_id)?;
+        }
+
+        diesel::sql_query(
+            "UPDATE banka_ekstre_aktarimlari SET durum = 'tamamlandi', uygulama_tarihi = ?1, updated_at = ?1
+             WHERE id = ?2",
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&aktarim_id)
+        .execute(conn)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
+        Ok(())
+    })
+    .map_err(|e: diesel::result::Error| e.to_string())?;
+
+    Ok(sonuc)
+}
+
+/// İncelenmekte olan aktarımı iptal eder; satırları mükerrer kontrolünde
+/// ve eşleşme adaylarında artık sayılmaz
+#[tauri::command]
+pub async fn banka_ekstresi_iptal(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    aktarim_id: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    denetim::kayitli_yazim(&mut conn, state.denetim("banka_ekstresi_iptal"), &tenant_id_param, "banka_ekstre_aktarimlari", &aktarim_id, |conn| {
+        let affected = diesel::sql_query(
+            "UPDATE banka_ekstre_aktarimlari SET durum = 'iptal', updated_at = ?1
+             WHERE id = ?2 AND tenant_id = ?3 AND durum = 'inceleniyor'",
+        )
+        .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(&aktarim_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+
+        if affected == 0 {
+            return Err("İncelenmekte olan ekstre aktarımı bulunamadı".to_string());
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,1092 @@
+// Yazdırılabilir PDF belgeler: aidat tahsilat makbuzu, üye hesap ekstresi, cari ekstre,
+// aidat borcu ihtar mektubu, genel kurul hazirun listesi ve toplantı tutanağı, karar defteri,
+// toplantı çağrı mektupları.
+//
+// Her sayfa dernek adı ve logosuyla (tenants.logo) başlar, sayfa numarasıyla biter.
+// Dosyalar Excel dışa aktarımları gibi çalışma dizinine zaman damgalı adla yazılır
+// ve dosya yolu döner.
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::commands::{aidat, cariler, mali, tenant};
+use crate::db::{avans, genel_kurul, ihtar, karar_defteri, toplanti_katilimi};
+use crate::db::para::Para;
+use crate::db::pdf::{self, Font, PdfBelge, SAYFA_GENISLIK, SAYFA_YUKSEKLIK};
+use crate::state::AppState;
+
+const KENAR: f64 = 40.0;
+const ICERIK_GENISLIK: f64 = SAYFA_GENISLIK - 2.0 * KENAR;
+const ALT_SINIR: f64 = SAYFA_YUKSEKLIK - 60.0;
+const SATIR: f64 = 15.0;
+const TABLO_PUNTO: f64 = 8.5;
+
+struct Sutun {
+    baslik: &'static str,
+    genislik: f64,
+    sag: bool,
+}
+
+const fn sutun(baslik: &'static str, genislik: f64, sag: bool) -> Sutun {
+    Sutun { baslik, genislik, sag }
+}
+
+/// Sayfa başlığını, tabloları ve sayfa geçişlerini yöneten yazıcı
+struct Yazici {
+    belge: PdfBelge,
+    dernek: String,
+    belge_adi: String,
+    logo: Option<usize>,
+    duzenleme: String,
+    y: f64,
+}
+
+impl Yazici {
+    fn new(conn: &mut SqliteConnection, tenant_id: &str, belge_adi: &str) -> Result<Self, String> {
+        #[derive(QueryableByName)]
+        struct DernekRow {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            name: String,
+        }
+
+        let dernek = diesel::sql_query("SELECT name FROM tenants WHERE id = ?1")
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .get_result::<DernekRow>(conn)
+            .map_err(|_| "Dernek bulunamadı".to_string())?
+            .name;
+
+        let mut belge = PdfBelge::new(&format!("{} - {}", dernek, belge_adi));
+        // Logo okunamazsa belge logosuz basılır; logo kaydedilirken zaten doğrulanır
+        let logo = tenant::tenant_logosu(conn, tenant_id)?
+            .and_then(|b64| general_purpose::STANDARD.decode(b64).ok())
+            .and_then(|baytlar| pdf::gorsel_coz(&baytlar).ok())
+            .map(|g| belge.gorsel_ekle(g));
+
+        let mut yazici = Yazici {
+            belge,
+            dernek,
+            belge_adi: belge_adi.to_string(),
+            logo,
+            duzenleme: chrono::Local::now().format("%d.%m.%Y %H:%M").to_string(),
+            y: 0.0,
+        };
+        yazici.sayfa_basligi();
+        Ok(yazici)
+    }
+
+    fn sayfa_basligi(&mut self) {
+        let mut x = KENAR;
+        if let Some(logo) = self.logo {
+            x += self.belge.gorsel_ciz(logo, KENAR, 28.0, 90.0, 46.0) + 12.0;
+        }
+        let genislik = SAYFA_GENISLIK - KENAR - x - 110.0;
+        let dernek = pdf::kisalt(&self.dernek, Font::Kalin, 14.0, genislik);
+        self.belge.metin(x, 46.0, 14.0, Font::Kalin, &dernek);
+        self.belge.metin(x, 64.0, 11.0, Font::Normal, &self.belge_adi);
+        self.belge.metin_sag(SAYFA_GENISLIK - KENAR, 46.0, 8.0, Font::Normal, "Düzenleme tarihi");
+        self.belge.metin_sag(SAYFA_GENISLIK - KENAR, 58.0, 9.0, Font::Normal, &self.duzenleme);
+        self.belge.cizgi(KENAR, 80.0, SAYFA_GENISLIK - KENAR, 80.0, 1.0);
+        self.y = 100.0;
+    }
+
+    /// Sığmıyorsa yeni sayfaya geçer; geçildiyse true
+    fn yer_ayir(&mut self, yukseklik: f64) -> bool {
+        if self.y + yukseklik <= ALT_SINIR {
+            return false;
+        }
+        self.belge.yeni_sayfa();
+        self.sayfa_basligi();
+        true
+    }
+
+    fn bolum_basligi(&mut self, baslik: &str) {
+        // Başlık sayfanın dibinde tek başına kalmasın
+        self.yer_ayir(SATIR * 4.0);
+        self.y += 8.0;
+        self.belge.metin(KENAR, self.y, 11.0, Font::Kalin, baslik);
+        self.y += SATIR;
+    }
+
+    fn bilgi(&mut self, etiket: &str, deger: &str) {
+        self.yer_ayir(SATIR);
+        self.belge.metin(KENAR, self.y, 9.0, Font::Kalin, etiket);
+        let deger = pdf::kisalt(deger, Font::Normal, 9.0, ICERIK_GENISLIK - 120.0);
+        self.belge.metin(KENAR + 120.0, self.y, 9.0, Font::Normal, &deger);
+        self.y += SATIR - 1.0;
+    }
+
+    fn not_satiri(&mut self, metin: &str) {
+        self.yer_ayir(SATIR);
+        self.belge.metin(KENAR, self.y, 8.5, Font::Normal, metin);
+        self.y += SATIR;
+    }
+
+    /// Satırlara bölünmüş düz metin (mektup gövdesi)
+    fn paragraf(&mut self, metin: &str, font: Font, boyut: f64) {
+        for satir in pdf::satirlara_bol(metin, font, boyut, ICERIK_GENISLIK) {
+            self.yer_ayir(SATIR);
+            self.belge.metin(KENAR, self.y, boyut, font, &satir);
+            self.y += SATIR;
+        }
+    }
+
+    fn tablo_basligi(&mut self, sutunlar: &[Sutun]) {
+        let toplam: f64 = sutunlar.iter().map(|s| s.genislik).sum();
+        self.belge.dikdortgen(KENAR, self.y - 11.0, toplam, 16.0, Some(0.88));
+        self.hucreler(sutunlar, sutunlar.iter().map(|s| s.baslik.to_string()), Font::Kalin);
+        self.y += SATIR + 3.0;
+    }
+
+    fn tablo_satiri(&mut self, sutunlar: &[Sutun], hucreler: Vec<String>, font: Font) {
+        if self.yer_ayir(SATIR) {
+            self.tablo_basligi(sutunlar);
+        }
+        self.hucreler(sutunlar, hucreler.into_iter(), font);
+        let toplam: f64 = sutunlar.iter().map(|s| s.genislik).sum();
+        self.belge.cizgi(KENAR, self.y + 4.5, KENAR + toplam, self.y + 4.5, 0.25);
+        self.y += SATIR;
+    }
+
+    fn hucreler(&mut self, sutunlar: &[Sutun], hucreler: impl Iterator<Item = String>, font: Font) {
+        let mut x = KENAR;
+        for (s, h) in sutunlar.iter().zip(hucreler) {
+            let metin = pdf::kisalt(&h, font, TABLO_PUNTO, s.genislik - 6.0);
+            if s.sag {
+                self.belge.metin_sag(x + s.genislik - 3.0, self.y, TABLO_PUNTO, font, &metin);
+            } else {
+                self.belge.metin(x + 3.0, self.y, TABLO_PUNTO, font, &metin);
+            }
+            x += s.genislik;
+        }
+    }
+
+    /// Alt bilgiyi (sayfa numaraları) yazar ve dosyaya kaydeder
+    fn kaydet(mut self, dosya_oneki: &str) -> Result<String, String> {
+        let sayfa_sayisi = self.belge.sayfa_sayisi();
+        let alt = format!("{} · {}", self.dernek, self.belge_adi);
+        let alt = pdf::kisalt(&alt, Font::Normal, 7.5, ICERIK_GENISLIK - 80.0);
+        for i in 0..sayfa_sayisi {
+            self.belge.sayfa_sec(i);
+            let y = ALT_SINIR + 20.0;
+            self.belge.cizgi(KENAR, y - 10.0, SAYFA_GENISLIK - KENAR, y - 10.0, 0.5);
+            self.belge.metin(KENAR, y, 7.5, Font::Normal, &alt);
+            self.belge.metin_sag(
+                SAYFA_GENISLIK - KENAR,
+                y,
+                7.5,
+                Font::Normal,
+                &format!("Sayfa {}/{}", i + 1, sayfa_sayisi),
+            );
+        }
+
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let filepath = PathBuf::from(format!("{}_{}.pdf", dosya_oneki, timestamp));
+        std::fs::write(&filepath, self.belge.bitir()).map_err(|e| format!("PDF yazılamadı: {}", e))?;
+        Ok(filepath.to_string_lossy().to_string())
+    }
+}
+
+/// "2026-03-05 10:00:00" → "05.03.2026"; çözülemeyen değer olduğu gibi döner
+fn tr_tarih(tarih: &str) -> String {
+    tarih
+        .get(..10)
+        .and_then(|t| chrono::NaiveDate::parse_from_str(t, "%Y-%m-%d").ok())
+        .map(|t| t.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|| tarih.to_string())
+}
+
+fn donem(yil: Option<i32>, ay: Option<i32>) -> String {
+    match (yil, ay) {
+        (Some(y), Some(a)) if (1..=12).contains(&a) => format!("{}/{:02}", y, a),
+        (Some(y), _) => y.to_string(),
+        _ => "-".to_string(),
+    }
+}
+
+/// KVKK: belgede TC kimlik numarasının yalnızca ilk 3 ve son 2 hanesi görünür
+fn tc_maskele(tc: &str) -> String {
+    let rakamlar: Vec<char> = tc.trim().chars().collect();
+    if rakamlar.len() < 6 {
+        return "-".to_string();
+    }
+    rakamlar
+        .iter()
+        .enumerate()
+        .map(|(i, c)| if i < 3 || i >= rakamlar.len() - 2 { *c } else { '*' })
+        .collect()
+}
+
+fn dosya_adi(metin: &str) -> String {
+    metin
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
+
+// ============================================================================
+// Aidat tahsilat makbuzu
+// ============================================================================
+
+#[derive(QueryableByName)]
+struct MakbuzRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    makbuz_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    tahsil_eden: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    kasa_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    para_birimi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    uye_no: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    yil: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    ay: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    aidat_tutar: Option<Para>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    aidat_odenen: Option<Para>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    aidat_kalan: Option<Para>,
+}
+
+/// Aidat tahsilat makbuzu PDF'i. gelir_id verilmezse aidatın son tahsilatı
+/// (aidat_takip.gelir_id) basılır; add_aidat_odeme_with_gelir sonrasında aynı
+/// aidat_id ile çağrılır.
+#[tauri::command]
+pub async fn aidat_makbuzu_pdf(
+    state: State<'_, AppState>,
+    tenant_id_param: String,
+    aidat_id: Option<String>,
+    gelir_id: Option<String>,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let gelir_id = match (gelir_id, aidat_id) {
+        (Some(g), _) => g,
+        (None, Some(a)) => {
+            #[derive(QueryableByName)]
+            struct AidatGelir {
+                #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+                gelir_id: Option<String>,
+            }
+            diesel::sql_query("SELECT gelir_id FROM aidat_takip WHERE id = ?1 AND tenant_id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&a)
+                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+                .get_result::<AidatGelir>(&mut conn)
+                .optional()
+                .map_err(|e| e.to_string())?
+                .ok_or("Aidat kaydı bulunamadı")?
+                .gelir_id
+                .ok_or("Bu aidat için kayıtlı tahsilat yok")?
+        }
+        (None, None) => return Err("Makbuz için aidat veya gelir seçilmelidir".to_string()),
+    };
+
+    let m = diesel::sql_query(
+        "SELECT g.id, g.tarih, g.tutar, g.aciklama, g.makbuz_no, g.tahsil_eden,
+                k.kasa_adi, k.para_birimi,
+                u.id as uye_id, u.uye_no, u.ad_soyad,
+                a.yil, a.ay, a.tutar as aidat_tutar, a.odenen as aidat_odenen, a.kalan as aidat_kalan
+         FROM gelirler g
+         JOIN uyeler u ON u.id = g.uye_id AND u.tenant_id = g.tenant_id
+         LEFT JOIN kasalar k ON k.id = g.kasa_id
+         LEFT JOIN aidat_takip a ON a.id = g.aidat_id AND a.tenant_id = g.tenant_id
+         WHERE g.id = ?1 AND g.tenant_id = ?2
+           AND (g.is_deleted IS NULL OR g.is_deleted = 0)"
+    )
+    .bind::<diesel::sql_types::Text, _>(&gelir_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result::<MakbuzRow>(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Üyeye bağlı tahsilat bulunamadı")?;
+
+    let acik_borc: Para = aidat::uye_aidat_borclari(&mut conn, &tenant_id_param, &m.uye_id)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|b| b.kalan)
+        .sum();
+    let birim = match m.para_birimi.as_deref() {
+        None | Some("TRY") => "TL".to_string(),
+        Some(pb) => pb.to_string(),
+    };
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, "Aidat Tahsilat Makbuzu")?;
+
+    y.bilgi("Makbuz No", m.makbuz_no.as_deref().unwrap_or("-"));
+    y.bilgi("Tahsilat Tarihi", &tr_tarih(&m.tarih));
+    y.bilgi("Üye No", &m.uye_no);
+    y.bilgi("Adı Soyadı", &m.ad_soyad);
+    y.bilgi("Aidat Dönemi", &donem(m.yil, m.ay));
+    y.bilgi("Kasa", m.kasa_adi.as_deref().unwrap_or("-"));
+    if let Some(aciklama) = m.aciklama.as_deref().filter(|a| !a.is_empty()) {
+        y.bilgi("Açıklama", aciklama);
+    }
+
+    // Tutar kutusu
+    y.y += 10.0;
+    y.belge.dikdortgen(KENAR, y.y, ICERIK_GENISLIK, 48.0, Some(0.94));
+    y.belge.dikdortgen(KENAR, y.y, ICERIK_GENISLIK, 48.0, None);
+    y.belge.metin(KENAR + 10.0, y.y + 20.0, 11.0, Font::Kalin, "Tahsil Edilen Tutar");
+    y.belge.metin_sag(
+        SAYFA_GENISLIK - KENAR - 10.0,
+        y.y + 21.0,
+        16.0,
+        Font::Kalin,
+        &format!("{} {}", m.tutar.tr_format(), birim),
+    );
+    let yaziyla = if birim == "TL" { m.tutar.yaziyla() } else { format!("{} {}", m.tutar.tr_format(), birim) };
+    let yaziyla = pdf::kisalt(&format!("Yalnız {}", yaziyla), Font::Normal, 9.0, ICERIK_GENISLIK - 20.0);
+    y.belge.metin(KENAR + 10.0, y.y + 38.0, 9.0, Font::Normal, &yaziyla);
+    y.y += 70.0;
+
+    if let (Some(tutar), Some(odenen)) = (m.aidat_tutar, m.aidat_odenen) {
+        y.bolum_basligi("Aidat Durumu");
+        y.bilgi("Dönem tahakkuku", &format!("{} {}", tutar.tr_format(), birim));
+        y.bilgi("Dönemde ödenen", &format!("{} {}", odenen.tr_format(), birim));
+        y.bilgi("Dönem kalanı", &format!("{} {}", m.aidat_kalan.unwrap_or(tutar - odenen).tr_format(), birim));
+    }
+    y.bilgi("Toplam açık aidat borcu", &format!("{} {}", acik_borc.tr_format(), birim));
+
+    // İmza alanları
+    y.yer_ayir(80.0);
+    y.y += 50.0;
+    let imza_genislik = 180.0;
+    let sag_x = SAYFA_GENISLIK - KENAR - imza_genislik;
+    y.belge.cizgi(KENAR, y.y, KENAR + imza_genislik, y.y, 0.5);
+    y.belge.cizgi(sag_x, y.y, sag_x + imza_genislik, y.y, 0.5);
+    y.belge.metin_orta(KENAR + imza_genislik / 2.0, y.y + 12.0, 9.0, Font::Kalin, "Ödeyen");
+    y.belge.metin_orta(sag_x + imza_genislik / 2.0, y.y + 12.0, 9.0, Font::Kalin, "Tahsil Eden");
+    y.belge.metin_orta(KENAR + imza_genislik / 2.0, y.y + 24.0, 9.0, Font::Normal, &m.ad_soyad);
+    if let Some(tahsil_eden) = m.tahsil_eden.as_deref() {
+        y.belge.metin_orta(sag_x + imza_genislik / 2.0, y.y + 24.0, 9.0, Font::Normal, tahsil_eden);
+    }
+    y.y += 30.0;
+
+    let onek = format!("aidat_makbuzu_{}", dosya_adi(m.makbuz_no.as_deref().unwrap_or(&m.id[..8])));
+    y.kaydet(&onek)
+}
+
+// ============================================================================
+// Üye hesap ekstresi
+// ============================================================================
+
+#[derive(QueryableByName)]
+struct UyeRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    uye_no: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tc_no: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    giris_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    durum: String,
+}
+
+/// Üye hesap ekstresi PDF'i: açık aidat borçları (get_uye_aidat_borclari),
+/// yapılan ödemeler (get_uyeye_ait_gelirler) ve varsa avans hareketleri
+#[tauri::command]
+pub async fn uye_hesap_ekstresi_pdf(
+    state: State<'_, AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let uye = diesel::sql_query(
+        "SELECT uye_no, ad_soyad, tc_no, giris_tarihi, durum FROM uyeler WHERE id = ?1 AND tenant_id = ?2"
+    )
+    .bind::<diesel::sql_types::Text, _>(&uye_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result::<UyeRow>(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Üye bulunamadı")?;
+
+    let borclar = aidat::uye_aidat_borclari(&mut conn, &tenant_id_param, &uye_id).map_err(|e| e.to_string())?;
+    let mut odemeler = mali::uyeye_ait_gelirler(&mut conn, &tenant_id_param, &uye_id).map_err(|e| e.to_string())?;
+    odemeler.reverse(); // eskiden yeniye
+    let avans_hareketleri = avans::hareketleri_getir(&mut conn, &tenant_id_param, &uye_id)?;
+    let avans_bakiyesi = avans_hareketleri.last().map(|h| h.bakiye).unwrap_or_default();
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, "Üye Hesap Ekstresi")?;
+    y.bilgi("Üye No", &uye.uye_no);
+    y.bilgi("Adı Soyadı", &uye.ad_soyad);
+    y.bilgi("TC Kimlik No", &tc_maskele(&uye.tc_no));
+    y.bilgi("Üyelik Tarihi", &tr_tarih(&uye.giris_tarihi));
+    y.bilgi("Durum", &uye.durum);
+
+    const BORC_SUTUNLARI: [Sutun; 4] = [
+        sutun("Yıl", 115.0, false),
+        sutun("Tahakkuk", 133.0, true),
+        sutun("Ödenen", 133.0, true),
+        sutun("Kalan", 134.0, true),
+    ];
+    y.bolum_basligi("Açık Aidat Borçları");
+    let toplam_kalan: Para = borclar.iter().map(|b| b.kalan).sum();
+    if borclar.is_empty() {
+        y.not_satiri("Açık aidat borcu bulunmamaktadır.");
+    } else {
+        y.tablo_basligi(&BORC_SUTUNLARI);
+        for b in &borclar {
+            y.tablo_satiri(
+                &BORC_SUTUNLARI,
+                vec![b.yil.to_string(), b.tutar.tr_format(), b.odenen.tr_format(), b.kalan.tr_format()],
+                Font::Normal,
+            );
+        }
+        let toplam_tutar: Para = borclar.iter().map(|b| b.tutar).sum();
+        let toplam_odenen: Para = borclar.iter().map(|b| b.odenen).sum();
+        y.tablo_satiri(
+            &BORC_SUTUNLARI,
+            vec!["Toplam".to_string(), toplam_tutar.tr_format(), toplam_odenen.tr_format(), toplam_kalan.tr_format()],
+            Font::Kalin,
+        );
+    }
+
+    const ODEME_SUTUNLARI: [Sutun; 5] = [
+        sutun("Tarih", 65.0, false),
+        sutun("Makbuz No", 80.0, false),
+        sutun("Dönem", 60.0, false),
+        sutun("Açıklama", 220.0, false),
+        sutun("Tutar", 90.0, true),
+    ];
+    y.bolum_basligi("Ödemeler");
+    if odemeler.is_empty() {
+        y.not_satiri("Kayıtlı ödeme bulunmamaktadır.");
+    } else {
+        y.tablo_basligi(&ODEME_SUTUNLARI);
+        for o in &odemeler {
+            y.tablo_satiri(
+                &ODEME_SUTUNLARI,
+                vec![
+                    tr_tarih(&o.tarih),
+                    o.makbuz_no.clone().unwrap_or_default(),
+                    if o.aidat_id.is_some() { donem(o.aidat_yil, o.aidat_ay) } else { String::new() },
+                    o.aciklama.clone().or_else(|| o.gelir_turu.clone()).unwrap_or_default(),
+                    o.tutar.tr_format(),
+                ],
+                Font::Normal,
+            );
+        }
+        let toplam: Para = odemeler.iter().map(|o| o.tutar).sum();
+        y.tablo_satiri(
+            &ODEME_SUTUNLARI,
+            vec![String::new(), String::new(), String::new(), "Toplam".to_string(), toplam.tr_format()],
+            Font::Kalin,
+        );
+    }
+
+    if !avans_hareketleri.is_empty() {
+        const AVANS_SUTUNLARI: [Sutun; 5] = [
+            sutun("Tarih", 65.0, false),
+            sutun("İşlem", 70.0, false),
+            sutun("Açıklama", 200.0, false),
+            sutun("Tutar", 90.0, true),
+            sutun("Bakiye", 90.0, true),
+        ];
+        y.bolum_basligi("Avans Hareketleri");
+        y.tablo_basligi(&AVANS_SUTUNLARI);
+        for h in &avans_hareketleri {
+            let islem = match h.tur.as_str() {
+                "giris" => "Fazla ödeme",
+                "mahsup" => "Mahsup",
+                "iade" => "İade",
+                diger => diger,
+            };
+            y.tablo_satiri(
+                &AVANS_SUTUNLARI,
+                vec![
+                    tr_tarih(&h.tarih),
+                    islem.to_string(),
+                    h.aciklama.clone().unwrap_or_default(),
+                    h.tutar.tr_format(),
+                    h.bakiye.tr_format(),
+                ],
+                Font::Normal,
+            );
+        }
+    }
+
+    y.y += 10.0;
+    y.bilgi("Güncel aidat borcu", &format!("{} TL", toplam_kalan.tr_format()));
+    if !avans_bakiyesi.is_zero() {
+        y.bilgi("Avans bakiyesi", &format!("{} TL", avans_bakiyesi.tr_format()));
+    }
+
+    y.kaydet(&format!("uye_hesap_ekstresi_{}", dosya_adi(&uye.uye_no)))
+}
+
+// ============================================================================
+// Cari ekstre
+// ============================================================================
+
+/// Cari ekstre PDF'i (get_cari_ekstre hareketleri). Başlangıç tarihi verilirse
+/// önceki hareketler devir satırında toplanır; bakiye borç − alacak olarak yürür.
+#[tauri::command]
+pub fn cari_ekstre_pdf(
+    state: State<AppState>,
+    tenant_id_param: String,
+    cari_id: String,
+    baslangic_tarihi: Option<String>,
+    bitis_tarihi: Option<String>,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let cari = cariler::get_cari(state.clone(), tenant_id_param.clone(), cari_id.clone())?
+        .ok_or("Cari bulunamadı")?;
+    let mut hareketler = cariler::get_cari_hareketler(state.clone(), tenant_id_param.clone(), cari_id)?;
+    hareketler.sort_by(|a, b| a.tarih.cmp(&b.tarih));
+
+    let baslangic = baslangic_tarihi.as_deref().filter(|t| !t.is_empty());
+    let bitis = bitis_tarihi.as_deref().filter(|t| !t.is_empty());
+    let isaretli = |h: &cariler::CariHareket| if h.hareket_tipi == "Borç" { h.tutar } else { -h.tutar };
+
+    let devir: Para = hareketler
+        .iter()
+        .filter(|h| baslangic.is_some_and(|b| h.tarih.as_str() < b))
+        .map(isaretli)
+        .sum();
+    let donemdekiler: Vec<&cariler::CariHareket> = hareketler
+        .iter()
+        .filter(|h| baslangic.is_none_or(|b| h.tarih.as_str() >= b))
+        .filter(|h| bitis.is_none_or(|b| h.tarih.get(..10).unwrap_or(&h.tarih) <= b))
+        .collect();
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, "Cari Hesap Ekstresi")?;
+    y.bilgi("Ünvan", &cari.unvan);
+    if let Some(kod) = cari.cari_kodu.as_deref() {
+        y.bilgi("Cari Kodu", kod);
+    }
+    if let Some(vn) = cari.vergi_no.as_deref().filter(|v| !v.is_empty()) {
+        let vd = cari.vergi_dairesi.as_deref().unwrap_or("");
+        y.bilgi("Vergi Dairesi / No", format!("{} {}", vd, vn).trim());
+    }
+    let aralik = match (baslangic, bitis) {
+        (None, None) => "Tüm hareketler".to_string(),
+        (b, s) => format!(
+            "{} - {}",
+            b.map(tr_tarih).unwrap_or_else(|| "…".to_string()),
+            s.map(tr_tarih).unwrap_or_else(|| "…".to_string())
+        ),
+    };
+    y.bilgi("Dönem", &aralik);
+
+    const SUTUNLAR: [Sutun; 6] = [
+        sutun("Tarih", 60.0, false),
+        sutun("Belge No", 70.0, false),
+        sutun("Açıklama", 165.0, false),
+        sutun("Borç", 70.0, true),
+        sutun("Alacak", 70.0, true),
+        sutun("Bakiye", 80.0, true),
+    ];
+    let bakiye_metni = |b: Para| match b {
+        b if b.is_zero() => "0,00".to_string(),
+        b if b.is_positive() => format!("{} (B)", b.tr_format()),
+        b => format!("{} (A)", b.abs().tr_format()),
+    };
+
+    y.bolum_basligi("Hareketler");
+    y.tablo_basligi(&SUTUNLAR);
+    let mut bakiye = devir;
+    if baslangic.is_some() {
+        y.tablo_satiri(
+            &SUTUNLAR,
+            vec![String::new(), String::new(), "Devir".to_string(), String::new(), String::new(), bakiye_metni(devir)],
+            Font::Kalin,
+        );
+    }
+    let (mut toplam_borc, mut toplam_alacak) = (Para::SIFIR, Para::SIFIR);
+    for h in &donemdekiler {
+        let borc = h.hareket_tipi == "Borç";
+        if borc {
+            toplam_borc += h.tutar;
+        } else {
+            toplam_alacak += h.tutar;
+        }
+        bakiye += isaretli(h);
+        y.tablo_satiri(
+            &SUTUNLAR,
+            vec![
+                tr_tarih(&h.tarih),
+                h.belge_no.clone().unwrap_or_default(),
+                h.aciklama.clone().unwrap_or_default(),
+                if borc { h.tutar.tr_format() } else { String::new() },
+                if borc { String::new() } else { h.tutar.tr_format() },
+                bakiye_metni(bakiye),
+            ],
+            Font::Normal,
+        );
+    }
+    y.tablo_satiri(
+        &SUTUNLAR,
+        vec![
+            String::new(),
+            String::new(),
+            "Dönem toplamı".to_string(),
+            toplam_borc.tr_format(),
+            toplam_alacak.tr_format(),
+            bakiye_metni(bakiye),
+        ],
+        Font::Kalin,
+    );
+    y.y += 6.0;
+    y.not_satiri("(B): cari borçlu, (A): cari alacaklı");
+
+    y.kaydet(&format!("cari_ekstre_{}", dosya_adi(cari.cari_kodu.as_deref().unwrap_or(&cari.unvan))))
+}
+
+// ============================================================================
+// İhtar mektubu
+// ============================================================================
+
+#[derive(QueryableByName)]
+struct MuhatapRow {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    adres: Option<String>,
+}
+
+/// İhtar mektubu PDF'i: kesildiği andaki metin ve borç dökümü basılır,
+/// dosya yolu ihtar kaydına yazılır
+#[tauri::command]
+pub fn ihtar_mektubu_pdf(
+    state: State<AppState>,
+    tenant_id_param: String,
+    ihtar_id: String,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let kayit = ihtar::ihtar_getir(&mut conn, &tenant_id_param, &ihtar_id)?;
+    let kalemler = ihtar::ihtar_kalemleri(&mut conn, &tenant_id_param, &ihtar_id)?;
+    let adres = diesel::sql_query("SELECT adres FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(&kayit.uye_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .get_result::<MuhatapRow>(&mut conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .and_then(|r| r.adres)
+        .filter(|a| !a.trim().is_empty());
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, &format!("{}. Seviye İhtar", kayit.seviye))?;
+    y.bilgi("Tarih", &tr_tarih(&kayit.tarih));
+    y.bilgi("Sayın", kayit.ad_soyad.as_deref().unwrap_or("-"));
+    y.bilgi("Üye No", kayit.uye_no.as_deref().unwrap_or("-"));
+    if let Some(adres) = adres.as_deref() {
+        y.bilgi("Adres", adres);
+    }
+
+    y.y += 10.0;
+    y.yer_ayir(SATIR * 2.0);
+    y.belge.metin_orta(SAYFA_GENISLIK / 2.0, y.y, 12.0, Font::Kalin, &kayit.baslik);
+    y.y += SATIR * 2.0;
+    y.paragraf(&kayit.metin, Font::Normal, 10.0);
+
+    const SUTUNLAR: [Sutun; 3] = [
+        sutun("Dönem", 170.0, false),
+        sutun("Son Ödeme Tarihi", 170.0, false),
+        sutun("Kalan Borç", 175.0, true),
+    ];
+    y.bolum_basligi("Borç Dökümü");
+    y.tablo_basligi(&SUTUNLAR);
+    for k in &kalemler {
+        y.tablo_satiri(
+            &SUTUNLAR,
+            vec![donem(Some(k.yil), Some(k.ay)), tr_tarih(&k.vade_tarihi), k.kalan.tr_format()],
+            Font::Normal,
+        );
+    }
+    y.tablo_satiri(
+        &SUTUNLAR,
+        vec!["Toplam".to_string(), String::new(), kayit.borc_tutari.tr_format()],
+        Font::Kalin,
+    );
+    y.y += 6.0;
+    y.not_satiri(&format!(
+        "Cevap süresi: tebliğden itibaren {} gün. Döküm {} tarihli kayıtlara göredir.",
+        kayit.cevap_suresi_gun,
+        tr_tarih(&kayit.tarih)
+    ));
+
+    let yol = y.kaydet(&format!(
+        "ihtar_{}_{}",
+        kayit.seviye,
+        dosya_adi(kayit.uye_no.as_deref().unwrap_or(&kayit.uye_id))
+    ))?;
+    ihtar::belge_yolu_kaydet(&mut conn, &tenant_id_param, &ihtar_id, &yol)?;
+    Ok(yol)
+}
+
+// ============================================================================
+// Genel kurul hazirun listesi ve tutanak
+// ============================================================================
+
+fn genel_kurul_bilgileri(y: &mut Yazici, gk: &genel_kurul::GenelKurul) {
+    y.bilgi("Toplantı", &gk.baslik);
+    y.bilgi("Türü", if gk.tur == "olaganustu" { "Olağanüstü" } else { "Olağan" });
+    let saat = gk.saat.as_deref().map(|s| format!(" {}", s)).unwrap_or_default();
+    y.bilgi("Tarih", &format!("{}{}", tr_tarih(&gk.tarih), saat));
+    if let Some(ikinci) = gk.ikinci_toplanti_tarihi.as_deref() {
+        y.bilgi("İkinci toplantı", &tr_tarih(ikinci));
+    }
+    if let Some(yer) = gk.yer.as_deref() {
+        y.bilgi("Yer", yer);
+    }
+}
+
+/// Hazirun listesi: katılım hakkı olan üyeler ve imza sütunu
+#[tauri::command]
+pub fn hazirun_listesi_pdf(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let gk = genel_kurul::genel_kurul_getir(&mut conn, &tenant_id_param, &genel_kurul_id)?;
+    let hazirun = genel_kurul::hazirun_getir(&mut conn, &tenant_id_param, &genel_kurul_id)?;
+    if hazirun.is_empty() {
+        return Err("Hazirun listesi henüz çıkarılmamış".to_string());
+    }
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, "Genel Kurul Hazirun Listesi")?;
+    genel_kurul_bilgileri(&mut y, &gk);
+    y.bilgi("Hazirun tarihi", &tr_tarih(&gk.hazirun_tarihi));
+
+    const SUTUNLAR: [Sutun; 5] = [
+        sutun("Sıra", 35.0, true),
+        sutun("Üye No", 60.0, false),
+        sutun("Ad Soyad", 190.0, false),
+        sutun("TC Kimlik No", 90.0, false),
+        sutun("İmza", 140.0, false),
+    ];
+    y.bolum_basligi("Katılma Hakkı Olan Üyeler");
+    y.tablo_basligi(&SUTUNLAR);
+    let mut sira = 0;
+    for h in hazirun.iter().filter(|h| h.katilim_hakki) {
+        sira += 1;
+        // İmza sonradan alındıysa saatini yaz, değilse ıslak imza için boş bırak
+        let imza = h.imza_zamani.as_deref().map(|z| format!("({})", z)).unwrap_or_default();
+        y.tablo_satiri(
+            &SUTUNLAR,
+            vec![
+                sira.to_string(),
+                h.uye_no.clone().unwrap_or_default(),
+                h.ad_soyad.clone(),
+                h.tc_no.as_deref().map(tc_maskele).unwrap_or_else(|| "-".to_string()),
+                imza,
+            ],
+            Font::Normal,
+        );
+    }
+    let engelli = hazirun.iter().filter(|h| !h.katilim_hakki).count();
+    y.y += 6.0;
+    y.not_satiri(&format!("Katılma hakkı olan üye sayısı: {}", sira));
+    if engelli > 0 {
+        y.not_satiri(&format!(
+            "Tüzük gereği vadesi geçmiş aidat borcu bulunan {} üye listeye alınmamıştır.",
+            engelli
+        ));
+    }
+    y.y += SATIR * 2.0;
+    y.yer_ayir(SATIR * 3.0);
+    y.belge.metin(KENAR, y.y, 9.0, Font::Kalin, "Yönetim Kurulu Başkanı");
+    y.belge.metin_sag(SAYFA_GENISLIK - KENAR, y.y, 9.0, Font::Kalin, "Yönetim Kurulu Üyesi");
+
+    y.kaydet(&format!("hazirun_{}", dosya_adi(&gk.tarih)))
+}
+
+/// Genel kurul toplantı tutanağı: yeter sayı, gündem oylamaları ve seçim sonuçları
+#[tauri::command]
+pub fn genel_kurul_tutanagi_pdf(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let detay = genel_kurul::genel_kurul_detay(&mut conn, &tenant_id_param, &genel_kurul_id)?;
+    let gk = &detay.genel_kurul;
+    if !["acildi", "tamamlandi"].contains(&gk.durum.as_str()) {
+        return Err("Toplantı açılmadan tutanak düzenlenemez".to_string());
+    }
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, "Genel Kurul Toplantı Tutanağı")?;
+    genel_kurul_bilgileri(&mut y, gk);
+    y.bilgi("Açılış", gk.acilis_zamani.as_deref().unwrap_or("-"));
+    y.bilgi("Divan başkanı", gk.divan_baskani.as_deref().unwrap_or("-"));
+    y.bilgi("Katip üyeler", gk.divan_katipleri.as_deref().unwrap_or("-"));
+
+    y.bolum_basligi("Yeter Sayı");
+    y.paragraf(
+        &format!(
+            "Hazirun listesine göre genel kurula katılma hakkı bulunan {} üyeden {} üyenin katılımıyla \
+             toplantı {}. çağrıda açılmıştır (gerekli yeter sayı {}). Toplantı sonunda hazirunu imzalayan \
+             üye sayısı: {}.",
+            detay.yetersayi.uye_tamsayisi,
+            gk.acilista_katilan.unwrap_or(0),
+            detay.yetersayi.cagri,
+            detay.yetersayi.gerekli,
+            detay.yetersayi.katilan
+        ),
+        Font::Normal,
+        9.5,
+    );
+
+    const GUNDEM: [Sutun; 6] = [
+        sutun("Sıra", 35.0, true),
+        sutun("Gündem Maddesi", 250.0, false),
+        sutun("Kabul", 55.0, true),
+        sutun("Ret", 55.0, true),
+        sutun("Çekimser", 60.0, true),
+        sutun("Sonuç", 60.0, false),
+    ];
+    y.bolum_basligi("Gündem ve Kararlar");
+    y.tablo_basligi(&GUNDEM);
+    let sayi = |s: Option<i32>| s.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string());
+    for m in &detay.gundem {
+        let sonuc = match m.sonuc.as_deref() {
+            Some("kabul") => "Kabul",
+            Some(_) => "Ret",
+            None => "Oylanmadı",
+        };
+        let baslik = if m.nitelikli { format!("{} (2/3)", m.baslik) } else { m.baslik.clone() };
+        y.tablo_satiri(
+            &GUNDEM,
+            vec![m.sira.to_string(), baslik, sayi(m.kabul), sayi(m.ret), sayi(m.cekimser), sonuc.to_string()],
+            Font::Normal,
+        );
+    }
+    for m in detay.gundem.iter().filter(|m| m.karar_metni.is_some()) {
+        y.y += 4.0;
+        y.paragraf(
+            &format!("Madde {}: {}", m.sira, m.karar_metni.as_deref().unwrap_or_default()),
+            Font::Normal,
+            9.0,
+        );
+    }
+
+    const ADAYLAR: [Sutun; 4] = [
+        sutun("Sıra", 35.0, true),
+        sutun("Ad Soyad", 280.0, false),
+        sutun("Oy", 80.0, true),
+        sutun("Sonuç", 120.0, false),
+    ];
+    for s in &detay.secimler {
+        y.bolum_basligi(&format!("{} Seçimi", genel_kurul::organ_adi(&s.secim.organ)));
+        y.not_satiri(&format!(
+            "{} asil, {} yedek üye. Kullanılan oy: {}, geçersiz oy: {}",
+            s.secim.asil_sayisi,
+            s.secim.yedek_sayisi,
+            sayi(s.secim.kullanilan_oy),
+            sayi(s.secim.gecersiz_oy)
+        ));
+        y.tablo_basligi(&ADAYLAR);
+        for a in &s.adaylar {
+            let sonuc = match a.sonuc.as_deref() {
+                Some("asil") => "Asil üye",
+                Some("yedek") => "Yedek üye",
+                Some(_) => "-",
+                None => "Sayılmadı",
+            };
+            let kura = if a.kura_sirasi.is_some() { " (kura)" } else { "" };
+            y.tablo_satiri(
+                &ADAYLAR,
+                vec![sayi(a.sira), a.ad_soyad.clone(), format!("{}{}", a.oy, kura), sonuc.to_string()],
+                Font::Normal,
+            );
+        }
+    }
+
+    y.y += SATIR * 2.0;
+    y.yer_ayir(SATIR * 3.0);
+    y.belge.metin(KENAR, y.y, 9.0, Font::Kalin, "Divan Başkanı");
+    y.belge.metin_orta(SAYFA_GENISLIK / 2.0, y.y, 9.0, Font::Kalin, "Katip Üye");
+    y.belge.metin_sag(SAYFA_GENISLIK - KENAR, y.y, 9.0, Font::Kalin, "Katip Üye");
+
+    y.kaydet(&format!("genel_kurul_tutanagi_{}", dosya_adi(&gk.tarih)))
+}
+
+// ============================================================================
+// Karar defteri
+// ============================================================================
+
+/// Bir organın yıllık karar defteri: kararlar numara sırasıyla, oylar ve muhalefet şerhleriyle
+#[tauri::command]
+pub fn karar_defteri_pdf(
+    state: State<AppState>,
+    tenant_id_param: String,
+    organ: String,
+    yil: i32,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let kararlar = karar_defteri::kararlari_getir(&mut conn, &tenant_id_param, Some(&organ), Some(yil))?;
+    if kararlar.is_empty() {
+        return Err(format!("{} yılında bu organın kararı yok", yil));
+    }
+
+    let organ_adi = genel_kurul::organ_adi(&organ).to_string();
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, &format!("{} Karar Defteri {}", organ_adi, yil))?;
+    for k in &kararlar {
+        let detay = karar_defteri::karar_detay(&mut conn, &tenant_id_param, &k.id)?;
+        y.bolum_basligi(&format!("Karar No {} · {}", k.numara, tr_tarih(&k.karar_tarihi)));
+        if let Some(toplanti) = k.toplanti_basligi.as_deref() {
+            y.bilgi("Toplantı", toplanti);
+        }
+        y.bilgi("Konu", &k.konu);
+        y.paragraf(&k.metin, Font::Normal, 9.5);
+        let sonuc = match (k.sonuc.as_str(), k.ret + k.cekimser) {
+            ("kabul", 0) => "oy birliğiyle kabul edilmiştir",
+            ("kabul", _) => "oy çokluğuyla kabul edilmiştir",
+            _ => "reddedilmiştir",
+        };
+        y.not_satiri(&format!("Kabul: {}, ret: {}, çekimser: {}. Karar {}.", k.kabul, k.ret, k.cekimser, sonuc));
+        let katilanlar: Vec<&str> = detay
+            .katilimcilar
+            .iter()
+            .filter(|p| p.oy != "katilmadi")
+            .map(|p| p.ad_soyad.as_str())
+            .collect();
+        if !katilanlar.is_empty() {
+            y.paragraf(&format!("Katılanlar: {}", katilanlar.join(", ")), Font::Normal, 8.5);
+        }
+        for p in detay.katilimcilar.iter().filter(|p| p.muhalefet_serhi.is_some()) {
+            y.paragraf(
+                &format!("Muhalefet şerhi ({}): {}", p.ad_soyad, p.muhalefet_serhi.as_deref().unwrap_or_default()),
+                Font::Normal,
+                8.5,
+            );
+        }
+        if k.durum == "iptal" {
+            y.paragraf(
+                &format!("İPTAL EDİLDİ: {}", k.iptal_nedeni.as_deref().unwrap_or_default()),
+                Font::Kalin,
+                9.0,
+            );
+        }
+        y.y += 6.0;
+    }
+
+    y.kaydet(&format!("karar_defteri_{}_{}", dosya_adi(&organ), yil))
+}
+
+// ============================================================================
+// Toplantı davetiyesi
+// ============================================================================
+
+/// Davetli başına bir sayfa çağrı mektubu: toplantı bilgileri ve gündem
+#[tauri::command]
+pub fn toplanti_davetiyesi_pdf(
+    state: State<AppState>,
+    tenant_id_param: String,
+    toplanti_id: String,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let davetiye = toplanti_katilimi::davetiye(&mut conn, &tenant_id_param, &toplanti_id)?;
+    let davetliler = toplanti_katilimi::davetlileri_getir(&mut conn, &tenant_id_param, &toplanti_id)?;
+    if davetliler.is_empty() {
+        return Err("Toplantının davetlisi yok; önce davetli listesini oluşturun".to_string());
+    }
+
+    let mut y = Yazici::new(&mut conn, &tenant_id_param, "Toplantı Çağrısı")?;
+    let metin = toplanti_katilimi::davet_metni(&davetiye, &y.dernek);
+    for (i, d) in davetliler.iter().enumerate() {
+        if i > 0 {
+            y.belge.yeni_sayfa();
+            y.sayfa_basligi();
+        }
+        if let Some(tarih) = d.davet_tarihi.as_deref() {
+            y.bilgi("Tarih", &tr_tarih(tarih));
+        }
+        y.bilgi("Sayın", &d.ad_soyad);
+        if let Some(uye_no) = d.uye_no.as_deref() {
+            y.bilgi("Üye No", uye_no);
+        }
+        if let Some(gorev) = d.gorev.as_deref() {
+            y.bilgi("Görevi", gorev);
+        }
+
+        y.y += 10.0;
+        y.belge.metin_orta(SAYFA_GENISLIK / 2.0, y.y, 12.0, Font::Kalin, &davetiye.baslik);
+        y.y += SATIR * 2.0;
+        y.paragraf(&metin, Font::Normal, 10.0);
+
+        y.bolum_basligi("Gündem");
+        for (sira, madde) in davetiye.gundem.iter().enumerate() {
+            y.paragraf(&format!("{}. {}", sira + 1, madde), Font::Normal, 10.0);
+        }
+        y.y += SATIR * 2.0;
+        y.yer_ayir(SATIR * 2.0);
+        y.belge.metin_sag(SAYFA_GENISLIK - KENAR, y.y, 9.0, Font::Kalin, "Yönetim Kurulu adına");
+    }
+
+    y.kaydet(&format!("toplanti_cagrisi_{}", dosya_adi(&davetiye.tarih)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tarih_donem_ve_tc_gosterimi() {
+        assert_eq!(tr_tarih("2026-03-05 10:00:00"), "05.03.2026");
+        assert_eq!(tr_tarih("bilinmiyor"), "bilinmiyor");
+        assert_eq!(donem(Some(2026), Some(3)), "2026/03");
+        assert_eq!(donem(Some(2026), None), "2026");
+        assert_eq!(tc_maskele("12345678901"), "123******01");
+        assert_eq!(dosya_adi("A-000123/Ş"), "A-000123__");
+    }
+}
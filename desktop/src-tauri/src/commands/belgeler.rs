@@ -3,6 +3,7 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use serde::{Serialize, Deserialize};
 use std::fs;
 use std::path::Path;
@@ -154,27 +155,29 @@ pub fn create_belge(
     let now = Utc::now().to_rfc3339();
     let resmi_durum = request.resmi_durum.clone().unwrap_or_else(|| "gayri_resmi".to_string());
 
-    diesel::sql_query(
-        "INSERT INTO belgeler (id, tenant_id, belge_turu, baslik, dosya_adi, dosya_yolu, dosya_boyutu, mime_type, bagli_kayit_turu, bagli_kayit_id, aciklama, etiketler, resmi_durum, is_active, created_at, updated_at) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 1, ?14, ?15)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&belgeId)
-    .bind::<diesel::sql_types::Text, _>(&tenantIdParam)
-    .bind::<diesel::sql_types::Text, _>(&request.belge_turu)
-    .bind::<diesel::sql_types::Text, _>(&request.baslik)
-    .bind::<diesel::sql_types::Text, _>(&request.dosya_adi)
-    .bind::<diesel::sql_types::Text, _>(&stored_file_path) // Yeni path
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(Some(stored_file_size)) // Gerçek boyut
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.mime_type)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_turu)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_id)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.aciklama)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.etiketler)
-    .bind::<diesel::sql_types::Text, _>(&resmi_durum)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("create_belge"), &tenantIdParam, "belgeler", &belgeId, |conn| {
+        diesel::sql_query(
+            "INSERT INTO belgeler (id, tenant_id, belge_turu, baslik, dosya_adi, dosya_yolu, dosya_boyutu, mime_type, bagli_kayit_turu, bagli_kayit_id, aciklama, etiketler, resmi_durum, is_active, created_at, updated_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 1, ?14, ?15)"
+        )
+        .bind::<diesel::sql_types::Text, _>(&belgeId)
+        .bind::<diesel::sql_types::Text, _>(&tenantIdParam)
+        .bind::<diesel::sql_types::Text, _>(&request.belge_turu)
+        .bind::<diesel::sql_types::Text, _>(&request.baslik)
+        .bind::<diesel::sql_types::Text, _>(&request.dosya_adi)
+        .bind::<diesel::sql_types::Text, _>(&stored_file_path) // Yeni path
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(Some(stored_file_size)) // Gerçek boyut
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.mime_type)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_turu)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.aciklama)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.etiketler)
+        .bind::<diesel::sql_types::Text, _>(&resmi_durum)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     diesel::sql_query("SELECT * FROM belgeler WHERE id = ?1")
         .bind::<diesel::sql_types::Text, _>(&belgeId)
@@ -196,25 +199,27 @@ pub fn update_belge(
     let now = Utc::now().to_rfc3339();
     let resmi_durum = request.resmi_durum.clone().unwrap_or_else(|| "gayri_resmi".to_string());
 
-    diesel::sql_query(
-        "UPDATE belgeler SET belge_turu = ?1, baslik = ?2, dosya_adi = ?3, dosya_yolu = ?4, dosya_boyutu = ?5, mime_type = ?6, bagli_kayit_turu = ?7, bagli_kayit_id = ?8, aciklama = ?9, etiketler = ?10, resmi_durum = ?11, updated_at = ?12 WHERE id = ?13 AND tenant_id = ?14"
-    )
-    .bind::<diesel::sql_types::Text, _>(&request.belge_turu)
-    .bind::<diesel::sql_types::Text, _>(&request.baslik)
-    .bind::<diesel::sql_types::Text, _>(&request.dosya_adi)
-    .bind::<diesel::sql_types::Text, _>(&request.dosya_yolu)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&request.dosya_boyutu)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.mime_type)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_turu)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_id)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.aciklama)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.etiketler)
-    .bind::<diesel::sql_types::Text, _>(&resmi_durum)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&belgeId)
-    .bind::<diesel::sql_types::Text, _>(&tenantIdParam)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("update_belge"), &tenantIdParam, "belgeler", &belgeId, |conn| {
+        diesel::sql_query(
+            "UPDATE belgeler SET belge_turu = ?1, baslik = ?2, dosya_adi = ?3, dosya_yolu = ?4, dosya_boyutu = ?5, mime_type = ?6, bagli_kayit_turu = ?7, bagli_kayit_id = ?8, aciklama = ?9, etiketler = ?10, resmi_durum = ?11, updated_at = ?12 WHERE id = ?13 AND tenant_id = ?14"
+        )
+        .bind::<diesel::sql_types::Text, _>(&request.belge_turu)
+        .bind::<diesel::sql_types::Text, _>(&request.baslik)
+        .bind::<diesel::sql_types::Text, _>(&request.dosya_adi)
+        .bind::<diesel::sql_types::Text, _>(&request.dosya_yolu)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&request.dosya_boyutu)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.mime_type)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_turu)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.bagli_kayit_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.aciklama)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&request.etiketler)
+        .bind::<diesel::sql_types::Text, _>(&resmi_durum)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&belgeId)
+        .bind::<diesel::sql_types::Text, _>(&tenantIdParam)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     diesel::sql_query("SELECT * FROM belgeler WHERE id = ?1")
         .bind::<diesel::sql_types::Text, _>(&belgeId)
@@ -301,14 +306,16 @@ pub fn delete_belge(
     let now = Utc::now().to_rfc3339();
 
     // Soft delete
-    diesel::sql_query(
-        "UPDATE belgeler SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&belgeId)
-    .bind::<diesel::sql_types::Text, _>(&tenantIdParam)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_belge"), &tenantIdParam, "belgeler", &belgeId, |conn| {
+        diesel::sql_query(
+            "UPDATE belgeler SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&belgeId)
+        .bind::<diesel::sql_types::Text, _>(&tenantIdParam)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
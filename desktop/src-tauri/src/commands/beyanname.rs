@@ -0,0 +1,945 @@
+// Dernek Beyannamesi (DERBİS yıllık beyanname)
+//
+// Beyannamenin numaralı alanları uyeler, gelirler, giderler, virmanlar,
+// demirbaslar, kasalar, aidat_takip, toplantilar ve organ_gorevleri kayıtlarından hesaplanır.
+// Her alan, değerini oluşturan kayıtları (kaynaklar) taşır; ekranda alan
+// seçildiğinde get_beyanname_alan_kaynaklari ile dökümü gösterilir.
+// Bölüm ve alan sırası matbu formla aynıdır; XLSX çıktısı commands::export'tadır.
+//
+// Gelir/gider türleri beyanname kalemlerine tür kodu ve adındaki anahtar
+// kelimelerle eşlenir (GELIR_KURALLARI / GIDER_KURALLARI); eşleşmeyenler
+// "Diğer" kalemine düşer.
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use tauri::State;
+
+use crate::commands::resmi_defterler::{kasa_mevcutlari, kur_farklari, tl_karsiligi, yil_araligi, MevcutToplami};
+use crate::db::genel_kurul;
+use crate::db::organ_gorevi::{self, OrganGorevi};
+use crate::db::para::Para;
+use crate::db::yevmiye::hesap_anahtari;
+
+/// Beyanname alanının değeri
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "tur", content = "deger", rename_all = "snake_case")]
+pub enum AlanDegeri {
+    Sayi(i64),
+    Tutar(Para),
+    Metin(String),
+}
+
+/// Bir alanın değerine katkıda bulunan kayıt
+#[derive(Debug, Clone, Serialize)]
+pub struct AlanKaynagi {
+    pub tablo: String,
+    pub kayit_id: String,
+    pub tarih: Option<String>,
+    pub aciklama: String,
+    pub tutar: Option<Para>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BeyannameAlani {
+    pub no: String,
+    pub ad: String,
+    pub deger: AlanDegeri,
+    pub kaynak_sayisi: usize,
+    /// get_dernek_beyannamesi'nde boş döner; döküm için get_beyanname_alan_kaynaklari
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kaynaklar: Vec<AlanKaynagi>,
+}
+
+impl BeyannameAlani {
+    fn yeni(no: &str, ad: &str, deger: AlanDegeri, kaynaklar: Vec<AlanKaynagi>) -> Self {
+        BeyannameAlani {
+            no: no.to_string(),
+            ad: ad.to_string(),
+            deger,
+            kaynak_sayisi: kaynaklar.len(),
+            kaynaklar,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BeyannameBolumu {
+    pub no: String,
+    pub baslik: String,
+    pub alanlar: Vec<BeyannameAlani>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DernekBeyannamesi {
+    pub yil: i32,
+    pub dernek_adi: String,
+    pub bolumler: Vec<BeyannameBolumu>,
+    pub toplam_gelir: Para,
+    pub toplam_gider: Para,
+    /// Eksik/şüpheli veri uyarıları (beyannameye yazılmaz)
+    pub notlar: Vec<String>,
+}
+
+impl DernekBeyannamesi {
+    pub fn alan(&self, no: &str) -> Option<&BeyannameAlani> {
+        self.bolumler.iter().flat_map(|b| b.alanlar.iter()).find(|a| a.no == no)
+    }
+
+    /// Kaynak dökümlerini ayırır (liste ekranı için hafif çıktı)
+    fn kaynaklari_cikar(mut self) -> Self {
+        for alan in self.bolumler.iter_mut().flat_map(|b| b.alanlar.iter_mut()) {
+            alan.kaynaklar = Vec::new();
+        }
+        self
+    }
+}
+
+// ============================================================================
+// KALEM EŞLEME
+// ============================================================================
+
+/// (alan no, alan adı) — formdaki sırayla
+pub const GELIR_KALEMLERI: [(&str, &str); 8] = [
+    ("4.1", "Üye aidatları"),
+    ("4.2", "Bağış ve yardımlar"),
+    ("4.3", "Kamu kurum ve kuruluşlarından alınan yardımlar"),
+    ("4.4", "Yurt dışından alınan yardımlar"),
+    ("4.5", "Etkinlik gelirleri (kermes, piyango, konser vb.)"),
+    ("4.6", "İktisadi işletme gelirleri"),
+    ("4.7", "Mal varlığı gelirleri (faiz, kira vb.)"),
+    ("4.8", "Diğer gelirler"),
+];
+
+pub const GIDER_KALEMLERI: [(&str, &str); 8] = [
+    ("5.1", "Personel giderleri"),
+    ("5.2", "Kira giderleri"),
+    ("5.3", "Vergi, resim ve harçlar"),
+    ("5.4", "Yapılan yardım ve bağışlar"),
+    ("5.5", "Etkinlik giderleri"),
+    ("5.6", "Demirbaş alımları"),
+    ("5.7", "Genel yönetim giderleri (elektrik, su, iletişim, kırtasiye vb.)"),
+    ("5.8", "Diğer giderler"),
+];
+
+/// (anahtar kelime, alan no). İlk eşleşen kazanır: "Kamu yardımı" bağış değil 4.3'tür.
+/// Anahtar kelime bir kelimenin başıyla eşleşir; sonu boşluklu olan ("SU ") tam kelimedir.
+const GELIR_KURALLARI: &[(&str, &str)] = &[
+    ("YURT DIS", "4.4"),
+    ("YURTDIS", "4.4"),
+    ("KAMU", "4.3"),
+    ("BELEDIYE", "4.3"),
+    ("VALILIK", "4.3"),
+    ("BAKANLIK", "4.3"),
+    ("AIDAT", "4.1"),
+    ("BAGIS", "4.2"),
+    ("YARDIM", "4.2"),
+    ("HIBE", "4.2"),
+    ("ETK", "4.5"),
+    ("KERMES", "4.5"),
+    ("PIYANGO", "4.5"),
+    ("KONSER", "4.5"),
+    ("BILET", "4.5"),
+    ("IKTISADI", "4.6"),
+    ("ISLETME", "4.6"),
+    ("FAIZ", "4.7"),
+    ("KIRA", "4.7"),
+    ("KAR PAYI", "4.7"),
+    ("TEMETTU", "4.7"),
+];
+
+const GIDER_KURALLARI: &[(&str, &str)] = &[
+    ("PERS", "5.1"),
+    ("MAAS", "5.1"),
+    ("SGK", "5.1"),
+    ("KIRA", "5.2"),
+    ("VERGI", "5.3"),
+    ("HARC", "5.3"),
+    ("STOPAJ", "5.3"),
+    ("BAGIS", "5.4"),
+    ("YARDIM", "5.4"),
+    ("BURS", "5.4"),
+    ("ETK", "5.5"),
+    ("KERMES", "5.5"),
+    ("DEMIRBAS", "5.6"),
+    ("FATURA", "5.7"),
+    ("ELEKTRIK", "5.7"),
+    ("SU ", "5.7"),
+    ("DOGALGAZ", "5.7"),
+    ("INTERNET", "5.7"),
+    ("TELEFON", "5.7"),
+    ("ILETISIM", "5.7"),
+    ("KIRTASIYE", "5.7"),
+    ("BAKIM", "5.7"),
+];
+
+/// Türkçe harfleri ASCII'ye katlar, noktalama işaretlerini boşluğa çevirir:
+/// "Bağış (nakdi)" → "BAGIS NAKDI"
+fn katla(metin: &str) -> String {
+    hesap_anahtari(metin)
+        .chars()
+        .map(|c| match c {
+            'İ' => 'I',
+            'Ğ' => 'G',
+            'Ş' => 'S',
+            'Ç' => 'C',
+            'Ö' => 'O',
+            'Ü' => 'U',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn kalem_bul(kurallar: &[(&str, &'static str)], metin: &str, varsayilan: &'static str) -> &'static str {
+    let metin = format!(" {} ", katla(metin));
+    kurallar
+        .iter()
+        .find(|(anahtar, _)| metin.contains(&format!(" {}", anahtar)))
+        .map(|(_, no)| *no)
+        .unwrap_or(varsayilan)
+}
+
+pub fn gelir_kalemi(tur_metni: &str) -> &'static str {
+    kalem_bul(GELIR_KURALLARI, tur_metni, "4.8")
+}
+
+pub fn gider_kalemi(tur_metni: &str) -> &'static str {
+    kalem_bul(GIDER_KURALLARI, tur_metni, "5.8")
+}
+
+// ============================================================================
+// ÜYE SAYILARI
+// ============================================================================
+
+#[derive(Debug, Clone, QueryableByName)]
+pub struct UyeKaydi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_no: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub cinsiyet: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub giris_tarihi: String,
+    /// cikis_tarihi; yoksa ayrılmış üyeler için pasife_alinma_tarihi
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub cikis_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+}
+
+impl UyeKaydi {
+    /// Çıkış girişten önce yazılmışsa giriş günü ayrılmış sayılır
+    fn cikis(&self) -> Option<&str> {
+        self.cikis_tarihi.as_deref().map(|c| c.max(self.giris_tarihi.as_str()))
+    }
+
+    /// `tarih` gününün başında üye mi (tarihler YYYY-MM-DD)
+    fn uye_mi(&self, tarih: &str) -> bool {
+        self.giris_tarihi.as_str() < tarih && self.cikis().is_none_or(|c| c >= tarih)
+    }
+
+    fn kaynak(&self, tarih: Option<&str>) -> AlanKaynagi {
+        AlanKaynagi {
+            tablo: "uyeler".to_string(),
+            kayit_id: self.id.clone(),
+            tarih: tarih.map(str::to_string),
+            aciklama: format!("{} {}", self.uye_no, self.ad_soyad),
+            tutar: None,
+        }
+    }
+}
+
+/// 2.1–2.6: yıl başı + giren − çıkan = yıl sonu; cinsiyet dağılımı yıl sonu üyeleri üzerinden
+pub fn uye_alanlari(uyeler: &[UyeKaydi], yil: i32) -> Vec<BeyannameAlani> {
+    let (bas, son) = yil_araligi(yil);
+    let yilda = |t: &str| t >= bas.as_str() && t < son.as_str();
+
+    let yil_basi: Vec<AlanKaynagi> = uyeler.iter().filter(|u| u.uye_mi(&bas)).map(|u| u.kaynak(None)).collect();
+    let giren: Vec<AlanKaynagi> = uyeler
+        .iter()
+        .filter(|u| yilda(&u.giris_tarihi))
+        .map(|u| u.kaynak(Some(&u.giris_tarihi)))
+        .collect();
+    let cikan: Vec<AlanKaynagi> = uyeler
+        .iter()
+        .filter_map(|u| u.cikis().filter(|c| yilda(c)).map(|c| u.kaynak(Some(c))))
+        .collect();
+    let yil_sonu: Vec<&UyeKaydi> = uyeler.iter().filter(|u| u.uye_mi(&son)).collect();
+
+    let cinsiyetli = |aranan: &str| -> Vec<AlanKaynagi> {
+        yil_sonu
+            .iter()
+            .filter(|u| u.cinsiyet.as_deref().map(katla).as_deref() == Some(aranan))
+            .map(|u| u.kaynak(None))
+            .collect()
+    };
+    let kadin = cinsiyetli("KADIN");
+    let erkek = cinsiyetli("ERKEK");
+
+    let sayi = |v: &Vec<AlanKaynagi>| AlanDegeri::Sayi(v.len() as i64);
+    vec![
+        BeyannameAlani::yeni("2.1", "Yıl başındaki üye sayısı", sayi(&yil_basi), yil_basi),
+        BeyannameAlani::yeni("2.2", "Yıl içinde kaydolan üye sayısı", sayi(&giren), giren),
+        BeyannameAlani::yeni("2.3", "Yıl içinde ayrılan üye sayısı", sayi(&cikan), cikan),
+        BeyannameAlani::yeni(
+            "2.4",
+            "Yıl sonundaki üye sayısı",
+            AlanDegeri::Sayi(yil_sonu.len() as i64),
+            yil_sonu.iter().map(|u| u.kaynak(None)).collect(),
+        ),
+        BeyannameAlani::yeni("2.5", "Yıl sonundaki kadın üye sayısı", sayi(&kadin), kadin),
+        BeyannameAlani::yeni("2.6", "Yıl sonundaki erkek üye sayısı", sayi(&erkek), erkek),
+    ]
+}
+
+fn uye_kayitlari(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<UyeKaydi>, String> {
+    diesel::sql_query(
+        "SELECT id, uye_no, ad_soyad, cinsiyet, substr(giris_tarihi, 1, 10) as giris_tarihi,
+                substr(COALESCE(cikis_tarihi,
+                                CASE WHEN durum = 'Ayrıldı' THEN pasife_alinma_tarihi END), 1, 10) as cikis_tarihi,
+                COALESCE(durum, 'Aktif') as durum
+         FROM uyeler
+         WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)
+         ORDER BY uye_no"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| format!("Database error (uyeler): {}", e))
+}
+
+// ============================================================================
+// GELİR / GİDER KALEMLERİ
+// ============================================================================
+
+#[derive(QueryableByName)]
+struct HareketKaydi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    belge_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    kategori: String,
+    /// Eşleme metni: tür kodu + tür adı + serbest tür alanı
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tur_metni: String,
+    /// TL karşılığı (dövizli kasada kayıt tarihindeki kurla)
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    para_birimi: String,
+}
+
+impl HareketKaydi {
+    fn kaynak(&self, tablo: &str) -> AlanKaynagi {
+        let mut aciklama = self.kategori.clone();
+        if let Some(belge) = self.belge_no.as_deref().filter(|b| !b.is_empty()) {
+            aciklama = format!("{} - {}", belge, aciklama);
+        }
+        if let Some(a) = self.aciklama.as_deref().filter(|a| !a.is_empty()) {
+            aciklama = format!("{} - {}", aciklama, a);
+        }
+        AlanKaynagi {
+            tablo: tablo.to_string(),
+            kayit_id: self.id.clone(),
+            tarih: Some(self.tarih.clone()),
+            aciklama,
+            tutar: Some(self.tutar),
+        }
+    }
+}
+
+fn hareketler(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+    gelir_mi: bool,
+) -> Result<Vec<HareketKaydi>, String> {
+    let (bas, son) = yil_araligi(yil);
+    let sorgu = if gelir_mi {
+        "SELECT g.id, substr(g.tarih, 1, 10) as tarih, COALESCE(g.makbuz_no, g.belge_no) as belge_no, g.aciklama,
+                COALESCE(NULLIF(TRIM(t.ad), ''), NULLIF(TRIM(g.gelir_turu), ''), 'Diğer Gelirler') as kategori,
+                COALESCE(t.kod, '') || ' ' || COALESCE(t.ad, '') || ' ' || COALESCE(g.gelir_turu, '') as tur_metni,
+                g.tutar, COALESCE(k.para_birimi, 'TRY') as para_birimi
+         FROM gelirler g
+         LEFT JOIN gelir_turleri t ON t.id = g.gelir_turu_id
+         LEFT JOIN kasalar k ON k.id = g.kasa_id
+         WHERE g.tenant_id = ?1 AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+           AND g.tarih >= ?2 AND g.tarih < ?3
+         ORDER BY g.tarih, g.created_at, g.id"
+    } else {
+        "SELECT g.id, substr(g.tarih, 1, 10) as tarih, COALESCE(g.fatura_no, g.islem_no) as belge_no, g.aciklama,
+                COALESCE(NULLIF(TRIM(t.ad), ''), NULLIF(TRIM(g.gider_turu), ''), 'Diğer Giderler') as kategori,
+                COALESCE(t.kod, '') || ' ' || COALESCE(t.ad, '') || ' ' || COALESCE(g.gider_turu, '') as tur_metni,
+                g.tutar, COALESCE(k.para_birimi, 'TRY') as para_birimi
+         FROM giderler g
+         LEFT JOIN gider_turleri t ON t.id = g.gider_turu_id
+         LEFT JOIN kasalar k ON k.id = g.kasa_id
+         WHERE g.tenant_id = ?1 AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+           AND g.tarih >= ?2 AND g.tarih < ?3
+         ORDER BY g.tarih, g.created_at, g.id"
+    };
+    let mut kayitlar: Vec<HareketKaydi> = diesel::sql_query(sorgu)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&bas)
+        .bind::<diesel::sql_types::Text, _>(&son)
+        .load(conn)
+        .map_err(|e| format!("Database error ({}): {}", if gelir_mi { "gelirler" } else { "giderler" }, e))?;
+    for k in kayitlar.iter_mut() {
+        k.tutar = tl_karsiligi(conn, tenant_id, &k.para_birimi, k.tutar, &k.tarih)?;
+    }
+    Ok(kayitlar)
+}
+
+/// Dövizli kasaların değerleme farkı (kur farkı); gelir-gider tablosuyla tutarlı olması için
+/// pozitif fark 4.8'e, negatif fark 5.8'e yazılır.
+fn kur_farki_kaynaklari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+) -> Result<Vec<AlanKaynagi>, String> {
+    Ok(kur_farklari(conn, tenant_id, yil)?
+        .into_iter()
+        .map(|k| AlanKaynagi {
+            tablo: "kasalar".to_string(),
+            kayit_id: k.kasa_id,
+            tarih: None,
+            aciklama: format!("Kur farkı - {} ({})", k.kasa_adi, k.para_birimi),
+            tutar: Some(k.tutar),
+        })
+        .collect())
+}
+
+/// Kayıtları kalemlere dağıtır; kalemler formdaki sırayla, en sonda toplam alanı
+fn kalem_alanlari(
+    kalemler: &[(&str, &str)],
+    toplam_alani: (&str, &str),
+    atamalar: Vec<(&'static str, AlanKaynagi)>,
+) -> (Vec<BeyannameAlani>, Para) {
+    let mut kaynaklar: Vec<Vec<AlanKaynagi>> = vec![Vec::new(); kalemler.len()];
+    for (no, kaynak) in atamalar {
+        let i = kalemler.iter().position(|(k, _)| *k == no).unwrap_or(kalemler.len() - 1);
+        kaynaklar[i].push(kaynak);
+    }
+
+    let mut toplam = Para::SIFIR;
+    let mut alanlar: Vec<BeyannameAlani> = kalemler
+        .iter()
+        .zip(kaynaklar)
+        .map(|((no, ad), kaynaklar)| {
+            let tutar: Para = kaynaklar.iter().filter_map(|k| k.tutar).sum();
+            toplam += tutar;
+            BeyannameAlani::yeni(no, ad, AlanDegeri::Tutar(tutar), kaynaklar)
+        })
+        .collect();
+    alanlar.push(BeyannameAlani::yeni(toplam_alani.0, toplam_alani.1, AlanDegeri::Tutar(toplam), Vec::new()));
+    (alanlar, toplam)
+}
+
+// ============================================================================
+// DİĞER BÖLÜMLER
+// ============================================================================
+
+#[derive(QueryableByName)]
+struct ToplantiKaydi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    baslik: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    toplanti_tipi: String,
+}
+
+impl ToplantiKaydi {
+    fn kaynak(&self) -> AlanKaynagi {
+        AlanKaynagi {
+            tablo: "toplantilar".to_string(),
+            kayit_id: self.id.clone(),
+            tarih: Some(self.tarih.clone()),
+            aciklama: self.baslik.clone(),
+            tutar: None,
+        }
+    }
+
+    fn tipi(&self, aranan: &str) -> bool {
+        katla(&self.toplanti_tipi).contains(aranan)
+    }
+}
+
+/// Yıl sonuna kadarki toplantılar (iptal edilenler hariç), tarih sırasıyla
+fn toplantilar(conn: &mut SqliteConnection, tenant_id: &str, son: &str) -> Result<Vec<ToplantiKaydi>, String> {
+    diesel::sql_query(
+        "SELECT id, baslik, substr(tarih, 1, 10) as tarih, COALESCE(toplanti_tipi, '') as toplanti_tipi
+         FROM toplantilar
+         WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)
+           AND tarih < ?2 AND COALESCE(durum, '') NOT IN ('İptal', 'iptal')
+         ORDER BY tarih, id"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(son)
+    .load(conn)
+    .map_err(|e| format!("Database error (toplantilar): {}", e))
+}
+
+/// Kurulun yıl sonunda görevde olan üyeleri: değer "Ad Soyad (Görev)" listesi, kaynaklar görev kayıtları
+fn kurul_alani(no: &str, ad: &str, gorevler: &[OrganGorevi]) -> BeyannameAlani {
+    let liste = |yedek: bool| -> Vec<String> {
+        gorevler
+            .iter()
+            .filter(|g| (g.gorev == "yedek_uye") == yedek)
+            .map(|g| format!("{} ({})", g.ad_soyad, organ_gorevi::gorev_adi(&g.gorev)))
+            .collect()
+    };
+    let (asiller, yedekler) = (liste(false), liste(true));
+    let mut deger = asiller.join(", ");
+    if !yedekler.is_empty() {
+        deger = format!("{}; {}", deger, yedekler.join(", "));
+    }
+    let kaynaklar = gorevler
+        .iter()
+        .map(|g| AlanKaynagi {
+            tablo: "organ_gorevleri".to_string(),
+            kayit_id: g.id.clone(),
+            tarih: Some(g.baslangic_tarihi.clone()),
+            aciklama: format!("{} {} - {}", g.uye_no.as_deref().unwrap_or(""), g.ad_soyad, organ_gorevi::gorev_adi(&g.gorev))
+                .trim()
+                .to_string(),
+            tutar: None,
+        })
+        .collect();
+    BeyannameAlani::yeni(no, ad, AlanDegeri::Metin(deger), kaynaklar)
+}
+
+fn demirbas_alanlari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    son: &str,
+) -> Result<(BeyannameAlani, BeyannameAlani), String> {
+    #[derive(QueryableByName)]
+    struct DemirbasRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        demirbas_no: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        alis_tarihi: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        alis_bedeli: Para,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        guncel_deger: Para,
+    }
+
+    // Satılan/hurdaya ayrılanlar mal varlığında gösterilmez
+    let demirbaslar: Vec<DemirbasRow> = diesel::sql_query(
+        "SELECT id, ad, demirbas_no, substr(alis_tarihi, 1, 10) as alis_tarihi,
+                COALESCE(alis_bedeli, 0) as alis_bedeli, COALESCE(guncel_deger, 0) as guncel_deger
+         FROM demirbaslar
+         WHERE tenant_id = ?1 AND is_active = 1
+           AND (alis_tarihi IS NULL OR alis_tarihi < ?2)
+           AND LOWER(COALESCE(durum, 'aktif')) NOT IN ('satildi', 'hurda')
+         ORDER BY demirbas_no, ad"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(son)
+    .load(conn)
+    .map_err(|e| format!("Database error (demirbaslar): {}", e))?;
+
+    let kaynaklar = |deger: fn(&DemirbasRow) -> Para| -> Vec<AlanKaynagi> {
+        demirbaslar
+            .iter()
+            .map(|d| AlanKaynagi {
+                tablo: "demirbaslar".to_string(),
+                kayit_id: d.id.clone(),
+                tarih: d.alis_tarihi.clone(),
+                aciklama: match &d.demirbas_no {
+                    Some(no) if !no.is_empty() => format!("{} {}", no, d.ad),
+                    _ => d.ad.clone(),
+                },
+                tutar: Some(deger(d)),
+            })
+            .collect()
+    };
+    let alis = kaynaklar(|d| d.alis_bedeli);
+    let guncel = kaynaklar(|d| d.guncel_deger);
+    let toplam = |v: &Vec<AlanKaynagi>| AlanDegeri::Tutar(v.iter().filter_map(|k| k.tutar).sum());
+
+    Ok((
+        BeyannameAlani::yeni("6.3", "Demirbaşlar (alış bedeli)", toplam(&alis), alis),
+        BeyannameAlani::yeni("6.4", "Demirbaşlar (güncel değer)", toplam(&guncel), guncel),
+    ))
+}
+
+/// Beyanname yılı ve öncesine ait açık aidat borçları (güncel kalan tutar üzerinden)
+fn aidat_alacaklari(conn: &mut SqliteConnection, tenant_id: &str, yil: i32) -> Result<BeyannameAlani, String> {
+    #[derive(QueryableByName)]
+    struct AlacakRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        kalan: Para,
+    }
+
+    let alacaklar: Vec<AlacakRow> = diesel::sql_query(
+        "SELECT a.id, COALESCE(u.ad_soyad, '-') as ad_soyad, a.yil, a.ay, a.kalan
+         FROM aidat_takip a
+         LEFT JOIN uyeler u ON u.id = a.uye_id
+         WHERE a.tenant_id = ?1 AND (a.is_deleted IS NULL OR a.is_deleted = 0)
+           AND a.durum != 'iptal' AND a.yil <= ?2 AND a.kalan > 0
+         ORDER BY a.yil, a.ay, u.ad_soyad"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .load(conn)
+    .map_err(|e| format!("Database error (aidat_takip): {}", e))?;
+
+    let kaynaklar: Vec<AlanKaynagi> = alacaklar
+        .into_iter()
+        .map(|a| AlanKaynagi {
+            tablo: "aidat_takip".to_string(),
+            kayit_id: a.id,
+            tarih: Some(format!("{}-{:02}", a.yil, a.ay)),
+            aciklama: format!("{} {}/{:02} aidatı", a.ad_soyad, a.yil, a.ay),
+            tutar: Some(a.kalan),
+        })
+        .collect();
+    let toplam: Para = kaynaklar.iter().filter_map(|k| k.tutar).sum();
+    Ok(BeyannameAlani::yeni("6.5", "Üyelerden aidat alacakları", AlanDegeri::Tutar(toplam), kaynaklar))
+}
+
+// ============================================================================
+// BEYANNAME
+// ============================================================================
+
+pub(crate) fn beyanname_hazirla(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+) -> Result<DernekBeyannamesi, String> {
+    let (bas, son) = yil_araligi(yil);
+    let mut notlar = Vec::new();
+
+    // 1. Dernek bilgileri
+    let dernek_adi: String = {
+        use crate::db::schema::tenants::dsl::*;
+        tenants
+            .filter(id.eq(tenant_id))
+            .select(name)
+            .first(conn)
+            .map_err(|e| format!("Database error (tenants): {}", e))?
+    };
+    let toplantilar = toplantilar(conn, tenant_id, &son)?;
+    let son_genel_kurul = toplantilar.iter().rev().find(|t| t.tipi("GENEL KURUL"));
+    if son_genel_kurul.is_none() {
+        notlar.push("Genel kurul toplantısı kaydı bulunamadı (1.3 boş).".to_string());
+    }
+    let dernek = BeyannameBolumu {
+        no: "1".to_string(),
+        baslik: "Dernek Bilgileri".to_string(),
+        alanlar: vec![
+            BeyannameAlani::yeni("1.1", "Derneğin adı", AlanDegeri::Metin(dernek_adi.clone()), Vec::new()),
+            BeyannameAlani::yeni(
+                "1.2",
+                "Beyanname dönemi",
+                AlanDegeri::Metin(format!("01.01.{} - 31.12.{}", yil, yil)),
+                Vec::new(),
+            ),
+            BeyannameAlani::yeni(
+                "1.3",
+                "Son genel kurul toplantı tarihi",
+                AlanDegeri::Metin(son_genel_kurul.map(|t| t.tarih.clone()).unwrap_or_default()),
+                son_genel_kurul.map(|t| vec![t.kaynak()]).unwrap_or_default(),
+            ),
+        ],
+    };
+
+    // 2. Üye bilgileri
+    let uyeler = uye_kayitlari(conn, tenant_id)?;
+    let ayrilmis_tarihsiz = uyeler.iter().filter(|u| u.durum == "Ayrıldı" && u.cikis_tarihi.is_none()).count();
+    if ayrilmis_tarihsiz > 0 {
+        notlar.push(format!(
+            "{} ayrılmış üyenin çıkış tarihi yok; bu üyeler hâlâ üye sayılmıştır.",
+            ayrilmis_tarihsiz
+        ));
+    }
+    let uye_alanlari = uye_alanlari(&uyeler, yil);
+    let cinsiyetsiz = match (&uye_alanlari[3].deger, &uye_alanlari[4].deger, &uye_alanlari[5].deger) {
+        (AlanDegeri::Sayi(t), AlanDegeri::Sayi(k), AlanDegeri::Sayi(e)) => t - k - e,
+        _ => 0,
+    };
+    if cinsiyetsiz > 0 {
+        notlar.push(format!(
+            "Yıl sonu üyelerinden {} kişinin cinsiyeti kayıtlı değil; 2.5 + 2.6, 2.4'ten azdır.",
+            cinsiyetsiz
+        ));
+    }
+    let uye = BeyannameBolumu {
+        no: "2".to_string(),
+        baslik: "Üye Bilgileri".to_string(),
+        alanlar: uye_alanlari,
+    };
+
+    // 3. Organlar: yıl içindeki toplantı sayıları ve yıl sonundaki kurul üyeleri
+    let yil_toplantilari: Vec<&ToplantiKaydi> = toplantilar.iter().filter(|t| t.tarih >= bas).collect();
+    let toplanti_alani = |no: &str, ad: &str, aranan: &str| {
+        let kaynaklar: Vec<AlanKaynagi> =
+            yil_toplantilari.iter().filter(|t| t.tipi(aranan)).map(|t| t.kaynak()).collect();
+        BeyannameAlani::yeni(no, ad, AlanDegeri::Sayi(kaynaklar.len() as i64), kaynaklar)
+    };
+    // Yıl sonunda görevdeki kurullar; asil üye sayısı yasal asgarinin altındaysa not düşülür
+    let yil_sonu = format!("{}-12-31", yil);
+    let mut kurul_alanlari = Vec::new();
+    for (no, organ, ad) in [
+        ("3.4", "yonetim_kurulu", "Yönetim kurulu üyeleri (yıl sonu)"),
+        ("3.5", "denetim_kurulu", "Denetim kurulu üyeleri (yıl sonu)"),
+    ] {
+        let gorevler: Vec<OrganGorevi> = organ_gorevi::gorevleri_getir(conn, tenant_id, Some(organ), Some(&yil_sonu))?
+            .into_iter()
+            .filter(|g| g.komisyon_adi.is_none())
+            .collect();
+        let asil = gorevler.iter().filter(|g| g.gorev != "yedek_uye").count() as i32;
+        let asgari = genel_kurul::ORGANLAR.iter().find(|(o, _)| *o == organ).map(|(_, n)| *n).unwrap_or(0);
+        if gorevler.is_empty() {
+            notlar.push(format!("{} görev kayıtları girilmemiş ({} boş).", genel_kurul::organ_adi(organ), no));
+        } else if asil < asgari {
+            notlar.push(format!(
+                "Yıl sonunda {} {} asil üyeden oluşuyor; yasal asgari {}.",
+                genel_kurul::organ_adi(organ).to_lowercase(),
+                asil,
+                asgari
+            ));
+        }
+        kurul_alanlari.push(kurul_alani(no, ad, &gorevler));
+    }
+    let mut organ_alanlari = vec![
+        toplanti_alani("3.1", "Yıl içinde yapılan genel kurul toplantısı sayısı", "GENEL KURUL"),
+        toplanti_alani("3.2", "Yıl içinde yapılan yönetim kurulu toplantısı sayısı", "YONETIM"),
+        toplanti_alani("3.3", "Yıl içinde yapılan denetim kurulu toplantısı sayısı", "DENETIM"),
+    ];
+    organ_alanlari.extend(kurul_alanlari);
+    let organlar = BeyannameBolumu {
+        no: "3".to_string(),
+        baslik: "Organlar".to_string(),
+        alanlar: organ_alanlari,
+    };
+
+    // 4-5. Gelirler ve giderler
+    let kur_farklari = kur_farki_kaynaklari(conn, tenant_id, yil)?;
+    let mut gelir_atamalari: Vec<(&'static str, AlanKaynagi)> = hareketler(conn, tenant_id, yil, true)?
+        .iter()
+        .map(|h| (gelir_kalemi(&h.tur_metni), h.kaynak("gelirler")))
+        .collect();
+    let mut gider_atamalari: Vec<(&'static str, AlanKaynagi)> = hareketler(conn, tenant_id, yil, false)?
+        .iter()
+        .map(|h| (gider_kalemi(&h.tur_metni), h.kaynak("giderler")))
+        .collect();
+    for mut k in kur_farklari {
+        match k.tutar {
+            Some(t) if t.is_positive() => gelir_atamalari.push(("4.8", k)),
+            Some(t) => {
+                k.tutar = Some(t.abs());
+                gider_atamalari.push(("5.8", k));
+            }
+            None => {}
+        }
+    }
+    let (gelir_alanlari, toplam_gelir) = kalem_alanlari(&GELIR_KALEMLERI, ("4.9", "Toplam gelirler"), gelir_atamalari);
+    let (gider_alanlari, toplam_gider) = kalem_alanlari(&GIDER_KALEMLERI, ("5.9", "Toplam giderler"), gider_atamalari);
+    let gelirler = BeyannameBolumu { no: "4".to_string(), baslik: "Gelirler".to_string(), alanlar: gelir_alanlari };
+    let giderler = BeyannameBolumu { no: "5".to_string(), baslik: "Giderler".to_string(), alanlar: gider_alanlari };
+
+    // 6. Mal varlığı (yıl sonu)
+    let mevcutlar = kasa_mevcutlari(conn, tenant_id, &son)?;
+    let mevcut_toplami = MevcutToplami::hesapla(&mevcutlar);
+    let kasa_kaynaklari = |banka: bool| -> Vec<AlanKaynagi> {
+        mevcutlar
+            .iter()
+            .filter(|m| (m.kasa_tipi == "Banka") == banka)
+            .map(|m| AlanKaynagi {
+                tablo: "kasalar".to_string(),
+                kayit_id: m.kasa_id.clone(),
+                tarih: None,
+                aciklama: if m.para_birimi == "TRY" {
+                    m.kasa_adi.clone()
+                } else {
+                    format!("{} ({} {})", m.kasa_adi, m.tutar.tr_format(), m.para_birimi)
+                },
+                tutar: Some(m.tl_tutar),
+            })
+            .collect()
+    };
+    if mevcutlar.iter().any(|m| m.para_birimi != "TRY") {
+        notlar.push("Dövizli kasaların yıl sonu bakiyeleri yıl sonu kuruyla TL'ye çevrilmiştir.".to_string());
+    }
+    let (demirbas_alis, demirbas_guncel) = demirbas_alanlari(conn, tenant_id, &son)?;
+    let mal_varligi = BeyannameBolumu {
+        no: "6".to_string(),
+        baslik: "Mal Varlığı (Yıl Sonu)".to_string(),
+        alanlar: vec![
+            BeyannameAlani::yeni("6.1", "Kasa mevcudu", AlanDegeri::Tutar(mevcut_toplami.kasa), kasa_kaynaklari(false)),
+            BeyannameAlani::yeni("6.2", "Banka mevcudu", AlanDegeri::Tutar(mevcut_toplami.banka), kasa_kaynaklari(true)),
+            demirbas_alis,
+            demirbas_guncel,
+            aidat_alacaklari(conn, tenant_id, yil)?,
+        ],
+    };
+
+    Ok(DernekBeyannamesi {
+        yil,
+        dernek_adi,
+        bolumler: vec![dernek, uye, organlar, gelirler, giderler, mal_varligi],
+        toplam_gelir,
+        toplam_gider,
+        notlar,
+    })
+}
+
+// ============================================================================
+// KOMUTLAR
+// ============================================================================
+
+/// Beyannamenin tüm bölüm ve alanları (kaynak dökümleri olmadan, yalnızca kaynak sayısı)
+#[tauri::command]
+pub async fn get_dernek_beyannamesi(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    yil: i32,
+) -> Result<DernekBeyannamesi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    Ok(beyanname_hazirla(&mut conn, &tenant_id_param, yil)?.kaynaklari_cikar())
+}
+
+/// Tek bir alanın değeri ve onu oluşturan kayıtlar (ör. alan_no = "4.1")
+#[tauri::command]
+pub async fn get_beyanname_alan_kaynaklari(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    yil: i32,
+    alan_no: String,
+) -> Result<BeyannameAlani, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let beyanname = beyanname_hazirla(&mut conn, &tenant_id_param, yil)?;
+    beyanname
+        .alan(&alan_no)
+        .cloned()
+        .ok_or_else(|| format!("Beyannamede {} numaralı alan yok", alan_no))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uye(id: &str, cinsiyet: Option<&str>, giris: &str, cikis: Option<&str>) -> UyeKaydi {
+        UyeKaydi {
+            id: id.to_string(),
+            uye_no: id.to_string(),
+            ad_soyad: id.to_string(),
+            cinsiyet: cinsiyet.map(str::to_string),
+            giris_tarihi: giris.to_string(),
+            cikis_tarihi: cikis.map(str::to_string),
+            durum: if cikis.is_some() { "Ayrıldı" } else { "Aktif" }.to_string(),
+        }
+    }
+
+    fn sayi(alan: &BeyannameAlani) -> i64 {
+        match alan.deger {
+            AlanDegeri::Sayi(n) => n,
+            _ => panic!("{} sayı değil", alan.no),
+        }
+    }
+
+    #[test]
+    fn uye_sayilari_tutarli() {
+        let uyeler = vec![
+            uye("eski", Some("Kadın"), "2020-03-01", None),
+            uye("ayrilan", Some("Erkek"), "2021-05-10", Some("2026-06-30")),
+            uye("yeni", Some("KADIN"), "2026-02-14", None),
+            uye("gelip_giden", None, "2026-04-01", Some("2026-09-01")),
+            uye("yilbasi_ayrilan", Some("Erkek"), "2019-01-01", Some("2026-01-01")),
+            uye("gelecek", Some("Erkek"), "2027-01-01", None),
+            uye("hatali_cikis", None, "2026-05-05", Some("2026-01-01")),
+        ];
+        let alanlar = uye_alanlari(&uyeler, 2026);
+        let [bas, giren, cikan, son, kadin, erkek] = [0, 1, 2, 3, 4, 5].map(|i| sayi(&alanlar[i]));
+
+        assert_eq!(bas, 3);
+        assert_eq!(giren, 3);
+        assert_eq!(cikan, 4);
+        assert_eq!(son, 2);
+        assert_eq!(bas + giren - cikan, son);
+        assert_eq!((kadin, erkek), (2, 0));
+        assert_eq!(alanlar[3].kaynaklar.iter().map(|k| k.kayit_id.as_str()).collect::<Vec<_>>(), ["eski", "yeni"]);
+    }
+
+    #[test]
+    fn tur_adlari_kalemlere_eslenir() {
+        assert_eq!(gelir_kalemi("AIDAT Aidat Geliri "), "4.1");
+        assert_eq!(gelir_kalemi("  AİDAT"), "4.1");
+        assert_eq!(gelir_kalemi("BAGIS Bağış "), "4.2");
+        assert_eq!(gelir_kalemi(" Belediye yardımı"), "4.3");
+        assert_eq!(gelir_kalemi(" Yurt dışı bağış"), "4.4");
+        assert_eq!(gelir_kalemi("ETK Etkinlik Geliri "), "4.5");
+        assert_eq!(gelir_kalemi("FAIZ Faiz Geliri "), "4.7");
+        assert_eq!(gelir_kalemi("DIGER Diğer Gelirler "), "4.8");
+
+        assert_eq!(gider_kalemi("PERS Personel Giderleri "), "5.1");
+        assert_eq!(gider_kalemi("KIRA Kira "), "5.2");
+        assert_eq!(gider_kalemi("KIRTASIYE Kırtasiye "), "5.7");
+        assert_eq!(gider_kalemi("SU Su "), "5.7");
+        assert_eq!(gider_kalemi(" Sunucu bedeli"), "5.8");
+        assert_eq!(gider_kalemi("DEMIRBAS Demirbaş "), "5.6");
+        assert_eq!(gider_kalemi(""), "5.8");
+    }
+
+    #[test]
+    fn kalem_toplamlari_ve_siralama() {
+        let kaynak = |id: &str, kurus: i64| AlanKaynagi {
+            tablo: "gelirler".to_string(),
+            kayit_id: id.to_string(),
+            tarih: None,
+            aciklama: String::new(),
+            tutar: Some(Para::from_kurus(kurus)),
+        };
+        let (alanlar, toplam) = kalem_alanlari(
+            &GELIR_KALEMLERI,
+            ("4.9", "Toplam gelirler"),
+            vec![("4.2", kaynak("a", 1_000)), ("4.1", kaynak("b", 250)), ("4.2", kaynak("c", 500))],
+        );
+        assert_eq!(alanlar.len(), 9);
+        assert_eq!(alanlar.iter().map(|a| a.no.as_str()).collect::<Vec<_>>()[..3], ["4.1", "4.2", "4.3"]);
+        assert_eq!(alanlar[1].deger, AlanDegeri::Tutar(Para::from_kurus(1_500)));
+        assert_eq!(alanlar[1].kaynak_sayisi, 2);
+        assert_eq!(alanlar[2].deger, AlanDegeri::Tutar(Para::SIFIR));
+        assert_eq!(toplam, Para::from_kurus(1_750));
+        assert_eq!(alanlar[8].deger, AlanDegeri::Tutar(toplam));
+    }
+}
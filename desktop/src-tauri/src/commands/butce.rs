@@ -3,6 +3,7 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize, Queryable, QueryableByName)]
@@ -86,25 +87,27 @@ pub fn create_butce(
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-        diesel::sql_query(
-            "INSERT INTO butce (id, tenant_id, yil, kategori, alt_kategori, donem, planlanan_gelir, planlanan_gider, gerceklesen_gelir, gerceklesen_gider, notlar, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Integer, _>(data.yil)
-        .bind::<diesel::sql_types::Text, _>(&data.kategori)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.donem)
-        .bind::<diesel::sql_types::Double, _>(data.planlanan_gelir)
-        .bind::<diesel::sql_types::Double, _>(data.planlanan_gider)
-        .bind::<diesel::sql_types::Double, _>(0.0)  // gerceklesen_gelir başlangıç değeri
-        .bind::<diesel::sql_types::Double, _>(0.0)  // gerceklesen_gider başlangıç değeri
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("create_butce"), &tenant_id_param, "butce", &new_id, |conn| {
+            diesel::sql_query(
+                "INSERT INTO butce (id, tenant_id, yil, kategori, alt_kategori, donem, planlanan_gelir, planlanan_gider, gerceklesen_gelir, gerceklesen_gider, notlar, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&new_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Integer, _>(data.yil)
+            .bind::<diesel::sql_types::Text, _>(&data.kategori)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.donem)
+            .bind::<diesel::sql_types::Double, _>(data.planlanan_gelir)
+            .bind::<diesel::sql_types::Double, _>(data.planlanan_gider)
+            .bind::<diesel::sql_types::Double, _>(0.0)  // gerceklesen_gelir başlangıç değeri
+            .bind::<diesel::sql_types::Double, _>(0.0)  // gerceklesen_gider başlangıç değeri
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
     }
 
     let db = state.db.lock().unwrap();
@@ -131,22 +134,24 @@ pub fn update_butce(
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-        diesel::sql_query(
-            "UPDATE butce SET yil = ?1, kategori = ?2, alt_kategori = ?3, donem = ?4, planlanan_gelir = ?5, planlanan_gider = ?6, notlar = ?7, updated_at = ?8
-             WHERE id = ?9 AND tenant_id = ?10"
-        )
-        .bind::<diesel::sql_types::Integer, _>(data.yil)
-        .bind::<diesel::sql_types::Text, _>(&data.kategori)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.donem)
-        .bind::<diesel::sql_types::Double, _>(data.planlanan_gelir)
-        .bind::<diesel::sql_types::Double, _>(data.planlanan_gider)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&butce_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("update_butce"), &tenant_id_param, "butce", &butce_id, |conn| {
+            diesel::sql_query(
+                "UPDATE butce SET yil = ?1, kategori = ?2, alt_kategori = ?3, donem = ?4, planlanan_gelir = ?5, planlanan_gider = ?6, notlar = ?7, updated_at = ?8
+                 WHERE id = ?9 AND tenant_id = ?10"
+            )
+            .bind::<diesel::sql_types::Integer, _>(data.yil)
+            .bind::<diesel::sql_types::Text, _>(&data.kategori)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.donem)
+            .bind::<diesel::sql_types::Double, _>(data.planlanan_gelir)
+            .bind::<diesel::sql_types::Double, _>(data.planlanan_gider)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&butce_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
     }
 
     let db = state.db.lock().unwrap();
@@ -169,13 +174,15 @@ pub fn delete_butce(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "DELETE FROM butce WHERE id = ?1 AND tenant_id = ?2"
-    )
-    .bind::<diesel::sql_types::Text, _>(&butce_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_butce"), &tenant_id_param, "butce", &butce_id, |conn| {
+        diesel::sql_query(
+            "DELETE FROM butce WHERE id = ?1 AND tenant_id = ?2"
+        )
+        .bind::<diesel::sql_types::Text, _>(&butce_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -212,16 +219,18 @@ pub fn update_butce_gerceklesen(
     let new_gelir = request.gerceklesen_gelir.unwrap_or(0.0);
     let new_gider = request.gerceklesen_gider.unwrap_or(0.0);
 
-    diesel::sql_query(
-        "UPDATE butce SET gerceklesen_gelir = gerceklesen_gelir + ?1, gerceklesen_gider = gerceklesen_gider + ?2, updated_at = ?3 WHERE id = ?4 AND tenant_id = ?5"
-    )
-    .bind::<diesel::sql_types::Double, _>(new_gelir)
-    .bind::<diesel::sql_types::Double, _>(new_gider)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&butce_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("update_butce_gerceklesen"), &tenant_id_param, "butce", &butce_id, |conn| {
+        diesel::sql_query(
+            "UPDATE butce SET gerceklesen_gelir = gerceklesen_gelir + ?1, gerceklesen_gider = gerceklesen_gider + ?2, updated_at = ?3 WHERE id = ?4 AND tenant_id = ?5"
+        )
+        .bind::<diesel::sql_types::Double, _>(new_gelir)
+        .bind::<diesel::sql_types::Double, _>(new_gider)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&butce_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     diesel::sql_query("SELECT * FROM butce WHERE id = ?1")
         .bind::<diesel::sql_types::Text, _>(&butce_id)
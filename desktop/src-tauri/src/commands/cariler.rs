@@ -3,7 +3,13 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim::{self, Denetim};
+use diesel::sqlite::SqliteConnection;
 use serde::{Serialize, Deserialize};
+use crate::db::para::{KurusToplam, Para};
+use crate::db::yevmiye;
+use crate::db::donem_kilidi;
+use crate::commands::mali::outbox_err;
 
 #[derive(Debug, Serialize, Deserialize, QueryableByName)]
 pub struct Cari {
@@ -47,11 +53,11 @@ pub struct Cari {
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub iban: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub borc_bakiye: Option<f64>,
+    pub borc_bakiye: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub alacak_bakiye: Option<f64>,
+    pub alacak_bakiye: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub kredi_limiti: Option<f64>,
+    pub kredi_limiti: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
     pub odeme_vadesi: Option<i32>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
@@ -84,7 +90,7 @@ pub struct CariInput {
     pub banka_adi: Option<String>,
     pub iban: Option<String>,
     pub odeme_vade: Option<i32>,
-    pub risk_limiti: Option<f64>,
+    pub risk_limiti: Option<Para>,
     pub notlar: Option<String>,
 }
 
@@ -103,11 +109,11 @@ pub struct CariHareket {
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub vade_tarihi: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub tutar: f64,
+    pub tutar: Para,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub odenen: Option<f64>,
+    pub odenen: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub kalan: Option<f64>,
+    pub kalan: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub belge_turu: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
@@ -130,8 +136,8 @@ pub struct CariHareket {
 pub struct CariOzet {
     pub toplam: i64,
     pub aktif: i64,
-    pub toplam_borc: f64,
-    pub toplam_alacak: f64,
+    pub toplam_borc: Para,
+    pub toplam_alacak: Para,
     pub musteri_sayisi: i64,
     pub tedarikci_sayisi: i64,
 }
@@ -142,12 +148,6 @@ pub struct CountResult {
     pub count: i64,
 }
 
-#[derive(Debug, Serialize, QueryableByName)]
-pub struct SumResult {
-    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub total: Option<f64>,
-}
-
 #[tauri::command]
 pub fn get_cariler(
     state: State<AppState>,
@@ -211,7 +211,7 @@ pub fn create_cari(
     let now = Utc::now().naive_utc().to_string();
     let cari_tipi = data.tip.unwrap_or_else(|| "Müşteri".to_string());
     let odeme_vadesi = data.odeme_vade.unwrap_or(30);
-    let kredi_limiti = data.risk_limiti.unwrap_or(0.0);
+    let kredi_limiti = data.risk_limiti.unwrap_or_default();
 
     let db = state.db.lock().unwrap();
     let pool = db.as_ref().ok_or("Database not initialized")?;
@@ -231,35 +231,37 @@ pub fn create_cari(
         Some(format!("CRI-{:04}", count_result.count + 1))
     };
 
-    diesel::sql_query(
-        "INSERT INTO cariler (id, tenant_id, cari_kodu, cari_tipi, unvan, vergi_dairesi, vergi_no, tc_no, yetkili_kisi, telefon, telefon2, email, web, adres, il, ilce, posta_kodu, banka_adi, iban, odeme_vadesi, kredi_limiti, notlar, borc_bakiye, alacak_bakiye, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, 0, 0, 1, ?23, ?24)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&new_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&cari_kodu)
-    .bind::<diesel::sql_types::Text, _>(&cari_tipi)
-    .bind::<diesel::sql_types::Text, _>(&data.unvan)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_dairesi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tc_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetkili_kisi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon2)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.email)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.web)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.adres)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.il)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.ilce)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.posta_kodu)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.banka_adi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.iban)
-    .bind::<diesel::sql_types::Integer, _>(odeme_vadesi)
-    .bind::<diesel::sql_types::Double, _>(kredi_limiti)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("create_cari"), &tenant_id_param, "cariler", &new_id, |conn| {
+        diesel::sql_query(
+            "INSERT INTO cariler (id, tenant_id, cari_kodu, cari_tipi, unvan, vergi_dairesi, vergi_no, tc_no, yetkili_kisi, telefon, telefon2, email, web, adres, il, ilce, posta_kodu, banka_adi, iban, odeme_vadesi, kredi_limiti, notlar, borc_bakiye, alacak_bakiye, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, 0, 0, 1, ?23, ?24)"
+        )
+        .bind::<diesel::sql_types::Text, _>(&new_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&cari_kodu)
+        .bind::<diesel::sql_types::Text, _>(&cari_tipi)
+        .bind::<diesel::sql_types::Text, _>(&data.unvan)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_dairesi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tc_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetkili_kisi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon2)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.email)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.web)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.adres)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.il)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.ilce)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.posta_kodu)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.banka_adi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.iban)
+        .bind::<diesel::sql_types::Integer, _>(odeme_vadesi)
+        .bind::<diesel::sql_types::Double, _>(kredi_limiti)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(new_id)
 }
@@ -274,40 +276,42 @@ pub fn update_cari(
     let now = Utc::now().naive_utc().to_string();
     let cari_tipi = data.tip.unwrap_or_else(|| "Müşteri".to_string());
     let odeme_vadesi = data.odeme_vade.unwrap_or(30);
-    let kredi_limiti = data.risk_limiti.unwrap_or(0.0);
+    let kredi_limiti = data.risk_limiti.unwrap_or_default();
 
     let db = state.db.lock().unwrap();
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE cariler SET cari_kodu = ?1, cari_tipi = ?2, unvan = ?3, vergi_dairesi = ?4, vergi_no = ?5, tc_no = ?6, yetkili_kisi = ?7, telefon = ?8, telefon2 = ?9, email = ?10, web = ?11, adres = ?12, il = ?13, ilce = ?14, posta_kodu = ?15, banka_adi = ?16, iban = ?17, odeme_vadesi = ?18, kredi_limiti = ?19, notlar = ?20, updated_at = ?21 WHERE id = ?22 AND tenant_id = ?23"
-    )
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.cari_kodu)
-    .bind::<diesel::sql_types::Text, _>(&cari_tipi)
-    .bind::<diesel::sql_types::Text, _>(&data.unvan)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_dairesi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tc_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetkili_kisi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon2)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.email)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.web)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.adres)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.il)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.ilce)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.posta_kodu)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.banka_adi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.iban)
-    .bind::<diesel::sql_types::Integer, _>(odeme_vadesi)
-    .bind::<diesel::sql_types::Double, _>(kredi_limiti)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&cari_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("update_cari"), &tenant_id_param, "cariler", &cari_id, |conn| {
+        diesel::sql_query(
+            "UPDATE cariler SET cari_kodu = ?1, cari_tipi = ?2, unvan = ?3, vergi_dairesi = ?4, vergi_no = ?5, tc_no = ?6, yetkili_kisi = ?7, telefon = ?8, telefon2 = ?9, email = ?10, web = ?11, adres = ?12, il = ?13, ilce = ?14, posta_kodu = ?15, banka_adi = ?16, iban = ?17, odeme_vadesi = ?18, kredi_limiti = ?19, notlar = ?20, updated_at = ?21 WHERE id = ?22 AND tenant_id = ?23"
+        )
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.cari_kodu)
+        .bind::<diesel::sql_types::Text, _>(&cari_tipi)
+        .bind::<diesel::sql_types::Text, _>(&data.unvan)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_dairesi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.vergi_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tc_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yetkili_kisi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.telefon2)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.email)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.web)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.adres)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.il)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.ilce)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.posta_kodu)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.banka_adi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.iban)
+        .bind::<diesel::sql_types::Integer, _>(odeme_vadesi)
+        .bind::<diesel::sql_types::Double, _>(kredi_limiti)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&cari_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -325,14 +329,16 @@ pub fn delete_cari(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE cariler SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&cari_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_cari"), &tenant_id_param, "cariler", &cari_id, |conn| {
+        diesel::sql_query(
+            "UPDATE cariler SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&cari_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -349,14 +355,16 @@ pub fn activate_cari(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE cariler SET is_active = 1, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&cari_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("activate_cari"), &tenant_id_param, "cariler", &cari_id, |conn| {
+        diesel::sql_query(
+            "UPDATE cariler SET is_active = 1, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&cari_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -386,29 +394,50 @@ pub fn create_cari_hareket(
     tenant_id_param: String,
     cari_id: String,
     hareket_tipi: String,
-    tutar: f64,
+    tutar: Para,
     aciklama: Option<String>,
     belge_no: Option<String>,
     tarih: Option<String>,
     kasa_id: Option<String>,
 ) -> Result<String, String> {
-    let new_id = Uuid::new_v4().to_string();
-    let now = Utc::now().naive_utc().to_string();
-    let tarih = tarih.unwrap_or_else(|| now.split('T').next().unwrap_or(&now).to_string());
-
     let db = state.db.lock().unwrap();
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("create_cari_hareket");
+    cari_hareket_ekle(&mut conn, &mut denetim, &tenant_id_param, &cari_id, &hareket_tipi, tutar, aciklama, belge_no, tarih, kasa_id)
+}
+
+/// Cari hareket kaydı: cari bakiyesi, kasalı tahsilatta bağlı gelir ve kasa bakiyesi,
+/// yevmiye tek transaction'da. Hareket kimliğini döner.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn cari_hareket_ekle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id_param: &str,
+    cari_id: &str,
+    hareket_tipi: &str,
+    tutar: Para,
+    aciklama: Option<String>,
+    belge_no: Option<String>,
+    tarih: Option<String>,
+    kasa_id: Option<String>,
+) -> Result<String, String> {
+    let new_id = Uuid::new_v4().to_string();
+    let now = Utc::now().naive_utc().to_string();
+    let tarih = tarih.unwrap_or_else(|| now.split('T').next().unwrap_or(&now).to_string());
+
     // Tüm işlem tek transaction — kasa, cari, gelir atomic güncelleniyor.
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, tenant_id_param, "cari_hareketler", &new_id).map_err(outbox_err)?;
+        donem_kilidi::tarihi_dogrula(conn, tenant_id_param, &tarih).map_err(outbox_err)?;
         diesel::sql_query(
             "INSERT INTO cari_hareketler (id, tenant_id, cari_id, hareket_tipi, tarih, tutar, kalan, aciklama, belge_no, kasa_id, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 1, ?11, ?12)"
         )
         .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&cari_id)
-        .bind::<diesel::sql_types::Text, _>(&hareket_tipi)
+        .bind::<diesel::sql_types::Text, _>(tenant_id_param)
+        .bind::<diesel::sql_types::Text, _>(cari_id)
+        .bind::<diesel::sql_types::Text, _>(hareket_tipi)
         .bind::<diesel::sql_types::Text, _>(&tarih)
         .bind::<diesel::sql_types::Double, _>(tutar)
         .bind::<diesel::sql_types::Double, _>(tutar)
@@ -422,19 +451,19 @@ pub fn create_cari_hareket(
         // Cari bakiye güncelle
         if hareket_tipi == "Borç" {
             diesel::sql_query(
-                "UPDATE cariler SET borc_bakiye = COALESCE(borc_bakiye, 0) + ?1, updated_at = ?2 WHERE id = ?3"
+                "UPDATE cariler SET borc_bakiye = ROUND(COALESCE(borc_bakiye, 0) + ?1, 2), updated_at = ?2 WHERE id = ?3"
             )
             .bind::<diesel::sql_types::Double, _>(tutar)
             .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(&cari_id)
+            .bind::<diesel::sql_types::Text, _>(cari_id)
             .execute(conn)?;
         } else {
             diesel::sql_query(
-                "UPDATE cariler SET alacak_bakiye = COALESCE(alacak_bakiye, 0) + ?1, updated_at = ?2 WHERE id = ?3"
+                "UPDATE cariler SET alacak_bakiye = ROUND(COALESCE(alacak_bakiye, 0) + ?1, 2), updated_at = ?2 WHERE id = ?3"
             )
             .bind::<diesel::sql_types::Double, _>(tutar)
             .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(&cari_id)
+            .bind::<diesel::sql_types::Text, _>(cari_id)
             .execute(conn)?;
         }
 
@@ -445,8 +474,8 @@ pub fn create_cari_hareket(
                 let cari_unvan: String = diesel::sql_query(
                     "SELECT unvan FROM cariler WHERE id = ?1 AND tenant_id = ?2"
                 )
-                .bind::<diesel::sql_types::Text, _>(&cari_id)
-                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+                .bind::<diesel::sql_types::Text, _>(cari_id)
+                .bind::<diesel::sql_types::Text, _>(tenant_id_param)
                 .get_result::<CariUnvan>(conn)
                 .map(|r| r.unvan)
                 .unwrap_or_else(|_| "Cari".to_string());
@@ -461,7 +490,7 @@ pub fn create_cari_hareket(
                      VALUES (?1, ?2, ?3, 'CARI_TAHSILAT', ?4, ?5, ?6, ?7, ?8, ?9)"
                 )
                 .bind::<diesel::sql_types::Text, _>(&gelir_id)
-                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+                .bind::<diesel::sql_types::Text, _>(tenant_id_param)
                 .bind::<diesel::sql_types::Text, _>(k_id)
                 .bind::<diesel::sql_types::Text, _>(&tarih)
                 .bind::<diesel::sql_types::Double, _>(tutar)
@@ -482,9 +511,17 @@ pub fn create_cari_hareket(
 
                 // Kasa bakiyesini gelirler SUM'ından yeniden hesapla (direkt += yerine).
                 crate::commands::mali::update_kasa_bakiye(conn, k_id)?;
+
+                // Yevmiye: gelir cari hareketine bağlandıktan sonra işlenir (Kasa / Cari)
+                yevmiye::kaydi_isle(conn, tenant_id_param, "gelirler", &gelir_id)
+                    .map_err(outbox_err)?;
             }
         }
 
+        yevmiye::kaydi_isle(conn, tenant_id_param, "cari_hareketler", &new_id)
+            .map_err(outbox_err)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e| format!("Cari hareket kaydedilemedi: {}", e))?;
 
@@ -502,7 +539,7 @@ pub fn odeme_kaydet_cari(
     state: State<AppState>,
     tenant_id_param: String,
     cari_id: String,
-    tutar: f64,
+    tutar: Para,
     aciklama: Option<String>,
     belge_no: Option<String>,
     kasa_id: Option<String>,
@@ -523,12 +560,12 @@ pub fn get_cari_ekstre(
     
     let hareketler = get_cari_hareketler(state, tenant_id_param, cari_id)?;
     
-    let toplam_borc: f64 = hareketler.iter()
+    let toplam_borc: Para = hareketler.iter()
         .filter(|h| h.hareket_tipi == "Borç")
         .map(|h| h.tutar)
         .sum();
     
-    let toplam_alacak: f64 = hareketler.iter()
+    let toplam_alacak: Para = hareketler.iter()
         .filter(|h| h.hareket_tipi == "Alacak")
         .map(|h| h.tutar)
         .sum();
@@ -568,22 +605,20 @@ pub fn get_cari_ozet(
     .count;
 
     let toplam_borc = diesel::sql_query(
-        "SELECT COALESCE(SUM(borc_bakiye), 0) as total FROM cariler WHERE tenant_id = ?1 AND is_active = 1"
+        "SELECT COALESCE(SUM(CAST(ROUND(borc_bakiye * 100) AS INTEGER)), 0) as kurus FROM cariler WHERE tenant_id = ?1 AND is_active = 1"
     )
     .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<SumResult>(&mut conn)
+    .get_result::<KurusToplam>(&mut conn)
     .map_err(|e| e.to_string())?
-    .total
-    .unwrap_or(0.0);
+    .para();
 
     let toplam_alacak = diesel::sql_query(
-        "SELECT COALESCE(SUM(alacak_bakiye), 0) as total FROM cariler WHERE tenant_id = ?1 AND is_active = 1"
+        "SELECT COALESCE(SUM(CAST(ROUND(alacak_bakiye * 100) AS INTEGER)), 0) as kurus FROM cariler WHERE tenant_id = ?1 AND is_active = 1"
     )
     .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<SumResult>(&mut conn)
+    .get_result::<KurusToplam>(&mut conn)
     .map_err(|e| e.to_string())?
-    .total
-    .unwrap_or(0.0);
+    .para();
 
     let musteri_sayisi = diesel::sql_query(
         "SELECT COUNT(*) as count FROM cariler WHERE tenant_id = ?1 AND is_active = 1 AND (cari_tipi = 'Müşteri' OR cari_tipi = 'Hem Müşteri Hem Tedarikçi')"
@@ -3,6 +3,7 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize, QueryableByName)]
@@ -181,32 +182,34 @@ pub fn create_demirbas(
         Some(format!("DMB-{:04}", count_result.count + 1))
     };
 
-    diesel::sql_query(
-        "INSERT INTO demirbaslar (id, tenant_id, demirbas_no, ad, kategori, marka_model, seri_no, alis_tarihi, alis_bedeli, amortisman_suresi, amortisman_turu, guncel_deger, konum, sorumlu_uye_id, durum, garanti_bitis, fatura_no, tedarikci, notlar, gider_id, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'Doğrusal', ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, 1, ?20, ?21)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&new_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&demirbas_no)
-    .bind::<diesel::sql_types::Text, _>(&data.ad)
-    .bind::<diesel::sql_types::Text, _>(&kategori)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.marka_model)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.seri_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alis_tarihi)
-    .bind::<diesel::sql_types::Double, _>(alis_bedeli)
-    .bind::<diesel::sql_types::Integer, _>(amortisman_suresi)
-    .bind::<diesel::sql_types::Double, _>(alis_bedeli)  // guncel_deger = alis_bedeli
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.konum)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.sorumlu_uye_id)
-    .bind::<diesel::sql_types::Text, _>(&durum)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.garanti_bitis)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tedarikci)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gider_id)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("create_demirbas"), &tenant_id_param, "demirbaslar", &new_id, |conn| {
+        diesel::sql_query(
+            "INSERT INTO demirbaslar (id, tenant_id, demirbas_no, ad, kategori, marka_model, seri_no, alis_tarihi, alis_bedeli, amortisman_suresi, amortisman_turu, guncel_deger, konum, sorumlu_uye_id, durum, garanti_bitis, fatura_no, tedarikci, notlar, gider_id, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'Doğrusal', ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, 1, ?20, ?21)"
+        )
+        .bind::<diesel::sql_types::Text, _>(&new_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&demirbas_no)
+        .bind::<diesel::sql_types::Text, _>(&data.ad)
+        .bind::<diesel::sql_types::Text, _>(&kategori)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.marka_model)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.seri_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alis_tarihi)
+        .bind::<diesel::sql_types::Double, _>(alis_bedeli)
+        .bind::<diesel::sql_types::Integer, _>(amortisman_suresi)
+        .bind::<diesel::sql_types::Double, _>(alis_bedeli)  // guncel_deger = alis_bedeli
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.konum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.sorumlu_uye_id)
+        .bind::<diesel::sql_types::Text, _>(&durum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.garanti_bitis)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tedarikci)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gider_id)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(new_id)
 }
@@ -228,29 +231,31 @@ pub fn update_demirbas(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE demirbaslar SET demirbas_no = ?1, ad = ?2, kategori = ?3, marka_model = ?4, seri_no = ?5, alis_tarihi = ?6, alis_bedeli = ?7, amortisman_suresi = ?8, konum = ?9, sorumlu_uye_id = ?10, durum = ?11, garanti_bitis = ?12, fatura_no = ?13, tedarikci = ?14, notlar = ?15, updated_at = ?16 WHERE id = ?17 AND tenant_id = ?18"
-    )
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.demirbas_no)
-    .bind::<diesel::sql_types::Text, _>(&data.ad)
-    .bind::<diesel::sql_types::Text, _>(&kategori)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.marka_model)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.seri_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alis_tarihi)
-    .bind::<diesel::sql_types::Double, _>(alis_bedeli)
-    .bind::<diesel::sql_types::Integer, _>(amortisman_suresi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.konum)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.sorumlu_uye_id)
-    .bind::<diesel::sql_types::Text, _>(&durum)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.garanti_bitis)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tedarikci)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&demirbas_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("update_demirbas"), &tenant_id_param, "demirbaslar", &demirbas_id, |conn| {
+        diesel::sql_query(
+            "UPDATE demirbaslar SET demirbas_no = ?1, ad = ?2, kategori = ?3, marka_model = ?4, seri_no = ?5, alis_tarihi = ?6, alis_bedeli = ?7, amortisman_suresi = ?8, konum = ?9, sorumlu_uye_id = ?10, durum = ?11, garanti_bitis = ?12, fatura_no = ?13, tedarikci = ?14, notlar = ?15, updated_at = ?16 WHERE id = ?17 AND tenant_id = ?18"
+        )
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.demirbas_no)
+        .bind::<diesel::sql_types::Text, _>(&data.ad)
+        .bind::<diesel::sql_types::Text, _>(&kategori)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.marka_model)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.seri_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alis_tarihi)
+        .bind::<diesel::sql_types::Double, _>(alis_bedeli)
+        .bind::<diesel::sql_types::Integer, _>(amortisman_suresi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.konum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.sorumlu_uye_id)
+        .bind::<diesel::sql_types::Text, _>(&durum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.garanti_bitis)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tedarikci)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&demirbas_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -267,14 +272,16 @@ pub fn delete_demirbas(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE demirbaslar SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&demirbas_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_demirbas"), &tenant_id_param, "demirbaslar", &demirbas_id, |conn| {
+        diesel::sql_query(
+            "UPDATE demirbaslar SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&demirbas_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -291,14 +298,16 @@ pub fn activate_demirbas(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE demirbaslar SET is_active = 1, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&demirbas_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("activate_demirbas"), &tenant_id_param, "demirbaslar", &demirbas_id, |conn| {
+        diesel::sql_query(
+            "UPDATE demirbaslar SET is_active = 1, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&demirbas_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -0,0 +1,139 @@
+// Denetim izi sorgu komutları
+// Kayıtlar db::denetim tarafından yazım komutlarıyla aynı transaction'da eklenir;
+// buradaki komutlar bir kaydın ve bir kullanıcının değişiklik geçmişini sunar.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use tauri::State;
+
+#[derive(QueryableByName)]
+struct DenetimSatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    kullanici_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    kullanici_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    komut: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    table_name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    record_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    islem: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    once: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    sonra: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    degisen_alanlar: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DenetimKaydi {
+    pub id: String,
+    pub kullanici_id: Option<String>,
+    pub kullanici_adi: Option<String>,
+    pub komut: String,
+    pub table_name: String,
+    pub record_id: String,
+    pub islem: String,
+    pub once: Option<serde_json::Value>,
+    pub sonra: Option<serde_json::Value>,
+    pub degisen_alanlar: Vec<String>,
+    pub created_at: String,
+}
+
+impl From<DenetimSatiri> for DenetimKaydi {
+    fn from(s: DenetimSatiri) -> Self {
+        let json = |v: Option<String>| v.and_then(|t| serde_json::from_str(&t).ok());
+        Self {
+            id: s.id,
+            kullanici_id: s.kullanici_id,
+            kullanici_adi: s.kullanici_adi,
+            komut: s.komut,
+            table_name: s.table_name,
+            record_id: s.record_id,
+            islem: s.islem,
+            once: json(s.once),
+            sonra: json(s.sonra),
+            degisen_alanlar: s
+                .degisen_alanlar
+                .and_then(|t| serde_json::from_str(&t).ok())
+                .unwrap_or_default(),
+            created_at: s.created_at,
+        }
+    }
+}
+
+const DENETIM_KOLONLARI: &str = "id, kullanici_id, kullanici_adi, komut, table_name, record_id, islem, \
+                                 once, sonra, degisen_alanlar, created_at";
+
+/// Bir kaydın tüm değişiklik geçmişi, eskiden yeniye
+#[tauri::command]
+pub async fn get_kayit_gecmisi(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    table_name: String,
+    record_id: String,
+) -> Result<Vec<DenetimKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let satirlar: Vec<DenetimSatiri> = diesel::sql_query(format!(
+        "SELECT {} FROM denetim_kayitlari
+         WHERE tenant_id = ?1 AND table_name = ?2 AND record_id = ?3
+         ORDER BY created_at, rowid",
+        DENETIM_KOLONLARI
+    ))
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Text, _>(&table_name)
+    .bind::<diesel::sql_types::Text, _>(&record_id)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(satirlar.into_iter().map(DenetimKaydi::from).collect())
+}
+
+/// Bir kullanıcının yaptığı değişiklikler, yeniden eskiye.
+/// Tarihler "YYYY-MM-DD" (bitiş günü dahil); limit verilmezse 500.
+#[tauri::command]
+pub async fn get_kullanici_islemleri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kullanici_id: String,
+    baslangic_tarihi: Option<String>,
+    bitis_tarihi: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<DenetimKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let satirlar: Vec<DenetimSatiri> = diesel::sql_query(format!(
+        "SELECT {} FROM denetim_kayitlari
+         WHERE tenant_id = ?1 AND kullanici_id = ?2
+           AND (?3 IS NULL OR substr(created_at, 1, 10) >= ?3)
+           AND (?4 IS NULL OR substr(created_at, 1, 10) <= ?4)
+         ORDER BY created_at DESC, rowid DESC
+         LIMIT ?5",
+        DENETIM_KOLONLARI
+    ))
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Text, _>(&kullanici_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&baslangic_tarihi)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&bitis_tarihi)
+    .bind::<diesel::sql_types::BigInt, _>(limit.unwrap_or(500).clamp(1, 10_000))
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(satirlar.into_iter().map(DenetimKaydi::from).collect())
+}
@@ -0,0 +1,286 @@
+// Muhasebe dönemi kilidi komutları
+// Kilit kontrolleri db::donem_kilidi tarafından yazımlarla aynı transaction'da
+// yapılır; buradaki komutlar kilit listesi, kilitleme/açma ve kilitli döneme
+// düşen sync değişikliklerinin yönetici kararıyla sonuçlandırılmasını sunar.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::denetim;
+use crate::db::donem_kilidi;
+use crate::db::outbox::{self, TxError};
+use crate::db::yevmiye;
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct DonemKilidiKaydi {
+    #[diesel(embed)]
+    #[serde(flatten)]
+    pub kilit: donem_kilidi::DonemKilidi,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kilitleyen_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub acan_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub bekleyen_ihlal: i64,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct DonemKilidiIhlali {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kilit_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub ay: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub table_name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub record_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub islem: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub degisiklik: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karar_veren: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karar_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karar_nedeni: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+/// Tüm dönem kilitleri (açılmış olanlar dahil), en yeni dönem önce
+#[tauri::command]
+pub async fn get_donem_kilitleri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<DonemKilidiKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    diesel::sql_query(
+        "SELECT k.id, k.yil, k.ay, k.durum, k.aciklama, k.kilitleyen, k.kilit_tarihi,
+                k.acan, k.acilis_tarihi, k.acilis_nedeni,
+                ku.full_name as kilitleyen_adi, au.full_name as acan_adi,
+                (SELECT COUNT(*) FROM donem_kilidi_ihlalleri i
+                 WHERE i.kilit_id = k.id AND i.durum = 'bekliyor') as bekleyen_ihlal
+         FROM donem_kilitleri k
+         LEFT JOIN users ku ON ku.id = k.kilitleyen
+         LEFT JOIN users au ON au.id = k.acan
+         WHERE k.tenant_id = ?1
+         ORDER BY k.yil DESC, k.ay IS NULL DESC, k.ay DESC, k.kilit_tarihi DESC",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Yılı (ay = None) veya tek bir ayı kilitler
+#[tauri::command]
+pub async fn donem_kilitle(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    yil: i32,
+    ay: Option<i32>,
+    aciklama: Option<String>,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let kullanici = state.verify_admin()?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("donem_kilitle");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let id = donem_kilidi::kilitle(conn, &tenant_id_param, yil, ay, aciklama.as_deref(), Some(&kullanici.id))
+            .map_err(TxError::Msg)?;
+        denetim.yeni_kayit(&tenant_id_param, "donem_kilitleri", &id);
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Kilidi açar. Yalnızca yönetici; gerekçe zorunludur ve kilit kaydında saklanır.
+#[tauri::command]
+pub async fn donem_kilidi_ac(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kilit_id: String,
+    neden: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let kullanici = state.verify_admin()?;
+
+    let neden = neden.trim();
+    if neden.is_empty() {
+        return Err("Kilidi açmak için gerekçe yazılmalıdır".to_string());
+    }
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim::kayitli_yazim(&mut conn, state.denetim("donem_kilidi_ac"), &tenant_id_param, "donem_kilitleri", &kilit_id, |conn| {
+        let affected = diesel::sql_query(
+            "UPDATE donem_kilitleri
+             SET durum = 'acik', acan = ?1, acilis_tarihi = ?2, acilis_nedeni = ?3, updated_at = ?2
+             WHERE id = ?4 AND tenant_id = ?5 AND durum = 'kilitli'",
+        )
+        .bind::<diesel::sql_types::Text, _>(&kullanici.id)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(neden)
+        .bind::<diesel::sql_types::Text, _>(&kilit_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+
+        if affected == 0 {
+            return Err("Kilitli durumda dönem kilidi bulunamadı".to_string());
+        }
+        Ok(())
+    })
+}
+
+/// Kilitli döneme düşen sync değişiklikleri (durum: 'bekliyor' | 'uygulandi' | 'reddedildi')
+#[tauri::command]
+pub async fn get_donem_kilidi_ihlalleri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    durum: Option<String>,
+) -> Result<Vec<DonemKilidiIhlali>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    diesel::sql_query(
+        "SELECT i.id, i.kilit_id, k.yil, k.ay, i.table_name, i.record_id, i.islem, i.degisiklik,
+                i.durum, i.karar_veren, i.karar_tarihi, i.karar_nedeni, i.created_at
+         FROM donem_kilidi_ihlalleri i
+         JOIN donem_kilitleri k ON k.id = i.kilit_id
+         WHERE i.tenant_id = ?1 AND (?2 IS NULL OR i.durum = ?2)
+         ORDER BY i.created_at DESC",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&durum)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())
+}
+
+/// İşaretlenmiş değişikliği sonuçlandırır (yalnızca yönetici).
+/// - uygula = true: dönem kilidi önce açılmış olmalıdır; değişiklik sync'teki
+///   gibi uygulanır, kasa bakiyesi ve yevmiye yeniden üretilir.
+/// - uygula = false: değişiklik reddedilir; yerel kayıt sunucuya yeniden
+///   gönderilir ki diğer cihazlar da kilitli dönemdeki değere dönsün.
+#[tauri::command]
+pub async fn donem_kilidi_ihlali_sonuclandir(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    ihlal_id: String,
+    uygula: bool,
+    neden: Option<String>,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let kullanici = state.verify_admin()?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let ihlal: DonemKilidiIhlali = diesel::sql_query(
+        "SELECT i.id, i.kilit_id, k.yil, k.ay, i.table_name, i.record_id, i.islem, i.degisiklik,
+                i.durum, i.karar_veren, i.karar_tarihi, i.karar_nedeni, i.created_at
+         FROM donem_kilidi_ihlalleri i
+         JOIN donem_kilitleri k ON k.id = i.kilit_id
+         WHERE i.id = ?1 AND i.tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(&ihlal_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result(&mut conn)
+    .map_err(|_| "Dönem kilidi ihlali bulunamadı".to_string())?;
+
+    if ihlal.durum != "bekliyor" {
+        return Err(format!("Bu değişiklik zaten sonuçlandırılmış ({})", ihlal.durum));
+    }
+
+    let degisiklik: serde_json::Value =
+        serde_json::from_str(&ihlal.degisiklik).map_err(|e| format!("Değişiklik okunamadı: {}", e))?;
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let mut denetim = state.denetim("donem_kilidi_ihlali_sonuclandir");
+    conn.transaction::<_, TxError, _>(|conn| {
+        if uygula {
+            let data = degisiklik.get("data").cloned().unwrap_or(serde_json::Value::Null);
+            if let Some(kilit) = donem_kilidi::uzak_degisiklik_kilidi(
+                conn,
+                &tenant_id_param,
+                &ihlal.table_name,
+                &ihlal.record_id,
+                &data,
+            )
+            .map_err(TxError::Msg)?
+            {
+                return Err(TxError::Msg(format!(
+                    "{} dönemi hâlâ kilitli. Değişikliği uygulamak için önce kilidi açın.",
+                    kilit.donem()
+                )));
+            }
+
+            denetim.izle(conn, &tenant_id_param, &ihlal.table_name, &ihlal.record_id)
+                .map_err(TxError::Msg)?;
+            let mut kasalar = std::collections::HashSet::new();
+            crate::commands::sync::uzak_degisikligi_uygula(
+                conn,
+                &tenant_id_param,
+                &ihlal.table_name,
+                &ihlal.record_id,
+                &degisiklik,
+                &mut kasalar,
+            )
+            .map_err(TxError::Msg)?;
+            for kasa_id in &kasalar {
+                crate::commands::mali::update_kasa_bakiye(conn, kasa_id)?;
+            }
+            if yevmiye::KAYNAK_TABLOLAR.contains(&ihlal.table_name.as_str()) {
+                yevmiye::kaydi_isle(conn, &tenant_id_param, &ihlal.table_name, &ihlal.record_id)
+                    .map_err(TxError::Msg)?;
+            }
+        } else {
+            let yerel_var = outbox::snapshot_row(conn, &ihlal.table_name, &ihlal.record_id, &tenant_id_param)
+                .map_err(TxError::Msg)?
+                .is_some();
+            let op = if yerel_var { "update" } else { "delete" };
+            outbox::queue_change(conn, &tenant_id_param, &ihlal.table_name, &ihlal.record_id, op)
+                .map_err(TxError::Msg)?;
+        }
+
+        diesel::sql_query(
+            "UPDATE donem_kilidi_ihlalleri
+             SET durum = ?1, karar_veren = ?2, karar_tarihi = ?3, karar_nedeni = ?4
+             WHERE id = ?5",
+        )
+        .bind::<diesel::sql_types::Text, _>(if uygula { "uygulandi" } else { "reddedildi" })
+        .bind::<diesel::sql_types::Text, _>(&kullanici.id)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&neden)
+        .bind::<diesel::sql_types::Text, _>(&ihlal.id)
+        .execute(conn)?;
+
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,183 @@
+// Etkinlik kayıtları, yedek liste, bilet tahsilatı ve QR ile giriş (bkz. db::etkinlik_kaydi)
+use tauri::State;
+use diesel::prelude::*;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::state::AppState;
+use crate::commands::mali::{self, outbox_err, CreateGelirRequest};
+use crate::db::outbox::TxError;
+use crate::db::etkinlik_kaydi::{self, EtkinlikKatilimRaporu, EtkinlikKaydi, KayitGirdi};
+
+#[derive(Debug, Deserialize)]
+pub struct BiletTahsilatRequest {
+    pub kasa_id: String,
+    pub tarih: String,
+    pub gelir_turu_id: Option<String>,
+    pub tahsil_eden: Option<String>,
+    pub makbuz_no: Option<String>,
+}
+
+fn bos_degil(s: Option<String>) -> Option<String> {
+    s.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Kesin kayıtlar, sırasıyla yedekler ve iptaller
+#[tauri::command]
+pub fn get_etkinlik_kayitlari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    etkinlik_id: String,
+) -> Result<Vec<EtkinlikKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    etkinlik_kaydi::kayitlari_getir(&mut conn, &tenant_id_param, &etkinlik_id)
+}
+
+/// Üye ya da misafir kaydı; kapasite doluysa yedek listeye alınır
+#[tauri::command]
+pub fn etkinlik_kaydi_ekle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    etkinlik_id: String,
+    data: KayitGirdi,
+) -> Result<EtkinlikKaydi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("etkinlik_kaydi_ekle");
+    let id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let id = etkinlik_kaydi::kayit_ekle(conn, &mut denetim, &tenant_id_param, &etkinlik_id, &data)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    etkinlik_kaydi::kayit_getir(&mut conn, &tenant_id_param, &id)
+}
+
+/// Kaydı iptal eder; yedekten kesin kayda geçenler döner
+#[tauri::command]
+pub fn etkinlik_kaydi_iptal(
+    state: State<AppState>,
+    tenant_id_param: String,
+    kayit_id: String,
+    neden: String,
+) -> Result<Vec<EtkinlikKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("etkinlik_kaydi_iptal");
+    let alinan = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let alinan = etkinlik_kaydi::kayit_iptal(conn, &mut denetim, &tenant_id_param, &kayit_id, &neden)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(alinan)
+        })
+        .map_err(|e| e.to_string())?;
+
+    alinan.iter().map(|id| etkinlik_kaydi::kayit_getir(&mut conn, &tenant_id_param, id)).collect()
+}
+
+/// Bilet ücretini etkinliğe bağlı tek gelir kaydıyla tahsil eder
+#[tauri::command]
+pub fn etkinlik_bileti_tahsil_et(
+    state: State<AppState>,
+    tenant_id_param: String,
+    kayit_id: String,
+    data: BiletTahsilatRequest,
+) -> Result<EtkinlikKaydi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let kayit = etkinlik_kaydi::tahsil_edilecek(&mut conn, &tenant_id_param, &kayit_id)?;
+    let gelir_id = Uuid::new_v4().to_string();
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let mut denetim = state.denetim("etkinlik_bileti_tahsil_et");
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.yeni_kayit(&tenant_id_param, "gelirler", &gelir_id);
+        let mut gelir = CreateGelirRequest {
+            kasa_id: data.kasa_id.clone(),
+            gelir_turu_id: data.gelir_turu_id.clone(),
+            tarih: data.tarih.clone(),
+            tutar: kayit.bilet_ucreti,
+            aciklama: Some(format!("Etkinlik bileti · {}", kayit.ad_soyad)),
+            makbuz_no: bos_degil(data.makbuz_no.clone()),
+            alt_kategori: Some("Etkinlik Bileti".to_string()),
+            tahakkuk_durumu: None,
+            belge_no: Some(kayit.qr_kodu.clone()),
+            tahsil_eden: bos_degil(data.tahsil_eden.clone()),
+            belge_id: None,
+            uye_id: kayit.uye_id.clone(),
+            aidat_id: None,
+            ait_oldugu_yil: None,
+            etkinlik_id: Some(kayit.etkinlik_id.clone()),
+        };
+        mali::makbuz_ve_tahsil_eden(conn, &tenant_id_param, kullanici_id.as_deref(), &gelir_id, &mut gelir)?;
+        mali::gelir_ekle(conn, &tenant_id_param, &gelir_id, &gelir)?;
+        mali::update_kasa_bakiye(conn, &data.kasa_id)?;
+        etkinlik_kaydi::odeme_isle(conn, &mut denetim, &tenant_id_param, &kayit_id, &gelir_id).map_err(outbox_err)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    etkinlik_kaydi::kayit_getir(&mut conn, &tenant_id_param, &kayit_id)
+}
+
+/// QR kodu değeriyle giriş; kayıtlı değil, yedekte ya da ücreti ödenmemişse hata
+#[tauri::command]
+pub fn etkinlik_girisi(
+    state: State<AppState>,
+    tenant_id_param: String,
+    etkinlik_id: String,
+    qr_kodu: String,
+) -> Result<EtkinlikKaydi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let zaman = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    let mut denetim = state.denetim("etkinlik_girisi");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let kayit = etkinlik_kaydi::giris_yap(conn, &mut denetim, &tenant_id_param, &etkinlik_id, &qr_kodu, &zaman)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(kayit)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_etkinlik_katilim_raporu(
+    state: State<AppState>,
+    tenant_id_param: String,
+    etkinlik_id: String,
+) -> Result<EtkinlikKatilimRaporu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    etkinlik_kaydi::katilim_raporu(&mut conn, &tenant_id_param, &etkinlik_id)
+}
@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use tauri::State;
 use uuid::Uuid;
 
+use crate::db::etkinlik_kaydi;
 use crate::db::outbox::{self, TxError};
+use crate::db::para::Para;
 
 #[derive(Debug, Serialize, Deserialize, Queryable, diesel::QueryableByName)]
 #[diesel(table_name = crate::db::schema::etkinlikler)]
@@ -26,6 +28,9 @@ pub struct Etkinlik {
     pub updated_at: Option<String>,
     pub created_by: Option<String>,
     pub is_deleted: Option<i32>,
+    pub kapasite: Option<i32>,              // Boşsa sınırsız; dolunca kayıtlar yedek listeye
+    pub bilet_ucreti: Option<Para>,
+    pub uye_bilet_ucreti: Option<Para>,     // Boşsa üyeler de bilet_ucreti öder
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,6 +46,9 @@ pub struct CreateEtkinlikRequest {
     pub katilimci_sayisi: Option<i32>,
     pub sorumlu_uye_id: Option<String>,
     pub notlar: Option<String>,
+    pub kapasite: Option<i32>,
+    pub bilet_ucreti: Option<Para>,
+    pub uye_bilet_ucreti: Option<Para>,
 }
 
 #[tauri::command]
@@ -111,11 +119,13 @@ pub async fn create_etkinlik(
     let new_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let mut denetim = state.denetim("create_etkinlik");
     // Yazım + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "etkinlikler", &new_id).map_err(TxError::Msg)?;
         diesel::sql_query(
-            "INSERT INTO etkinlikler (id, tenant_id, baslik, aciklama, baslangic_tarihi, bitis_tarihi, yer, etkinlik_tipi, durum, tahmini_butce, katilimci_sayisi, sorumlu_uye_id, notlar, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
+            "INSERT INTO etkinlikler (id, tenant_id, baslik, aciklama, baslangic_tarihi, bitis_tarihi, yer, etkinlik_tipi, durum, tahmini_butce, katilimci_sayisi, sorumlu_uye_id, notlar, kapasite, bilet_ucreti, uye_bilet_ucreti, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)"
         )
         .bind::<diesel::sql_types::Text, _>(&new_id)
         .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
@@ -130,12 +140,16 @@ pub async fn create_etkinlik(
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.katilimci_sayisi)
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.sorumlu_uye_id)
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.kapasite)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&data.bilet_ucreti)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&data.uye_bilet_ucreti)
         .bind::<diesel::sql_types::Text, _>(&now)
         .bind::<diesel::sql_types::Text, _>(&now)
         .execute(conn)?;
 
         outbox::queue_change(conn, &tenant_id_param, "etkinlikler", &new_id, "create")
             .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -163,10 +177,13 @@ pub async fn update_etkinlik(
 
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let mut denetim = state.denetim("update_etkinlik");
     // Update + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "etkinlikler", &etkinlik_id).map_err(TxError::Msg)?;
+        // Kapasite ve bilet ücretleri gönderilmezse mevcut değerler korunur
         let affected = diesel::sql_query(
-            "UPDATE etkinlikler SET baslik = ?1, aciklama = ?2, baslangic_tarihi = ?3, yer = ?4, durum = ?5, tahmini_butce = ?6, notlar = ?7, updated_at = ?8 WHERE id = ?9 AND tenant_id = ?10"
+            "UPDATE etkinlikler SET baslik = ?1, aciklama = ?2, baslangic_tarihi = ?3, yer = ?4, durum = ?5, tahmini_butce = ?6, notlar = ?7, kapasite = COALESCE(?8, kapasite), bilet_ucreti = COALESCE(?9, bilet_ucreti), uye_bilet_ucreti = COALESCE(?10, uye_bilet_ucreti), updated_at = ?11 WHERE id = ?12 AND tenant_id = ?13"
         )
         .bind::<diesel::sql_types::Text, _>(&data.baslik)
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
@@ -175,6 +192,9 @@ pub async fn update_etkinlik(
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.durum)
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&data.tahmini_butce)
         .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.kapasite)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&data.bilet_ucreti)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(&data.uye_bilet_ucreti)
         .bind::<diesel::sql_types::Text, _>(&now)
         .bind::<diesel::sql_types::Text, _>(&etkinlik_id)
         .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
@@ -183,7 +203,11 @@ pub async fn update_etkinlik(
         if affected > 0 {
             outbox::queue_change(conn, &tenant_id_param, "etkinlikler", &etkinlik_id, "update")
                 .map_err(TxError::Msg)?;
+            // Kapasite artırıldıysa yedek listedekiler sırayla kesin kayda geçer
+            etkinlik_kaydi::yedekleri_al(conn, &mut denetim, &tenant_id_param, &etkinlik_id)
+                .map_err(TxError::Msg)?;
         }
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -208,8 +232,10 @@ pub async fn delete_etkinlik(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("delete_etkinlik");
     // DELETE + outbox tombstone kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "etkinlikler", &etkinlik_id).map_err(TxError::Msg)?;
         let affected = diesel::sql_query(&format!(
             "DELETE FROM etkinlikler WHERE id = '{}' AND tenant_id = '{}'",
             etkinlik_id, tenant_id_param
@@ -220,6 +246,7 @@ pub async fn delete_etkinlik(
             outbox::queue_change(conn, &tenant_id_param, "etkinlikler", &etkinlik_id, "delete")
                 .map_err(TxError::Msg)?;
         }
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -236,17 +263,22 @@ pub async fn delete_etkinlik(
 #[derive(Debug, serde::Serialize)]
 pub struct EtkinlikMaliOzet {
     pub etkinlik_id: String,
-    pub toplam_gelir: f64,
-    pub toplam_gider: f64,
-    pub net: f64,
+    pub toplam_gelir: Para,
+    pub toplam_gider: Para,
+    pub net: Para,
     pub gelir_sayisi: i64,
     pub gider_sayisi: i64,
+    /// Giriş yapan kayıtlar; etkinlikte kayıt tutulmamışsa etkinlikler.katilimci_sayisi
+    pub katilimci_sayisi: i64,
+    pub kisi_basi_gider: Option<Para>,
+    pub kisi_basi_net: Option<Para>,
+    pub katilim: etkinlik_kaydi::EtkinlikKatilimRaporu,
 }
 
 #[derive(diesel::QueryableByName)]
 struct MaliOzetRow {
     #[diesel(sql_type = diesel::sql_types::Double)]
-    toplam: f64,
+    toplam: Para,
     #[diesel(sql_type = diesel::sql_types::BigInt)]
     adet: i64,
 }
@@ -281,6 +313,24 @@ pub async fn get_etkinlik_mali_ozet(
     .get_result(&mut conn)
     .map_err(|e| e.to_string())?;
 
+    let katilim = etkinlik_kaydi::katilim_raporu(&mut conn, &tenant_id_param, &etkinlik_id_param)?;
+    let katilimci_sayisi = if katilim.kayitli + katilim.yedek + katilim.iptal > 0 {
+        katilim.katilan
+    } else {
+        diesel::sql_query("SELECT * FROM etkinlikler WHERE id = ?1 AND tenant_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(&etkinlik_id_param)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .get_result::<Etkinlik>(&mut conn)
+            .map_err(|e| e.to_string())?
+            .katilimci_sayisi
+            .unwrap_or(0) as i64
+    };
+    // Kişi başı tutar kuruşta bölünür, yarım kuruş sıfırdan uzağa yuvarlanır
+    let kisi_basi = |tutar: Para| {
+        let (kurus, kisi) = (tutar.kurus(), katilimci_sayisi);
+        (kisi > 0).then(|| Para::from_kurus((2 * kurus + kurus.signum() * kisi) / (2 * kisi)))
+    };
+
     Ok(EtkinlikMaliOzet {
         etkinlik_id: etkinlik_id_param,
         toplam_gelir: gelir.toplam,
@@ -288,5 +338,9 @@ pub async fn get_etkinlik_mali_ozet(
         net: gelir.toplam - gider.toplam,
         gelir_sayisi: gelir.adet,
         gider_sayisi: gider.adet,
+        katilimci_sayisi,
+        kisi_basi_gider: kisi_basi(gider.toplam),
+        kisi_basi_net: kisi_basi(gelir.toplam - gider.toplam),
+        katilim,
     })
 }
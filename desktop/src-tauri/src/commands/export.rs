@@ -299,14 +299,14 @@ pub async fn export_kasalar_excel(
         
         worksheet.write_string(row, 0, &kasa.kasa_adi).map_err(|e| e.to_string())?;
         worksheet.write_string(row, 1, &kasa.para_birimi).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 2, kasa.devir_bakiye.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 3, kasa.toplam_gelir.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 4, kasa.toplam_gider.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 5, kasa.virman_giris.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 6, kasa.virman_cikis.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 7, kasa.fiziksel_bakiye.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 8, kasa.tahakkuk_tutari.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
-        worksheet.write_number_with_format(row, 9, kasa.serbest_bakiye.unwrap_or(0.0), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 2, kasa.devir_bakiye.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 3, kasa.toplam_gelir.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 4, kasa.toplam_gider.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 5, kasa.virman_giris.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 6, kasa.virman_cikis.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 7, kasa.fiziksel_bakiye.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 8, kasa.tahakkuk_tutari.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 9, kasa.serbest_bakiye.unwrap_or_default().tl(), &currency_format).map_err(|e| e.to_string())?;
         worksheet.write_string(row, 10, if kasa.is_active { "Aktif" } else { "Pasif" }).map_err(|e| e.to_string())?;
     }
     
@@ -752,3 +752,441 @@ pub async fn export_demirbaslar_excel(
     
     Ok(filepath.to_string_lossy().to_string())
 }
+
+// ============================================================================
+// RESMİ DEFTER EXPORT (İşletme Hesabı Defteri / Tablosu)
+// ============================================================================
+
+/// Defter tarafının (gider: sol, gelir: sağ) nakil/toplam satırını yazar:
+/// etiket açıklama sütununa, tutarlar tür sütunlarına ve toplam sütununa.
+fn write_defter_toplam_satiri(
+    worksheet: &mut Worksheet,
+    row: u32,
+    first_col: u16,
+    label: &str,
+    toplam: &crate::commands::resmi_defterler::DefterToplami,
+    format: &Format,
+) -> Result<(), String> {
+    worksheet.write_string_with_format(row, first_col + 3, label, format).map_err(|e| e.to_string())?;
+    for (i, tutar) in toplam.kategoriler.iter().enumerate() {
+        worksheet.write_number_with_format(row, first_col + 4 + i as u16, tutar.tl(), format).map_err(|e| e.to_string())?;
+    }
+    let toplam_col = first_col + 4 + toplam.kategoriler.len() as u16;
+    worksheet.write_number_with_format(row, toplam_col, toplam.toplam.tl(), format).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// İşletme Hesabı Defteri Excel Export
+/// Her defter sayfası ayrı baskı sayfasıdır: solda giderler, sağda gelirler;
+/// önceki sayfadan nakil, sayfa toplamı ve sonraki sayfaya nakil satırlarıyla.
+#[tauri::command]
+pub async fn export_isletme_hesabi_defteri_excel(
+    state: State<'_, AppState>,
+    tenant_id_param: String,
+    yil: i32,
+    sayfa_satir_sayisi: Option<usize>,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let defter = crate::commands::resmi_defterler::isletme_defteri_hazirla(
+        &mut conn, &tenant_id_param, yil, sayfa_satir_sayisi,
+    )?;
+
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let filename = format!("isletme_hesabi_defteri_{}_{}.xlsx", yil, timestamp);
+    let filepath = PathBuf::from(&filename);
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(format!("İşletme Defteri {}", yil)).map_err(|e| e.to_string())?;
+    worksheet.set_landscape();
+
+    let title_format = Format::new().set_bold().set_font_size(12);
+    let header_format = Format::new()
+        .set_bold()
+        .set_font_color(Color::White)
+        .set_background_color(Color::RGB(0x4472C4))
+        .set_text_wrap();
+    let currency_format = Format::new().set_num_format("#,##0.00");
+    let total_format = Format::new().set_bold().set_num_format("#,##0.00").set_border_top(FormatBorder::Thin);
+
+    // Sol blok: gider, bir boş sütun, sağ blok: gelir
+    let gider_col: u16 = 0;
+    let gider_genislik = 5 + defter.gider_kategorileri.len() as u16;
+    let gelir_col: u16 = gider_genislik + 1;
+
+    let mut row: u32 = 0;
+    let mut page_breaks: Vec<u32> = Vec::new();
+
+    for sayfa in &defter.sayfalar {
+        if row > 0 {
+            page_breaks.push(row);
+        }
+        worksheet
+            .write_string_with_format(row, gider_col, format!("GİDERLER — {} yılı, sayfa {}", yil, sayfa.sayfa_no), &title_format)
+            .map_err(|e| e.to_string())?;
+        worksheet
+            .write_string_with_format(row, gelir_col, format!("GELİRLER — {} yılı, sayfa {}", yil, sayfa.sayfa_no), &title_format)
+            .map_err(|e| e.to_string())?;
+        row += 1;
+
+        for (first_col, kategoriler) in [(gider_col, &defter.gider_kategorileri), (gelir_col, &defter.gelir_kategorileri)] {
+            let mut col = first_col;
+            for header in ["Sıra No", "Tarih", "Belge No", "Açıklama"] {
+                worksheet.write_string_with_format(row, col, header, &header_format).map_err(|e| e.to_string())?;
+                col += 1;
+            }
+            for kategori in kategoriler.iter() {
+                worksheet.write_string_with_format(row, col, kategori, &header_format).map_err(|e| e.to_string())?;
+                col += 1;
+            }
+            worksheet.write_string_with_format(row, col, "Toplam", &header_format).map_err(|e| e.to_string())?;
+        }
+        row += 1;
+
+        write_defter_toplam_satiri(worksheet, row, gider_col, "Önceki sayfadan nakil", &sayfa.gider.onceki_sayfadan_nakil, &total_format)?;
+        write_defter_toplam_satiri(worksheet, row, gelir_col, "Önceki sayfadan nakil", &sayfa.gelir.onceki_sayfadan_nakil, &total_format)?;
+        row += 1;
+
+        for i in 0..defter.sayfa_satir_sayisi {
+            for (first_col, taraf, kategori_sayisi) in [
+                (gider_col, &sayfa.gider, defter.gider_kategorileri.len()),
+                (gelir_col, &sayfa.gelir, defter.gelir_kategorileri.len()),
+            ] {
+                if let Some(satir) = taraf.satirlar.get(i) {
+                    worksheet.write_number(row, first_col, satir.sira_no as f64).map_err(|e| e.to_string())?;
+                    worksheet.write_string(row, first_col + 1, &satir.tarih).map_err(|e| e.to_string())?;
+                    worksheet.write_string(row, first_col + 2, satir.belge_no.as_deref().unwrap_or("-")).map_err(|e| e.to_string())?;
+                    worksheet.write_string(row, first_col + 3, satir.aciklama.as_deref().unwrap_or(&satir.kategori)).map_err(|e| e.to_string())?;
+                    worksheet.write_number_with_format(row, first_col + 4 + satir.kategori_sira as u16, satir.tutar.tl(), &currency_format).map_err(|e| e.to_string())?;
+                    worksheet.write_number_with_format(row, first_col + 4 + kategori_sayisi as u16, satir.tutar.tl(), &currency_format).map_err(|e| e.to_string())?;
+                }
+            }
+            row += 1;
+        }
+
+        write_defter_toplam_satiri(worksheet, row, gider_col, "Sayfa toplamı", &sayfa.gider.sayfa_toplami, &total_format)?;
+        write_defter_toplam_satiri(worksheet, row, gelir_col, "Sayfa toplamı", &sayfa.gelir.sayfa_toplami, &total_format)?;
+        row += 1;
+        write_defter_toplam_satiri(worksheet, row, gider_col, "Sonraki sayfaya nakil", &sayfa.gider.sonraki_sayfaya_nakil, &total_format)?;
+        write_defter_toplam_satiri(worksheet, row, gelir_col, "Sonraki sayfaya nakil", &sayfa.gelir.sonraki_sayfaya_nakil, &total_format)?;
+        row += 2;
+    }
+
+    worksheet.set_page_breaks(&page_breaks).map_err(|e| e.to_string())?;
+
+    // Sütun genişlikleri
+    for first_col in [gider_col, gelir_col] {
+        worksheet.set_column_width(first_col, 7).map_err(|e| e.to_string())?;
+        worksheet.set_column_width(first_col + 1, 11).map_err(|e| e.to_string())?;
+        worksheet.set_column_width(first_col + 2, 12).map_err(|e| e.to_string())?;
+        worksheet.set_column_width(first_col + 3, 30).map_err(|e| e.to_string())?;
+    }
+    for col in 4..gider_genislik {
+        worksheet.set_column_width(gider_col + col, 14).map_err(|e| e.to_string())?;
+    }
+    for col in 4..(5 + defter.gelir_kategorileri.len() as u16) {
+        worksheet.set_column_width(gelir_col + col, 14).map_err(|e| e.to_string())?;
+    }
+    worksheet.set_column_width(gider_genislik, 3).map_err(|e| e.to_string())?;
+
+    workbook.save(&filepath).map_err(|e| e.to_string())?;
+
+    Ok(filepath.to_string_lossy().to_string())
+}
+
+/// Yıllık Gelir-Gider (İşletme Hesabı) Tablosu Excel Export
+/// Sol: giderler + ertesi yıla devreden; sağ: önceki yıldan devreden + gelirler. İki taraf toplamı eşittir.
+#[tauri::command]
+pub async fn export_gelir_gider_tablosu_excel(
+    state: State<'_, AppState>,
+    tenant_id_param: String,
+    yil: i32,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let tablo = crate::commands::resmi_defterler::gelir_gider_tablosu_hazirla(&mut conn, &tenant_id_param, yil)?;
+
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let filename = format!("gelir_gider_tablosu_{}_{}.xlsx", yil, timestamp);
+    let filepath = PathBuf::from(&filename);
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(format!("Gelir-Gider {}", yil)).map_err(|e| e.to_string())?;
+
+    let title_format = Format::new().set_bold().set_font_size(12);
+    let header_format = Format::new()
+        .set_bold()
+        .set_font_color(Color::White)
+        .set_background_color(Color::RGB(0x4472C4));
+    let section_format = Format::new().set_bold();
+    let currency_format = Format::new().set_num_format("#,##0.00");
+    let total_format = Format::new().set_bold().set_num_format("#,##0.00").set_border_top(FormatBorder::Thin);
+
+    worksheet
+        .write_string_with_format(0, 0, format!("{} YILI İŞLETME HESABI TABLOSU", yil), &title_format)
+        .map_err(|e| e.to_string())?;
+    worksheet.write_string_with_format(2, 0, "GİDERLER", &header_format).map_err(|e| e.to_string())?;
+    worksheet.write_string_with_format(2, 1, "Tutar", &header_format).map_err(|e| e.to_string())?;
+    worksheet.write_string_with_format(2, 3, "GELİRLER", &header_format).map_err(|e| e.to_string())?;
+    worksheet.write_string_with_format(2, 4, "Tutar", &header_format).map_err(|e| e.to_string())?;
+
+    // Sol taraf: giderler, sonra ertesi yıla devreden
+    let mut sol: u32 = 3;
+    for kalem in &tablo.giderler {
+        worksheet.write_string(sol, 0, &kalem.kalem).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(sol, 1, kalem.tutar.tl(), &currency_format).map_err(|e| e.to_string())?;
+        sol += 1;
+    }
+    worksheet.write_string_with_format(sol, 0, "Giderler Toplamı", &section_format).map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(sol, 1, tablo.toplam_gider.tl(), &total_format).map_err(|e| e.to_string())?;
+    sol += 2;
+    worksheet.write_string_with_format(sol, 0, "Ertesi Yıla Devreden", &section_format).map_err(|e| e.to_string())?;
+    sol += 1;
+    worksheet.write_string(sol, 0, "Kasa").map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(sol, 1, tablo.ertesi_yila_devir_toplami.kasa.tl(), &currency_format).map_err(|e| e.to_string())?;
+    sol += 1;
+    worksheet.write_string(sol, 0, "Banka").map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(sol, 1, tablo.ertesi_yila_devir_toplami.banka.tl(), &currency_format).map_err(|e| e.to_string())?;
+    sol += 1;
+
+    // Sağ taraf: önceki yıldan devreden, sonra gelirler
+    let mut sag: u32 = 3;
+    worksheet.write_string_with_format(sag, 3, "Önceki Yıldan Devreden", &section_format).map_err(|e| e.to_string())?;
+    sag += 1;
+    worksheet.write_string(sag, 3, "Kasa").map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(sag, 4, tablo.onceki_yildan_devir_toplami.kasa.tl(), &currency_format).map_err(|e| e.to_string())?;
+    sag += 1;
+    worksheet.write_string(sag, 3, "Banka").map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(sag, 4, tablo.onceki_yildan_devir_toplami.banka.tl(), &currency_format).map_err(|e| e.to_string())?;
+    sag += 2;
+    for kalem in &tablo.gelirler {
+        worksheet.write_string(sag, 3, &kalem.kalem).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(sag, 4, kalem.tutar.tl(), &currency_format).map_err(|e| e.to_string())?;
+        sag += 1;
+    }
+    worksheet.write_string_with_format(sag, 3, "Gelirler Toplamı", &section_format).map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(sag, 4, tablo.toplam_gelir.tl(), &total_format).map_err(|e| e.to_string())?;
+    sag += 1;
+
+    // Genel toplamlar aynı satırda
+    let toplam_row = sol.max(sag) + 1;
+    worksheet.write_string_with_format(toplam_row, 0, "TOPLAM", &section_format).map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(toplam_row, 1, tablo.gider_tarafi_toplami.tl(), &total_format).map_err(|e| e.to_string())?;
+    worksheet.write_string_with_format(toplam_row, 3, "TOPLAM", &section_format).map_err(|e| e.to_string())?;
+    worksheet.write_number_with_format(toplam_row, 4, tablo.gelir_tarafi_toplami.tl(), &total_format).map_err(|e| e.to_string())?;
+
+    // Bilgi: virman hareketleri (toplamı etkilemez)
+    let mut row = toplam_row + 2;
+    worksheet.write_string_with_format(row, 0, "Virmanlar (bilgi)", &section_format).map_err(|e| e.to_string())?;
+    row += 1;
+    for (label, tutar) in [
+        ("Bankaya yatırılan", tablo.virmanlar.bankaya_yatirilan),
+        ("Bankadan çekilen", tablo.virmanlar.bankadan_cekilen),
+        ("Kasalar arası", tablo.virmanlar.kasalar_arasi),
+        ("Bankalar arası", tablo.virmanlar.bankalar_arasi),
+    ] {
+        worksheet.write_string(row, 0, label).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 1, tutar.tl(), &currency_format).map_err(|e| e.to_string())?;
+        row += 1;
+    }
+
+    worksheet.set_column_width(0, 32).map_err(|e| e.to_string())?;
+    worksheet.set_column_width(1, 16).map_err(|e| e.to_string())?;
+    worksheet.set_column_width(2, 3).map_err(|e| e.to_string())?;
+    worksheet.set_column_width(3, 32).map_err(|e| e.to_string())?;
+    worksheet.set_column_width(4, 16).map_err(|e| e.to_string())?;
+
+    workbook.save(&filepath).map_err(|e| e.to_string())?;
+
+    Ok(filepath.to_string_lossy().to_string())
+}
+
+/// Dernek Beyannamesi: bölüm ve alan sırası formla aynı; ikinci sayfada her alanın kaynak kayıtları
+#[tauri::command]
+pub async fn export_dernek_beyannamesi_excel(
+    state: State<'_, AppState>,
+    tenant_id_param: String,
+    yil: i32,
+) -> Result<String, String> {
+    use crate::commands::beyanname::AlanDegeri;
+
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let beyanname = crate::commands::beyanname::beyanname_hazirla(&mut conn, &tenant_id_param, yil)?;
+
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let filename = format!("dernek_beyannamesi_{}_{}.xlsx", yil, timestamp);
+    let filepath = PathBuf::from(&filename);
+
+    let mut workbook = Workbook::new();
+    let title_format = Format::new().set_bold().set_font_size(12);
+    let header_format = Format::new()
+        .set_bold()
+        .set_font_color(Color::White)
+        .set_background_color(Color::RGB(0x4472C4));
+    let section_format = Format::new().set_bold();
+    let currency_format = Format::new().set_num_format("#,##0.00");
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(format!("Beyanname {}", yil)).map_err(|e| e.to_string())?;
+    worksheet
+        .write_string_with_format(0, 0, format!("{} - {} YILI DERNEK BEYANNAMESİ", beyanname.dernek_adi, yil), &title_format)
+        .map_err(|e| e.to_string())?;
+    for (col, header) in ["No", "Alan", "Değer"].iter().enumerate() {
+        worksheet.write_string_with_format(2, col as u16, *header, &header_format).map_err(|e| e.to_string())?;
+    }
+
+    let mut row: u32 = 3;
+    for bolum in &beyanname.bolumler {
+        worksheet.write_string_with_format(row, 0, &bolum.no, &section_format).map_err(|e| e.to_string())?;
+        worksheet.write_string_with_format(row, 1, bolum.baslik.to_uppercase(), &section_format).map_err(|e| e.to_string())?;
+        row += 1;
+        for alan in &bolum.alanlar {
+            worksheet.write_string(row, 0, &alan.no).map_err(|e| e.to_string())?;
+            worksheet.write_string(row, 1, &alan.ad).map_err(|e| e.to_string())?;
+            match &alan.deger {
+                AlanDegeri::Sayi(n) => worksheet.write_number(row, 2, *n as f64),
+                AlanDegeri::Tutar(t) => worksheet.write_number_with_format(row, 2, t.tl(), &currency_format),
+                AlanDegeri::Metin(m) => worksheet.write_string(row, 2, m),
+            }
+            .map_err(|e| e.to_string())?;
+            row += 1;
+        }
+        row += 1;
+    }
+
+    if !beyanname.notlar.is_empty() {
+        worksheet.write_string_with_format(row, 0, "Notlar", &section_format).map_err(|e| e.to_string())?;
+        row += 1;
+        for not in &beyanname.notlar {
+            worksheet.write_string(row, 1, not).map_err(|e| e.to_string())?;
+            row += 1;
+        }
+    }
+
+    worksheet.set_column_width(0, 6).map_err(|e| e.to_string())?;
+    worksheet.set_column_width(1, 60).map_err(|e| e.to_string())?;
+    worksheet.set_column_width(2, 18).map_err(|e| e.to_string())?;
+
+    // Kaynak dökümü
+    let kaynaklar = workbook.add_worksheet();
+    kaynaklar.set_name("Kaynaklar").map_err(|e| e.to_string())?;
+    let headers = ["Alan", "Tablo", "Kayıt ID", "Tarih", "Açıklama", "Tutar"];
+    for (col, header) in headers.iter().enumerate() {
+        kaynaklar.write_string_with_format(0, col as u16, *header, &header_format).map_err(|e| e.to_string())?;
+    }
+    let mut row: u32 = 1;
+    for alan in beyanname.bolumler.iter().flat_map(|b| b.alanlar.iter()) {
+        for kaynak in &alan.kaynaklar {
+            kaynaklar.write_string(row, 0, &alan.no).map_err(|e| e.to_string())?;
+            kaynaklar.write_string(row, 1, &kaynak.tablo).map_err(|e| e.to_string())?;
+            kaynaklar.write_string(row, 2, &kaynak.kayit_id).map_err(|e| e.to_string())?;
+            kaynaklar.write_string(row, 3, kaynak.tarih.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+            kaynaklar.write_string(row, 4, &kaynak.aciklama).map_err(|e| e.to_string())?;
+            if let Some(tutar) = kaynak.tutar {
+                kaynaklar.write_number_with_format(row, 5, tutar.tl(), &currency_format).map_err(|e| e.to_string())?;
+            }
+            row += 1;
+        }
+    }
+    for (col, width) in [6, 14, 38, 12, 50, 16].iter().enumerate() {
+        kaynaklar.set_column_width(col as u16, *width).map_err(|e| e.to_string())?;
+    }
+
+    workbook.save(&filepath).map_err(|e| e.to_string())?;
+
+    Ok(filepath.to_string_lossy().to_string())
+}
+
+/// Alındı Belgesi Kayıt Defteri: her cilt bir satır (matbaa, seri/sıra, teslim alan, iade)
+#[tauri::command]
+pub async fn export_alindi_belgesi_kayit_defteri_excel(
+    state: State<'_, AppState>,
+    tenant_id_param: String,
+    yil: Option<i32>,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let ciltler = crate::commands::alindi_belgesi::kayit_defteri_hazirla(&mut conn, &tenant_id_param, yil)?;
+
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let filename = match yil {
+        Some(y) => format!("alindi_belgesi_kayit_defteri_{}_{}.xlsx", y, timestamp),
+        None => format!("alindi_belgesi_kayit_defteri_{}.xlsx", timestamp),
+    };
+    let filepath = PathBuf::from(&filename);
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Alındı Belgesi Kayıt Defteri").map_err(|e| e.to_string())?;
+    worksheet.set_landscape();
+
+    let title_format = Format::new().set_bold().set_font_size(12);
+    let header_format = Format::new()
+        .set_bold()
+        .set_font_color(Color::White)
+        .set_background_color(Color::RGB(0x4472C4))
+        .set_text_wrap();
+    let currency_format = Format::new().set_num_format("#,##0.00");
+
+    let baslik = match yil {
+        Some(y) => format!("{} YILI ALINDI BELGESİ KAYIT DEFTERİ", y),
+        None => "ALINDI BELGESİ KAYIT DEFTERİ".to_string(),
+    };
+    worksheet.write_string_with_format(0, 0, baslik, &title_format).map_err(|e| e.to_string())?;
+
+    let headers = [
+        "Sıra No", "Matbaa Adı", "Fatura Tarihi", "Fatura No", "Seri", "Cilt No", "Sıra No (Başı)",
+        "Sıra No (Sonu)", "Teslim Alan", "Yetki Belgesi", "Teslim Tarihi", "İade Tarihi",
+        "Kullanılan", "İptal", "Tahsil Edilen", "Açıklama",
+    ];
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string_with_format(2, col as u16, *header, &header_format).map_err(|e| e.to_string())?;
+    }
+
+    for (i, cilt) in ciltler.iter().enumerate() {
+        let row = 3 + i as u32;
+        worksheet.write_number(row, 0, (i + 1) as f64).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 1, cilt.matbaa_adi.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 2, cilt.matbaa_fatura_tarihi.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 3, cilt.matbaa_fatura_no.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 4, &cilt.seri).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 5, cilt.cilt_no.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_number(row, 6, cilt.baslangic_no as f64).map_err(|e| e.to_string())?;
+        worksheet.write_number(row, 7, cilt.bitis_no as f64).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 8, cilt.teslim_alan_adi.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 9, cilt.yetki_belgesi_no.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 10, cilt.teslim_tarihi.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 11, cilt.iade_tarihi.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+        worksheet.write_number(row, 12, cilt.kullanilan_sayisi as f64).map_err(|e| e.to_string())?;
+        worksheet.write_number(row, 13, cilt.iptal_sayisi as f64).map_err(|e| e.to_string())?;
+        worksheet.write_number_with_format(row, 14, cilt.tahsil_edilen.tl(), &currency_format).map_err(|e| e.to_string())?;
+        worksheet.write_string(row, 15, cilt.aciklama.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+    }
+
+    for (col, width) in [8, 24, 12, 12, 6, 8, 10, 10, 24, 14, 12, 12, 10, 8, 14, 30].iter().enumerate() {
+        worksheet.set_column_width(col as u16, *width).map_err(|e| e.to_string())?;
+    }
+
+    workbook.save(&filepath).map_err(|e| e.to_string())?;
+
+    Ok(filepath.to_string_lossy().to_string())
+}
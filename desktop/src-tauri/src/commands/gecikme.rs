@@ -0,0 +1,348 @@
+// Gecikme faizi politikaları, önizleme, tahakkuk, feragat ve tahsilat (bkz. db::gecikme)
+use tauri::State;
+use diesel::prelude::*;
+use chrono::{Local, NaiveDate, Utc};
+use uuid::Uuid;
+use serde::Deserialize;
+use crate::state::AppState;
+use crate::commands::mali::{self, outbox_err, CreateGelirRequest};
+use crate::db::denetim;
+use crate::db::gecikme::{self, GecikmeFaizi, GecikmeOnizleme, GecikmePolitikasi, GecikmeTahakkukSonucu};
+use crate::db::para::Para;
+
+#[derive(Debug, Deserialize)]
+pub struct GecikmePolitikasiRequest {
+    pub ad: String,
+    pub uye_turu: Option<String>,
+    pub odemesiz_gun: Option<i32>,
+    pub hesaplama: String,
+    pub oran_birimi: String,
+    pub oran: Option<f64>,
+    pub tavan_yuzde: Option<f64>,
+    pub asgari_tutar: Option<Para>,
+    pub feragat_yonetici: Option<bool>,
+    pub aciklama: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GecikmeTahsilatRequest {
+    pub kasa_id: String,
+    pub tarih: String,
+    pub gelir_turu_id: Option<String>,
+    pub tahsil_eden: Option<String>,
+    pub makbuz_no: Option<String>,
+}
+
+fn bos_degil(s: Option<String>) -> Option<String> {
+    s.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// YYYY-MM-DD; boşsa bugün
+fn hesap_tarihi(tarih: Option<String>) -> Result<NaiveDate, String> {
+    match bos_degil(tarih) {
+        Some(t) => NaiveDate::parse_from_str(&t, "%Y-%m-%d").map_err(|_| format!("Geçersiz tarih: {}", t)),
+        None => Ok(Local::now().date_naive()),
+    }
+}
+
+#[tauri::command]
+pub fn get_gecikme_politikalari(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<GecikmePolitikasi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    gecikme::politikalari_getir(&mut conn, &tenant_id_param)
+}
+
+/// Politika ekler (politika_id yoksa) veya günceller; üye türü başına tek aktif politika
+#[tauri::command]
+pub fn gecikme_politikasi_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    politika_id: Option<String>,
+    data: GecikmePolitikasiRequest,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let politika = GecikmePolitikasi {
+        id: politika_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string()),
+        ad: data.ad.trim().to_string(),
+        uye_turu: bos_degil(data.uye_turu),
+        odemesiz_gun: data.odemesiz_gun.unwrap_or(0),
+        hesaplama: data.hesaplama.trim().to_string(),
+        oran_birimi: data.oran_birimi.trim().to_string(),
+        oran: data.oran,
+        tavan_yuzde: data.tavan_yuzde,
+        asgari_tutar: data.asgari_tutar.unwrap_or(Para::SIFIR),
+        feragat_yonetici: data.feragat_yonetici.unwrap_or(true),
+        aciklama: bos_degil(data.aciklama),
+    };
+    gecikme::politika_dogrula(&politika)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let cakisan = gecikme::politikalari_getir(&mut conn, &tenant_id_param)?
+        .into_iter()
+        .find(|p| {
+            p.id != politika.id
+                && match (&p.uye_turu, &politika.uye_turu) {
+                    (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                    (None, None) => true,
+                    _ => false,
+                }
+        });
+    if let Some(p) = cakisan {
+        return Err(format!(
+            "{} için zaten bir gecikme politikası var: {}",
+            politika.uye_turu.as_deref().unwrap_or("Varsayılan"),
+            p.ad
+        ));
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let yeni = politika_id.is_none();
+    let etkilenen = denetim::kayitli_yazim(
+        &mut conn,
+        state.denetim("gecikme_politikasi_kaydet"),
+        &tenant_id_param,
+        "gecikme_politikalari",
+        &politika.id,
+        |conn| {
+            let sql = if yeni {
+                "INSERT INTO gecikme_politikalari
+                    (id, tenant_id, ad, uye_turu, odemesiz_gun, hesaplama, oran_birimi, oran, tavan_yuzde,
+                     asgari_tutar, feragat_yonetici, aciklama, is_active, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 1, ?13, ?13)"
+            } else {
+                "UPDATE gecikme_politikalari
+                 SET ad = ?3, uye_turu = ?4, odemesiz_gun = ?5, hesaplama = ?6, oran_birimi = ?7, oran = ?8,
+                     tavan_yuzde = ?9, asgari_tutar = ?10, feragat_yonetici = ?11, aciklama = ?12, updated_at = ?13
+                 WHERE id = ?1 AND tenant_id = ?2 AND is_active = 1"
+            };
+            diesel::sql_query(sql)
+                .bind::<diesel::sql_types::Text, _>(&politika.id)
+                .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+                .bind::<diesel::sql_types::Text, _>(&politika.ad)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&politika.uye_turu)
+                .bind::<diesel::sql_types::Integer, _>(politika.odemesiz_gun)
+                .bind::<diesel::sql_types::Text, _>(&politika.hesaplama)
+                .bind::<diesel::sql_types::Text, _>(&politika.oran_birimi)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(politika.oran)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(politika.tavan_yuzde)
+                .bind::<diesel::sql_types::Double, _>(politika.asgari_tutar)
+                .bind::<diesel::sql_types::Bool, _>(politika.feragat_yonetici)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&politika.aciklama)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .execute(conn)
+                .map_err(|e| e.to_string())
+        },
+    )?;
+
+    if etkilenen == 0 {
+        return Err("Politika bulunamadı".to_string());
+    }
+    Ok(politika.id)
+}
+
+#[tauri::command]
+pub fn gecikme_politikasi_sil(
+    state: State<AppState>,
+    tenant_id_param: String,
+    politika_id: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim::kayitli_yazim(
+        &mut conn,
+        state.denetim("gecikme_politikasi_sil"),
+        &tenant_id_param,
+        "gecikme_politikalari",
+        &politika_id,
+        |conn| {
+            diesel::sql_query(
+                "UPDATE gecikme_politikalari SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+            )
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&politika_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Tarih itibarıyla (boşsa bugün) vadesi geçmiş aidatların faizi; hiçbir şey yazmaz
+#[tauri::command]
+pub fn gecikme_faizi_onizle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tarih: Option<String>,
+) -> Result<GecikmeOnizleme, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let tarih = hesap_tarihi(tarih)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    gecikme::onizle(&mut conn, &tenant_id_param, tarih)
+}
+
+/// Önizlemedeki faizleri ayrı alacak olarak yazar; yalnızca yönetici
+#[tauri::command]
+pub fn gecikme_faizlerini_tahakkuk_et(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tarih: Option<String>,
+) -> Result<GecikmeTahakkukSonucu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+    let tarih = hesap_tarihi(tarih)?;
+    if tarih > Local::now().date_naive() {
+        return Err("Gecikme faizi ileri tarihli tahakkuk edilemez".to_string());
+    }
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("gecikme_faizlerini_tahakkuk_et");
+    gecikme::tahakkuk_et(&mut conn, &tenant_id_param, tarih, &mut denetim)
+}
+
+#[tauri::command]
+pub fn get_gecikme_faizleri(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: Option<String>,
+    sadece_acik: Option<bool>,
+) -> Result<Vec<GecikmeFaizi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    gecikme::faizleri_getir(&mut conn, &tenant_id_param, uye_id.as_deref(), sadece_acik.unwrap_or(false))
+}
+
+/// Faizin bir kısmından (tutar) veya tamamından vazgeçer. Neden zorunludur; politika
+/// yönetici istiyorsa (veya politika silinmişse) yalnızca yönetici yapabilir.
+#[tauri::command]
+pub fn gecikme_faizi_feragat_et(
+    state: State<AppState>,
+    tenant_id_param: String,
+    faiz_id: String,
+    tutar: Option<Para>,
+    neden: String,
+) -> Result<GecikmeFaizi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let faiz = gecikme::faiz_getir(&mut conn, &tenant_id_param, &faiz_id)?;
+    let yonetici_gerekli = gecikme::politikalari_getir(&mut conn, &tenant_id_param)?
+        .iter()
+        .find(|p| Some(&p.id) == faiz.politika_id.as_ref())
+        .map(|p| p.feragat_yonetici)
+        .unwrap_or(true);
+    if yonetici_gerekli {
+        state.verify_admin()?;
+    }
+    let feragat = gecikme::feragat_tutari(&faiz, tutar, &neden)?;
+
+    let feragat_eden = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let mut denetim = state.denetim("gecikme_faizi_feragat_et");
+    gecikme::feragat_et(&mut conn, &tenant_id_param, &faiz, feragat, &neden, feragat_eden.as_deref(), &mut denetim)?;
+
+    gecikme::faiz_getir(&mut conn, &tenant_id_param, &faiz_id)
+}
+
+/// Kalan faizi tek gelir kaydıyla tahsil eder (gelir 127'yi kapatır, ikinci kez gelir yazmaz)
+#[tauri::command]
+pub fn gecikme_faizi_tahsil_et(
+    state: State<AppState>,
+    tenant_id_param: String,
+    faiz_id: String,
+    data: GecikmeTahsilatRequest,
+) -> Result<GecikmeFaizi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let faiz = gecikme::faiz_getir(&mut conn, &tenant_id_param, &faiz_id)?;
+    if faiz.durum != "acik" || !faiz.kalan.is_positive() {
+        return Err("Bu gecikme faizinin tahsil edilecek bakiyesi yok".to_string());
+    }
+
+    let gelir_id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let mut denetim = state.denetim("gecikme_faizi_tahsil_et");
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gecikme_faizleri", &faiz.id).map_err(outbox_err)?;
+        // Bağlantı gelirden önce yazılır: gelirin yevmiye fişi karşı hesabı buradan bulur
+        diesel::sql_query(
+            "UPDATE gecikme_faizleri SET odenen = odenen + ?1, gelir_id = ?2, durum = 'odendi', updated_at = ?3
+             WHERE id = ?4 AND tenant_id = ?5 AND durum = 'acik'",
+        )
+        .bind::<diesel::sql_types::Double, _>(faiz.kalan)
+        .bind::<diesel::sql_types::Text, _>(&gelir_id)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&faiz.id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)?;
+
+        denetim.yeni_kayit(&tenant_id_param, "gelirler", &gelir_id);
+        let mut gelir = CreateGelirRequest {
+            kasa_id: data.kasa_id.clone(),
+            gelir_turu_id: data.gelir_turu_id.clone(),
+            tarih: data.tarih.clone(),
+            tutar: faiz.kalan,
+            aciklama: Some(format!(
+                "Gecikme faizi tahsilatı · {}",
+                faiz.aciklama.as_deref().unwrap_or(&faiz.hesap_tarihi)
+            )),
+            makbuz_no: bos_degil(data.makbuz_no.clone()),
+            alt_kategori: Some("Gecikme Faizi".to_string()),
+            tahakkuk_durumu: None,
+            belge_no: None,
+            tahsil_eden: bos_degil(data.tahsil_eden.clone()),
+            belge_id: None,
+            uye_id: Some(faiz.uye_id.clone()),
+            aidat_id: None,
+            ait_oldugu_yil: None,
+            etkinlik_id: None,
+        };
+        mali::makbuz_ve_tahsil_eden(conn, &tenant_id_param, kullanici_id.as_deref(), &gelir_id, &mut gelir)?;
+        mali::gelir_ekle(conn, &tenant_id_param, &gelir_id, &gelir)?;
+        mali::update_kasa_bakiye(conn, &data.kasa_id)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    gecikme::faiz_getir(&mut conn, &tenant_id_param, &faiz_id)
+}
@@ -60,8 +60,10 @@ pub fn create_gelir_turu(
     let now = Utc::now().to_rfc3339();
     let id = Uuid::new_v4().to_string();
 
+    let mut denetim = state.denetim("create_gelir_turu");
     // Yazım + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gelir_turleri", &id).map_err(TxError::Msg)?;
         diesel::insert_into(gelir_turleri::table)
             .values((
                 gelir_turleri::id.eq(&id),
@@ -78,6 +80,7 @@ pub fn create_gelir_turu(
 
         outbox::queue_change(conn, &tenant_id_param, "gelir_turleri", &id, "create")
             .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -110,8 +113,10 @@ pub fn update_gelir_turu(
         .first::<GelirTuru>(&mut conn)
         .map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("update_gelir_turu");
     // Update + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gelir_turleri", &id).map_err(TxError::Msg)?;
         diesel::update(gelir_turleri::table.find(&id))
             .set((
                 gelir_turleri::ad.eq(request.ad.unwrap_or(current.ad)),
@@ -125,6 +130,7 @@ pub fn update_gelir_turu(
 
         outbox::queue_change(conn, &tenant_id_param, "gelir_turleri", &id, "update")
             .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -149,10 +155,12 @@ pub fn delete_gelir_turu(
 
     let now = Utc::now().to_rfc3339();
 
+    let mut denetim = state.denetim("delete_gelir_turu");
     // Soft delete - is_active = false
     // Kayıt fiziksel olarak silinmediği için outbox'a "delete" (tombstone) değil,
     // is_active=0 snapshot'ını taşıyan "update" yazılır.
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gelir_turleri", &id).map_err(TxError::Msg)?;
         let affected = diesel::update(gelir_turleri::table.find(&id))
             .filter(gelir_turleri::tenant_id.eq(&tenant_id_param))
             .set((
@@ -165,6 +173,7 @@ pub fn delete_gelir_turu(
             outbox::queue_change(conn, &tenant_id_param, "gelir_turleri", &id, "update")
                 .map_err(TxError::Msg)?;
         }
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
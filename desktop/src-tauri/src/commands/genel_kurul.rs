@@ -0,0 +1,282 @@
+// Genel kurul: hazirun, yeter sayı, gündem oylamaları ve organ seçimleri (bkz. db::genel_kurul)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::genel_kurul::{
+    self, AcilisGirdi, GenelKurul, GenelKurulDetay, GenelKurulGirdi, GundemGirdi, GundemMaddesi, HazirunSatiri,
+    OylamaGirdi, SecimAdayi, SecimGirdi, SecimSonucuGirdi, Yetersayi,
+};
+use crate::db::toplanti_katilimi;
+
+#[tauri::command]
+pub fn get_genel_kurullar(state: State<AppState>, tenant_id_param: String) -> Result<Vec<GenelKurul>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    genel_kurul::genel_kurullari_getir(&mut conn, &tenant_id_param)
+}
+
+/// Genel kurul, anlık yeter sayı, gündem ve seçimler
+#[tauri::command]
+pub fn get_genel_kurul(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+) -> Result<GenelKurulDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    genel_kurul::genel_kurul_detay(&mut conn, &tenant_id_param, &genel_kurul_id)
+}
+
+/// Toplantıya bağlı genel kurul açar (genel_kurul_id yoksa) ya da hazırlıktakini günceller
+#[tauri::command]
+pub fn genel_kurul_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: Option<String>,
+    data: GenelKurulGirdi,
+) -> Result<GenelKurul, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_kaydet");
+    let id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let id = genel_kurul::genel_kurul_kaydet(conn, &mut denetim, &tenant_id_param, genel_kurul_id.as_deref(), &data)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    genel_kurul::genel_kurul_getir(&mut conn, &tenant_id_param, &id)
+}
+
+#[tauri::command]
+pub fn get_genel_kurul_hazirun(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+) -> Result<Vec<HazirunSatiri>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    genel_kurul::hazirun_getir(&mut conn, &tenant_id_param, &genel_kurul_id)
+}
+
+/// Hazirun listesini hazirun tarihindeki asil üyelerden ve aidat borçlarından yeniden çıkarır
+#[tauri::command]
+pub fn genel_kurul_hazirun_olustur(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+) -> Result<Vec<HazirunSatiri>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_hazirun_olustur");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let hazirun = genel_kurul::hazirun_olustur(conn, &mut denetim, &tenant_id_param, &genel_kurul_id)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(hazirun)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Hazirun imzası; imzali = false imzayı geri alır. Güncel yeter sayı döner.
+#[tauri::command]
+pub fn genel_kurul_imza(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    uye_id: String,
+    imzali: bool,
+) -> Result<Yetersayi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let zaman = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    let mut denetim = state.denetim("genel_kurul_imza");
+    conn.transaction::<_, TxError, _>(|conn| {
+        genel_kurul::imza_kaydet(conn, &mut denetim, &tenant_id_param, &genel_kurul_id, &uye_id, imzali, &zaman)
+            .map_err(TxError::Msg)?;
+        toplanti_katilimi::hazirun_imzasini_isle(
+            conn,
+            &mut denetim,
+            &tenant_id_param,
+            &genel_kurul_id,
+            &uye_id,
+            imzali.then_some(zaman.as_str()),
+        )
+        .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    let gk = genel_kurul::genel_kurul_getir(&mut conn, &tenant_id_param, &genel_kurul_id)?;
+    genel_kurul::yetersayi_hesapla(&mut conn, &tenant_id_param, &gk)
+}
+
+/// Gündemi verilen sırayla eşitler; toplantı açıldıktan sonra değiştirilemez
+#[tauri::command]
+pub fn genel_kurul_gundem_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    maddeler: Vec<GundemGirdi>,
+) -> Result<Vec<GundemMaddesi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_gundem_kaydet");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let gundem = genel_kurul::gundem_kaydet(conn, &mut denetim, &tenant_id_param, &genel_kurul_id, &maddeler)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(gundem)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Toplantıyı açar; ilk çağrıda yeter sayı yoksa genel kurul ertelenir (saglandi = false)
+#[tauri::command]
+pub fn genel_kurul_ac(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    data: AcilisGirdi,
+) -> Result<Yetersayi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_ac");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let yetersayi = genel_kurul::toplantiyi_ac(conn, &mut denetim, &tenant_id_param, &genel_kurul_id, &data)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(yetersayi)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn genel_kurul_oylama(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    madde_id: String,
+    data: OylamaGirdi,
+) -> Result<GundemMaddesi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_oylama");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let madde = genel_kurul::oylama_kaydet(conn, &mut denetim, &tenant_id_param, &genel_kurul_id, &madde_id, &data)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(madde)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Organ seçimi ve aday listesi; seçim id'si döner
+#[tauri::command]
+pub fn genel_kurul_secim_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    data: SecimGirdi,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_secim_kaydet");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let id = genel_kurul::secim_kaydet(conn, &mut denetim, &tenant_id_param, &genel_kurul_id, &data)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Oy sayımını yazar; sınırda eşitlik varsa kura sırası istenir
+#[tauri::command]
+pub fn genel_kurul_secim_sonucu(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    secim_id: String,
+    data: SecimSonucuGirdi,
+) -> Result<Vec<SecimAdayi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_secim_sonucu");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let adaylar = genel_kurul::secim_sonucu_kaydet(conn, &mut denetim, &tenant_id_param, &genel_kurul_id, &secim_id, &data)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(adaylar)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn genel_kurul_tamamla(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("genel_kurul_tamamla");
+    conn.transaction::<_, TxError, _>(|conn| {
+        genel_kurul::tamamla(conn, &mut denetim, &tenant_id_param, &genel_kurul_id).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
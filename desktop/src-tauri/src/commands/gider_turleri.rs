@@ -60,8 +60,10 @@ pub fn create_gider_turu(
     let now = Utc::now().to_rfc3339();
     let id = Uuid::new_v4().to_string();
 
+    let mut denetim = state.denetim("create_gider_turu");
     // Yazım + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gider_turleri", &id).map_err(TxError::Msg)?;
         diesel::insert_into(gider_turleri::table)
             .values((
                 gider_turleri::id.eq(&id),
@@ -78,6 +80,7 @@ pub fn create_gider_turu(
 
         outbox::queue_change(conn, &tenant_id_param, "gider_turleri", &id, "create")
             .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -110,8 +113,10 @@ pub fn update_gider_turu(
         .first::<GiderTuru>(&mut conn)
         .map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("update_gider_turu");
     // Update + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gider_turleri", &id).map_err(TxError::Msg)?;
         diesel::update(gider_turleri::table.find(&id))
             .set((
                 gider_turleri::ad.eq(request.ad.unwrap_or(current.ad)),
@@ -125,6 +130,7 @@ pub fn update_gider_turu(
 
         outbox::queue_change(conn, &tenant_id_param, "gider_turleri", &id, "update")
             .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -149,10 +155,12 @@ pub fn delete_gider_turu(
 
     let now = Utc::now().to_rfc3339();
 
+    let mut denetim = state.denetim("delete_gider_turu");
     // Soft delete - is_active = false
     // Kayıt fiziksel olarak silinmediği için outbox'a "delete" (tombstone) değil,
     // is_active=0 snapshot'ını taşıyan "update" yazılır.
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gider_turleri", &id).map_err(TxError::Msg)?;
         let affected = diesel::update(gider_turleri::table.find(&id))
             .filter(gider_turleri::tenant_id.eq(&tenant_id_param))
             .set((
@@ -165,6 +173,7 @@ pub fn delete_gider_turu(
             outbox::queue_change(conn, &tenant_id_param, "gider_turleri", &id, "update")
                 .map_err(TxError::Msg)?;
         }
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -0,0 +1,121 @@
+// Aile (hane) üyelikleri ve hane aidatlarının üye payları (bkz. db::hane)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::hane::{self, Hane, HaneAidatPayi, HaneDetay, HaneGirdi};
+
+/// Haneler; sadece_aktif verilmezse yalnızca açık haneler
+#[tauri::command]
+pub fn get_haneler(
+    state: State<AppState>,
+    tenant_id_param: String,
+    sadece_aktif: Option<bool>,
+) -> Result<Vec<Hane>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    hane::haneleri_getir(&mut conn, &tenant_id_param, sadece_aktif.unwrap_or(true))
+}
+
+#[tauri::command]
+pub fn get_hane(
+    state: State<AppState>,
+    tenant_id_param: String,
+    hane_id: String,
+) -> Result<HaneDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    hane::hane_getir(&mut conn, &tenant_id_param, &hane_id)
+}
+
+/// Üyenin bağlı olduğu hane; bağlı değilse null
+#[tauri::command]
+pub fn get_uye_hanesi(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+) -> Result<Option<HaneDetay>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    hane::uye_hanesi(&mut conn, &tenant_id_param, &uye_id)
+}
+
+/// Hane ekler (hane_id yoksa) ya da günceller; üye listesi verilen listeyle eşitlenir.
+/// Mevcut aidatların payları değişmez, yeni üye listesi sonraki tahakkuklarda kullanılır.
+#[tauri::command]
+pub fn hane_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    hane_id: Option<String>,
+    data: HaneGirdi,
+) -> Result<HaneDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("hane_kaydet");
+    let id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let id = hane::hane_kaydet(conn, &mut denetim, &tenant_id_param, hane_id.as_deref(), &data)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    hane::hane_getir(&mut conn, &tenant_id_param, &id)
+}
+
+/// Haneyi kapatır; üyeler sonraki tahakkuklarda tek tek borçlandırılır
+#[tauri::command]
+pub fn hane_kapat(
+    state: State<AppState>,
+    tenant_id_param: String,
+    hane_id: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("hane_kapat");
+    conn.transaction::<_, TxError, _>(|conn| {
+        hane::hane_kapat(conn, &mut denetim, &tenant_id_param, &hane_id).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Hane aidatlarının üye payları ve paylara dağıtılmış ödemeler; hane ya da üye ile süzülebilir
+#[tauri::command]
+pub fn get_hane_aidat_paylari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    hane_id: Option<String>,
+    uye_id: Option<String>,
+    yil: Option<i32>,
+) -> Result<Vec<HaneAidatPayi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    hane::aidat_paylari(&mut conn, &tenant_id_param, hane_id.as_deref(), uye_id.as_deref(), yil)
+}
@@ -0,0 +1,250 @@
+// Aidat borcu yaşlandırma raporu, ihtar şablonları, ihtar kesimi/tebliği ve ihraç adayları (bkz. db::ihtar)
+use tauri::State;
+use diesel::prelude::*;
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::ihtar::{
+    self, BorcYaslandirma, IhracAdayi, IhracKurali, Ihtar, IhtarAdayi, IhtarKalemi, IhtarSablonu, IhtarSablonuGirdi,
+};
+
+#[derive(Debug, Serialize)]
+pub struct IhtarDetay {
+    #[serde(flatten)]
+    pub ihtar: Ihtar,
+    pub kalemler: Vec<IhtarKalemi>,
+}
+
+/// YYYY-MM-DD; boşsa bugün
+fn rapor_tarihi(tarih: Option<String>) -> Result<NaiveDate, String> {
+    match tarih.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(t) => NaiveDate::parse_from_str(t, "%Y-%m-%d").map_err(|_| format!("Geçersiz tarih: {}", t)),
+        None => Ok(Local::now().date_naive()),
+    }
+}
+
+/// Üye bazında 0-30 / 31-90 / 91-365 / 1 yıl üstü borç yaşlandırması; tarih verilmezse bugün
+#[tauri::command]
+pub fn get_borc_yaslandirma(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tarih: Option<String>,
+) -> Result<BorcYaslandirma, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let tarih = rapor_tarihi(tarih)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ihtar::yaslandirma(&mut conn, &tenant_id_param, tarih)
+}
+
+/// Derneğin şablonları; hiç tanımlanmamışsa yerleşik şablonlar (id: null) döner
+#[tauri::command]
+pub fn get_ihtar_sablonlari(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<IhtarSablonu>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let sablonlar = ihtar::sablonlari_getir(&mut conn, &tenant_id_param)?;
+    if sablonlar.is_empty() {
+        return Ok(ihtar::varsayilan_sablonlar());
+    }
+    Ok(sablonlar)
+}
+
+/// Seviyeye göre şablon ekler ya da günceller (yönetici)
+#[tauri::command]
+pub fn ihtar_sablonu_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    data: IhtarSablonuGirdi,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("ihtar_sablonu_kaydet");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let id = ihtar::sablon_kaydet(conn, &mut denetim, &tenant_id_param, &data).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_ihrac_kurali(
+    state: State<AppState>,
+    tenant_id_param: String,
+) -> Result<IhracKurali, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ihtar::kurali_getir(&mut conn, &tenant_id_param)
+}
+
+/// Tüzüğün ödenmeyen aidat hükmü (yönetici)
+#[tauri::command]
+pub fn ihrac_kurali_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    data: IhracKurali,
+) -> Result<IhracKurali, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("ihrac_kurali_kaydet");
+    conn.transaction::<_, TxError, _>(|conn| {
+        ihtar::kurali_kaydet(conn, &mut denetim, &tenant_id_param, &data).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    ihtar::kurali_getir(&mut conn, &tenant_id_param)
+}
+
+/// Vadesi geçmiş borcu olan üyeler, kesilebilecek seviye ve varsa engel
+#[tauri::command]
+pub fn get_ihtar_adaylari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tarih: Option<String>,
+) -> Result<Vec<IhtarAdayi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let tarih = rapor_tarihi(tarih)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ihtar::ihtar_adaylari(&mut conn, &tenant_id_param, tarih)
+}
+
+/// Seçilen üyelere sıradaki seviyeden ihtar keser; biri kesilemezse hiçbiri kesilmez.
+/// Mektuplar ihtar_mektubu_pdf ile basılır.
+#[tauri::command]
+pub fn ihtar_olustur(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_idleri: Vec<String>,
+    tarih: Option<String>,
+) -> Result<Vec<Ihtar>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let tarih = rapor_tarihi(tarih)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let olusturan = state.current_user.lock().unwrap().as_ref().map(|u| u.full_name.clone());
+    let mut denetim = state.denetim("ihtar_olustur");
+    let idler = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let idler = ihtar::ihtar_olustur(conn, &mut denetim, &tenant_id_param, &uye_idleri, tarih, olusturan.as_deref())
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(idler)
+        })
+        .map_err(|e| e.to_string())?;
+
+    idler.iter().map(|id| ihtar::ihtar_getir(&mut conn, &tenant_id_param, id)).collect()
+}
+
+/// İhtar geçmişi; üye verilirse yalnızca o üyenin
+#[tauri::command]
+pub fn get_ihtarlar(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: Option<String>,
+) -> Result<Vec<Ihtar>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ihtar::ihtarlari_getir(&mut conn, &tenant_id_param, uye_id.as_deref())
+}
+
+#[tauri::command]
+pub fn get_ihtar(
+    state: State<AppState>,
+    tenant_id_param: String,
+    ihtar_id: String,
+) -> Result<IhtarDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    Ok(IhtarDetay {
+        ihtar: ihtar::ihtar_getir(&mut conn, &tenant_id_param, &ihtar_id)?,
+        kalemler: ihtar::ihtar_kalemleri(&mut conn, &tenant_id_param, &ihtar_id)?,
+    })
+}
+
+/// Tebliğ tarihini ve kanalını kaydeder; cevap süresi bu tarihten işler
+#[tauri::command]
+pub fn ihtar_bildirimi_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    ihtar_id: String,
+    bildirim_tarihi: String,
+    bildirim_kanali: String,
+) -> Result<Ihtar, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let bildirim_tarihi = NaiveDate::parse_from_str(bildirim_tarihi.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Geçersiz tarih: {}", bildirim_tarihi))?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("ihtar_bildirimi_kaydet");
+    conn.transaction::<_, TxError, _>(|conn| {
+        ihtar::bildirim_kaydet(conn, &mut denetim, &tenant_id_param, &ihtar_id, bildirim_tarihi, bildirim_kanali.trim())
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    ihtar::ihtar_getir(&mut conn, &tenant_id_param, &ihtar_id)
+}
+
+/// Tüzük hükmüne göre ihraç edilebilecek üyeler; tarih verilmezse bugün
+#[tauri::command]
+pub fn get_ihrac_adaylari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tarih: Option<String>,
+) -> Result<Vec<IhracAdayi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let tarih = rapor_tarihi(tarih)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    ihtar::ihrac_adaylari(&mut conn, &tenant_id_param, tarih)
+}
@@ -0,0 +1,163 @@
+// Karar defteri: organ kararları, oylar ve dayanak olduğu kayıtlar (bkz. db::karar_defteri)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::karar_defteri::{self, BaglantiGirdi, Karar, KararDetay, KararGirdi};
+
+#[tauri::command]
+pub fn get_kararlar(
+    state: State<AppState>,
+    tenant_id_param: String,
+    organ: Option<String>,
+    yil: Option<i32>,
+) -> Result<Vec<Karar>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    karar_defteri::kararlari_getir(&mut conn, &tenant_id_param, organ.as_deref(), yil)
+}
+
+/// Karar, katılımcı oyları ve bağlı kayıtlar
+#[tauri::command]
+pub fn get_karar(state: State<AppState>, tenant_id_param: String, karar_id: String) -> Result<KararDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    karar_defteri::karar_detay(&mut conn, &tenant_id_param, &karar_id)
+}
+
+/// Deftere yeni karar yazar; numara organ ve yıl içindeki sıradan verilir
+#[tauri::command]
+pub fn karar_ekle(state: State<AppState>, tenant_id_param: String, data: KararGirdi) -> Result<KararDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("karar_ekle");
+    let id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let id = karar_defteri::karar_ekle(conn, &mut denetim, &tenant_id_param, &data).map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    karar_defteri::karar_detay(&mut conn, &tenant_id_param, &id)
+}
+
+#[tauri::command]
+pub fn karar_guncelle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    karar_id: String,
+    data: KararGirdi,
+) -> Result<KararDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("karar_guncelle");
+    conn.transaction::<_, TxError, _>(|conn| {
+        karar_defteri::karar_guncelle(conn, &mut denetim, &tenant_id_param, &karar_id, &data).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    karar_defteri::karar_detay(&mut conn, &tenant_id_param, &karar_id)
+}
+
+/// Kararı gerekçesiyle iptal eder; numarası defterde kalır
+#[tauri::command]
+pub fn karar_iptal(
+    state: State<AppState>,
+    tenant_id_param: String,
+    karar_id: String,
+    neden: String,
+) -> Result<Karar, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("karar_iptal");
+    conn.transaction::<_, TxError, _>(|conn| {
+        karar_defteri::karar_iptal(conn, &mut denetim, &tenant_id_param, &karar_id, &neden).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    karar_defteri::karar_getir(&mut conn, &tenant_id_param, &karar_id)
+}
+
+/// Kararı gelir, gider, demirbaş ya da üye kaydına bağlar
+#[tauri::command]
+pub fn karar_baglanti_ekle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    karar_id: String,
+    data: BaglantiGirdi,
+) -> Result<KararDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("karar_baglanti_ekle");
+    conn.transaction::<_, TxError, _>(|conn| {
+        karar_defteri::baglanti_ekle(conn, &mut denetim, &tenant_id_param, &karar_id, &data).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    karar_defteri::karar_detay(&mut conn, &tenant_id_param, &karar_id)
+}
+
+#[tauri::command]
+pub fn karar_baglanti_sil(state: State<AppState>, tenant_id_param: String, baglanti_id: String) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("karar_baglanti_sil");
+    conn.transaction::<_, TxError, _>(|conn| {
+        karar_defteri::baglanti_sil(conn, &mut denetim, &tenant_id_param, &baglanti_id).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Bir kaydın dayanağı olan kararlar (ör. gider formundaki "karar" bilgisi)
+#[tauri::command]
+pub fn get_kayit_kararlari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tablo: String,
+    kayit_id: String,
+) -> Result<Vec<Karar>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    karar_defteri::kayit_kararlari(&mut conn, &tenant_id_param, &tablo, &kayit_id)
+}
@@ -0,0 +1,369 @@
+// Kasa sayımı (mutabakat) komutları
+// Akış: kasa_sayimi_kaydet (sayılan tutar + o günkü defter bakiyesi ve fark) →
+// kasa_sayimi_onayla (fark açıklanır, istenirse düzeltme gelir/gideri oluşur).
+// Onaylanan sayımlar kasa başına imza geçmişi olarak kalır.
+
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::commands::mali::{self, outbox_err, CreateGelirRequest, CreateGiderRequest};
+use crate::db::denetim;
+use crate::db::kasa_sayimi::{self, Kupur};
+use crate::db::para::Para;
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct KasaSayimi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kasa_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kasa_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub sayim_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub sayim_tipi: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kupurler: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub sayilan_tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub defter_bakiyesi: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub fark: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub sayan_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub onaylayan_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub onay_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub fark_aciklamasi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub duzeltme_tablo: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub duzeltme_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+/// Nakit kasada kupurler, banka kasasında banka_bakiyesi zorunludur
+#[derive(Debug, Deserialize)]
+pub struct KasaSayimiRequest {
+    pub kasa_id: String,
+    pub sayim_tarihi: String,
+    pub kupurler: Option<Vec<Kupur>>,
+    pub banka_bakiyesi: Option<Para>,
+    pub aciklama: Option<String>,
+}
+
+/// Onay: fark varsa fark_aciklamasi zorunlu. duzeltme_kaydet = true ise fark
+/// sayım tarihli gelir (fazla) veya gider (açık) olarak kasaya işlenir;
+/// false ise fark yalnızca açıklanır (ör. bankada henüz yansımamış işlem).
+#[derive(Debug, Deserialize)]
+pub struct KasaSayimiOnayRequest {
+    pub fark_aciklamasi: Option<String>,
+    #[serde(default)]
+    pub duzeltme_kaydet: bool,
+    pub gelir_turu_id: Option<String>,
+    pub gider_turu_id: Option<String>,
+}
+
+const SAYIM_SORGUSU: &str = "SELECT s.id, s.kasa_id, k.kasa_adi, s.sayim_tarihi, s.sayim_tipi, s.kupurler,
+        s.sayilan_tutar, s.defter_bakiyesi, s.fark, s.aciklama, s.durum,
+        su.full_name as sayan_adi, ou.full_name as onaylayan_adi, s.onay_tarihi,
+        s.fark_aciklamasi, s.duzeltme_tablo, s.duzeltme_id, s.created_at
+     FROM kasa_sayimlari s
+     LEFT JOIN kasalar k ON k.id = s.kasa_id
+     LEFT JOIN users su ON su.id = s.sayan
+     LEFT JOIN users ou ON ou.id = s.onaylayan";
+
+fn sayim_getir(conn: &mut SqliteConnection, tenant_id: &str, sayim_id: &str) -> Result<KasaSayimi, String> {
+    diesel::sql_query(format!("{} WHERE s.id = ?1 AND s.tenant_id = ?2", SAYIM_SORGUSU))
+        .bind::<diesel::sql_types::Text, _>(sayim_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Kasa sayımı bulunamadı".to_string())
+}
+
+/// Sayım gününün sonundaki defter bakiyesi (o günün hareketleri dahil)
+fn gun_sonu_bakiyesi(conn: &mut SqliteConnection, kasa_id: &str, tarih: &str) -> Result<Para, String> {
+    let gun = NaiveDate::parse_from_str(tarih, "%Y-%m-%d")
+        .map_err(|_| format!("Geçersiz sayım tarihi: {}", tarih))?;
+    let ertesi = (gun + chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+    mali::kasa_bakiyesi_tarihte(conn, kasa_id, &ertesi).map_err(|e| e.to_string())
+}
+
+/// Sayım ekranı için kasanın verilen tarihteki gün sonu defter bakiyesi
+#[tauri::command]
+pub async fn get_kasa_defter_bakiyesi(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kasa_id: String,
+    tarih: String,
+) -> Result<Para, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    gun_sonu_bakiyesi(&mut conn, &kasa_id, &tarih)
+}
+
+/// Sayımı kaydeder; defter bakiyesi ve fark kayıt anında hesaplanıp saklanır
+#[tauri::command]
+pub async fn kasa_sayimi_kaydet(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    data: KasaSayimiRequest,
+) -> Result<KasaSayimi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let sayan = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    #[derive(QueryableByName)]
+    struct KasaBilgisi {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        kasa_tipi: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        para_birimi: String,
+    }
+    let kasa: KasaBilgisi = diesel::sql_query(
+        "SELECT kasa_tipi, para_birimi FROM kasalar WHERE id = ?1 AND tenant_id = ?2 AND is_active = 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .get_result(&mut conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Kasa bulunamadı")?;
+
+    let (sayim_tipi, kupurler_json, sayilan) = if kasa.kasa_tipi.as_deref() == Some("Banka") {
+        let bakiye = data.banka_bakiyesi.ok_or("Banka kasası için ekstre bakiyesi girilmelidir")?;
+        ("banka", None, bakiye)
+    } else {
+        let kupurler = data.kupurler.as_deref().unwrap_or_default();
+        if kupurler.is_empty() {
+            return Err("Nakit sayımında küpür adetleri girilmelidir".to_string());
+        }
+        let (kupurler, toplam) = kasa_sayimi::kupur_toplami(&kasa.para_birimi, kupurler)?;
+        ("nakit", Some(serde_json::to_string(&kupurler).map_err(|e| e.to_string())?), toplam)
+    };
+
+    let defter = gun_sonu_bakiyesi(&mut conn, &data.kasa_id, &data.sayim_tarihi)?;
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    denetim::kayitli_yazim(&mut conn, state.denetim("kasa_sayimi_kaydet"), &tenant_id_param, "kasa_sayimlari", &id, |conn| {
+        diesel::sql_query(
+            "INSERT INTO kasa_sayimlari
+                (id, tenant_id, kasa_id, sayim_tarihi, sayim_tipi, kupurler, sayilan_tutar,
+                 defter_bakiyesi, fark, aciklama, durum, sayan, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'sayildi', ?11, ?12, ?12)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
+        .bind::<diesel::sql_types::Text, _>(&data.sayim_tarihi)
+        .bind::<diesel::sql_types::Text, _>(sayim_tipi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kupurler_json)
+        .bind::<diesel::sql_types::Double, _>(sayilan)
+        .bind::<diesel::sql_types::Double, _>(defter)
+        .bind::<diesel::sql_types::Double, _>(sayilan - defter)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&sayan)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
+
+    sayim_getir(&mut conn, &tenant_id_param, &id)
+}
+
+/// Sayımı onaylar (imza). Defter bakiyesi onay anında yeniden hesaplanır; sayımdan
+/// sonra o tarihe kayıt girildiyse fark güncellenmiş haliyle onaylanır.
+#[tauri::command]
+pub async fn kasa_sayimi_onayla(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    sayim_id: String,
+    data: KasaSayimiOnayRequest,
+) -> Result<KasaSayimi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let onaylayan = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let sayim = sayim_getir(&mut conn, &tenant_id_param, &sayim_id)?;
+    if sayim.durum != "sayildi" {
+        return Err("Bu sayım zaten onaylanmış veya iptal edilmiş".to_string());
+    }
+
+    let defter = gun_sonu_bakiyesi(&mut conn, &sayim.kasa_id, &sayim.sayim_tarihi)?;
+    let fark = sayim.sayilan_tutar - defter;
+    let fark_aciklamasi = data
+        .fark_aciklamasi
+        .as_deref()
+        .map(str::trim)
+        .filter(|a| !a.is_empty());
+    if !fark.is_zero() && fark_aciklamasi.is_none() {
+        return Err(format!(
+            "Sayım farkı {} ₺; onay için farkın açıklaması yazılmalıdır",
+            fark.tr_format()
+        ));
+    }
+    let duzeltme = data.duzeltme_kaydet && !fark.is_zero();
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut denetim = state.denetim("kasa_sayimi_onayla");
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "kasa_sayimlari", &sayim_id).map_err(outbox_err)?;
+        let mut duzeltme_kaydi: Option<(&str, String)> = None;
+        if duzeltme {
+            let yeni_id = Uuid::new_v4().to_string();
+            let aciklama = Some(format!(
+                "Kasa sayım {} ({}): {}",
+                if fark.is_positive() { "fazlası" } else { "açığı" },
+                sayim.sayim_tarihi,
+                fark_aciklamasi.unwrap_or_default()
+            ));
+            if fark.is_positive() {
+                denetim.yeni_kayit(&tenant_id_param, "gelirler", &yeni_id);
+                mali::gelir_ekle(conn, &tenant_id_param, &yeni_id, &CreateGelirRequest {
+                    kasa_id: sayim.kasa_id.clone(),
+                    gelir_turu_id: data.gelir_turu_id.clone(),
+                    tarih: sayim.sayim_tarihi.clone(),
+                    tutar: fark,
+                    aciklama,
+                    makbuz_no: None,
+                    alt_kategori: Some("Sayım Farkı".to_string()),
+                    tahakkuk_durumu: None,
+                    belge_no: None,
+                    tahsil_eden: None,
+                    belge_id: None,
+                    uye_id: None,
+                    aidat_id: None,
+                    ait_oldugu_yil: None,
+                    etkinlik_id: None,
+                })?;
+                duzeltme_kaydi = Some(("gelirler", yeni_id));
+            } else {
+                denetim.yeni_kayit(&tenant_id_param, "giderler", &yeni_id);
+                mali::gider_ekle(conn, &tenant_id_param, &yeni_id, &CreateGiderRequest {
+                    kasa_id: sayim.kasa_id.clone(),
+                    gider_turu_id: data.gider_turu_id.clone(),
+                    tarih: sayim.sayim_tarihi.clone(),
+                    tutar: fark.abs(),
+                    aciklama,
+                    fatura_no: None,
+                    alt_kategori: Some("Sayım Farkı".to_string()),
+                    islem_no: None,
+                    odeyen: None,
+                    notlar: None,
+                    belge_id: None,
+                    uye_id: None,
+                    demirbas_id: None,
+                    etkinlik_id: None,
+                })?;
+                duzeltme_kaydi = Some(("giderler", yeni_id));
+            }
+            mali::update_kasa_bakiye(conn, &sayim.kasa_id)?;
+        }
+
+        diesel::sql_query(
+            "UPDATE kasa_sayimlari
+             SET durum = 'onaylandi', defter_bakiyesi = ?1, fark = ?2, onaylayan = ?3, onay_tarihi = ?4,
+                 fark_aciklamasi = ?5, duzeltme_tablo = ?6, duzeltme_id = ?7, updated_at = ?4
+             WHERE id = ?8",
+        )
+        .bind::<diesel::sql_types::Double, _>(defter)
+        .bind::<diesel::sql_types::Double, _>(fark)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&onaylayan)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(fark_aciklamasi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(duzeltme_kaydi.as_ref().map(|(t, _)| *t))
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(duzeltme_kaydi.as_ref().map(|(_, id)| id.as_str()))
+        .bind::<diesel::sql_types::Text, _>(&sayim_id)
+        .execute(conn)?;
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    sayim_getir(&mut conn, &tenant_id_param, &sayim_id)
+}
+
+/// Onaylanmamış sayımı iptal eder (onaylı sayımlar imza geçmişi olarak kalır)
+#[tauri::command]
+pub async fn kasa_sayimi_iptal(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    sayim_id: String,
+) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    denetim::kayitli_yazim(&mut conn, state.denetim("kasa_sayimi_iptal"), &tenant_id_param, "kasa_sayimlari", &sayim_id, |conn| {
+        let affected = diesel::sql_query(
+            "UPDATE kasa_sayimlari SET durum = 'iptal', updated_at = ?1
+             WHERE id = ?2 AND tenant_id = ?3 AND durum = 'sayildi'",
+        )
+        .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(&sayim_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+
+        if affected == 0 {
+            return Err("Onay bekleyen kasa sayımı bulunamadı".to_string());
+        }
+        Ok(())
+    })
+}
+
+/// Sayım geçmişi, en yeni önce (kasa verilirse yalnızca o kasanınki)
+#[tauri::command]
+pub async fn get_kasa_sayimlari(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    kasa_id: Option<String>,
+    durum: Option<String>,
+) -> Result<Vec<KasaSayimi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    diesel::sql_query(format!(
+        "{} WHERE s.tenant_id = ?1 AND (?2 IS NULL OR s.kasa_id = ?2) AND (?3 IS NULL OR s.durum = ?3)
+         ORDER BY s.sayim_tarihi DESC, s.created_at DESC",
+        SAYIM_SORGUSU
+    ))
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&kasa_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&durum)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())
+}
@@ -3,7 +3,11 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
+use crate::db::donem_kilidi;
+use crate::commands::mali::outbox_err;
 use serde::{Serialize, Deserialize};
+use crate::db::para::Para;
 use diesel::r2d2::{self, ConnectionManager, Pool};
 use diesel::sqlite::SqliteConnection;
 
@@ -14,7 +18,7 @@ pub struct KoyKasa {
     pub tenant_id: String,
     pub kasa_adi: String,
     pub para_birimi: String,
-    pub bakiye: f64,
+    pub bakiye: Para,
     pub aciklama: Option<String>,
     pub is_active: bool,
     pub created_at: String,
@@ -29,7 +33,7 @@ pub struct KoyGelir {
     pub kasa_id: String,
     pub gelir_turu: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub makbuz_no: Option<String>,
     pub created_at: String,
@@ -44,7 +48,7 @@ pub struct KoyGider {
     pub kasa_id: String,
     pub gider_turu: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub fatura_no: Option<String>,
     pub created_at: String,
@@ -59,7 +63,7 @@ pub struct KoyVirman {
     pub kaynak_kasa_id: String,
     pub hedef_kasa_id: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub created_at: String,
     pub updated_at: String,
@@ -77,7 +81,7 @@ pub struct CreateKoyGelirRequest {
     pub kasa_id: String,
     pub gelir_turu: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub makbuz_no: Option<String>,
 }
@@ -87,7 +91,7 @@ pub struct UpdateKoyGelirRequest {
     pub kasa_id: Option<String>,
     pub gelir_turu: Option<String>,
     pub tarih: Option<String>,
-    pub tutar: Option<f64>,
+    pub tutar: Option<Para>,
     pub aciklama: Option<String>,
     pub makbuz_no: Option<String>,
 }
@@ -97,7 +101,7 @@ pub struct CreateKoyGiderRequest {
     pub kasa_id: String,
     pub gider_turu: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub fatura_no: Option<String>,
 }
@@ -107,7 +111,7 @@ pub struct UpdateKoyGiderRequest {
     pub kasa_id: Option<String>,
     pub gider_turu: Option<String>,
     pub tarih: Option<String>,
-    pub tutar: Option<f64>,
+    pub tutar: Option<Para>,
     pub aciklama: Option<String>,
     pub fatura_no: Option<String>,
 }
@@ -150,19 +154,21 @@ pub fn create_koy_kasa(
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-        diesel::sql_query(
-            "INSERT INTO koy_kasalar (id, tenant_id, kasa_adi, para_birimi, bakiye, aciklama, is_active, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, 0, ?5, 1, ?6, ?7)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_adi)
-        .bind::<diesel::sql_types::Text, _>(&data.para_birimi)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("create_koy_kasa"), &tenant_id_param, "koy_kasalar", &new_id, |conn| {
+            diesel::sql_query(
+                "INSERT INTO koy_kasalar (id, tenant_id, kasa_adi, para_birimi, bakiye, aciklama, is_active, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, 0, ?5, 1, ?6, ?7)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&new_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Text, _>(&data.kasa_adi)
+            .bind::<diesel::sql_types::Text, _>(&data.para_birimi)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
     }
 
     // Fetch the created record
@@ -193,18 +199,20 @@ pub fn update_koy_kasa(
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-        diesel::sql_query(
-            "UPDATE koy_kasalar SET kasa_adi = ?1, para_birimi = ?2, aciklama = ?3, updated_at = ?4
-             WHERE id = ?5 AND tenant_id = ?6"
-        )
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_adi)
-        .bind::<diesel::sql_types::Text, _>(&data.para_birimi)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("update_koy_kasa"), &tenant_id_param, "koy_kasalar", &kasa_id, |conn| {
+            diesel::sql_query(
+                "UPDATE koy_kasalar SET kasa_adi = ?1, para_birimi = ?2, aciklama = ?3, updated_at = ?4
+                 WHERE id = ?5 AND tenant_id = ?6"
+            )
+            .bind::<diesel::sql_types::Text, _>(&data.kasa_adi)
+            .bind::<diesel::sql_types::Text, _>(&data.para_birimi)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&kasa_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
     }
 
     let db = state.db.lock().unwrap();
@@ -230,13 +238,15 @@ pub fn delete_koy_kasa(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "DELETE FROM koy_kasalar WHERE id = ?1 AND tenant_id = ?2"
-    )
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_koy_kasa"), &tenant_id_param, "koy_kasalar", &kasa_id, |conn| {
+        diesel::sql_query(
+            "DELETE FROM koy_kasalar WHERE id = ?1 AND tenant_id = ?2"
+        )
+        .bind::<diesel::sql_types::Text, _>(&kasa_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -326,28 +336,31 @@ pub fn create_koy_gelir(
         let db = state.db.lock().unwrap();
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
+        donem_kilidi::tarihi_dogrula(&mut conn, &tenant_id_param, &data.tarih)?;
 
         // Insert gelir
-        diesel::sql_query(
-            "INSERT INTO koy_gelirler (id, tenant_id, kasa_id, gelir_turu, tarih, tutar, aciklama, makbuz_no, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&data.gelir_turu)
-        .bind::<diesel::sql_types::Text, _>(&data.tarih)
-        .bind::<diesel::sql_types::Double, _>(data.tutar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.makbuz_no)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("create_koy_gelir"), &tenant_id_param, "koy_gelirler", &new_id, |conn| {
+            diesel::sql_query(
+                "INSERT INTO koy_gelirler (id, tenant_id, kasa_id, gelir_turu, tarih, tutar, aciklama, makbuz_no, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&new_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
+            .bind::<diesel::sql_types::Text, _>(&data.gelir_turu)
+            .bind::<diesel::sql_types::Text, _>(&data.tarih)
+            .bind::<diesel::sql_types::Double, _>(data.tutar)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.makbuz_no)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
 
         // Update kasa bakiye
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye + ?1, 2), updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
         )
         .bind::<diesel::sql_types::Double, _>(data.tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
@@ -386,12 +399,13 @@ pub fn delete_koy_gelir(
         .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
         .get_result(&mut conn)
         .map_err(|e| e.to_string())?;
+    donem_kilidi::tarihi_dogrula(&mut conn, &tenant_id_param, &gelir.tarih)?;
 
     let now = Utc::now().naive_utc().to_string();
 
     // Update kasa bakiye
     diesel::sql_query(
-        "UPDATE koy_kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
+        "UPDATE koy_kasalar SET bakiye = ROUND(bakiye - ?1, 2), updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
     )
     .bind::<diesel::sql_types::Double, _>(gelir.tutar)
     .bind::<diesel::sql_types::Text, _>(&now)
@@ -401,11 +415,13 @@ pub fn delete_koy_gelir(
     .map_err(|e| e.to_string())?;
 
     // Delete gelir
-    diesel::sql_query("DELETE FROM koy_gelirler WHERE id = ?1 AND tenant_id = ?2")
-        .bind::<diesel::sql_types::Text, _>(&gelir_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_koy_gelir"), &tenant_id_param, "koy_gelirler", &gelir_id, |conn| {
+        diesel::sql_query("DELETE FROM koy_gelirler WHERE id = ?1 AND tenant_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(&gelir_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -494,28 +510,31 @@ pub fn create_koy_gider(
         let db = state.db.lock().unwrap();
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
+        donem_kilidi::tarihi_dogrula(&mut conn, &tenant_id_param, &data.tarih)?;
 
         // Insert gider
-        diesel::sql_query(
-            "INSERT INTO koy_giderler (id, tenant_id, kasa_id, gider_turu, tarih, tutar, aciklama, fatura_no, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&data.gider_turu)
-        .bind::<diesel::sql_types::Text, _>(&data.tarih)
-        .bind::<diesel::sql_types::Double, _>(data.tutar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("create_koy_gider"), &tenant_id_param, "koy_giderler", &new_id, |conn| {
+            diesel::sql_query(
+                "INSERT INTO koy_giderler (id, tenant_id, kasa_id, gider_turu, tarih, tutar, aciklama, fatura_no, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&new_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
+            .bind::<diesel::sql_types::Text, _>(&data.gider_turu)
+            .bind::<diesel::sql_types::Text, _>(&data.tarih)
+            .bind::<diesel::sql_types::Double, _>(data.tutar)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
 
         // Update kasa bakiye
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye - ?1, 2), updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
         )
         .bind::<diesel::sql_types::Double, _>(data.tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
@@ -554,12 +573,13 @@ pub fn delete_koy_gider(
         .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
         .get_result(&mut conn)
         .map_err(|e| e.to_string())?;
+    donem_kilidi::tarihi_dogrula(&mut conn, &tenant_id_param, &gider.tarih)?;
 
     let now = Utc::now().naive_utc().to_string();
 
     // Update kasa bakiye
     diesel::sql_query(
-        "UPDATE koy_kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
+        "UPDATE koy_kasalar SET bakiye = ROUND(bakiye + ?1, 2), updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
     )
     .bind::<diesel::sql_types::Double, _>(gider.tutar)
     .bind::<diesel::sql_types::Text, _>(&now)
@@ -569,11 +589,13 @@ pub fn delete_koy_gider(
     .map_err(|e| e.to_string())?;
 
     // Delete gider
-    diesel::sql_query("DELETE FROM koy_giderler WHERE id = ?1 AND tenant_id = ?2")
-        .bind::<diesel::sql_types::Text, _>(&gider_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_koy_gider"), &tenant_id_param, "koy_giderler", &gider_id, |conn| {
+        diesel::sql_query("DELETE FROM koy_giderler WHERE id = ?1 AND tenant_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(&gider_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -610,7 +632,13 @@ pub fn update_koy_gelir(
     let yeni_kasa_id = request.kasa_id.clone().unwrap_or(eski_kasa_id.clone());
     let yeni_tutar = request.tutar.unwrap_or(eski_tutar);
 
+    let mut denetim = state.denetim("update_koy_gelir");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &current.tarih).map_err(outbox_err)?;
+        if let Some(yeni_tarih) = request.tarih.as_deref() {
+            donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, yeni_tarih).map_err(outbox_err)?;
+        }
+        denetim.izle(conn, &tenant_id_param, "koy_gelirler", &gelir_id).map_err(outbox_err)?;
         // Update gelir
         diesel::sql_query(
             "UPDATE koy_gelirler 
@@ -629,7 +657,7 @@ pub fn update_koy_gelir(
 
         // Update old kasa (subtract old amount)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye - ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(eski_tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
@@ -638,13 +666,14 @@ pub fn update_koy_gelir(
 
         // Update new kasa (add new amount)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye + ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(yeni_tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
         .bind::<diesel::sql_types::Text, _>(&yeni_kasa_id)
         .execute(conn)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -683,7 +712,13 @@ pub fn update_koy_gider(
     let yeni_kasa_id = request.kasa_id.clone().unwrap_or(eski_kasa_id.clone());
     let yeni_tutar = request.tutar.unwrap_or(eski_tutar);
 
+    let mut denetim = state.denetim("update_koy_gider");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &current.tarih).map_err(outbox_err)?;
+        if let Some(yeni_tarih) = request.tarih.as_deref() {
+            donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, yeni_tarih).map_err(outbox_err)?;
+        }
+        denetim.izle(conn, &tenant_id_param, "koy_giderler", &gider_id).map_err(outbox_err)?;
         // Update gider
         diesel::sql_query(
             "UPDATE koy_giderler 
@@ -702,7 +737,7 @@ pub fn update_koy_gider(
 
         // Update old kasa (add old amount back)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye + ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(eski_tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
@@ -711,13 +746,14 @@ pub fn update_koy_gider(
 
         // Update new kasa (subtract new amount)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye - ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(yeni_tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
         .bind::<diesel::sql_types::Text, _>(&yeni_kasa_id)
         .execute(conn)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -735,7 +771,7 @@ pub struct CreateKoyVirmanRequest {
     pub kaynak_kasa_id: String,
     pub hedef_kasa_id: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
 }
 
@@ -797,8 +833,11 @@ pub fn create_koy_virman(
     let virman_id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
+    let mut denetim = state.denetim("create_koy_virman");
     // Execute in transaction
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &request.tarih).map_err(outbox_err)?;
+        denetim.izle(conn, &tenant_id_param, "koy_virmanlar", &virman_id).map_err(outbox_err)?;
         // Insert virman
         diesel::sql_query(
             "INSERT INTO koy_virmanlar (id, tenant_id, kaynak_kasa_id, hedef_kasa_id, tarih, tutar, aciklama, created_at, updated_at) 
@@ -817,7 +856,7 @@ pub fn create_koy_virman(
 
         // Update kaynak kasa (subtract amount)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye - ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(request.tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
@@ -826,13 +865,14 @@ pub fn create_koy_virman(
 
         // Update hedef kasa (add amount)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye + ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(request.tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
         .bind::<diesel::sql_types::Text, _>(&request.hedef_kasa_id)
         .execute(conn)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -867,8 +907,11 @@ pub fn delete_koy_virman(
 
     let now = Utc::now().to_rfc3339();
 
+    let mut denetim = state.denetim("delete_koy_virman");
     // Execute in transaction
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &virman.tarih).map_err(outbox_err)?;
+        denetim.izle(conn, &tenant_id_param, "koy_virmanlar", &virman_id).map_err(outbox_err)?;
         // Delete virman
         diesel::sql_query("DELETE FROM koy_virmanlar WHERE id = ?1")
             .bind::<diesel::sql_types::Text, _>(&virman_id)
@@ -876,7 +919,7 @@ pub fn delete_koy_virman(
 
         // Reverse kaynak kasa (add amount back)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye + ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(virman.tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
@@ -885,13 +928,14 @@ pub fn delete_koy_virman(
 
         // Reverse hedef kasa (subtract amount)
         diesel::sql_query(
-            "UPDATE koy_kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE koy_kasalar SET bakiye = ROUND(bakiye - ?1, 2), updated_at = ?2 WHERE id = ?3"
         )
         .bind::<diesel::sql_types::Double, _>(virman.tutar)
         .bind::<diesel::sql_types::Text, _>(&now)
         .bind::<diesel::sql_types::Text, _>(&virman.hedef_kasa_id)
         .execute(conn)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())
 }
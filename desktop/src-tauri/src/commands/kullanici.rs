@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use tauri::State;
 use crate::state::AppState;
 use crate::db::models::User;
+use crate::db::denetim;
 
 #[derive(Serialize, Deserialize)]
 pub struct ChangePasswordInput {
@@ -14,11 +15,12 @@ pub struct ChangePasswordInput {
 #[tauri::command]
 pub fn admin_change_user_password(
     user_id: String,
-    _tenant_id_param: String,
+    tenant_id_param: String,
     input: ChangePasswordInput,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     use crate::db::schema::users::dsl::*;
+    state.verify_tenant_access(&tenant_id_param)?;
     
     let pool = state.db.lock().unwrap();
     let pool = pool.as_ref().ok_or("Database not initialized")?;
@@ -27,13 +29,16 @@ pub fn admin_change_user_password(
     // Hash new password
     let new_password_hash = format!("hashed_{}", input.new_password);
     
-    diesel::update(users.filter(id.eq(&user_id)))
-        .set((
-            password_hash.eq(&new_password_hash),
-            updated_at.eq(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()),
-        ))
-        .execute(&mut conn)
-        .map_err(|e| format!("Failed to change password: {}", e))?;
+    // Denetim görüntüsünde password_hash gizlenir; yalnızca değiştiği kaydedilir
+    denetim::kayitli_yazim(&mut conn, state.denetim("admin_change_user_password"), &tenant_id_param, "users", &user_id, |conn| {
+        diesel::update(users.filter(id.eq(&user_id)).filter(tenant_id.eq(&tenant_id_param)))
+            .set((
+                password_hash.eq(&new_password_hash),
+                updated_at.eq(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+            ))
+            .execute(conn)
+            .map_err(|e| format!("Failed to change password: {}", e))
+    })?;
     
     Ok(())
 }
@@ -3,6 +3,7 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize, QueryableByName)]
@@ -252,8 +253,14 @@ pub fn set_kur(
     .bind::<diesel::sql_types::Text, _>(&data.gecerlilik_baslangic)
     .get_result::<Kur>(&mut conn);
 
-    match existing {
-        Ok(kur) => {
+    let (kur_id, mevcut) = match existing {
+        Ok(kur) => (kur.id, true),
+        Err(diesel::result::Error::NotFound) => (Uuid::new_v4().to_string(), false),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    denetim::kayitli_yazim(&mut conn, state.denetim("set_kur"), &tenant_id_param, "kurlar", &kur_id, |conn| {
+        if mevcut {
             // Güncelle
             diesel::sql_query(
                 "UPDATE kurlar SET kur_degeri = ?1, aciklama = ?2, is_active = 1, updated_at = ?3 WHERE id = ?4"
@@ -261,21 +268,16 @@ pub fn set_kur(
             .bind::<diesel::sql_types::Double, _>(data.kur_degeri)
             .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
             .bind::<diesel::sql_types::Text, _>(&now)
-            .bind::<diesel::sql_types::Text, _>(&kur.id)
-            .execute(&mut conn)
+            .bind::<diesel::sql_types::Text, _>(&kur_id)
+            .execute(conn)
             .map_err(|e| e.to_string())?;
-            
-            Ok(kur.id)
-        }
-        Err(diesel::result::Error::NotFound) => {
+        } else {
             // Yeni kayıt
-            let new_id = Uuid::new_v4().to_string();
-            
             diesel::sql_query(
                 "INSERT INTO kurlar (id, tenant_id, para_birimi, hedef_para_birimi, kur_degeri, gecerlilik_baslangic, aciklama, is_active, created_at, updated_at) 
                  VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 1, ?8, ?9)"
             )
-            .bind::<diesel::sql_types::Text, _>(&new_id)
+            .bind::<diesel::sql_types::Text, _>(&kur_id)
             .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
             .bind::<diesel::sql_types::Text, _>(&data.para_birimi)
             .bind::<diesel::sql_types::Text, _>(&hedef)
@@ -284,13 +286,13 @@ pub fn set_kur(
             .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
             .bind::<diesel::sql_types::Text, _>(&now)
             .bind::<diesel::sql_types::Text, _>(&now)
-            .execute(&mut conn)
+            .execute(conn)
             .map_err(|e| e.to_string())?;
-            
-            Ok(new_id)
         }
-        Err(e) => Err(e.to_string()),
-    }
+        Ok(())
+    })?;
+
+    Ok(kur_id)
 }
 
 /// Kur sil (soft delete)
@@ -306,14 +308,16 @@ pub fn delete_kur(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE kurlar SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&kur_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_kur"), &tenant_id_param, "kurlar", &kur_id, |conn| {
+        diesel::sql_query(
+            "UPDATE kurlar SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&kur_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
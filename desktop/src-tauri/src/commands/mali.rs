@@ -6,12 +6,20 @@ use tauri::State;
 use uuid::Uuid;
 use crate::db::models::{Kasa, Gelir, Gider, GelirTuru, GiderTuru, Virman};
 use crate::db::outbox::{self, TxError};
+use crate::db::denetim::Denetim;
+use crate::db::yevmiye;
+use crate::db::donem_kilidi;
+use crate::db::alindi_belgesi;
+use crate::db::organ_gorevi;
+use crate::db::etkinlik_kaydi;
+use crate::db::gecikme;
+use crate::db::para::{KurusToplam, Para};
 
 type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
-/// Outbox'ın String hatasını, diesel::result::Error tipli transaction bloklarında
-/// taşıyabilmek için sarmalar (mesaj kaybolmaz, mevcut hata akışı değişmez).
-fn outbox_err(e: String) -> diesel::result::Error {
+/// Outbox/yevmiye gibi String hata dönen yardımcıların hatasını, diesel::result::Error
+/// tipli transaction bloklarında taşıyabilmek için sarmalar (mesaj kaybolmaz, mevcut hata akışı değişmez).
+pub(crate) fn outbox_err(e: String) -> diesel::result::Error {
     diesel::result::Error::QueryBuilderError(e.into())
 }
 
@@ -53,12 +61,12 @@ pub struct CreateGiderTuruRequest {
     pub varsayilan_fatura_prefix: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CreateGelirRequest {
     pub kasa_id: String,
     pub gelir_turu_id: Option<String>,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub makbuz_no: Option<String>,
     pub alt_kategori: Option<String>,
@@ -72,12 +80,12 @@ pub struct CreateGelirRequest {
     pub etkinlik_id: Option<String>,   // Etkinlik bağlantısı (gerçekleşen bütçe hesabı)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct UpdateGelirRequest {
     pub kasa_id: Option<String>,
     pub gelir_turu_id: Option<String>,
     pub tarih: Option<String>,
-    pub tutar: Option<f64>,
+    pub tutar: Option<Para>,
     pub aciklama: Option<String>,
     pub makbuz_no: Option<String>,
     pub alt_kategori: Option<String>,
@@ -90,12 +98,12 @@ pub struct UpdateGelirRequest {
     pub ait_oldugu_yil: Option<i32>,   // Ait olduğu yıl
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CreateGiderRequest {
     pub kasa_id: String,
     pub gider_turu_id: Option<String>,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub fatura_no: Option<String>,
     pub alt_kategori: Option<String>,
@@ -113,7 +121,7 @@ pub struct UpdateGiderRequest {
     pub kasa_id: Option<String>,
     pub gider_turu_id: Option<String>,
     pub tarih: Option<String>,
-    pub tutar: Option<f64>,
+    pub tutar: Option<Para>,
     pub aciklama: Option<String>,
     pub fatura_no: Option<String>,
     pub alt_kategori: Option<String>,
@@ -127,25 +135,19 @@ pub struct UpdateGiderRequest {
 pub struct VirmanRequest {
     pub kaynak_kasa_id: String,
     pub hedef_kasa_id: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub uygulanan_kur: Option<f64>,  // Manuel kur override
 }
 
 #[derive(Debug, Serialize)]
 pub struct KasaOzet {
-    pub toplam_bakiye: f64,
-    pub toplam_gelir: f64,
-    pub toplam_gider: f64,
+    pub toplam_bakiye: Para,
+    pub toplam_gelir: Para,
+    pub toplam_gider: Para,
     pub kasa_sayisi: i32,
 }
 
-#[derive(QueryableByName)]
-struct DoubleResult {
-    #[diesel(sql_type = diesel::sql_types::Double)]
-    pub total: f64,
-}
-
 #[derive(QueryableByName)]
 struct IntegerResult {
     #[diesel(sql_type = diesel::sql_types::Integer)]
@@ -192,8 +194,10 @@ pub async fn create_kasa(
     let new_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let mut denetim = state.denetim("create_kasa");
     // Yazım + outbox kaydı aynı transaction'da (doküman merkezi deseni)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "kasalar", &new_id).map_err(TxError::Msg)?;
         diesel::sql_query(
             "INSERT INTO kasalar (id, tenant_id, kasa_adi, bakiye, para_birimi, is_active, created_at, updated_at)
              VALUES (?1, ?2, ?3, 0.0, ?4, 1, ?5, ?6)"
@@ -208,7 +212,10 @@ pub async fn create_kasa(
 
         outbox::queue_change(conn, &tenant_id_param, "kasalar", &new_id, "create")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "kasalar", &new_id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -284,7 +291,7 @@ pub async fn get_gelirler(
 pub async fn create_gelir(
     state: State<'_, crate::AppState>,
     tenant_id_param: String,
-    data: CreateGelirRequest,
+    mut data: CreateGelirRequest,
 ) -> Result<Gelir, String> {
     // TENANT ISOLATION: Verify access
     state.verify_tenant_access(&tenant_id_param)?;
@@ -294,59 +301,17 @@ pub async fn create_gelir(
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
     let new_id = Uuid::new_v4().to_string();
-    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Get gelir_turu name if gelir_turu_id is provided
-    let gelir_turu_name: Option<String> = if let Some(ref turu_id) = data.gelir_turu_id {
-        use crate::db::schema::gelir_turleri::dsl::*;
-        gelir_turleri
-            .filter(id.eq(turu_id))
-            .select(ad)
-            .first::<String>(&mut conn)
-            .ok()
-    } else {
-        None
-    };
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
 
+    let mut denetim = state.denetim("create_gelir");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
-        diesel::sql_query(
-            "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, gelir_turu_id, tarih, tutar, aciklama, makbuz_no, alt_kategori, tahakkuk_durumu, belge_no, tahsil_eden, uye_id, aidat_id, ait_oldugu_yil, etkinlik_id, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&gelir_turu_name)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gelir_turu_id)
-        .bind::<diesel::sql_types::Text, _>(&data.tarih)
-        .bind::<diesel::sql_types::Double, _>(data.tutar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.makbuz_no)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tahakkuk_durumu)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.belge_no)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tahsil_eden)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.uye_id)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aidat_id)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.ait_oldugu_yil)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.etkinlik_id)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(conn)?;
-
-        diesel::sql_query(
-            "UPDATE kasalar SET bakiye = bakiye + ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
-        )
-        .bind::<diesel::sql_types::Double, _>(data.tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(conn)?;
-
-        // Outbox: kasa bakiye değişimi türetilmiş alan olduğundan yalnızca gelir kaydı kuyruğa girer
-        outbox::queue_change(conn, &tenant_id_param, "gelirler", &new_id, "create")
-            .map_err(outbox_err)?;
+        denetim.izle(conn, &tenant_id_param, "gelirler", &new_id).map_err(outbox_err)?;
+        makbuz_ve_tahsil_eden(conn, &tenant_id_param, kullanici_id.as_deref(), &new_id, &mut data)?;
+        gelir_ekle(conn, &tenant_id_param, &new_id, &data)?;
+        // Kasa toplamları gelirler SUM'ından (kuruş) yeniden hesaplanır — inline += yok.
+        update_kasa_bakiye(conn, &data.kasa_id)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -493,56 +458,15 @@ pub async fn create_gider(
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
     let new_id = Uuid::new_v4().to_string();
-    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-    // Get gider_turu name if gider_turu_id is provided
-    let gider_turu_name: Option<String> = if let Some(ref turu_id) = data.gider_turu_id {
-        use crate::db::schema::gider_turleri::dsl::*;
-        gider_turleri
-            .filter(id.eq(turu_id))
-            .select(ad)
-            .first::<String>(&mut conn)
-            .ok()
-    } else {
-        None
-    };
 
+    let mut denetim = state.denetim("create_gider");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
-        diesel::sql_query(
-            "INSERT INTO giderler (id, tenant_id, kasa_id, gider_turu, gider_turu_id, tarih, tutar, aciklama, fatura_no, alt_kategori, islem_no, odeyen, notlar, etkinlik_id, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&gider_turu_name)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gider_turu_id)
-        .bind::<diesel::sql_types::Text, _>(&data.tarih)
-        .bind::<diesel::sql_types::Double, _>(data.tutar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.islem_no)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.odeyen)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.etkinlik_id)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(conn)?;
-
-        diesel::sql_query(
-            "UPDATE kasalar SET bakiye = bakiye - ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
-        )
-        .bind::<diesel::sql_types::Double, _>(data.tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(conn)?;
-
-        // Outbox: kasa bakiye değişimi türetilmiş alan olduğundan yalnızca gider kaydı kuyruğa girer
-        outbox::queue_change(conn, &tenant_id_param, "giderler", &new_id, "create")
-            .map_err(outbox_err)?;
+        denetim.izle(conn, &tenant_id_param, "giderler", &new_id).map_err(outbox_err)?;
+        gider_ekle(conn, &tenant_id_param, &new_id, &data)?;
+        // Kasa toplamları giderler SUM'ından (kuruş) yeniden hesaplanır — inline -= yok.
+        update_kasa_bakiye(conn, &data.kasa_id)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -568,13 +492,25 @@ pub async fn virman_yap(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("virman_yap");
+    virman_ekle(&mut conn, &mut denetim, &tenant_id_param, &virman)
+}
+
+/// Virman kaydı: kasa, bakiye ve kur kontrolleri; INSERT, iki kasanın bakiyesi, outbox ve
+/// yevmiye tek transaction'da. Virman kimliğini döner.
+pub(crate) fn virman_ekle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id_param: &str,
+    virman: &VirmanRequest,
+) -> Result<String, String> {
     // Aynı kasaya virman kontrolü
     if virman.kaynak_kasa_id == virman.hedef_kasa_id {
         return Err("Kaynak ve hedef kasa aynı olamaz!".to_string());
     }
 
     // Tutar kontrolü
-    if virman.tutar <= 0.0 {
+    if !virman.tutar.is_positive() {
         return Err("Virman tutarı 0'dan büyük olmalıdır!".to_string());
     }
 
@@ -584,7 +520,7 @@ pub async fn virman_yap(
         #[diesel(sql_type = diesel::sql_types::Text)]
         para_birimi: String,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        fiziksel_bakiye: f64,
+        fiziksel_bakiye: Para,
     }
     
     let kaynak_kasa = diesel::sql_query(
@@ -592,8 +528,8 @@ pub async fn virman_yap(
          FROM kasalar WHERE id = ?1 AND tenant_id = ?2"
     )
     .bind::<diesel::sql_types::Text, _>(&virman.kaynak_kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<KasaBilgi>(&mut conn)
+    .bind::<diesel::sql_types::Text, _>(tenant_id_param)
+    .get_result::<KasaBilgi>(conn)
     .map_err(|_| "Kaynak kasa bulunamadı!")?;
     
     let hedef_kasa = diesel::sql_query(
@@ -601,14 +537,14 @@ pub async fn virman_yap(
          FROM kasalar WHERE id = ?1 AND tenant_id = ?2"
     )
     .bind::<diesel::sql_types::Text, _>(&virman.hedef_kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<KasaBilgi>(&mut conn)
+    .bind::<diesel::sql_types::Text, _>(tenant_id_param)
+    .get_result::<KasaBilgi>(conn)
     .map_err(|_| "Hedef kasa bulunamadı!")?;
     
     // KRİTİK: Kaynak kasada yeterli bakiye kontrolü
     if kaynak_kasa.fiziksel_bakiye < virman.tutar {
         return Err(format!(
-            "Kaynak kasada yeterli bakiye yok! Mevcut: {:.2}, İstenen: {:.2}",
+            "Kaynak kasada yeterli bakiye yok! Mevcut: {}, İstenen: {}",
             kaynak_kasa.fiziksel_bakiye, virman.tutar
         ));
     }
@@ -625,16 +561,19 @@ pub async fn virman_yap(
         (1.0, virman.tutar)
     } else if let Some(manuel_kur) = virman.uygulanan_kur {
         // Manuel kur verilmiş
-        (manuel_kur, virman.tutar * manuel_kur)
+        (manuel_kur, virman.tutar.carp(manuel_kur))
     } else {
         // Sistemden kur al
-        let kur = get_kur_degeri(&mut conn, &tenant_id_param, &kaynak_para_birimi, &hedef_para_birimi, &today)?;
-        (kur, virman.tutar * kur)
+        let kur = get_kur_degeri(conn, tenant_id_param, &kaynak_para_birimi, &hedef_para_birimi, &today)?;
+        (kur, virman.tutar.carp(kur))
     };
     
     // Transaction başlat
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        donem_kilidi::tarihi_dogrula(conn, tenant_id_param, &today).map_err(outbox_err)?;
+
         let virman_id = Uuid::new_v4().to_string();
+        denetim.izle(conn, tenant_id_param, "virmanlar", &virman_id).map_err(outbox_err)?;
         
         // 1. Virmanlar tablosuna kaydet (kur bilgileri ile)
         diesel::sql_query(
@@ -642,7 +581,7 @@ pub async fn virman_yap(
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)"
         )
         .bind::<diesel::sql_types::Text, _>(&virman_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Text, _>(tenant_id_param)
         .bind::<diesel::sql_types::Text, _>(&virman.kaynak_kasa_id)
         .bind::<diesel::sql_types::Text, _>(&virman.hedef_kasa_id)
         .bind::<diesel::sql_types::Text, _>(&today)
@@ -657,41 +596,25 @@ pub async fn virman_yap(
         .bind::<diesel::sql_types::Text, _>(&now)
         .execute(conn)?;
 
-        // 2. Kaynak kasadan çıkar (kaynak tutarı ile)
-        diesel::sql_query(
-            "UPDATE kasalar SET virman_cikis = virman_cikis + ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
-        )
-        .bind::<diesel::sql_types::Double, _>(virman.tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&virman.kaynak_kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(conn)?;
-
-        // 3. Hedef kasaya ekle (hedef tutarı ile - kur uygulanmış)
-        diesel::sql_query(
-            "UPDATE kasalar SET virman_giris = virman_giris + ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4"
-        )
-        .bind::<diesel::sql_types::Double, _>(hedef_tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&virman.hedef_kasa_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(conn)?;
-
-        // 4. Her iki kasanın bakiyesini yeniden hesapla
+        // 2. Her iki kasanın virman_giris/cikis ve bakiyesini virmanlar SUM'ından yeniden hesapla
+        //    (kaynak: kaynak tutarı, hedef: kur uygulanmış hedef tutarı)
         update_kasa_bakiye(conn, &virman.kaynak_kasa_id)?;
         update_kasa_bakiye(conn, &virman.hedef_kasa_id)?;
 
         // Outbox: kasa değişimleri türetilmiş alan (virman_giris/cikis, bakiye) olduğundan
         // yalnızca virman kaydı kuyruğa girer
-        outbox::queue_change(conn, &tenant_id_param, "virmanlar", &virman_id, "create")
+        outbox::queue_change(conn, tenant_id_param, "virmanlar", &virman_id, "create")
+            .map_err(outbox_err)?;
+        yevmiye::kaydi_isle(conn, tenant_id_param, "virmanlar", &virman_id)
             .map_err(outbox_err)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(virman_id)
     }).map_err(|e| e.to_string())
 }
 
 // Helper: Kur değerini hesapla
-fn get_kur_degeri(
+pub(crate) fn get_kur_degeri(
     conn: &mut SqliteConnection,
     tenant_id: &str,
     kaynak_para_birimi: &str,
@@ -794,44 +717,269 @@ fn get_kur_degeri(
     ))
 }
 
+/// Bir kasanın son devirden bu yana hareket toplamları (kuruş hassasiyetinde).
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct KasaHareketToplami {
+    pub toplam_gelir: Para,
+    pub toplam_gider: Para,
+    pub virman_giris: Para,
+    pub virman_cikis: Para,
+}
+
+impl KasaHareketToplami {
+    pub fn net(&self) -> Para {
+        self.toplam_gelir - self.toplam_gider + self.virman_giris - self.virman_cikis
+    }
+}
+
+// Helper: Kasa hareketlerini baz kayıtlardan topla.
+// Tutarlar SQL'de kuruşa çevrilip INTEGER olarak toplanır (REAL SUM kayması yok).
+// Son devirden (kasalar.devir_tarihi) önceki hareketler devir_bakiye içinde olduğundan dahil edilmez;
+// `bitis` verilirse o tarihten (hariç) önceki hareketler alınır (devir önizlemesi için).
+pub(crate) fn hesapla_kasa_hareketleri(
+    conn: &mut SqliteConnection,
+    kasa_id: &str,
+    bitis: Option<&str>,
+) -> Result<KasaHareketToplami, diesel::result::Error> {
+    #[derive(QueryableByName)]
+    struct HareketRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        gelir: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        gider: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        virman_in: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        virman_out: i64,
+    }
+
+    let row = diesel::sql_query(
+        "SELECT
+            (SELECT COALESCE(SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)), 0) FROM gelirler g
+              WHERE g.kasa_id = k.id AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+                AND (k.devir_tarihi IS NULL OR g.tarih >= k.devir_tarihi)
+                AND (?2 IS NULL OR g.tarih < ?2)) as gelir,
+            (SELECT COALESCE(SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)), 0) FROM giderler g
+              WHERE g.kasa_id = k.id AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+                AND (k.devir_tarihi IS NULL OR g.tarih >= k.devir_tarihi)
+                AND (?2 IS NULL OR g.tarih < ?2)) as gider,
+            (SELECT COALESCE(SUM(CAST(ROUND(COALESCE(v.hedef_tutar, v.tutar) * 100) AS INTEGER)), 0) FROM virmanlar v
+              WHERE v.hedef_kasa_id = k.id AND (v.is_deleted IS NULL OR v.is_deleted = 0)
+                AND (k.devir_tarihi IS NULL OR v.tarih >= k.devir_tarihi)
+                AND (?2 IS NULL OR v.tarih < ?2)) as virman_in,
+            (SELECT COALESCE(SUM(CAST(ROUND(v.tutar * 100) AS INTEGER)), 0) FROM virmanlar v
+              WHERE v.kaynak_kasa_id = k.id AND (v.is_deleted IS NULL OR v.is_deleted = 0)
+                AND (k.devir_tarihi IS NULL OR v.tarih >= k.devir_tarihi)
+                AND (?2 IS NULL OR v.tarih < ?2)) as virman_out
+         FROM kasalar k WHERE k.id = ?1"
+    )
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bitis)
+    .get_result::<HareketRow>(conn)?;
+
+    Ok(KasaHareketToplami {
+        toplam_gelir: Para::from_kurus(row.gelir),
+        toplam_gider: Para::from_kurus(row.gider),
+        virman_giris: Para::from_kurus(row.virman_in),
+        virman_cikis: Para::from_kurus(row.virman_out),
+    })
+}
+
+/// Nakit tahsilatın makbuz no ve tahsil edenini doldurur; gelir_ekle'den önce, aynı
+/// transaction'da çağrılır ki outbox snapshot'ı numarayı içersin.
+pub(crate) fn makbuz_ve_tahsil_eden(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kullanici_id: Option<&str>,
+    gelir_id: &str,
+    data: &mut CreateGelirRequest,
+) -> Result<(), diesel::result::Error> {
+    // Makbuz no girilmemişse kullanıcının alındı belgesi cildinden sıradaki numara
+    if data.makbuz_no.as_deref().is_none_or(|m| m.trim().is_empty()) {
+        if let Some(makbuz) = alindi_belgesi::numara_ayir(conn, tenant_id, kullanici_id, &data.tarih, gelir_id)
+            .map_err(outbox_err)?
+        {
+            data.makbuz_no = Some(makbuz.makbuz_no);
+            data.tahsil_eden = data.tahsil_eden.take().or(makbuz.tahsil_eden);
+        }
+    }
+    // Tahsil eden yine boşsa o tarihteki tahsilat yetkilisi (yoksa sayman)
+    if data.tahsil_eden.as_deref().is_none_or(|t| t.trim().is_empty()) {
+        data.tahsil_eden = organ_gorevi::tahsil_yetkilisi(conn, tenant_id, &data.tarih).map_err(outbox_err)?;
+    }
+    Ok(())
+}
+
+/// Gelir kaydını ekler: dönem kilidi kontrolü, INSERT, outbox ve yevmiye.
+/// Kasa bakiyesi çağıran tarafından güncellenir (toplu eklemede kasa başına bir kez).
+pub(crate) fn gelir_ekle(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    id: &str,
+    data: &CreateGelirRequest,
+) -> Result<(), diesel::result::Error> {
+    donem_kilidi::tarihi_dogrula(conn, tenant_id, &data.tarih).map_err(outbox_err)?;
+
+    let tur_adi: Option<String> = match &data.gelir_turu_id {
+        Some(turu_id) => {
+            use crate::db::schema::gelir_turleri;
+            gelir_turleri::table
+                .filter(gelir_turleri::id.eq(turu_id))
+                .select(gelir_turleri::ad)
+                .first::<String>(conn)
+                .optional()?
+        }
+        None => None,
+    };
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    diesel::sql_query(
+        "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, gelir_turu_id, tarih, tutar, aciklama, makbuz_no, alt_kategori, tahakkuk_durumu, belge_no, tahsil_eden, uye_id, aidat_id, ait_oldugu_yil, etkinlik_id, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)"
+    )
+    .bind::<diesel::sql_types::Text, _>(id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&tur_adi)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gelir_turu_id)
+    .bind::<diesel::sql_types::Text, _>(&data.tarih)
+    .bind::<diesel::sql_types::Double, _>(data.tutar)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.makbuz_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tahakkuk_durumu)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.belge_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.tahsil_eden)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.uye_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aidat_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.ait_oldugu_yil)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.etkinlik_id)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)?;
+
+    // Outbox: kasa bakiye değişimi türetilmiş alan olduğundan yalnızca gelir kaydı kuyruğa girer
+    outbox::queue_change(conn, tenant_id, "gelirler", id, "create")
+        .map_err(outbox_err)?;
+    yevmiye::kaydi_isle(conn, tenant_id, "gelirler", id)
+        .map_err(outbox_err)?;
+    Ok(())
+}
+
+/// Gider kaydını ekler: dönem kilidi kontrolü, INSERT, outbox ve yevmiye.
+/// Kasa bakiyesi çağıran tarafından güncellenir (toplu eklemede kasa başına bir kez).
+pub(crate) fn gider_ekle(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    id: &str,
+    data: &CreateGiderRequest,
+) -> Result<(), diesel::result::Error> {
+    donem_kilidi::tarihi_dogrula(conn, tenant_id, &data.tarih).map_err(outbox_err)?;
+
+    let tur_adi: Option<String> = match &data.gider_turu_id {
+        Some(turu_id) => {
+            use crate::db::schema::gider_turleri;
+            gider_turleri::table
+                .filter(gider_turleri::id.eq(turu_id))
+                .select(gider_turleri::ad)
+                .first::<String>(conn)
+                .optional()?
+        }
+        None => None,
+    };
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    diesel::sql_query(
+        "INSERT INTO giderler (id, tenant_id, kasa_id, gider_turu, gider_turu_id, tarih, tutar, aciklama, fatura_no, alt_kategori, islem_no, odeyen, notlar, etkinlik_id, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+    )
+    .bind::<diesel::sql_types::Text, _>(id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&data.kasa_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&tur_adi)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gider_turu_id)
+    .bind::<diesel::sql_types::Text, _>(&data.tarih)
+    .bind::<diesel::sql_types::Double, _>(data.tutar)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.fatura_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.alt_kategori)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.islem_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.odeyen)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.etkinlik_id)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)?;
+
+    // Outbox: kasa bakiye değişimi türetilmiş alan olduğundan yalnızca gider kaydı kuyruğa girer
+    outbox::queue_change(conn, tenant_id, "giderler", id, "create")
+        .map_err(outbox_err)?;
+    yevmiye::kaydi_isle(conn, tenant_id, "giderler", id)
+        .map_err(outbox_err)?;
+    Ok(())
+}
+
+// Helper: Kasanın devri kesim tarihi olmadan mı yazılmış (devir_bakiye ≠ 0, devir_tarihi NULL).
+// Böyle bir kasada bakiye hesaplanamaz; sync uygulaması kasayı atlar ve
+// check_kasa_bakiye_tutarliligi onu onarım bekleyen olarak raporlar.
+pub(crate) fn devir_tarihi_eksik(
+    conn: &mut SqliteConnection,
+    kasa_id: &str,
+) -> Result<bool, diesel::result::Error> {
+    #[derive(QueryableByName)]
+    struct EksikRow {
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        eksik: bool,
+    }
+
+    Ok(diesel::sql_query(
+        "SELECT (devir_tarihi IS NULL AND ROUND(COALESCE(devir_bakiye, 0), 2) != 0) AS eksik
+         FROM kasalar WHERE id = ?1"
+    )
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .get_result::<EksikRow>(conn)
+    .optional()?
+    .is_some_and(|r| r.eksik))
+}
+
 // Helper: Kasa bakiyesini yeniden hesapla (diğer modüller de çağırabilir).
+// Tek kaynak: gelirler/giderler/virmanlar SUM'ı + devir_bakiye. Tüm aritmetik kuruş (i64).
 pub(crate) fn update_kasa_bakiye(
     conn: &mut SqliteConnection,
     kasa_id: &str,
 ) -> Result<(), diesel::result::Error> {
     #[derive(QueryableByName)]
-    struct KasaBalanceData {
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        devir: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        gelir: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        gider: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        virman_in: f64,
+    struct KasaBazData {
         #[diesel(sql_type = diesel::sql_types::Double)]
-        virman_out: f64,
+        devir: Para,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        tahakkuk: f64,
+        tahakkuk: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        devir_tarihi: Option<String>,
     }
 
-    // Kasa bilgilerini al
     let kasa_data = diesel::sql_query(
         "SELECT 
             COALESCE(devir_bakiye, 0.0) as devir,
-            COALESCE(toplam_gelir, 0.0) as gelir,
-            COALESCE(toplam_gider, 0.0) as gider,
-            COALESCE(virman_giris, 0.0) as virman_in,
-            COALESCE(virman_cikis, 0.0) as virman_out,
-            COALESCE(tahakkuk_tutari, 0.0) as tahakkuk
+            COALESCE(tahakkuk_tutari, 0.0) as tahakkuk,
+            devir_tarihi
          FROM kasalar WHERE id = ?1"
     )
     .bind::<diesel::sql_types::Text, _>(kasa_id)
-    .get_result::<KasaBalanceData>(conn)?;
+    .get_result::<KasaBazData>(conn)?;
+
+    // Kesim tarihi olmayan devir tüm hareketleri ikinci kez sayar; bakiye yazılmaz
+    if kasa_data.devir_tarihi.is_none() && !kasa_data.devir.is_zero() {
+        return Err(outbox_err(format!(
+            "Kasanın devir tarihi eksik, bakiye yeniden hesaplanamaz (kasa: {})",
+            kasa_id
+        )));
+    }
+
+    let hareket = hesapla_kasa_hareketleri(conn, kasa_id, None)?;
 
     // Fiziksel bakiye = devir + gelir - gider + virman_giris - virman_cikis
-    let fiziksel_bakiye = kasa_data.devir + kasa_data.gelir - kasa_data.gider 
-                          + kasa_data.virman_in - kasa_data.virman_out;
+    let fiziksel_bakiye = kasa_data.devir + hareket.net();
     
     // Serbest bakiye = fiziksel - tahakkuk
     let serbest_bakiye = fiziksel_bakiye - kasa_data.tahakkuk;
@@ -840,12 +988,20 @@ pub(crate) fn update_kasa_bakiye(
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
     diesel::sql_query(
         "UPDATE kasalar 
-         SET fiziksel_bakiye = ?1, 
-             serbest_bakiye = ?2, 
-             bakiye = ?3,
-             updated_at = ?4
-         WHERE id = ?5"
+         SET toplam_gelir = ?1,
+             toplam_gider = ?2,
+             virman_giris = ?3,
+             virman_cikis = ?4,
+             fiziksel_bakiye = ?5, 
+             serbest_bakiye = ?6, 
+             bakiye = ?7,
+             updated_at = ?8
+         WHERE id = ?9"
     )
+    .bind::<diesel::sql_types::Double, _>(hareket.toplam_gelir)
+    .bind::<diesel::sql_types::Double, _>(hareket.toplam_gider)
+    .bind::<diesel::sql_types::Double, _>(hareket.virman_giris)
+    .bind::<diesel::sql_types::Double, _>(hareket.virman_cikis)
     .bind::<diesel::sql_types::Double, _>(fiziksel_bakiye)
     .bind::<diesel::sql_types::Double, _>(serbest_bakiye)
     .bind::<diesel::sql_types::Double, _>(fiziksel_bakiye) // bakiye = fiziksel_bakiye
@@ -856,6 +1012,43 @@ pub(crate) fn update_kasa_bakiye(
     Ok(())
 }
 
+// Helper: Kasanın belirli bir tarihin başındaki bakiyesi (tarih hariç, kuruş).
+// devir_bakiye, devir_tarihi öncesi hareketlerin netini içerir; bu yüzden
+// bakiye(tarih) = devir_bakiye − net(< devir_tarihi) + net(< tarih). Tarih devirden önce de olabilir.
+pub(crate) fn kasa_bakiyesi_tarihte(
+    conn: &mut SqliteConnection,
+    kasa_id: &str,
+    tarih: &str,
+) -> Result<Para, diesel::result::Error> {
+    let row = diesel::sql_query(
+        "SELECT CAST(ROUND(COALESCE(k.devir_bakiye, 0) * 100) AS INTEGER)
+              + COALESCE(SUM(CASE WHEN h.tarih < ?2 THEN h.kurus ELSE 0 END), 0)
+              - COALESCE(SUM(CASE WHEN k.devir_tarihi IS NOT NULL AND h.tarih < k.devir_tarihi THEN h.kurus ELSE 0 END), 0)
+              as kurus
+         FROM kasalar k
+         LEFT JOIN (
+             SELECT kasa_id, tarih, CAST(ROUND(tutar * 100) AS INTEGER) as kurus FROM gelirler
+              WHERE is_deleted IS NULL OR is_deleted = 0
+             UNION ALL
+             SELECT kasa_id, tarih, -CAST(ROUND(tutar * 100) AS INTEGER) FROM giderler
+              WHERE is_deleted IS NULL OR is_deleted = 0
+             UNION ALL
+             SELECT hedef_kasa_id, tarih, CAST(ROUND(COALESCE(hedef_tutar, tutar) * 100) AS INTEGER) FROM virmanlar
+              WHERE is_deleted IS NULL OR is_deleted = 0
+             UNION ALL
+             SELECT kaynak_kasa_id, tarih, -CAST(ROUND(tutar * 100) AS INTEGER) FROM virmanlar
+              WHERE is_deleted IS NULL OR is_deleted = 0
+         ) h ON h.kasa_id = k.id
+         WHERE k.id = ?1
+         GROUP BY k.id"
+    )
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .bind::<diesel::sql_types::Text, _>(tarih)
+    .get_result::<KurusToplam>(conn)?;
+
+    Ok(row.para())
+}
+
 #[tauri::command]
 pub async fn get_virmanlar(
     state: State<'_, crate::AppState>,
@@ -900,14 +1093,19 @@ pub struct DevirOnizleme {
     pub kasa_id: String,
     pub kasa_adi: String,
     pub para_birimi: String,
-    pub onceki_devir: f64,
-    pub toplam_gelir: f64,
-    pub toplam_gider: f64,
-    pub virman_net: f64,
-    pub fiziksel_bakiye: f64,
-    pub tahakkuk_tutari: f64,
-    pub serbest_bakiye: f64,
-    pub yeni_devir: f64,
+    pub onceki_devir: Para,
+    pub toplam_gelir: Para,
+    pub toplam_gider: Para,
+    pub virman_net: Para,
+    pub fiziksel_bakiye: Para,
+    pub tahakkuk_tutari: Para,
+    pub serbest_bakiye: Para,
+    pub yeni_devir: Para,
+}
+
+/// Devir kesim tarihi: `yil` kapanınca yeni dönem (yil + 1)-01-01'de başlar.
+fn devir_kesim_tarihi(yil: i32) -> String {
+    format!("{}-01-01", yil + 1)
 }
 
 #[tauri::command]
@@ -929,21 +1127,9 @@ pub async fn get_devir_onizleme(
         #[diesel(sql_type = diesel::sql_types::Text)]
         para_birimi: String,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        devir_bakiye: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        toplam_gelir: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        toplam_gider: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        virman_giris: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        virman_cikis: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        fiziksel_bakiye: f64,
+        devir_bakiye: Para,
         #[diesel(sql_type = diesel::sql_types::Double)]
-        tahakkuk_tutari: f64,
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        serbest_bakiye: f64,
+        tahakkuk_tutari: Para,
     }
 
     let kasalar = diesel::sql_query(
@@ -952,13 +1138,7 @@ pub async fn get_devir_onizleme(
             kasa_adi,
             para_birimi,
             COALESCE(devir_bakiye, 0.0) as devir_bakiye,
-            COALESCE(toplam_gelir, 0.0) as toplam_gelir,
-            COALESCE(toplam_gider, 0.0) as toplam_gider,
-            COALESCE(virman_giris, 0.0) as virman_giris,
-            COALESCE(virman_cikis, 0.0) as virman_cikis,
-            COALESCE(fiziksel_bakiye, 0.0) as fiziksel_bakiye,
-            COALESCE(tahakkuk_tutari, 0.0) as tahakkuk_tutari,
-            COALESCE(serbest_bakiye, 0.0) as serbest_bakiye
+            COALESCE(tahakkuk_tutari, 0.0) as tahakkuk_tutari
          FROM kasalar 
          WHERE tenant_id = ?1 AND is_active = 1
          ORDER BY kasa_adi"
@@ -967,22 +1147,27 @@ pub async fn get_devir_onizleme(
     .load::<KasaDevirData>(&mut conn)
     .map_err(|e| e.to_string())?;
 
+    // Yalnızca kapanan yıla ait hareketler devredilir; kesim sonrası tarihli kayıtlar yeni dönemde kalır.
+    let kesim = devir_kesim_tarihi(yil);
+
     let mut onizleme_list = Vec::new();
     for kasa in kasalar {
-        let virman_net = kasa.virman_giris - kasa.virman_cikis;
-        let yeni_devir = kasa.fiziksel_bakiye; // Fiziksel bakiye yeni yıla devredilecek
+        let hareket = hesapla_kasa_hareketleri(&mut conn, &kasa.kasa_id, Some(&kesim))
+            .map_err(|e| e.to_string())?;
+        let fiziksel_bakiye = kasa.devir_bakiye + hareket.net();
+        let yeni_devir = fiziksel_bakiye; // Fiziksel bakiye yeni yıla devredilecek
 
         onizleme_list.push(DevirOnizleme {
             kasa_id: kasa.kasa_id,
             kasa_adi: kasa.kasa_adi,
             para_birimi: kasa.para_birimi,
             onceki_devir: kasa.devir_bakiye,
-            toplam_gelir: kasa.toplam_gelir,
-            toplam_gider: kasa.toplam_gider,
-            virman_net,
-            fiziksel_bakiye: kasa.fiziksel_bakiye,
+            toplam_gelir: hareket.toplam_gelir,
+            toplam_gider: hareket.toplam_gider,
+            virman_net: hareket.virman_giris - hareket.virman_cikis,
+            fiziksel_bakiye,
             tahakkuk_tutari: kasa.tahakkuk_tutari,
-            serbest_bakiye: kasa.serbest_bakiye,
+            serbest_bakiye: fiziksel_bakiye - kasa.tahakkuk_tutari,
             yeni_devir,
         });
     }
@@ -1007,8 +1192,16 @@ pub async fn uygula_yil_sonu_devir(
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let kesim = devir_kesim_tarihi(data.yil);
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let mut denetim = state.denetim("uygula_yil_sonu_devir");
 
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        // Kapatılmış yıl yeniden devredilemez (kilit açılmadan)
+        if let Some(kilit) = donem_kilidi::yil_kilidi(conn, &tenant_id_param, data.yil).map_err(outbox_err)? {
+            return Err(outbox_err(format!("{} yılı zaten kapatılmış (kilit: {})", data.yil, kilit.kilit_tarihi)));
+        }
+
         // Tüm kasaları al
         use crate::db::schema::kasalar::dsl::*;
         let kasa_list = kasalar
@@ -1017,37 +1210,49 @@ pub async fn uygula_yil_sonu_devir(
             .load::<Kasa>(conn)?;
 
         for kasa in kasa_list {
-            // Yeni devir = mevcut fiziksel bakiye
-            let yeni_devir = kasa.fiziksel_bakiye.unwrap_or(0.0);
+            denetim.izle(conn, &tenant_id_param, "kasalar", &kasa.id).map_err(outbox_err)?;
+            // Yeni devir = önceki devir + kesim tarihine kadarki hareketler (kuruş)
+            let hareket = hesapla_kasa_hareketleri(conn, &kasa.id, Some(&kesim))?;
+            let yeni_devir = kasa.devir_bakiye.unwrap_or_default() + hareket.net();
 
-            // Kasayı sıfırla ve yeni devir bakiyesini ayarla
+            // Devir bakiyesini ve kesim tarihini yaz; türetilmiş alanlar
+            // kesim sonrası hareketlerden yeniden hesaplanır.
             diesel::sql_query(
                 "UPDATE kasalar 
                  SET devir_bakiye = ?1,
-                     toplam_gelir = 0.0,
-                     toplam_gider = 0.0,
-                     virman_giris = 0.0,
-                     virman_cikis = 0.0,
-                     fiziksel_bakiye = ?2,
-                     serbest_bakiye = ?3,
-                     bakiye = ?4,
-                     updated_at = ?5
-                 WHERE id = ?6"
+                     devir_tarihi = ?2,
+                     updated_at = ?3
+                 WHERE id = ?4"
             )
             .bind::<diesel::sql_types::Double, _>(yeni_devir)
-            .bind::<diesel::sql_types::Double, _>(yeni_devir)
-            .bind::<diesel::sql_types::Double, _>(yeni_devir - kasa.tahakkuk_tutari.unwrap_or(0.0))
-            .bind::<diesel::sql_types::Double, _>(yeni_devir)
+            .bind::<diesel::sql_types::Text, _>(&kesim)
             .bind::<diesel::sql_types::Text, _>(&now)
             .bind::<diesel::sql_types::Text, _>(&kasa.id)
             .execute(conn)?;
 
-            // Outbox: devir_bakiye baz alandır (türetilmiş değil) — her etkilenen kasa kuyruğa girer.
-            // Sıfırlanan toplam_gelir/gider vb. türetilmiş alanlar payload'dan zaten ayıklanır.
+            update_kasa_bakiye(conn, &kasa.id)?;
+
+            // Outbox: devir_bakiye/devir_tarihi baz alandır (türetilmiş değil) — her etkilenen kasa kuyruğa girer.
+            // Yeniden hesaplanan toplam_gelir/gider vb. türetilmiş alanlar payload'dan zaten ayıklanır.
             outbox::queue_change(conn, &tenant_id_param, "kasalar", &kasa.id, "update")
                 .map_err(outbox_err)?;
+            yevmiye::kaydi_isle(conn, &tenant_id_param, "kasalar", &kasa.id)
+                .map_err(outbox_err)?;
         }
 
+        // Devredilen yıl kilitlenir: sonradan yapılacak değişiklik devir bakiyesini bozar
+        let kilit_id = donem_kilidi::kilitle(
+            conn,
+            &tenant_id_param,
+            data.yil,
+            None,
+            Some(data.aciklama.as_deref().unwrap_or("Yıl sonu devri")),
+            kullanici_id.as_deref(),
+        )
+        .map_err(outbox_err)?;
+        denetim.yeni_kayit(&tenant_id_param, "donem_kilitleri", &kilit_id);
+
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(format!("{} yılı devir işlemi tamamlandı", data.yil))
     }).map_err(|e| e.to_string())
 }
@@ -1061,29 +1266,29 @@ pub async fn get_kasa_ozet(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    let toplam_bakiye: f64 = diesel::sql_query(
-        "SELECT COALESCE(SUM(bakiye), 0.0) as total FROM kasalar WHERE tenant_id = ?1 AND is_active = 1"
+    let toplam_bakiye = diesel::sql_query(
+        "SELECT COALESCE(SUM(CAST(ROUND(bakiye * 100) AS INTEGER)), 0) as kurus FROM kasalar WHERE tenant_id = ?1 AND is_active = 1"
     )
     .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<DoubleResult>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+    .get_result::<KurusToplam>(&mut conn)
+    .map(|r| r.para())
+    .unwrap_or_default();
 
-    let toplam_gelir: f64 = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM gelirler WHERE tenant_id = ?1"
+    let toplam_gelir = diesel::sql_query(
+        "SELECT COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) as kurus FROM gelirler WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)"
     )
     .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<DoubleResult>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+    .get_result::<KurusToplam>(&mut conn)
+    .map(|r| r.para())
+    .unwrap_or_default();
 
-    let toplam_gider: f64 = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM giderler WHERE tenant_id = ?1"
+    let toplam_gider = diesel::sql_query(
+        "SELECT COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) as kurus FROM giderler WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)"
     )
     .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<DoubleResult>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+    .get_result::<KurusToplam>(&mut conn)
+    .map(|r| r.para())
+    .unwrap_or_default();
 
     let kasa_sayisi: i32 = diesel::sql_query(
         "SELECT COUNT(*) as total FROM kasalar WHERE tenant_id = ?1 AND is_active = 1"
@@ -1127,8 +1332,10 @@ pub async fn update_kasa(
         .first::<Kasa>(&mut conn)
         .map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("update_kasa");
     // Yazım + outbox kaydı aynı transaction'da (kasa_adi/para_birimi baz alanlar)
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "kasalar", &id).map_err(TxError::Msg)?;
         diesel::update(
             kasa_dsl::kasalar
                 .filter(kasa_dsl::id.eq(&id))
@@ -1143,7 +1350,10 @@ pub async fn update_kasa(
 
         outbox::queue_change(conn, &tenant_id_param, "kasalar", &id, "update")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "kasalar", &id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -1172,27 +1382,46 @@ pub async fn update_gelir(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("update_gelir");
+    gelir_guncelle(&mut conn, &mut denetim, &tenant_id_param, &id, request)
+}
+
+/// Gelir güncellemesi: dönem kilidi (eski ve yeni tarih), UPDATE, eski/yeni kasa bakiyeleri,
+/// outbox ve yevmiye tek transaction'da.
+pub(crate) fn gelir_guncelle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id_param: &str,
+    id: &str,
+    request: UpdateGelirRequest,
+) -> Result<Gelir, String> {
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
     // Mevcut kaydı al
     use crate::db::schema::gelirler::dsl as gelir_dsl;
     let current = gelir_dsl::gelirler
-        .filter(gelir_dsl::id.eq(&id))
-        .filter(gelir_dsl::tenant_id.eq(&tenant_id_param))
-        .first::<Gelir>(&mut conn)
+        .filter(gelir_dsl::id.eq(id))
+        .filter(gelir_dsl::tenant_id.eq(tenant_id_param))
+        .first::<Gelir>(conn)
         .map_err(|e| e.to_string())?;
 
     let eski_kasa_id = current.kasa_id.clone();
-    let eski_tutar = current.tutar;
     let yeni_kasa_id = request.kasa_id.clone().unwrap_or(eski_kasa_id.clone());
-    let yeni_tutar = request.tutar.unwrap_or(eski_tutar);
+    let yeni_tutar = request.tutar.unwrap_or(current.tutar);
 
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, tenant_id_param, "gelirler", id).map_err(outbox_err)?;
+        // Dönem kilidi: hem mevcut hem yeni tarih açık dönemde olmalı
+        donem_kilidi::tarihi_dogrula(conn, tenant_id_param, &current.tarih).map_err(outbox_err)?;
+        if let Some(ref yeni_tarih) = request.tarih {
+            donem_kilidi::tarihi_dogrula(conn, tenant_id_param, yeni_tarih).map_err(outbox_err)?;
+        }
+
         // Gelir kaydını güncelle (tenant filtreli)
         diesel::update(
             gelir_dsl::gelirler
-                .filter(gelir_dsl::id.eq(&id))
-                .filter(gelir_dsl::tenant_id.eq(&tenant_id_param)),
+                .filter(gelir_dsl::id.eq(id))
+                .filter(gelir_dsl::tenant_id.eq(tenant_id_param)),
         )
         .set((
             gelir_dsl::kasa_id.eq(&yeni_kasa_id),
@@ -1218,18 +1447,19 @@ pub async fn update_gelir(
         }
 
         // Outbox: kasa bakiye değişimleri türetilmiş alan — yalnızca gelir kaydı kuyruğa girer
-        outbox::queue_change(conn, &tenant_id_param, "gelirler", &id, "update")
+        outbox::queue_change(conn, tenant_id_param, "gelirler", id, "update")
+            .map_err(outbox_err)?;
+        yevmiye::kaydi_isle(conn, tenant_id_param, "gelirler", id)
             .map_err(outbox_err)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
-    let _ = eski_tutar; // bilgi amaçlı tutuluyor; artık inline aritmetik yok
-
     let updated = gelir_dsl::gelirler
-        .filter(gelir_dsl::id.eq(&id))
-        .filter(gelir_dsl::tenant_id.eq(&tenant_id_param))
-        .first::<Gelir>(&mut conn)
+        .filter(gelir_dsl::id.eq(id))
+        .filter(gelir_dsl::tenant_id.eq(tenant_id_param))
+        .first::<Gelir>(conn)
         .map_err(|e| e.to_string())?;
 
     Ok(updated)
@@ -1261,11 +1491,18 @@ pub async fn update_gider(
         .map_err(|e| e.to_string())?;
 
     let eski_kasa_id = current.kasa_id.clone();
-    let eski_tutar = current.tutar;
     let yeni_kasa_id = request.kasa_id.clone().unwrap_or(eski_kasa_id.clone());
-    let yeni_tutar = request.tutar.unwrap_or(eski_tutar);
+    let yeni_tutar = request.tutar.unwrap_or(current.tutar);
 
+    let mut denetim = state.denetim("update_gider");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "giderler", &id).map_err(outbox_err)?;
+        // Dönem kilidi: hem mevcut hem yeni tarih açık dönemde olmalı
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &current.tarih).map_err(outbox_err)?;
+        if let Some(ref yeni_tarih) = request.tarih {
+            donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, yeni_tarih).map_err(outbox_err)?;
+        }
+
         // Gider kaydını güncelle (tenant filtreli)
         diesel::update(
             gider_dsl::giderler
@@ -1296,12 +1533,13 @@ pub async fn update_gider(
         // Outbox: kasa bakiye değişimleri türetilmiş alan — yalnızca gider kaydı kuyruğa girer
         outbox::queue_change(conn, &tenant_id_param, "giderler", &id, "update")
             .map_err(outbox_err)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "giderler", &id)
+            .map_err(outbox_err)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
-    let _ = (eski_tutar, yeni_tutar);
-
     let updated = gider_dsl::giderler
         .filter(gider_dsl::id.eq(&id))
         .filter(gider_dsl::tenant_id.eq(&tenant_id_param))
@@ -1330,8 +1568,10 @@ pub async fn delete_kasa(
 
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
+    let mut denetim = state.denetim("delete_kasa");
     // Soft delete + outbox kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "kasalar", &id).map_err(TxError::Msg)?;
         diesel::update(
             kasa_dsl::kasalar
                 .filter(kasa_dsl::id.eq(&id))
@@ -1345,7 +1585,10 @@ pub async fn delete_kasa(
 
         outbox::queue_change(conn, &tenant_id_param, "kasalar", &id, "delete")
             .map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "kasalar", &id)
+            .map_err(TxError::Msg)?;
 
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -1376,7 +1619,14 @@ pub async fn delete_gelir(
         .first::<Gelir>(&mut conn)
         .map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("delete_gelir");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "gelirler", &record_id).map_err(outbox_err)?;
+        if let Some(ref aid_id) = gelir.aidat_id {
+            denetim.izle(conn, &tenant_id_param, "aidat_takip", aid_id).map_err(outbox_err)?;
+        }
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &gelir.tarih).map_err(outbox_err)?;
+
         // SOFT DELETE: Gelir kaydını silindi olarak işaretle
         diesel::sql_query(
             "UPDATE gelirler SET is_deleted = 1, updated_at = ?1 WHERE id = ?2"
@@ -1385,22 +1635,8 @@ pub async fn delete_gelir(
         .bind::<diesel::sql_types::Text, _>(&record_id)
         .execute(conn)?;
 
-        // Kasa bakiyesini güncelle (geliri düş)
-        use crate::db::schema::kasalar;
-        diesel::sql_query(
-            "UPDATE kasalar 
-             SET toplam_gelir = COALESCE(toplam_gelir, 0.0) - ?1,
-                 fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) - ?2,
-                 bakiye = bakiye - ?3,
-                 updated_at = ?4
-             WHERE id = ?5"
-        )
-        .bind::<diesel::sql_types::Double, _>(gelir.tutar)
-        .bind::<diesel::sql_types::Double, _>(gelir.tutar)
-        .bind::<diesel::sql_types::Double, _>(gelir.tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&gelir.kasa_id)
-        .execute(conn)?;
+        // Kasa bakiyesini gelirler SUM'ından yeniden hesapla (silinen kayıt hariç)
+        update_kasa_bakiye(conn, &gelir.kasa_id)?;
 
         // Aidat ile ilişkili ise aidat tablosunu da güncelle
         if let Some(ref aid_id) = gelir.aidat_id {
@@ -1410,9 +1646,9 @@ pub async fn delete_gelir(
             #[derive(QueryableByName)]
             struct AidatRow {
                 #[diesel(sql_type = diesel::sql_types::Double)]
-                aidat_tutar: f64,
+                aidat_tutar: Para,
                 #[diesel(sql_type = diesel::sql_types::Double)]
-                aidat_odenen: f64,
+                aidat_odenen: Para,
             }
             
             let aidat_opt = diesel::sql_query(
@@ -1424,9 +1660,9 @@ pub async fn delete_gelir(
             .ok();
             
             if let Some(aidat) = aidat_opt {
-                let yeni_odenen = (aidat.aidat_odenen - gelir.tutar).max(0.0);
+                let yeni_odenen = (aidat.aidat_odenen - gelir.tutar).sifirdan_kucukse_sifir();
                 let yeni_kalan = aidat.aidat_tutar - yeni_odenen;
-                let yeni_durum = if yeni_odenen <= 0.01 { 
+                let yeni_durum = if yeni_odenen.is_zero() { 
                     "beklemede" 
                 } else if yeni_odenen >= aidat.aidat_tutar { 
                     "odendi" 
@@ -1447,13 +1683,23 @@ pub async fn delete_gelir(
                 // Outbox: dokunulan aidat kaydı da kuyruğa girer
                 outbox::queue_change(conn, &tenant_id_param, "aidat_takip", aid_id, "update")
                     .map_err(outbox_err)?;
+                yevmiye::kaydi_isle(conn, &tenant_id_param, "aidat_takip", aid_id)
+                    .map_err(outbox_err)?;
             }
         }
 
+        // Bilet tahsilatıysa etkinlik kaydı yeniden ödeme bekler
+        etkinlik_kaydi::gelir_silindi(conn, &mut denetim, &tenant_id_param, &record_id).map_err(outbox_err)?;
+        // Gecikme faizi tahsilatıysa faiz yeniden açık alacak olur
+        gecikme::gelir_silindi(conn, &mut denetim, &tenant_id_param, &record_id, gelir.tutar).map_err(outbox_err)?;
+
         // Outbox: gelir silme (kasa bakiye değişimi türetilmiş alan, kuyruğa girmez)
         outbox::queue_change(conn, &tenant_id_param, "gelirler", &record_id, "delete")
             .map_err(outbox_err)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "gelirler", &record_id)
+            .map_err(outbox_err)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -1473,46 +1719,49 @@ pub async fn delete_gider(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("delete_gider");
+    gider_sil(&mut conn, &mut denetim, &tenant_id_param, &record_id)
+}
+
+/// Gider silme (soft delete): dönem kilidi, kasa bakiyesi, outbox ve yevmiye tek transaction'da.
+pub(crate) fn gider_sil(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id_param: &str,
+    record_id: &str,
+) -> Result<(), String> {
     let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
     // Önce kaydı al
     use crate::db::schema::giderler::dsl as gider_dsl;
     let gider = gider_dsl::giderler
-        .filter(gider_dsl::id.eq(&record_id))
-        .filter(gider_dsl::tenant_id.eq(&tenant_id_param))
-        .first::<Gider>(&mut conn)
+        .filter(gider_dsl::id.eq(record_id))
+        .filter(gider_dsl::tenant_id.eq(tenant_id_param))
+        .first::<Gider>(conn)
         .map_err(|e| e.to_string())?;
 
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, tenant_id_param, "giderler", record_id).map_err(outbox_err)?;
+        donem_kilidi::tarihi_dogrula(conn, tenant_id_param, &gider.tarih).map_err(outbox_err)?;
+
         // SOFT DELETE: Gider kaydını silindi olarak işaretle
         diesel::sql_query(
             "UPDATE giderler SET is_deleted = 1, updated_at = ?1 WHERE id = ?2"
         )
         .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&record_id)
+        .bind::<diesel::sql_types::Text, _>(record_id)
         .execute(conn)?;
 
-        // Kasa bakiyesini güncelle (gideri geri ekle)
-        use crate::db::schema::kasalar;
-        diesel::sql_query(
-            "UPDATE kasalar 
-             SET toplam_gider = COALESCE(toplam_gider, 0.0) - ?1,
-                 fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) + ?2,
-                 bakiye = bakiye + ?3,
-                 updated_at = ?4
-             WHERE id = ?5"
-        )
-        .bind::<diesel::sql_types::Double, _>(gider.tutar)
-        .bind::<diesel::sql_types::Double, _>(gider.tutar)
-        .bind::<diesel::sql_types::Double, _>(gider.tutar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&gider.kasa_id)
-        .execute(conn)?;
+        // Kasa bakiyesini giderler SUM'ından yeniden hesapla (silinen kayıt hariç)
+        update_kasa_bakiye(conn, &gider.kasa_id)?;
 
         // Outbox: gider silme (kasa bakiye değişimi türetilmiş alan, kuyruğa girmez)
-        outbox::queue_change(conn, &tenant_id_param, "giderler", &record_id, "delete")
+        outbox::queue_change(conn, tenant_id_param, "giderler", record_id, "delete")
+            .map_err(outbox_err)?;
+        yevmiye::kaydi_isle(conn, tenant_id_param, "giderler", record_id)
             .map_err(outbox_err)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -1542,7 +1791,11 @@ pub async fn delete_virman(
         .first::<Virman>(&mut conn)
         .map_err(|e| e.to_string())?;
 
+    let mut denetim = state.denetim("delete_virman");
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "virmanlar", &record_id).map_err(outbox_err)?;
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &virman.tarih).map_err(outbox_err)?;
+
         // SOFT DELETE: Virman kaydını silindi olarak işaretle
         diesel::sql_query(
             "UPDATE virmanlar SET is_deleted = 1, updated_at = ?1 WHERE id = ?2"
@@ -1551,47 +1804,17 @@ pub async fn delete_virman(
         .bind::<diesel::sql_types::Text, _>(&record_id)
         .execute(conn)?;
 
-        // Cross-currency için: kaynak = virman.tutar, hedef = hedef_tutar (varsa)
-        let kaynak_miktar = virman.tutar;
-        let hedef_miktar = virman.hedef_tutar.unwrap_or(virman.tutar);
-
-        // Kaynak kasaya parayı geri ekle (kaynak para biriminde)
-        use crate::db::schema::kasalar;
-        diesel::sql_query(
-            "UPDATE kasalar
-             SET virman_cikis = COALESCE(virman_cikis, 0.0) - ?1,
-                 fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) + ?2,
-                 bakiye = bakiye + ?3,
-                 updated_at = ?4
-             WHERE id = ?5"
-        )
-        .bind::<diesel::sql_types::Double, _>(kaynak_miktar)
-        .bind::<diesel::sql_types::Double, _>(kaynak_miktar)
-        .bind::<diesel::sql_types::Double, _>(kaynak_miktar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&virman.kaynak_kasa_id)
-        .execute(conn)?;
-
-        // Hedef kasadan parayı çıkar (hedef para biriminde — kur uygulanmış)
-        diesel::sql_query(
-            "UPDATE kasalar
-             SET virman_giris = COALESCE(virman_giris, 0.0) - ?1,
-                 fiziksel_bakiye = COALESCE(fiziksel_bakiye, 0.0) - ?2,
-                 bakiye = bakiye - ?3,
-                 updated_at = ?4
-             WHERE id = ?5"
-        )
-        .bind::<diesel::sql_types::Double, _>(hedef_miktar)
-        .bind::<diesel::sql_types::Double, _>(hedef_miktar)
-        .bind::<diesel::sql_types::Double, _>(hedef_miktar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&virman.hedef_kasa_id)
-        .execute(conn)?;
+        // Her iki kasayı virmanlar SUM'ından yeniden hesapla (cross-currency hedef_tutar dahil)
+        update_kasa_bakiye(conn, &virman.kaynak_kasa_id)?;
+        update_kasa_bakiye(conn, &virman.hedef_kasa_id)?;
 
         // Outbox: virman silme (kasa bakiye değişimleri türetilmiş alan, kuyruğa girmez)
         outbox::queue_change(conn, &tenant_id_param, "virmanlar", &record_id, "delete")
             .map_err(outbox_err)?;
+        yevmiye::kaydi_isle(conn, &tenant_id_param, "virmanlar", &record_id)
+            .map_err(outbox_err)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e: diesel::result::Error| e.to_string())?;
 
@@ -1612,105 +1835,128 @@ pub async fn recalculate_kasa_bakiye(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    #[derive(QueryableByName)]
-    struct SumRow {
-        #[diesel(sql_type = diesel::sql_types::Double)]
-        total: f64,
-    }
+    use crate::db::schema::kasalar::dsl as kasa_dsl;
+    let kasa = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        // Kasa bu tenant'a ait mi?
+        kasa_dsl::kasalar
+            .filter(kasa_dsl::id.eq(&kasa_id))
+            .filter(kasa_dsl::tenant_id.eq(&tenant_id_param))
+            .first::<Kasa>(conn)?;
 
-    // 1. Toplam gelir
-    let toplam_gelir = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM gelirler WHERE kasa_id = ?1 AND tenant_id = ?2"
-    )
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<SumRow>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+        update_kasa_bakiye(conn, &kasa_id)?;
 
-    // 2. Toplam gider
-    let toplam_gider = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM giderler WHERE kasa_id = ?1 AND tenant_id = ?2"
-    )
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<SumRow>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+        kasa_dsl::kasalar
+            .filter(kasa_dsl::id.eq(&kasa_id))
+            .first::<Kasa>(conn)
+    }).map_err(|e| e.to_string())?;
 
-    // 3. Virman giriş (bu kasaya transfer edilen — kur uygulanmış hedef tutar)
-    let virman_giris = diesel::sql_query(
-        "SELECT COALESCE(SUM(COALESCE(hedef_tutar, tutar)), 0.0) as total FROM virmanlar WHERE hedef_kasa_id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<SumRow>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+    Ok(format!(
+        "Kasa bakiyesi yeniden hesaplandı: {} TL (Gelir: {}, Gider: {}, Virman Giriş: {}, Virman Çıkış: {})",
+        kasa.bakiye,
+        kasa.toplam_gelir.unwrap_or_default(),
+        kasa.toplam_gider.unwrap_or_default(),
+        kasa.virman_giris.unwrap_or_default(),
+        kasa.virman_cikis.unwrap_or_default()
+    ))
+}
 
-    // 4. Virman çıkış (bu kasadan başka kasaya transfer — kaynak para birimi)
-    let virman_cikis = diesel::sql_query(
-        "SELECT COALESCE(SUM(tutar), 0.0) as total FROM virmanlar WHERE kaynak_kasa_id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<SumRow>(&mut conn)
-    .map(|r| r.total)
-    .unwrap_or(0.0);
+// ============================================================================
+// KASA BAKİYE TUTARLILIK DENETİMİ
+// Kural: kasalar.fiziksel_bakiye == devir_bakiye + SUM(gelir) - SUM(gider) + virman_net
+// (kuruş cinsinden, tam eşitlik). Migration 034 öncesi bakiyeler para_donusum_kontrol
+// tablosunda saklanır; dönüşümün bakiyeleri değiştirmediği buradan doğrulanır.
+// onar=true ile uyumsuz kasalar baz kayıtlardan yeniden hesaplanır.
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct KasaTutarlilikSonucu {
+    pub kasa_id: String,
+    pub kasa_adi: String,
+    pub kayitli_bakiye: Para,
+    pub hesaplanan_bakiye: Para,
+    pub fark: Para,
+    pub donusum_oncesi_bakiye: Option<Para>,
+    pub donusum_farki: Option<Para>,
+    pub tutarli: bool,
+    /// Devir bakiyesi kesim tarihi olmadan gelmiş (ör. eski sunucudan sync); yıl sonu
+    /// devri yeniden uygulanana kadar bakiye hesaplanmaz
+    pub devir_tarihi_eksik: bool,
+    pub onarildi: bool,
+}
+
+#[tauri::command]
+pub async fn check_kasa_bakiye_tutarliligi(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    onar: bool,
+) -> Result<Vec<KasaTutarlilikSonucu>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    // 5. Devir bakiye al
     #[derive(QueryableByName)]
-    struct DevirRow {
+    struct KasaKontrolRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_adi: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        devir: Para,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        fiziksel: Para,
         #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-        devir_bakiye: Option<f64>,
+        oncesi: Option<Para>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        devir_tarihi: Option<String>,
     }
 
-    let devir = diesel::sql_query(
-        "SELECT devir_bakiye FROM kasalar WHERE id = ?1 AND tenant_id = ?2"
+    let rows = diesel::sql_query(
+        "SELECT k.id, k.kasa_adi, k.devir_tarihi,
+                COALESCE(k.devir_bakiye, 0.0) as devir,
+                COALESCE(k.fiziksel_bakiye, k.bakiye, 0.0) as fiziksel,
+                (SELECT p.fiziksel_bakiye FROM para_donusum_kontrol p WHERE p.kasa_id = k.id) as oncesi
+         FROM kasalar k
+         WHERE k.tenant_id = ?1 AND k.is_active = 1
+         ORDER BY k.kasa_adi"
     )
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
     .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .get_result::<DevirRow>(&mut conn)
-    .map(|r| r.devir_bakiye.unwrap_or(0.0))
-    .unwrap_or(0.0);
-
-    // 6. Yeni bakiye hesapla
-    let yeni_bakiye = devir + toplam_gelir - toplam_gider + virman_giris - virman_cikis;
-    let fiziksel_bakiye = yeni_bakiye;
-    let serbest_bakiye = yeni_bakiye;
+    .load::<KasaKontrolRow>(&mut conn)
+    .map_err(|e| e.to_string())?;
 
-    // 7. Kasa güncelle
-    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut sonuclar = Vec::with_capacity(rows.len());
+    for r in rows {
+        let hareket = hesapla_kasa_hareketleri(&mut conn, &r.id, None).map_err(|e| e.to_string())?;
+        let hesaplanan = r.devir + hareket.net();
+        let fark = r.fiziksel - hesaplanan;
+        let tutarli = fark.is_zero();
+
+        // Kesim tarihi olmayan devirde hesaplanan bakiye güvenilmez; onarılmaz
+        let devir_tarihi_eksik = r.devir_tarihi.is_none() && !r.devir.is_zero();
+
+        let mut onarildi = false;
+        if onar && !tutarli && !devir_tarihi_eksik {
+            conn.transaction::<_, diesel::result::Error, _>(|conn| update_kasa_bakiye(conn, &r.id))
+                .map_err(|e| e.to_string())?;
+            onarildi = true;
+        }
 
-    diesel::sql_query(
-        "UPDATE kasalar
-         SET bakiye = ?1,
-             toplam_gelir = ?2,
-             toplam_gider = ?3,
-             virman_giris = ?4,
-             virman_cikis = ?5,
-             fiziksel_bakiye = ?6,
-             serbest_bakiye = ?7,
-             updated_at = ?8
-         WHERE id = ?9 AND tenant_id = ?10"
-    )
-    .bind::<diesel::sql_types::Double, _>(yeni_bakiye)
-    .bind::<diesel::sql_types::Double, _>(toplam_gelir)
-    .bind::<diesel::sql_types::Double, _>(toplam_gider)
-    .bind::<diesel::sql_types::Double, _>(virman_giris)
-    .bind::<diesel::sql_types::Double, _>(virman_cikis)
-    .bind::<diesel::sql_types::Double, _>(fiziksel_bakiye)
-    .bind::<diesel::sql_types::Double, _>(serbest_bakiye)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+        sonuclar.push(KasaTutarlilikSonucu {
+            kasa_id: r.id,
+            kasa_adi: r.kasa_adi,
+            kayitli_bakiye: r.fiziksel,
+            hesaplanan_bakiye: hesaplanan,
+            fark,
+            donusum_oncesi_bakiye: r.oncesi,
+            donusum_farki: r.oncesi.map(|o| o - hesaplanan),
+            tutarli,
+            devir_tarihi_eksik,
+            onarildi,
+        });
+    }
 
-    Ok(format!(
-        "Kasa bakiyesi yeniden hesaplandı: {} TL (Gelir: {}, Gider: {}, Virman Giriş: {}, Virman Çıkış: {})",
-        yeni_bakiye, toplam_gelir, toplam_gider, virman_giris, virman_cikis
-    ))
+    Ok(sonuclar)
 }
 
 // ============================================================================
@@ -1733,7 +1979,7 @@ pub struct GelirWithAidatDetails {
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub tarih: String,
     #[diesel(sql_type = diesel::sql_types::Double)]
-    pub tutar: f64,
+    pub tutar: Para,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub aciklama: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
@@ -1768,6 +2014,16 @@ pub async fn get_uyeye_ait_gelirler(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+    uyeye_ait_gelirler(&mut conn, &tenant_id_param, &uye_id).map_err(|e| e.to_string())
+}
+
+/// Üyenin silinmemiş gelirleri, aidat dönem bilgisiyle (yeniden eskiye).
+/// Üye hesap ekstresi PDF'i de bunu kullanır.
+pub(crate) fn uyeye_ait_gelirler(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: &str,
+) -> QueryResult<Vec<GelirWithAidatDetails>> {
     // JOIN ile aidat dönem bilgisini al
     let query = "
         SELECT
@@ -1788,16 +2044,14 @@ pub async fn get_uyeye_ait_gelirler(
         FROM gelirler g
         LEFT JOIN aidat_takip a ON a.id = g.aidat_id AND a.tenant_id = g.tenant_id
         WHERE g.tenant_id = ?1 AND g.uye_id = ?2
+          AND (g.is_deleted IS NULL OR g.is_deleted = 0)
         ORDER BY g.tarih DESC, g.created_at DESC
     ";
 
-    let results = diesel::sql_query(query)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&uye_id)
-        .load::<GelirWithAidatDetails>(&mut conn)
-        .map_err(|e| e.to_string())?;
-
-    Ok(results)
+    diesel::sql_query(query)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .load::<GelirWithAidatDetails>(conn)
 }
 
 // NOT: get_uyeye_ait_giderler fonksiyonu kaldırıldı
@@ -32,3 +32,27 @@ pub mod kur;
 pub mod gorunum;
 pub mod device;
 pub mod error_logger;
+pub mod yevmiye;
+pub mod resmi_defterler;
+pub mod beyanname;
+pub mod donem_kilidi;
+pub mod denetim;
+pub mod banka_ekstresi;
+pub mod kasa_sayimi;
+pub mod alindi_belgesi;
+pub mod belge_pdf;
+pub mod aidat_fiyat;
+pub mod aidat_tahakkuku;
+pub mod gecikme;
+pub mod avans;
+pub mod taksit;
+pub mod ihtar;
+pub mod hane;
+pub mod uye_aktarimi;
+pub mod uyelik_durumu;
+pub mod uyelik_basvurusu;
+pub mod genel_kurul;
+pub mod karar_defteri;
+pub mod organ_gorevi;
+pub mod toplanti_katilimi;
+pub mod etkinlik_kaydi;
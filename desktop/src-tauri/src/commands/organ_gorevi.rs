@@ -0,0 +1,148 @@
+// Kurul ve komisyon görevleri, görev dönemleri ve toplantı yeter sayısı (bkz. db::organ_gorevi)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::organ_gorevi::{self, GorevGirdi, OrganGorevi, OrganYetersayisi};
+
+/// Görev kayıtları; tarih verilirse o gün görevde olanlar
+#[tauri::command]
+pub fn get_organ_gorevleri(
+    state: State<AppState>,
+    tenant_id_param: String,
+    organ: Option<String>,
+    tarih: Option<String>,
+) -> Result<Vec<OrganGorevi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    organ_gorevi::gorevleri_getir(&mut conn, &tenant_id_param, organ.as_deref(), tarih.as_deref())
+}
+
+/// Görev ekler (gorev_id yoksa) ya da günceller
+#[tauri::command]
+pub fn organ_gorevi_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    gorev_id: Option<String>,
+    data: GorevGirdi,
+) -> Result<OrganGorevi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("organ_gorevi_kaydet");
+    let id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let id = organ_gorevi::gorev_kaydet(conn, &mut denetim, &tenant_id_param, gorev_id.as_deref(), &data)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    organ_gorevi::gorev_getir(&mut conn, &tenant_id_param, &id)
+}
+
+/// Görevi son_gun itibarıyla sonlandırır; yedek_cagir ile sıradaki yedek asil üyeliğe geçer
+#[tauri::command]
+pub fn organ_gorevinden_ayril(
+    state: State<AppState>,
+    tenant_id_param: String,
+    gorev_id: String,
+    son_gun: String,
+    neden: String,
+    yedek_cagir: bool,
+) -> Result<Option<OrganGorevi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let son_gun = chrono::NaiveDate::parse_from_str(&son_gun, "%Y-%m-%d").map_err(|_| "Geçersiz tarih".to_string())?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("organ_gorevinden_ayril");
+    let yeni = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let yeni = organ_gorevi::gorevden_ayril(conn, &mut denetim, &tenant_id_param, &gorev_id, son_gun, &neden, yedek_cagir)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(yeni)
+        })
+        .map_err(|e| e.to_string())?;
+
+    yeni.map(|id| organ_gorevi::gorev_getir(&mut conn, &tenant_id_param, &id)).transpose()
+}
+
+/// Genel kurulun sonuçlanmış seçimlerinden kurulların yeni dönemini oluşturur
+#[tauri::command]
+pub fn organ_secimden_gorevlendir(
+    state: State<AppState>,
+    tenant_id_param: String,
+    genel_kurul_id: String,
+    bitis_tarihi: Option<String>,
+) -> Result<Vec<OrganGorevi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("organ_secimden_gorevlendir");
+    let idler = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let idler = organ_gorevi::secimden_gorevlendir(
+                conn,
+                &mut denetim,
+                &tenant_id_param,
+                &genel_kurul_id,
+                bitis_tarihi.as_deref(),
+            )
+            .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(idler)
+        })
+        .map_err(|e| e.to_string())?;
+
+    idler.iter().map(|id| organ_gorevi::gorev_getir(&mut conn, &tenant_id_param, id)).collect()
+}
+
+/// Önümüzdeki `gun` (varsayılan 60) gün içinde görev süresi dolacak üyeler
+#[tauri::command]
+pub fn get_biten_organ_gorevleri(
+    state: State<AppState>,
+    tenant_id_param: String,
+    gun: Option<i64>,
+) -> Result<Vec<OrganGorevi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let bugun = chrono::Local::now().date_naive();
+    organ_gorevi::biten_gorevler(&mut conn, &tenant_id_param, bugun, gun.unwrap_or(60))
+}
+
+/// Kurulun tarihteki üye tamsayısı ve toplantı yeter sayısı (üyeleri kayıtlı değilse null)
+#[tauri::command]
+pub fn get_organ_yetersayisi(
+    state: State<AppState>,
+    tenant_id_param: String,
+    organ: String,
+    tarih: String,
+) -> Result<Option<OrganYetersayisi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    organ_gorevi::yetersayi(&mut conn, &tenant_id_param, &organ, &tarih)
+}
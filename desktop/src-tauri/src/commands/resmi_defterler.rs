@@ -0,0 +1,831 @@
+// Resmi defter ve tablolar (Dernekler Yönetmeliği)
+//
+// İşletme hesabı esasına göre defter tutan dernekler için:
+//   - İşletme Hesabı Defteri: sol sayfa giderler, sağ sayfa gelirler; her sayfada
+//     türlere göre sütunlar, "önceki sayfadan nakil", "sayfa toplamı" ve
+//     "sonraki sayfaya nakil" satırları.
+//   - İşletme Hesabı Tablosu (yıllık gelir-gider tablosu): önceki yıldan devreden
+//     kasa/banka mevcudu + gelirler = giderler + ertesi yıla devreden kasa/banka.
+// Kaynak: gelirler, giderler, virmanlar (silinmişler hariç). XLSX çıktıları commands::export'tadır.
+// Defter ve tablo TL tutulur: dövizli kasaların kayıtları kayıt tarihindeki kurla, mevcutları
+// devir tarihindeki kurla TL'ye çevrilir; aradaki değerleme farkı kur farkı kalemine yazılır.
+
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::para::Para;
+use crate::db::yevmiye::hesap_anahtari;
+
+/// Sayfa başına satır sayısı verilmezse (matbu defterlerde yaygın olan)
+pub const VARSAYILAN_SAYFA_SATIR_SAYISI: usize = 25;
+
+// ============================================================================
+// İŞLETME HESABI DEFTERİ
+// ============================================================================
+
+/// Deftere yazılacak tek gelir veya gider kaydı; tutar TL karşılığıdır.
+#[derive(Debug, Clone, QueryableByName)]
+pub struct DefterKaydi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub belge_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kasa_adi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kategori: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub tutar: Para,
+    /// Kaydın kasasının para birimi
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub para_birimi: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DefterSatiri {
+    pub sira_no: u32,
+    pub tarih: String,
+    pub belge_no: Option<String>,
+    pub aciklama: Option<String>,
+    pub kasa_adi: String,
+    pub kategori: String,
+    /// Satır tutarının yazıldığı tür sütunu (gelir_kategorileri / gider_kategorileri indeksi)
+    pub kategori_sira: usize,
+    pub tutar: Para,
+}
+
+/// Tür sütunlarının toplamları ve genel toplam
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DefterToplami {
+    pub kategoriler: Vec<Para>,
+    pub toplam: Para,
+}
+
+impl DefterToplami {
+    fn sifir(kategori_sayisi: usize) -> Self {
+        DefterToplami { kategoriler: vec![Para::SIFIR; kategori_sayisi], toplam: Para::SIFIR }
+    }
+
+    fn ekle(&mut self, kategori_sira: usize, tutar: Para) {
+        self.kategoriler[kategori_sira] += tutar;
+        self.toplam += tutar;
+    }
+
+    fn topla(&self, diger: &DefterToplami) -> DefterToplami {
+        DefterToplami {
+            kategoriler: self.kategoriler.iter().zip(&diger.kategoriler).map(|(a, b)| *a + *b).collect(),
+            toplam: self.toplam + diger.toplam,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DefterSayfaTarafi {
+    pub satirlar: Vec<DefterSatiri>,
+    pub onceki_sayfadan_nakil: DefterToplami,
+    pub sayfa_toplami: DefterToplami,
+    pub sonraki_sayfaya_nakil: DefterToplami,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DefterSayfasi {
+    pub sayfa_no: u32,
+    /// Sol sayfa
+    pub gider: DefterSayfaTarafi,
+    /// Sağ sayfa
+    pub gelir: DefterSayfaTarafi,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IsletmeHesabiDefteri {
+    pub yil: i32,
+    pub sayfa_satir_sayisi: usize,
+    pub gelir_kategorileri: Vec<String>,
+    pub gider_kategorileri: Vec<String>,
+    pub sayfalar: Vec<DefterSayfasi>,
+    pub toplam_gelir: Para,
+    pub toplam_gider: Para,
+    /// Gelir − gider (dönem sonucu)
+    pub donem_farki: Para,
+}
+
+/// Kayıtların türlerini sütun listesine çevirir. Yazımı farklı aynı türler
+/// ("Aidat" / "AİDAT") tek sütunda toplanır; sütun adı ilk görülen yazımdır.
+fn kategori_sutunlari(kayitlar: &[DefterKaydi]) -> (Vec<String>, Vec<usize>) {
+    let mut anahtarlar: Vec<String> = Vec::new();
+    let mut adlar: Vec<String> = Vec::new();
+    for k in kayitlar {
+        let anahtar = hesap_anahtari(&k.kategori);
+        if !anahtarlar.contains(&anahtar) {
+            anahtarlar.push(anahtar);
+            adlar.push(k.kategori.trim().to_string());
+        }
+    }
+
+    // Sütunlar ada göre sıralı (defter her yıl aynı düzende basılır)
+    let mut sira: Vec<usize> = (0..adlar.len()).collect();
+    sira.sort_by(|a, b| anahtarlar[*a].cmp(&anahtarlar[*b]));
+    let sirali_adlar: Vec<String> = sira.iter().map(|i| adlar[*i].clone()).collect();
+    let sirali_anahtarlar: Vec<&String> = sira.iter().map(|i| &anahtarlar[*i]).collect();
+
+    let indeksler = kayitlar
+        .iter()
+        .map(|k| {
+            let anahtar = hesap_anahtari(&k.kategori);
+            sirali_anahtarlar.iter().position(|a| **a == anahtar).unwrap_or(0)
+        })
+        .collect();
+    (sirali_adlar, indeksler)
+}
+
+/// Bir defter tarafını (gelir veya gider) sayfalara böler; nakil toplamları sayfadan sayfaya taşınır.
+fn tarafi_sayfala(
+    kayitlar: &[DefterKaydi],
+    indeksler: &[usize],
+    kategori_sayisi: usize,
+    satir_sayisi: usize,
+    sayfa_sayisi: usize,
+) -> Vec<DefterSayfaTarafi> {
+    let mut sayfalar = Vec::with_capacity(sayfa_sayisi);
+    let mut nakil = DefterToplami::sifir(kategori_sayisi);
+
+    for sayfa in 0..sayfa_sayisi {
+        let bas = (sayfa * satir_sayisi).min(kayitlar.len());
+        let son = ((sayfa + 1) * satir_sayisi).min(kayitlar.len());
+
+        let mut sayfa_toplami = DefterToplami::sifir(kategori_sayisi);
+        let satirlar: Vec<DefterSatiri> = (bas..son)
+            .map(|i| {
+                let k = &kayitlar[i];
+                sayfa_toplami.ekle(indeksler[i], k.tutar);
+                DefterSatiri {
+                    sira_no: i as u32 + 1,
+                    tarih: k.tarih.clone(),
+                    belge_no: k.belge_no.clone(),
+                    aciklama: k.aciklama.clone(),
+                    kasa_adi: k.kasa_adi.clone(),
+                    kategori: k.kategori.clone(),
+                    kategori_sira: indeksler[i],
+                    tutar: k.tutar,
+                }
+            })
+            .collect();
+
+        let sonraki = nakil.topla(&sayfa_toplami);
+        sayfalar.push(DefterSayfaTarafi {
+            satirlar,
+            onceki_sayfadan_nakil: nakil,
+            sayfa_toplami,
+            sonraki_sayfaya_nakil: sonraki.clone(),
+        });
+        nakil = sonraki;
+    }
+
+    sayfalar
+}
+
+/// Tarih sıralı gelir ve gider kayıtlarından defter sayfalarını oluşturur.
+pub fn isletme_defteri_olustur(
+    yil: i32,
+    gelirler: &[DefterKaydi],
+    giderler: &[DefterKaydi],
+    sayfa_satir_sayisi: usize,
+) -> IsletmeHesabiDefteri {
+    let satir_sayisi = sayfa_satir_sayisi.max(1);
+    let (gelir_kategorileri, gelir_indeksleri) = kategori_sutunlari(gelirler);
+    let (gider_kategorileri, gider_indeksleri) = kategori_sutunlari(giderler);
+
+    let sayfa_sayisi = gelirler.len().div_ceil(satir_sayisi)
+        .max(giderler.len().div_ceil(satir_sayisi))
+        .max(1);
+
+    let gelir_sayfalari = tarafi_sayfala(gelirler, &gelir_indeksleri, gelir_kategorileri.len(), satir_sayisi, sayfa_sayisi);
+    let gider_sayfalari = tarafi_sayfala(giderler, &gider_indeksleri, gider_kategorileri.len(), satir_sayisi, sayfa_sayisi);
+
+    let toplam_gelir: Para = gelirler.iter().map(|k| k.tutar).sum();
+    let toplam_gider: Para = giderler.iter().map(|k| k.tutar).sum();
+
+    let sayfalar = gider_sayfalari
+        .into_iter()
+        .zip(gelir_sayfalari)
+        .enumerate()
+        .map(|(i, (gider, gelir))| DefterSayfasi { sayfa_no: i as u32 + 1, gider, gelir })
+        .collect();
+
+    IsletmeHesabiDefteri {
+        yil,
+        sayfa_satir_sayisi: satir_sayisi,
+        gelir_kategorileri,
+        gider_kategorileri,
+        sayfalar,
+        toplam_gelir,
+        toplam_gider,
+        donem_farki: toplam_gelir - toplam_gider,
+    }
+}
+
+pub(crate) fn yil_araligi(yil: i32) -> (String, String) {
+    (format!("{}-01-01", yil), format!("{}-01-01", yil + 1))
+}
+
+/// Tutarın verilen tarihteki kurla TL karşılığı; TL tutarlar olduğu gibi döner.
+pub(crate) fn tl_karsiligi(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    para_birimi: &str,
+    tutar: Para,
+    tarih: &str,
+) -> Result<Para, String> {
+    if para_birimi == "TRY" {
+        return Ok(tutar);
+    }
+    let kur = crate::commands::mali::get_kur_degeri(conn, tenant_id, para_birimi, "TRY", tarih)?;
+    Ok(tutar.carp(kur))
+}
+
+/// Yılın gelir ve gider kayıtları, deftere yazılış sırasıyla (tarih, kayıt zamanı).
+fn defter_kayitlari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+) -> Result<(Vec<DefterKaydi>, Vec<DefterKaydi>), String> {
+    let (bas, son) = yil_araligi(yil);
+
+    let mut gelirler: Vec<DefterKaydi> = diesel::sql_query(
+        "SELECT substr(g.tarih, 1, 10) as tarih, COALESCE(g.makbuz_no, g.belge_no) as belge_no, g.aciklama,
+                COALESCE(k.kasa_adi, '-') as kasa_adi,
+                COALESCE(NULLIF(TRIM(gt.ad), ''), NULLIF(TRIM(g.gelir_turu), ''), 'Diğer Gelirler') as kategori,
+                g.tutar, COALESCE(k.para_birimi, 'TRY') as para_birimi
+         FROM gelirler g
+         LEFT JOIN kasalar k ON k.id = g.kasa_id
+         LEFT JOIN gelir_turleri gt ON gt.id = g.gelir_turu_id
+         WHERE g.tenant_id = ?1 AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+           AND g.tarih >= ?2 AND g.tarih < ?3
+         ORDER BY g.tarih, g.created_at, g.id"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&bas)
+    .bind::<diesel::sql_types::Text, _>(&son)
+    .load(conn)
+    .map_err(|e| format!("Database error (gelirler): {}", e))?;
+
+    let mut giderler: Vec<DefterKaydi> = diesel::sql_query(
+        "SELECT substr(g.tarih, 1, 10) as tarih, COALESCE(g.fatura_no, g.islem_no) as belge_no, g.aciklama,
+                COALESCE(k.kasa_adi, '-') as kasa_adi,
+                COALESCE(NULLIF(TRIM(gt.ad), ''), NULLIF(TRIM(g.gider_turu), ''), 'Diğer Giderler') as kategori,
+                g.tutar, COALESCE(k.para_birimi, 'TRY') as para_birimi
+         FROM giderler g
+         LEFT JOIN kasalar k ON k.id = g.kasa_id
+         LEFT JOIN gider_turleri gt ON gt.id = g.gider_turu_id
+         WHERE g.tenant_id = ?1 AND (g.is_deleted IS NULL OR g.is_deleted = 0)
+           AND g.tarih >= ?2 AND g.tarih < ?3
+         ORDER BY g.tarih, g.created_at, g.id"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&bas)
+    .bind::<diesel::sql_types::Text, _>(&son)
+    .load(conn)
+    .map_err(|e| format!("Database error (giderler): {}", e))?;
+
+    for k in gelirler.iter_mut().chain(giderler.iter_mut()) {
+        k.tutar = tl_karsiligi(conn, tenant_id, &k.para_birimi, k.tutar, &k.tarih)?;
+    }
+    Ok((gelirler, giderler))
+}
+
+pub(crate) fn isletme_defteri_hazirla(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+    sayfa_satir_sayisi: Option<usize>,
+) -> Result<IsletmeHesabiDefteri, String> {
+    let (gelirler, giderler) = defter_kayitlari(conn, tenant_id, yil)?;
+    Ok(isletme_defteri_olustur(
+        yil,
+        &gelirler,
+        &giderler,
+        sayfa_satir_sayisi.unwrap_or(VARSAYILAN_SAYFA_SATIR_SAYISI),
+    ))
+}
+
+// ============================================================================
+// İŞLETME HESABI TABLOSU (YILLIK GELİR-GİDER TABLOSU)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TabloKalemi {
+    pub kalem: String,
+    pub tutar: Para,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KasaMevcudu {
+    pub kasa_id: String,
+    pub kasa_adi: String,
+    pub kasa_tipi: String,
+    pub para_birimi: String,
+    /// Kasanın kendi para birimiyle bakiye
+    pub tutar: Para,
+    /// Bakiye tarihindeki kurla TL karşılığı
+    pub tl_tutar: Para,
+}
+
+/// Mevcutların TL karşılığı toplamları
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MevcutToplami {
+    pub kasa: Para,
+    pub banka: Para,
+    pub toplam: Para,
+}
+
+impl MevcutToplami {
+    pub(crate) fn hesapla(mevcutlar: &[KasaMevcudu]) -> Self {
+        let banka: Para = mevcutlar.iter().filter(|m| m.kasa_tipi == "Banka").map(|m| m.tl_tutar).sum();
+        let toplam: Para = mevcutlar.iter().map(|m| m.tl_tutar).sum();
+        MevcutToplami { kasa: toplam - banka, banka, toplam }
+    }
+}
+
+/// Dövizli bir kasanın yıl içi değerleme farkı: TL karşılığındaki değişimin yıl içi
+/// hareketlerin TL karşılığıyla açıklanamayan kısmı. Pozitifse gelir, negatifse gider.
+#[derive(Debug, Clone, Serialize)]
+pub struct KurFarki {
+    pub kasa_id: String,
+    pub kasa_adi: String,
+    pub para_birimi: String,
+    pub tutar: Para,
+}
+
+/// Yıl içindeki virmanların özeti (TL karşılıkları). Kasa ↔ banka aktarımları tablonun
+/// toplamını değiştirmez; dövizli kasaların değerleme farkı kur farkı olarak tabloya girer.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VirmanOzeti {
+    pub bankaya_yatirilan: Para,
+    pub bankadan_cekilen: Para,
+    pub kasalar_arasi: Para,
+    pub bankalar_arasi: Para,
+    pub kur_farki: Para,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GelirGiderTablosu {
+    pub yil: i32,
+    // Gelir tarafı
+    pub onceki_yildan_devir: Vec<KasaMevcudu>,
+    pub onceki_yildan_devir_toplami: MevcutToplami,
+    pub gelirler: Vec<TabloKalemi>,
+    pub toplam_gelir: Para,
+    pub gelir_tarafi_toplami: Para,
+    // Gider tarafı
+    pub giderler: Vec<TabloKalemi>,
+    pub toplam_gider: Para,
+    pub ertesi_yila_devir: Vec<KasaMevcudu>,
+    pub ertesi_yila_devir_toplami: MevcutToplami,
+    pub gider_tarafi_toplami: Para,
+    pub virmanlar: VirmanOzeti,
+    /// Gelir tarafı = gider tarafı (kur farkı dahil). Yanlışsa silinmiş/eksik kayıt vardır.
+    pub dengeli: bool,
+}
+
+/// Defter kayıtlarını tür bazında toplar (sütun sırasıyla aynı).
+fn kalemlere_topla(kayitlar: &[DefterKaydi]) -> Vec<TabloKalemi> {
+    let (kategoriler, indeksler) = kategori_sutunlari(kayitlar);
+    let mut tutarlar = vec![Para::SIFIR; kategoriler.len()];
+    for (k, i) in kayitlar.iter().zip(indeksler) {
+        tutarlar[i] += k.tutar;
+    }
+    kategoriler
+        .into_iter()
+        .zip(tutarlar)
+        .map(|(kalem, tutar)| TabloKalemi { kalem, tutar })
+        .collect()
+}
+
+pub(crate) fn kasa_mevcutlari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tarih: &str,
+) -> Result<Vec<KasaMevcudu>, String> {
+    #[derive(QueryableByName)]
+    struct KasaRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_adi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_tipi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        para_birimi: String,
+    }
+
+    let kasalar: Vec<KasaRow> = diesel::sql_query(
+        "SELECT id, kasa_adi, COALESCE(kasa_tipi, 'Nakit') as kasa_tipi, COALESCE(para_birimi, 'TRY') as para_birimi
+         FROM kasalar WHERE tenant_id = ?1 ORDER BY kasa_adi"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let mut mevcutlar = Vec::with_capacity(kasalar.len());
+    for k in kasalar {
+        let tutar = crate::commands::mali::kasa_bakiyesi_tarihte(conn, &k.id, tarih)
+            .map_err(|e| e.to_string())?;
+        let tl_tutar = tl_karsiligi(conn, tenant_id, &k.para_birimi, tutar, tarih)?;
+        mevcutlar.push(KasaMevcudu {
+            kasa_id: k.id,
+            kasa_adi: k.kasa_adi,
+            kasa_tipi: k.kasa_tipi,
+            para_birimi: k.para_birimi,
+            tutar,
+            tl_tutar,
+        });
+    }
+    Ok(mevcutlar)
+}
+
+/// Yılın virmanı; tutar TL karşılığıdır (iki taraf da aynı TL değeriyle hareket eder).
+struct VirmanKaydi {
+    kaynak_kasa_id: String,
+    hedef_kasa_id: String,
+    kaynak_tipi: String,
+    hedef_tipi: String,
+    tl_tutar: Para,
+}
+
+fn virman_kayitlari(conn: &mut SqliteConnection, tenant_id: &str, yil: i32) -> Result<Vec<VirmanKaydi>, String> {
+    #[derive(QueryableByName)]
+    struct VirmanRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kaynak_kasa_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hedef_kasa_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kaynak_tipi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hedef_tipi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kaynak_para_birimi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hedef_para_birimi: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        hedef_tutar: Para,
+    }
+
+    let (bas, son) = yil_araligi(yil);
+    let satirlar: Vec<VirmanRow> = diesel::sql_query(
+        "SELECT substr(v.tarih, 1, 10) as tarih, v.kaynak_kasa_id, v.hedef_kasa_id,
+                COALESCE(kk.kasa_tipi, 'Nakit') as kaynak_tipi, COALESCE(hk.kasa_tipi, 'Nakit') as hedef_tipi,
+                COALESCE(v.kaynak_para_birimi, kk.para_birimi, 'TRY') as kaynak_para_birimi,
+                COALESCE(v.hedef_para_birimi, hk.para_birimi, 'TRY') as hedef_para_birimi,
+                v.tutar, COALESCE(v.hedef_tutar, v.tutar) as hedef_tutar
+         FROM virmanlar v
+         LEFT JOIN kasalar kk ON kk.id = v.kaynak_kasa_id
+         LEFT JOIN kasalar hk ON hk.id = v.hedef_kasa_id
+         WHERE v.tenant_id = ?1 AND (v.is_deleted IS NULL OR v.is_deleted = 0)
+           AND v.tarih >= ?2 AND v.tarih < ?3"
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&bas)
+    .bind::<diesel::sql_types::Text, _>(&son)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let mut virmanlar = Vec::with_capacity(satirlar.len());
+    for v in satirlar {
+        // TL tarafı varsa kayıttaki tutar esastır; iki taraf da dövizliyse kaynak tutarı çevrilir
+        let tl_tutar = if v.kaynak_para_birimi == "TRY" {
+            v.tutar
+        } else if v.hedef_para_birimi == "TRY" {
+            v.hedef_tutar
+        } else {
+            tl_karsiligi(conn, tenant_id, &v.kaynak_para_birimi, v.tutar, &v.tarih)?
+        };
+        virmanlar.push(VirmanKaydi {
+            kaynak_kasa_id: v.kaynak_kasa_id,
+            hedef_kasa_id: v.hedef_kasa_id,
+            kaynak_tipi: v.kaynak_tipi,
+            hedef_tipi: v.hedef_tipi,
+            tl_tutar,
+        });
+    }
+    Ok(virmanlar)
+}
+
+fn virman_ozeti(virmanlar: &[VirmanKaydi], kur_farklari: &[KurFarki]) -> VirmanOzeti {
+    let mut ozet = VirmanOzeti::default();
+    for v in virmanlar {
+        match (v.kaynak_tipi == "Banka", v.hedef_tipi == "Banka") {
+            (false, true) => ozet.bankaya_yatirilan += v.tl_tutar,
+            (true, false) => ozet.bankadan_cekilen += v.tl_tutar,
+            (false, false) => ozet.kasalar_arasi += v.tl_tutar,
+            (true, true) => ozet.bankalar_arasi += v.tl_tutar,
+        }
+    }
+    ozet.kur_farki = kur_farklari.iter().map(|k| k.tutar).sum();
+    ozet
+}
+
+/// Dövizli kasaların yıl içi gelir, gider ve virmanlarının TL karşılığı netleri (kasa_id → net).
+fn dovizli_kasa_akislari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+    mevcutlar: &[KasaMevcudu],
+    virmanlar: &[VirmanKaydi],
+) -> Result<HashMap<String, Para>, String> {
+    #[derive(QueryableByName)]
+    struct HareketRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+    }
+
+    let (bas, son) = yil_araligi(yil);
+    let mut akislar = HashMap::new();
+    for m in mevcutlar.iter().filter(|m| m.para_birimi != "TRY") {
+        // Giderler eksi işaretli; çeviri defter kayıtlarıyla aynı tarih ve kurla yapılır
+        let hareketler: Vec<HareketRow> = diesel::sql_query(
+            "SELECT substr(tarih, 1, 10) as tarih, tutar FROM gelirler
+              WHERE kasa_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0) AND tarih >= ?2 AND tarih < ?3
+             UNION ALL
+             SELECT substr(tarih, 1, 10), -tutar FROM giderler
+              WHERE kasa_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0) AND tarih >= ?2 AND tarih < ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&m.kasa_id)
+        .bind::<diesel::sql_types::Text, _>(&bas)
+        .bind::<diesel::sql_types::Text, _>(&son)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+
+        let mut net = Para::SIFIR;
+        for h in hareketler {
+            net += tl_karsiligi(conn, tenant_id, &m.para_birimi, h.tutar, &h.tarih)?;
+        }
+        for v in virmanlar {
+            if v.hedef_kasa_id == m.kasa_id {
+                net += v.tl_tutar;
+            }
+            if v.kaynak_kasa_id == m.kasa_id {
+                net -= v.tl_tutar;
+            }
+        }
+        akislar.insert(m.kasa_id.clone(), net);
+    }
+    Ok(akislar)
+}
+
+/// Dövizli kasa başına değerleme farkı = yıl sonu TL − yıl başı TL − yıl içi akışların TL karşılığı.
+pub(crate) fn kur_farki_hesapla(
+    onceki: &[KasaMevcudu],
+    ertesi: &[KasaMevcudu],
+    akislar: &HashMap<String, Para>,
+) -> Vec<KurFarki> {
+    ertesi
+        .iter()
+        .filter(|m| m.para_birimi != "TRY")
+        .filter_map(|m| {
+            let baslangic = onceki.iter().find(|o| o.kasa_id == m.kasa_id).map(|o| o.tl_tutar).unwrap_or_default();
+            let akis = akislar.get(&m.kasa_id).copied().unwrap_or_default();
+            let tutar = m.tl_tutar - baslangic - akis;
+            (!tutar.is_zero()).then(|| KurFarki {
+                kasa_id: m.kasa_id.clone(),
+                kasa_adi: m.kasa_adi.clone(),
+                para_birimi: m.para_birimi.clone(),
+                tutar,
+            })
+        })
+        .collect()
+}
+
+/// Yılın kur farkları (dövizli kasa başına)
+pub(crate) fn kur_farklari(conn: &mut SqliteConnection, tenant_id: &str, yil: i32) -> Result<Vec<KurFarki>, String> {
+    let (bas, son) = yil_araligi(yil);
+    let onceki = kasa_mevcutlari(conn, tenant_id, &bas)?;
+    let ertesi = kasa_mevcutlari(conn, tenant_id, &son)?;
+    let virmanlar = virman_kayitlari(conn, tenant_id, yil)?;
+    let akislar = dovizli_kasa_akislari(conn, tenant_id, yil, &ertesi, &virmanlar)?;
+    Ok(kur_farki_hesapla(&onceki, &ertesi, &akislar))
+}
+
+pub(crate) fn gelir_gider_tablosu_hazirla(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+) -> Result<GelirGiderTablosu, String> {
+    let (bas, son) = yil_araligi(yil);
+    let (gelir_kayitlari, gider_kayitlari) = defter_kayitlari(conn, tenant_id, yil)?;
+
+    let mut gelirler = kalemlere_topla(&gelir_kayitlari);
+    let mut giderler = kalemlere_topla(&gider_kayitlari);
+
+    let onceki_yildan_devir = kasa_mevcutlari(conn, tenant_id, &bas)?;
+    let ertesi_yila_devir = kasa_mevcutlari(conn, tenant_id, &son)?;
+    let virman_listesi = virman_kayitlari(conn, tenant_id, yil)?;
+    let akislar = dovizli_kasa_akislari(conn, tenant_id, yil, &ertesi_yila_devir, &virman_listesi)?;
+    let virmanlar = virman_ozeti(&virman_listesi, &kur_farki_hesapla(&onceki_yildan_devir, &ertesi_yila_devir, &akislar));
+
+    // Dövizli kasaların net değerleme farkı: pozitif ise gelir, negatif ise gider kalemi
+    if virmanlar.kur_farki.is_positive() {
+        gelirler.push(TabloKalemi { kalem: "Kur Farkı Gelirleri".to_string(), tutar: virmanlar.kur_farki });
+    } else if virmanlar.kur_farki.is_negative() {
+        giderler.push(TabloKalemi { kalem: "Kur Farkı Giderleri".to_string(), tutar: virmanlar.kur_farki.abs() });
+    }
+
+    let toplam_gelir: Para = gelirler.iter().map(|k| k.tutar).sum();
+    let toplam_gider: Para = giderler.iter().map(|k| k.tutar).sum();
+
+    let onceki_yildan_devir_toplami = MevcutToplami::hesapla(&onceki_yildan_devir);
+    let ertesi_yila_devir_toplami = MevcutToplami::hesapla(&ertesi_yila_devir);
+
+    let gelir_tarafi_toplami = onceki_yildan_devir_toplami.toplam + toplam_gelir;
+    let gider_tarafi_toplami = toplam_gider + ertesi_yila_devir_toplami.toplam;
+
+    Ok(GelirGiderTablosu {
+        yil,
+        onceki_yildan_devir,
+        onceki_yildan_devir_toplami,
+        gelirler,
+        toplam_gelir,
+        gelir_tarafi_toplami,
+        giderler,
+        toplam_gider,
+        ertesi_yila_devir,
+        ertesi_yila_devir_toplami,
+        gider_tarafi_toplami,
+        virmanlar,
+        dengeli: gelir_tarafi_toplami == gider_tarafi_toplami,
+    })
+}
+
+// ============================================================================
+// KOMUTLAR
+// ============================================================================
+
+#[tauri::command]
+pub async fn get_isletme_hesabi_defteri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    yil: i32,
+    sayfa_satir_sayisi: Option<usize>,
+) -> Result<IsletmeHesabiDefteri, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    isletme_defteri_hazirla(&mut conn, &tenant_id_param, yil, sayfa_satir_sayisi)
+}
+
+#[tauri::command]
+pub async fn get_gelir_gider_tablosu(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    yil: i32,
+) -> Result<GelirGiderTablosu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    gelir_gider_tablosu_hazirla(&mut conn, &tenant_id_param, yil)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kayit(tarih: &str, kategori: &str, kurus: i64) -> DefterKaydi {
+        DefterKaydi {
+            tarih: tarih.to_string(),
+            belge_no: None,
+            aciklama: None,
+            kasa_adi: "Ana Kasa".to_string(),
+            kategori: kategori.to_string(),
+            tutar: Para::from_kurus(kurus),
+            para_birimi: "TRY".to_string(),
+        }
+    }
+
+    #[test]
+    fn sayfa_nakilleri_tasinir() {
+        let gelirler: Vec<DefterKaydi> = (1..=7)
+            .map(|i| kayit(&format!("2026-01-{:02}", i), if i % 2 == 0 { "Bağış" } else { "Aidat" }, i * 1_000))
+            .collect();
+        let giderler = vec![kayit("2026-01-05", "Kira", 50_000)];
+
+        let defter = isletme_defteri_olustur(2026, &gelirler, &giderler, 3);
+
+        // 7 gelir / 3 satır → 3 sayfa; gider tarafı 1. sayfada, diğer sayfalar boş ama nakilli
+        assert_eq!(defter.sayfalar.len(), 3);
+        assert_eq!(defter.gelir_kategorileri, vec!["Aidat", "Bağış"]);
+        assert_eq!(defter.sayfalar[2].gelir.satirlar.len(), 1);
+        assert_eq!(defter.sayfalar[2].gelir.satirlar[0].sira_no, 7);
+        assert!(defter.sayfalar[1].gider.satirlar.is_empty());
+        assert_eq!(defter.sayfalar[2].gider.sonraki_sayfaya_nakil.toplam, Para::from_kurus(50_000));
+
+        for pencere in defter.sayfalar.windows(2) {
+            assert_eq!(pencere[0].gelir.sonraki_sayfaya_nakil, pencere[1].gelir.onceki_sayfadan_nakil);
+            assert_eq!(pencere[0].gider.sonraki_sayfaya_nakil, pencere[1].gider.onceki_sayfadan_nakil);
+        }
+
+        let son = &defter.sayfalar[2].gelir.sonraki_sayfaya_nakil;
+        assert_eq!(son.toplam, defter.toplam_gelir);
+        assert_eq!(son.toplam, Para::from_kurus(28_000));
+        assert_eq!(son.kategoriler, vec![Para::from_kurus(16_000), Para::from_kurus(12_000)]);
+        assert_eq!(defter.donem_farki, Para::from_kurus(-22_000));
+    }
+
+    #[test]
+    fn ayni_tur_farkli_yazim_tek_sutun() {
+        let gelirler = vec![
+            kayit("2026-02-01", "Aidat", 100),
+            kayit("2026-02-02", "AİDAT", 200),
+            kayit("2026-02-03", "aidat", 300),
+        ];
+        let defter = isletme_defteri_olustur(2026, &gelirler, &[], 10);
+        assert_eq!(defter.gelir_kategorileri, vec!["Aidat"]);
+        assert_eq!(defter.sayfalar.len(), 1);
+        assert_eq!(defter.sayfalar[0].gelir.sayfa_toplami.kategoriler, vec![Para::from_kurus(600)]);
+
+        let kalemler = kalemlere_topla(&gelirler);
+        assert_eq!(kalemler.len(), 1);
+        assert_eq!(kalemler[0].tutar, Para::from_kurus(600));
+    }
+
+    fn mevcut(kasa_id: &str, kasa_tipi: &str, para_birimi: &str, tutar: i64, tl_tutar: i64) -> KasaMevcudu {
+        KasaMevcudu {
+            kasa_id: kasa_id.to_string(),
+            kasa_adi: kasa_id.to_string(),
+            kasa_tipi: kasa_tipi.to_string(),
+            para_birimi: para_birimi.to_string(),
+            tutar: Para::from_kurus(tutar),
+            tl_tutar: Para::from_kurus(tl_tutar),
+        }
+    }
+
+    #[test]
+    fn dovizli_kasa_tl_karsiligiyla_toplanir() {
+        let mevcutlar = vec![
+            mevcut("k1", "Nakit", "TRY", 100_000, 100_000),
+            // 100 EUR, 35,00 kurla 3.500 TL
+            mevcut("k2", "Banka", "EUR", 10_000, 350_000),
+            mevcut("k3", "Banka", "TRY", 20_000, 20_000),
+        ];
+        let toplam = MevcutToplami::hesapla(&mevcutlar);
+        assert_eq!(toplam.kasa, Para::from_kurus(100_000));
+        assert_eq!(toplam.banka, Para::from_kurus(370_000));
+        assert_eq!(toplam.toplam, Para::from_kurus(470_000));
+    }
+
+    #[test]
+    fn kur_farki_akislarla_aciklanamayan_tl_degisimidir() {
+        // TL kasadan EUR bankaya 1.600 TL'lik virmanla 50 EUR girdi. EUR banka: yıl başı 100 EUR
+        // (30 TL) = 3.000 TL, yıl sonu 150 EUR (36 TL) = 5.400 TL → 800 TL kur farkı geliri.
+        // USD kasanın kuru değişmedi: fark yok.
+        let onceki = vec![
+            mevcut("k1", "Nakit", "TRY", 200_000, 200_000),
+            mevcut("k2", "Banka", "EUR", 10_000, 300_000),
+            mevcut("k3", "Nakit", "USD", 1_000, 40_000),
+        ];
+        let ertesi = vec![
+            mevcut("k1", "Nakit", "TRY", 40_000, 40_000),
+            mevcut("k2", "Banka", "EUR", 15_000, 540_000),
+            mevcut("k3", "Nakit", "USD", 1_000, 40_000),
+        ];
+        let virmanlar = vec![VirmanKaydi {
+            kaynak_kasa_id: "k1".to_string(),
+            hedef_kasa_id: "k2".to_string(),
+            kaynak_tipi: "Nakit".to_string(),
+            hedef_tipi: "Banka".to_string(),
+            tl_tutar: Para::from_kurus(160_000),
+        }];
+        let akislar = HashMap::from([("k2".to_string(), Para::from_kurus(160_000)), ("k3".to_string(), Para::SIFIR)]);
+
+        let farklar = kur_farki_hesapla(&onceki, &ertesi, &akislar);
+        assert_eq!(farklar.len(), 1);
+        assert_eq!(farklar[0].kasa_id, "k2");
+        assert_eq!(farklar[0].tutar, Para::from_kurus(80_000));
+
+        let ozet = virman_ozeti(&virmanlar, &farklar);
+        assert_eq!(ozet.bankaya_yatirilan, Para::from_kurus(160_000));
+        assert_eq!(ozet.kur_farki, Para::from_kurus(80_000));
+
+        // Tablo dengesi (gelir/gider yok): yıl başı + kur farkı = yıl sonu
+        let basi = MevcutToplami::hesapla(&onceki).toplam;
+        let sonu = MevcutToplami::hesapla(&ertesi).toplam;
+        assert_eq!(basi + ozet.kur_farki, sonu);
+    }
+}
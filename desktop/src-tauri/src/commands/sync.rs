@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use tauri::State;
 use reqwest;
 use std::collections::HashMap;
+use crate::db::donem_kilidi;
 
 type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
@@ -481,9 +482,72 @@ impl From<diesel::result::Error> for ApplyError {
     }
 }
 
+/// Tek bir sunucu değişikliğini uygular (silme → tombstone, diğerleri upsert) ve
+/// bakiyesi yeniden hesaplanacak kasaları `affected_kasalar`'a ekler.
+/// apply_sync_changes ve dönem kilidi ihlalinin sonradan uygulanması bunu kullanır.
+pub(crate) fn uzak_degisikligi_uygula(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    table_name: &str,
+    record_id: &str,
+    change: &serde_json::Value,
+    affected_kasalar: &mut std::collections::HashSet<String>,
+) -> Result<(), String> {
+    let action = change
+        .get("action")
+        .or_else(|| change.get("operation"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("update");
+    let data = change
+        .get("data")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+
+    let is_delete = action == "delete"
+        || data
+            .get("is_deleted")
+            .map(|v| v.as_i64() == Some(1) || v.as_bool() == Some(true))
+            .unwrap_or(false);
+
+    let server_version = change
+        .get("version")
+        .and_then(|v| v.as_i64())
+        .or_else(|| data.get("version").and_then(|v| v.as_i64()));
+
+    if is_delete {
+        crate::db::outbox::apply_remote_delete(conn, tenant_id, table_name, record_id, server_version)?;
+    } else {
+        crate::db::outbox::apply_remote_upsert(conn, tenant_id, table_name, record_id, &data)?;
+    }
+
+    // Kasa bakiyesini etkileyen kayıtları topla.
+    match table_name {
+        "gelirler" | "giderler" => {
+            if let Some(kid) = data.get("kasa_id").and_then(|v| v.as_str()) {
+                affected_kasalar.insert(kid.to_string());
+            }
+        }
+        "virmanlar" => {
+            for f in ["kaynak_kasa_id", "hedef_kasa_id"] {
+                if let Some(kid) = data.get(f).and_then(|v| v.as_str()) {
+                    affected_kasalar.insert(kid.to_string());
+                }
+            }
+        }
+        "kasalar" => {
+            affected_kasalar.insert(record_id.to_string());
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
 /// Sunucudan gelen değişiklikleri TEK transaction içinde uygular.
 /// - Yerel bekleyen değişikliği olan kayıtlar atlanır (çatışma push'ta
 ///   versiyon kontrolüyle çözülür; pull yerel düzenlemeyi ezmez).
+/// - Kilitli muhasebe dönemine düşen değişiklikler uygulanmaz,
+///   donem_kilidi_ihlalleri'ne yönetici kararı için kaydedilir.
 /// - Silmeler tombstone (is_deleted=1) olarak uygulanır.
 /// - Uygulama sonrası etkilenen kasaların bakiyesi baz kayıtlardan
 ///   yeniden hesaplanır (türetilmiş alanlar sync edilmez).
@@ -500,10 +564,16 @@ pub fn apply_sync_changes(
 
     let mut applied: i32 = 0;
     let mut skipped: i32 = 0;
+    let mut flagged: i32 = 0;
+    let mut onarim_bekleyen: i32 = 0;
+    // Sunucudan gelen değişikliğin yazarı bu cihazda bilinmez: kullanıcı boş kalır
+    let mut denetim = crate::db::denetim::Denetim::yeni(None, None, "apply_sync_changes");
 
     let tx_result = conn.transaction::<_, ApplyError, _>(|conn| {
         let mut affected_kasalar: std::collections::HashSet<String> =
             std::collections::HashSet::new();
+        // Yevmiyeye kaynak olan kayıtlar (yevmiye sync dışıdır, yerelde üretilir).
+        let mut yevmiye_kaynaklari: Vec<(String, String)> = Vec::new();
 
         for change in &changes {
             let table_name = change
@@ -540,72 +610,72 @@ pub fn apply_sync_changes(
                 continue;
             }
 
-            let is_delete = action == "delete"
-                || data
-                    .get("is_deleted")
-                    .map(|v| v.as_i64() == Some(1) || v.as_bool() == Some(true))
-                    .unwrap_or(false);
+            // Üyenin aynı dönemi için başka kimlikli yerel aidat var: yerel tekillik kısıtı
+            // bütün uygulamayı düşürmesin diye atlanır (sunucu da bu kaydı reddeder).
+            if table_name == "aidat_takip"
+                && crate::db::aidat_tahakkuku::uzak_aidat_cakisiyor_mu(conn, &tenant_id_param, record_id, &data)
+                    .map_err(ApplyError::Msg)?
+            {
+                skipped += 1;
+                println!("⏭️ Aynı dönem için yerel aidat var, atlandı: {} / {}", table_name, record_id);
+                continue;
+            }
 
-            let server_version = change
-                .get("version")
-                .and_then(|v| v.as_i64())
-                .or_else(|| data.get("version").and_then(|v| v.as_i64()));
+            // Kilitli döneme düşen değişiklik: uygulanmaz, ihlal olarak işaretlenir.
+            if let Some(kilit) =
+                donem_kilidi::uzak_degisiklik_kilidi(conn, &tenant_id_param, table_name, record_id, &data)
+                    .map_err(ApplyError::Msg)?
+            {
+                donem_kilidi::ihlal_kaydet(conn, &tenant_id_param, &kilit, table_name, record_id, action, change)
+                    .map_err(ApplyError::Msg)?;
+                flagged += 1;
+                println!("🔒 Kilitli dönem ({}), işaretlendi: {} / {}", kilit.donem(), table_name, record_id);
+                continue;
+            }
 
-            if is_delete {
-                crate::db::outbox::apply_remote_delete(
-                    conn,
-                    &tenant_id_param,
-                    table_name,
-                    record_id,
-                    server_version,
-                )
+            denetim.izle(conn, &tenant_id_param, table_name, record_id).map_err(ApplyError::Msg)?;
+            uzak_degisikligi_uygula(conn, &tenant_id_param, table_name, record_id, change, &mut affected_kasalar)
                 .map_err(ApplyError::Msg)?;
-            } else {
-                crate::db::outbox::apply_remote_upsert(
-                    conn,
-                    &tenant_id_param,
-                    table_name,
-                    record_id,
-                    &data,
-                )
-                .map_err(ApplyError::Msg)?;
-            }
 
-            // Kasa bakiyesini etkileyen kayıtları topla.
-            match table_name {
-                "gelirler" | "giderler" => {
-                    if let Some(kid) = data.get("kasa_id").and_then(|v| v.as_str()) {
-                        affected_kasalar.insert(kid.to_string());
-                    }
-                }
-                "virmanlar" => {
-                    for f in ["kaynak_kasa_id", "hedef_kasa_id"] {
-                        if let Some(kid) = data.get(f).and_then(|v| v.as_str()) {
-                            affected_kasalar.insert(kid.to_string());
-                        }
-                    }
-                }
-                "kasalar" => {
-                    affected_kasalar.insert(record_id.to_string());
-                }
-                _ => {}
+            if crate::db::yevmiye::KAYNAK_TABLOLAR.contains(&table_name) {
+                yevmiye_kaynaklari.push((table_name.to_string(), record_id.to_string()));
             }
 
             applied += 1;
         }
 
         // Türetilmiş bakiyeler her zaman baz kayıtlardan yeniden hesaplanır.
+        // Devir tarihi eksik kasa sync'i durdurmaz: bakiyesi yazılmaz, onarım bekler
+        // (check_kasa_bakiye_tutarliligi devir_tarihi_eksik olarak raporlar).
         for kasa_id in &affected_kasalar {
+            if crate::commands::mali::devir_tarihi_eksik(conn, kasa_id)? {
+                onarim_bekleyen += 1;
+                println!("⚠️ Kasanın devir tarihi eksik, bakiye onarım bekliyor: {}", kasa_id);
+                continue;
+            }
             crate::commands::mali::update_kasa_bakiye(conn, kasa_id)?;
         }
 
+        for (tablo, kayit_id) in &yevmiye_kaynaklari {
+            // Açılış fişi de devir kesim tarihine bağlıdır; kasa onarılınca yeniden işlenir
+            if tablo == "kasalar" && crate::commands::mali::devir_tarihi_eksik(conn, kayit_id)? {
+                continue;
+            }
+            crate::db::yevmiye::kaydi_isle(conn, &tenant_id_param, tablo, kayit_id)
+                .map_err(ApplyError::Msg)?;
+        }
+
+        denetim.kaydet(conn).map_err(ApplyError::Msg)?;
         Ok(())
     });
 
     match tx_result {
         Ok(()) => {
-            if skipped > 0 {
-                println!("ℹ️ apply_sync_changes: {} uygulandı, {} yerel bekleyen nedeniyle atlandı", applied, skipped);
+            if skipped > 0 || flagged > 0 || onarim_bekleyen > 0 {
+                println!(
+                    "ℹ️ apply_sync_changes: {} uygulandı, {} atlandı (yerel bekleyen ya da aynı dönem aidatı), {} kilitli dönem nedeniyle işaretlendi, {} kasa devir tarihi eksik (onarım bekliyor)",
+                    applied, skipped, flagged, onarim_bekleyen
+                );
             }
             Ok(applied)
         }
@@ -0,0 +1,206 @@
+// Aidat borcu taksitlendirme: plan önizleme/oluşturma, taksit tahsilatı ve bozuk plan raporu (bkz. db::taksit)
+use tauri::State;
+use diesel::prelude::*;
+use chrono::{Local, NaiveDate};
+use serde::Deserialize;
+use crate::state::AppState;
+use crate::commands::aidat::aidat_odemesi_gelirle;
+use crate::db::outbox::TxError;
+use crate::db::para::Para;
+use crate::db::taksit::{self, BozukPlan, TaksitOnizleme, TaksitPlani, TaksitPlaniDetay};
+
+#[derive(Debug, Deserialize)]
+pub struct TaksitPlaniRequest {
+    pub uye_id: String,
+    pub aidat_idleri: Vec<String>,
+    pub taksit_sayisi: i32,
+    pub ilk_vade: String,
+    pub aralik_ay: Option<i32>,
+    pub tolerans_gun: Option<i32>,
+    pub aciklama: Option<String>,
+}
+
+fn tarih_coz(tarih: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(tarih.trim(), "%Y-%m-%d").map_err(|_| format!("Geçersiz tarih: {}", tarih))
+}
+
+#[tauri::command]
+pub fn get_taksit_planlari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: Option<String>,
+    durum: Option<String>,
+) -> Result<Vec<TaksitPlani>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    taksit::planlari_getir(&mut conn, &tenant_id_param, uye_id.as_deref(), durum.as_deref())
+}
+
+#[tauri::command]
+pub fn get_taksit_plani(
+    state: State<AppState>,
+    tenant_id_param: String,
+    plan_id: String,
+) -> Result<TaksitPlaniDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    taksit::plan_getir(&mut conn, &tenant_id_param, &plan_id)
+}
+
+/// Kaydetmeden taksit takvimi (seçilen aidatların güncel kalanı üzerinden)
+#[tauri::command]
+pub fn taksit_plani_onizle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    data: TaksitPlaniRequest,
+) -> Result<Vec<TaksitOnizleme>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let borclar = taksit::plan_borclari(&mut conn, &tenant_id_param, &data.uye_id, &data.aidat_idleri)?;
+    let toplam: Para = borclar.iter().map(|(_, kalan)| *kalan).sum();
+    taksit::takvim(toplam, data.taksit_sayisi, tarih_coz(&data.ilk_vade)?, data.aralik_ay.unwrap_or(1))
+}
+
+#[tauri::command]
+pub fn taksit_plani_olustur(
+    state: State<AppState>,
+    tenant_id_param: String,
+    data: TaksitPlaniRequest,
+) -> Result<TaksitPlaniDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let ilk_vade = tarih_coz(&data.ilk_vade)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let olusturan = state.current_user.lock().unwrap().as_ref().map(|u| u.full_name.clone());
+    let mut denetim = state.denetim("taksit_plani_olustur");
+    let plan_id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let plan_id = taksit::plan_olustur(
+                conn,
+                &mut denetim,
+                &tenant_id_param,
+                &data.uye_id,
+                &data.aidat_idleri,
+                data.taksit_sayisi,
+                ilk_vade,
+                data.aralik_ay.unwrap_or(1),
+                data.tolerans_gun.unwrap_or(0),
+                data.aciklama.as_deref().map(str::trim).filter(|s| !s.is_empty()),
+                olusturan.as_deref(),
+            )
+            .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(plan_id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    taksit::plan_getir(&mut conn, &tenant_id_param, &plan_id)
+}
+
+/// Taksit tahsilatı: tutar planın aidatlarına en eskiden başlayarak
+/// add_aidat_odeme_with_gelir akışıyla (aidat başına bir gelir) dağıtılır;
+/// taksitlere işlenmesi o akışın içinde olur
+#[tauri::command]
+pub fn taksit_odemesi_al(
+    state: State<AppState>,
+    tenant_id_param: String,
+    plan_id: String,
+    odeme_tutari: Para,
+    kasa_id: String,
+) -> Result<TaksitPlaniDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    if !odeme_tutari.is_positive() {
+        return Err("Ödeme tutarı sıfırdan büyük olmalıdır".to_string());
+    }
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let detay = taksit::plan_getir(&mut conn, &tenant_id_param, &plan_id)?;
+    if detay.plan.durum != "aktif" {
+        return Err("Yalnızca aktif plana ödeme alınabilir".to_string());
+    }
+    let borclar: Vec<(String, Para)> = detay.aidatlar.iter().map(|a| (a.aidat_id.clone(), a.kalan)).collect();
+    let kalan: Para = borclar.iter().map(|(_, k)| *k).sum();
+    if odeme_tutari > kalan {
+        return Err(format!("Ödeme planın kalan borcunu aşıyor (kalan: {})", kalan.tr_format()));
+    }
+
+    let kullanici_id = state.current_user.lock().unwrap().as_ref().map(|u| u.id.clone());
+    let mut denetim = state.denetim("taksit_odemesi_al");
+    conn.transaction::<_, TxError, _>(|conn| {
+        for (aidat_id, tutar) in crate::db::avans::dagit(odeme_tutari, &borclar) {
+            aidat_odemesi_gelirle(conn, &mut denetim, &tenant_id_param, &aidat_id, tutar, &kasa_id, kullanici_id.as_deref())?;
+        }
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    taksit::plan_getir(&mut conn, &tenant_id_param, &plan_id)
+}
+
+/// Bozuk planlar raporu; tarih verilmezse bugün
+#[tauri::command]
+pub fn get_bozuk_taksit_planlari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    tarih: Option<String>,
+) -> Result<Vec<BozukPlan>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let bugun = match tarih.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(t) => tarih_coz(t)?,
+        None => Local::now().date_naive(),
+    };
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    taksit::bozuk_planlar(&mut conn, &tenant_id_param, bugun)
+}
+
+/// Aktif planı bozuldu / iptal olarak kapatır (yönetici)
+#[tauri::command]
+pub fn taksit_plani_kapat(
+    state: State<AppState>,
+    tenant_id_param: String,
+    plan_id: String,
+    durum: String,
+    neden: String,
+) -> Result<TaksitPlaniDetay, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    state.verify_admin()?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let bugun = Local::now().format("%Y-%m-%d").to_string();
+    let mut denetim = state.denetim("taksit_plani_kapat");
+    conn.transaction::<_, TxError, _>(|conn| {
+        taksit::plani_kapat(conn, &mut denetim, &tenant_id_param, &plan_id, &durum, &neden, &bugun)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    taksit::plan_getir(&mut conn, &tenant_id_param, &plan_id)
+}
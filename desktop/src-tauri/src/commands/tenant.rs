@@ -4,6 +4,12 @@ use diesel::prelude::*;
 use crate::state::AppState;
 use uuid::Uuid;
 use bcrypt::{hash, DEFAULT_COST};
+use base64::{Engine as _, engine::general_purpose};
+use crate::db::pdf;
+use crate::db::denetim;
+
+/// Logo her belgeye olduğu gibi gömüldüğünden boyutu sınırlı tutulur
+const LOGO_AZAMI_BOYUT: usize = 512 * 1024;
 
 // ============================================================================
 // REQUEST/RESPONSE STRUCTS
@@ -390,19 +396,83 @@ pub fn update_tenant(
 
     // Basit güncelleme (sadece name için örnek)
     if let Some(ref name) = data.name {
-        diesel::sql_query(
-            "UPDATE tenants SET name = ?1, updated_at = ?2 WHERE id = ?3"
-        )
-        .bind::<diesel::sql_types::Text, _>(name)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id)
-        .execute(&mut conn)
-        .map_err(|e| format!("Güncelleme başarısız: {}", e))?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("update_tenant"), &tenant_id, "tenants", &tenant_id, |conn| {
+            diesel::sql_query(
+                "UPDATE tenants SET name = ?1, updated_at = ?2 WHERE id = ?3"
+            )
+            .bind::<diesel::sql_types::Text, _>(name)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id)
+            .execute(conn)
+            .map_err(|e| format!("Güncelleme başarısız: {}", e))
+        })?;
     }
 
     Ok("Dernek bilgileri güncellendi".to_string())
 }
 
+/// Belgelerde kullanılan logoyu getir (base64, yoksa None)
+#[tauri::command]
+pub fn get_tenant_logo(
+    tenant_id: String,
+    state: State<AppState>,
+) -> Result<Option<String>, String> {
+    state.verify_tenant_access(&tenant_id)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    tenant_logosu(&mut conn, &tenant_id)
+}
+
+/// Makbuz ve ekstre başlığındaki logoyu ayarla; None logoyu kaldırır
+#[tauri::command]
+pub fn set_tenant_logo(
+    tenant_id: String,
+    logo_base64: Option<String>,
+    state: State<AppState>,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id)?;
+    state.verify_admin()?;
+
+    let logo = match logo_base64.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(veri) => {
+            // "data:image/png;base64,..." biçimi de kabul edilir
+            let veri = veri.split_once(',').map(|(_, v)| v).unwrap_or(veri);
+            let baytlar = general_purpose::STANDARD
+                .decode(veri)
+                .map_err(|_| "Logo base64 olarak okunamadı".to_string())?;
+            if baytlar.len() > LOGO_AZAMI_BOYUT {
+                return Err(format!("Logo en fazla {} KB olabilir", LOGO_AZAMI_BOYUT / 1024));
+            }
+            pdf::gorsel_coz(&baytlar)?;
+            Some(general_purpose::STANDARD.encode(&baytlar))
+        }
+        None => None,
+    };
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim::kayitli_yazim(&mut conn, state.denetim("set_tenant_logo"), &tenant_id, "tenants", &tenant_id, |conn| {
+        let guncellenen = diesel::sql_query("UPDATE tenants SET logo = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&logo)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        if guncellenen == 0 {
+            return Err("Dernek bulunamadı".to_string());
+        }
+        Ok(())
+    })?;
+
+    Ok(if logo.is_some() { "Logo kaydedildi" } else { "Logo kaldırıldı" }.to_string())
+}
+
 /// Tüm tenant'ları listele (superadmin için)
 #[tauri::command]
 pub fn list_tenants(
@@ -495,6 +565,25 @@ fn is_slug_available(slug: &str, conn: &mut diesel::sqlite::SqliteConnection) ->
     Ok(result.count == 0)
 }
 
+/// Dernek logosu (base64)
+pub(crate) fn tenant_logosu(
+    conn: &mut diesel::sqlite::SqliteConnection,
+    tenant_id: &str,
+) -> Result<Option<String>, String> {
+    #[derive(QueryableByName)]
+    struct LogoRow {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        logo: Option<String>,
+    }
+
+    diesel::sql_query("SELECT logo FROM tenants WHERE id = ?1")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result::<LogoRow>(conn)
+        .optional()
+        .map(|r| r.and_then(|r| r.logo))
+        .map_err(|e| e.to_string())
+}
+
 /// Şifreyi bcrypt ile hashle
 fn hash_password(password: &str) -> String {
     hash(password, DEFAULT_COST)
@@ -0,0 +1,177 @@
+// Toplantı çağrıları, davetli listesi, katılım ve üye katılım raporları (bkz. db::toplanti_katilimi)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::toplanti_katilimi::{
+    self, Davetli, DavetliGirdi, DavetGirdi, KatilimGirdi, ToplantiKatilimi, UyeKatilimGecmisi, UyeKatilimOzeti,
+};
+
+/// Toplantının çağrı bilgileri, davetlileri ve katılım sayıları
+#[tauri::command]
+pub fn get_toplanti_katilimi(
+    state: State<AppState>,
+    tenant_id_param: String,
+    toplanti_id: String,
+) -> Result<ToplantiKatilimi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    toplanti_katilimi::toplanti_katilimi(&mut conn, &tenant_id_param, &toplanti_id)
+}
+
+/// Toplantı türüne göre çağrılması gerekenleri davetli listesine ekler
+#[tauri::command]
+pub fn toplanti_davetlileri_olustur(
+    state: State<AppState>,
+    tenant_id_param: String,
+    toplanti_id: String,
+) -> Result<Vec<Davetli>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("toplanti_davetlileri_olustur");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let davetliler = toplanti_katilimi::davetlileri_olustur(conn, &mut denetim, &tenant_id_param, &toplanti_id)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(davetliler)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn toplanti_davetli_ekle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    toplanti_id: String,
+    data: DavetliGirdi,
+) -> Result<String, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("toplanti_davetli_ekle");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let id = toplanti_katilimi::davetli_ekle(conn, &mut denetim, &tenant_id_param, &toplanti_id, &data)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn toplanti_davetli_sil(state: State<AppState>, tenant_id_param: String, davetli_id: String) -> Result<(), String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("toplanti_davetli_sil");
+    conn.transaction::<_, TxError, _>(|conn| {
+        toplanti_katilimi::davetli_sil(conn, &mut denetim, &tenant_id_param, &davetli_id).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Çağrıyı kaydeder; çağrı yapılan davetli sayısı döner
+#[tauri::command]
+pub fn toplanti_davet_gonder(
+    state: State<AppState>,
+    tenant_id_param: String,
+    toplanti_id: String,
+    data: DavetGirdi,
+) -> Result<usize, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("toplanti_davet_gonder");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let sayi = toplanti_katilimi::davet_gonder(conn, &mut denetim, &tenant_id_param, &toplanti_id, &data)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(sayi)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Katılımı işaretler; genel kurul katılımı hazirun imzasından gelir
+#[tauri::command]
+pub fn toplanti_katilim_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    toplanti_id: String,
+    data: Vec<KatilimGirdi>,
+) -> Result<ToplantiKatilimi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let bugun = chrono::Local::now().date_naive();
+    let mut denetim = state.denetim("toplanti_katilim_kaydet");
+    conn.transaction::<_, TxError, _>(|conn| {
+        toplanti_katilimi::katilim_kaydet(conn, &mut denetim, &tenant_id_param, &toplanti_id, &data, bugun)
+            .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    toplanti_katilimi::toplanti_katilimi(&mut conn, &tenant_id_param, &toplanti_id)
+}
+
+/// Üyenin aralıktaki toplantı davetleri ve katılımı (varsayılan: içinde bulunulan yıl)
+#[tauri::command]
+pub fn get_uye_katilim_gecmisi(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+    baslangic: Option<String>,
+    bitis: Option<String>,
+) -> Result<UyeKatilimGecmisi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let yil = chrono::Local::now().format("%Y").to_string();
+    let baslangic = baslangic.unwrap_or_else(|| format!("{}-01-01", yil));
+    let bitis = bitis.unwrap_or_else(|| format!("{}-12-31", yil));
+    toplanti_katilimi::uye_katilim_gecmisi(&mut conn, &tenant_id_param, &uye_id, &baslangic, &bitis)
+}
+
+/// Üye bazında katılım oranları; organ verilirse yalnızca o kurulun toplantıları
+#[tauri::command]
+pub fn get_katilim_raporu(
+    state: State<AppState>,
+    tenant_id_param: String,
+    baslangic: String,
+    bitis: String,
+    organ: Option<String>,
+) -> Result<Vec<UyeKatilimOzeti>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    toplanti_katilimi::katilim_raporu(&mut conn, &tenant_id_param, &baslangic, &bitis, organ.as_deref())
+}
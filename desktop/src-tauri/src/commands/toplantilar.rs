@@ -3,6 +3,7 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize, Queryable, QueryableByName)]
@@ -109,27 +110,29 @@ pub fn create_toplanti(
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-        diesel::sql_query(
-            "INSERT INTO toplantilar (id, tenant_id, baslik, aciklama, tarih, saat, yer, toplanti_tipi, durum, katilimci_sayisi, gundem, kararlar, notlar, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
-        )
-        .bind::<diesel::sql_types::Text, _>(&new_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .bind::<diesel::sql_types::Text, _>(&data.baslik)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Text, _>(&data.tarih)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.saat)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yer)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.toplanti_tipi)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.durum)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.katilimci_sayisi)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gundem)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kararlar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("create_toplanti"), &tenant_id_param, "toplantilar", &new_id, |conn| {
+            diesel::sql_query(
+                "INSERT INTO toplantilar (id, tenant_id, baslik, aciklama, tarih, saat, yer, toplanti_tipi, durum, katilimci_sayisi, gundem, kararlar, notlar, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&new_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .bind::<diesel::sql_types::Text, _>(&data.baslik)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+            .bind::<diesel::sql_types::Text, _>(&data.tarih)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.saat)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yer)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.toplanti_tipi)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.durum)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.katilimci_sayisi)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gundem)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kararlar)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
     }
 
     let db = state.db.lock().unwrap();
@@ -156,26 +159,28 @@ pub fn update_toplanti(
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-        diesel::sql_query(
-            "UPDATE toplantilar SET baslik = ?1, aciklama = ?2, tarih = ?3, saat = ?4, yer = ?5, toplanti_tipi = ?6, durum = ?7, katilimci_sayisi = ?8, gundem = ?9, kararlar = ?10, notlar = ?11, updated_at = ?12
-             WHERE id = ?13 AND tenant_id = ?14"
-        )
-        .bind::<diesel::sql_types::Text, _>(&data.baslik)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-        .bind::<diesel::sql_types::Text, _>(&data.tarih)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.saat)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yer)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.toplanti_tipi)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.durum)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.katilimci_sayisi)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gundem)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kararlar)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-        .bind::<diesel::sql_types::Text, _>(&now)
-        .bind::<diesel::sql_types::Text, _>(&toplanti_id)
-        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("update_toplanti"), &tenant_id_param, "toplantilar", &toplanti_id, |conn| {
+            diesel::sql_query(
+                "UPDATE toplantilar SET baslik = ?1, aciklama = ?2, tarih = ?3, saat = ?4, yer = ?5, toplanti_tipi = ?6, durum = ?7, katilimci_sayisi = ?8, gundem = ?9, kararlar = ?10, notlar = ?11, updated_at = ?12
+                 WHERE id = ?13 AND tenant_id = ?14"
+            )
+            .bind::<diesel::sql_types::Text, _>(&data.baslik)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+            .bind::<diesel::sql_types::Text, _>(&data.tarih)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.saat)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.yer)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.toplanti_tipi)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.durum)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.katilimci_sayisi)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.gundem)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kararlar)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&toplanti_id)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+            .execute(conn)
+            .map_err(|e| e.to_string())
+        })?;
     }
 
     let db = state.db.lock().unwrap();
@@ -198,13 +203,15 @@ pub fn delete_toplanti(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "DELETE FROM toplantilar WHERE id = ?1 AND tenant_id = ?2"
-    )
-    .bind::<diesel::sql_types::Text, _>(&toplanti_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_toplanti"), &tenant_id_param, "toplantilar", &toplanti_id, |conn| {
+        diesel::sql_query(
+            "DELETE FROM toplantilar WHERE id = ?1 AND tenant_id = ?2"
+        )
+        .bind::<diesel::sql_types::Text, _>(&toplanti_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
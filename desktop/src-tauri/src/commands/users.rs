@@ -6,6 +6,7 @@ use diesel::prelude::*;
 use uuid::Uuid;
 use sha2::{Sha256, Digest};
 use crate::state::AppState;
+use crate::db::denetim;
 
 // ============================================================================
 // REQUEST/RESPONSE TYPES
@@ -234,22 +235,24 @@ pub async fn create_user(
     hasher.update(data.password.as_bytes());
     let password_hash = format!("{:x}", hasher.finalize());
 
-    diesel::sql_query(
-        "INSERT INTO users (id, tenant_id, email, full_name, username, phone, password_hash, role, is_active, is_superuser, created_at, updated_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1, 0, ?9, ?10)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&new_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .bind::<diesel::sql_types::Text, _>(&data.email)
-    .bind::<diesel::sql_types::Text, _>(&data.full_name)
-    .bind::<diesel::sql_types::Text, _>(&data.username)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.phone)
-    .bind::<diesel::sql_types::Text, _>(&password_hash)
-    .bind::<diesel::sql_types::Text, _>(&data.role.unwrap_or_else(|| "viewer".to_string()))
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("create_user"), &tenant_id_param, "users", &new_id, |conn| {
+        diesel::sql_query(
+            "INSERT INTO users (id, tenant_id, email, full_name, username, phone, password_hash, role, is_active, is_superuser, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1, 0, ?9, ?10)"
+        )
+        .bind::<diesel::sql_types::Text, _>(&new_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Text, _>(&data.email)
+        .bind::<diesel::sql_types::Text, _>(&data.full_name)
+        .bind::<diesel::sql_types::Text, _>(&data.username)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.phone)
+        .bind::<diesel::sql_types::Text, _>(&password_hash)
+        .bind::<diesel::sql_types::Text, _>(&data.role.unwrap_or_else(|| "viewer".to_string()))
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     // Yeni kullanıcıyı getir
     #[derive(QueryableByName)]
@@ -362,9 +365,11 @@ pub async fn update_user(
             escape_sql(&tenant_id_param)
         );
 
-        diesel::sql_query(&query)
-            .execute(&mut conn)
-            .map_err(|e| e.to_string())?;
+        denetim::kayitli_yazim(&mut conn, state.denetim("update_user"), &tenant_id_param, "users", &user_id, |conn| {
+            diesel::sql_query(&query)
+                .execute(conn)
+                .map_err(|e| e.to_string())
+        })?;
     }
 
     // Güncellenmiş kullanıcıyı getir
@@ -456,14 +461,16 @@ pub async fn delete_user(
     }
 
     // Soft delete (is_active = 0)
-    diesel::sql_query(
-        "UPDATE users SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
-    .bind::<diesel::sql_types::Text, _>(&user_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("delete_user"), &tenant_id_param, "users", &user_id, |conn| {
+        diesel::sql_query(
+            "UPDATE users SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(&user_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -480,14 +487,16 @@ pub async fn activate_user(
     let pool = pool.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE users SET is_active = 1, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
-    .bind::<diesel::sql_types::Text, _>(&user_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("activate_user"), &tenant_id_param, "users", &user_id, |conn| {
+        diesel::sql_query(
+            "UPDATE users SET is_active = 1, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(&user_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     // Aktif edilmiş kullanıcıyı getir
     #[derive(QueryableByName)]
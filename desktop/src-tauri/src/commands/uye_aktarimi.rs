@@ -0,0 +1,77 @@
+// Üye aktarımı: XLSX/CSV dosyasından önizleme ve toplu kayıt (bkz. db::uye_aktarimi)
+use tauri::State;
+use diesel::prelude::*;
+use base64::{Engine as _, engine::general_purpose};
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::banka_ekstresi::tarih_coz;
+use crate::db::uye_aktarimi::{self, UyeAktarimEslestirme, UyeAktarimRaporu, UyeAktarimSonucu};
+
+fn dosya_icerigi(icerik_base64: &str) -> Result<Vec<u8>, String> {
+    general_purpose::STANDARD
+        .decode(icerik_base64.trim())
+        .map_err(|e| format!("Dosya içeriği çözülemedi: {}", e))
+}
+
+/// Dosyayı okuyup her satırın doğrulama ve mükerrerlik sonucunu döner; kayıt yapmaz
+#[tauri::command]
+pub fn uye_aktarimi_onizle(
+    state: State<AppState>,
+    tenant_id_param: String,
+    dosya_adi: String,
+    icerik_base64: String,
+    eslestirme: Option<UyeAktarimEslestirme>,
+) -> Result<UyeAktarimRaporu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let icerik = dosya_icerigi(&icerik_base64)?;
+    let eslestirme = eslestirme.unwrap_or_else(|| UyeAktarimEslestirme { baslik_var: true, ..Default::default() });
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    uye_aktarimi::rapor_olustur(&mut conn, &tenant_id_param, &dosya_adi, &icerik, &eslestirme)
+}
+
+/// Geçerli satırları tek transaction'da üye olarak ekler. Mükerrerler atlanır;
+/// hatalı satır varsa hatalilari_atla verilmedikçe hiçbir satır eklenmez.
+#[tauri::command]
+pub fn uye_aktarimi_uygula(
+    state: State<AppState>,
+    tenant_id_param: String,
+    dosya_adi: String,
+    icerik_base64: String,
+    eslestirme: Option<UyeAktarimEslestirme>,
+    hatalilari_atla: Option<bool>,
+) -> Result<UyeAktarimSonucu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+    let icerik = dosya_icerigi(&icerik_base64)?;
+    let eslestirme = eslestirme.unwrap_or_else(|| UyeAktarimEslestirme { baslik_var: true, ..Default::default() });
+    let giris_tarihi = match eslestirme.varsayilan_giris_tarihi.as_deref() {
+        Some(t) => tarih_coz(t, None).ok_or_else(|| format!("Geçersiz varsayılan giriş tarihi: {}", t))?,
+        None => chrono::Local::now().format("%Y-%m-%d").to_string(),
+    };
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("uye_aktarimi_uygula");
+    conn.transaction::<_, TxError, _>(|conn| {
+        // Önizlemeden bu yana eklenen üyeler de mükerrer sayılsın diye rapor yeniden üretilir
+        let rapor = uye_aktarimi::rapor_olustur(conn, &tenant_id_param, &dosya_adi, &icerik, &eslestirme)
+            .map_err(TxError::Msg)?;
+        let sonuc = uye_aktarimi::aktar(
+            conn,
+            &mut denetim,
+            &tenant_id_param,
+            &rapor,
+            hatalilari_atla.unwrap_or(false),
+            &giris_tarihi,
+        )
+        .map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(sonuc)
+    })
+    .map_err(|e| e.to_string())
+}
@@ -1,11 +1,14 @@
 use tauri::State;
 use diesel::prelude::*;
 use diesel::sql_types::BigInt;
+use diesel::sqlite::SqliteConnection;
 use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim::Denetim;
 use crate::db::models::Uye;
 use crate::db::outbox::{self, TxError};
+use crate::db::uyelik_durumu::{self, DurumDegisikligi, UyelikDurumu};
 use crate::db::schema::uyeler_basic;
 
 #[derive(QueryableByName)]
@@ -161,14 +164,17 @@ pub fn create_uye(
     let now = Utc::now().naive_utc();
     let giris_tarihi = NaiveDate::parse_from_str(&data.giris_tarihi, "%Y-%m-%d")
         .map_err(|e| format!("Invalid date format: {}", e))?;
+    let ilk_durum = UyelikDurumu::coz(&data.durum).ok_or_else(|| format!("Geçersiz üyelik durumu: {}", data.durum))?;
 
     {
         let db = state.db.lock().unwrap();
         let pool = db.as_ref().ok_or("Database not initialized")?;
         let mut conn = pool.get().map_err(|e| e.to_string())?;
 
+        let mut denetim = state.denetim("create_uye");
         // Yazım + outbox kaydı aynı transaction'da
         conn.transaction::<_, TxError, _>(|conn| {
+            denetim.izle(conn, &tenant_id_param, "uyeler", &new_id).map_err(TxError::Msg)?;
             diesel::sql_query(
                 "INSERT INTO uyeler (
                     id, tenant_id, uye_no, tc_no, ad, soyad, ad_soyad,
@@ -225,8 +231,19 @@ pub fn create_uye(
             .bind::<diesel::sql_types::Timestamp, _>(now)                   // 33
             .execute(conn)?;
 
+            uyelik_durumu::ilk_durumu_yaz(
+                conn,
+                &mut denetim,
+                &tenant_id_param,
+                &new_id,
+                ilk_durum,
+                &giris_tarihi.format("%Y-%m-%d").to_string(),
+                None,
+            )
+            .map_err(TxError::Msg)?;
             outbox::queue_change(conn, &tenant_id_param, "uyeler", &new_id, "create")
                 .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
             Ok(())
         })
         .map_err(|e| e.to_string())?;
@@ -252,8 +269,10 @@ pub fn update_uye(
 
         let now = Utc::now().naive_utc().format("%Y-%m-%d %H:%M:%S").to_string();
 
+        let mut denetim = state.denetim("update_uye");
         // Tüm update'ler + outbox kaydı tek transaction'da
         conn.transaction::<_, TxError, _>(|conn| {
+            denetim.izle(conn, &tenant_id_param, "uyeler", &uye_id).map_err(TxError::Msg)?;
             // Parameterized queries - SQL injection safe
             if let Some(ad) = &data.ad {
                 diesel::sql_query("UPDATE uyeler SET ad = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
@@ -309,24 +328,6 @@ pub fn update_uye(
                     .execute(conn)?;
             }
 
-            if let Some(cikis_tarihi) = &data.cikis_tarihi {
-                diesel::sql_query("UPDATE uyeler SET cikis_tarihi = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
-                    .bind::<diesel::sql_types::Text, _>(cikis_tarihi)
-                    .bind::<diesel::sql_types::Text, _>(&now)
-                    .bind::<diesel::sql_types::Text, _>(&uye_id)
-                    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-                    .execute(conn)?;
-            }
-
-            if let Some(durum) = &data.durum {
-                diesel::sql_query("UPDATE uyeler SET durum = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
-                    .bind::<diesel::sql_types::Text, _>(durum)
-                    .bind::<diesel::sql_types::Text, _>(&now)
-                    .bind::<diesel::sql_types::Text, _>(&uye_id)
-                    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-                    .execute(conn)?;
-            }
-
             if let Some(ozel_aidat_tutari) = data.ozel_aidat_tutari {
                 diesel::sql_query("UPDATE uyeler SET ozel_aidat_tutari = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
                     .bind::<diesel::sql_types::Double, _>(ozel_aidat_tutari)
@@ -354,15 +355,6 @@ pub fn update_uye(
                     .execute(conn)?;
             }
 
-            if let Some(ayrilma_nedeni) = &data.ayrilma_nedeni {
-                diesel::sql_query("UPDATE uyeler SET ayrilma_nedeni = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
-                    .bind::<diesel::sql_types::Text, _>(ayrilma_nedeni)
-                    .bind::<diesel::sql_types::Text, _>(&now)
-                    .bind::<diesel::sql_types::Text, _>(&uye_id)
-                    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-                    .execute(conn)?;
-            }
-
             if let Some(notlar) = &data.notlar {
                 diesel::sql_query("UPDATE uyeler SET notlar = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
                     .bind::<diesel::sql_types::Text, _>(notlar)
@@ -372,8 +364,13 @@ pub fn update_uye(
                     .execute(conn)?;
             }
 
+            // Durum, çıkış tarihi ve ayrılma nedeni durum geçmişinden yürür; farklı bir
+            // durum geçişe çevrilir (ayrılmada cikis_tarihi yürürlük tarihi olur)
+            durum_guncelle(conn, &mut denetim, &tenant_id_param, &uye_id, &data).map_err(TxError::Msg)?;
+
             outbox::queue_change(conn, &tenant_id_param, "uyeler", &uye_id, "update")
                 .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
             Ok(())
         })
         .map_err(|e| e.to_string())?;
@@ -382,6 +379,63 @@ pub fn update_uye(
     get_uye_by_id(state, tenant_id_param, uye_id)
 }
 
+fn durum_guncelle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_id: &str,
+    data: &UpdateUyeRequest,
+) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct DurumRow {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        durum: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        cikis_tarihi: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        ayrilma_nedeni: Option<String>,
+    }
+
+    let mevcut: DurumRow =
+        diesel::sql_query("SELECT durum, cikis_tarihi, ayrilma_nedeni FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(uye_id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .get_result(conn)
+            .map_err(|e| e.to_string())?;
+    let kayitli = mevcut.durum.as_deref().and_then(UyelikDurumu::coz);
+    let hedef = match &data.durum {
+        Some(d) => Some(UyelikDurumu::coz(d).ok_or_else(|| format!("Geçersiz üyelik durumu: {}", d))?),
+        None => None,
+    };
+
+    match hedef {
+        Some(hedef) if Some(hedef) != kayitli => {
+            let tarih = match (&data.cikis_tarihi, hedef.ayrilmis()) {
+                (Some(t), true) if !t.trim().is_empty() => t.trim().to_string(),
+                _ => chrono::Local::now().format("%Y-%m-%d").to_string(),
+            };
+            let degisiklik = DurumDegisikligi {
+                durum: hedef.kod().to_string(),
+                tarih,
+                karar_no: None,
+                karar_tarihi: None,
+                neden: data.ayrilma_nedeni.clone(),
+                aciklama: None,
+            };
+            uyelik_durumu::durum_degistir(conn, denetim, tenant_id, uye_id, &degisiklik)?;
+        }
+        _ => {
+            let degisti = |yeni: &Option<String>, eski: &Option<String>| {
+                yeni.as_deref().is_some_and(|y| y.trim() != eski.as_deref().unwrap_or("").trim())
+            };
+            if degisti(&data.cikis_tarihi, &mevcut.cikis_tarihi) || degisti(&data.ayrilma_nedeni, &mevcut.ayrilma_nedeni) {
+                return Err("Çıkış tarihi ve ayrılma nedeni üyelik durumu değiştirilerek girilir".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub fn delete_uye(
     state: State<AppState>,
@@ -463,8 +517,10 @@ pub fn delete_uye(
         ));
     }
 
+    let mut denetim = state.denetim("delete_uye");
     // DELETE + outbox tombstone kaydı aynı transaction'da
     conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "uyeler", &uye_id).map_err(TxError::Msg)?;
         outbox::queue_change(conn, &tenant_id_param, "uyeler", &uye_id, "delete")
             .map_err(TxError::Msg)?;
 
@@ -472,6 +528,7 @@ pub fn delete_uye(
             .bind::<diesel::sql_types::Text, _>(&uye_id)
             .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
             .execute(conn)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
         Ok(())
     })
     .map_err(|e| e.to_string())?;
@@ -0,0 +1,98 @@
+// Üyelik başvuruları ve yönetim kurulu kararı (bkz. db::uyelik_basvurusu)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::TxError;
+use crate::db::uyelik_basvurusu::{self, BasvuruGirdi, BasvuruKarari, BasvuruSonucu, UyelikBasvurusu};
+
+/// Başvurular; durum "bekliyor", "onaylandi", "reddedildi" ya da karar süresi geçmiş
+/// bekleyenler için "suresi_gecen"
+#[tauri::command]
+pub fn get_uyelik_basvurulari(
+    state: State<AppState>,
+    tenant_id_param: String,
+    durum: Option<String>,
+) -> Result<Vec<UyelikBasvurusu>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let bugun = chrono::Local::now().date_naive();
+    uyelik_basvurusu::basvurulari_getir(&mut conn, &tenant_id_param, durum.as_deref(), bugun)
+}
+
+#[tauri::command]
+pub fn get_uyelik_basvurusu(
+    state: State<AppState>,
+    tenant_id_param: String,
+    basvuru_id: String,
+) -> Result<UyelikBasvurusu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let bugun = chrono::Local::now().date_naive();
+    uyelik_basvurusu::basvuru_getir(&mut conn, &tenant_id_param, &basvuru_id, bugun)
+}
+
+/// Başvuru açar (basvuru_id yoksa) ya da bekleyen başvuruyu günceller
+#[tauri::command]
+pub fn uyelik_basvurusu_kaydet(
+    state: State<AppState>,
+    tenant_id_param: String,
+    basvuru_id: Option<String>,
+    data: BasvuruGirdi,
+) -> Result<UyelikBasvurusu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("uyelik_basvurusu_kaydet");
+    let id = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let id = uyelik_basvurusu::basvuru_kaydet(conn, &mut denetim, &tenant_id_param, basvuru_id.as_deref(), &data)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(id)
+        })
+        .map_err(|e| e.to_string())?;
+
+    let bugun = chrono::Local::now().date_naive();
+    uyelik_basvurusu::basvuru_getir(&mut conn, &tenant_id_param, &id, bugun)
+}
+
+/// Yönetim kurulu kararını işler. Onayda üye kaydı açılır ve (ilk_aidat kapatılmadıkça)
+/// giriş döneminin aidatı tahakkuk eder; redde gerekçe zorunludur.
+#[tauri::command]
+pub fn uyelik_basvurusu_karar(
+    state: State<AppState>,
+    tenant_id_param: String,
+    basvuru_id: String,
+    data: BasvuruKarari,
+) -> Result<BasvuruSonucu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let bugun = chrono::Local::now().date_naive();
+    let mut denetim = state.denetim("uyelik_basvurusu_karar");
+    let ilk_aidatlar = conn
+        .transaction::<_, TxError, _>(|conn| {
+            let ilk_aidatlar = uyelik_basvurusu::karar_ver(conn, &mut denetim, &tenant_id_param, &basvuru_id, &data, bugun)
+                .map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(ilk_aidatlar)
+        })
+        .map_err(|e| e.to_string())?;
+
+    let basvuru = uyelik_basvurusu::basvuru_getir(&mut conn, &tenant_id_param, &basvuru_id, bugun)?;
+    Ok(BasvuruSonucu { basvuru, ilk_aidatlar })
+}
@@ -0,0 +1,48 @@
+// Üyelik durum geçmişi ve durum geçişleri (bkz. db::uyelik_durumu)
+use tauri::State;
+use diesel::prelude::*;
+use crate::state::AppState;
+use crate::db::outbox::{self, TxError};
+use crate::db::uyelik_durumu::{self, DurumDegisikligi, UyelikDurumKaydi};
+
+/// Üyenin durum geçmişi, eskiden yeniye
+#[tauri::command]
+pub fn get_uyelik_durum_gecmisi(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+) -> Result<Vec<UyelikDurumKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    uyelik_durumu::gecmis(&mut conn, &tenant_id_param, &uye_id)
+}
+
+/// Durum geçişi: askıya alma, ayrılma, ihraç, yeniden kabul. Kabul ve ihraçta
+/// yönetim kurulu karar numarası zorunludur.
+#[tauri::command]
+pub fn uyelik_durumu_degistir(
+    state: State<AppState>,
+    tenant_id_param: String,
+    uye_id: String,
+    data: DurumDegisikligi,
+) -> Result<UyelikDurumKaydi, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let db = state.db.lock().unwrap();
+    let pool = db.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let mut denetim = state.denetim("uyelik_durumu_degistir");
+    conn.transaction::<_, TxError, _>(|conn| {
+        let kayit = uyelik_durumu::durum_degistir(conn, &mut denetim, &tenant_id_param, &uye_id, &data)
+            .map_err(TxError::Msg)?;
+        outbox::queue_change(conn, &tenant_id_param, "uyeler", &uye_id, "update").map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(kayit)
+    })
+    .map_err(|e| e.to_string())
+}
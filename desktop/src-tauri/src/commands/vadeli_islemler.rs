@@ -3,7 +3,11 @@ use diesel::prelude::*;
 use chrono::Utc;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::db::denetim;
 use serde::{Serialize, Deserialize};
+use crate::db::yevmiye;
+use crate::db::donem_kilidi;
+use crate::commands::mali::outbox_err;
 
 #[derive(Debug, Serialize, Deserialize, QueryableByName)]
 pub struct VadeliIslem {
@@ -256,26 +260,28 @@ pub fn create_vadeli_islem(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "INSERT INTO vadeli_islemler (id, tenant_id, kasa_id, islem_tipi, tutar, vade_tarihi, aciklama, kategori, tekrar_tipi, ilgili_kisi, cari_id, durum, hatirlatma_gun, notlar, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'Bekliyor', ?12, ?13, 1, ?14, ?15)"
-    )
-    .bind::<diesel::sql_types::Text, _>(&new_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kasa_id)
-    .bind::<diesel::sql_types::Text, _>(&data.islem_tipi)
-    .bind::<diesel::sql_types::Double, _>(data.tutar)
-    .bind::<diesel::sql_types::Text, _>(&data.vade_tarihi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kategori)
-    .bind::<diesel::sql_types::Text, _>(&tekrar_tipi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.ilgili_kisi)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.cari_id)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.hatirlatma_gun)
-    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("create_vadeli_islem"), &tenant_id_param, "vadeli_islemler", &new_id, |conn| {
+        diesel::sql_query(
+            "INSERT INTO vadeli_islemler (id, tenant_id, kasa_id, islem_tipi, tutar, vade_tarihi, aciklama, kategori, tekrar_tipi, ilgili_kisi, cari_id, durum, hatirlatma_gun, notlar, is_active, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'Bekliyor', ?12, ?13, 1, ?14, ?15)"
+        )
+        .bind::<diesel::sql_types::Text, _>(&new_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kasa_id)
+        .bind::<diesel::sql_types::Text, _>(&data.islem_tipi)
+        .bind::<diesel::sql_types::Double, _>(data.tutar)
+        .bind::<diesel::sql_types::Text, _>(&data.vade_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.aciklama)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.kategori)
+        .bind::<diesel::sql_types::Text, _>(&tekrar_tipi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.ilgili_kisi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.cari_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(&data.hatirlatma_gun)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&data.notlar)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(new_id)
 }
@@ -309,8 +315,12 @@ pub fn gerceklestir_vadeli_islem(
     let gerceklesen_id = Uuid::new_v4().to_string();
     let is_gelir = vadeli.islem_tipi.to_lowercase() == "gelir";
 
+    let mut denetim = state.denetim("gerceklestir_vadeli_islem");
     // Tüm işlem tek bir transaction içinde — 4 tablo atomic güncelleniyor.
     conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        denetim.izle(conn, &tenant_id_param, "vadeli_islemler", &vadeli_islem_id).map_err(outbox_err)?;
+        denetim.yeni_kayit(&tenant_id_param, if is_gelir { "gelirler" } else { "giderler" }, &gerceklesen_id);
+        donem_kilidi::tarihi_dogrula(conn, &tenant_id_param, &today).map_err(outbox_err)?;
         // 2. Gelir veya gider kaydı oluştur (kasa.bakiye ve toplam_* alanlarına
         // DIREKT UPDATE yapılmıyor — update_kasa_bakiye tek kaynak).
         if is_gelir {
@@ -351,6 +361,7 @@ pub fn gerceklestir_vadeli_islem(
             // Diğer modüllerle tutarlı: "Alacak" / "Borç" (Türkçe).
             let hareket_tipi = if is_gelir { "Alacak" } else { "Borç" };
             let hareket_id = Uuid::new_v4().to_string();
+            denetim.yeni_kayit(&tenant_id_param, "cari_hareketler", &hareket_id);
 
             diesel::sql_query(
                 "INSERT INTO cari_hareketler (id, tenant_id, cari_id, hareket_tipi, tarih, tutar, odenen, kalan, belge_turu, kasa_id, gelir_id, gider_id, durum, created_at, updated_at)
@@ -388,6 +399,11 @@ pub fn gerceklestir_vadeli_islem(
             }
         }
 
+        // Yevmiye: cari bağlantısı kurulduktan sonra işlenir (cariliyse karşı hesap cari olur)
+        let kaynak_tablo = if is_gelir { "gelirler" } else { "giderler" };
+        yevmiye::kaydi_isle(conn, &tenant_id_param, kaynak_tablo, &gerceklesen_id)
+            .map_err(outbox_err)?;
+
         // 4. Vadeli işlem durumunu güncelle
         diesel::sql_query(
             "UPDATE vadeli_islemler SET durum = 'Gerçekleşti', gerceklesen_id = ?1, gerceklesme_tarihi = ?2, updated_at = ?3 WHERE id = ?4 AND tenant_id = ?5"
@@ -399,6 +415,7 @@ pub fn gerceklestir_vadeli_islem(
         .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
         .execute(conn)?;
 
+        denetim.kaydet(conn).map_err(outbox_err)?;
         Ok(())
     }).map_err(|e| format!("Vadeli işlem gerçekleştirilemedi: {}", e))?;
 
@@ -418,14 +435,16 @@ pub fn iptal_vadeli_islem(
     let pool = db.as_ref().ok_or("Database not initialized")?;
     let mut conn = pool.get().map_err(|e| e.to_string())?;
 
-    diesel::sql_query(
-        "UPDATE vadeli_islemler SET durum = 'İptal', updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
-    )
-    .bind::<diesel::sql_types::Text, _>(&now)
-    .bind::<diesel::sql_types::Text, _>(&vadeli_islem_id)
-    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
-    .execute(&mut conn)
-    .map_err(|e| e.to_string())?;
+    denetim::kayitli_yazim(&mut conn, state.denetim("iptal_vadeli_islem"), &tenant_id_param, "vadeli_islemler", &vadeli_islem_id, |conn| {
+        diesel::sql_query(
+            "UPDATE vadeli_islemler SET durum = 'İptal', updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3"
+        )
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&vadeli_islem_id)
+        .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+        .execute(conn)
+        .map_err(|e| e.to_string())
+    })?;
 
     Ok(())
 }
@@ -0,0 +1,200 @@
+// Yevmiye (çift taraflı kayıt) komutları
+// Fişler db::yevmiye tarafından kaynak kayıtlarla aynı transaction'da üretilir;
+// buradaki komutlar hesap planı, fiş listesi ve mizan raporlarını sunar.
+
+use diesel::prelude::*;
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::para::Para;
+use crate::db::yevmiye::{self, Mizan, YenidenOlusturmaSonucu};
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct HesapPlaniKaydi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kod: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hesap_turu: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ust_kod: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub bagli_tablo: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub bagli_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct YevmiyeSatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub fis_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub sira: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hesap_kodu: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hesap_adi: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub borc: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub alacak: Para,
+}
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct YevmiyeFisBasligi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub fis_no: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kaynak_tablo: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kaynak_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub iptal_tarihi: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct YevmiyeFisi {
+    #[serde(flatten)]
+    pub fis: YevmiyeFisBasligi,
+    pub satirlar: Vec<YevmiyeSatiri>,
+}
+
+/// Hesap planı (ana hesaplar + ilk kullanımda açılmış alt hesaplar), kod sırasıyla
+#[tauri::command]
+pub async fn get_hesap_plani(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+) -> Result<Vec<HesapPlaniKaydi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    yevmiye::hesap_plani_hazirla(&mut conn, &tenant_id_param)?;
+
+    diesel::sql_query(
+        "SELECT id, kod, ad, hesap_turu, ust_kod, bagli_tablo, bagli_id FROM hesap_plani
+         WHERE tenant_id = ?1 AND is_active = 1 ORDER BY kod"
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Yevmiye fişleri (satırlarıyla). İptal edilmiş fişler yalnızca istenirse döner.
+#[tauri::command]
+pub async fn get_yevmiye_fisleri(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    baslangic_tarihi: Option<String>,
+    bitis_tarihi: Option<String>,
+    iptaller_dahil: Option<bool>,
+) -> Result<Vec<YevmiyeFisi>, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    let basliklar: Vec<YevmiyeFisBasligi> = diesel::sql_query(
+        "SELECT id, yil, fis_no, tarih, aciklama, kaynak_tablo, kaynak_id, durum, iptal_tarihi
+         FROM yevmiye_fisleri
+         WHERE tenant_id = ?1
+           AND (?2 IS NULL OR tarih >= ?2)
+           AND (?3 IS NULL OR tarih <= ?3)
+           AND (?4 = 1 OR durum = 'aktif')
+         ORDER BY tarih, yil, fis_no"
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&baslangic_tarihi)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&bitis_tarihi)
+    .bind::<diesel::sql_types::Integer, _>(iptaller_dahil.unwrap_or(false) as i32)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    let satirlar: Vec<YevmiyeSatiri> = diesel::sql_query(
+        "SELECT s.fis_id, s.sira, s.hesap_kodu, h.ad AS hesap_adi, s.borc, s.alacak
+         FROM yevmiye_satirlari s
+         JOIN yevmiye_fisleri f ON f.id = s.fis_id
+         JOIN hesap_plani h ON h.id = s.hesap_id
+         WHERE s.tenant_id = ?1
+           AND (?2 IS NULL OR f.tarih >= ?2)
+           AND (?3 IS NULL OR f.tarih <= ?3)
+           AND (?4 = 1 OR f.durum = 'aktif')
+         ORDER BY s.fis_id, s.sira"
+    )
+    .bind::<diesel::sql_types::Text, _>(&tenant_id_param)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&baslangic_tarihi)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&bitis_tarihi)
+    .bind::<diesel::sql_types::Integer, _>(iptaller_dahil.unwrap_or(false) as i32)
+    .load(&mut conn)
+    .map_err(|e| e.to_string())?;
+
+    let mut satir_map: std::collections::HashMap<String, Vec<YevmiyeSatiri>> = std::collections::HashMap::new();
+    for s in satirlar {
+        satir_map.entry(s.fis_id.clone()).or_default().push(s);
+    }
+    Ok(basliklar
+        .into_iter()
+        .map(|fis| {
+            let satirlar = satir_map.remove(&fis.id).unwrap_or_default();
+            YevmiyeFisi { fis, satirlar }
+        })
+        .collect())
+}
+
+/// Mizan (geçici mizan): dönem içindeki aktif fişlerden hesap bazında borç/alacak toplam ve bakiyeleri
+#[tauri::command]
+pub async fn get_mizan(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+    baslangic_tarihi: Option<String>,
+    bitis_tarihi: Option<String>,
+) -> Result<Mizan, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    yevmiye::mizan_hesapla(
+        &mut conn,
+        &tenant_id_param,
+        baslangic_tarihi.as_deref(),
+        bitis_tarihi.as_deref(),
+    )
+}
+
+/// Tüm gelir/gider/virman/aidat/cari/kasa kayıtlarını yevmiyeye yeniden işler.
+/// Yevmiye öncesi verilerin ilk aktarımı ve denetim için; değişmeyen fişlere dokunmaz.
+#[tauri::command]
+pub async fn yevmiye_yeniden_olustur(
+    state: State<'_, crate::AppState>,
+    tenant_id_param: String,
+) -> Result<YenidenOlusturmaSonucu, String> {
+    state.verify_tenant_access(&tenant_id_param)?;
+
+    let pool = state.db.lock().unwrap();
+    let pool = pool.as_ref().ok_or("Database not initialized")?;
+    let mut conn = pool.get().map_err(|e| e.to_string())?;
+
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        yevmiye::tumunu_yeniden_olustur(conn, &tenant_id_param)
+            .map_err(crate::commands::mali::outbox_err)
+    })
+    .map_err(|e| e.to_string())
+}
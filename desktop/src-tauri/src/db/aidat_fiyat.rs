@@ -0,0 +1,489 @@
+// Aidat fiyatlandırma: taban tutar + sıralı kurallar.
+//
+// Taban: üyeye özel tutar (ozel_aidat_tutari) varsa o kullanılır ve kural
+// uygulanmaz. Yoksa üye türüne göre aidat tanımı (aidat_tanimlari.uye_turu),
+// o da yoksa çağıranın varsayılan tutarı. Ardından üyenin kişisel indirimi
+// (aidat_indirimi_yuzde) ve aidat_fiyat_kurallari sira sırasıyla uygulanır.
+// Her adım önceki/sonraki tutarla kaydedilir; böylece önizleme her üye için
+// tutarın hangi kurallardan geçtiğini gösterebilir.
+//
+// Kural koşulları (hepsi isteğe bağlı): yıl, üye türü, yıl başındaki yaş aralığı,
+// özel durum (ogrenci / emekli: meslek alanından; aile: üyenin kayıtlı bir aile
+// yakını da dernek üyesiyse). Sonuç hiçbir zaman sıfırın altına inmez.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::db::models::Uye;
+use crate::db::para::Para;
+
+pub const ISLEMLER: [&str; 6] = ["sabit", "yuzde_indirim", "tutar_indirim", "ust_sinir", "alt_sinir", "kist_giris"];
+pub const OZEL_DURUMLAR: [&str; 3] = ["ogrenci", "emekli", "aile"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct AidatFiyatKurali {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub sira: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub yil: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_turu: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub yas_min: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub yas_max: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ozel_durum: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub islem: String,
+    /// yuzde_indirim için yüzde, kist_giris için kullanılmaz, diğerlerinde TL
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub deger: f64,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub durdur: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AidatFiyatAdimi {
+    pub kural_id: Option<String>,
+    pub aciklama: String,
+    pub onceki: Para,
+    pub sonraki: Para,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AidatFiyati {
+    pub tutar: Para,
+    /// ozel, tanim, varsayilan, tanimsiz
+    pub kaynak: String,
+    pub adimlar: Vec<AidatFiyatAdimi>,
+}
+
+impl AidatFiyati {
+    /// Kural uygulanıp uygulanmadığı (taban ve kişisel indirim hariç)
+    pub fn kural_uygulandi(&self) -> bool {
+        self.adimlar.iter().any(|a| a.kural_id.is_some())
+    }
+
+    /// aidat_takip.notlar için kısa açıklama: uygulanan kuralların adları
+    pub fn ozet(&self) -> Option<String> {
+        let kurallar: Vec<&str> = self
+            .adimlar
+            .iter()
+            .skip(1)
+            .map(|a| a.aciklama.as_str())
+            .collect();
+        (!kurallar.is_empty()).then(|| kurallar.join(", "))
+    }
+}
+
+/// Kural kaydedilmeden önceki doğrulama; TL tutarlı kurallarda değer kuruşa
+/// yuvarlanır ki önizleme ve tahakkuk kullanıcının girdiği tutarla aynı kalsın
+pub fn kural_dogrula(kural: &mut AidatFiyatKurali) -> Result<(), String> {
+    if kural.ad.trim().is_empty() {
+        return Err("Kural adı boş olamaz".to_string());
+    }
+    if !ISLEMLER.contains(&kural.islem.as_str()) {
+        return Err(format!("Geçersiz kural işlemi: {}", kural.islem));
+    }
+    if let Some(d) = kural.ozel_durum.as_deref() {
+        if !OZEL_DURUMLAR.contains(&d) {
+            return Err(format!("Geçersiz özel durum: {}", d));
+        }
+    }
+    if !kural.deger.is_finite() || kural.deger < 0.0 {
+        return Err("Kural değeri negatif olamaz".to_string());
+    }
+    if kural.islem == "yuzde_indirim" && kural.deger > 100.0 {
+        return Err("İndirim yüzdesi 100'ü geçemez".to_string());
+    }
+    if matches!(kural.islem.as_str(), "sabit" | "tutar_indirim" | "ust_sinir" | "alt_sinir") {
+        kural.deger = Para::from_tl(kural.deger).tl();
+    }
+    if let (Some(min), Some(max)) = (kural.yas_min, kural.yas_max) {
+        if min > max {
+            return Err("Yaş alt sınırı üst sınırdan büyük olamaz".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn tarih_coz(tarih: &str) -> Option<NaiveDate> {
+    let t = tarih.trim();
+    let t = t.get(..10).unwrap_or(t);
+    NaiveDate::parse_from_str(t, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(t, "%d.%m.%Y"))
+        .ok()
+}
+
+/// Yıl başındaki (1 Ocak) yaş
+fn yil_basinda_yas(dogum_tarihi: Option<&str>, yil: i32) -> Option<i32> {
+    let dogum = tarih_coz(dogum_tarihi?)?;
+    let yil_basi = NaiveDate::from_ymd_opt(yil, 1, 1)?;
+    let mut yas = yil_basi.year() - dogum.year();
+    if (yil_basi.month(), yil_basi.day()) < (dogum.month(), dogum.day()) {
+        yas -= 1;
+    }
+    Some(yas)
+}
+
+fn meslek_iceriyor(meslek: Option<&str>, anahtarlar: &[&str]) -> bool {
+    let Some(m) = meslek else { return false };
+    // "İ".to_lowercase() birleşik nokta bırakır
+    let m: String = m.to_lowercase().chars().filter(|c| *c != '\u{307}').collect();
+    anahtarlar.iter().any(|a| m.contains(a))
+}
+
+/// Üyenin kural koşullarında kullanılan bilgileri
+pub struct UyeFiyatBilgisi<'a> {
+    pub uye_turu: &'a str,
+    pub dogum_tarihi: Option<&'a str>,
+    pub meslek: Option<&'a str>,
+    pub giris_tarihi: &'a str,
+    pub ozel_aidat_tutari: Option<Para>,
+    pub aidat_indirimi_yuzde: Option<f64>,
+    pub aile_uyesi: bool,
+}
+
+impl<'a> UyeFiyatBilgisi<'a> {
+    pub fn uyeden(uye: &'a Uye, aile_uyesi: bool) -> Self {
+        UyeFiyatBilgisi {
+            uye_turu: uye.uyelik_tipi.as_deref().unwrap_or("Asil"),
+            dogum_tarihi: uye.dogum_tarihi.as_deref(),
+            meslek: uye.meslek.as_deref(),
+            giris_tarihi: &uye.giris_tarihi,
+            ozel_aidat_tutari: uye.ozel_aidat_tutari,
+            aidat_indirimi_yuzde: uye.aidat_indirimi_yuzde,
+            aile_uyesi,
+        }
+    }
+}
+
+fn kosul_saglaniyor(kural: &AidatFiyatKurali, uye: &UyeFiyatBilgisi, yil: i32) -> bool {
+    if kural.yil.is_some_and(|y| y != yil) {
+        return false;
+    }
+    if kural.uye_turu.as_deref().is_some_and(|t| !t.eq_ignore_ascii_case(uye.uye_turu)) {
+        return false;
+    }
+    if kural.yas_min.is_some() || kural.yas_max.is_some() {
+        let Some(yas) = yil_basinda_yas(uye.dogum_tarihi, yil) else { return false };
+        if kural.yas_min.is_some_and(|m| yas < m) || kural.yas_max.is_some_and(|m| yas > m) {
+            return false;
+        }
+    }
+    match kural.ozel_durum.as_deref() {
+        Some("ogrenci") => meslek_iceriyor(uye.meslek, &["öğrenci", "ogrenci"]),
+        Some("emekli") => meslek_iceriyor(uye.meslek, &["emekli"]),
+        Some("aile") => uye.aile_uyesi,
+        _ => true,
+    }
+}
+
+/// Kuralı uygular; tutarı değiştirmeyen kural (ör. yıl başından önce katılan
+/// üyeye kıst) None döner ve açıklamaya yazılmaz
+fn kural_uygula(kural: &AidatFiyatKurali, uye: &UyeFiyatBilgisi, yil: i32, tutar: Para) -> Option<(Para, String)> {
+    let tl = Para::from_tl(kural.deger);
+    let (yeni, aciklama) = match kural.islem.as_str() {
+        "sabit" => (tl, format!("{}: {} TL", kural.ad, tl.tr_format())),
+        "yuzde_indirim" => (tutar - tutar.yuzde(kural.deger), format!("{}: %{} indirim", kural.ad, kural.deger)),
+        "tutar_indirim" => (tutar - tl, format!("{}: {} TL indirim", kural.ad, tl.tr_format())),
+        "ust_sinir" => (tutar.min(tl), format!("{}: en fazla {} TL", kural.ad, tl.tr_format())),
+        "alt_sinir" => (tutar.max(tl), format!("{}: en az {} TL", kural.ad, tl.tr_format())),
+        "kist_giris" => {
+            let giris = tarih_coz(uye.giris_tarihi)?;
+            if giris.year() != yil || giris.month() == 1 {
+                return None;
+            }
+            let kalan_ay = 13 - giris.month() as i64;
+            (
+                Para::from_kurus(tutar.kurus() * kalan_ay / 12),
+                format!("{}: {}/12 ay (giriş {})", kural.ad, kalan_ay, giris.format("%d.%m.%Y")),
+            )
+        }
+        _ => return None,
+    };
+    let yeni = yeni.sifirdan_kucukse_sifir();
+    (yeni != tutar || matches!(kural.islem.as_str(), "sabit")).then_some((yeni, aciklama))
+}
+
+/// Taban tutardan başlayarak kişisel indirimi ve kuralları uygular
+pub fn fiyat_hesapla(
+    uye: &UyeFiyatBilgisi,
+    yil: i32,
+    tanim_tutari: Option<Para>,
+    varsayilan: Option<Para>,
+    kurallar: &[AidatFiyatKurali],
+) -> AidatFiyati {
+    if let Some(ozel) = uye.ozel_aidat_tutari.filter(|t| t.is_positive()) {
+        return AidatFiyati {
+            tutar: ozel,
+            kaynak: "ozel".to_string(),
+            adimlar: vec![AidatFiyatAdimi {
+                kural_id: None,
+                aciklama: "Üyeye özel aidat tutarı (kurallar uygulanmaz)".to_string(),
+                onceki: Para::SIFIR,
+                sonraki: ozel,
+            }],
+        };
+    }
+
+    let (taban, kaynak, aciklama) = match (tanim_tutari, varsayilan) {
+        (Some(t), _) => (t, "tanim", format!("{} {} aidat tanımı", yil, uye.uye_turu)),
+        (None, Some(v)) => (v, "varsayilan", format!("{} türü için tanım yok, varsayılan tutar", uye.uye_turu)),
+        (None, None) => (Para::SIFIR, "tanimsiz", format!("{} {} için aidat tanımı yok", yil, uye.uye_turu)),
+    };
+    let mut adimlar = vec![AidatFiyatAdimi { kural_id: None, aciklama, onceki: Para::SIFIR, sonraki: taban }];
+    let mut tutar = taban;
+
+    if let Some(yuzde) = uye.aidat_indirimi_yuzde.filter(|y| *y > 0.0) {
+        let yeni = (tutar - tutar.yuzde(yuzde.min(100.0))).sifirdan_kucukse_sifir();
+        adimlar.push(AidatFiyatAdimi {
+            kural_id: None,
+            aciklama: format!("Üye indirimi: %{}", yuzde),
+            onceki: tutar,
+            sonraki: yeni,
+        });
+        tutar = yeni;
+    }
+
+    let mut sirali: Vec<&AidatFiyatKurali> = kurallar.iter().collect();
+    sirali.sort_by_key(|k| k.sira);
+    for kural in sirali {
+        if !kosul_saglaniyor(kural, uye, yil) {
+            continue;
+        }
+        if let Some((yeni, aciklama)) = kural_uygula(kural, uye, yil, tutar) {
+            adimlar.push(AidatFiyatAdimi { kural_id: Some(kural.id.clone()), aciklama, onceki: tutar, sonraki: yeni });
+            tutar = yeni;
+        }
+        if kural.durdur {
+            break;
+        }
+    }
+
+    AidatFiyati { tutar, kaynak: kaynak.to_string(), adimlar }
+}
+
+// ---------------------------------------------------------------------------
+// Veritabanı
+// ---------------------------------------------------------------------------
+
+/// Bir tenant ve yıl için tanımları, kuralları ve aile bağlarını bir kez
+/// yükleyip üyeler için tutar hesaplar (toplu tahakkukta üye başına sorgu yok)
+pub struct FiyatMotoru {
+    yil: i32,
+    varsayilan: Option<Para>,
+    tanimlar: HashMap<String, Para>,
+    kurallar: Vec<AidatFiyatKurali>,
+    aile_uyeleri: HashSet<String>,
+}
+
+impl FiyatMotoru {
+    pub fn yukle(
+        conn: &mut SqliteConnection,
+        tenant_id: &str,
+        yil: i32,
+        varsayilan: Option<Para>,
+    ) -> Result<Self, String> {
+        #[derive(QueryableByName)]
+        struct TanimRow {
+            #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+            uye_turu: Option<String>,
+            #[diesel(sql_type = diesel::sql_types::Double)]
+            tutar: Para,
+        }
+        #[derive(QueryableByName)]
+        struct IdRow {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            id: String,
+        }
+
+        let tanimlar = diesel::sql_query(
+            "SELECT uye_turu, tutar FROM aidat_tanimlari
+             WHERE tenant_id = ?1 AND yil = ?2 AND is_active = 1
+             ORDER BY updated_at ASC",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Integer, _>(yil)
+        .load::<TanimRow>(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| (r.uye_turu.unwrap_or_else(|| "Asil".to_string()), r.tutar))
+        .collect();
+
+        let kurallar = kurallari_getir(conn, tenant_id, Some(yil))?;
+
+        // Aile: üyenin aile yakınları arasında TC'si eşleşen başka bir aktif üye var
+        // (veya başka bir üyenin aile yakını olarak bu üye kayıtlı) ya da üye aktif bir haneye bağlı
+        let aile_uyeleri = diesel::sql_query(
+            "SELECT DISTINCT u.id FROM uyeler u
+             JOIN uyeler d ON d.tenant_id = u.tenant_id AND d.id != u.id AND d.cikis_tarihi IS NULL
+             JOIN uye_aile_uyeleri a ON a.tenant_id = u.tenant_id
+                  AND (a.is_deleted IS NULL OR a.is_deleted = 0)
+                  AND a.tc_no IS NOT NULL AND a.tc_no != ''
+                  AND ((a.uye_id = u.id AND a.tc_no = d.tc_no) OR (a.uye_id = d.id AND a.tc_no = u.tc_no))
+             WHERE u.tenant_id = ?1
+             UNION
+             SELECT hu.uye_id AS id FROM hane_uyeleri hu
+             JOIN haneler h ON h.id = hu.hane_id AND h.is_active = 1
+             WHERE hu.tenant_id = ?1",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .load::<IdRow>(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        Ok(FiyatMotoru { yil, varsayilan, tanimlar, kurallar, aile_uyeleri })
+    }
+
+    /// Kıst giriş kuralları yalnızca yıllık aidatta anlamlıdır; aylık / üç aylık tahakkukta çıkarılır
+    pub fn kist_haric(mut self) -> Self {
+        self.kurallar.retain(|k| k.islem != "kist_giris");
+        self
+    }
+
+    pub fn hesapla(&self, uye: &Uye) -> AidatFiyati {
+        let bilgi = UyeFiyatBilgisi::uyeden(uye, self.aile_uyeleri.contains(&uye.id));
+        let tanim = self.tanimlar.get(bilgi.uye_turu).copied();
+        fiyat_hesapla(&bilgi, self.yil, tanim, self.varsayilan, &self.kurallar)
+    }
+}
+
+/// Aktif kurallar, sıra ile; yil verilirse yalnızca o yıla uygulananlar
+pub fn kurallari_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: Option<i32>,
+) -> Result<Vec<AidatFiyatKurali>, String> {
+    diesel::sql_query(
+        "SELECT id, ad, sira, yil, uye_turu, yas_min, yas_max, ozel_durum, islem, deger, durdur, aciklama
+         FROM aidat_fiyat_kurallari
+         WHERE tenant_id = ?1 AND is_active = 1 AND (?2 IS NULL OR yil IS NULL OR yil = ?2)
+         ORDER BY sira ASC, created_at ASC",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(yil)
+    .load::<AidatFiyatKurali>(conn)
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kural(ad: &str, sira: i32, islem: &str, deger: f64) -> AidatFiyatKurali {
+        AidatFiyatKurali {
+            id: ad.to_string(),
+            ad: ad.to_string(),
+            sira,
+            yil: None,
+            uye_turu: None,
+            yas_min: None,
+            yas_max: None,
+            ozel_durum: None,
+            islem: islem.to_string(),
+            deger,
+            durdur: false,
+            aciklama: None,
+        }
+    }
+
+    fn uye<'a>(dogum: Option<&'a str>, meslek: Option<&'a str>, giris: &'a str) -> UyeFiyatBilgisi<'a> {
+        UyeFiyatBilgisi {
+            uye_turu: "Asil",
+            dogum_tarihi: dogum,
+            meslek,
+            giris_tarihi: giris,
+            ozel_aidat_tutari: None,
+            aidat_indirimi_yuzde: None,
+            aile_uyesi: false,
+        }
+    }
+
+    #[test]
+    fn kurallar_sirayla_uygulanir_ve_aciklanir() {
+        let mut ogrenci = kural("Öğrenci", 10, "yuzde_indirim", 50.0);
+        ogrenci.ozel_durum = Some("ogrenci".to_string());
+        let mut genc = kural("Genç", 20, "tutar_indirim", 100.0);
+        genc.yas_max = Some(25);
+        let taban = kural("Taban", 90, "alt_sinir", 300.0);
+        let kurallar = vec![taban, genc, ogrenci];
+
+        let u = uye(Some("2004-06-15"), Some("ÜNİVERSİTE ÖĞRENCİSİ"), "2020-01-01");
+        let f = fiyat_hesapla(&u, 2026, Some(Para::from_tl(1000.0)), None, &kurallar);
+        // 1000 → %50 → 500 → -100 → 400 (alt sınır 300 değiştirmez)
+        assert_eq!(f.tutar, Para::from_tl(400.0));
+        assert_eq!(f.kaynak, "tanim");
+        assert_eq!(f.adimlar.len(), 3);
+        assert_eq!(f.adimlar[1].kural_id.as_deref(), Some("Öğrenci"));
+        assert!(f.kural_uygulandi());
+        assert_eq!(f.ozet().unwrap(), "Öğrenci: %50 indirim, Genç: 100,00 TL indirim");
+
+        // 26 yaşında (yıl başında) ve öğrenci değil: yalnızca taban
+        let u = uye(Some("1999-12-31"), Some("Mühendis"), "2020-01-01");
+        let f = fiyat_hesapla(&u, 2026, Some(Para::from_tl(1000.0)), None, &kurallar);
+        assert_eq!(f.tutar, Para::from_tl(1000.0));
+        assert!(!f.kural_uygulandi());
+    }
+
+    #[test]
+    fn kist_durdur_ve_ozel_tutar() {
+        let mut kist = kural("Kıst", 1, "kist_giris", 0.0);
+        kist.durdur = true;
+        let sonra = kural("Sabit", 2, "sabit", 1.0);
+        let kurallar = vec![kist, sonra];
+
+        // Nisan'da katılan: 9/12
+        let u = uye(None, None, "2026-04-10");
+        let f = fiyat_hesapla(&u, 2026, Some(Para::from_tl(1200.0)), None, &kurallar);
+        assert_eq!(f.tutar, Para::from_tl(900.0));
+        assert_eq!(f.adimlar.len(), 2);
+
+        // Önceki yıl katılan: kıst uygulanmaz ama durdur yine de sonraki kuralı keser
+        let u = uye(None, None, "2025-04-10");
+        let f = fiyat_hesapla(&u, 2026, Some(Para::from_tl(1200.0)), None, &kurallar);
+        assert_eq!(f.tutar, Para::from_tl(1200.0));
+
+        // Özel tutar kuralları atlar; tanım yoksa varsayılan
+        let mut u = uye(None, None, "2026-04-10");
+        u.ozel_aidat_tutari = Some(Para::from_tl(50.0));
+        let f = fiyat_hesapla(&u, 2026, Some(Para::from_tl(1200.0)), None, &kurallar);
+        assert_eq!((f.tutar, f.kaynak.as_str()), (Para::from_tl(50.0), "ozel"));
+        let u = uye(None, None, "2020-01-01");
+        let f = fiyat_hesapla(&u, 2026, None, Some(Para::from_tl(250.0)), &[]);
+        assert_eq!((f.tutar, f.kaynak.as_str()), (Para::from_tl(250.0), "varsayilan"));
+    }
+
+    #[test]
+    fn kural_dogrulama() {
+        assert!(kural_dogrula(&mut kural("A", 1, "yuzde_indirim", 50.0)).is_ok());
+        assert!(kural_dogrula(&mut kural("A", 1, "yuzde_indirim", 150.0)).is_err());
+        assert!(kural_dogrula(&mut kural("A", 1, "carp", 2.0)).is_err());
+        assert!(kural_dogrula(&mut kural(" ", 1, "sabit", 2.0)).is_err());
+        let mut k = kural("A", 1, "sabit", 2.0);
+        k.yas_min = Some(30);
+        k.yas_max = Some(20);
+        assert!(kural_dogrula(&mut k).is_err());
+
+        // TL tutarlı kurallar kuruşa yuvarlanır, yüzde olduğu gibi kalır
+        let mut k = kural("A", 1, "tutar_indirim", 100.005);
+        assert!(kural_dogrula(&mut k).is_ok());
+        assert_eq!(k.deger, 100.01);
+        let mut k = kural("A", 1, "yuzde_indirim", 12.345);
+        assert!(kural_dogrula(&mut k).is_ok());
+        assert_eq!(k.deger, 12.345);
+    }
+}
@@ -0,0 +1,887 @@
+// Zamanlanmış aidat tahakkuku: aidat_tanimlari.aidat_tipi'ne göre (Yıllık, Üç aylık, Aylık)
+// her dönemin ilk günü, dönemin bir kısmında asil olan üyelere aidat kaydı açılır
+// (durum geçmişi: db::uyelik_durumu).
+//
+// Dönem yil/ay ile gösterilir ve ay dönem başlangıcıdır (Yıllık: 1, Üç aylık: 1/4/7/10);
+// yıllık aidat, toplu_aidat_olustur'da olduğu gibi ay = 1 ile yazılır. Her çalışma
+// aidat_tahakkuk_calismalari'na kaydedilir. Hiç çalışma yoksa cari dönemden başlanır;
+// sonra son başarılı dönemden (veya ondan sonraki ilk başarısız dönemden) bugüne kadarki
+// dönemler sırayla işlenir, böylece uygulama kapalıyken kaçırılan dönemler tamamlanır.
+// Üyede o dönem için kayıt varsa (silinmiş olsa bile) tekrar oluşturulmaz; kontrol
+// kayıtla aynı transaction içindedir, iki çalışma aynı aidatı açamaz. Kayıt kimliği
+// tenant/üye/dönemden türetilir (aidat_takip'te üye+dönem zaten tekildir); sync'ten önce
+// aynı dönemi işleyen iki cihaz aynı kaydı üretir, sunucuda mükerrer borç oluşmaz.
+// Zamanlayıcı yalnızca tenants.otomatik_aidat_tahakkuku açık tenant'larda çalışır.
+// Aile tutarı tanımlı hanelerde aidat yalnızca ödeyene açılır (bkz. db::hane).
+
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::db::aidat_fiyat::FiyatMotoru;
+use crate::db::denetim::Denetim;
+use crate::db::models::Uye;
+use crate::db::outbox::{self, TxError};
+use crate::db::para::Para;
+use crate::db::hane::{self, HaneAidati, HaneKarari, HaneTahakkuku};
+use crate::db::{avans, donem_kilidi, uyelik_durumu, yevmiye};
+
+/// Bir çalıştırmada aidat tipi başına tamamlanan en fazla dönem; kalanlar sonraki çalışmaya kalır
+pub const AZAMI_TELAFI_DONEMI: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AidatTipi {
+    Yillik,
+    UcAylik,
+    Aylik,
+}
+
+impl AidatTipi {
+    pub const HEPSI: [AidatTipi; 3] = [AidatTipi::Yillik, AidatTipi::UcAylik, AidatTipi::Aylik];
+
+    /// "Yıllık", "Üç aylık", "Aylık" (büyük/küçük harf, boşluk ve Türkçe karakter farkı gözetmez)
+    pub fn coz(s: &str) -> Option<Self> {
+        let sade: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                'ı' | 'I' | 'İ' => 'i',
+                'ü' | 'Ü' => 'u',
+                'ç' | 'Ç' => 'c',
+                c => c.to_ascii_lowercase(),
+            })
+            .collect();
+        match sade.as_str() {
+            "yillik" => Some(AidatTipi::Yillik),
+            "ucaylik" | "3aylik" => Some(AidatTipi::UcAylik),
+            "aylik" => Some(AidatTipi::Aylik),
+            _ => None,
+        }
+    }
+
+    pub fn ad(self) -> &'static str {
+        match self {
+            AidatTipi::Yillik => "Yıllık",
+            AidatTipi::UcAylik => "Üç aylık",
+            AidatTipi::Aylik => "Aylık",
+        }
+    }
+
+    fn ay_adimi(self) -> i32 {
+        match self {
+            AidatTipi::Yillik => 12,
+            AidatTipi::UcAylik => 3,
+            AidatTipi::Aylik => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Donem {
+    pub yil: i32,
+    pub ay: i32,
+}
+
+impl Donem {
+    /// Tarihi içeren dönem
+    pub fn tarihten(tip: AidatTipi, tarih: NaiveDate) -> Self {
+        let adim = tip.ay_adimi();
+        Donem { yil: tarih.year(), ay: (tarih.month() as i32 - 1) / adim * adim + 1 }
+    }
+
+    pub fn sonraki(self, tip: AidatTipi) -> Self {
+        let ay_sirasi = self.yil * 12 + self.ay - 1 + tip.ay_adimi();
+        Donem { yil: ay_sirasi.div_euclid(12), ay: ay_sirasi.rem_euclid(12) + 1 }
+    }
+
+    pub fn baslangic(self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.yil, self.ay as u32, 1).expect("geçerli dönem")
+    }
+
+    pub fn bitis(self, tip: AidatTipi) -> NaiveDate {
+        self.sonraki(tip).baslangic().pred_opt().expect("geçerli dönem")
+    }
+
+    /// "2026", "2026/Ç2", "2026/03"
+    pub fn etiket(self, tip: AidatTipi) -> String {
+        match tip {
+            AidatTipi::Yillik => self.yil.to_string(),
+            AidatTipi::UcAylik => format!("{}/Ç{}", self.yil, (self.ay - 1) / 3 + 1),
+            AidatTipi::Aylik => format!("{}/{:02}", self.yil, self.ay),
+        }
+    }
+}
+
+/// İşlenmesi gereken dönemler (eskiden yeniye). gecmis: daha önce çalışılmış dönemler ve
+/// başarılı olup olmadıkları. Son başarılı dönemden sonra başarısız kalan ilk dönemden,
+/// yoksa son başarılı dönemin ardından, hiç çalışma yoksa cari dönemden başlanır.
+pub fn bekleyen_donemler(tip: AidatTipi, gecmis: &[(Donem, bool)], bugun: NaiveDate) -> Vec<Donem> {
+    let cari = Donem::tarihten(tip, bugun);
+    let son_basarili = gecmis.iter().filter(|(_, b)| *b).map(|(d, _)| *d).max();
+    let ilk_basarisiz = gecmis
+        .iter()
+        .filter(|(d, b)| !*b && son_basarili.is_none_or(|s| *d > s))
+        .map(|(d, _)| *d)
+        .min();
+
+    let mut donem = match (ilk_basarisiz, son_basarili) {
+        (Some(d), _) => d,
+        (None, Some(s)) => s.sonraki(tip),
+        (None, None) => cari,
+    };
+
+    let mut donemler = Vec::new();
+    while donem <= cari && donemler.len() < AZAMI_TELAFI_DONEMI {
+        donemler.push(donem);
+        donem = donem.sonraki(tip);
+    }
+    donemler
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct TahakkukCalismasi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub aidat_tipi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub donem: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub ay: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tetikleyen: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kullanici_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub olusturulan: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub atlanan: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub hatali: i32,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub toplam_tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub hata: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslangic: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub bitis: String,
+}
+
+/// Üyeliğe kabulde açılan ilk aidat
+#[derive(Debug, Clone, Serialize)]
+pub struct IlkAidat {
+    pub aidat_tipi: String,
+    pub donem: String,
+    pub tutar: Para,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BekleyenDonem {
+    pub aidat_tipi: String,
+    pub donem: String,
+    pub yil: i32,
+    pub ay: i32,
+}
+
+/// Tahakkuku kimin başlattığı: zamanlayıcı (kullanıcı yok) veya elle çalıştıran yönetici
+pub struct Tetikleyen {
+    pub tur: &'static str,
+    pub kullanici_id: Option<String>,
+    pub kullanici_adi: Option<String>,
+}
+
+impl Tetikleyen {
+    pub fn zamanlayici() -> Self {
+        Tetikleyen { tur: "zamanlayici", kullanici_id: None, kullanici_adi: None }
+    }
+
+    pub fn manuel(kullanici_id: Option<String>, kullanici_adi: Option<String>) -> Self {
+        Tetikleyen { tur: "manuel", kullanici_id, kullanici_adi }
+    }
+}
+
+#[derive(QueryableByName)]
+struct TanimTipiRow {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    uye_turu: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    aidat_tipi: Option<String>,
+}
+
+fn tanim_tipi(row: &TanimTipiRow) -> AidatTipi {
+    row.aidat_tipi.as_deref().and_then(AidatTipi::coz).unwrap_or(AidatTipi::Yillik)
+}
+
+/// Aktif aidat tanımı bulunan tipler
+fn kullanilan_tipler(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<AidatTipi>, String> {
+    let satirlar = diesel::sql_query(
+        "SELECT DISTINCT NULL AS uye_turu, aidat_tipi FROM aidat_tanimlari WHERE tenant_id = ?1 AND is_active = 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load::<TanimTipiRow>(conn)
+    .map_err(|e| e.to_string())?;
+
+    let kullanilan: HashSet<AidatTipi> = satirlar.iter().map(tanim_tipi).collect();
+    Ok(AidatTipi::HEPSI.into_iter().filter(|t| kullanilan.contains(t)).collect())
+}
+
+/// O yılın bu tipte tanımı olan üye türleri
+fn donem_uye_turleri(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tip: AidatTipi,
+    yil: i32,
+) -> Result<HashSet<String>, String> {
+    let satirlar = diesel::sql_query(
+        "SELECT uye_turu, aidat_tipi FROM aidat_tanimlari WHERE tenant_id = ?1 AND yil = ?2 AND is_active = 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .load::<TanimTipiRow>(conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(satirlar
+        .into_iter()
+        .filter(|r| tanim_tipi(r) == tip)
+        .map(|r| r.uye_turu.unwrap_or_else(|| "Asil".to_string()))
+        .collect())
+}
+
+fn calisma_gecmisi(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tip: AidatTipi,
+) -> Result<Vec<(Donem, bool)>, String> {
+    #[derive(QueryableByName)]
+    struct GecmisRow {
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        basarili: bool,
+    }
+
+    Ok(diesel::sql_query(
+        "SELECT yil, ay, MAX(durum = 'basarili') AS basarili FROM aidat_tahakkuk_calismalari
+         WHERE tenant_id = ?1 AND aidat_tipi = ?2
+         GROUP BY yil, ay",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(tip.ad())
+    .load::<GecmisRow>(conn)
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .map(|r| (Donem { yil: r.yil, ay: r.ay }, r.basarili))
+    .collect())
+}
+
+/// Çalıştırılırsa işlenecek dönemler
+pub fn bekleyenleri_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    bugun: NaiveDate,
+) -> Result<Vec<BekleyenDonem>, String> {
+    let mut bekleyenler = Vec::new();
+    for tip in kullanilan_tipler(conn, tenant_id)? {
+        let gecmis = calisma_gecmisi(conn, tenant_id, tip)?;
+        for donem in bekleyen_donemler(tip, &gecmis, bugun) {
+            bekleyenler.push(BekleyenDonem {
+                aidat_tipi: tip.ad().to_string(),
+                donem: donem.etiket(tip),
+                yil: donem.yil,
+                ay: donem.ay,
+            });
+        }
+    }
+    Ok(bekleyenler)
+}
+
+pub fn calismalari_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    limit: i64,
+) -> Result<Vec<TahakkukCalismasi>, String> {
+    diesel::sql_query(
+        "SELECT id, aidat_tipi, donem, yil, ay, tetikleyen, kullanici_id, durum, olusturulan, atlanan,
+                hatali, toplam_tutar, hata, baslangic, bitis
+         FROM aidat_tahakkuk_calismalari
+         WHERE tenant_id = ?1
+         ORDER BY baslangic DESC, yil DESC, ay DESC
+         LIMIT ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::BigInt, _>(limit)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Zamanlayıcının işleyeceği tenant'lar: aktif ve otomatik tahakkuku açık olanlar
+pub fn zamanlanmis_kiracilar(conn: &mut SqliteConnection) -> Result<Vec<String>, String> {
+    #[derive(QueryableByName)]
+    struct TenantRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    Ok(diesel::sql_query("SELECT id FROM tenants WHERE is_active = 1 AND otomatik_aidat_tahakkuku = 1")
+        .load::<TenantRow>(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| r.id)
+        .collect())
+}
+
+pub fn otomatik_tahakkuk_acik_mi(conn: &mut SqliteConnection, tenant_id: &str) -> Result<bool, String> {
+    #[derive(QueryableByName)]
+    struct AcikRow {
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        acik: bool,
+    }
+
+    diesel::sql_query("SELECT otomatik_aidat_tahakkuku AS acik FROM tenants WHERE id = ?1")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result::<AcikRow>(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .map(|r| r.acik)
+        .ok_or_else(|| "Dernek bulunamadı".to_string())
+}
+
+pub fn otomatik_tahakkuk_ayarla(conn: &mut SqliteConnection, tenant_id: &str, acik: bool) -> Result<(), String> {
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let guncellenen = diesel::sql_query("UPDATE tenants SET otomatik_aidat_tahakkuku = ?1, updated_at = ?2 WHERE id = ?3")
+        .bind::<diesel::sql_types::Bool, _>(acik)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    if guncellenen == 0 {
+        return Err("Dernek bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+/// Sunucudan gelen aidat kaydı, üyenin aynı dönemdeki başka kimlikli yerel kaydıyla
+/// çakışıyor mu (ör. bir cihazda elle, diğerinde zamanlayıcıyla açılmış). Yerelde
+/// üye+dönem tekil olduğundan böyle bir kayıt uygulanamaz; sync onu atlar.
+pub fn uzak_aidat_cakisiyor_mu(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    record_id: &str,
+    data: &serde_json::Value,
+) -> Result<bool, String> {
+    #[derive(QueryableByName)]
+    struct SayiRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    if data.get("is_deleted").is_some_and(|v| v.as_i64() == Some(1) || v.as_bool() == Some(true)) {
+        return Ok(false);
+    }
+    let (Some(uye_id), Some(yil), Some(ay)) = (
+        data.get("uye_id").and_then(|v| v.as_str()),
+        data.get("yil").and_then(|v| v.as_i64()),
+        data.get("ay").and_then(|v| v.as_i64()),
+    ) else {
+        return Ok(false);
+    };
+
+    let sayi = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM aidat_takip
+         WHERE tenant_id = ?1 AND uye_id = ?2 AND yil = ?3 AND ay = ?4 AND id != ?5",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .bind::<diesel::sql_types::BigInt, _>(yil)
+    .bind::<diesel::sql_types::BigInt, _>(ay)
+    .bind::<diesel::sql_types::Text, _>(record_id)
+    .get_result::<SayiRow>(conn)
+    .map_err(|e| e.to_string())?
+    .sayi;
+    Ok(sayi > 0)
+}
+
+/// Bekleyen tüm dönemleri işler ve her dönem için bir çalışma kaydı döner
+pub fn calistir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    bugun: NaiveDate,
+    tetikleyen: &Tetikleyen,
+) -> Result<Vec<TahakkukCalismasi>, String> {
+    let mut calismalar = Vec::new();
+    for tip in kullanilan_tipler(conn, tenant_id)? {
+        let gecmis = calisma_gecmisi(conn, tenant_id, tip)?;
+        for donem in bekleyen_donemler(tip, &gecmis, bugun) {
+            if let Some(calisma) = donemi_isle(conn, tenant_id, tip, donem, tetikleyen)? {
+                calismalar.push(calisma);
+            }
+        }
+    }
+    Ok(calismalar)
+}
+
+/// Tek dönem; o yıl bu tipte tanım yoksa None (kayıt açılmaz, dönem bekler)
+fn donemi_isle(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tip: AidatTipi,
+    donem: Donem,
+    tetikleyen: &Tetikleyen,
+) -> Result<Option<TahakkukCalismasi>, String> {
+    let turler = donem_uye_turleri(conn, tenant_id, tip, donem.yil)?;
+    if turler.is_empty() {
+        return Ok(None);
+    }
+
+    let mut calisma = TahakkukCalismasi {
+        id: Uuid::new_v4().to_string(),
+        aidat_tipi: tip.ad().to_string(),
+        donem: donem.etiket(tip),
+        yil: donem.yil,
+        ay: donem.ay,
+        tetikleyen: tetikleyen.tur.to_string(),
+        kullanici_id: tetikleyen.kullanici_id.clone(),
+        durum: "basarili".to_string(),
+        olusturulan: 0,
+        atlanan: 0,
+        hatali: 0,
+        toplam_tutar: Para::SIFIR,
+        hata: None,
+        baslangic: Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        bitis: String::new(),
+    };
+
+    if let Err(hata) = donem_kilidi::donemi_dogrula(conn, tenant_id, donem.yil, donem.ay) {
+        // Kilit açılana kadar her çalışmada aynı hata tekrar yazılmaz
+        if son_hata(conn, tenant_id, tip, donem)?.as_deref() == Some(hata.as_str()) {
+            return Ok(None);
+        }
+        calisma.durum = "hatali".to_string();
+        calisma.hata = Some(hata);
+        calismayi_kaydet(conn, tenant_id, &mut calisma)?;
+        return Ok(Some(calisma));
+    }
+
+    let mut motor = FiyatMotoru::yukle(conn, tenant_id, donem.yil, None)?;
+    if tip != AidatTipi::Yillik {
+        motor = motor.kist_haric();
+    }
+
+    // Dönemin bir kısmında asil olan üyeler (aday, askıda ve ayrılmış günler sayılmaz)
+    let asiller = uyelik_durumu::donemde_asil_uyeler(conn, tenant_id, donem.baslangic(), donem.bitis(tip))?;
+    let uyeler: Vec<Uye> = diesel::sql_query("SELECT * FROM uyeler WHERE tenant_id = ?1 ORDER BY uye_no")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+
+    let uyeler: Vec<Uye> = uyeler
+        .into_iter()
+        .filter(|u| asiller.contains(&u.id) && turler.contains(u.uyelik_tipi.as_deref().unwrap_or("Asil")))
+        .collect();
+    let haneler = HaneTahakkuku::yukle(
+        conn,
+        tenant_id,
+        donem.yil,
+        uyeler.iter().map(|u| (u.id.as_str(), u.uyelik_tipi.as_deref().unwrap_or("Asil"))),
+    )?;
+
+    let mut denetim = Denetim::yeni(
+        tetikleyen.kullanici_id.clone(),
+        tetikleyen.kullanici_adi.clone(),
+        "aidat_tahakkuku",
+    );
+    let mut hatalar = Vec::new();
+    for uye in uyeler {
+        let (fiyat, hane) = match haneler.karar(&uye.id) {
+            HaneKarari::Bireysel => (motor.hesapla(&uye), None),
+            HaneKarari::Odeyen(h) => (h.fiyat(), Some(h)),
+            HaneKarari::Kapsanan(_) => {
+                calisma.atlanan += 1;
+                continue;
+            }
+        };
+        if fiyat.tutar.is_zero() {
+            calisma.atlanan += 1;
+            continue;
+        }
+        let notlar = match fiyat.ozet() {
+            Some(ozet) => format!("Otomatik tahakkuk ({}) · {}", calisma.donem, ozet),
+            None => format!("Otomatik tahakkuk ({})", calisma.donem),
+        };
+
+        match uye_aidati_olustur(conn, &mut denetim, tenant_id, tip, donem, &uye.id, fiyat.tutar, &notlar, hane) {
+            Ok(true) => {
+                calisma.olusturulan += 1;
+                calisma.toplam_tutar += fiyat.tutar;
+            }
+            Ok(false) => calisma.atlanan += 1,
+            Err(e) => {
+                calisma.hatali += 1;
+                hatalar.push(format!("{}: {}", uye.uye_no, e));
+            }
+        }
+    }
+
+    if !hatalar.is_empty() {
+        calisma.durum = "kismi".to_string();
+        calisma.hata = Some(hatalar.join("\n"));
+    }
+    calismayi_kaydet(conn, tenant_id, &mut calisma)?;
+    Ok(Some(calisma))
+}
+
+/// Yeni kabul edilen üyeye giriş tarihinin dönemi için aidat açar: tanımı olan her aidat
+/// tipinde zamanlanmış tahakkukla aynı fiyat (kıst ve hane dahil). Dönem daha sonra
+/// zamanlayıcıyla işlenirse mevcut kayıt bulunur ve üye atlanır.
+/// Denetim kaydı aidatla birlikte yazıldığı için çağıranın son yazımı olmalıdır.
+pub fn ilk_aidatlari_olustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_id: &str,
+    giris: NaiveDate,
+) -> Result<Vec<IlkAidat>, String> {
+    let uye: Uye = diesel::sql_query("SELECT * FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .map_err(|e| e.to_string())?;
+    let uye_turu = uye.uyelik_tipi.clone().unwrap_or_else(|| "Asil".to_string());
+
+    let mut aidatlar = Vec::new();
+    for tip in kullanilan_tipler(conn, tenant_id)? {
+        let donem = Donem::tarihten(tip, giris);
+        if !donem_uye_turleri(conn, tenant_id, tip, donem.yil)?.contains(&uye_turu) {
+            continue;
+        }
+        donem_kilidi::donemi_dogrula(conn, tenant_id, donem.yil, donem.ay)?;
+
+        let mut motor = FiyatMotoru::yukle(conn, tenant_id, donem.yil, None)?;
+        if tip != AidatTipi::Yillik {
+            motor = motor.kist_haric();
+        }
+        let haneler = HaneTahakkuku::aktif_uyelerle(conn, tenant_id, donem.yil)?;
+        let (fiyat, hane) = match haneler.karar(&uye.id) {
+            HaneKarari::Bireysel => (motor.hesapla(&uye), None),
+            HaneKarari::Odeyen(h) => (h.fiyat(), Some(h)),
+            HaneKarari::Kapsanan(_) => continue,
+        };
+        if fiyat.tutar.is_zero() {
+            continue;
+        }
+        let etiket = donem.etiket(tip);
+        let notlar = match fiyat.ozet() {
+            Some(ozet) => format!("Üyelik kabulü ilk aidat ({}) · {}", etiket, ozet),
+            None => format!("Üyelik kabulü ilk aidat ({})", etiket),
+        };
+        if uye_aidati_olustur(conn, denetim, tenant_id, tip, donem, &uye.id, fiyat.tutar, &notlar, hane)? {
+            aidatlar.push(IlkAidat { aidat_tipi: tip.ad().to_string(), donem: etiket, tutar: fiyat.tutar });
+        }
+    }
+    Ok(aidatlar)
+}
+
+/// Dönem aidatının kimliği: her cihazda aynı tenant/üye/dönem için aynı UUID
+fn donem_aidat_id(tenant_id: &str, uye_id: &str, donem: Donem) -> String {
+    let ozet = Sha256::digest(format!("aidat_takip:{}:{}:{}:{}", tenant_id, uye_id, donem.yil, donem.ay));
+    let mut baytlar = [0u8; 16];
+    baytlar.copy_from_slice(&ozet[..16]);
+    uuid::Builder::from_custom_bytes(baytlar).into_uuid().to_string()
+}
+
+/// Aidatı açar; üyede bu dönem için kayıt zaten varsa false. Hane aidatında üye payları da yazılır.
+#[allow(clippy::too_many_arguments)]
+fn uye_aidati_olustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    tip: AidatTipi,
+    donem: Donem,
+    uye_id: &str,
+    tutar: Para,
+    notlar: &str,
+    hane: Option<&HaneAidati>,
+) -> Result<bool, String> {
+    #[derive(QueryableByName)]
+    struct SayiRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    conn.transaction::<_, TxError, _>(|conn| {
+        // Yıllık aidatta ay bakılmaz: elle (ay = 1) açılmış yıllık kayıt da sayılır
+        let mevcut = diesel::sql_query(
+            "SELECT COUNT(*) AS sayi FROM aidat_takip
+             WHERE tenant_id = ?1 AND uye_id = ?2 AND yil = ?3 AND (?4 = 1 OR ay = ?5)",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Integer, _>(donem.yil)
+        .bind::<diesel::sql_types::Bool, _>(tip == AidatTipi::Yillik)
+        .bind::<diesel::sql_types::Integer, _>(donem.ay)
+        .get_result::<SayiRow>(conn)?
+        .sayi;
+        if mevcut > 0 {
+            return Ok(false);
+        }
+
+        let aidat_id = donem_aidat_id(tenant_id, uye_id, donem);
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        // Aynı kimlik ya da dönem kaydı (başka cihazdan gelmiş, silinmiş olsa bile) varsa eklenmez
+        let eklenen = diesel::sql_query(
+            "INSERT INTO aidat_takip (
+                id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan,
+                durum, gecikme_gun, gecikme_faiz, notlar, aktarim_durumu,
+                created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind::<diesel::sql_types::Text, _>(&aidat_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Integer, _>(donem.yil)
+        .bind::<diesel::sql_types::Integer, _>(donem.ay)
+        .bind::<diesel::sql_types::Double, _>(tutar)
+        .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
+        .bind::<diesel::sql_types::Double, _>(tutar)
+        .bind::<diesel::sql_types::Text, _>("beklemede")
+        .bind::<diesel::sql_types::Integer, _>(0)
+        .bind::<diesel::sql_types::Double, _>(Para::SIFIR)
+        .bind::<diesel::sql_types::Text, _>(notlar)
+        .bind::<diesel::sql_types::Text, _>("Bekliyor")
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)?;
+        if eklenen == 0 {
+            return Ok(false);
+        }
+        denetim.yeni_kayit(tenant_id, "aidat_takip", &aidat_id);
+
+        if let Some(hane) = hane {
+            hane::paylari_yaz(conn, tenant_id, &aidat_id, hane).map_err(TxError::Msg)?;
+        }
+        outbox::queue_change(conn, tenant_id, "aidat_takip", &aidat_id, "create").map_err(TxError::Msg)?;
+        yevmiye::kaydi_isle(conn, tenant_id, "aidat_takip", &aidat_id).map_err(TxError::Msg)?;
+        // Üyenin avans bakiyesi varsa yeni borca (ve varsa eski açıklara) mahsup edilir
+        avans::mahsup_et(conn, denetim, tenant_id, uye_id, &now[..10]).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(true)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Dönemin son çalışması hatalıysa hata metni
+fn son_hata(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tip: AidatTipi,
+    donem: Donem,
+) -> Result<Option<String>, String> {
+    #[derive(QueryableByName)]
+    struct HataRow {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        hata: Option<String>,
+    }
+
+    Ok(diesel::sql_query(
+        "SELECT CASE WHEN durum = 'hatali' THEN hata END AS hata FROM aidat_tahakkuk_calismalari
+         WHERE tenant_id = ?1 AND aidat_tipi = ?2 AND yil = ?3 AND ay = ?4
+         ORDER BY baslangic DESC LIMIT 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(tip.ad())
+    .bind::<diesel::sql_types::Integer, _>(donem.yil)
+    .bind::<diesel::sql_types::Integer, _>(donem.ay)
+    .get_result::<HataRow>(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .and_then(|r| r.hata))
+}
+
+fn calismayi_kaydet(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    calisma: &mut TahakkukCalismasi,
+) -> Result<(), String> {
+    calisma.bitis = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO aidat_tahakkuk_calismalari
+            (id, tenant_id, aidat_tipi, donem, yil, ay, tetikleyen, kullanici_id, durum,
+             olusturulan, atlanan, hatali, toplam_tutar, hata, baslangic, bitis)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&calisma.id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&calisma.aidat_tipi)
+    .bind::<diesel::sql_types::Text, _>(&calisma.donem)
+    .bind::<diesel::sql_types::Integer, _>(calisma.yil)
+    .bind::<diesel::sql_types::Integer, _>(calisma.ay)
+    .bind::<diesel::sql_types::Text, _>(&calisma.tetikleyen)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&calisma.kullanici_id)
+    .bind::<diesel::sql_types::Text, _>(&calisma.durum)
+    .bind::<diesel::sql_types::Integer, _>(calisma.olusturulan)
+    .bind::<diesel::sql_types::Integer, _>(calisma.atlanan)
+    .bind::<diesel::sql_types::Integer, _>(calisma.hatali)
+    .bind::<diesel::sql_types::Double, _>(calisma.toplam_tutar)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&calisma.hata)
+    .bind::<diesel::sql_types::Text, _>(&calisma.baslangic)
+    .bind::<diesel::sql_types::Text, _>(&calisma.bitis)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tarih(y: i32, a: u32, g: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, a, g).unwrap()
+    }
+
+    fn d(yil: i32, ay: i32) -> Donem {
+        Donem { yil, ay }
+    }
+
+    #[test]
+    fn aidat_tipi_cozumlenir() {
+        assert_eq!(AidatTipi::coz("Yıllık"), Some(AidatTipi::Yillik));
+        assert_eq!(AidatTipi::coz("YILLIK"), Some(AidatTipi::Yillik));
+        assert_eq!(AidatTipi::coz("Üç aylık"), Some(AidatTipi::UcAylik));
+        assert_eq!(AidatTipi::coz("Üç Aylık"), Some(AidatTipi::UcAylik));
+        assert_eq!(AidatTipi::coz(" aylik "), Some(AidatTipi::Aylik));
+        assert_eq!(AidatTipi::coz("Haftalık"), None);
+        for tip in AidatTipi::HEPSI {
+            assert_eq!(AidatTipi::coz(tip.ad()), Some(tip));
+        }
+    }
+
+    #[test]
+    fn donem_hesaplari() {
+        let bugun = tarih(2026, 8, 17);
+        assert_eq!(Donem::tarihten(AidatTipi::Yillik, bugun), d(2026, 1));
+        assert_eq!(Donem::tarihten(AidatTipi::UcAylik, bugun), d(2026, 7));
+        assert_eq!(Donem::tarihten(AidatTipi::Aylik, bugun), d(2026, 8));
+
+        assert_eq!(d(2026, 12).sonraki(AidatTipi::Aylik), d(2027, 1));
+        assert_eq!(d(2026, 10).sonraki(AidatTipi::UcAylik), d(2027, 1));
+        assert_eq!(d(2026, 1).sonraki(AidatTipi::Yillik), d(2027, 1));
+
+        assert_eq!(d(2028, 2).bitis(AidatTipi::Aylik), tarih(2028, 2, 29));
+        assert_eq!(d(2026, 4).bitis(AidatTipi::UcAylik), tarih(2026, 6, 30));
+        assert_eq!(d(2026, 1).bitis(AidatTipi::Yillik), tarih(2026, 12, 31));
+
+        assert_eq!(d(2026, 10).etiket(AidatTipi::UcAylik), "2026/Ç4");
+        assert_eq!(d(2026, 3).etiket(AidatTipi::Aylik), "2026/03");
+    }
+
+    #[test]
+    fn kacirilan_donemler_tamamlanir() {
+        let bugun = tarih(2026, 3, 5);
+
+        // İlk çalışma: yalnızca cari dönem
+        assert_eq!(bekleyen_donemler(AidatTipi::Aylik, &[], bugun), vec![d(2026, 3)]);
+
+        // Uygulama Kasım'dan beri kapalı
+        assert_eq!(
+            bekleyen_donemler(AidatTipi::Aylik, &[(d(2025, 11), true)], bugun),
+            vec![d(2025, 12), d(2026, 1), d(2026, 2), d(2026, 3)]
+        );
+
+        // Cari dönem tamamlanmış: bekleyen yok
+        assert!(bekleyen_donemler(AidatTipi::UcAylik, &[(d(2026, 1), true)], bugun).is_empty());
+
+        // Başarısız kalan dönem yeniden denenir, ondan öncekiler yeniden denenmez
+        let gecmis = [(d(2025, 12), false), (d(2026, 1), true), (d(2026, 2), false)];
+        assert_eq!(bekleyen_donemler(AidatTipi::Aylik, &gecmis, bugun), vec![d(2026, 2), d(2026, 3)]);
+
+        // Uzun kesintide bir çalışmada en fazla AZAMI_TELAFI_DONEMI dönem
+        let uzun = bekleyen_donemler(AidatTipi::Aylik, &[(d(2020, 1), true)], bugun);
+        assert_eq!(uzun.len(), AZAMI_TELAFI_DONEMI);
+        assert_eq!(uzun[0], d(2020, 2));
+    }
+
+    fn aidat_ac(conn: &mut SqliteConnection, donem: Donem) -> bool {
+        let mut denetim = Denetim::yeni(None, None, "test");
+        uye_aidati_olustur(conn, &mut denetim, "t1", AidatTipi::Aylik, donem, "u1", Para::from_tl(100.0), "Otomatik tahakkuk", None)
+            .unwrap()
+    }
+
+    fn aidat_idleri(conn: &mut SqliteConnection) -> Vec<String> {
+        #[derive(QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            id: String,
+        }
+        diesel::sql_query("SELECT id FROM aidat_takip ORDER BY yil, ay")
+            .load::<Row>(conn)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect()
+    }
+
+    #[test]
+    fn iki_cihaz_ayni_donemi_ayni_kayitla_acar() {
+        let cihaz = || {
+            let mut conn = crate::db::test_db::baglanti();
+            crate::db::test_db::kiraci(&mut conn, "t1", "Dernek");
+            crate::db::test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+            conn
+        };
+        let (mut a, mut b) = (cihaz(), cihaz());
+        assert!(aidat_ac(&mut a, d(2026, 3)));
+        assert!(aidat_ac(&mut b, d(2026, 3)));
+        assert_eq!(aidat_idleri(&mut a), aidat_idleri(&mut b));
+
+        // Aynı cihazda ikinci çalışma kayıt açmaz
+        assert!(!aidat_ac(&mut a, d(2026, 3)));
+        assert!(aidat_ac(&mut a, d(2026, 4)));
+        assert_eq!(aidat_idleri(&mut a).len(), 2);
+    }
+
+    #[test]
+    fn uzaktan_gelen_ayni_donem_aidati_cakisir() {
+        let mut conn = crate::db::test_db::baglanti();
+        crate::db::test_db::kiraci(&mut conn, "t1", "Dernek");
+        crate::db::test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        assert!(aidat_ac(&mut conn, d(2026, 3)));
+        let yerel = aidat_idleri(&mut conn).remove(0);
+
+        let uzak = serde_json::json!({ "uye_id": "u1", "yil": 2026, "ay": 3, "tutar": 100.0 });
+        assert!(uzak_aidat_cakisiyor_mu(&mut conn, "t1", "uzak", &uzak).unwrap());
+        // Kendi kaydının güncellemesi, başka dönem ve silme çakışma sayılmaz
+        assert!(!uzak_aidat_cakisiyor_mu(&mut conn, "t1", &yerel, &uzak).unwrap());
+        let nisan = serde_json::json!({ "uye_id": "u1", "yil": 2026, "ay": 4 });
+        assert!(!uzak_aidat_cakisiyor_mu(&mut conn, "t1", "uzak", &nisan).unwrap());
+        let silme = serde_json::json!({ "uye_id": "u1", "yil": 2026, "ay": 3, "is_deleted": 1 });
+        assert!(!uzak_aidat_cakisiyor_mu(&mut conn, "t1", "uzak", &silme).unwrap());
+    }
+
+    #[test]
+    fn zamanlayici_yalnizca_acik_tenantlarda_calisir() {
+        let mut conn = crate::db::test_db::baglanti();
+        crate::db::test_db::kiraci(&mut conn, "t1", "Dernek");
+        crate::db::test_db::kiraci(&mut conn, "t2", "Vakıf");
+        // Güncellemeden sonra varsayılan kapalı
+        assert!(zamanlanmis_kiracilar(&mut conn).unwrap().is_empty());
+        assert!(!otomatik_tahakkuk_acik_mi(&mut conn, "t1").unwrap());
+
+        otomatik_tahakkuk_ayarla(&mut conn, "t2", true).unwrap();
+        assert_eq!(zamanlanmis_kiracilar(&mut conn).unwrap(), vec!["t2".to_string()]);
+        assert!(otomatik_tahakkuk_acik_mi(&mut conn, "t2").unwrap());
+        assert!(otomatik_tahakkuk_ayarla(&mut conn, "yok", true).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+// Alındı belgesi ciltleri ve numara verme.
+//
+// Dernekler Yönetmeliği'ne göre gelirler matbaada bastırılmış, seri ve sıra
+// numaralı alındı belgeleriyle tahsil edilir; ciltler yetki belgesi verilmiş
+// görevliye (tahsil eden) teslim edilir. Burada:
+//   - oturumdaki kullanıcıya teslim edilmiş aktif ciltten sıradaki numara verilir
+//     (create_gelir ve aidat tahsilatları); ciltte numara kalmamışsa veya yetki
+//     belgesinin süresi tahsilat tarihini kapsamıyorsa kayıt engellenir,
+//   - kullanıcıya hiç cilt teslim edilmemişse eski davranış sürer (makbuz_no serbest).
+// Numara verme yazımla aynı transaction içinde çağrılır; (cilt_id, sira_no) tekil
+// olduğundan aynı numara iki gelire verilemez.
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AyrilanMakbuz {
+    pub cilt_id: String,
+    pub sira_no: i64,
+    pub makbuz_no: String,
+    /// Cildi teslim alan görevlinin adı (gelirler.tahsil_eden boşsa yazılır)
+    pub tahsil_eden: Option<String>,
+}
+
+/// "A-000123"; serisiz ciltlerde yalnızca sıra numarası
+pub fn makbuz_no_bicimle(seri: &str, sira_no: i64) -> String {
+    let seri = seri.trim();
+    if seri.is_empty() {
+        format!("{:06}", sira_no)
+    } else {
+        format!("{}-{:06}", seri, sira_no)
+    }
+}
+
+/// Cildin başından son verilen numaraya kadar hiç kaydı olmayan aralıklar
+/// (kullanilan: kesilmiş veya iptal edilmiş sıra numaraları, sıralı)
+pub fn bosluklari_bul(baslangic_no: i64, kullanilan: &[i64]) -> Vec<(i64, i64)> {
+    let mut bosluklar = Vec::new();
+    let mut beklenen = baslangic_no;
+    for &no in kullanilan {
+        if no > beklenen {
+            bosluklar.push((beklenen, no - 1));
+        }
+        beklenen = beklenen.max(no + 1);
+    }
+    bosluklar
+}
+
+/// Aynı seride sıra numarası aralığı başka bir cilde çakışıyor mu
+pub fn aralik_cakisiyor(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    seri: &str,
+    baslangic_no: i64,
+    bitis_no: i64,
+    haric_cilt_id: Option<&str>,
+) -> Result<bool, diesel::result::Error> {
+    #[derive(QueryableByName)]
+    struct Sayi {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+    let row: Sayi = diesel::sql_query(
+        "SELECT COUNT(*) as sayi FROM alindi_belgesi_ciltleri
+         WHERE tenant_id = ?1 AND seri = ?2 AND baslangic_no <= ?4 AND bitis_no >= ?3
+           AND (?5 IS NULL OR id != ?5)",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(seri.trim())
+    .bind::<diesel::sql_types::BigInt, _>(baslangic_no)
+    .bind::<diesel::sql_types::BigInt, _>(bitis_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(haric_cilt_id)
+    .get_result(conn)?;
+    Ok(row.sayi > 0)
+}
+
+#[derive(QueryableByName)]
+struct UygunCilt {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    seri: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    sonraki_no: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    bitis_no: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    yetki_baslangic: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    yetki_bitis: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    tahsil_eden: Option<String>,
+}
+
+/// Kullanıcının aktif cildinden `tarih` için sıradaki numarayı verir ve
+/// `gelir_id` adına kullanıldı olarak işaretler. Kullanıcıya cilt teslim
+/// edilmemişse None döner.
+pub fn numara_ayir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kullanici_id: Option<&str>,
+    tarih: &str,
+    gelir_id: &str,
+) -> Result<Option<AyrilanMakbuz>, String> {
+    let Some(kullanici_id) = kullanici_id else {
+        return Ok(None);
+    };
+    let gun: String = tarih.chars().take(10).collect();
+
+    let ciltler: Vec<UygunCilt> = diesel::sql_query(
+        "SELECT c.id, c.seri, c.bitis_no, c.yetki_baslangic, c.yetki_bitis, u.full_name as tahsil_eden,
+                COALESCE((SELECT MAX(k.sira_no) FROM alindi_belgesi_kullanimlari k WHERE k.cilt_id = c.id),
+                         c.baslangic_no - 1) + 1 as sonraki_no
+         FROM alindi_belgesi_ciltleri c
+         LEFT JOIN users u ON u.id = c.teslim_alan
+         WHERE c.tenant_id = ?1 AND c.teslim_alan = ?2 AND c.durum = 'aktif'
+         ORDER BY c.seri, c.baslangic_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(kullanici_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    if ciltler.is_empty() {
+        return Ok(None);
+    }
+
+    let yetkili = |c: &&UygunCilt| {
+        c.yetki_baslangic.as_deref().is_none_or(|b| b <= gun.as_str())
+            && c.yetki_bitis.as_deref().is_none_or(|b| b >= gun.as_str())
+    };
+    if !ciltler.iter().any(|c| yetkili(&c)) {
+        return Err(format!(
+            "Yetki belgeniz {} tarihli tahsilatı kapsamıyor; alındı belgesi kesilemez",
+            gun
+        ));
+    }
+    let cilt = ciltler
+        .iter()
+        .filter(yetkili)
+        .find(|c| c.sonraki_no <= c.bitis_no)
+        .ok_or("Teslim aldığınız alındı belgesi ciltlerinde boş numara kalmadı; yeni cilt teslim alınmalıdır")?;
+
+    let makbuz_no = makbuz_no_bicimle(&cilt.seri, cilt.sonraki_no);
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO alindi_belgesi_kullanimlari
+            (id, tenant_id, cilt_id, sira_no, makbuz_no, durum, gelir_id, tarih, kullanici_id, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 'kullanildi', ?6, ?7, ?8, ?9, ?9)",
+    )
+    .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&cilt.id)
+    .bind::<diesel::sql_types::BigInt, _>(cilt.sonraki_no)
+    .bind::<diesel::sql_types::Text, _>(&makbuz_no)
+    .bind::<diesel::sql_types::Text, _>(gelir_id)
+    .bind::<diesel::sql_types::Text, _>(&gun)
+    .bind::<diesel::sql_types::Text, _>(kullanici_id)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| format!("Alındı belgesi numarası verilemedi: {}", e))?;
+
+    Ok(Some(AyrilanMakbuz {
+        cilt_id: cilt.id.clone(),
+        sira_no: cilt.sonraki_no,
+        makbuz_no,
+        tahsil_eden: cilt.tahsil_eden.clone(),
+    }))
+}
+
+/// Eklenmiş gelire numara verir: makbuz_no yazılır, tahsil_eden boşsa cildin
+/// sahibi yazılır. Outbox kaydından önce çağrılmalıdır ki snapshot numarayı içersin.
+pub fn gelire_makbuz_kes(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kullanici_id: Option<&str>,
+    gelir_id: &str,
+) -> Result<Option<String>, String> {
+    if kullanici_id.is_none() {
+        return Ok(None);
+    }
+    #[derive(QueryableByName)]
+    struct GelirTarihi {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+    }
+    let gelir: GelirTarihi = diesel::sql_query("SELECT tarih FROM gelirler WHERE id = ?1")
+        .bind::<diesel::sql_types::Text, _>(gelir_id)
+        .get_result(conn)
+        .map_err(|e| e.to_string())?;
+
+    let Some(makbuz) = numara_ayir(conn, tenant_id, kullanici_id, &gelir.tarih, gelir_id)? else {
+        return Ok(None);
+    };
+    diesel::sql_query(
+        "UPDATE gelirler SET makbuz_no = ?1, tahsil_eden = COALESCE(tahsil_eden, ?2) WHERE id = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(&makbuz.makbuz_no)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&makbuz.tahsil_eden)
+    .bind::<diesel::sql_types::Text, _>(gelir_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(Some(makbuz.makbuz_no))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO users (id, tenant_id, username, email, password_hash, full_name, created_at, updated_at) VALUES
+                ('u1', 't1', 'ayse', 'ayse@ornek.org', '', 'Ayşe Tahsildar', '', ''),
+                ('u2', 't1', 'mehmet', 'mehmet@ornek.org', '', 'Mehmet', '', '')",
+        );
+        conn
+    }
+
+    fn cilt_ekle(conn: &mut SqliteConnection, id: &str, seri: &str, bas: i64, bit: i64, yetki_bitis: Option<&str>) {
+        diesel::sql_query(
+            "INSERT INTO alindi_belgesi_ciltleri (id, tenant_id, seri, baslangic_no, bitis_no, teslim_alan,
+                yetki_bitis, created_at, updated_at)
+             VALUES (?1, 't1', ?2, ?3, ?4, 'u1', ?5, '', '')",
+        )
+        .bind::<diesel::sql_types::Text, _>(id)
+        .bind::<diesel::sql_types::Text, _>(seri)
+        .bind::<diesel::sql_types::BigInt, _>(bas)
+        .bind::<diesel::sql_types::BigInt, _>(bit)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(yetki_bitis)
+        .execute(conn)
+        .unwrap();
+    }
+
+    #[test]
+    fn numaralar_sirayla_ve_cilt_bitince_sonrakinden() {
+        let mut conn = test_db();
+        cilt_ekle(&mut conn, "c1", "A", 1, 2, None);
+        cilt_ekle(&mut conn, "c2", "A", 51, 100, None);
+
+        let n: Vec<String> = (0..3)
+            .map(|i| {
+                numara_ayir(&mut conn, "t1", Some("u1"), "2026-03-01", &format!("g{}", i))
+                    .unwrap()
+                    .unwrap()
+                    .makbuz_no
+            })
+            .collect();
+        assert_eq!(n, vec!["A-000001", "A-000002", "A-000051"]);
+
+        // Cildi olmayan kullanıcı: numara verilmez, eski davranış
+        assert!(numara_ayir(&mut conn, "t1", Some("u2"), "2026-03-01", "g9").unwrap().is_none());
+        assert!(numara_ayir(&mut conn, "t1", None, "2026-03-01", "g9").unwrap().is_none());
+    }
+
+    #[test]
+    fn yetki_suresi_ve_dolu_cilt_engeller() {
+        let mut conn = test_db();
+        cilt_ekle(&mut conn, "c1", "", 10, 10, Some("2026-06-30"));
+
+        assert!(numara_ayir(&mut conn, "t1", Some("u1"), "2026-07-01", "g1").is_err());
+        let m = numara_ayir(&mut conn, "t1", Some("u1"), "2026-06-30 10:00:00", "g1").unwrap().unwrap();
+        assert_eq!(m.makbuz_no, "000010");
+        assert_eq!(m.tahsil_eden.as_deref(), Some("Ayşe Tahsildar"));
+        assert!(numara_ayir(&mut conn, "t1", Some("u1"), "2026-06-30", "g2").is_err());
+    }
+
+    #[test]
+    fn aralik_cakismasi_ve_bosluklar() {
+        let mut conn = test_db();
+        cilt_ekle(&mut conn, "c1", "A", 1, 50, None);
+        assert!(aralik_cakisiyor(&mut conn, "t1", "A", 50, 100, None).unwrap());
+        assert!(!aralik_cakisiyor(&mut conn, "t1", "A", 51, 100, None).unwrap());
+        assert!(!aralik_cakisiyor(&mut conn, "t1", "B", 1, 50, None).unwrap());
+        assert!(!aralik_cakisiyor(&mut conn, "t1", "A", 1, 50, Some("c1")).unwrap());
+
+        assert_eq!(bosluklari_bul(1, &[1, 2, 5, 6, 9]), vec![(3, 4), (7, 8)]);
+        assert_eq!(bosluklari_bul(10, &[12]), vec![(10, 11)]);
+        assert!(bosluklari_bul(1, &[]).is_empty());
+    }
+}
@@ -0,0 +1,364 @@
+// Üye avansı (alacak bakiyesi): borçtan fazla ödenen tutarlar ve bunların mahsubu.
+//
+// Bakiye uye_avans_hareketleri toplamıdır (giris +, mahsup −, iade −). Avans oluştuğunda
+// ve üyeye yeni aidat tahakkuk ettiğinde mahsup_et çağrılır: bakiye, kalan borcu olan
+// aidatlara en eski dönemden başlayarak (yil, ay, oluşturulma sırası) uygulanır. Mahsup
+// nakit hareketi değildir; parası fazla ödemenin gelir kaydıyla zaten 127 Üye Aidat
+// Alacakları'na alacak yazılmıştır, bu yüzden yalnızca aidatın odenen/kalan alanları
+// güncellenir. Bütün fonksiyonlar çağıranın transaction'ı içinde çalışır.
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::outbox;
+use crate::db::para::Para;
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct AvansHareketi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tur: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aidat_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gelir_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gider_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    /// Hareket sonrası bakiye
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub bakiye: Para,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct UyeAvansBakiyesi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub bakiye: Para,
+}
+
+/// Avansı sırayla borçlara dağıtır: (kayıt, uygulanan) çiftleri, eskiden yeniye
+pub fn dagit(avans: Para, borclar: &[(String, Para)]) -> Vec<(String, Para)> {
+    let mut kalan = avans;
+    let mut dagilim = Vec::new();
+    for (id, borc) in borclar {
+        if !kalan.is_positive() {
+            break;
+        }
+        if !borc.is_positive() {
+            continue;
+        }
+        let uygulanan = kalan.min(*borc);
+        dagilim.push((id.clone(), uygulanan));
+        kalan -= uygulanan;
+    }
+    dagilim
+}
+
+pub fn bakiye(conn: &mut SqliteConnection, tenant_id: &str, uye_id: &str) -> Result<Para, String> {
+    #[derive(QueryableByName)]
+    struct BakiyeRow {
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        bakiye: Para,
+    }
+
+    diesel::sql_query(
+        "SELECT COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) / 100.0 AS bakiye
+         FROM uye_avans_hareketleri WHERE tenant_id = ?1 AND uye_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .get_result::<BakiyeRow>(conn)
+    .map(|r| r.bakiye)
+    .map_err(|e| e.to_string())
+}
+
+/// Avans bakiyesi olan üyeler
+pub fn bakiyeleri_getir(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<UyeAvansBakiyesi>, String> {
+    diesel::sql_query(
+        "SELECT h.uye_id, u.uye_no, u.ad_soyad,
+                SUM(CAST(ROUND(h.tutar * 100) AS INTEGER)) / 100.0 AS bakiye
+         FROM uye_avans_hareketleri h
+         LEFT JOIN uyeler u ON u.id = h.uye_id AND u.tenant_id = h.tenant_id
+         WHERE h.tenant_id = ?1
+         GROUP BY h.uye_id
+         HAVING SUM(CAST(ROUND(h.tutar * 100) AS INTEGER)) != 0
+         ORDER BY u.uye_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Üyenin avans hareketleri, her satırda hareket sonrası bakiye ile (eskiden yeniye)
+pub fn hareketleri_getir(conn: &mut SqliteConnection, tenant_id: &str, uye_id: &str) -> Result<Vec<AvansHareketi>, String> {
+    diesel::sql_query(
+        "SELECT id, tarih, tur, tutar, aidat_id, gelir_id, gider_id, aciklama,
+                SUM(CAST(ROUND(tutar * 100) AS INTEGER)) OVER (ORDER BY created_at, rowid) / 100.0 AS bakiye
+         FROM uye_avans_hareketleri
+         WHERE tenant_id = ?1 AND uye_id = ?2
+         ORDER BY created_at, rowid",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Hareket satırı; tutar işaretlidir (giris +, mahsup / iade −)
+#[allow(clippy::too_many_arguments)]
+pub fn hareket_ekle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_id: &str,
+    tarih: &str,
+    tur: &str,
+    tutar: Para,
+    baglanti: (Option<&str>, Option<&str>, Option<&str>),
+    aciklama: &str,
+) -> Result<String, String> {
+    let (aidat_id, gelir_id, gider_id) = baglanti;
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    denetim.yeni_kayit(tenant_id, "uye_avans_hareketleri", &id);
+    diesel::sql_query(
+        "INSERT INTO uye_avans_hareketleri
+            (id, tenant_id, uye_id, tarih, tur, tutar, aidat_id, gelir_id, gider_id, aciklama, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .bind::<diesel::sql_types::Text, _>(tarih.get(..10).unwrap_or(tarih))
+    .bind::<diesel::sql_types::Text, _>(tur)
+    .bind::<diesel::sql_types::Double, _>(tutar)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aidat_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(gelir_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(gider_id)
+    .bind::<diesel::sql_types::Text, _>(aciklama)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+/// Gelir kaydının borcu aşan kısmını avansa alır ve hemen açık borçlara mahsup eder;
+/// mahsup edilen tutarı döner
+pub fn fazla_odemeyi_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_id: &str,
+    tarih: &str,
+    tutar: Para,
+    gelir_id: &str,
+) -> Result<Para, String> {
+    if !tutar.is_positive() {
+        return Ok(Para::SIFIR);
+    }
+    hareket_ekle(
+        conn,
+        denetim,
+        tenant_id,
+        uye_id,
+        tarih,
+        "giris",
+        tutar,
+        (None, Some(gelir_id), None),
+        "Fazla ödeme avansa alındı",
+    )?;
+    mahsup_et(conn, denetim, tenant_id, uye_id, tarih)
+}
+
+/// Avans bakiyesini üyenin açık aidatlarına en eskiden başlayarak uygular
+pub fn mahsup_et(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_id: &str,
+    tarih: &str,
+) -> Result<Para, String> {
+    #[derive(QueryableByName)]
+    struct AcikAidatRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        kalan: Para,
+    }
+
+    let avans = bakiye(conn, tenant_id, uye_id)?;
+    if !avans.is_positive() {
+        return Ok(Para::SIFIR);
+    }
+
+    let acik: Vec<AcikAidatRow> = diesel::sql_query(
+        "SELECT id, yil, ay, COALESCE(kalan, tutar - odenen) AS kalan FROM aidat_takip
+         WHERE tenant_id = ?1 AND uye_id = ?2
+           AND (is_deleted IS NULL OR is_deleted = 0) AND durum != 'iptal'
+           AND COALESCE(kalan, tutar - odenen) > 0
+         ORDER BY yil, ay, created_at",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let borclar: Vec<(String, Para)> = acik.iter().map(|a| (a.id.clone(), a.kalan)).collect();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut toplam = Para::SIFIR;
+    for (aidat_id, uygulanan) in dagit(avans, &borclar) {
+        let aidat = acik.iter().find(|a| a.id == aidat_id).expect("dağıtım açık aidatlardan");
+        denetim.izle(conn, tenant_id, "aidat_takip", &aidat_id)?;
+        diesel::sql_query(
+            "UPDATE aidat_takip
+             SET odenen = odenen + ?1, kalan = COALESCE(kalan, tutar - odenen) - ?1,
+                 durum = CASE WHEN COALESCE(kalan, tutar - odenen) - ?1 <= 0.004 THEN 'odendi' ELSE 'kismi_odendi' END,
+                 odeme_tarihi = ?2, version = version + 1, updated_at = ?3
+             WHERE id = ?4 AND tenant_id = ?5",
+        )
+        .bind::<diesel::sql_types::Double, _>(uygulanan)
+        .bind::<diesel::sql_types::Text, _>(tarih)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&aidat_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        outbox::queue_change(conn, tenant_id, "aidat_takip", &aidat_id, "update")?;
+
+        let aciklama = if aidat.ay <= 1 {
+            format!("{} aidatına mahsup", aidat.yil)
+        } else {
+            format!("{}/{:02} aidatına mahsup", aidat.yil, aidat.ay)
+        };
+        hareket_ekle(
+            conn,
+            denetim,
+            tenant_id,
+            uye_id,
+            tarih,
+            "mahsup",
+            -uygulanan,
+            (Some(&aidat_id), None, None),
+            &aciklama,
+        )?;
+        toplam += uygulanan;
+    }
+    Ok(toplam)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::uye(&mut conn, "t1", "u2", "2", "Ayşe Kaya");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan, created_at) VALUES
+                ('a25', 't1', 'u1', 2025, 1, 100.0, 0, 100.0, '2025-01-01'),
+                ('a24', 't1', 'u1', 2024, 1, 100.0, 60.0, NULL, '2024-01-01'),
+                ('a26', 't1', 'u1', 2026, 1, 120.0, 0, 120.0, '2026-01-01'),
+                ('b26', 't1', 'u2', 2026, 1, 120.0, 0, 120.0, '2026-01-01')",
+        );
+        conn
+    }
+
+    fn aidat(conn: &mut SqliteConnection, id: &str) -> (Para, Para, String) {
+        #[derive(QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = diesel::sql_types::Double)]
+            odenen: Para,
+            #[diesel(sql_type = diesel::sql_types::Double)]
+            kalan: Para,
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            durum: String,
+        }
+        let r: Row = diesel::sql_query("SELECT odenen, kalan, durum FROM aidat_takip WHERE id = ?1")
+            .bind::<diesel::sql_types::Text, _>(id)
+            .get_result(conn)
+            .unwrap();
+        (r.odenen, r.kalan, r.durum)
+    }
+
+    fn tl(v: f64) -> Para {
+        Para::from_tl(v)
+    }
+
+    #[test]
+    fn avans_eskiden_yeniye_dagitilir() {
+        let borclar = vec![
+            ("2024".to_string(), tl(100.0)),
+            ("2025".to_string(), tl(120.0)),
+            ("2026".to_string(), tl(150.0)),
+        ];
+
+        assert_eq!(
+            dagit(tl(250.0), &borclar),
+            vec![("2024".to_string(), tl(100.0)), ("2025".to_string(), tl(120.0)), ("2026".to_string(), tl(30.0))]
+        );
+        assert_eq!(dagit(tl(50.0), &borclar), vec![("2024".to_string(), tl(50.0))]);
+        // Borçtan fazla avans: kalan bakiye avansta kalır
+        let tumu: Para = dagit(tl(500.0), &borclar).iter().map(|(_, t)| *t).sum();
+        assert_eq!(tumu, tl(370.0));
+        assert!(dagit(Para::SIFIR, &borclar).is_empty());
+        assert!(dagit(tl(10.0), &[]).is_empty());
+    }
+
+    #[test]
+    fn fazla_odeme_en_eski_borca_mahsup_edilir() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut denetim = Denetim::yeni(None, None, "test");
+
+        // 2024 kalanı 40, 2025 kalanı 100: 150 TL'lik fazla ödeme ikisini kapatır, 10 TL 2026'ya gider
+        let mahsup = fazla_odemeyi_kaydet(c, &mut denetim, "t1", "u1", "2026-02-01", tl(150.0), "g1").unwrap();
+        assert_eq!(mahsup, tl(150.0));
+        assert_eq!(aidat(c, "a24"), (tl(100.0), Para::SIFIR, "odendi".to_string()));
+        assert_eq!(aidat(c, "a25"), (tl(100.0), Para::SIFIR, "odendi".to_string()));
+        assert_eq!(aidat(c, "a26"), (tl(10.0), tl(110.0), "kismi_odendi".to_string()));
+        assert_eq!(aidat(c, "b26").0, Para::SIFIR);
+        assert_eq!(bakiye(c, "t1", "u1").unwrap(), Para::SIFIR);
+
+        // Borcu aşan ödeme avansta kalır; yeni tahakkukta mahsup edilir
+        fazla_odemeyi_kaydet(c, &mut denetim, "t1", "u2", "2026-02-01", tl(200.0), "g2").unwrap();
+        assert_eq!(bakiye(c, "t1", "u2").unwrap(), tl(80.0));
+        diesel::sql_query(
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, kalan, created_at)
+             VALUES ('b27', 't1', 'u2', 2027, 1, 120.0, 120.0, '2027-01-01')",
+        )
+        .execute(c)
+        .unwrap();
+        assert_eq!(mahsup_et(c, &mut denetim, "t1", "u2", "2027-01-01").unwrap(), tl(80.0));
+        assert_eq!(aidat(c, "b27"), (tl(80.0), tl(40.0), "kismi_odendi".to_string()));
+
+        let hareketler = hareketleri_getir(c, "t1", "u2").unwrap();
+        let turler: Vec<&str> = hareketler.iter().map(|h| h.tur.as_str()).collect();
+        assert_eq!(turler, ["giris", "mahsup", "mahsup"]);
+        assert_eq!(hareketler.last().unwrap().bakiye, Para::SIFIR);
+        assert!(bakiyeleri_getir(c, "t1").unwrap().is_empty());
+    }
+}
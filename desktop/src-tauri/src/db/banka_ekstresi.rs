@@ -0,0 +1,852 @@
+// Banka ekstresi okuma ve mevcut kayıtlarla eşleştirme.
+//
+// Desteklenen biçimler:
+//   - CSV: sütunlar CsvEslestirme ile seçilir (başlık adı veya 1'den başlayan
+//     sütun numarası). Tutar tek sütunda (işaretli) ya da ayrı borç/alacak
+//     sütunlarında olabilir.
+//   - ISO 20022 camt.053 (BkToCstmrStmt) XML
+//   - SWIFT MT940
+//
+// Tüm biçimler aynı EkstreHareketi listesine çevrilir: tutar işaretlidir
+// (+ hesaba giriş, - hesaptan çıkış), tarih "YYYY-MM-DD" biçimindedir.
+//
+// Eşleştirme saf fonksiyondur: tutar kuruşu kuruşuna tutmalıdır; dekont/belge
+// numarası tutan aday tarih toleransından bağımsız öne geçer, aksi halde tarih
+// farkı toleransı aşmamalıdır. Her hareket ve her aday en fazla bir kez eşleşir.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::db::para::Para;
+
+/// Dekont numarası tutmadan tarihe göre eşleşmede varsayılan gün toleransı
+pub const VARSAYILAN_TARIH_TOLERANSI: i64 = 3;
+/// Dekont numarası tutan adaylar için en fazla gün farkı
+pub const DEKONT_TARIH_TOLERANSI: i64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EkstreHareketi {
+    pub sira: usize,
+    pub tarih: String,
+    pub valor_tarihi: Option<String>,
+    /// + hesaba giriş (gelir), - hesaptan çıkış (gider)
+    pub tutar: Para,
+    pub aciklama: Option<String>,
+    pub dekont_no: Option<String>,
+    pub karsi_taraf: Option<String>,
+    pub karsi_iban: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Ekstre {
+    pub bicim: String,
+    pub hesap: Option<String>,
+    pub acilis_bakiyesi: Option<Para>,
+    pub kapanis_bakiyesi: Option<Para>,
+    pub hareketler: Vec<EkstreHareketi>,
+}
+
+/// CSV sütun eşleştirmesi. Sütunlar başlık adıyla (büyük/küçük harf duyarsız)
+/// veya 1'den başlayan sütun numarasıyla verilir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsvEslestirme {
+    /// Verilmezse başlık satırında ';', ',' ve sekmeden en sık geçeni
+    pub ayirici: Option<char>,
+    /// Başlık satırından önce atlanacak satır sayısı (banka logosu, hesap bilgisi vb.)
+    #[serde(default)]
+    pub atlanacak_satir: usize,
+    /// false ise ilk satır da veridir; sütunlar yalnızca numarayla seçilebilir
+    #[serde(default = "varsayilan_baslik")]
+    pub baslik_var: bool,
+    pub tarih_sutunu: String,
+    /// chrono biçimi ("%d.%m.%Y"); verilmezse yaygın biçimler denenir
+    pub tarih_bicimi: Option<String>,
+    pub valor_sutunu: Option<String>,
+    /// İşaretli tutar sütunu
+    pub tutar_sutunu: Option<String>,
+    /// Ayrı sütunlu ekstrelerde çıkış (borç) ve giriş (alacak) sütunları
+    pub borc_sutunu: Option<String>,
+    pub alacak_sutunu: Option<String>,
+    /// Tutar sütununda çıkışlar pozitif yazılıyorsa işaret çevrilir
+    #[serde(default)]
+    pub isaret_ters: bool,
+    pub aciklama_sutunu: Option<String>,
+    pub dekont_sutunu: Option<String>,
+    pub karsi_taraf_sutunu: Option<String>,
+}
+
+fn varsayilan_baslik() -> bool {
+    true
+}
+
+/// İçeriğe bakarak biçimi tahmin eder: "camt053" | "mt940" | "csv"
+pub fn bicim_tahmin_et(icerik: &str) -> &'static str {
+    let bas = icerik.trim_start_matches('\u{feff}').trim_start();
+    if bas.starts_with('<') && (bas.contains("BkToCstmrStmt") || bas.contains("camt.053")) {
+        "camt053"
+    } else if bas.contains(":20:") && bas.contains(":61:") {
+        "mt940"
+    } else {
+        "csv"
+    }
+}
+
+/// Biçime göre ekstreyi okur. CSV için eşleştirme zorunludur.
+pub fn ekstre_oku(bicim: &str, icerik: &str, csv: Option<&CsvEslestirme>) -> Result<Ekstre, String> {
+    let icerik = icerik.trim_start_matches('\u{feff}');
+    match bicim {
+        "camt053" => camt053_oku(icerik),
+        "mt940" => mt940_oku(icerik),
+        "csv" => csv_oku(icerik, csv.ok_or("CSV ekstresi için sütun eşleştirmesi gerekir")?),
+        _ => Err(format!("Desteklenmeyen ekstre biçimi: {}", bicim)),
+    }
+}
+
+// ============================================================================
+// TARİH
+// ============================================================================
+
+const TARIH_BICIMLERI: [&str; 7] = ["%Y-%m-%d", "%d.%m.%Y", "%d/%m/%Y", "%d-%m-%Y", "%Y%m%d", "%d.%m.%y", "%Y/%m/%d"];
+
+/// Tarihi "YYYY-MM-DD"ye çevirir; saat kısmı (boşluk veya 'T' sonrası) yok sayılır
+pub fn tarih_coz(deger: &str, bicim: Option<&str>) -> Option<String> {
+    let gun = deger.trim().split([' ', 'T']).next().unwrap_or("");
+    if gun.is_empty() {
+        return None;
+    }
+    let bicimler: Vec<&str> = match bicim {
+        Some(b) => vec![b],
+        None => TARIH_BICIMLERI.to_vec(),
+    };
+    bicimler
+        .into_iter()
+        .find_map(|b| chrono::NaiveDate::parse_from_str(gun, b).ok())
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+fn gun_farki(a: &str, b: &str) -> Option<i64> {
+    let a = chrono::NaiveDate::parse_from_str(a, "%Y-%m-%d").ok()?;
+    let b = chrono::NaiveDate::parse_from_str(b, "%Y-%m-%d").ok()?;
+    Some((a - b).num_days().abs())
+}
+
+fn bos_degilse(s: &str) -> Option<String> {
+    let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+// ============================================================================
+// CSV
+// ============================================================================
+
+/// Tırnaklı alanları ("a;b", "" kaçışı, satır içi yeni satır) destekleyen CSV bölücü
+pub(crate) fn csv_satirlari(icerik: &str, ayirici: char) -> Vec<Vec<String>> {
+    let mut satirlar = Vec::new();
+    let mut satir = Vec::new();
+    let mut alan = String::new();
+    let mut tirnakta = false;
+    let mut karakterler = icerik.chars().peekable();
+
+    while let Some(c) = karakterler.next() {
+        match c {
+            '"' if tirnakta => {
+                if karakterler.peek() == Some(&'"') {
+                    alan.push('"');
+                    karakterler.next();
+                } else {
+                    tirnakta = false;
+                }
+            }
+            '"' if alan.is_empty() => tirnakta = true,
+            '\r' if !tirnakta => {}
+            '\n' if !tirnakta => {
+                satir.push(std::mem::take(&mut alan));
+                satirlar.push(std::mem::take(&mut satir));
+            }
+            c if c == ayirici && !tirnakta => satir.push(std::mem::take(&mut alan)),
+            c => alan.push(c),
+        }
+    }
+    if !alan.is_empty() || !satir.is_empty() {
+        satir.push(alan);
+        satirlar.push(satir);
+    }
+    satirlar.retain(|s| s.iter().any(|a| !a.trim().is_empty()));
+    satirlar
+}
+
+pub(crate) fn ayirici_tahmin_et(satir: &str) -> char {
+    [';', ',', '\t']
+        .into_iter()
+        .max_by_key(|c| satir.matches(*c).count())
+        .unwrap_or(';')
+}
+
+pub(crate) fn sutun_bul(baslik: Option<&[String]>, secim: &str) -> Result<usize, String> {
+    let secim = secim.trim();
+    if let Some(baslik) = baslik {
+        let aranan = secim.to_lowercase();
+        if let Some(i) = baslik.iter().position(|b| b.trim().to_lowercase() == aranan) {
+            return Ok(i);
+        }
+    }
+    match secim.parse::<usize>() {
+        Ok(n) if n >= 1 => Ok(n - 1),
+        _ => Err(format!("Dosyada '{}' sütunu bulunamadı", secim)),
+    }
+}
+
+fn csv_oku(icerik: &str, e: &CsvEslestirme) -> Result<Ekstre, String> {
+    let veri: String = icerik.lines().skip(e.atlanacak_satir).collect::<Vec<_>>().join("\n");
+    let ayirici = e
+        .ayirici
+        .unwrap_or_else(|| ayirici_tahmin_et(veri.lines().next().unwrap_or("")));
+    let mut satirlar = csv_satirlari(&veri, ayirici).into_iter();
+    let baslik = if e.baslik_var { satirlar.next() } else { None };
+    let baslik = baslik.as_deref();
+
+    let sutun = |s: &Option<String>| -> Result<Option<usize>, String> {
+        s.as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| sutun_bul(baslik, s))
+            .transpose()
+    };
+    let tarih_i = sutun_bul(baslik, &e.tarih_sutunu)?;
+    let valor_i = sutun(&e.valor_sutunu)?;
+    let tutar_i = sutun(&e.tutar_sutunu)?;
+    let borc_i = sutun(&e.borc_sutunu)?;
+    let alacak_i = sutun(&e.alacak_sutunu)?;
+    let aciklama_i = sutun(&e.aciklama_sutunu)?;
+    let dekont_i = sutun(&e.dekont_sutunu)?;
+    let karsi_i = sutun(&e.karsi_taraf_sutunu)?;
+    if tutar_i.is_none() && borc_i.is_none() && alacak_i.is_none() {
+        return Err("Tutar sütunu veya borç/alacak sütunları seçilmelidir".to_string());
+    }
+
+    let mut hareketler = Vec::new();
+    for (n, satir) in satirlar.enumerate() {
+        let satir_no = n + 1 + e.atlanacak_satir + usize::from(e.baslik_var);
+        let al = |i: Option<usize>| i.and_then(|i| satir.get(i)).map(|s| s.trim()).unwrap_or("");
+        let tutar_oku = |i: Option<usize>| -> Result<Para, String> {
+            let s = al(i);
+            if s.is_empty() {
+                Ok(Para::SIFIR)
+            } else {
+                s.parse::<Para>().map_err(|h| format!("Satır {}: {}", satir_no, h))
+            }
+        };
+
+        let tarih_ham = al(Some(tarih_i));
+        // Alt toplam / bakiye satırları gibi tarihsiz satırlar atlanır
+        let Some(tarih) = tarih_coz(tarih_ham, e.tarih_bicimi.as_deref()) else {
+            if tarih_ham.is_empty() {
+                continue;
+            }
+            return Err(format!("Satır {}: tarih okunamadı: '{}'", satir_no, tarih_ham));
+        };
+
+        let mut tutar = if tutar_i.is_some() {
+            let t = tutar_oku(tutar_i)?;
+            if e.isaret_ters { -t } else { t }
+        } else {
+            tutar_oku(alacak_i)?.abs() - tutar_oku(borc_i)?.abs()
+        };
+        if tutar_i.is_some() && (borc_i.is_some() || alacak_i.is_some()) {
+            // Hem tutar hem yön sütunu verilmişse yön, dolu olan sütundan alınır
+            if !tutar_oku(borc_i)?.is_zero() {
+                tutar = -tutar.abs();
+            } else if !tutar_oku(alacak_i)?.is_zero() {
+                tutar = tutar.abs();
+            }
+        }
+        if tutar.is_zero() {
+            continue;
+        }
+
+        hareketler.push(EkstreHareketi {
+            sira: hareketler.len() + 1,
+            tarih,
+            valor_tarihi: valor_i.and_then(|i| tarih_coz(al(Some(i)), e.tarih_bicimi.as_deref())),
+            tutar,
+            aciklama: bos_degilse(al(aciklama_i)),
+            dekont_no: bos_degilse(al(dekont_i)),
+            karsi_taraf: bos_degilse(al(karsi_i)),
+            karsi_iban: None,
+        });
+    }
+
+    Ok(Ekstre {
+        bicim: "csv".to_string(),
+        hareketler,
+        ..Default::default()
+    })
+}
+
+// ============================================================================
+// camt.053
+// ============================================================================
+
+#[derive(Default)]
+struct CamtGirdisi {
+    tutar: Option<Para>,
+    alacak: bool,
+    tarih: Option<String>,
+    valor: Option<String>,
+    referans: Option<String>,
+    uctan_uca: Option<String>,
+    aciklama: Vec<String>,
+    borclu: Option<String>,
+    alacakli: Option<String>,
+    borclu_iban: Option<String>,
+    alacakli_iban: Option<String>,
+}
+
+#[derive(Default)]
+struct CamtBakiyesi {
+    kod: Option<String>,
+    tutar: Option<Para>,
+    alacak: bool,
+}
+
+fn camt053_oku(icerik: &str) -> Result<Ekstre, String> {
+    // Metin kırpılmaz: "a &amp; b" gibi değerlerde varlık çevresindeki boşluklar korunmalı
+    let mut okuyucu = Reader::from_str(icerik);
+
+    let mut ekstre = Ekstre { bicim: "camt053".to_string(), ..Default::default() };
+    let mut yol: Vec<String> = Vec::new();
+    let mut metin = String::new();
+    let mut girdi: Option<CamtGirdisi> = None;
+    let mut bakiye: Option<CamtBakiyesi> = None;
+
+    loop {
+        match okuyucu.read_event().map_err(|e| format!("camt.053 okunamadı: {}", e))? {
+            Event::Start(e) => {
+                let ad = e.local_name().as_ref().to_string();
+                match ad.as_str() {
+                    "Ntry" => girdi = Some(CamtGirdisi::default()),
+                    "Bal" => bakiye = Some(CamtBakiyesi::default()),
+                    _ => {}
+                }
+                yol.push(ad);
+                metin.clear();
+            }
+            Event::Text(t) => metin.push_str(&t.xml10_content()),
+            Event::CData(t) => metin.push_str(&t.xml10_content()),
+            Event::GeneralRef(r) => {
+                let ad = r.xml10_content();
+                match ad.as_ref() {
+                    "amp" => metin.push('&'),
+                    "lt" => metin.push('<'),
+                    "gt" => metin.push('>'),
+                    "quot" => metin.push('"'),
+                    "apos" => metin.push('\''),
+                    _ => {
+                        if let Ok(Some(c)) = r.resolve_char_ref() {
+                            metin.push(c);
+                        }
+                    }
+                }
+            }
+            Event::End(_) => {
+                let deger = metin.trim().to_string();
+                metin.clear();
+                camt_alani(&yol, &deger, &mut ekstre, girdi.as_mut(), bakiye.as_mut());
+                match yol.pop().as_deref() {
+                    Some("Ntry") => {
+                        if let Some(g) = girdi.take() {
+                            camt_girdisi_ekle(&mut ekstre, g)?;
+                        }
+                    }
+                    Some("Bal") => {
+                        if let Some(b) = bakiye.take() {
+                            let tutar = b.tutar.map(|t| if b.alacak { t } else { -t });
+                            match b.kod.as_deref() {
+                                Some("OPBD") | Some("PRCD") => ekstre.acilis_bakiyesi = tutar,
+                                Some("CLBD") => ekstre.kapanis_bakiyesi = tutar,
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if ekstre.hareketler.is_empty() && ekstre.acilis_bakiyesi.is_none() {
+        return Err("camt.053 dosyasında hesap hareketi bulunamadı".to_string());
+    }
+    Ok(ekstre)
+}
+
+/// Kapanan elemanın değerini yoluna göre ilgili alana yazar
+fn camt_alani(
+    yol: &[String],
+    deger: &str,
+    ekstre: &mut Ekstre,
+    girdi: Option<&mut CamtGirdisi>,
+    bakiye: Option<&mut CamtBakiyesi>,
+) {
+    if deger.is_empty() {
+        return;
+    }
+    let son = |n: usize| -> Vec<&str> {
+        yol.iter().rev().take(n).rev().map(|s| s.as_str()).collect()
+    };
+    let ici = |ad: &str| yol.iter().any(|s| s == ad);
+
+    if let Some(g) = girdi {
+        let s2 = son(2);
+        let s3 = son(3);
+        match s2.as_slice() {
+            ["Ntry", "Amt"] => g.tutar = deger.parse().ok(),
+            ["Ntry", "CdtDbtInd"] => g.alacak = deger == "CRDT",
+            ["BookgDt", "Dt"] | ["BookgDt", "DtTm"] if g.tarih.is_none() => g.tarih = tarih_coz(deger, None),
+            ["ValDt", "Dt"] | ["ValDt", "DtTm"] if g.valor.is_none() => g.valor = tarih_coz(deger, None),
+            ["Ntry", "AcctSvcrRef"] => g.referans = Some(deger.to_string()),
+            ["Refs", "AcctSvcrRef"] if g.referans.is_none() => g.referans = Some(deger.to_string()),
+            ["Refs", "EndToEndId"] if deger != "NOTPROVIDED" => g.uctan_uca = Some(deger.to_string()),
+            ["RmtInf", "Ustrd"] | ["Ntry", "AddtlNtryInf"] | ["TxDtls", "AddtlTxInf"] => {
+                g.aciklama.push(deger.to_string())
+            }
+            _ => {}
+        }
+        match s3.as_slice() {
+            ["DbtrAcct", "Id", "IBAN"] => g.borclu_iban = Some(deger.to_string()),
+            ["CdtrAcct", "Id", "IBAN"] => g.alacakli_iban = Some(deger.to_string()),
+            _ => {}
+        }
+        // Taraf adı: Dbtr/Nm, camt.053.001.08 ve sonrasında Dbtr/Pty/Nm
+        if s2.as_slice() == ["Dbtr", "Nm"] || (s2.as_slice() == ["Pty", "Nm"] && ici("Dbtr")) {
+            g.borclu = Some(deger.to_string());
+        } else if s2.as_slice() == ["Cdtr", "Nm"] || (s2.as_slice() == ["Pty", "Nm"] && ici("Cdtr")) {
+            g.alacakli = Some(deger.to_string());
+        }
+        return;
+    }
+
+    if let Some(b) = bakiye {
+        match son(2).as_slice() {
+            ["CdOrPrtry", "Cd"] => b.kod = Some(deger.to_string()),
+            ["Bal", "Amt"] => b.tutar = deger.parse().ok(),
+            ["Bal", "CdtDbtInd"] => b.alacak = deger == "CRDT",
+            _ => {}
+        }
+        return;
+    }
+
+    if son(3).as_slice() == ["Acct", "Id", "IBAN"] && ekstre.hesap.is_none() {
+        ekstre.hesap = Some(deger.to_string());
+    }
+}
+
+fn camt_girdisi_ekle(ekstre: &mut Ekstre, g: CamtGirdisi) -> Result<(), String> {
+    let sira = ekstre.hareketler.len() + 1;
+    let tutar = g.tutar.ok_or_else(|| format!("camt.053 {}. harekette tutar yok", sira))?;
+    let tarih = g
+        .tarih
+        .clone()
+        .or_else(|| g.valor.clone())
+        .ok_or_else(|| format!("camt.053 {}. harekette tarih yok", sira))?;
+    // Gelen ödemede karşı taraf borçlu, giden ödemede alacaklıdır
+    let (karsi_taraf, karsi_iban) = if g.alacak {
+        (g.borclu, g.borclu_iban)
+    } else {
+        (g.alacakli, g.alacakli_iban)
+    };
+    ekstre.hareketler.push(EkstreHareketi {
+        sira,
+        tarih,
+        valor_tarihi: g.valor,
+        tutar: if g.alacak { tutar.abs() } else { -tutar.abs() },
+        aciklama: bos_degilse(&g.aciklama.join(" ")),
+        dekont_no: g.referans.or(g.uctan_uca),
+        karsi_taraf,
+        karsi_iban,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// MT940
+// ============================================================================
+
+/// ":61:" satırı: YYMMDD[MMDD](C|D|RC|RD)[para kodu harfi]tutar(N|F|S)xxx referans[//banka ref][\n ek bilgi]
+fn mt940_61_coz(satir: &str) -> Result<(String, Option<String>, Para, Option<String>), String> {
+    let hata = || format!("MT940 :61: satırı okunamadı: '{}'", satir);
+    let ilk = satir.lines().next().unwrap_or("");
+    let b = ilk.as_bytes();
+    if b.len() < 8 || !b[..6].iter().all(u8::is_ascii_digit) {
+        return Err(hata());
+    }
+    let valor = tarih_coz(&format!("20{}", &ilk[..6]), Some("%Y%m%d")).ok_or_else(hata)?;
+    let mut i = 6;
+    let mut kayit = None;
+    if b.len() >= 10 && b[6..10].iter().all(u8::is_ascii_digit) {
+        // Kayıt (booking) tarihi MMDD; yılı valörden alınır, yıl dönümü düzeltilir
+        let yil: i32 = valor[..4].parse().map_err(|_| hata())?;
+        let ay: u32 = ilk[6..8].parse().map_err(|_| hata())?;
+        let valor_ay: u32 = valor[5..7].parse().map_err(|_| hata())?;
+        let yil = match (valor_ay, ay) {
+            (12, 1) => yil + 1,
+            (1, 12) => yil - 1,
+            _ => yil,
+        };
+        kayit = tarih_coz(&format!("{}{}", yil, &ilk[6..10]), Some("%Y%m%d"));
+        i = 10;
+    }
+    let alacak = match (b.get(i), b.get(i + 1)) {
+        (Some(b'R'), Some(b'C')) => { i += 2; false }
+        (Some(b'R'), Some(b'D')) => { i += 2; true }
+        (Some(b'C'), _) => { i += 1; true }
+        (Some(b'D'), _) => { i += 1; false }
+        _ => return Err(hata()),
+    };
+    if b.get(i).is_some_and(|c| c.is_ascii_alphabetic()) {
+        i += 1;
+    }
+    let bas = i;
+    while b.get(i).is_some_and(|c| c.is_ascii_digit() || *c == b',') {
+        i += 1;
+    }
+    let tutar = mt940_tutar(&ilk[bas..i]).ok_or_else(hata)?;
+    // İşlem türü (N/F/S + 3 karakter) sonrası müşteri referansı ve // sonrası banka referansı
+    let kalan = ilk.get(i + 4..).unwrap_or("");
+    let (musteri, banka) = match kalan.split_once("//") {
+        Some((m, b)) => (m, Some(b)),
+        None => (kalan, None),
+    };
+    let referans = banka
+        .and_then(bos_degilse)
+        .or_else(|| Some(musteri).filter(|m| !m.eq_ignore_ascii_case("NONREF")).and_then(bos_degilse));
+
+    let tarih = kayit.clone().unwrap_or_else(|| valor.clone());
+    Ok((tarih, Some(valor), if alacak { tutar } else { -tutar }, referans))
+}
+
+/// ":60F:" / ":62F:" bakiyesi: (C|D)YYMMDD PARA tutar
+fn mt940_bakiye_coz(deger: &str) -> Option<Para> {
+    let alacak = deger.starts_with('C');
+    let tutar = mt940_tutar(deger.get(10..)?.trim())?;
+    Some(if alacak { tutar } else { -tutar })
+}
+
+/// MT940 tutarı; tam tutarlar "500," gibi ondalıksız virgülle yazılır
+fn mt940_tutar(s: &str) -> Option<Para> {
+    match s.strip_suffix(',') {
+        Some(tam) => format!("{},00", tam).parse().ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn mt940_oku(icerik: &str) -> Result<Ekstre, String> {
+    // Alanlar ":etiket:" ile başlar; sonraki etikete kadar süren satırlar alanın devamıdır
+    let mut alanlar: Vec<(String, String)> = Vec::new();
+    for satir in icerik.lines() {
+        let satir = satir.trim_end_matches('\r');
+        if satir.starts_with('-') && satir.trim_matches('-').trim().is_empty() {
+            continue;
+        }
+        let etiketli = satir
+            .strip_prefix(':')
+            .and_then(|s| s.split_once(':'))
+            .filter(|(etiket, _)| etiket.len() <= 3 && etiket.chars().next().is_some_and(|c| c.is_ascii_digit()));
+        match etiketli {
+            Some((etiket, deger)) => alanlar.push((etiket.to_string(), deger.to_string())),
+            None => {
+                if let Some((_, deger)) = alanlar.last_mut() {
+                    deger.push('\n');
+                    deger.push_str(satir);
+                }
+            }
+        }
+    }
+
+    let mut ekstre = Ekstre { bicim: "mt940".to_string(), ..Default::default() };
+    for (etiket, deger) in &alanlar {
+        match etiket.as_str() {
+            "25" if ekstre.hesap.is_none() => ekstre.hesap = bos_degilse(deger),
+            "60F" | "60M" if ekstre.acilis_bakiyesi.is_none() => ekstre.acilis_bakiyesi = mt940_bakiye_coz(deger),
+            "62F" | "62M" => ekstre.kapanis_bakiyesi = mt940_bakiye_coz(deger),
+            "61" => {
+                let (tarih, valor, tutar, dekont) = mt940_61_coz(deger)?;
+                // :61: satırının ikinci satırı (ek bilgi) açıklamanın başına eklenir
+                let ek = deger.lines().nth(1).map(str::to_string);
+                ekstre.hareketler.push(EkstreHareketi {
+                    sira: ekstre.hareketler.len() + 1,
+                    tarih,
+                    valor_tarihi: valor,
+                    tutar,
+                    aciklama: ek.as_deref().and_then(bos_degilse),
+                    dekont_no: dekont,
+                    karsi_taraf: None,
+                    karsi_iban: None,
+                });
+            }
+            "86" => {
+                if let Some(h) = ekstre.hareketler.last_mut() {
+                    // Yapılandırılmış :86: alt alan ayraçları (?20, ?32 ...) boşluğa çevrilir
+                    let mut temiz = String::new();
+                    let mut karakterler = deger.chars().peekable();
+                    while let Some(c) = karakterler.next() {
+                        if c == '?' && karakterler.peek().is_some_and(|d| d.is_ascii_digit()) {
+                            karakterler.next();
+                            karakterler.next();
+                            temiz.push(' ');
+                        } else {
+                            temiz.push(if c == '\n' { ' ' } else { c });
+                        }
+                    }
+                    let yeni = bos_degilse(&format!("{} {}", h.aciklama.as_deref().unwrap_or(""), temiz));
+                    h.aciklama = yeni;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if ekstre.hareketler.is_empty() && ekstre.acilis_bakiyesi.is_none() {
+        return Err("MT940 dosyasında hesap hareketi bulunamadı".to_string());
+    }
+    Ok(ekstre)
+}
+
+// ============================================================================
+// EŞLEŞTİRME
+// ============================================================================
+
+/// Ekstre satırıyla eşleşebilecek mevcut gelir/gider kaydı
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EslesmeAdayi {
+    /// "gelirler" | "giderler"
+    pub tablo: String,
+    pub id: String,
+    pub tarih: String,
+    /// Ekstreyle aynı işaret kuralı: gelir +, gider -
+    pub tutar: Para,
+    /// makbuz_no / belge_no / fatura_no / islem_no / aidat dekont_no
+    pub belge_nolari: Vec<String>,
+    pub aciklama: Option<String>,
+    /// Aidat tahsilatından oluşan gelirlerde aidat_takip kaydı
+    pub aidat_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eslesme {
+    pub aday: usize,
+    pub puan: i32,
+}
+
+/// Dekont karşılaştırması için yalnızca harf/rakam, büyük harf
+fn belge_anahtari(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_uppercase).collect()
+}
+
+/// Aday uygun değilse None; uygunsa 0-100 arası puan.
+/// Tutar tutması 50, dekont tutması 40 puan; kalan 10 puan aynı gün için,
+/// her gün farkı 2 puan düşürür.
+pub fn eslesme_puani(h: &EkstreHareketi, a: &EslesmeAdayi, tarih_toleransi: i64) -> Option<i32> {
+    if h.tutar != a.tutar {
+        return None;
+    }
+    let fark = gun_farki(&h.tarih, &a.tarih)?;
+    let dekont_tuttu = h.dekont_no.as_deref().map(belge_anahtari).is_some_and(|d| {
+        !d.is_empty() && a.belge_nolari.iter().any(|b| belge_anahtari(b) == d)
+    });
+    let sinir = if dekont_tuttu { DEKONT_TARIH_TOLERANSI } else { tarih_toleransi };
+    if fark > sinir {
+        return None;
+    }
+    let tarih_puani = (10 - 2 * fark).max(0) as i32;
+    Some(50 + if dekont_tuttu { 40 } else { 0 } + tarih_puani)
+}
+
+/// Hareketlerle adaylar arasında bire bir eşleşme önerir. En yüksek puanlı
+/// çiftler önce atanır; eşit puanda önce sıradaki hareket ve aday kazanır.
+pub fn eslestir(
+    hareketler: &[EkstreHareketi],
+    adaylar: &[EslesmeAdayi],
+    tarih_toleransi: i64,
+) -> Vec<Option<Eslesme>> {
+    let mut ciftler: Vec<(i32, usize, usize)> = Vec::new();
+    for (hi, h) in hareketler.iter().enumerate() {
+        for (ai, a) in adaylar.iter().enumerate() {
+            if let Some(p) = eslesme_puani(h, a, tarih_toleransi) {
+                ciftler.push((p, hi, ai));
+            }
+        }
+    }
+    ciftler.sort_by(|x, y| y.0.cmp(&x.0).then(x.1.cmp(&y.1)).then(x.2.cmp(&y.2)));
+
+    let mut sonuc = vec![None; hareketler.len()];
+    let mut kullanilan = vec![false; adaylar.len()];
+    for (puan, hi, ai) in ciftler {
+        if sonuc[hi].is_none() && !kullanilan[ai] {
+            sonuc[hi] = Some(Eslesme { aday: ai, puan });
+            kullanilan[ai] = true;
+        }
+    }
+    sonuc
+}
+
+/// Aynı hareketin başka bir aktarımda tekrar yüklenmesini yakalamak için anahtar
+pub fn parmak_izi(h: &EkstreHareketi) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        h.tarih,
+        h.tutar.kurus(),
+        h.dekont_no.as_deref().map(belge_anahtari).unwrap_or_default(),
+        h.aciklama.as_deref().map(belge_anahtari).unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tl(s: &str) -> Para {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn csv_ayri_borc_alacak_sutunlari_ve_turkce_tutarlar() {
+        let icerik = "Ziraat Bankası Hesap Hareketleri\n\
+                      Tarih;Açıklama;Dekont No;Borç;Alacak;Bakiye\n\
+                      02.01.2026;\"Aidat; Ocak\";D-101;;1.250,00;1.250,00\n\
+                      05.01.2026;Elektrik faturası;D-102;430,5;;819,50\n\
+                      ;Dönem toplamı;;430,50;1.250,00;\n";
+        let e = CsvEslestirme {
+            atlanacak_satir: 1,
+            baslik_var: true,
+            tarih_sutunu: "tarih".into(),
+            borc_sutunu: Some("Borç".into()),
+            alacak_sutunu: Some("Alacak".into()),
+            aciklama_sutunu: Some("Açıklama".into()),
+            dekont_sutunu: Some("3".into()),
+            ..Default::default()
+        };
+        let ekstre = ekstre_oku("csv", icerik, Some(&e)).unwrap();
+        assert_eq!(ekstre.hareketler.len(), 2);
+        let h = &ekstre.hareketler[0];
+        assert_eq!((h.tarih.as_str(), h.tutar), ("2026-01-02", tl("1250")));
+        assert_eq!(h.aciklama.as_deref(), Some("Aidat; Ocak"));
+        assert_eq!(h.dekont_no.as_deref(), Some("D-101"));
+        assert_eq!(ekstre.hareketler[1].tutar, tl("-430.50"));
+    }
+
+    #[test]
+    fn camt053_hareket_ve_bakiyeler() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+ <BkToCstmrStmt><Stmt>
+  <Acct><Id><IBAN>TR330006100519786457841326</IBAN></Id></Acct>
+  <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy="TRY">1000.00</Amt><CdtDbtInd>CRDT</CdtDbtInd></Bal>
+  <Bal><Tp><CdOrPrtry><Cd>CLBD</Cd></CdOrPrtry></Tp><Amt Ccy="TRY">1150.00</Amt><CdtDbtInd>CRDT</CdtDbtInd></Bal>
+  <Ntry>
+   <Amt Ccy="TRY">250.00</Amt><CdtDbtInd>CRDT</CdtDbtInd>
+   <BookgDt><Dt>2026-02-03</Dt></BookgDt><ValDt><Dt>2026-02-03</Dt></ValDt>
+   <AcctSvcrRef>EFT-778</AcctSvcrRef>
+   <NtryDtls><TxDtls>
+    <RltdPties><Dbtr><Nm>Ayşe Yılmaz</Nm></Dbtr><DbtrAcct><Id><IBAN>TR12</IBAN></Id></DbtrAcct></RltdPties>
+    <RmtInf><Ustrd>Şubat aidatı &amp; bağış</Ustrd></RmtInf>
+   </TxDtls></NtryDtls>
+  </Ntry>
+  <Ntry>
+   <Amt Ccy="TRY">100.00</Amt><CdtDbtInd>DBIT</CdtDbtInd>
+   <BookgDt><DtTm>2026-02-10T10:15:00</DtTm></BookgDt>
+   <NtryDtls><TxDtls><RltdPties><Cdtr><Nm>Kırtasiye Ltd</Nm></Cdtr></RltdPties></TxDtls></NtryDtls>
+  </Ntry>
+ </Stmt></BkToCstmrStmt>
+</Document>"#;
+        assert_eq!(bicim_tahmin_et(xml), "camt053");
+        let e = ekstre_oku("camt053", xml, None).unwrap();
+        assert_eq!(e.hesap.as_deref(), Some("TR330006100519786457841326"));
+        assert_eq!((e.acilis_bakiyesi, e.kapanis_bakiyesi), (Some(tl("1000")), Some(tl("1150"))));
+        assert_eq!(e.hareketler.len(), 2);
+        let h = &e.hareketler[0];
+        assert_eq!((h.tarih.as_str(), h.tutar), ("2026-02-03", tl("250")));
+        assert_eq!(h.aciklama.as_deref(), Some("Şubat aidatı & bağış"));
+        assert_eq!(h.dekont_no.as_deref(), Some("EFT-778"));
+        assert_eq!(h.karsi_taraf.as_deref(), Some("Ayşe Yılmaz"));
+        assert_eq!(h.karsi_iban.as_deref(), Some("TR12"));
+        let g = &e.hareketler[1];
+        assert_eq!((g.tarih.as_str(), g.tutar), ("2026-02-10", tl("-100")));
+        assert_eq!(g.karsi_taraf.as_deref(), Some("Kırtasiye Ltd"));
+    }
+
+    #[test]
+    fn mt940_hareket_ve_bakiyeler() {
+        let mt = ":20:STMT2026\r\n:25:TR330006100519786457841326\r\n:28C:12/1\r\n\
+                  :60F:C251231TRY500,\r\n\
+                  :61:2601020102C250,00NTRFNONREF//DKN-55\r\n\
+                  :86:?20Ocak aidatı?32Mehmet Kaya\r\n\
+                  :61:2512311231D75,5NMSCREF9\r\nkomisyon\r\n\
+                  :86:Hesap işletim\r\nücreti\r\n\
+                  :61:260102C500,NTRFREF10\r\n\
+                  :62F:C260102TRY1174,50\r\n-";
+        assert_eq!(bicim_tahmin_et(mt), "mt940");
+        let e = ekstre_oku("mt940", mt, None).unwrap();
+        assert_eq!(e.acilis_bakiyesi, Some(tl("500")));
+        assert_eq!(e.kapanis_bakiyesi, Some(tl("1174.50")));
+        assert_eq!(e.hareketler.len(), 3);
+        let h = &e.hareketler[0];
+        assert_eq!((h.tarih.as_str(), h.tutar), ("2026-01-02", tl("250")));
+        assert_eq!(h.dekont_no.as_deref(), Some("DKN-55"));
+        assert_eq!(h.aciklama.as_deref(), Some("Ocak aidatı Mehmet Kaya"));
+        let g = &e.hareketler[1];
+        assert_eq!((g.tarih.as_str(), g.tutar), ("2025-12-31", tl("-75.50")));
+        assert_eq!(g.dekont_no.as_deref(), Some("REF9"));
+        assert_eq!(g.aciklama.as_deref(), Some("komisyon Hesap işletim ücreti"));
+        let t = &e.hareketler[2];
+        assert_eq!((t.tarih.as_str(), t.tutar), ("2026-01-02", tl("500")));
+        assert_eq!(t.dekont_no.as_deref(), Some("REF10"));
+    }
+
+    #[test]
+    fn eslestirme_dekontu_one_alir_ve_bire_birdir() {
+        let h = |sira, tarih: &str, tutar: &str, dekont: Option<&str>| EkstreHareketi {
+            sira,
+            tarih: tarih.into(),
+            valor_tarihi: None,
+            tutar: tl(tutar),
+            aciklama: None,
+            dekont_no: dekont.map(String::from),
+            karsi_taraf: None,
+            karsi_iban: None,
+        };
+        let a = |id: &str, tarih: &str, tutar: &str, belge: &[&str]| EslesmeAdayi {
+            tablo: "gelirler".into(),
+            id: id.into(),
+            tarih: tarih.into(),
+            tutar: tl(tutar),
+            belge_nolari: belge.iter().map(|s| s.to_string()).collect(),
+            aciklama: None,
+            aidat_id: None,
+        };
+        let hareketler = [
+            h(1, "2026-03-01", "100", None),
+            h(2, "2026-03-01", "100", Some("dk 9")),
+            h(3, "2026-03-20", "100", None),
+            h(4, "2026-03-01", "55", None),
+        ];
+        let adaylar = [
+            a("g1", "2026-03-01", "100", &[]),
+            // Dekontu tutuyor: 10 gün fark olsa da 2. harekete gider
+            a("g2", "2026-03-11", "100", &["DK-9"]),
+            a("g3", "2026-03-01", "100.01", &[]),
+        ];
+        let s = eslestir(&hareketler, &adaylar, VARSAYILAN_TARIH_TOLERANSI);
+        assert_eq!(s[0], Some(Eslesme { aday: 0, puan: 60 }));
+        assert_eq!(s[1], Some(Eslesme { aday: 1, puan: 90 }));
+        // g1 kullanıldı, 19 gün fark toleransı aşıyor
+        assert_eq!(s[2], None);
+        assert_eq!(s[3], None);
+    }
+}
@@ -0,0 +1,307 @@
+// Denetim izi: yazım komutlarının kim/ne/ne zaman kaydı (yalnızca ekleme).
+//
+// Komut, transaction'a girmeden oturumdaki kullanıcı ve komut adıyla bir
+// `Denetim` oluşturur (AppState::denetim). Transaction içinde değişecek her
+// kayıt yazımdan ÖNCE `izle` ile işaretlenir (outbox::snapshot_row ile önceki
+// görüntü alınır); yazımlar bittikten sonra `kaydet` sonraki görüntüleri alıp
+// denetim_kayitlari'na ekler. Böylece denetim satırı ile değişiklik aynı
+// transaction'da atılır ya da birlikte geri alınır.
+//
+// İşlem türü görüntülerden çıkarılır: önce yok → create, sonra yok → delete,
+// is_active 1→0 veya is_deleted 0→1 → delete (soft delete), diğerleri update.
+// Yalnızca updated_at/version değiştiyse kayıt atılmaz.
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde_json::Value;
+
+use crate::db::outbox;
+
+/// Değişiklik listesinde sayılmayan teknik alanlar
+const TEKNIK_ALANLAR: [&str; 3] = ["updated_at", "version", "last_modified"];
+
+/// Görüntüde değeri saklanmayan alanlar (gizli ya da büyük); değiştikleri yine
+/// degisen_alanlar'da görünür
+const GIZLI_ALANLAR: [&str; 2] = ["password_hash", "logo"];
+
+struct Izlenen {
+    tenant_id: String,
+    tablo: String,
+    kayit_id: String,
+    once: Option<Value>,
+}
+
+pub struct Denetim {
+    kullanici_id: Option<String>,
+    kullanici_adi: Option<String>,
+    komut: String,
+    izlenenler: Vec<Izlenen>,
+}
+
+impl Denetim {
+    pub fn yeni(kullanici_id: Option<String>, kullanici_adi: Option<String>, komut: &str) -> Self {
+        Self {
+            kullanici_id,
+            kullanici_adi,
+            komut: komut.to_string(),
+            izlenenler: Vec::new(),
+        }
+    }
+
+    fn izleniyor(&self, tenant_id: &str, tablo: &str, kayit_id: &str) -> bool {
+        self.izlenenler
+            .iter()
+            .any(|i| i.tablo == tablo && i.kayit_id == kayit_id && i.tenant_id == tenant_id)
+    }
+
+    /// Kaydın yazımdan önceki görüntüsünü alır. Aynı kayıt ikinci kez
+    /// izlenirse ilk (transaction başındaki) görüntü korunur.
+    pub fn izle(
+        &mut self,
+        conn: &mut SqliteConnection,
+        tenant_id: &str,
+        tablo: &str,
+        kayit_id: &str,
+    ) -> Result<(), String> {
+        if self.izleniyor(tenant_id, tablo, kayit_id) {
+            return Ok(());
+        }
+        let once = outbox::snapshot_row(conn, tablo, kayit_id, tenant_id)?;
+        self.izlenenler.push(Izlenen {
+            tenant_id: tenant_id.to_string(),
+            tablo: tablo.to_string(),
+            kayit_id: kayit_id.to_string(),
+            once,
+        });
+        Ok(())
+    }
+
+    /// Bu transaction'da oluşturulan kayıt (önceki görüntü yok). Kimliği
+    /// yazım sırasında üretilen kayıtlar için yazımdan sonra da çağrılabilir.
+    pub fn yeni_kayit(&mut self, tenant_id: &str, tablo: &str, kayit_id: &str) {
+        if self.izleniyor(tenant_id, tablo, kayit_id) {
+            return;
+        }
+        self.izlenenler.push(Izlenen {
+            tenant_id: tenant_id.to_string(),
+            tablo: tablo.to_string(),
+            kayit_id: kayit_id.to_string(),
+            once: None,
+        });
+    }
+
+    /// İzlenen kayıtların sonraki görüntülerini alıp denetim satırlarını ekler;
+    /// eklenen satır sayısını döner. Transaction'ın son adımı olarak çağrılır.
+    pub fn kaydet(&mut self, conn: &mut SqliteConnection) -> Result<usize, String> {
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+        let mut eklenen = 0;
+        for izlenen in std::mem::take(&mut self.izlenenler) {
+            let sonra = outbox::snapshot_row(conn, &izlenen.tablo, &izlenen.kayit_id, &izlenen.tenant_id)?;
+            let Some(islem) = islem_turu(izlenen.once.as_ref(), sonra.as_ref()) else {
+                continue;
+            };
+            let alanlar = degisen_alanlar(izlenen.once.as_ref(), sonra.as_ref());
+            if islem == "update" && alanlar.is_empty() {
+                continue;
+            }
+
+            diesel::sql_query(
+                "INSERT INTO denetim_kayitlari
+                    (id, tenant_id, kullanici_id, kullanici_adi, komut, table_name, record_id,
+                     islem, once, sonra, degisen_alanlar, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            )
+            .bind::<diesel::sql_types::Text, _>(uuid::Uuid::new_v4().to_string())
+            .bind::<diesel::sql_types::Text, _>(&izlenen.tenant_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&self.kullanici_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&self.kullanici_adi)
+            .bind::<diesel::sql_types::Text, _>(&self.komut)
+            .bind::<diesel::sql_types::Text, _>(&izlenen.tablo)
+            .bind::<diesel::sql_types::Text, _>(&izlenen.kayit_id)
+            .bind::<diesel::sql_types::Text, _>(islem)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(izlenen.once.map(gizle))
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(sonra.map(gizle))
+            .bind::<diesel::sql_types::Text, _>(serde_json::to_string(&alanlar).unwrap_or_default())
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| format!("Denetim kaydı yazılamadı: {}", e))?;
+            eklenen += 1;
+        }
+        Ok(eklenen)
+    }
+}
+
+/// Tek kayıtlık yazımı denetim kaydıyla birlikte tek transaction'da çalıştırır
+/// (yalnızca bir satırı değiştiren basit CRUD komutları için).
+pub fn kayitli_yazim<T>(
+    conn: &mut SqliteConnection,
+    mut denetim: Denetim,
+    tenant_id: &str,
+    tablo: &str,
+    kayit_id: &str,
+    yazim: impl FnOnce(&mut SqliteConnection) -> Result<T, String>,
+) -> Result<T, String> {
+    conn.transaction::<_, outbox::TxError, _>(|conn| {
+        denetim.izle(conn, tenant_id, tablo, kayit_id).map_err(outbox::TxError::Msg)?;
+        let sonuc = yazim(conn).map_err(outbox::TxError::Msg)?;
+        denetim.kaydet(conn).map_err(outbox::TxError::Msg)?;
+        Ok(sonuc)
+    })
+    .map_err(|e| e.to_string())
+}
+
+fn gizle(mut v: Value) -> String {
+    if let Value::Object(map) = &mut v {
+        for alan in GIZLI_ALANLAR {
+            if let Some(x) = map.get_mut(alan) {
+                *x = Value::String("***".to_string());
+            }
+        }
+    }
+    v.to_string()
+}
+
+fn bayrak(v: Option<&Value>, alan: &str) -> Option<i64> {
+    v?.get(alan).and_then(|x| x.as_i64().or_else(|| x.as_bool().map(i64::from)))
+}
+
+fn islem_turu(once: Option<&Value>, sonra: Option<&Value>) -> Option<&'static str> {
+    match (once, sonra) {
+        (None, None) => None,
+        (None, Some(_)) => Some("create"),
+        (Some(_), None) => Some("delete"),
+        (Some(_), Some(_)) => {
+            let pasiflesti = bayrak(once, "is_active") == Some(1) && bayrak(sonra, "is_active") == Some(0);
+            let silindi = bayrak(once, "is_deleted") != Some(1) && bayrak(sonra, "is_deleted") == Some(1);
+            Some(if pasiflesti || silindi { "delete" } else { "update" })
+        }
+    }
+}
+
+/// Değeri değişen alanlar (teknik alanlar hariç), alfabetik
+fn degisen_alanlar(once: Option<&Value>, sonra: Option<&Value>) -> Vec<String> {
+    let bos = serde_json::Map::new();
+    let once = once.and_then(|v| v.as_object()).unwrap_or(&bos);
+    let sonra = sonra.and_then(|v| v.as_object()).unwrap_or(&bos);
+    let mut alanlar: Vec<String> = once
+        .keys()
+        .chain(sonra.keys())
+        .filter(|k| !TEKNIK_ALANLAR.contains(&k.as_str()))
+        .filter(|k| once.get(*k) != sonra.get(*k))
+        .cloned()
+        .collect();
+    alanlar.sort();
+    alanlar.dedup();
+    alanlar
+}
+
+/// denetim_kayitlari'nı yalnızca eklemeye açık tutan tetikleyiciler.
+/// Migration çalıştırıcı ';' ile böldüğü için tetikleyici gövdeleri migration
+/// dosyasında tanımlanamaz; açılışta migration'lardan sonra kurulur.
+pub fn koruma_kur(conn: &mut SqliteConnection) -> QueryResult<()> {
+    for (ad, olay) in [("trg_denetim_guncelleme_yok", "UPDATE"), ("trg_denetim_silme_yok", "DELETE")] {
+        diesel::sql_query(format!(
+            "CREATE TRIGGER IF NOT EXISTS {} BEFORE {} ON denetim_kayitlari
+             BEGIN SELECT RAISE(ABORT, 'denetim kayıtları değiştirilemez'); END",
+            ad, olay
+        ))
+        .execute(conn)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    const GELIR_G1: &str = "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar, updated_at)
+        VALUES ('g1', 't1', 'k1', '2026-01-05', 1.0, 'a')";
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::kasa(&mut conn, "t1", "k1", "Ana Kasa");
+        conn
+    }
+
+    #[derive(QueryableByName)]
+    struct Satir {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        islem: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        once: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        degisen_alanlar: Option<String>,
+    }
+
+    fn satirlar(conn: &mut SqliteConnection) -> Vec<Satir> {
+        diesel::sql_query("SELECT islem, once, degisen_alanlar FROM denetim_kayitlari ORDER BY rowid")
+            .load(conn)
+            .unwrap()
+    }
+
+    fn calistir(conn: &mut SqliteConnection, komut: &str, sql: &str) {
+        let denetim = Denetim::yeni(Some("u1".into()), Some("Ayşe".into()), komut);
+        kayitli_yazim(conn, denetim, "t1", "gelirler", "g1", |conn| {
+            diesel::sql_query(sql).execute(conn).map_err(|e| e.to_string())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn islem_turleri_ve_degisen_alanlar() {
+        let mut conn = test_db();
+        calistir(
+            &mut conn,
+            "create_gelir",
+            "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar, aciklama, updated_at)
+             VALUES ('g1', 't1', 'k1', '2026-01-05', 100.0, 'Bağış', 'a')",
+        );
+        calistir(&mut conn, "update_gelir", "UPDATE gelirler SET tutar = 150.0, updated_at = 'b' WHERE id = 'g1'");
+        // Yalnızca teknik alan değişti: kayıt atılmaz
+        calistir(&mut conn, "update_gelir", "UPDATE gelirler SET updated_at = 'c' WHERE id = 'g1'");
+        calistir(&mut conn, "delete_gelir", "UPDATE gelirler SET is_active = 0 WHERE id = 'g1'");
+
+        let s = satirlar(&mut conn);
+        let islemler: Vec<&str> = s.iter().map(|r| r.islem.as_str()).collect();
+        assert_eq!(islemler, ["create", "update", "delete"]);
+        assert!(s[0].once.is_none());
+        assert_eq!(s[1].degisen_alanlar.as_deref(), Some(r#"["tutar"]"#));
+        assert!(s[1].once.as_deref().unwrap().contains("100"));
+    }
+
+    #[test]
+    fn yazim_hatasinda_denetim_de_geri_alinir_ve_kayitlar_degistirilemez() {
+        let mut conn = test_db();
+        let denetim = Denetim::yeni(None, None, "create_gelir");
+        let sonuc: Result<(), String> = kayitli_yazim(&mut conn, denetim, "t1", "gelirler", "g1", |conn| {
+            diesel::sql_query(GELIR_G1)
+                .execute(conn)
+                .map_err(|e| e.to_string())?;
+            Err("kasa bulunamadı".to_string())
+        });
+        assert!(sonuc.is_err());
+        assert!(satirlar(&mut conn).is_empty());
+
+        calistir(&mut conn, "create_gelir", GELIR_G1);
+        assert!(diesel::sql_query("UPDATE denetim_kayitlari SET islem = 'x'").execute(&mut conn).is_err());
+        assert!(diesel::sql_query("DELETE FROM denetim_kayitlari").execute(&mut conn).is_err());
+    }
+
+    #[test]
+    fn kiraci_kaydi_kendi_kimligiyle_izlenir() {
+        let mut conn = test_db();
+        let denetim = Denetim::yeni(None, None, "update_tenant");
+        kayitli_yazim(&mut conn, denetim, "t1", "tenants", "t1", |conn| {
+            diesel::sql_query("UPDATE tenants SET name = 'Yeni Ad' WHERE id = 't1'")
+                .execute(conn)
+                .map_err(|e| e.to_string())
+        })
+        .unwrap();
+
+        let s = satirlar(&mut conn);
+        assert_eq!(s.len(), 1);
+        assert_eq!((s[0].islem.as_str(), s[0].degisen_alanlar.as_deref()), ("update", Some(r#"["name"]"#)));
+    }
+}
@@ -0,0 +1,393 @@
+// Dönem kilidi: kapatılmış ay/yıllarda mali kayıt değişikliğini engeller.
+//
+// Kilit tenant bazında bir yıl (ay = NULL) veya tek bir ay içindir. Yıl sonu
+// devri uygulanan yıl otomatik kilitlenir; kilidi yalnızca yönetici, gerekçe
+// yazarak açabilir (açılan kilit silinmez, durum = 'acik' olur).
+//
+// Bir değişikliğin hangi döneme düştüğü:
+//   - gelirler / giderler / virmanlar: kaydın tarihi (güncellemede eski ve yeni tarih)
+//   - aidat_takip tahakkuku (oluşturma, tutar değişikliği, iptal/silme): dönemin
+//     ilk günü (yil-ay-01) — yevmiyedeki tahakkuk fişiyle aynı tarih
+//   - aidat ödemesi: ödemenin (gelir kaydının) tarihi
+//
+// Kontroller yazımla aynı transaction içinde çağrılır. Sunucudan gelen ve kilitli
+// döneme düşen değişiklikler uygulanmaz, donem_kilidi_ihlalleri'ne kaydedilir.
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct DonemKilidi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub ay: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kilitleyen: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kilit_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub acan: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub acilis_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub acilis_nedeni: Option<String>,
+}
+
+impl DonemKilidi {
+    /// "2025" veya "2025/03"
+    pub fn donem(&self) -> String {
+        match self.ay {
+            Some(ay) => format!("{}/{:02}", self.yil, ay),
+            None => self.yil.to_string(),
+        }
+    }
+
+    fn hata(&self) -> String {
+        format!(
+            "{} dönemi kilitli{}. Değişiklik için dönem kilidini yönetici açmalıdır.",
+            self.donem(),
+            self.aciklama
+                .as_deref()
+                .filter(|a| !a.is_empty())
+                .map(|a| format!(" ({})", a))
+                .unwrap_or_default()
+        )
+    }
+}
+
+const KILIT_KOLONLARI: &str =
+    "id, yil, ay, durum, aciklama, kilitleyen, kilit_tarihi, acan, acilis_tarihi, acilis_nedeni";
+
+/// Dönemi kapsayan aktif kilit. `ay` verilirse yıl kilidi veya o ayın kilidi;
+/// verilmezse yıl içindeki herhangi bir kilit (yıl kilidi önce).
+pub fn kilit_bul(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+    ay: Option<i32>,
+) -> Result<Option<DonemKilidi>, String> {
+    diesel::sql_query(format!(
+        "SELECT {} FROM donem_kilitleri
+         WHERE tenant_id = ?1 AND durum = 'kilitli' AND yil = ?2
+           AND (ay IS NULL OR ?3 IS NULL OR ay = ?3)
+         ORDER BY ay IS NULL DESC, ay
+         LIMIT 1",
+        KILIT_KOLONLARI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(ay)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| format!("Dönem kilidi okunamadı: {}", e))
+}
+
+/// Yalnızca tüm yılı kapatan aktif kilit (yıl sonu devri)
+pub fn yil_kilidi(conn: &mut SqliteConnection, tenant_id: &str, yil: i32) -> Result<Option<DonemKilidi>, String> {
+    Ok(kilit_bul(conn, tenant_id, yil, None)?.filter(|k| k.ay.is_none()))
+}
+
+/// "YYYY-MM-DD..." → (yıl, ay)
+fn tarih_donemi(tarih: &str) -> Result<(i32, i32), String> {
+    let yil = tarih.get(0..4).and_then(|s| s.parse().ok());
+    let ay = tarih.get(5..7).and_then(|s| s.parse().ok());
+    match (yil, ay) {
+        (Some(yil), Some(ay)) => Ok((yil, ay)),
+        _ => Err(format!("Geçersiz tarih: {}", tarih)),
+    }
+}
+
+pub fn tarih_kilidi(conn: &mut SqliteConnection, tenant_id: &str, tarih: &str) -> Result<Option<DonemKilidi>, String> {
+    let (yil, ay) = tarih_donemi(tarih)?;
+    kilit_bul(conn, tenant_id, yil, Some(ay))
+}
+
+/// Tarihin düştüğü dönem kilitliyse hata
+pub fn tarihi_dogrula(conn: &mut SqliteConnection, tenant_id: &str, tarih: &str) -> Result<(), String> {
+    match tarih_kilidi(conn, tenant_id, tarih)? {
+        Some(kilit) => Err(kilit.hata()),
+        None => Ok(()),
+    }
+}
+
+/// Aidat dönemi (yil/ay) kilitliyse hata
+pub fn donemi_dogrula(conn: &mut SqliteConnection, tenant_id: &str, yil: i32, ay: i32) -> Result<(), String> {
+    tarihi_dogrula(conn, tenant_id, &format!("{:04}-{:02}-01", yil, ay.clamp(1, 12)))
+}
+
+/// Mevcut kaydın (gelirler, giderler, virmanlar, aidat_takip) dönem tarihi; kayıt yoksa None
+pub fn kayit_tarihi(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tablo: &str,
+    kayit_id: &str,
+) -> Result<Option<String>, String> {
+    #[derive(QueryableByName)]
+    struct TarihRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+    }
+
+    let sorgu = match tablo {
+        "gelirler" | "giderler" | "virmanlar" => {
+            format!("SELECT substr(tarih, 1, 10) as tarih FROM {} WHERE id = ?1 AND tenant_id = ?2", tablo)
+        }
+        "aidat_takip" => "SELECT printf('%04d-%02d-01', yil, MIN(MAX(ay, 1), 12)) as tarih
+                          FROM aidat_takip WHERE id = ?1 AND tenant_id = ?2"
+            .to_string(),
+        _ => return Ok(None),
+    };
+    let row: Option<TarihRow> = diesel::sql_query(sorgu)
+        .bind::<diesel::sql_types::Text, _>(kayit_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.tarih))
+}
+
+/// Mevcut kaydın dönemi kilitliyse hata (güncelleme ve silmelerde eski değer için)
+pub fn kaydi_dogrula(conn: &mut SqliteConnection, tenant_id: &str, tablo: &str, kayit_id: &str) -> Result<(), String> {
+    match kayit_tarihi(conn, tenant_id, tablo, kayit_id)? {
+        Some(tarih) => tarihi_dogrula(conn, tenant_id, &tarih),
+        None => Ok(()),
+    }
+}
+
+/// Sync payload'ından dönem tarihi (tarih alanı veya aidat yil/ay)
+pub fn veri_tarihi(tablo: &str, data: &serde_json::Value) -> Option<String> {
+    match tablo {
+        "gelirler" | "giderler" | "virmanlar" => data
+            .get("tarih")
+            .and_then(|v| v.as_str())
+            .map(|t| t.chars().take(10).collect()),
+        "aidat_takip" => {
+            let yil = data.get("yil").and_then(|v| v.as_i64())?;
+            let ay = data.get("ay").and_then(|v| v.as_i64()).unwrap_or(1);
+            Some(format!("{:04}-{:02}-01", yil, ay.clamp(1, 12)))
+        }
+        _ => None,
+    }
+}
+
+/// Sunucudan gelen değişikliğin dokunduğu kilitli dönem: yerel kaydın mevcut
+/// dönemi veya payload'daki yeni dönem kilitliyse o kilit döner.
+pub fn uzak_degisiklik_kilidi(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tablo: &str,
+    kayit_id: &str,
+    data: &serde_json::Value,
+) -> Result<Option<DonemKilidi>, String> {
+    let tarihler = [kayit_tarihi(conn, tenant_id, tablo, kayit_id)?, veri_tarihi(tablo, data)];
+    for tarih in tarihler.iter().flatten() {
+        if let Some(kilit) = tarih_kilidi(conn, tenant_id, tarih)? {
+            return Ok(Some(kilit));
+        }
+    }
+    Ok(None)
+}
+
+/// Kilitli döneme düşen uzak değişikliği uygulamadan kayda alır
+pub fn ihlal_kaydet(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kilit: &DonemKilidi,
+    tablo: &str,
+    kayit_id: &str,
+    islem: &str,
+    degisiklik: &serde_json::Value,
+) -> Result<(), String> {
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO donem_kilidi_ihlalleri
+            (id, tenant_id, kilit_id, table_name, record_id, islem, degisiklik, durum, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'bekliyor', ?8)"
+    )
+    .bind::<diesel::sql_types::Text, _>(uuid::Uuid::new_v4().to_string())
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&kilit.id)
+    .bind::<diesel::sql_types::Text, _>(tablo)
+    .bind::<diesel::sql_types::Text, _>(kayit_id)
+    .bind::<diesel::sql_types::Text, _>(islem)
+    .bind::<diesel::sql_types::Text, _>(degisiklik.to_string())
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| format!("Dönem kilidi ihlali kaydedilemedi: {}", e))?;
+    Ok(())
+}
+
+/// Dönemi kilitler. Aynı dönemde aktif kilit varsa hata.
+pub fn kilitle(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    yil: i32,
+    ay: Option<i32>,
+    aciklama: Option<&str>,
+    kilitleyen: Option<&str>,
+) -> Result<String, String> {
+    if let Some(ay) = ay {
+        if !(1..=12).contains(&ay) {
+            return Err(format!("Geçersiz ay: {}", ay));
+        }
+    }
+    let mevcut: Option<DonemKilidi> = diesel::sql_query(format!(
+        "SELECT {} FROM donem_kilitleri
+         WHERE tenant_id = ?1 AND durum = 'kilitli' AND yil = ?2 AND ay IS ?3
+         LIMIT 1",
+        KILIT_KOLONLARI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(ay)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+    if let Some(k) = mevcut {
+        return Err(format!("{} dönemi zaten kilitli", k.donem()));
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO donem_kilitleri
+            (id, tenant_id, yil, ay, durum, aciklama, kilitleyen, kilit_tarihi, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, 'kilitli', ?5, ?6, ?7, ?7, ?7)"
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(ay)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aciklama)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(kilitleyen)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{connection, test_db};
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        conn
+    }
+
+    #[test]
+    fn migration_devri_yapilmis_son_yili_kilitler() {
+        let mut conn = test_db::surumden_once("036");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, devir_tarihi) VALUES
+             ('k1', 't1', 'Ana Kasa', '2025-01-01'), ('k2', 't1', 'Banka', '2026-01-01')",
+        );
+        connection::run_migrations(&mut conn).unwrap();
+
+        assert!(yil_kilidi(&mut conn, "t1", 2025).unwrap().is_some());
+        assert!(yil_kilidi(&mut conn, "t1", 2024).unwrap().is_none());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2025-12-31").is_err());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2026-01-01").is_ok());
+        assert!(tarihi_dogrula(&mut conn, "t2", "2025-06-01").is_ok());
+    }
+
+    #[test]
+    fn migration_ilk_hareketten_itibaren_devredilen_her_yili_kilitler() {
+        let mut conn = test_db::surumden_once("036");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, devir_tarihi) VALUES ('k1', 't1', 'Ana Kasa', '2026-01-01')",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar) VALUES
+             ('g1', 't1', 'k1', '2023-05-10', 100), ('g2', 't1', 'k1', '2026-02-01', 50)",
+        );
+        connection::run_migrations(&mut conn).unwrap();
+
+        for yil in 2023..=2025 {
+            assert!(yil_kilidi(&mut conn, "t1", yil).unwrap().is_some(), "{} kilitli olmalı", yil);
+        }
+        assert!(yil_kilidi(&mut conn, "t1", 2022).unwrap().is_none());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2023-05-10").is_err());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2024-07-01").is_err());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2026-02-01").is_ok());
+    }
+
+    #[test]
+    fn migration_eski_surumde_devredilmis_yili_kilitler() {
+        // Eski devir: 2025 hareketleri devir_bakiye'de, kesim tarihi yok; 2026'da +250 TL
+        let mut conn = test_db::surumden_once("034");
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, devir_bakiye, fiziksel_bakiye, bakiye) VALUES
+             ('k1', 't1', 'Ana Kasa', 1000, 1250, 1250)",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar) VALUES
+             ('g1', 't1', 'k1', '2025-02-10', 1000), ('g2', 't1', 'k1', '2026-01-20', 250)",
+        );
+        connection::run_migrations(&mut conn).unwrap();
+
+        assert!(yil_kilidi(&mut conn, "t1", 2025).unwrap().is_some());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2025-02-10").is_err());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2026-01-20").is_ok());
+    }
+
+    #[test]
+    fn ay_kilidi_yalnizca_o_ayi_kapatir() {
+        let mut conn = test_db();
+        kilitle(&mut conn, "t1", 2026, Some(3), Some("Mart kapanışı"), None).unwrap();
+
+        let hata = tarihi_dogrula(&mut conn, "t1", "2026-03-15 10:00:00").unwrap_err();
+        assert!(hata.contains("2026/03") && hata.contains("Mart kapanışı"));
+        assert!(donemi_dogrula(&mut conn, "t1", 2026, 3).is_err());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2026-02-28").is_ok());
+        assert!(tarihi_dogrula(&mut conn, "t1", "2026-04-01").is_ok());
+
+        // Yılı kapatmaz ama yıl içinde kilit olarak görünür
+        assert!(yil_kilidi(&mut conn, "t1", 2026).unwrap().is_none());
+        assert!(kilit_bul(&mut conn, "t1", 2026, None).unwrap().is_some());
+    }
+
+    #[test]
+    fn ayni_donem_ikinci_kez_ve_gecersiz_ay_kilitlenemez() {
+        let mut conn = test_db();
+        kilitle(&mut conn, "t1", 2026, Some(3), None, None).unwrap();
+        assert!(kilitle(&mut conn, "t1", 2026, Some(3), None, None).is_err());
+        assert!(kilitle(&mut conn, "t1", 2026, Some(13), None, None).is_err());
+        assert!(kilitle(&mut conn, "t1", 2026, Some(0), None, None).is_err());
+    }
+
+    #[test]
+    fn aidat_tahakkuku_donemine_gore_dogrulanir() {
+        let mut conn = test_db();
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar) VALUES ('a1', 't1', 'u1', 2026, 3, 100.0)",
+        );
+        kilitle(&mut conn, "t1", 2026, Some(3), None, None).unwrap();
+
+        assert!(kaydi_dogrula(&mut conn, "t1", "aidat_takip", "a1").is_err());
+        assert!(kaydi_dogrula(&mut conn, "t1", "aidat_takip", "yok").is_ok());
+
+        // Uzak değişiklik kaydın mevcut dönemi kilitliyse reddedilir; yeni kayıt kendi dönemine bakar
+        let veri = serde_json::json!({ "yil": 2026, "ay": 5 });
+        assert!(uzak_degisiklik_kilidi(&mut conn, "t1", "aidat_takip", "a1", &veri).unwrap().is_some());
+        assert!(uzak_degisiklik_kilidi(&mut conn, "t1", "aidat_takip", "yeni", &veri).unwrap().is_none());
+    }
+}
@@ -0,0 +1,730 @@
+// Etkinlik kayıtları: üye ve misafir kaydı, kapasite ve yedek liste, bilet tahsilatı bağlantısı,
+// QR koduyla giriş ve katılım raporu.
+//
+// Kapasite (etkinlikler.kapasite) doluysa yeni kayıt yedek listeye sırayla alınır; kesin kayıt
+// iptal edildiğinde ya da kapasite artırıldığında yedekler sırayla kesin kayda geçer
+// (yedekleri_al). Bilet ücreti kayıt anında belirlenir; tahsilat etkinlik_id ile bağlı gelir
+// kaydıdır ve komut katmanında mali::gelir_ekle ile yazılıp odeme_isle ile kayda bağlanır;
+// gelir silinirse bağ gelir_silindi ile çözülür.
+// Ücreti ödenmemiş kayıtla giriş yapılamaz; giriş yapanlar etkinlikler.katilimci_sayisi'ni belirler.
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::outbox;
+use crate::db::para::Para;
+
+pub const DURUM_KAYITLI: &str = "kayitli";
+pub const DURUM_YEDEK: &str = "yedek";
+pub const DURUM_IPTAL: &str = "iptal";
+pub const KAYIT_DURUMLARI: [&str; 3] = [DURUM_KAYITLI, DURUM_YEDEK, DURUM_IPTAL];
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct EtkinlikKaydi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub etkinlik_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub telefon: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub email: Option<String>,
+    /// DURUM_KAYITLI | DURUM_YEDEK | DURUM_IPTAL
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub yedek_sira: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub bilet_ucreti: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gelir_id: Option<String>,
+    /// Girişte okutulan QR kodunun değeri
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub qr_kodu: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub giris_zamani: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub iptal_nedeni: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+impl EtkinlikKaydi {
+    /// Ücretli kaydın tahsilatı yapılmamışsa true
+    pub fn odeme_bekliyor(&self) -> bool {
+        self.bilet_ucreti.is_positive() && self.gelir_id.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KayitGirdi {
+    pub uye_id: Option<String>,
+    /// Misafirde zorunlu; üyede boşsa üye kaydından
+    pub ad_soyad: Option<String>,
+    pub telefon: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EtkinlikKatilimRaporu {
+    pub etkinlik_id: String,
+    pub kapasite: Option<i32>,
+    pub kayitli: i64,
+    pub yedek: i64,
+    pub iptal: i64,
+    /// Giriş yapan kayıtlar
+    pub katilan: i64,
+    pub uye_katilan: i64,
+    pub misafir_katilan: i64,
+    /// Kesin kaydı olup giriş yapmayanlar
+    pub gelmeyen: i64,
+    pub tahsil_edilen: Para,
+    pub tahsil_edilmemis: Para,
+}
+
+#[derive(QueryableByName)]
+struct EtkinlikRow {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    durum: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    kapasite: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    bilet_ucreti: Option<Para>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    uye_bilet_ucreti: Option<Para>,
+}
+
+impl EtkinlikRow {
+    fn durum_mu(&self, durumlar: &[&str]) -> bool {
+        self.durum.as_deref().is_some_and(|d| durumlar.contains(&d))
+    }
+}
+
+fn etkinlik_getir(conn: &mut SqliteConnection, tenant_id: &str, etkinlik_id: &str) -> Result<EtkinlikRow, String> {
+    diesel::sql_query(
+        "SELECT durum, kapasite, bilet_ucreti, uye_bilet_ucreti FROM etkinlikler
+         WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Etkinlik bulunamadı".to_string())
+}
+
+const KAYIT_SECIMI: &str = "SELECT k.id, k.etkinlik_id, k.uye_id, u.uye_no, k.ad_soyad, k.telefon, k.email, k.durum,
+        k.yedek_sira, k.bilet_ucreti, k.gelir_id, k.qr_kodu, k.giris_zamani, k.iptal_nedeni, k.created_at
+     FROM etkinlik_kayitlari k
+     LEFT JOIN uyeler u ON u.id = k.uye_id AND u.tenant_id = k.tenant_id";
+
+/// Kesin kayıtlar, sonra sırasıyla yedekler, en sonda iptaller
+pub fn kayitlari_getir(conn: &mut SqliteConnection, tenant_id: &str, etkinlik_id: &str) -> Result<Vec<EtkinlikKaydi>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE k.tenant_id = ?1 AND k.etkinlik_id = ?2
+         ORDER BY CASE k.durum WHEN ?3 THEN 0 WHEN ?4 THEN 1 ELSE 2 END, k.yedek_sira, k.created_at",
+        KAYIT_SECIMI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+    .bind::<diesel::sql_types::Text, _>(DURUM_KAYITLI)
+    .bind::<diesel::sql_types::Text, _>(DURUM_YEDEK)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn kayit_getir(conn: &mut SqliteConnection, tenant_id: &str, kayit_id: &str) -> Result<EtkinlikKaydi, String> {
+    diesel::sql_query(format!("{} WHERE k.id = ?1 AND k.tenant_id = ?2", KAYIT_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(kayit_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Etkinlik kaydı bulunamadı".to_string())
+}
+
+#[derive(QueryableByName)]
+struct SayiRow {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    sayi: i64,
+}
+
+fn say(conn: &mut SqliteConnection, tenant_id: &str, etkinlik_id: &str, durum: &str) -> Result<i64, String> {
+    diesel::sql_query("SELECT COUNT(*) AS sayi FROM etkinlik_kayitlari WHERE tenant_id = ?1 AND etkinlik_id = ?2 AND durum = ?3")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+        .bind::<diesel::sql_types::Text, _>(durum)
+        .get_result::<SayiRow>(conn)
+        .map(|r| r.sayi)
+        .map_err(|e| e.to_string())
+}
+
+/// Kayıt ekler; kapasite doluysa yedek listenin sonuna alınır
+pub fn kayit_ekle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    etkinlik_id: &str,
+    girdi: &KayitGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        telefon: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        email: Option<String>,
+    }
+
+    let etkinlik = etkinlik_getir(conn, tenant_id, etkinlik_id)?;
+    if etkinlik.durum_mu(&["İptal", "iptal", "Tamamlandı", "tamamlandi"]) {
+        return Err("İptal edilmiş ya da tamamlanmış etkinliğe kayıt alınamaz".to_string());
+    }
+
+    let bos = |s: &Option<String>| s.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+    let uye_id = bos(&girdi.uye_id);
+    let (mut ad_soyad, mut telefon, mut email) = (bos(&girdi.ad_soyad), bos(&girdi.telefon), bos(&girdi.email));
+    if let Some(uye_id) = uye_id.as_deref() {
+        let uye: UyeRow = diesel::sql_query(
+            "SELECT ad_soyad, telefon, email FROM uyeler WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+        )
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or("Üye bulunamadı")?;
+        let kayitli = diesel::sql_query(
+            "SELECT COUNT(*) AS sayi FROM etkinlik_kayitlari
+             WHERE tenant_id = ?1 AND etkinlik_id = ?2 AND uye_id = ?3 AND durum <> ?4",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Text, _>(DURUM_IPTAL)
+        .get_result::<SayiRow>(conn)
+        .map_err(|e| e.to_string())?;
+        if kayitli.sayi > 0 {
+            return Err("Üyenin bu etkinlikte kaydı var".to_string());
+        }
+        ad_soyad = ad_soyad.or(Some(uye.ad_soyad));
+        telefon = telefon.or(uye.telefon);
+        email = email.or(uye.email);
+    }
+    let ad_soyad = ad_soyad.ok_or("Misafir kaydında ad soyad zorunludur")?;
+
+    // Üyeye üye fiyatı (yoksa genel fiyat), misafire genel fiyat
+    let bilet_ucreti = match uye_id {
+        Some(_) => etkinlik.uye_bilet_ucreti.or(etkinlik.bilet_ucreti),
+        None => etkinlik.bilet_ucreti,
+    }
+    .unwrap_or(Para::SIFIR);
+    let dolu = match etkinlik.kapasite {
+        Some(kapasite) => say(conn, tenant_id, etkinlik_id, DURUM_KAYITLI)? >= kapasite as i64,
+        None => false,
+    };
+    let yedek_sira = if dolu {
+        Some(say(conn, tenant_id, etkinlik_id, DURUM_YEDEK)? as i32 + 1)
+    } else {
+        None
+    };
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO etkinlik_kayitlari (id, tenant_id, etkinlik_id, uye_id, ad_soyad, telefon, email, durum, yedek_sira,
+                bilet_ucreti, qr_kodu, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&uye_id)
+    .bind::<diesel::sql_types::Text, _>(&ad_soyad)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&telefon)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&email)
+    .bind::<diesel::sql_types::Text, _>(if dolu { DURUM_YEDEK } else { DURUM_KAYITLI })
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(yedek_sira)
+    .bind::<diesel::sql_types::Double, _>(bilet_ucreti)
+    .bind::<diesel::sql_types::Text, _>(format!("ETK-{}", Uuid::new_v4().simple()).to_uppercase())
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    denetim.yeni_kayit(tenant_id, "etkinlik_kayitlari", &id);
+    Ok(id)
+}
+
+/// Boşalan kapasite kadar yedeği sırayla kesin kayda alır; alınan kayıtlar döner
+pub fn yedekleri_al(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    etkinlik_id: &str,
+) -> Result<Vec<String>, String> {
+    let etkinlik = etkinlik_getir(conn, tenant_id, etkinlik_id)?;
+    let yedekler: Vec<EtkinlikKaydi> =
+        kayitlari_getir(conn, tenant_id, etkinlik_id)?.into_iter().filter(|k| k.durum == DURUM_YEDEK).collect();
+    let bos_yer = match etkinlik.kapasite {
+        Some(kapasite) => (kapasite as i64 - say(conn, tenant_id, etkinlik_id, DURUM_KAYITLI)?).max(0) as usize,
+        None => yedekler.len(),
+    };
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut alinan = Vec::new();
+    for (i, kayit) in yedekler.iter().enumerate() {
+        denetim.izle(conn, tenant_id, "etkinlik_kayitlari", &kayit.id)?;
+        // Kesin kayda geçenlerin sırası silinir, kalan yedekler baştan numaralanır
+        let (durum, sira) = if i < bos_yer { (DURUM_KAYITLI, None) } else { (DURUM_YEDEK, Some((i - bos_yer + 1) as i32)) };
+        diesel::sql_query(
+            "UPDATE etkinlik_kayitlari SET durum = ?1, yedek_sira = ?2, updated_at = ?3 WHERE id = ?4 AND tenant_id = ?5",
+        )
+        .bind::<diesel::sql_types::Text, _>(durum)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(sira)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&kayit.id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        if durum == DURUM_KAYITLI {
+            alinan.push(kayit.id.clone());
+        }
+    }
+    Ok(alinan)
+}
+
+/// Kaydı iptal eder ve boşalan yere yedek alır; yedekten kesin kayda geçenler döner.
+/// Giriş yapılmış ya da ücreti tahsil edilmiş kayıt iptal edilemez (önce gelir iade edilmeli).
+pub fn kayit_iptal(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    kayit_id: &str,
+    neden: &str,
+) -> Result<Vec<String>, String> {
+    let kayit = kayit_getir(conn, tenant_id, kayit_id)?;
+    if kayit.durum == DURUM_IPTAL {
+        return Err("Kayıt zaten iptal edilmiş".to_string());
+    }
+    if kayit.giris_zamani.is_some() {
+        return Err("Giriş yapılmış kayıt iptal edilemez".to_string());
+    }
+    if kayit.gelir_id.is_some() {
+        return Err("Bilet ücreti tahsil edilmiş kayıt iptal edilemez; önce tahsilat kaydını iptal edin".to_string());
+    }
+    let neden = neden.trim();
+    if neden.is_empty() {
+        return Err("İptal nedeni zorunludur".to_string());
+    }
+
+    denetim.izle(conn, tenant_id, "etkinlik_kayitlari", kayit_id)?;
+    diesel::sql_query(
+        "UPDATE etkinlik_kayitlari SET durum = ?1, yedek_sira = NULL, iptal_nedeni = ?2, updated_at = ?3
+         WHERE id = ?4 AND tenant_id = ?5",
+    )
+    .bind::<diesel::sql_types::Text, _>(DURUM_IPTAL)
+    .bind::<diesel::sql_types::Text, _>(neden)
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(kayit_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    yedekleri_al(conn, denetim, tenant_id, &kayit.etkinlik_id)
+}
+
+/// Tahsil edilecek kaydı doğrular: kesin kayıt, ücretli ve henüz ödenmemiş olmalı
+pub fn tahsil_edilecek(conn: &mut SqliteConnection, tenant_id: &str, kayit_id: &str) -> Result<EtkinlikKaydi, String> {
+    let kayit = kayit_getir(conn, tenant_id, kayit_id)?;
+    if kayit.durum != DURUM_KAYITLI {
+        return Err("Yalnızca kesin kayıtların bilet ücreti tahsil edilir".to_string());
+    }
+    if !kayit.bilet_ucreti.is_positive() {
+        return Err("Kayıt ücretsizdir".to_string());
+    }
+    if kayit.gelir_id.is_some() {
+        return Err("Bilet ücreti zaten tahsil edilmiş".to_string());
+    }
+    Ok(kayit)
+}
+
+/// Tahsilat gelirini kayda bağlar (gelir aynı transaction'da yazılmış olmalı)
+pub fn odeme_isle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    kayit_id: &str,
+    gelir_id: &str,
+) -> Result<(), String> {
+    tahsil_edilecek(conn, tenant_id, kayit_id)?;
+    denetim.izle(conn, tenant_id, "etkinlik_kayitlari", kayit_id)?;
+    diesel::sql_query("UPDATE etkinlik_kayitlari SET gelir_id = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
+        .bind::<diesel::sql_types::Text, _>(gelir_id)
+        .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(kayit_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Silinen tahsilat gelirinin kayıtlarla bağını çözer; kayıt yeniden ödeme bekler ve iptal
+/// edilebilir. Gelir silme transaction'ında çağrılır.
+pub fn gelir_silindi(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    gelir_id: &str,
+) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let kayitlar: Vec<IdRow> = diesel::sql_query("SELECT id FROM etkinlik_kayitlari WHERE tenant_id = ?1 AND gelir_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(gelir_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for kayit in kayitlar {
+        denetim.izle(conn, tenant_id, "etkinlik_kayitlari", &kayit.id)?;
+        diesel::sql_query("UPDATE etkinlik_kayitlari SET gelir_id = NULL, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3")
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&kayit.id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// QR kodu değeriyle girişi kaydeder ve etkinliğin katılımcı sayısını günceller
+pub fn giris_yap(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    etkinlik_id: &str,
+    qr_kodu: &str,
+    zaman: &str,
+) -> Result<EtkinlikKaydi, String> {
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let etkinlik = etkinlik_getir(conn, tenant_id, etkinlik_id)?;
+    if etkinlik.durum_mu(&["İptal", "iptal"]) {
+        return Err("İptal edilmiş etkinliğe giriş yapılamaz".to_string());
+    }
+    let kayit_id = diesel::sql_query(
+        "SELECT id FROM etkinlik_kayitlari WHERE tenant_id = ?1 AND etkinlik_id = ?2 AND qr_kodu = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+    .bind::<diesel::sql_types::Text, _>(qr_kodu.trim().to_uppercase())
+    .get_result::<IdRow>(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("QR kodu bu etkinliğin kayıtlarında bulunamadı")?
+    .id;
+    let kayit = kayit_getir(conn, tenant_id, &kayit_id)?;
+    match kayit.durum.as_str() {
+        DURUM_IPTAL => return Err(format!("{} adına kayıt iptal edilmiş", kayit.ad_soyad)),
+        DURUM_YEDEK => return Err(format!("{} yedek listede ({}. sıra)", kayit.ad_soyad, kayit.yedek_sira.unwrap_or_default())),
+        _ => {}
+    }
+    if let Some(giris) = kayit.giris_zamani.as_deref() {
+        return Err(format!("{} {} tarihinde giriş yapmış", kayit.ad_soyad, giris));
+    }
+    if kayit.odeme_bekliyor() {
+        return Err(format!("{} adına bilet ücreti ({} TL) tahsil edilmemiş", kayit.ad_soyad, kayit.bilet_ucreti.tr_format()));
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim.izle(conn, tenant_id, "etkinlik_kayitlari", &kayit_id)?;
+    diesel::sql_query("UPDATE etkinlik_kayitlari SET giris_zamani = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
+        .bind::<diesel::sql_types::Text, _>(zaman)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&kayit_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+
+    // etkinlikler sync yüzeyinde: sayı güncellemesi outbox'a da yazılır
+    denetim.izle(conn, tenant_id, "etkinlikler", etkinlik_id)?;
+    diesel::sql_query(
+        "UPDATE etkinlikler SET updated_at = ?1,
+            katilimci_sayisi = (SELECT COUNT(*) FROM etkinlik_kayitlari
+                                WHERE tenant_id = ?2 AND etkinlik_id = ?3 AND giris_zamani IS NOT NULL)
+         WHERE id = ?3 AND tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(etkinlik_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    outbox::queue_change(conn, tenant_id, "etkinlikler", etkinlik_id, "update")?;
+
+    kayit_getir(conn, tenant_id, &kayit_id)
+}
+
+/// Kayıt, yedek, giriş ve bilet tahsilatı sayıları
+pub fn katilim_raporu(conn: &mut SqliteConnection, tenant_id: &str, etkinlik_id: &str) -> Result<EtkinlikKatilimRaporu, String> {
+    let etkinlik = etkinlik_getir(conn, tenant_id, etkinlik_id)?;
+    let mut rapor = EtkinlikKatilimRaporu {
+        etkinlik_id: etkinlik_id.to_string(),
+        kapasite: etkinlik.kapasite,
+        ..Default::default()
+    };
+    for k in kayitlari_getir(conn, tenant_id, etkinlik_id)? {
+        match k.durum.as_str() {
+            DURUM_KAYITLI => rapor.kayitli += 1,
+            DURUM_YEDEK => rapor.yedek += 1,
+            _ => {
+                rapor.iptal += 1;
+                continue;
+            }
+        }
+        if k.giris_zamani.is_some() {
+            rapor.katilan += 1;
+            if k.uye_id.is_some() {
+                rapor.uye_katilan += 1;
+            } else {
+                rapor.misafir_katilan += 1;
+            }
+        } else if k.durum == DURUM_KAYITLI {
+            rapor.gelmeyen += 1;
+        }
+        if k.gelir_id.is_some() {
+            rapor.tahsil_edilen += k.bilet_ucreti;
+        } else if k.durum == DURUM_KAYITLI {
+            rapor.tahsil_edilmemis += k.bilet_ucreti;
+        }
+    }
+    Ok(rapor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    const GIRIS: &str = "2026-05-01 10:00";
+
+    /// Kapasite 2; üye bileti 100, misafir bileti 150
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::uye(&mut conn, "t1", "u2", "2", "Ayşe Kaya");
+        test_db::calistir(&mut conn, "UPDATE uyeler SET telefon = '5550000000' WHERE id = 'u1'");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO etkinlikler (id, tenant_id, baslik, baslangic_tarihi, durum, kapasite, bilet_ucreti, uye_bilet_ucreti)
+             VALUES ('e1', 't1', 'Bahar Şenliği', '2026-05-01', 'Planlandı', 2, 150.0, 100.0)",
+        );
+        conn
+    }
+
+    fn girdi(uye_id: Option<&str>, ad_soyad: Option<&str>) -> KayitGirdi {
+        KayitGirdi {
+            uye_id: uye_id.map(str::to_string),
+            ad_soyad: ad_soyad.map(str::to_string),
+            telefon: None,
+            email: None,
+        }
+    }
+
+    fn ekle(conn: &mut SqliteConnection, uye_id: Option<&str>, ad_soyad: Option<&str>) -> Result<String, String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        kayit_ekle(conn, &mut d, "t1", "e1", &girdi(uye_id, ad_soyad))
+    }
+
+    /// Kapasiteyi doldurur (u1, misafir) ve iki yedek yazar (u2, ikinci misafir)
+    fn dolu_etkinlik(conn: &mut SqliteConnection) -> [String; 4] {
+        [
+            ekle(conn, Some("u1"), None).unwrap(),
+            ekle(conn, None, Some("Misafir Bir")).unwrap(),
+            ekle(conn, Some("u2"), None).unwrap(),
+            ekle(conn, None, Some("Misafir İki")).unwrap(),
+        ]
+    }
+
+    fn iptal(conn: &mut SqliteConnection, kayit_id: &str) -> Result<Vec<String>, String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        kayit_iptal(conn, &mut d, "t1", kayit_id, "Gelemeyecek")
+    }
+
+    fn ode(conn: &mut SqliteConnection, kayit_id: &str, gelir_id: &str) -> Result<(), String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        odeme_isle(conn, &mut d, "t1", kayit_id, gelir_id)
+    }
+
+    fn gir(conn: &mut SqliteConnection, qr: &str, zaman: &str) -> Result<EtkinlikKaydi, String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        giris_yap(conn, &mut d, "t1", "e1", qr, zaman)
+    }
+
+    #[test]
+    fn uye_kaydi_uye_fiyatini_ve_iletisim_bilgisini_alir() {
+        let mut conn = test_db();
+        let ali = ekle(&mut conn, Some("u1"), None).unwrap();
+        let k = kayit_getir(&mut conn, "t1", &ali).unwrap();
+        assert_eq!((k.ad_soyad.as_str(), k.bilet_ucreti, k.telefon.as_deref()), ("Ali Veli", Para::from_tl(100.0), Some("5550000000")));
+        let misafir = ekle(&mut conn, None, Some("Misafir Bir")).unwrap();
+        assert_eq!(kayit_getir(&mut conn, "t1", &misafir).unwrap().bilet_ucreti, Para::from_tl(150.0));
+    }
+
+    #[test]
+    fn uye_ikinci_kez_kaydolamaz_misafirde_ad_zorunlu() {
+        let mut conn = test_db();
+        ekle(&mut conn, Some("u1"), None).unwrap();
+        assert!(ekle(&mut conn, Some("u1"), None).is_err());
+        assert!(ekle(&mut conn, None, Some(" ")).is_err());
+    }
+
+    #[test]
+    fn kapasite_dolunca_kayitlar_sirayla_yedege_alinir() {
+        let mut conn = test_db();
+        let [_, _, yedek1, yedek2] = dolu_etkinlik(&mut conn);
+        let k1 = kayit_getir(&mut conn, "t1", &yedek1).unwrap();
+        let k2 = kayit_getir(&mut conn, "t1", &yedek2).unwrap();
+        assert_eq!((k1.durum.as_str(), k1.yedek_sira), (DURUM_YEDEK, Some(1)));
+        assert_eq!((k2.durum.as_str(), k2.yedek_sira, k2.bilet_ucreti), (DURUM_YEDEK, Some(2), Para::from_tl(150.0)));
+    }
+
+    #[test]
+    fn iptal_edilen_kaydin_yerine_ilk_yedek_gecer() {
+        let mut conn = test_db();
+        let [_, misafir, yedek1, yedek2] = dolu_etkinlik(&mut conn);
+        assert_eq!(iptal(&mut conn, &misafir).unwrap(), vec![yedek1.clone()]);
+
+        let k1 = kayit_getir(&mut conn, "t1", &yedek1).unwrap();
+        assert_eq!((k1.durum.as_str(), k1.yedek_sira), (DURUM_KAYITLI, None));
+        // Kalan yedek birinci sıraya çıkar
+        assert_eq!(kayit_getir(&mut conn, "t1", &yedek2).unwrap().yedek_sira, Some(1));
+        assert_eq!(kayit_getir(&mut conn, "t1", &misafir).unwrap().durum, DURUM_IPTAL);
+    }
+
+    #[test]
+    fn yedegin_iptali_kimseyi_kesin_kayda_almaz() {
+        let mut conn = test_db();
+        let [_, _, yedek1, yedek2] = dolu_etkinlik(&mut conn);
+        assert!(iptal(&mut conn, &yedek1).unwrap().is_empty());
+        assert_eq!(kayit_getir(&mut conn, "t1", &yedek2).unwrap().yedek_sira, Some(1));
+        assert!(iptal(&mut conn, &yedek1).is_err());
+    }
+
+    #[test]
+    fn kapasite_artinca_yedekler_kesin_kayda_gecer() {
+        let mut conn = test_db();
+        let [_, _, yedek1, yedek2] = dolu_etkinlik(&mut conn);
+        test_db::calistir(&mut conn, "UPDATE etkinlikler SET kapasite = 3 WHERE id = 'e1'");
+        let mut d = Denetim::yeni(None, None, "test");
+        assert_eq!(yedekleri_al(&mut conn, &mut d, "t1", "e1").unwrap(), vec![yedek1]);
+        assert_eq!(kayit_getir(&mut conn, "t1", &yedek2).unwrap().durum, DURUM_YEDEK);
+    }
+
+    #[test]
+    fn ucreti_odenmeyen_ve_yedekteki_kayit_giremez() {
+        let mut conn = test_db();
+        let [ali, _, yedek1, _] = dolu_etkinlik(&mut conn);
+        let qr = kayit_getir(&mut conn, "t1", &ali).unwrap().qr_kodu;
+        assert!(gir(&mut conn, &qr, GIRIS).unwrap_err().contains("tahsil"));
+        let yedek_qr = kayit_getir(&mut conn, "t1", &yedek1).unwrap().qr_kodu;
+        assert!(gir(&mut conn, &yedek_qr, GIRIS).unwrap_err().contains("yedek"));
+        assert!(gir(&mut conn, "YOK-KOD", GIRIS).is_err());
+    }
+
+    #[test]
+    fn yedekteki_kaydin_ucreti_tahsil_edilemez() {
+        let mut conn = test_db();
+        let [_, _, yedek1, _] = dolu_etkinlik(&mut conn);
+        assert!(tahsil_edilecek(&mut conn, "t1", &yedek1).is_err());
+        assert!(ode(&mut conn, &yedek1, "g0").is_err());
+    }
+
+    #[test]
+    fn odenen_bilet_ikinci_kez_tahsil_edilemez_ve_iptal_edilemez() {
+        let mut conn = test_db();
+        let ali = ekle(&mut conn, Some("u1"), None).unwrap();
+        ode(&mut conn, &ali, "g1").unwrap();
+        assert_eq!(kayit_getir(&mut conn, "t1", &ali).unwrap().gelir_id.as_deref(), Some("g1"));
+        assert!(tahsil_edilecek(&mut conn, "t1", &ali).is_err());
+        assert!(iptal(&mut conn, &ali).is_err());
+    }
+
+    #[test]
+    fn tahsilat_geliri_silinince_kayit_odeme_bekler_ve_iptal_edilir() {
+        let mut conn = test_db();
+        let ali = ekle(&mut conn, Some("u1"), None).unwrap();
+        ode(&mut conn, &ali, "g1").unwrap();
+        let mut d = Denetim::yeni(None, None, "test");
+        gelir_silindi(&mut conn, &mut d, "t1", "g1").unwrap();
+
+        let k = kayit_getir(&mut conn, "t1", &ali).unwrap();
+        assert!(k.gelir_id.is_none() && k.odeme_bekliyor());
+        iptal(&mut conn, &ali).unwrap();
+        assert_eq!(kayit_getir(&mut conn, "t1", &ali).unwrap().durum, DURUM_IPTAL);
+    }
+
+    #[test]
+    fn qr_kodu_harf_buyuklugunden_bagimsiz_okunur() {
+        let mut conn = test_db();
+        let ali = ekle(&mut conn, Some("u1"), None).unwrap();
+        ode(&mut conn, &ali, "g1").unwrap();
+        let qr = kayit_getir(&mut conn, "t1", &ali).unwrap().qr_kodu.to_lowercase();
+        let giris = gir(&mut conn, &format!(" {} ", qr), GIRIS).unwrap();
+        assert_eq!(giris.giris_zamani.as_deref(), Some(GIRIS));
+    }
+
+    #[test]
+    fn qr_kodu_ikinci_kez_kullanilamaz() {
+        let mut conn = test_db();
+        let ali = ekle(&mut conn, Some("u1"), None).unwrap();
+        ode(&mut conn, &ali, "g1").unwrap();
+        let qr = kayit_getir(&mut conn, "t1", &ali).unwrap().qr_kodu;
+        gir(&mut conn, &qr, GIRIS).unwrap();
+
+        assert!(gir(&mut conn, &qr, "2026-05-01 10:05").is_err());
+        // İlk giriş zamanı korunur, giriş yapan kayıt iptal edilemez
+        assert_eq!(kayit_getir(&mut conn, "t1", &ali).unwrap().giris_zamani.as_deref(), Some(GIRIS));
+        assert!(iptal(&mut conn, &ali).is_err());
+    }
+
+    #[test]
+    fn katilim_raporu_giris_ve_tahsilati_ozetler() {
+        let mut conn = test_db();
+        let [ali, misafir, _, _] = dolu_etkinlik(&mut conn);
+        ode(&mut conn, &ali, "g1").unwrap();
+        let qr = kayit_getir(&mut conn, "t1", &ali).unwrap().qr_kodu;
+        gir(&mut conn, &qr, GIRIS).unwrap();
+        iptal(&mut conn, &misafir).unwrap();
+
+        #[derive(QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+            katilimci_sayisi: Option<i32>,
+        }
+        let row: Row = diesel::sql_query("SELECT katilimci_sayisi FROM etkinlikler WHERE id = 'e1'")
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(row.katilimci_sayisi, Some(1));
+
+        let rapor = katilim_raporu(&mut conn, "t1", "e1").unwrap();
+        assert_eq!((rapor.kayitli, rapor.yedek, rapor.iptal, rapor.katilan, rapor.gelmeyen), (2, 1, 1, 1, 1));
+        assert_eq!((rapor.tahsil_edilen, rapor.tahsil_edilmemis), (Para::from_tl(100.0), Para::from_tl(100.0)));
+    }
+}
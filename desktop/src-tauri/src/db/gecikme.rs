@@ -0,0 +1,640 @@
+// Gecikme faizi: vadesi geçmiş aidatlar için politika tabanlı faiz hesabı ve tahakkuku.
+//
+// Vade, aidat tanımının son_odeme_gunu ile aidat döneminin ayından bulunur (ay
+// kısaysa ayın son günü; tanım yoksa ayın son günü). Ödemesiz gün dolmadıkça faiz
+// işlemez; dolduktan sonra gün sayısı vadeden itibaren sayılır. Anapara aidatın
+// hesap anındaki kalan borcudur. Basit faiz: anapara × oran × dönem; bileşik faiz:
+// anapara × ((1 + oran)^dönem − 1); dönem aylık oranda gün / 30, günlük oranda gündür.
+// Tavan, faizi anaparanın yüzdesiyle sınırlar.
+//
+// Tahakkuk artımlıdır: aynı aidat için daha önce yazılmış faizler (feragat edilenler
+// dahil) hesaplanan toplamdan düşülür, yalnızca fark yeni alacak olarak yazılır.
+// Böylece aynı tarihle iki kez çalıştırmak ikinci kez faiz yazmaz. Alacak aidattan
+// ayrıdır (gecikme_faizleri) ve yevmiyede kendi fişiyle izlenir.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::outbox::TxError;
+use crate::db::para::Para;
+use crate::db::{donem_kilidi, yevmiye};
+
+pub const HESAPLAMALAR: [&str; 2] = ["basit", "bilesik"];
+pub const ORAN_BIRIMLERI: [&str; 2] = ["aylik", "gunluk"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct GecikmePolitikasi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad: String,
+    /// Boşsa diğer üye türleri için varsayılan politika
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_turu: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub odemesiz_gun: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hesaplama: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub oran_birimi: String,
+    /// Yüzde; boşsa aidat tanımının gecikme_faiz_orani
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub oran: Option<f64>,
+    /// Faiz en fazla anaparanın bu yüzdesi kadar olabilir
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub tavan_yuzde: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub asgari_tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub feragat_yonetici: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+}
+
+pub fn politika_dogrula(p: &GecikmePolitikasi) -> Result<(), String> {
+    if p.ad.trim().is_empty() {
+        return Err("Politika adı boş olamaz".to_string());
+    }
+    if !HESAPLAMALAR.contains(&p.hesaplama.as_str()) {
+        return Err(format!("Geçersiz hesaplama: {} (basit veya bilesik)", p.hesaplama));
+    }
+    if !ORAN_BIRIMLERI.contains(&p.oran_birimi.as_str()) {
+        return Err(format!("Geçersiz oran birimi: {} (aylik veya gunluk)", p.oran_birimi));
+    }
+    if p.odemesiz_gun < 0 {
+        return Err("Ödemesiz gün negatif olamaz".to_string());
+    }
+    if p.oran.is_some_and(|o| !o.is_finite() || o < 0.0) {
+        return Err("Faiz oranı negatif olamaz".to_string());
+    }
+    if p.tavan_yuzde.is_some_and(|t| !t.is_finite() || t < 0.0) {
+        return Err("Tavan yüzdesi negatif olamaz".to_string());
+    }
+    if p.asgari_tutar.is_negative() {
+        return Err("Asgari tutar negatif olamaz".to_string());
+    }
+    Ok(())
+}
+
+/// Üye türüne özel politika, yoksa varsayılan (uye_turu boş) politika
+pub fn politika_sec<'a>(politikalar: &'a [GecikmePolitikasi], uye_turu: &str) -> Option<&'a GecikmePolitikasi> {
+    politikalar
+        .iter()
+        .find(|p| p.uye_turu.as_deref().is_some_and(|t| t.eq_ignore_ascii_case(uye_turu)))
+        .or_else(|| politikalar.iter().find(|p| p.uye_turu.is_none()))
+}
+
+/// Dönem ayının son_odeme_gunu'nü (ay kısaysa ayın son günü)
+pub fn vade_tarihi(yil: i32, ay: i32, son_odeme_gunu: Option<i32>) -> Option<NaiveDate> {
+    let ay_basi = NaiveDate::from_ymd_opt(yil, ay.clamp(1, 12) as u32, 1)?;
+    let ay_sonu = ay_basi.checked_add_months(chrono::Months::new(1))?.pred_opt()?;
+    let gun = son_odeme_gunu.unwrap_or(31).clamp(1, ay_sonu.day() as i32) as u32;
+    ay_basi.with_day(gun)
+}
+
+/// Faiz işleyen gün sayısı: ödemesiz süre içindeyse 0, sonrasında vadeden itibaren
+pub fn gecikme_gunu(vade: NaiveDate, tarih: NaiveDate, odemesiz_gun: i32) -> i64 {
+    let gun = (tarih - vade).num_days();
+    if gun <= odemesiz_gun as i64 {
+        0
+    } else {
+        gun
+    }
+}
+
+/// Toplam faiz (tavan uygulanmış)
+pub fn faiz_hesapla(p: &GecikmePolitikasi, oran_yuzde: f64, anapara: Para, gun: i64) -> Para {
+    if gun <= 0 || oran_yuzde <= 0.0 || !anapara.is_positive() {
+        return Para::SIFIR;
+    }
+    let donem = if p.oran_birimi == "gunluk" { gun as f64 } else { gun as f64 / 30.0 };
+    let oran = oran_yuzde / 100.0;
+    let katsayi = if p.hesaplama == "bilesik" {
+        (1.0 + oran).powf(donem) - 1.0
+    } else {
+        oran * donem
+    };
+    let faiz = anapara.carp(katsayi);
+    match p.tavan_yuzde {
+        Some(tavan) => faiz.min(anapara.yuzde(tavan)),
+        None => faiz,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GecikmeHesabi {
+    pub aidat_id: String,
+    pub uye_id: String,
+    pub uye_no: String,
+    pub ad_soyad: String,
+    pub yil: i32,
+    pub ay: i32,
+    pub vade_tarihi: String,
+    pub gecikme_gun: i64,
+    pub anapara: Para,
+    pub oran: f64,
+    pub politika_id: String,
+    pub politika_adi: String,
+    /// Hesap tarihine kadar işlemiş toplam faiz
+    pub hesaplanan: Para,
+    /// Bu aidat için daha önce tahakkuk etmiş faiz (feragatler dahil)
+    pub onceki_tahakkuk: Para,
+    /// Yazılacak yeni alacak; asgari tutarın altındaysa 0
+    pub tahakkuk_edilecek: Para,
+    pub atlanma_nedeni: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GecikmeOnizleme {
+    pub tarih: String,
+    pub satirlar: Vec<GecikmeHesabi>,
+    pub toplam_tahakkuk: Para,
+    pub politika_yok: bool,
+}
+
+pub fn politikalari_getir(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<GecikmePolitikasi>, String> {
+    diesel::sql_query(
+        "SELECT id, ad, uye_turu, odemesiz_gun, hesaplama, oran_birimi, oran, tavan_yuzde, asgari_tutar,
+                feragat_yonetici, aciklama
+         FROM gecikme_politikalari
+         WHERE tenant_id = ?1 AND is_active = 1
+         ORDER BY uye_turu IS NULL, uye_turu, ad",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Tarih itibarıyla vadesi geçmiş tüm açık aidatların gecikme faizi
+pub fn onizle(conn: &mut SqliteConnection, tenant_id: &str, tarih: NaiveDate) -> Result<GecikmeOnizleme, String> {
+    #[derive(QueryableByName)]
+    struct GecikenRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        aidat_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_no: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_turu: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        kalan: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+        son_odeme_gunu: Option<i32>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+        tanim_orani: Option<f64>,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        onceki: Para,
+    }
+
+    let politikalar = politikalari_getir(conn, tenant_id)?;
+    let mut onizleme = GecikmeOnizleme {
+        tarih: tarih.format("%Y-%m-%d").to_string(),
+        satirlar: Vec::new(),
+        toplam_tahakkuk: Para::SIFIR,
+        politika_yok: politikalar.is_empty(),
+    };
+    if politikalar.is_empty() {
+        return Ok(onizleme);
+    }
+
+    let satirlar: Vec<GecikenRow> = diesel::sql_query(
+        "SELECT a.id AS aidat_id, a.uye_id, u.uye_no, u.ad_soyad, COALESCE(u.uyelik_tipi, 'Asil') AS uye_turu,
+                a.yil, a.ay, a.tutar - COALESCE(a.odenen, 0) AS kalan,
+                t.son_odeme_gunu, t.gecikme_faiz_orani AS tanim_orani,
+                (SELECT COALESCE(SUM(g.tutar), 0) FROM gecikme_faizleri g
+                  WHERE g.tenant_id = a.tenant_id AND g.aidat_id = a.id) AS onceki
+         FROM aidat_takip a
+         JOIN uyeler u ON u.id = a.uye_id AND u.tenant_id = a.tenant_id
+         LEFT JOIN aidat_tanimlari t ON t.id = (
+              SELECT t2.id FROM aidat_tanimlari t2
+              WHERE t2.tenant_id = a.tenant_id AND t2.yil = a.yil AND t2.is_active = 1
+                AND COALESCE(t2.uye_turu, 'Asil') = COALESCE(u.uyelik_tipi, 'Asil')
+              ORDER BY t2.updated_at DESC LIMIT 1)
+         WHERE a.tenant_id = ?1
+           AND (a.is_deleted IS NULL OR a.is_deleted = 0)
+           AND a.durum != 'iptal'
+           AND a.tutar - COALESCE(a.odenen, 0) > 0.004
+         ORDER BY u.uye_no, a.yil, a.ay",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    for s in satirlar {
+        let Some(politika) = politika_sec(&politikalar, &s.uye_turu) else { continue };
+        let Some(vade) = vade_tarihi(s.yil, s.ay, s.son_odeme_gunu) else { continue };
+        let gun = gecikme_gunu(vade, tarih, politika.odemesiz_gun);
+        if gun == 0 {
+            continue;
+        }
+        let oran = politika.oran.or(s.tanim_orani).unwrap_or(0.0);
+        let hesaplanan = faiz_hesapla(politika, oran, s.kalan, gun);
+        if hesaplanan.is_zero() {
+            continue;
+        }
+
+        let fark = (hesaplanan - s.onceki).sifirdan_kucukse_sifir();
+        let atlanma_nedeni = if fark.is_zero() {
+            Some("Faiz daha önce tahakkuk etti".to_string())
+        } else if fark < politika.asgari_tutar {
+            Some(format!("Asgari tutarın ({} ₺) altında", politika.asgari_tutar.tr_format()))
+        } else {
+            None
+        };
+        let tahakkuk_edilecek = if atlanma_nedeni.is_some() { Para::SIFIR } else { fark };
+        onizleme.toplam_tahakkuk += tahakkuk_edilecek;
+
+        onizleme.satirlar.push(GecikmeHesabi {
+            aidat_id: s.aidat_id,
+            uye_id: s.uye_id,
+            uye_no: s.uye_no,
+            ad_soyad: s.ad_soyad,
+            yil: s.yil,
+            ay: s.ay,
+            vade_tarihi: vade.format("%Y-%m-%d").to_string(),
+            gecikme_gun: gun,
+            anapara: s.kalan,
+            oran,
+            politika_id: politika.id.clone(),
+            politika_adi: politika.ad.clone(),
+            hesaplanan,
+            onceki_tahakkuk: s.onceki,
+            tahakkuk_edilecek,
+            atlanma_nedeni,
+        });
+    }
+    Ok(onizleme)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GecikmeTahakkukSonucu {
+    pub tarih: String,
+    pub olusturulan: i32,
+    pub toplam_tutar: Para,
+}
+
+/// Önizlemedeki tahakkukları gecikme_faizleri'ne yazar (her satır kendi transaction'ında)
+pub fn tahakkuk_et(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    tarih: NaiveDate,
+    denetim: &mut Denetim,
+) -> Result<GecikmeTahakkukSonucu, String> {
+    let tarih_str = tarih.format("%Y-%m-%d").to_string();
+    donem_kilidi::tarihi_dogrula(conn, tenant_id, &tarih_str)?;
+
+    let onizleme = onizle(conn, tenant_id, tarih)?;
+    let mut sonuc = GecikmeTahakkukSonucu { tarih: tarih_str.clone(), olusturulan: 0, toplam_tutar: Para::SIFIR };
+
+    for h in onizleme.satirlar.iter().filter(|h| h.tahakkuk_edilecek.is_positive()) {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let aciklama = format!(
+            "{} aidatı · {} gün gecikme ({})",
+            aidat_donemi(h.yil, h.ay),
+            h.gecikme_gun,
+            h.politika_adi
+        );
+
+        conn.transaction::<_, TxError, _>(|conn| {
+            denetim.yeni_kayit(tenant_id, "gecikme_faizleri", &id);
+            diesel::sql_query(
+                "INSERT INTO gecikme_faizleri
+                    (id, tenant_id, aidat_id, uye_id, politika_id, hesap_tarihi, vade_tarihi, gecikme_gun,
+                     anapara, tutar, durum, aciklama, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'acik', ?11, ?12, ?12)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(&h.aidat_id)
+            .bind::<diesel::sql_types::Text, _>(&h.uye_id)
+            .bind::<diesel::sql_types::Text, _>(&h.politika_id)
+            .bind::<diesel::sql_types::Text, _>(&tarih_str)
+            .bind::<diesel::sql_types::Text, _>(&h.vade_tarihi)
+            .bind::<diesel::sql_types::BigInt, _>(h.gecikme_gun)
+            .bind::<diesel::sql_types::Double, _>(h.anapara)
+            .bind::<diesel::sql_types::Double, _>(h.tahakkuk_edilecek)
+            .bind::<diesel::sql_types::Text, _>(&aciklama)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)?;
+
+            yevmiye::kaydi_isle(conn, tenant_id, "gecikme_faizleri", &id).map_err(TxError::Msg)?;
+            denetim.kaydet(conn).map_err(TxError::Msg)?;
+            Ok(())
+        })
+        .map_err(|e| format!("Gecikme faizi yazılamadı ({}): {}", h.uye_no, e))?;
+
+        sonuc.olusturulan += 1;
+        sonuc.toplam_tutar += h.tahakkuk_edilecek;
+    }
+    Ok(sonuc)
+}
+
+fn aidat_donemi(yil: i32, ay: i32) -> String {
+    if ay <= 1 {
+        yil.to_string()
+    } else {
+        format!("{}/{:02}", yil, ay)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct GecikmeFaizi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub aidat_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub politika_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hesap_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub vade_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub gecikme_gun: i32,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub anapara: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub feragat_tutari: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub odenen: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub kalan: Para,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gelir_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub feragat_nedeni: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub feragat_eden: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub feragat_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+}
+
+const GECIKME_FAIZI_SELECT: &str = "
+    SELECT g.id, g.aidat_id, g.uye_id, u.uye_no, u.ad_soyad, g.politika_id, g.hesap_tarihi, g.vade_tarihi,
+           g.gecikme_gun, g.anapara, g.tutar, g.feragat_tutari, g.odenen,
+           g.tutar - g.feragat_tutari - g.odenen AS kalan, g.durum, g.gelir_id,
+           g.feragat_nedeni, g.feragat_eden, g.feragat_tarihi, g.aciklama
+    FROM gecikme_faizleri g
+    LEFT JOIN uyeler u ON u.id = g.uye_id AND u.tenant_id = g.tenant_id";
+
+pub fn faizleri_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: Option<&str>,
+    sadece_acik: bool,
+) -> Result<Vec<GecikmeFaizi>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE g.tenant_id = ?1 AND (?2 IS NULL OR g.uye_id = ?2) AND (?3 = 0 OR g.durum = 'acik')
+         ORDER BY g.hesap_tarihi DESC, u.uye_no",
+        GECIKME_FAIZI_SELECT
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .bind::<diesel::sql_types::Bool, _>(sadece_acik)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn faiz_getir(conn: &mut SqliteConnection, tenant_id: &str, id: &str) -> Result<GecikmeFaizi, String> {
+    diesel::sql_query(format!("{} WHERE g.id = ?1 AND g.tenant_id = ?2", GECIKME_FAIZI_SELECT))
+        .bind::<diesel::sql_types::Text, _>(id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Gecikme faizi kaydı bulunamadı".to_string())
+}
+
+/// Feragat edilecek tutarı doğrular; tutar verilmezse kalan borcun tamamı
+pub fn feragat_tutari(faiz: &GecikmeFaizi, tutar: Option<Para>, neden: &str) -> Result<Para, String> {
+    if faiz.durum != "acik" {
+        return Err("Yalnızca açık gecikme faizinden feragat edilebilir".to_string());
+    }
+    if neden.trim().is_empty() {
+        return Err("Feragat nedeni yazılmalıdır".to_string());
+    }
+    let tutar = tutar.unwrap_or(faiz.kalan);
+    if !tutar.is_positive() {
+        return Err("Feragat tutarı sıfırdan büyük olmalıdır".to_string());
+    }
+    if tutar > faiz.kalan {
+        return Err(format!("Feragat tutarı kalan faizi ({} ₺) aşamaz", faiz.kalan.tr_format()));
+    }
+    Ok(tutar)
+}
+
+/// Faizin bir kısmından veya tamamından vazgeçer; yevmiye fişi net tutarla yenilenir
+pub fn feragat_et(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    faiz: &GecikmeFaizi,
+    tutar: Para,
+    neden: &str,
+    feragat_eden: Option<&str>,
+    denetim: &mut Denetim,
+) -> Result<(), String> {
+    donem_kilidi::tarihi_dogrula(conn, tenant_id, &faiz.hesap_tarihi)?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    // Kısmi feragatler sırayla birikir; her biri tarih ve tutarıyla not edilir
+    let not = format!("{} · {} ₺ · {}", &now[..10], tutar.tr_format(), neden.trim());
+    let nedenler = match &faiz.feragat_nedeni {
+        Some(onceki) => format!("{}\n{}", onceki, not),
+        None => not,
+    };
+    let tamami = tutar == faiz.kalan;
+
+    conn.transaction::<_, TxError, _>(|conn| {
+        denetim.izle(conn, tenant_id, "gecikme_faizleri", &faiz.id).map_err(TxError::Msg)?;
+        diesel::sql_query(
+            "UPDATE gecikme_faizleri
+             SET feragat_tutari = feragat_tutari + ?1, feragat_nedeni = ?2, feragat_eden = ?3,
+                 feragat_tarihi = ?4, durum = CASE WHEN ?5 THEN 'feragat' ELSE durum END, updated_at = ?4
+             WHERE id = ?6 AND tenant_id = ?7 AND durum = 'acik'",
+        )
+        .bind::<diesel::sql_types::Double, _>(tutar)
+        .bind::<diesel::sql_types::Text, _>(&nedenler)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(feragat_eden)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Bool, _>(tamami)
+        .bind::<diesel::sql_types::Text, _>(&faiz.id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)?;
+
+        yevmiye::kaydi_isle(conn, tenant_id, "gecikme_faizleri", &faiz.id).map_err(TxError::Msg)?;
+        denetim.kaydet(conn).map_err(TxError::Msg)?;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Silinen tahsilat gelirini faizden geri alır; faiz yeniden açık alacak olur.
+/// Gelir silme transaction'ında, silinen gelirin tutarıyla çağrılır.
+pub fn gelir_silindi(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    gelir_id: &str,
+    tutar: Para,
+) -> Result<(), String> {
+    let faizler: Vec<GecikmeFaizi> = diesel::sql_query(format!("{} WHERE g.tenant_id = ?1 AND g.gelir_id = ?2", GECIKME_FAIZI_SELECT))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(gelir_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for faiz in faizler {
+        denetim.izle(conn, tenant_id, "gecikme_faizleri", &faiz.id)?;
+        diesel::sql_query(
+            "UPDATE gecikme_faizleri SET odenen = ?1, gelir_id = NULL, durum = 'acik', updated_at = ?2
+             WHERE id = ?3 AND tenant_id = ?4",
+        )
+        .bind::<diesel::sql_types::Double, _>((faiz.odenen - tutar).sifirdan_kucukse_sifir())
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&faiz.id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn politika(hesaplama: &str, oran_birimi: &str) -> GecikmePolitikasi {
+        GecikmePolitikasi {
+            id: "p1".to_string(),
+            ad: "Varsayılan".to_string(),
+            uye_turu: None,
+            odemesiz_gun: 15,
+            hesaplama: hesaplama.to_string(),
+            oran_birimi: oran_birimi.to_string(),
+            oran: Some(2.0),
+            tavan_yuzde: None,
+            asgari_tutar: Para::SIFIR,
+            feragat_yonetici: true,
+            aciklama: None,
+        }
+    }
+
+    fn tarih(y: i32, a: u32, g: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, a, g).unwrap()
+    }
+
+    #[test]
+    fn vade_ve_odemesiz_sure() {
+        assert_eq!(vade_tarihi(2026, 2, Some(31)), Some(tarih(2026, 2, 28)));
+        assert_eq!(vade_tarihi(2026, 1, Some(15)), Some(tarih(2026, 1, 15)));
+        assert_eq!(vade_tarihi(2026, 4, None), Some(tarih(2026, 4, 30)));
+
+        let vade = tarih(2026, 1, 31);
+        assert_eq!(gecikme_gunu(vade, tarih(2026, 2, 10), 15), 0);
+        assert_eq!(gecikme_gunu(vade, tarih(2026, 2, 15), 15), 0);
+        // Ödemesiz süre dolunca gün vadeden sayılır
+        assert_eq!(gecikme_gunu(vade, tarih(2026, 2, 16), 15), 16);
+        assert_eq!(gecikme_gunu(vade, tarih(2026, 1, 1), 0), 0);
+    }
+
+    #[test]
+    fn basit_bilesik_ve_tavan() {
+        let anapara = Para::from_tl(1000.0);
+
+        // Aylık %2, 90 gün: basit 60, bileşik 1000 × (1,02³ − 1) = 61,21
+        assert_eq!(faiz_hesapla(&politika("basit", "aylik"), 2.0, anapara, 90), Para::from_tl(60.0));
+        assert_eq!(faiz_hesapla(&politika("bilesik", "aylik"), 2.0, anapara, 90), Para::from_tl(61.21));
+
+        // Günlük %0,1, 10 gün
+        assert_eq!(faiz_hesapla(&politika("basit", "gunluk"), 0.1, anapara, 10), Para::from_tl(10.0));
+
+        let mut tavanli = politika("basit", "aylik");
+        tavanli.tavan_yuzde = Some(5.0);
+        assert_eq!(faiz_hesapla(&tavanli, 2.0, anapara, 365), Para::from_tl(50.0));
+
+        assert_eq!(faiz_hesapla(&politika("basit", "aylik"), 0.0, anapara, 90), Para::SIFIR);
+    }
+
+    #[test]
+    fn politika_secimi_ve_feragat() {
+        let mut ogrenci = politika("basit", "aylik");
+        ogrenci.id = "p2".to_string();
+        ogrenci.uye_turu = Some("Öğrenci".to_string());
+        let politikalar = vec![politika("basit", "aylik"), ogrenci];
+        assert_eq!(politika_sec(&politikalar, "Öğrenci").unwrap().id, "p2");
+        assert_eq!(politika_sec(&politikalar, "Asil").unwrap().id, "p1");
+        assert!(politika_sec(&politikalar[1..], "Asil").is_none());
+
+        let faiz = GecikmeFaizi {
+            id: "g1".to_string(),
+            aidat_id: "a1".to_string(),
+            uye_id: "u1".to_string(),
+            uye_no: None,
+            ad_soyad: None,
+            politika_id: Some("p1".to_string()),
+            hesap_tarihi: "2026-03-01".to_string(),
+            vade_tarihi: "2026-01-31".to_string(),
+            gecikme_gun: 29,
+            anapara: Para::from_tl(1000.0),
+            tutar: Para::from_tl(19.33),
+            feragat_tutari: Para::from_tl(5.0),
+            odenen: Para::SIFIR,
+            kalan: Para::from_tl(14.33),
+            durum: "acik".to_string(),
+            gelir_id: None,
+            feragat_nedeni: None,
+            feragat_eden: None,
+            feragat_tarihi: None,
+            aciklama: None,
+        };
+        assert_eq!(feragat_tutari(&faiz, None, "Yönetim kurulu kararı"), Ok(Para::from_tl(14.33)));
+        assert!(feragat_tutari(&faiz, None, "  ").is_err());
+        assert!(feragat_tutari(&faiz, Some(Para::from_tl(20.0)), "Karar").is_err());
+        let odenmis = GecikmeFaizi { durum: "odendi".to_string(), ..faiz };
+        assert!(feragat_tutari(&odenmis, Some(Para::from_tl(1.0)), "Karar").is_err());
+    }
+
+    #[test]
+    fn tahsilat_geliri_silinince_faiz_yeniden_acilir() {
+        use crate::db::test_db;
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO gecikme_faizleri (id, tenant_id, aidat_id, uye_id, hesap_tarihi, vade_tarihi, gecikme_gun,
+                    anapara, tutar, feragat_tutari, odenen, durum, gelir_id, created_at, updated_at)
+             VALUES ('f1', 't1', 'a1', 'u1', '2026-03-01', '2026-01-31', 29, 1000.0, 19.33, 5.0, 14.33, 'odendi', 'g1', '', '')",
+        );
+        let mut d = Denetim::yeni(None, None, "test");
+        gelir_silindi(&mut conn, &mut d, "t1", "g1", Para::from_tl(14.33)).unwrap();
+
+        let faiz = faiz_getir(&mut conn, "t1", "f1").unwrap();
+        assert_eq!((faiz.durum.as_str(), faiz.gelir_id, faiz.odenen), ("acik", None, Para::SIFIR));
+        assert_eq!(faiz.kalan, Para::from_tl(14.33));
+    }
+}
@@ -0,0 +1,1316 @@
+// Genel kurul: hazirun listesi, yeter sayı, gündem oylamaları ve organ seçimleri.
+//
+// Genel kurul bir toplantilar kaydına bağlanır; ilk toplantı tarihi toplantının tarihidir.
+// Hazirun, hazirun_tarihi itibarıyla asil üyelerden (bkz. uyelik_durumu::donemde_asil_uyeler)
+// çıkarılır. Tüzük borç engeli açıksa vadesi hazirun tarihinden (tolerans günü düşülerek) önce
+// dolmuş aidat borcu olan üye listede kalır ama katılım hakkı olmaz ve üye tamsayısına girmez.
+// Yeter sayı (TMK 86): ilk çağrıda üye tamsayısının salt çoğunluğu, tüzük değişikliği ya da
+// fesih gündemi varsa üçte ikisi; ilk çağrıda sağlanamazsa ikinci çağrıda çoğunluk aranmaz
+// ama katılan sayısı yönetim ve denetim kurulları üye tamsayısının iki katından az olamaz.
+// Kararlar katılanların salt çoğunluğuyla, nitelikli gündemde üçte iki çoğunlukla alınır.
+
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::ihtar;
+use crate::db::para::Para;
+use crate::db::uyelik_durumu;
+
+/// Genel kurulun seçtiği organlar; yasal asgari asil üye sayısıyla
+pub const ORGANLAR: [(&str, i32); 3] = [("yonetim_kurulu", 5), ("denetim_kurulu", 3), ("disiplin_kurulu", 1)];
+/// İlk ve ikinci toplantı arasındaki yasal süre (gün)
+pub const IKINCI_TOPLANTI_EN_AZ_GUN: i64 = 7;
+pub const IKINCI_TOPLANTI_EN_COK_GUN: i64 = 60;
+/// Yönetim (5) ve denetim (3) kurullarının yasal asgari asil üye tamsayısı
+pub const ASGARI_YK_DK_UYE_SAYISI: i32 = 8;
+
+pub fn organ_adi(organ: &str) -> &str {
+    match organ {
+        "yonetim_kurulu" => "Yönetim Kurulu",
+        "denetim_kurulu" => "Denetim Kurulu",
+        "disiplin_kurulu" => "Disiplin Kurulu",
+        "genel_kurul" => "Genel Kurul",
+        diger => diger,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct GenelKurul {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub toplanti_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslik: String,
+    /// İlk toplantı tarihi (toplantilar.tarih)
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub saat: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub yer: Option<String>,
+    /// "olagan" | "olaganustu"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tur: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hazirun_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ikinci_toplanti_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yk_dk_uye_sayisi: i32,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub borc_engeli: bool,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub borc_tolerans_gun: i32,
+    /// "hazirlik" | "ertelendi" (ilk çağrıda yeter sayı yok) | "acildi" | "tamamlandi"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub toplanti_cagrisi: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub acilis_zamani: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub acilista_katilan: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub divan_baskani: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub divan_katipleri: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenelKurulGirdi {
+    pub toplanti_id: String,
+    pub tur: Option<String>,
+    /// YYYY-MM-DD; üyelik ve borç durumu bu tarihe göre
+    pub hazirun_tarihi: String,
+    /// İlk çağrıda yeter sayı sağlanamazsa yapılacak toplantı (YYYY-MM-DD)
+    pub ikinci_toplanti_tarihi: Option<String>,
+    pub yk_dk_uye_sayisi: i32,
+    pub borc_engeli: Option<bool>,
+    pub borc_tolerans_gun: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct HazirunSatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub sira: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub tc_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub katilim_hakki: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub engel_nedeni: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub borc_tutari: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub imza_zamani: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Yetersayi {
+    /// 1: ilk çağrı, 2: ikinci çağrı
+    pub cagri: i32,
+    /// Katılım hakkı olan üye sayısı
+    pub uye_tamsayisi: i64,
+    /// Hazirunu imzalayan
+    pub katilan: i64,
+    pub gerekli: i64,
+    /// Tüzük değişikliği ya da fesih gündemi
+    pub nitelikli_gundem: bool,
+    pub saglandi: bool,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct GundemMaddesi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub sira: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslik: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    /// Tüzük değişikliği/fesih: üçte iki çoğunluk
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub nitelikli: bool,
+    /// Oylama anında toplantıda bulunan
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub katilan: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub kabul: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub ret: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub cekimser: Option<i32>,
+    /// Oylanmadıysa None; "kabul" | "ret"
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub sonuc: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karar_metni: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GundemGirdi {
+    /// Mevcut madde; yoksa yeni madde eklenir
+    pub id: Option<String>,
+    pub baslik: String,
+    pub aciklama: Option<String>,
+    pub nitelikli: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OylamaGirdi {
+    pub kabul: i32,
+    pub ret: i32,
+    pub cekimser: i32,
+    pub karar_metni: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct Secim {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub organ: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub asil_sayisi: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yedek_sayisi: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub kullanilan_oy: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub gecersiz_oy: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub sonuclandi: bool,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct SecimAdayi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub oy: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub kura_sirasi: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub sira: Option<i32>,
+    /// "asil" | "yedek" | "kazanamadi"
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub sonuc: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecimGirdi {
+    pub organ: String,
+    pub asil_sayisi: i32,
+    pub yedek_sayisi: i32,
+    /// Aday üye id'leri
+    pub adaylar: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdayOyu {
+    pub uye_id: String,
+    pub oy: i32,
+    /// Sınırda oy eşitliğinde kura sırası (küçük olan önce)
+    pub kura_sirasi: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecimSonucuGirdi {
+    pub kullanilan_oy: i32,
+    pub gecersiz_oy: i32,
+    pub oylar: Vec<AdayOyu>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecimDetay {
+    #[serde(flatten)]
+    pub secim: Secim,
+    pub adaylar: Vec<SecimAdayi>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GenelKurulDetay {
+    pub genel_kurul: GenelKurul,
+    pub yetersayi: Yetersayi,
+    pub gundem: Vec<GundemMaddesi>,
+    pub secimler: Vec<SecimDetay>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AcilisGirdi {
+    /// YYYY-MM-DD HH:MM
+    pub zaman: String,
+    pub divan_baskani: Option<String>,
+    pub divan_katipleri: Option<String>,
+}
+
+#[derive(QueryableByName)]
+struct SayiRow {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    sayi: i64,
+}
+
+fn tarih_oku(tarih: &str, alan: &str) -> Result<NaiveDate, String> {
+    tarih
+        .trim()
+        .get(..10)
+        .and_then(|t| NaiveDate::parse_from_str(t, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("Geçersiz {}: {}", alan, tarih))
+}
+
+fn bos_degilse(s: &Option<String>) -> Option<&str> {
+    s.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Toplantıyı açmak için gereken katılan sayısı
+pub fn gerekli_yetersayi(cagri: i32, uye_tamsayisi: i64, yk_dk_uye_sayisi: i32, nitelikli: bool) -> i64 {
+    match cagri {
+        1 if nitelikli => (2 * uye_tamsayisi + 2) / 3,
+        1 => uye_tamsayisi / 2 + 1,
+        _ => 2 * yk_dk_uye_sayisi as i64,
+    }
+}
+
+/// Gündem maddesi kararı: katılanların salt çoğunluğu, nitelikli maddede üçte ikisi
+pub fn karar_sonucu(katilan: i64, kabul: i64, nitelikli: bool) -> &'static str {
+    let kabul_edildi = if nitelikli { 3 * kabul >= 2 * katilan } else { 2 * kabul > katilan };
+    if kabul_edildi && kabul > 0 {
+        "kabul"
+    } else {
+        "ret"
+    }
+}
+
+/// Adayların (oy, kura sırası) listesinden kazanma sırası; sınırlarda (asil/yedek ve
+/// yedek/kazanamadı) kura ile çözülmemiş oy eşitliği varsa eşit iki adayın indisi döner
+pub fn secim_sirasi(oylar: &[(i32, Option<i32>)], sinirlar: &[usize]) -> Result<Vec<usize>, (usize, usize)> {
+    let mut sira: Vec<usize> = (0..oylar.len()).collect();
+    sira.sort_by(|&a, &b| {
+        oylar[b].0.cmp(&oylar[a].0).then_with(|| match (oylar[a].1, oylar[b].1) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        })
+    });
+    for &sinir in sinirlar {
+        if sinir == 0 || sinir >= sira.len() {
+            continue;
+        }
+        let (once, sonra) = (oylar[sira[sinir - 1]], oylar[sira[sinir]]);
+        let kura_cozdu = matches!((once.1, sonra.1), (Some(x), Some(y)) if x != y);
+        if once.0 == sonra.0 && !kura_cozdu {
+            return Err((sira[sinir - 1], sira[sinir]));
+        }
+    }
+    Ok(sira)
+}
+
+const GENEL_KURUL_SECIMI: &str = "SELECT g.id, g.toplanti_id, t.baslik, substr(t.tarih, 1, 10) AS tarih, t.saat, t.yer,
+        g.tur, g.hazirun_tarihi, g.ikinci_toplanti_tarihi, g.yk_dk_uye_sayisi, g.borc_engeli, g.borc_tolerans_gun,
+        g.durum, g.toplanti_cagrisi, g.acilis_zamani, g.acilista_katilan, g.divan_baskani, g.divan_katipleri,
+        g.created_at, g.updated_at
+     FROM genel_kurullar g
+     JOIN toplantilar t ON t.id = g.toplanti_id AND t.tenant_id = g.tenant_id";
+
+pub fn genel_kurullari_getir(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<GenelKurul>, String> {
+    diesel::sql_query(format!("{} WHERE g.tenant_id = ?1 ORDER BY t.tarih DESC", GENEL_KURUL_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .load(conn)
+        .map_err(|e| e.to_string())
+}
+
+pub fn genel_kurul_getir(conn: &mut SqliteConnection, tenant_id: &str, genel_kurul_id: &str) -> Result<GenelKurul, String> {
+    diesel::sql_query(format!("{} WHERE g.tenant_id = ?1 AND g.id = ?2", GENEL_KURUL_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Genel kurul bulunamadı".to_string())
+}
+
+pub fn genel_kurul_detay(conn: &mut SqliteConnection, tenant_id: &str, genel_kurul_id: &str) -> Result<GenelKurulDetay, String> {
+    let genel_kurul = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    let yetersayi = yetersayi_hesapla(conn, tenant_id, &genel_kurul)?;
+    let gundem = gundem_getir(conn, tenant_id, genel_kurul_id)?;
+    let secimler: Vec<Secim> = diesel::sql_query(
+        "SELECT id, organ, asil_sayisi, yedek_sayisi, kullanilan_oy, gecersiz_oy, sonuclandi
+         FROM genel_kurul_secimleri WHERE tenant_id = ?1 AND genel_kurul_id = ?2 ORDER BY created_at, organ",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    let mut detaylar = Vec::new();
+    for secim in secimler {
+        let adaylar = adaylari_getir(conn, tenant_id, &secim.id)?;
+        detaylar.push(SecimDetay { secim, adaylar });
+    }
+    Ok(GenelKurulDetay { genel_kurul, yetersayi, gundem, secimler: detaylar })
+}
+
+/// Genel kurul açar (genel_kurul_id yoksa) ya da hazırlıktaki genel kurulu günceller.
+/// Bağlı toplantının türü "Genel Kurul" yapılır.
+pub fn genel_kurul_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: Option<&str>,
+    girdi: &GenelKurulGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct ToplantiRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        toplanti_tipi: Option<String>,
+    }
+
+    let tur = bos_degilse(&girdi.tur).unwrap_or("olagan");
+    if !["olagan", "olaganustu"].contains(&tur) {
+        return Err(format!("Geçersiz genel kurul türü: {}", tur));
+    }
+    if girdi.yk_dk_uye_sayisi < ASGARI_YK_DK_UYE_SAYISI {
+        return Err(format!(
+            "Yönetim ve denetim kurulları asil üye tamsayısı en az {} olmalıdır",
+            ASGARI_YK_DK_UYE_SAYISI
+        ));
+    }
+    let tolerans = girdi.borc_tolerans_gun.unwrap_or(0);
+    if tolerans < 0 {
+        return Err("Borç tolerans süresi negatif olamaz".to_string());
+    }
+
+    let toplanti: ToplantiRow = diesel::sql_query(
+        "SELECT tarih, toplanti_tipi FROM toplantilar
+         WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&girdi.toplanti_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Toplantı bulunamadı")?;
+    let ilk_toplanti = tarih_oku(&toplanti.tarih, "toplantı tarihi")?;
+    let hazirun_tarihi = tarih_oku(&girdi.hazirun_tarihi, "hazirun tarihi")?;
+    if hazirun_tarihi > ilk_toplanti {
+        return Err("Hazirun tarihi toplantı tarihinden sonra olamaz".to_string());
+    }
+    let ikinci = match bos_degilse(&girdi.ikinci_toplanti_tarihi) {
+        Some(t) => {
+            let ikinci = tarih_oku(t, "ikinci toplantı tarihi")?;
+            let ara = (ikinci - ilk_toplanti).num_days();
+            if !(IKINCI_TOPLANTI_EN_AZ_GUN..=IKINCI_TOPLANTI_EN_COK_GUN).contains(&ara) {
+                return Err(format!(
+                    "İkinci toplantı ilk toplantıdan en az {} en çok {} gün sonra olmalıdır",
+                    IKINCI_TOPLANTI_EN_AZ_GUN, IKINCI_TOPLANTI_EN_COK_GUN
+                ));
+            }
+            Some(ikinci.format("%Y-%m-%d").to_string())
+        }
+        None => None,
+    };
+
+    let baska: SayiRow = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM genel_kurullar WHERE tenant_id = ?1 AND toplanti_id = ?2 AND id != ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&girdi.toplanti_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id.unwrap_or(""))
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if baska.sayi > 0 {
+        return Err("Bu toplantıya bağlı bir genel kurul zaten var".to_string());
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let id = match genel_kurul_id {
+        Some(id) => {
+            let mevcut = genel_kurul_getir(conn, tenant_id, id)?;
+            if mevcut.durum != "hazirlik" {
+                return Err("Toplantısı açılmış ya da ertelenmiş genel kurul değiştirilemez".to_string());
+            }
+            denetim.izle(conn, tenant_id, "genel_kurullar", id)?;
+            id.to_string()
+        }
+        None => {
+            let id = Uuid::new_v4().to_string();
+            diesel::sql_query(
+                "INSERT INTO genel_kurullar (id, tenant_id, toplanti_id, hazirun_tarihi, yk_dk_uye_sayisi, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, '', 0, ?4, ?4)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(&girdi.toplanti_id)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            denetim.yeni_kayit(tenant_id, "genel_kurullar", &id);
+            id
+        }
+    };
+
+    diesel::sql_query(
+        "UPDATE genel_kurullar SET toplanti_id = ?1, tur = ?2, hazirun_tarihi = ?3, ikinci_toplanti_tarihi = ?4,
+            yk_dk_uye_sayisi = ?5, borc_engeli = ?6, borc_tolerans_gun = ?7, updated_at = ?8
+         WHERE id = ?9 AND tenant_id = ?10",
+    )
+    .bind::<diesel::sql_types::Text, _>(&girdi.toplanti_id)
+    .bind::<diesel::sql_types::Text, _>(tur)
+    .bind::<diesel::sql_types::Text, _>(hazirun_tarihi.format("%Y-%m-%d").to_string())
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&ikinci)
+    .bind::<diesel::sql_types::Integer, _>(girdi.yk_dk_uye_sayisi)
+    .bind::<diesel::sql_types::Bool, _>(girdi.borc_engeli.unwrap_or(true))
+    .bind::<diesel::sql_types::Integer, _>(tolerans)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+
+    if toplanti.toplanti_tipi.as_deref() != Some("Genel Kurul") {
+        denetim.izle(conn, tenant_id, "toplantilar", &girdi.toplanti_id)?;
+        diesel::sql_query("UPDATE toplantilar SET toplanti_tipi = 'Genel Kurul', updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3")
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&girdi.toplanti_id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(id)
+}
+
+pub fn hazirun_getir(conn: &mut SqliteConnection, tenant_id: &str, genel_kurul_id: &str) -> Result<Vec<HazirunSatiri>, String> {
+    diesel::sql_query(
+        "SELECT uye_id, sira, uye_no, ad_soyad, tc_no, katilim_hakki, engel_nedeni, borc_tutari, imza_zamani
+         FROM genel_kurul_hazirun WHERE tenant_id = ?1 AND genel_kurul_id = ?2 ORDER BY sira",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Hazirun listesini hazirun tarihine göre yeniden çıkarır; imza alınmaya başlandıysa değiştirilemez
+pub fn hazirun_olustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+) -> Result<Vec<HazirunSatiri>, String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        uye_no: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        tc_no: Option<String>,
+    }
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let gk = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    if gk.durum != "hazirlik" {
+        return Err("Hazirun yalnızca hazırlık aşamasında yeniden çıkarılabilir".to_string());
+    }
+    let mevcut = hazirun_getir(conn, tenant_id, genel_kurul_id)?;
+    if mevcut.iter().any(|s| s.imza_zamani.is_some()) {
+        return Err("Hazirun imzalanmaya başlandı, liste yeniden çıkarılamaz".to_string());
+    }
+    let eski: Vec<IdRow> = diesel::sql_query("SELECT id FROM genel_kurul_hazirun WHERE tenant_id = ?1 AND genel_kurul_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    for r in &eski {
+        denetim.izle(conn, tenant_id, "genel_kurul_hazirun", &r.id)?;
+    }
+    diesel::sql_query("DELETE FROM genel_kurul_hazirun WHERE tenant_id = ?1 AND genel_kurul_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+
+    let hazirun_tarihi = tarih_oku(&gk.hazirun_tarihi, "hazirun tarihi")?;
+    let asiller = uyelik_durumu::donemde_asil_uyeler(conn, tenant_id, hazirun_tarihi, hazirun_tarihi)?;
+    let borclar = if gk.borc_engeli {
+        ihtar::vadesi_gecmis_borclar(conn, tenant_id, hazirun_tarihi - Duration::days(gk.borc_tolerans_gun as i64))?
+    } else {
+        Default::default()
+    };
+    let uyeler: Vec<UyeRow> = diesel::sql_query(
+        "SELECT id, uye_no, ad_soyad, tc_no FROM uyeler
+         WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)
+         ORDER BY CAST(uye_no AS INTEGER), uye_no, ad_soyad",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for (sira, uye) in uyeler.into_iter().filter(|u| asiller.contains(&u.id)).enumerate() {
+        let borc = borclar.get(&uye.id).copied().unwrap_or(Para::SIFIR);
+        let engel = (!borc.is_zero()).then(|| format!("Vadesi geçmiş aidat borcu ({})", borc.tr_format()));
+        let id = Uuid::new_v4().to_string();
+        diesel::sql_query(
+            "INSERT INTO genel_kurul_hazirun (id, tenant_id, genel_kurul_id, uye_id, sira, uye_no, ad_soyad, tc_no,
+                katilim_hakki, engel_nedeni, borc_tutari, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+        .bind::<diesel::sql_types::Text, _>(&uye.id)
+        .bind::<diesel::sql_types::Integer, _>(sira as i32 + 1)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&uye.uye_no)
+        .bind::<diesel::sql_types::Text, _>(&uye.ad_soyad)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&uye.tc_no)
+        .bind::<diesel::sql_types::Bool, _>(engel.is_none())
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&engel)
+        .bind::<diesel::sql_types::Double, _>(borc)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        denetim.yeni_kayit(tenant_id, "genel_kurul_hazirun", &id);
+    }
+    hazirun_getir(conn, tenant_id, genel_kurul_id)
+}
+
+/// Hazirun imzası; imzali = false imzayı geri alır. Toplantı sürerken gelen üye de imzalayabilir.
+pub fn imza_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    uye_id: &str,
+    imzali: bool,
+    zaman: &str,
+) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        katilim_hakki: bool,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        engel_nedeni: Option<String>,
+    }
+
+    let gk = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    if gk.durum == "tamamlandi" {
+        return Err("Tamamlanmış genel kurulun hazirunu değiştirilemez".to_string());
+    }
+    let satir: Row = diesel::sql_query(
+        "SELECT id, katilim_hakki, engel_nedeni FROM genel_kurul_hazirun
+         WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND uye_id = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Üye hazirun listesinde değil")?;
+    if imzali && !satir.katilim_hakki {
+        return Err(format!(
+            "Üyenin genel kurula katılım hakkı yok: {}",
+            satir.engel_nedeni.unwrap_or_default()
+        ));
+    }
+    if !imzali && gk.durum == "acildi" {
+        return Err("Toplantı açıldıktan sonra imza geri alınamaz".to_string());
+    }
+
+    denetim.izle(conn, tenant_id, "genel_kurul_hazirun", &satir.id)?;
+    diesel::sql_query("UPDATE genel_kurul_hazirun SET imza_zamani = ?1 WHERE id = ?2")
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(imzali.then_some(zaman))
+        .bind::<diesel::sql_types::Text, _>(&satir.id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn katilan_sayisi(conn: &mut SqliteConnection, tenant_id: &str, genel_kurul_id: &str) -> Result<(i64, i64), String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        tamsayi: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        katilan: i64,
+    }
+
+    let r: Row = diesel::sql_query(
+        "SELECT COUNT(*) AS tamsayi, COUNT(imza_zamani) AS katilan FROM genel_kurul_hazirun
+         WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND katilim_hakki = 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    Ok((r.tamsayi, r.katilan))
+}
+
+/// Mevcut imzalara göre yeter sayı; açılmış toplantıda açıldığı çağrı esas alınır
+pub fn yetersayi_hesapla(conn: &mut SqliteConnection, tenant_id: &str, gk: &GenelKurul) -> Result<Yetersayi, String> {
+    let (uye_tamsayisi, katilan) = katilan_sayisi(conn, tenant_id, &gk.id)?;
+    let nitelikli: SayiRow = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM genel_kurul_gundem WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND nitelikli = 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&gk.id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    let cagri = gk.toplanti_cagrisi.unwrap_or(if gk.durum == "ertelendi" { 2 } else { 1 });
+    let nitelikli_gundem = nitelikli.sayi > 0;
+    let gerekli = gerekli_yetersayi(cagri, uye_tamsayisi, gk.yk_dk_uye_sayisi, nitelikli_gundem);
+    Ok(Yetersayi { cagri, uye_tamsayisi, katilan, gerekli, nitelikli_gundem, saglandi: katilan > 0 && katilan >= gerekli })
+}
+
+/// Toplantıyı açar. İlk çağrıda yeter sayı yoksa genel kurul ertelenir (hata değildir,
+/// dönen yeter sayıda saglandi = false); ikinci çağrıda yeter sayı yoksa hata döner.
+pub fn toplantiyi_ac(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    girdi: &AcilisGirdi,
+) -> Result<Yetersayi, String> {
+    let gk = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    if !["hazirlik", "ertelendi"].contains(&gk.durum.as_str()) {
+        return Err("Genel kurul toplantısı zaten açılmış".to_string());
+    }
+    let acilis = tarih_oku(&girdi.zaman, "açılış zamanı")?;
+    let beklenen = if gk.durum == "ertelendi" { gk.ikinci_toplanti_tarihi.as_deref() } else { Some(gk.tarih.as_str()) };
+    match beklenen {
+        Some(t) if tarih_oku(t, "toplantı tarihi")? == acilis => {}
+        Some(t) => return Err(format!("Toplantı {} tarihinde açılabilir", t)),
+        None => return Err("İkinci toplantı tarihi belirlenmemiş".to_string()),
+    }
+    let yetersayi = yetersayi_hesapla(conn, tenant_id, &gk)?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    denetim.izle(conn, tenant_id, "genel_kurullar", genel_kurul_id)?;
+    if !yetersayi.saglandi {
+        if yetersayi.cagri == 2 {
+            return Err(format!(
+                "İkinci toplantıda yeter sayı sağlanamadı: {} katılan, en az {} gerekli",
+                yetersayi.katilan, yetersayi.gerekli
+            ));
+        }
+        diesel::sql_query("UPDATE genel_kurullar SET durum = 'ertelendi', updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3")
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        return Ok(yetersayi);
+    }
+
+    diesel::sql_query(
+        "UPDATE genel_kurullar SET durum = 'acildi', toplanti_cagrisi = ?1, acilis_zamani = ?2, acilista_katilan = ?3,
+            divan_baskani = ?4, divan_katipleri = ?5, updated_at = ?6
+         WHERE id = ?7 AND tenant_id = ?8",
+    )
+    .bind::<diesel::sql_types::Integer, _>(yetersayi.cagri)
+    .bind::<diesel::sql_types::Text, _>(girdi.zaman.trim())
+    .bind::<diesel::sql_types::Integer, _>(yetersayi.katilan as i32)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.divan_baskani))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.divan_katipleri))
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(yetersayi)
+}
+
+pub fn gundem_getir(conn: &mut SqliteConnection, tenant_id: &str, genel_kurul_id: &str) -> Result<Vec<GundemMaddesi>, String> {
+    diesel::sql_query(
+        "SELECT id, sira, baslik, aciklama, nitelikli, katilan, kabul, ret, cekimser, sonuc, karar_metni
+         FROM genel_kurul_gundem WHERE tenant_id = ?1 AND genel_kurul_id = ?2 ORDER BY sira",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Gündemi verilen listeyle eşitler (sıra listedeki sıradır); toplantı açılmadan önce
+pub fn gundem_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    maddeler: &[GundemGirdi],
+) -> Result<Vec<GundemMaddesi>, String> {
+    let gk = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    if !["hazirlik", "ertelendi"].contains(&gk.durum.as_str()) {
+        return Err("Gündem toplantı açıldıktan sonra değiştirilemez".to_string());
+    }
+    if maddeler.iter().any(|m| m.baslik.trim().is_empty()) {
+        return Err("Gündem maddesi başlığı boş olamaz".to_string());
+    }
+
+    let mevcut = gundem_getir(conn, tenant_id, genel_kurul_id)?;
+    let korunan: HashSet<&str> = maddeler.iter().filter_map(|m| m.id.as_deref()).collect();
+    if let Some(id) = korunan.iter().find(|id| !mevcut.iter().any(|m| m.id == **id)) {
+        return Err(format!("Gündem maddesi bulunamadı: {}", id));
+    }
+    for m in mevcut.iter().filter(|m| !korunan.contains(m.id.as_str())) {
+        denetim.izle(conn, tenant_id, "genel_kurul_gundem", &m.id)?;
+        diesel::sql_query("DELETE FROM genel_kurul_gundem WHERE id = ?1")
+            .bind::<diesel::sql_types::Text, _>(&m.id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for (sira, m) in maddeler.iter().enumerate() {
+        let id = match &m.id {
+            Some(id) => {
+                denetim.izle(conn, tenant_id, "genel_kurul_gundem", id)?;
+                id.clone()
+            }
+            None => {
+                let id = Uuid::new_v4().to_string();
+                diesel::sql_query(
+                    "INSERT INTO genel_kurul_gundem (id, tenant_id, genel_kurul_id, sira, baslik, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, 0, '', ?4, ?4)",
+                )
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .bind::<diesel::sql_types::Text, _>(tenant_id)
+                .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .execute(conn)
+                .map_err(|e| e.to_string())?;
+                denetim.yeni_kayit(tenant_id, "genel_kurul_gundem", &id);
+                id
+            }
+        };
+        diesel::sql_query(
+            "UPDATE genel_kurul_gundem SET sira = ?1, baslik = ?2, aciklama = ?3, nitelikli = ?4, updated_at = ?5
+             WHERE id = ?6",
+        )
+        .bind::<diesel::sql_types::Integer, _>(sira as i32 + 1)
+        .bind::<diesel::sql_types::Text, _>(m.baslik.trim())
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&m.aciklama))
+        .bind::<diesel::sql_types::Bool, _>(m.nitelikli.unwrap_or(false))
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    gundem_getir(conn, tenant_id, genel_kurul_id)
+}
+
+fn acik_toplanti(conn: &mut SqliteConnection, tenant_id: &str, genel_kurul_id: &str) -> Result<GenelKurul, String> {
+    let gk = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    if gk.durum != "acildi" {
+        return Err("Oylama için genel kurul toplantısı açılmış olmalıdır".to_string());
+    }
+    Ok(gk)
+}
+
+/// Gündem maddesinin oy sayılarını yazar; sonuç oylama anındaki katılana göre hesaplanır
+pub fn oylama_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    madde_id: &str,
+    girdi: &OylamaGirdi,
+) -> Result<GundemMaddesi, String> {
+    acik_toplanti(conn, tenant_id, genel_kurul_id)?;
+    let madde = gundem_getir(conn, tenant_id, genel_kurul_id)?
+        .into_iter()
+        .find(|m| m.id == madde_id)
+        .ok_or("Gündem maddesi bulunamadı")?;
+    if girdi.kabul < 0 || girdi.ret < 0 || girdi.cekimser < 0 {
+        return Err("Oy sayıları negatif olamaz".to_string());
+    }
+    let (_, katilan) = katilan_sayisi(conn, tenant_id, genel_kurul_id)?;
+    let oy = (girdi.kabul + girdi.ret + girdi.cekimser) as i64;
+    if oy > katilan {
+        return Err(format!("Kullanılan oy ({}) toplantıya katılan sayısını ({}) aşıyor", oy, katilan));
+    }
+    let sonuc = karar_sonucu(katilan, girdi.kabul as i64, madde.nitelikli);
+
+    denetim.izle(conn, tenant_id, "genel_kurul_gundem", madde_id)?;
+    diesel::sql_query(
+        "UPDATE genel_kurul_gundem SET katilan = ?1, kabul = ?2, ret = ?3, cekimser = ?4, sonuc = ?5, karar_metni = ?6,
+            updated_at = ?7
+         WHERE id = ?8",
+    )
+    .bind::<diesel::sql_types::Integer, _>(katilan as i32)
+    .bind::<diesel::sql_types::Integer, _>(girdi.kabul)
+    .bind::<diesel::sql_types::Integer, _>(girdi.ret)
+    .bind::<diesel::sql_types::Integer, _>(girdi.cekimser)
+    .bind::<diesel::sql_types::Text, _>(sonuc)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.karar_metni))
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(madde_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+
+    gundem_getir(conn, tenant_id, genel_kurul_id)?
+        .into_iter()
+        .find(|m| m.id == madde_id)
+        .ok_or_else(|| "Gündem maddesi bulunamadı".to_string())
+}
+
+pub fn adaylari_getir(conn: &mut SqliteConnection, tenant_id: &str, secim_id: &str) -> Result<Vec<SecimAdayi>, String> {
+    diesel::sql_query(
+        "SELECT a.uye_id, h.uye_no, COALESCE(h.ad_soyad, u.ad_soyad, '') AS ad_soyad, a.oy, a.kura_sirasi, a.sira, a.sonuc
+         FROM genel_kurul_adaylari a
+         JOIN genel_kurul_secimleri s ON s.id = a.secim_id
+         LEFT JOIN genel_kurul_hazirun h ON h.genel_kurul_id = s.genel_kurul_id AND h.uye_id = a.uye_id
+         LEFT JOIN uyeler u ON u.id = a.uye_id AND u.tenant_id = a.tenant_id
+         WHERE a.tenant_id = ?1 AND a.secim_id = ?2
+         ORDER BY a.sira IS NULL, a.sira, ad_soyad",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(secim_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Organ seçimini ve aday listesini kaydeder (organ başına bir seçim); adaylar katılım
+/// hakkı olan üyeler olmalıdır. Sonuçlanmış seçim değiştirilemez.
+pub fn secim_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    girdi: &SecimGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct SecimRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        sonuclandi: bool,
+    }
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let gk = genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    if gk.durum == "tamamlandi" {
+        return Err("Tamamlanmış genel kurulda seçim değiştirilemez".to_string());
+    }
+    let (_, asgari) = ORGANLAR
+        .iter()
+        .find(|(o, _)| *o == girdi.organ)
+        .ok_or_else(|| format!("Geçersiz organ: {}", girdi.organ))?;
+    if girdi.asil_sayisi < *asgari {
+        return Err(format!("Asil üye sayısı en az {} olmalıdır", asgari));
+    }
+    if girdi.yedek_sayisi < 0 {
+        return Err("Yedek üye sayısı negatif olamaz".to_string());
+    }
+    let adaylar: HashSet<&str> = girdi.adaylar.iter().map(String::as_str).collect();
+    if adaylar.len() != girdi.adaylar.len() {
+        return Err("Aday listesinde tekrar eden üye var".to_string());
+    }
+    if (adaylar.len() as i32) < girdi.asil_sayisi {
+        return Err("Aday sayısı asil üye sayısından az olamaz".to_string());
+    }
+    let hazirun = hazirun_getir(conn, tenant_id, genel_kurul_id)?;
+    for aday in &adaylar {
+        match hazirun.iter().find(|h| h.uye_id == *aday) {
+            Some(h) if h.katilim_hakki => {}
+            Some(h) => return Err(format!("{} aday olamaz: katılım hakkı yok", h.ad_soyad)),
+            None => return Err("Aday hazirun listesinde değil".to_string()),
+        }
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mevcut: Option<SecimRow> = diesel::sql_query(
+        "SELECT id, sonuclandi FROM genel_kurul_secimleri WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND organ = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .bind::<diesel::sql_types::Text, _>(&girdi.organ)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+    let secim_id = match mevcut {
+        Some(s) if s.sonuclandi => return Err("Sonuçlanmış seçim değiştirilemez".to_string()),
+        Some(s) => {
+            denetim.izle(conn, tenant_id, "genel_kurul_secimleri", &s.id)?;
+            diesel::sql_query("UPDATE genel_kurul_secimleri SET asil_sayisi = ?1, yedek_sayisi = ?2, updated_at = ?3 WHERE id = ?4")
+                .bind::<diesel::sql_types::Integer, _>(girdi.asil_sayisi)
+                .bind::<diesel::sql_types::Integer, _>(girdi.yedek_sayisi)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&s.id)
+                .execute(conn)
+                .map_err(|e| e.to_string())?;
+            s.id
+        }
+        None => {
+            let id = Uuid::new_v4().to_string();
+            diesel::sql_query(
+                "INSERT INTO genel_kurul_secimleri (id, tenant_id, genel_kurul_id, organ, asil_sayisi, yedek_sayisi,
+                    created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+            .bind::<diesel::sql_types::Text, _>(&girdi.organ)
+            .bind::<diesel::sql_types::Integer, _>(girdi.asil_sayisi)
+            .bind::<diesel::sql_types::Integer, _>(girdi.yedek_sayisi)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            denetim.yeni_kayit(tenant_id, "genel_kurul_secimleri", &id);
+            id
+        }
+    };
+
+    let eski: Vec<IdRow> = diesel::sql_query("SELECT id FROM genel_kurul_adaylari WHERE secim_id = ?1")
+        .bind::<diesel::sql_types::Text, _>(&secim_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    for r in &eski {
+        denetim.izle(conn, tenant_id, "genel_kurul_adaylari", &r.id)?;
+    }
+    diesel::sql_query("DELETE FROM genel_kurul_adaylari WHERE secim_id = ?1")
+        .bind::<diesel::sql_types::Text, _>(&secim_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    for aday in &girdi.adaylar {
+        let id = Uuid::new_v4().to_string();
+        diesel::sql_query("INSERT INTO genel_kurul_adaylari (id, tenant_id, secim_id, uye_id) VALUES (?1, ?2, ?3, ?4)")
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(&secim_id)
+            .bind::<diesel::sql_types::Text, _>(aday)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        denetim.yeni_kayit(tenant_id, "genel_kurul_adaylari", &id);
+    }
+    Ok(secim_id)
+}
+
+/// Oy pusulası sayımını yazar ve adayları asil/yedek/kazanamadı olarak sıralar
+pub fn secim_sonucu_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    secim_id: &str,
+    girdi: &SecimSonucuGirdi,
+) -> Result<Vec<SecimAdayi>, String> {
+    #[derive(QueryableByName)]
+    struct AdayRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+    }
+
+    acik_toplanti(conn, tenant_id, genel_kurul_id)?;
+    let secim: Secim = diesel::sql_query(
+        "SELECT id, organ, asil_sayisi, yedek_sayisi, kullanilan_oy, gecersiz_oy, sonuclandi
+         FROM genel_kurul_secimleri WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND id = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .bind::<diesel::sql_types::Text, _>(secim_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or("Seçim bulunamadı")?;
+    if secim.sonuclandi {
+        return Err("Seçim zaten sonuçlanmış".to_string());
+    }
+
+    let (_, katilan) = katilan_sayisi(conn, tenant_id, genel_kurul_id)?;
+    if girdi.kullanilan_oy < 0 || girdi.gecersiz_oy < 0 || girdi.gecersiz_oy > girdi.kullanilan_oy {
+        return Err("Geçersiz oy sayısı".to_string());
+    }
+    if girdi.kullanilan_oy as i64 > katilan {
+        return Err(format!("Kullanılan oy ({}) toplantıya katılan sayısını ({}) aşıyor", girdi.kullanilan_oy, katilan));
+    }
+    let gecerli = girdi.kullanilan_oy - girdi.gecersiz_oy;
+
+    let adaylar: Vec<AdayRow> = diesel::sql_query("SELECT id, uye_id FROM genel_kurul_adaylari WHERE secim_id = ?1")
+        .bind::<diesel::sql_types::Text, _>(secim_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    if girdi.oylar.len() != adaylar.len() || adaylar.iter().any(|a| !girdi.oylar.iter().any(|o| o.uye_id == a.uye_id)) {
+        return Err("Her aday için bir kez oy sayısı girilmelidir".to_string());
+    }
+    if girdi.oylar.iter().any(|o| o.oy < 0 || o.oy > gecerli) {
+        return Err(format!("Adayın oyu 0 ile geçerli oy sayısı ({}) arasında olmalıdır", gecerli));
+    }
+
+    let asil = secim.asil_sayisi as usize;
+    let yedek = secim.yedek_sayisi as usize;
+    let oylar: Vec<(i32, Option<i32>)> = girdi.oylar.iter().map(|o| (o.oy, o.kura_sirasi)).collect();
+    let sira = match secim_sirasi(&oylar, &[asil, asil + yedek]) {
+        Ok(sira) => sira,
+        Err((a, b)) => {
+            let adlar = adaylari_getir(conn, tenant_id, secim_id)?;
+            let ad = |i: usize| {
+                adlar.iter().find(|x| x.uye_id == girdi.oylar[i].uye_id).map(|x| x.ad_soyad.as_str()).unwrap_or("")
+            };
+            return Err(format!("{} ve {} eşit oy aldı; kura sırası girilmelidir", ad(a), ad(b)));
+        }
+    };
+
+    for (konum, &i) in sira.iter().enumerate() {
+        let o = &girdi.oylar[i];
+        let aday = adaylar.iter().find(|a| a.uye_id == o.uye_id).expect("aday yukarıda doğrulandı");
+        let sonuc = if konum < asil {
+            "asil"
+        } else if konum < asil + yedek {
+            "yedek"
+        } else {
+            "kazanamadi"
+        };
+        denetim.izle(conn, tenant_id, "genel_kurul_adaylari", &aday.id)?;
+        diesel::sql_query("UPDATE genel_kurul_adaylari SET oy = ?1, kura_sirasi = ?2, sira = ?3, sonuc = ?4 WHERE id = ?5")
+            .bind::<diesel::sql_types::Integer, _>(o.oy)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(o.kura_sirasi)
+            .bind::<diesel::sql_types::Integer, _>(konum as i32 + 1)
+            .bind::<diesel::sql_types::Text, _>(sonuc)
+            .bind::<diesel::sql_types::Text, _>(&aday.id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+    }
+
+    denetim.izle(conn, tenant_id, "genel_kurul_secimleri", secim_id)?;
+    diesel::sql_query(
+        "UPDATE genel_kurul_secimleri SET kullanilan_oy = ?1, gecersiz_oy = ?2, sonuclandi = 1, updated_at = ?3 WHERE id = ?4",
+    )
+    .bind::<diesel::sql_types::Integer, _>(girdi.kullanilan_oy)
+    .bind::<diesel::sql_types::Integer, _>(girdi.gecersiz_oy)
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(secim_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    adaylari_getir(conn, tenant_id, secim_id)
+}
+
+/// Toplantıyı kapatır: tüm gündem maddeleri oylanmış ve seçimler sonuçlanmış olmalıdır.
+/// Bağlı toplantı tamamlandı olarak işaretlenir, katılımcı sayısı hazirundan yazılır.
+pub fn tamamla(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, genel_kurul_id: &str) -> Result<(), String> {
+    let gk = acik_toplanti(conn, tenant_id, genel_kurul_id)?;
+    if let Some(m) = gundem_getir(conn, tenant_id, genel_kurul_id)?.iter().find(|m| m.sonuc.is_none()) {
+        return Err(format!("{}. gündem maddesi oylanmadı: {}", m.sira, m.baslik));
+    }
+    let acik: SayiRow = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM genel_kurul_secimleri WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND sonuclandi = 0",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if acik.sayi > 0 {
+        return Err("Sonuçlanmamış seçim var".to_string());
+    }
+    let (_, katilan) = katilan_sayisi(conn, tenant_id, genel_kurul_id)?;
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim.izle(conn, tenant_id, "genel_kurullar", genel_kurul_id)?;
+    diesel::sql_query("UPDATE genel_kurullar SET durum = 'tamamlandi', updated_at = ?1 WHERE id = ?2")
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    denetim.izle(conn, tenant_id, "toplantilar", &gk.toplanti_id)?;
+    diesel::sql_query(
+        "UPDATE toplantilar SET durum = 'tamamlandi', katilimci_sayisi = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4",
+    )
+    .bind::<diesel::sql_types::Integer, _>(katilan as i32)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(&gk.toplanti_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        for i in 1..=12 {
+            test_db::uye(&mut conn, "t1", &format!("u{}", i), &i.to_string(), &format!("Üye {}", i));
+        }
+        // u10'un 2025 aidatı ödenmemiş, u12 2025 sonunda ayrılmış
+        test_db::calistir(&mut conn, "UPDATE uyeler SET cikis_tarihi = '2025-12-31' WHERE id = 'u12'");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan) VALUES
+                ('a1', 't1', 'u10', 2025, 6, 100.0, 0, 100.0),
+                ('a2', 't1', 'u1', 2026, 4, 100.0, 0, 100.0)",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO toplantilar (id, tenant_id, baslik, tarih) VALUES ('gk', 't1', '2026 Olağan Genel Kurul', '2026-04-04')",
+        );
+        conn
+    }
+
+    fn girdi() -> GenelKurulGirdi {
+        GenelKurulGirdi {
+            toplanti_id: "gk".to_string(),
+            tur: None,
+            hazirun_tarihi: "2026-03-20".to_string(),
+            ikinci_toplanti_tarihi: Some("2026-04-11".to_string()),
+            yk_dk_uye_sayisi: 8,
+            borc_engeli: None,
+            borc_tolerans_gun: None,
+        }
+    }
+
+    fn imzala(c: &mut SqliteConnection, d: &mut Denetim, gk: &str, uyeler: std::ops::RangeInclusive<i32>) {
+        for i in uyeler {
+            imza_kaydet(c, d, "t1", gk, &format!("u{}", i), true, "2026-04-04 10:00").unwrap();
+        }
+    }
+
+    #[test]
+    fn yetersayi_ve_karar_kurallari() {
+        assert_eq!(gerekli_yetersayi(1, 10, 8, false), 6);
+        assert_eq!(gerekli_yetersayi(1, 11, 8, false), 6);
+        assert_eq!(gerekli_yetersayi(1, 10, 8, true), 7);
+        assert_eq!(gerekli_yetersayi(1, 9, 8, true), 6);
+        assert_eq!(gerekli_yetersayi(2, 100, 8, true), 16);
+        assert_eq!(karar_sonucu(10, 5, false), "ret");
+        assert_eq!(karar_sonucu(10, 6, false), "kabul");
+        assert_eq!(karar_sonucu(9, 6, true), "kabul");
+        assert_eq!(karar_sonucu(10, 6, true), "ret");
+
+        // 3 asil 1 yedek: 2. ve 3. eşit ama ikisi de asil; 4. ve 5. eşit, yedek sınırında kura gerekir
+        let oylar = [(9, None), (7, None), (7, None), (5, None), (5, None)];
+        assert_eq!(secim_sirasi(&oylar, &[3, 4]), Err((3, 4)));
+        let oylar = [(9, None), (7, None), (7, None), (5, Some(2)), (5, Some(1))];
+        assert_eq!(secim_sirasi(&oylar, &[3, 4]), Ok(vec![0, 1, 2, 4, 3]));
+    }
+
+    #[test]
+    fn genel_kurul_akisi() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut d = Denetim::yeni(None, None, "test");
+
+        let mut yanlis = girdi();
+        yanlis.ikinci_toplanti_tarihi = Some("2026-04-08".to_string());
+        assert!(genel_kurul_kaydet(c, &mut d, "t1", None, &yanlis).is_err());
+        let gk = genel_kurul_kaydet(c, &mut d, "t1", None, &girdi()).unwrap();
+
+        // u12 ayrılmış, u10 borçlu: 11 satır, 10 katılım hakkı
+        let hazirun = hazirun_olustur(c, &mut d, "t1", &gk).unwrap();
+        assert_eq!(hazirun.len(), 11);
+        let borclu = hazirun.iter().find(|h| h.uye_id == "u10").unwrap();
+        assert!(!borclu.katilim_hakki && borclu.borc_tutari == Para::from_tl(100.0));
+        assert!(imza_kaydet(c, &mut d, "t1", &gk, "u10", true, "2026-04-04 10:00").is_err());
+
+        gundem_kaydet(c, &mut d, "t1", &gk, &[
+            GundemGirdi { id: None, baslik: "Açılış ve divan seçimi".into(), aciklama: None, nitelikli: None },
+            GundemGirdi { id: None, baslik: "Tüzük değişikliği".into(), aciklama: None, nitelikli: Some(true) },
+        ])
+        .unwrap();
+
+        // İlk çağrı: 10 üyenin üçte ikisi (7) gerekir, 6 imza ile ertelenir
+        imzala(c, &mut d, &gk, 1..=6);
+        let acilis = AcilisGirdi { zaman: "2026-04-04 10:30".into(), divan_baskani: None, divan_katipleri: None };
+        assert!(!toplantiyi_ac(c, &mut d, "t1", &gk, &acilis).unwrap().saglandi);
+        assert_eq!(genel_kurul_getir(c, "t1", &gk).unwrap().durum, "ertelendi");
+
+        // İkinci çağrı 11 Nisan'da ve 2 × 8 = 16 katılan ister: bu dernekte sağlanamaz
+        let ikinci = AcilisGirdi { zaman: "2026-04-11 10:30".into(), divan_baskani: Some("Üye 1".into()), divan_katipleri: None };
+        assert!(toplantiyi_ac(c, &mut d, "t1", &gk, &acilis).is_err());
+        assert!(toplantiyi_ac(c, &mut d, "t1", &gk, &ikinci).unwrap_err().contains("16"));
+    }
+
+    #[test]
+    fn oylama_secim_ve_tamamlama() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut d = Denetim::yeni(None, None, "test");
+
+        let gk = genel_kurul_kaydet(c, &mut d, "t1", None, &girdi()).unwrap();
+        hazirun_olustur(c, &mut d, "t1", &gk).unwrap();
+        let gundem = gundem_kaydet(c, &mut d, "t1", &gk, &[
+            GundemGirdi { id: None, baslik: "Faaliyet raporu".into(), aciklama: None, nitelikli: None },
+        ])
+        .unwrap();
+        let adaylar: Vec<String> = (1..=7).map(|i| format!("u{}", i)).collect();
+        let yk = secim_kaydet(c, &mut d, "t1", &gk, &SecimGirdi {
+            organ: "yonetim_kurulu".into(),
+            asil_sayisi: 5,
+            yedek_sayisi: 1,
+            adaylar: adaylar.clone(),
+        })
+        .unwrap();
+
+        imzala(c, &mut d, &gk, 1..=6);
+        let acilis = AcilisGirdi { zaman: "2026-04-04 10:30".into(), divan_baskani: None, divan_katipleri: None };
+        let y = toplantiyi_ac(c, &mut d, "t1", &gk, &acilis).unwrap();
+        assert_eq!((y.cagri, y.uye_tamsayisi, y.katilan, y.gerekli, y.saglandi), (1, 10, 6, 6, true));
+        assert!(gundem_kaydet(c, &mut d, "t1", &gk, &[]).is_err());
+        assert!(tamamla(c, &mut d, "t1", &gk).is_err());
+
+        // Geç gelen üye imzalar; oylama 7 katılana göre
+        imzala(c, &mut d, &gk, 7..=7);
+        let oylama = |kabul| OylamaGirdi { kabul, ret: 7 - kabul, cekimser: 0, karar_metni: None };
+        assert!(oylama_kaydet(c, &mut d, "t1", &gk, &gundem[0].id, &OylamaGirdi { kabul: 8, ..oylama(0) }).is_err());
+        let madde = oylama_kaydet(c, &mut d, "t1", &gk, &gundem[0].id, &oylama(4)).unwrap();
+        assert_eq!((madde.katilan, madde.sonuc.as_deref()), (Some(7), Some("kabul")));
+
+        let mut sonuc = SecimSonucuGirdi {
+            kullanilan_oy: 7,
+            gecersiz_oy: 1,
+            oylar: adaylar.iter().zip([6, 6, 5, 5, 4, 3, 3]).map(|(u, oy)| AdayOyu { uye_id: u.clone(), oy, kura_sirasi: None }).collect(),
+        };
+        assert!(secim_sonucu_kaydet(c, &mut d, "t1", &gk, &yk, &sonuc).unwrap_err().contains("kura"));
+        sonuc.oylar[5].kura_sirasi = Some(2);
+        sonuc.oylar[6].kura_sirasi = Some(1);
+        let adaylar = secim_sonucu_kaydet(c, &mut d, "t1", &gk, &yk, &sonuc).unwrap();
+        let yedek: Vec<&str> = adaylar.iter().filter(|a| a.sonuc.as_deref() == Some("yedek")).map(|a| a.uye_id.as_str()).collect();
+        assert_eq!(yedek, ["u7"]);
+        assert_eq!(adaylar.iter().filter(|a| a.sonuc.as_deref() == Some("asil")).count(), 5);
+
+        tamamla(c, &mut d, "t1", &gk).unwrap();
+        assert_eq!(genel_kurul_getir(c, "t1", &gk).unwrap().durum, "tamamlandi");
+    }
+}
@@ -0,0 +1,794 @@
+// Aile (hane) üyelikleri: ödeyen üye, bağlı üye kayıtları, aile aidatı ve üye payları.
+//
+// Hane tahakkuku: o yılın aidat tanımında ödeyenin üye türü için aile_tutari varsa,
+// dönemde üye olan en az iki hane üyesi (ödeyen dahil) için tek aidat ödeyene açılır
+// ve diğer hane üyeleri atlanır. Aile tutarına fiyat kuralları uygulanmaz (üyeye özel
+// tutar gibi). Ödeyen dönemde üye değilse ya da aile tutarı tanımlı değilse hane
+// üyeleri tek tek borçlandırılır.
+// Paylar: hane aidatı tahakkuk anındaki hane üyelerine pay_agirligi oranında bölünüp
+// hane_aidat_paylari'na yazılır. Borç ve tahsilat ödeyenin aidat kaydında kalır;
+// raporlamada aidatın ödenen tutarı aynı paylar oranında üyelere dağıtılır.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::aidat_fiyat::{AidatFiyatAdimi, AidatFiyati};
+use crate::db::denetim::Denetim;
+use crate::db::para::Para;
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct Hane {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub odeyen_uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub odeyen_ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub is_active: bool,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub uye_sayisi: i64,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct HaneUyesi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub yakinlik: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub pay_agirligi: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HaneDetay {
+    #[serde(flatten)]
+    pub hane: Hane,
+    pub uyeler: Vec<HaneUyesi>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HaneUyesiGirdi {
+    pub uye_id: String,
+    pub yakinlik: Option<String>,
+    pub pay_agirligi: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HaneGirdi {
+    pub ad: String,
+    pub odeyen_uye_id: String,
+    pub aciklama: Option<String>,
+    /// Ödeyen listede yoksa eklenir
+    pub uyeler: Vec<HaneUyesiGirdi>,
+}
+
+/// Hane aidatının bir üyeye düşen payı ve o paydan ödenen kısım
+#[derive(Debug, Clone, Serialize)]
+pub struct HaneAidatPayi {
+    pub hane_id: String,
+    pub hane_adi: String,
+    pub aidat_id: String,
+    pub yil: i32,
+    pub ay: i32,
+    pub uye_id: String,
+    pub uye_no: Option<String>,
+    pub ad_soyad: Option<String>,
+    pub odeyen: bool,
+    pub pay: Para,
+    pub odenen: Para,
+    pub kalan: Para,
+}
+
+/// Bir dönemde tek aidatla borçlandırılacak hane
+#[derive(Debug, Clone, PartialEq)]
+pub struct HaneAidati {
+    pub hane_id: String,
+    pub hane_adi: String,
+    pub odeyen_uye_id: String,
+    pub tutar: Para,
+    /// (uye_id, pay_agirligi); ödeyen dahil, dönemde üye olanlar
+    pub uyeler: Vec<(String, i64)>,
+}
+
+impl HaneAidati {
+    /// Üyelere düşen paylar (toplamı tutara eşit)
+    pub fn paylar(&self) -> Vec<(&str, Para)> {
+        let agirliklar: Vec<i64> = self.uyeler.iter().map(|(_, a)| *a).collect();
+        self.uyeler
+            .iter()
+            .map(|(u, _)| u.as_str())
+            .zip(self.tutar.agirlikli_paylastir(&agirliklar))
+            .collect()
+    }
+
+    /// Önizleme ve aidat notu için fiyat açıklaması; ozet() hane adını verir
+    pub fn fiyat(&self) -> AidatFiyati {
+        AidatFiyati {
+            tutar: self.tutar,
+            kaynak: "hane".to_string(),
+            adimlar: vec![
+                AidatFiyatAdimi {
+                    kural_id: None,
+                    aciklama: "Aile tutarı".to_string(),
+                    onceki: Para::SIFIR,
+                    sonraki: self.tutar,
+                },
+                AidatFiyatAdimi {
+                    kural_id: None,
+                    aciklama: format!("Hane: {} ({} üye)", self.hane_adi, self.uyeler.len()),
+                    onceki: self.tutar,
+                    sonraki: self.tutar,
+                },
+            ],
+        }
+    }
+}
+
+/// Tahakkukta üyenin hane durumu
+#[derive(Debug, PartialEq)]
+pub enum HaneKarari<'a> {
+    /// Hane aidatı yok; üye kendi aidatıyla borçlandırılır
+    Bireysel,
+    /// Hane aidatı bu üyeye açılır
+    Odeyen(&'a HaneAidati),
+    /// Üyenin aidatını hane ödeyeni öder
+    Kapsanan(&'a HaneAidati),
+}
+
+/// Hane üyeliği satırı (tahakkuk hesabı için)
+#[derive(Debug, Clone, QueryableByName)]
+pub struct HaneUyelikSatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hane_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub hane_adi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub odeyen_uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub pay_agirligi: i32,
+}
+
+/// Hane üyelikleri, aile tutarları (üye türü → tutar) ve dönemdeki üyeler
+/// (uye_id → üye türü) üzerinden tek aidatla borçlandırılacak haneler
+pub fn hane_aidatlari(
+    satirlar: &[HaneUyelikSatiri],
+    aile_tutarlari: &HashMap<String, Para>,
+    donemdeki_uyeler: &HashMap<String, String>,
+) -> Vec<HaneAidati> {
+    let mut haneler: Vec<HaneAidati> = Vec::new();
+    for s in satirlar {
+        if !donemdeki_uyeler.contains_key(&s.uye_id) {
+            continue;
+        }
+        let uye = (s.uye_id.clone(), s.pay_agirligi.max(1) as i64);
+        match haneler.iter_mut().find(|h| h.hane_id == s.hane_id) {
+            Some(h) => h.uyeler.push(uye),
+            None => {
+                let Some(tutar) = donemdeki_uyeler
+                    .get(&s.odeyen_uye_id)
+                    .and_then(|tur| aile_tutarlari.get(tur))
+                    .copied()
+                else {
+                    continue;
+                };
+                haneler.push(HaneAidati {
+                    hane_id: s.hane_id.clone(),
+                    hane_adi: s.hane_adi.clone(),
+                    odeyen_uye_id: s.odeyen_uye_id.clone(),
+                    tutar,
+                    uyeler: vec![uye],
+                });
+            }
+        }
+    }
+    haneler.retain(|h| h.uyeler.len() >= 2 && h.uyeler.iter().any(|(u, _)| *u == h.odeyen_uye_id));
+    haneler
+}
+
+/// Bir tenant ve yıl için hane aidatlarını bir kez hesaplayıp üye bazında karar verir
+pub struct HaneTahakkuku {
+    haneler: Vec<HaneAidati>,
+    uye_hanesi: HashMap<String, usize>,
+}
+
+impl HaneTahakkuku {
+    /// `uyeler`: dönemde üye olanlar (uye_id, üye türü)
+    pub fn yukle<'a>(
+        conn: &mut SqliteConnection,
+        tenant_id: &str,
+        yil: i32,
+        uyeler: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, String> {
+        #[derive(QueryableByName)]
+        struct AileTutariRow {
+            #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+            uye_turu: Option<String>,
+            #[diesel(sql_type = diesel::sql_types::Double)]
+            aile_tutari: Para,
+        }
+
+        let aile_tutarlari: HashMap<String, Para> = diesel::sql_query(
+            "SELECT uye_turu, aile_tutari FROM aidat_tanimlari
+             WHERE tenant_id = ?1 AND yil = ?2 AND is_active = 1 AND aile_tutari > 0
+             ORDER BY updated_at ASC",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Integer, _>(yil)
+        .load::<AileTutariRow>(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| (r.uye_turu.unwrap_or_else(|| "Asil".to_string()), r.aile_tutari))
+        .collect();
+
+        let donemdeki: HashMap<String, String> =
+            uyeler.into_iter().map(|(id, tur)| (id.to_string(), tur.to_string())).collect();
+        let haneler = if aile_tutarlari.is_empty() {
+            Vec::new()
+        } else {
+            hane_aidatlari(&uyelik_satirlari(conn, tenant_id)?, &aile_tutarlari, &donemdeki)
+        };
+
+        let mut uye_hanesi = HashMap::new();
+        for (i, h) in haneler.iter().enumerate() {
+            for (uye_id, _) in &h.uyeler {
+                uye_hanesi.insert(uye_id.clone(), i);
+            }
+        }
+        Ok(HaneTahakkuku { haneler, uye_hanesi })
+    }
+
+    /// Ayrılmamış (cikis_tarihi boş) üyelerle yükler
+    pub fn aktif_uyelerle(conn: &mut SqliteConnection, tenant_id: &str, yil: i32) -> Result<Self, String> {
+        #[derive(QueryableByName)]
+        struct UyeRow {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            id: String,
+            #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+            uyelik_tipi: Option<String>,
+        }
+
+        let uyeler: Vec<UyeRow> = diesel::sql_query(
+            "SELECT id, uyelik_tipi FROM uyeler
+             WHERE tenant_id = ?1 AND (cikis_tarihi IS NULL OR cikis_tarihi = '')",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+        Self::yukle(
+            conn,
+            tenant_id,
+            yil,
+            uyeler.iter().map(|u| (u.id.as_str(), u.uyelik_tipi.as_deref().unwrap_or("Asil"))),
+        )
+    }
+
+    pub fn karar(&self, uye_id: &str) -> HaneKarari<'_> {
+        match self.uye_hanesi.get(uye_id).map(|i| &self.haneler[*i]) {
+            None => HaneKarari::Bireysel,
+            Some(h) if h.odeyen_uye_id == uye_id => HaneKarari::Odeyen(h),
+            Some(h) => HaneKarari::Kapsanan(h),
+        }
+    }
+}
+
+fn uyelik_satirlari(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<HaneUyelikSatiri>, String> {
+    diesel::sql_query(
+        "SELECT h.id AS hane_id, h.ad AS hane_adi, h.odeyen_uye_id, hu.uye_id, hu.pay_agirligi
+         FROM haneler h
+         JOIN hane_uyeleri hu ON hu.hane_id = h.id AND hu.tenant_id = h.tenant_id
+         WHERE h.tenant_id = ?1 AND h.is_active = 1
+         ORDER BY h.id, hu.created_at, hu.uye_id",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Hane aidatının üye paylarını yazar; çağıranın transaction'ı içinde, aidat kaydıyla birlikte
+pub fn paylari_yaz(conn: &mut SqliteConnection, tenant_id: &str, aidat_id: &str, hane: &HaneAidati) -> Result<(), String> {
+    for (uye_id, pay) in hane.paylar() {
+        diesel::sql_query(
+            "INSERT INTO hane_aidat_paylari (id, tenant_id, hane_id, aidat_id, uye_id, pay) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&hane.hane_id)
+        .bind::<diesel::sql_types::Text, _>(aidat_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Double, _>(pay)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+const HANE_SECIMI: &str = "SELECT h.id, h.ad, h.odeyen_uye_id, o.ad_soyad AS odeyen_ad_soyad, h.aciklama,
+            h.is_active, (SELECT COUNT(*) FROM hane_uyeleri hu WHERE hu.hane_id = h.id) AS uye_sayisi
+     FROM haneler h
+     LEFT JOIN uyeler o ON o.id = h.odeyen_uye_id AND o.tenant_id = h.tenant_id";
+
+pub fn haneleri_getir(conn: &mut SqliteConnection, tenant_id: &str, sadece_aktif: bool) -> Result<Vec<Hane>, String> {
+    diesel::sql_query(format!("{} WHERE h.tenant_id = ?1 AND (?2 = 0 OR h.is_active = 1) ORDER BY h.ad", HANE_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Bool, _>(sadece_aktif)
+        .load(conn)
+        .map_err(|e| e.to_string())
+}
+
+pub fn hane_getir(conn: &mut SqliteConnection, tenant_id: &str, hane_id: &str) -> Result<HaneDetay, String> {
+    let hane: Hane = diesel::sql_query(format!("{} WHERE h.tenant_id = ?1 AND h.id = ?2", HANE_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(hane_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or("Hane bulunamadı")?;
+    let uyeler = diesel::sql_query(
+        "SELECT hu.uye_id, u.uye_no, u.ad_soyad, hu.yakinlik, hu.pay_agirligi
+         FROM hane_uyeleri hu
+         LEFT JOIN uyeler u ON u.id = hu.uye_id AND u.tenant_id = hu.tenant_id
+         WHERE hu.tenant_id = ?1 AND hu.hane_id = ?2
+         ORDER BY hu.uye_id = ?3 DESC, hu.created_at, u.uye_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(hane_id)
+    .bind::<diesel::sql_types::Text, _>(&hane.odeyen_uye_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(HaneDetay { hane, uyeler })
+}
+
+/// Üyenin bağlı olduğu hane
+pub fn uye_hanesi(conn: &mut SqliteConnection, tenant_id: &str, uye_id: &str) -> Result<Option<HaneDetay>, String> {
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hane_id: String,
+    }
+
+    let hane_id = diesel::sql_query("SELECT hane_id FROM hane_uyeleri WHERE tenant_id = ?1 AND uye_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .get_result::<IdRow>(conn)
+        .optional()
+        .map_err(|e| e.to_string())?;
+    hane_id.map(|r| hane_getir(conn, tenant_id, &r.hane_id)).transpose()
+}
+
+/// Haneyi ekler (hane_id yoksa) ya da ad, ödeyen ve üye listesini günceller
+pub fn hane_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    hane_id: Option<&str>,
+    girdi: &HaneGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        hane_id: Option<String>,
+    }
+
+    let ad = girdi.ad.trim();
+    if ad.is_empty() {
+        return Err("Hane adı boş olamaz".to_string());
+    }
+    let mut uyeler: Vec<HaneUyesiGirdi> = Vec::new();
+    for u in &girdi.uyeler {
+        if uyeler.iter().any(|m| m.uye_id == u.uye_id) {
+            return Err("Bir üye haneye birden fazla eklenemez".to_string());
+        }
+        if u.pay_agirligi.is_some_and(|p| p < 1) {
+            return Err("Pay ağırlığı en az 1 olmalıdır".to_string());
+        }
+        uyeler.push(u.clone());
+    }
+    if !uyeler.iter().any(|u| u.uye_id == girdi.odeyen_uye_id) {
+        uyeler.insert(0, HaneUyesiGirdi { uye_id: girdi.odeyen_uye_id.clone(), yakinlik: None, pay_agirligi: None });
+    }
+    if uyeler.len() < 2 {
+        return Err("Hanede ödeyen dışında en az bir üye olmalıdır".to_string());
+    }
+
+    let id = hane_id.map(str::to_string).unwrap_or_else(|| Uuid::new_v4().to_string());
+    for u in &uyeler {
+        let kayit = diesel::sql_query(
+            "SELECT u.ad_soyad, hu.hane_id FROM uyeler u
+             LEFT JOIN hane_uyeleri hu ON hu.uye_id = u.id AND hu.tenant_id = u.tenant_id
+             WHERE u.tenant_id = ?1 AND u.id = ?2",
+        )
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&u.uye_id)
+        .get_result::<UyeRow>(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Üye bulunamadı: {}", u.uye_id))?;
+        if kayit.hane_id.as_deref().is_some_and(|h| h != id) {
+            return Err(format!("{} başka bir haneye bağlı", kayit.ad_soyad));
+        }
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let aciklama = girdi.aciklama.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    match hane_id {
+        Some(id) => {
+            let mevcut = hane_getir(conn, tenant_id, id)?;
+            if !mevcut.hane.is_active {
+                return Err("Kapatılmış hane güncellenemez".to_string());
+            }
+            denetim.izle(conn, tenant_id, "haneler", id)?;
+            diesel::sql_query(
+                "UPDATE haneler SET ad = ?1, odeyen_uye_id = ?2, aciklama = ?3, updated_at = ?4
+                 WHERE id = ?5 AND tenant_id = ?6",
+            )
+            .bind::<diesel::sql_types::Text, _>(ad)
+            .bind::<diesel::sql_types::Text, _>(&girdi.odeyen_uye_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aciklama)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        }
+        None => {
+            diesel::sql_query(
+                "INSERT INTO haneler (id, tenant_id, ad, odeyen_uye_id, aciklama, is_active, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?6)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(ad)
+            .bind::<diesel::sql_types::Text, _>(&girdi.odeyen_uye_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aciklama)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            denetim.yeni_kayit(tenant_id, "haneler", &id);
+        }
+    }
+    uyeleri_yaz(conn, denetim, tenant_id, &id, &uyeler, &now)?;
+    Ok(id)
+}
+
+/// Üye listesini eşitler: listede olmayanlar çıkarılır, yeniler eklenir, kalanların yakınlık/pay bilgisi güncellenir
+fn uyeleri_yaz(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    hane_id: &str,
+    uyeler: &[HaneUyesiGirdi],
+    now: &str,
+) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct UyelikRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+    }
+
+    let mevcut: Vec<UyelikRow> = diesel::sql_query("SELECT id, uye_id FROM hane_uyeleri WHERE tenant_id = ?1 AND hane_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(hane_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    let istenen: HashSet<&str> = uyeler.iter().map(|u| u.uye_id.as_str()).collect();
+
+    for m in mevcut.iter().filter(|m| !istenen.contains(m.uye_id.as_str())) {
+        denetim.izle(conn, tenant_id, "hane_uyeleri", &m.id)?;
+        diesel::sql_query("DELETE FROM hane_uyeleri WHERE id = ?1 AND tenant_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(&m.id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+    }
+
+    for u in uyeler {
+        let yakinlik = u.yakinlik.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        let pay = u.pay_agirligi.unwrap_or(1);
+        match mevcut.iter().find(|m| m.uye_id == u.uye_id) {
+            Some(m) => {
+                denetim.izle(conn, tenant_id, "hane_uyeleri", &m.id)?;
+                diesel::sql_query("UPDATE hane_uyeleri SET yakinlik = ?1, pay_agirligi = ?2 WHERE id = ?3 AND tenant_id = ?4")
+                    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(yakinlik)
+                    .bind::<diesel::sql_types::Integer, _>(pay)
+                    .bind::<diesel::sql_types::Text, _>(&m.id)
+                    .bind::<diesel::sql_types::Text, _>(tenant_id)
+                    .execute(conn)
+                    .map_err(|e| e.to_string())?;
+            }
+            None => {
+                let id = Uuid::new_v4().to_string();
+                diesel::sql_query(
+                    "INSERT INTO hane_uyeleri (id, tenant_id, hane_id, uye_id, yakinlik, pay_agirligi, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .bind::<diesel::sql_types::Text, _>(tenant_id)
+                .bind::<diesel::sql_types::Text, _>(hane_id)
+                .bind::<diesel::sql_types::Text, _>(&u.uye_id)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(yakinlik)
+                .bind::<diesel::sql_types::Integer, _>(pay)
+                .bind::<diesel::sql_types::Text, _>(now)
+                .execute(conn)
+                .map_err(|e| e.to_string())?;
+                denetim.yeni_kayit(tenant_id, "hane_uyeleri", &id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Haneyi kapatır ve üyelerini serbest bırakır; geçmiş aidat payları korunur
+pub fn hane_kapat(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, hane_id: &str) -> Result<(), String> {
+    let hane = hane_getir(conn, tenant_id, hane_id)?;
+    if !hane.hane.is_active {
+        return Err("Hane zaten kapatılmış".to_string());
+    }
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    uyeleri_yaz(conn, denetim, tenant_id, hane_id, &[], &now)?;
+    denetim.izle(conn, tenant_id, "haneler", hane_id)?;
+    diesel::sql_query("UPDATE haneler SET is_active = 0, updated_at = ?1 WHERE id = ?2 AND tenant_id = ?3")
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(hane_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Hane aidatlarının üye payları; ödenen tutar paylar oranında dağıtılır.
+/// Üye verilirse o üyenin payı olan aidatlardaki yalnızca kendi satırları döner.
+pub fn aidat_paylari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    hane_id: Option<&str>,
+    uye_id: Option<&str>,
+    yil: Option<i32>,
+) -> Result<Vec<HaneAidatPayi>, String> {
+    #[derive(QueryableByName)]
+    struct PayRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hane_id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        hane_adi: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        aidat_id: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        odeyen_uye_id: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        odenen: Para,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        uye_no: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        ad_soyad: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        pay: Para,
+    }
+
+    let satirlar: Vec<PayRow> = diesel::sql_query(
+        "SELECT p.hane_id, h.ad AS hane_adi, p.aidat_id, a.yil, a.ay, a.uye_id AS odeyen_uye_id,
+                COALESCE(a.odenen, 0) AS odenen, p.uye_id, u.uye_no, u.ad_soyad, p.pay
+         FROM hane_aidat_paylari p
+         JOIN aidat_takip a ON a.id = p.aidat_id AND a.tenant_id = p.tenant_id
+         LEFT JOIN haneler h ON h.id = p.hane_id AND h.tenant_id = p.tenant_id
+         LEFT JOIN uyeler u ON u.id = p.uye_id AND u.tenant_id = p.tenant_id
+         WHERE p.tenant_id = ?1
+           AND (?2 IS NULL OR p.hane_id = ?2)
+           AND (?3 IS NULL OR p.aidat_id IN (SELECT aidat_id FROM hane_aidat_paylari WHERE tenant_id = ?1 AND uye_id = ?3))
+           AND (?4 IS NULL OR a.yil = ?4)
+           AND (a.is_deleted IS NULL OR a.is_deleted = 0)
+           AND a.durum != 'iptal'
+         ORDER BY a.yil, a.ay, p.aidat_id, p.uye_id = a.uye_id DESC, u.uye_no",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(hane_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(yil)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let mut sonuc = Vec::new();
+    for grup in satirlar.chunk_by(|a, b| a.aidat_id == b.aidat_id) {
+        let agirliklar: Vec<i64> = grup.iter().map(|s| s.pay.kurus()).collect();
+        let odenenler = grup[0].odenen.agirlikli_paylastir(&agirliklar);
+        for (s, odenen) in grup.iter().zip(odenenler) {
+            if uye_id.is_some_and(|u| u != s.uye_id) {
+                continue;
+            }
+            sonuc.push(HaneAidatPayi {
+                hane_id: s.hane_id.clone(),
+                hane_adi: s.hane_adi.clone().unwrap_or_default(),
+                aidat_id: s.aidat_id.clone(),
+                yil: s.yil,
+                ay: s.ay,
+                uye_id: s.uye_id.clone(),
+                uye_no: s.uye_no.clone(),
+                ad_soyad: s.ad_soyad.clone(),
+                odeyen: s.uye_id == s.odeyen_uye_id,
+                pay: s.pay,
+                odenen,
+                kalan: s.pay - odenen,
+            });
+        }
+    }
+    Ok(sonuc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    const UYELER: [(&str, &str); 4] = [("u1", "Asil"), ("u2", "Asil"), ("u3", "Öğrenci"), ("u4", "Asil")];
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        for (id, no, ad) in [
+            ("u1", "001", "Ali Veli"),
+            ("u2", "002", "Ayşe Veli"),
+            ("u3", "003", "Can Veli"),
+            ("u4", "004", "Zeynep Kaya"),
+        ] {
+            test_db::uye(&mut conn, "t1", id, no, ad);
+        }
+        test_db::calistir(&mut conn, "UPDATE uyeler SET uyelik_tipi = CASE id WHEN 'u3' THEN 'Öğrenci' ELSE 'Asil' END");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_tanimlari (id, tenant_id, yil, uye_turu, tutar, is_active, updated_at)
+             VALUES ('d1', 't1', 2026, 'Asil', 1000.0, 1, '2026-01-01')",
+        );
+        conn
+    }
+
+    fn girdi(odeyen: &str, uyeler: &[(&str, i32)]) -> HaneGirdi {
+        HaneGirdi {
+            ad: "Veli ailesi".to_string(),
+            odeyen_uye_id: odeyen.to_string(),
+            aciklama: None,
+            uyeler: uyeler
+                .iter()
+                .map(|(u, p)| HaneUyesiGirdi { uye_id: u.to_string(), yakinlik: None, pay_agirligi: Some(*p) })
+                .collect(),
+        }
+    }
+
+    /// u1 öder; u2 ağırlık 1, u3 ağırlık 2
+    fn veli_hanesi(conn: &mut SqliteConnection) -> String {
+        let mut denetim = Denetim::yeni(None, None, "test");
+        hane_kaydet(conn, &mut denetim, "t1", None, &girdi("u1", &[("u2", 1), ("u3", 2)])).unwrap()
+    }
+
+    fn aile_tutari_tanimla(conn: &mut SqliteConnection) {
+        test_db::calistir(conn, "UPDATE aidat_tanimlari SET aile_tutari = 1600.0");
+    }
+
+    #[test]
+    fn odeyen_listede_olmasa_da_ilk_uye_olarak_eklenir() {
+        let mut conn = test_db();
+        let hane_id = veli_hanesi(&mut conn);
+        let detay = hane_getir(&mut conn, "t1", &hane_id).unwrap();
+        assert_eq!(detay.uyeler.len(), 3);
+        assert_eq!(detay.uyeler[0].uye_id, "u1");
+    }
+
+    #[test]
+    fn tek_kisilik_hane_kurulamaz() {
+        let mut conn = test_db();
+        let mut denetim = Denetim::yeni(None, None, "test");
+        assert!(hane_kaydet(&mut conn, &mut denetim, "t1", None, &girdi("u1", &[])).is_err());
+    }
+
+    #[test]
+    fn bir_uye_iki_hanede_olamaz() {
+        let mut conn = test_db();
+        veli_hanesi(&mut conn);
+        let mut denetim = Denetim::yeni(None, None, "test");
+        assert!(hane_kaydet(&mut conn, &mut denetim, "t1", None, &girdi("u4", &[("u3", 1)])).is_err());
+    }
+
+    #[test]
+    fn aile_tutari_tanimli_degilse_herkes_bireysel() {
+        let mut conn = test_db();
+        veli_hanesi(&mut conn);
+        let t = HaneTahakkuku::yukle(&mut conn, "t1", 2026, UYELER).unwrap();
+        assert_eq!(t.karar("u1"), HaneKarari::Bireysel);
+        assert_eq!(t.karar("u2"), HaneKarari::Bireysel);
+    }
+
+    #[test]
+    fn aile_tutari_odeyene_acilir_ve_agirliga_gore_paylasilir() {
+        let mut conn = test_db();
+        veli_hanesi(&mut conn);
+        aile_tutari_tanimla(&mut conn);
+
+        let t = HaneTahakkuku::yukle(&mut conn, "t1", 2026, UYELER).unwrap();
+        let HaneKarari::Odeyen(hane) = t.karar("u1") else { panic!("ödeyen bekleniyordu") };
+        assert_eq!(hane.tutar, Para::from_tl(1600.0));
+        assert_eq!(hane.fiyat().ozet().as_deref(), Some("Hane: Veli ailesi (3 üye)"));
+        assert!(matches!(t.karar("u3"), HaneKarari::Kapsanan(_)));
+        assert_eq!(t.karar("u4"), HaneKarari::Bireysel);
+        let paylar: Vec<Para> = hane.paylar().into_iter().map(|(_, p)| p).collect();
+        assert_eq!(paylar, vec![Para::from_tl(400.0), Para::from_tl(400.0), Para::from_tl(800.0)]);
+    }
+
+    #[test]
+    fn odeyen_donemde_uye_degilse_hane_bireysel_borclandirilir() {
+        let mut conn = test_db();
+        veli_hanesi(&mut conn);
+        aile_tutari_tanimla(&mut conn);
+        let t = HaneTahakkuku::yukle(&mut conn, "t1", 2026, [("u2", "Asil"), ("u3", "Öğrenci")]).unwrap();
+        assert_eq!(t.karar("u2"), HaneKarari::Bireysel);
+        assert_eq!(t.karar("u3"), HaneKarari::Bireysel);
+    }
+
+    #[test]
+    fn odeme_paylara_oranla_dagitilir() {
+        let mut conn = test_db();
+        let hane_id = veli_hanesi(&mut conn);
+        aile_tutari_tanimla(&mut conn);
+        let t = HaneTahakkuku::yukle(&mut conn, "t1", 2026, UYELER).unwrap();
+        let HaneKarari::Odeyen(hane) = t.karar("u1") else { panic!("ödeyen bekleniyordu") };
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan)
+             VALUES ('a1', 't1', 'u1', 2026, 1, 1600.0, 400.0, 1200.0)",
+        );
+        paylari_yaz(&mut conn, "t1", "a1", hane).unwrap();
+
+        let rapor = aidat_paylari(&mut conn, "t1", Some(&hane_id), None, Some(2026)).unwrap();
+        assert_eq!(rapor.len(), 3);
+        assert!(rapor[0].odeyen);
+        assert_eq!(rapor.iter().map(|p| p.odenen).sum::<Para>(), Para::from_tl(400.0));
+        let can = aidat_paylari(&mut conn, "t1", None, Some("u3"), None).unwrap();
+        assert_eq!(can.len(), 1);
+        assert_eq!((can[0].pay, can[0].odenen, can[0].kalan), (Para::from_tl(800.0), Para::from_tl(200.0), Para::from_tl(600.0)));
+    }
+
+    #[test]
+    fn uye_cikarilinca_ve_hane_kapaninca_gecmis_paylar_kalir() {
+        let mut conn = test_db();
+        let hane_id = veli_hanesi(&mut conn);
+        aile_tutari_tanimla(&mut conn);
+        let t = HaneTahakkuku::yukle(&mut conn, "t1", 2026, UYELER).unwrap();
+        let HaneKarari::Odeyen(hane) = t.karar("u1") else { panic!("ödeyen bekleniyordu") };
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar) VALUES ('a1', 't1', 'u1', 2026, 1, 1600.0)",
+        );
+        paylari_yaz(&mut conn, "t1", "a1", hane).unwrap();
+
+        let mut denetim = Denetim::yeni(None, None, "test");
+        hane_kaydet(&mut conn, &mut denetim, "t1", Some(&hane_id), &girdi("u1", &[("u2", 1)])).unwrap();
+        assert!(uye_hanesi(&mut conn, "t1", "u3").unwrap().is_none());
+        hane_kapat(&mut conn, &mut denetim, "t1", &hane_id).unwrap();
+        assert!(uye_hanesi(&mut conn, "t1", "u1").unwrap().is_none());
+        assert_eq!(aidat_paylari(&mut conn, "t1", Some(&hane_id), None, None).unwrap().len(), 3);
+        assert_eq!(haneleri_getir(&mut conn, "t1", true).unwrap().len(), 0);
+    }
+}
@@ -0,0 +1,1030 @@
+// Aidat borcu yaşlandırma, kademeli ihtar mektupları ve ihraç adayları.
+//
+// Yaşlandırma açık aidatları vade tarihine (aidat tanımının son_odeme_gunu,
+// bkz. gecikme::vade_tarihi) göre gecikme günü dilimlerine ayırır.
+// İhtar: üyenin "borç dönemi" en eski açık aidatının vadesinde başlar; bu
+// tarihten sonra kesilen ihtarlar kademeyi belirler. İlk ihtar en düşük
+// seviyeden kesilir, üst seviye için önceki ihtarın tebliğ edilmiş ve cevap
+// süresinin dolmuş olması gerekir. Borç tamamen kapanıp yeniden oluşursa
+// kademe baştan başlar. Şablonu olmayan dernekte yerleşik şablonlar kullanılır.
+// İhraç adayı: en eski açık borcun gecikmesi tüzükteki süreyi aşmış ve gerekli
+// seviyedeki ihtarın cevap süresi dolmuş üye. İhraç kararı yönetim kurulunundur;
+// burada yalnızca aday listesi çıkarılır.
+
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::gecikme::vade_tarihi;
+use crate::db::para::Para;
+
+pub const BILDIRIM_KANALLARI: [&str; 5] = ["elden", "posta", "iadeli_taahhutlu", "eposta", "noter"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct YasDilimleri {
+    pub vadesi_gelmemis: Para,
+    pub gun_0_30: Para,
+    pub gun_31_90: Para,
+    pub gun_91_365: Para,
+    pub yil_ustu: Para,
+    pub toplam: Para,
+}
+
+impl YasDilimleri {
+    /// Vadesinden `gun` gün geçmiş tutarı dilimine ekler (negatif gün: vadesi gelmemiş)
+    pub fn ekle(&mut self, gun: i64, tutar: Para) {
+        let dilim = match gun {
+            i64::MIN..=-1 => &mut self.vadesi_gelmemis,
+            0..=30 => &mut self.gun_0_30,
+            31..=90 => &mut self.gun_31_90,
+            91..=365 => &mut self.gun_91_365,
+            _ => &mut self.yil_ustu,
+        };
+        *dilim += tutar;
+        self.toplam += tutar;
+    }
+
+    fn topla(&mut self, diger: &YasDilimleri) {
+        self.vadesi_gelmemis += diger.vadesi_gelmemis;
+        self.gun_0_30 += diger.gun_0_30;
+        self.gun_31_90 += diger.gun_31_90;
+        self.gun_91_365 += diger.gun_91_365;
+        self.yil_ustu += diger.yil_ustu;
+        self.toplam += diger.toplam;
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YaslandirmaSatiri {
+    pub uye_id: String,
+    pub uye_no: String,
+    pub ad_soyad: String,
+    #[serde(flatten)]
+    pub dilimler: YasDilimleri,
+    pub en_eski_vade: String,
+    pub gecikme_gun: i64,
+    pub son_ihtar_seviyesi: Option<i32>,
+    pub son_ihtar_tarihi: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BorcYaslandirma {
+    pub tarih: String,
+    pub satirlar: Vec<YaslandirmaSatiri>,
+    pub toplam: YasDilimleri,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct IhtarSablonu {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub seviye: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslik: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub metin: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub cevap_suresi_gun: i32,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IhtarSablonuGirdi {
+    pub seviye: i32,
+    pub baslik: String,
+    pub metin: String,
+    pub cevap_suresi_gun: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct IhracKurali {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub asgari_gecikme_gun: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub gerekli_seviye: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub tuzuk_maddesi: Option<String>,
+}
+
+impl Default for IhracKurali {
+    fn default() -> Self {
+        Self { asgari_gecikme_gun: 365, gerekli_seviye: None, tuzuk_maddesi: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct Ihtar {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub seviye: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslik: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub metin: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub borc_tutari: Para,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub en_eski_vade: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub cevap_suresi_gun: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub bildirim_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub bildirim_kanali: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub sure_bitis_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub belge_yolu: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub olusturan: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct IhtarKalemi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub aidat_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub ay: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub vade_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub kalan: Para,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IhtarAdayi {
+    pub uye_id: String,
+    pub uye_no: String,
+    pub ad_soyad: String,
+    pub borc_tutari: Para,
+    pub en_eski_vade: String,
+    pub gecikme_gun: i64,
+    pub son_ihtar_seviyesi: Option<i32>,
+    /// Kesilebilecek seviye; engel varsa None
+    pub sonraki_seviye: Option<i32>,
+    pub engel: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IhracAdayi {
+    pub uye_id: String,
+    pub uye_no: String,
+    pub ad_soyad: String,
+    pub borc_tutari: Para,
+    pub en_eski_vade: String,
+    pub gecikme_gun: i64,
+    pub ihtar_id: String,
+    pub ihtar_seviyesi: i32,
+    pub bildirim_tarihi: String,
+    pub sure_bitis_tarihi: String,
+    pub tuzuk_maddesi: Option<String>,
+}
+
+/// Kademe hesabı için önceki ihtarın özeti
+#[derive(Debug, Clone, PartialEq)]
+pub struct OncekiIhtar {
+    pub seviye: i32,
+    pub tarih: NaiveDate,
+    pub sure_bitis: Option<NaiveDate>,
+}
+
+/// Açık aidat satırı (vade tarihiyle)
+#[derive(Debug, Clone)]
+struct AcikBorc {
+    aidat_id: String,
+    uye_id: String,
+    uye_no: String,
+    ad_soyad: String,
+    yil: i32,
+    ay: i32,
+    kalan: Para,
+    vade: NaiveDate,
+}
+
+/// Üyenin açık borçlarının özeti; borçlar vadeye göre sıralı gelir
+struct UyeBorcu {
+    uye_id: String,
+    uye_no: String,
+    ad_soyad: String,
+    borclar: Vec<AcikBorc>,
+}
+
+impl UyeBorcu {
+    fn toplam(&self) -> Para {
+        self.borclar.iter().map(|b| b.kalan).sum()
+    }
+
+    fn en_eski_vade(&self) -> NaiveDate {
+        self.borclar[0].vade
+    }
+}
+
+fn tarih_yaz(t: NaiveDate) -> String {
+    t.format("%Y-%m-%d").to_string()
+}
+
+fn tarih_oku(t: &str) -> Option<NaiveDate> {
+    t.get(..10).and_then(|g| NaiveDate::parse_from_str(g, "%Y-%m-%d").ok())
+}
+
+fn tr_tarih(t: NaiveDate) -> String {
+    t.format("%d.%m.%Y").to_string()
+}
+
+fn donem_adi(yil: i32, ay: i32) -> String {
+    if (1..=12).contains(&ay) {
+        format!("{}/{:02}", yil, ay)
+    } else {
+        yil.to_string()
+    }
+}
+
+/// Metindeki {anahtar} yer tutucularını doldurur; bilinmeyenler olduğu gibi kalır
+pub fn sablon_doldur(metin: &str, degerler: &[(&str, String)]) -> String {
+    degerler
+        .iter()
+        .fold(metin.to_string(), |m, (anahtar, deger)| m.replace(&format!("{{{}}}", anahtar), deger))
+}
+
+/// Derneğin şablonu yoksa kullanılan yerleşik üç kademe
+pub fn varsayilan_sablonlar() -> Vec<IhtarSablonu> {
+    let sablon = |seviye: i32, baslik: &str, metin: &str| IhtarSablonu {
+        id: None,
+        seviye,
+        baslik: baslik.to_string(),
+        metin: metin.to_string(),
+        cevap_suresi_gun: 30,
+        is_active: true,
+    };
+    vec![
+        sablon(
+            1,
+            "Aidat Borcu Hatırlatması",
+            "Sayın {ad_soyad} (Üye No: {uye_no}),\n\n{tarih} itibarıyla {en_eski_donem} döneminden başlayan \
+             {borc_tutari} TL ödenmemiş aidat borcunuz bulunmaktadır. Borcunuzu bu yazının tebliğinden \
+             itibaren {cevap_suresi_gun} gün içinde ödemenizi rica ederiz.\n\nSaygılarımızla,\n{dernek_adi}",
+        ),
+        sablon(
+            2,
+            "Aidat Borcu İhtarı",
+            "Sayın {ad_soyad} (Üye No: {uye_no}),\n\nDaha önce hatırlatılmasına rağmen {en_eski_donem} \
+             döneminden başlayan {borc_tutari} TL aidat borcunuz ödenmemiştir. Borcunuzu bu ihtarın \
+             tebliğinden itibaren {cevap_suresi_gun} gün içinde ödemeniz gerekmektedir.\n\n{dernek_adi} \
+             Yönetim Kurulu",
+        ),
+        sablon(
+            3,
+            "Son İhtar",
+            "Sayın {ad_soyad} (Üye No: {uye_no}),\n\nYapılan ihtarlara rağmen {en_eski_donem} döneminden \
+             başlayan {borc_tutari} TL aidat borcunuz ödenmemiştir. Bu son ihtarın tebliğinden itibaren \
+             {cevap_suresi_gun} gün içinde borcunuzu ödemediğiniz takdirde tüzüğümüzün ilgili hükmü \
+             gereğince üyelikten çıkarılmanız yönetim kurulunun gündemine alınacaktır.\n\n{dernek_adi} \
+             Yönetim Kurulu",
+        ),
+    ]
+}
+
+/// Bu borç dönemindeki ihtarlara göre kesilebilecek seviye ya da engelin açıklaması.
+/// `seviyeler`: etkin şablon seviyeleri (artan sırada).
+pub fn sonraki_seviye(onceki: &[OncekiIhtar], seviyeler: &[i32], tarih: NaiveDate) -> Result<i32, String> {
+    let Some(ilk) = seviyeler.first() else {
+        return Err("Etkin ihtar şablonu yok".to_string());
+    };
+    let Some(son) = onceki.iter().max_by_key(|i| (i.seviye, i.tarih)) else {
+        return Ok(*ilk);
+    };
+    let Some(sure_bitis) = son.sure_bitis else {
+        return Err(format!("{}. seviye ihtar henüz tebliğ edilmedi", son.seviye));
+    };
+    if tarih <= sure_bitis {
+        return Err(format!("{}. seviye ihtarın cevap süresi {} tarihinde doluyor", son.seviye, tr_tarih(sure_bitis)));
+    }
+    seviyeler
+        .iter()
+        .copied()
+        .find(|s| *s > son.seviye)
+        .ok_or_else(|| "Son ihtar seviyesine ulaşıldı".to_string())
+}
+
+fn acik_borclar(conn: &mut SqliteConnection, tenant_id: &str, uye_id: Option<&str>) -> Result<Vec<UyeBorcu>, String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        aidat_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_no: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        kalan: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+        son_odeme_gunu: Option<i32>,
+    }
+
+    let satirlar: Vec<Row> = diesel::sql_query(
+        "SELECT a.id AS aidat_id, a.uye_id, u.uye_no, u.ad_soyad, a.yil, a.ay,
+                COALESCE(a.kalan, a.tutar - COALESCE(a.odenen, 0)) AS kalan,
+                t.son_odeme_gunu
+         FROM aidat_takip a
+         JOIN uyeler u ON u.id = a.uye_id AND u.tenant_id = a.tenant_id
+         LEFT JOIN aidat_tanimlari t ON t.id = (
+              SELECT t2.id FROM aidat_tanimlari t2
+              WHERE t2.tenant_id = a.tenant_id AND t2.yil = a.yil AND t2.is_active = 1
+                AND COALESCE(t2.uye_turu, 'Asil') = COALESCE(u.uyelik_tipi, 'Asil')
+              ORDER BY t2.updated_at DESC LIMIT 1)
+         WHERE a.tenant_id = ?1
+           AND (?2 IS NULL OR a.uye_id = ?2)
+           AND (a.is_deleted IS NULL OR a.is_deleted = 0)
+           AND (u.is_deleted IS NULL OR u.is_deleted = 0)
+           AND a.durum != 'iptal'
+           AND COALESCE(a.kalan, a.tutar - COALESCE(a.odenen, 0)) > 0.004
+         ORDER BY u.uye_no, a.uye_id, a.yil, a.ay",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let mut uyeler: Vec<UyeBorcu> = Vec::new();
+    for s in satirlar {
+        let Some(vade) = vade_tarihi(s.yil, s.ay, s.son_odeme_gunu) else { continue };
+        let borc = AcikBorc {
+            aidat_id: s.aidat_id,
+            uye_id: s.uye_id,
+            uye_no: s.uye_no,
+            ad_soyad: s.ad_soyad,
+            yil: s.yil,
+            ay: s.ay,
+            kalan: s.kalan,
+            vade,
+        };
+        match uyeler.last_mut() {
+            Some(u) if u.uye_id == borc.uye_id => u.borclar.push(borc),
+            _ => uyeler.push(UyeBorcu {
+                uye_id: borc.uye_id.clone(),
+                uye_no: borc.uye_no.clone(),
+                ad_soyad: borc.ad_soyad.clone(),
+                borclar: vec![borc],
+            }),
+        }
+    }
+    for u in &mut uyeler {
+        u.borclar.sort_by_key(|b| b.vade);
+    }
+    Ok(uyeler)
+}
+
+fn onceki_ihtarlar(conn: &mut SqliteConnection, tenant_id: &str, uye_id: &str, baslangic: NaiveDate) -> Result<Vec<OncekiIhtar>, String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        seviye: i32,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        sure_bitis_tarihi: Option<String>,
+    }
+
+    let satirlar: Vec<Row> = diesel::sql_query(
+        "SELECT seviye, tarih, sure_bitis_tarihi FROM ihtarlar
+         WHERE tenant_id = ?1 AND uye_id = ?2 AND tarih >= ?3
+         ORDER BY tarih, seviye",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .bind::<diesel::sql_types::Text, _>(tarih_yaz(baslangic))
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(satirlar
+        .into_iter()
+        .filter_map(|r| {
+            Some(OncekiIhtar {
+                seviye: r.seviye,
+                tarih: tarih_oku(&r.tarih)?,
+                sure_bitis: r.sure_bitis_tarihi.as_deref().and_then(tarih_oku),
+            })
+        })
+        .collect())
+}
+
+fn aktif_taksit_plani_var(conn: &mut SqliteConnection, tenant_id: &str, uye_id: &str) -> Result<bool, String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    let r: Row = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM taksit_planlari WHERE tenant_id = ?1 AND uye_id = ?2 AND durum = 'aktif'",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(r.sayi > 0)
+}
+
+/// Tarih itibarıyla üye bazında borç yaşlandırma
+pub fn yaslandirma(conn: &mut SqliteConnection, tenant_id: &str, tarih: NaiveDate) -> Result<BorcYaslandirma, String> {
+    let mut rapor = BorcYaslandirma { tarih: tarih_yaz(tarih), satirlar: Vec::new(), toplam: YasDilimleri::default() };
+    for uye in acik_borclar(conn, tenant_id, None)? {
+        let mut dilimler = YasDilimleri::default();
+        for b in &uye.borclar {
+            dilimler.ekle((tarih - b.vade).num_days(), b.kalan);
+        }
+        rapor.toplam.topla(&dilimler);
+
+        let onceki = onceki_ihtarlar(conn, tenant_id, &uye.uye_id, uye.en_eski_vade())?;
+        let son = onceki.iter().max_by_key(|i| (i.seviye, i.tarih));
+        rapor.satirlar.push(YaslandirmaSatiri {
+            uye_id: uye.uye_id.clone(),
+            uye_no: uye.uye_no.clone(),
+            ad_soyad: uye.ad_soyad.clone(),
+            dilimler,
+            en_eski_vade: tarih_yaz(uye.en_eski_vade()),
+            gecikme_gun: (tarih - uye.en_eski_vade()).num_days().max(0),
+            son_ihtar_seviyesi: son.map(|i| i.seviye),
+            son_ihtar_tarihi: son.map(|i| tarih_yaz(i.tarih)),
+        });
+    }
+    // En eski borcu olan üye başta
+    rapor.satirlar.sort_by(|a, b| b.gecikme_gun.cmp(&a.gecikme_gun).then_with(|| a.uye_no.cmp(&b.uye_no)));
+    Ok(rapor)
+}
+
+/// Vadesi `tarih`ten önce dolmuş açık aidat borcu, üye bazında (genel kurul hazirunu)
+pub fn vadesi_gecmis_borclar(conn: &mut SqliteConnection, tenant_id: &str, tarih: NaiveDate) -> Result<HashMap<String, Para>, String> {
+    let mut borclar = HashMap::new();
+    for uye in acik_borclar(conn, tenant_id, None)? {
+        let gecmis: Para = uye.borclar.iter().filter(|b| b.vade < tarih).map(|b| b.kalan).sum();
+        if !gecmis.is_zero() {
+            borclar.insert(uye.uye_id, gecmis);
+        }
+    }
+    Ok(borclar)
+}
+
+pub fn sablonlari_getir(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<IhtarSablonu>, String> {
+    diesel::sql_query(
+        "SELECT id, seviye, baslik, metin, cevap_suresi_gun, is_active FROM ihtar_sablonlari
+         WHERE tenant_id = ?1 ORDER BY seviye",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// İhtar kesiminde kullanılan şablonlar: derneğin etkin şablonları, hiç şablonu yoksa yerleşikler
+pub fn etkin_sablonlar(conn: &mut SqliteConnection, tenant_id: &str) -> Result<Vec<IhtarSablonu>, String> {
+    let sablonlar = sablonlari_getir(conn, tenant_id)?;
+    if sablonlar.is_empty() {
+        return Ok(varsayilan_sablonlar());
+    }
+    Ok(sablonlar.into_iter().filter(|s| s.is_active).collect())
+}
+
+/// Seviyeye göre şablonu ekler ya da günceller
+pub fn sablon_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    girdi: &IhtarSablonuGirdi,
+) -> Result<String, String> {
+    if girdi.seviye < 1 {
+        return Err("İhtar seviyesi 1 veya büyük olmalıdır".to_string());
+    }
+    if girdi.baslik.trim().is_empty() || girdi.metin.trim().is_empty() {
+        return Err("Şablon başlığı ve metni boş olamaz".to_string());
+    }
+    let cevap_suresi = girdi.cevap_suresi_gun.unwrap_or(30);
+    if cevap_suresi < 1 {
+        return Err("Cevap süresi en az 1 gün olmalıdır".to_string());
+    }
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mevcut = sablonlari_getir(conn, tenant_id)?
+        .into_iter()
+        .find(|s| s.seviye == girdi.seviye)
+        .and_then(|s| s.id);
+
+    let id = match mevcut {
+        Some(id) => {
+            denetim.izle(conn, tenant_id, "ihtar_sablonlari", &id)?;
+            diesel::sql_query(
+                "UPDATE ihtar_sablonlari SET baslik = ?1, metin = ?2, cevap_suresi_gun = ?3, is_active = ?4, updated_at = ?5
+                 WHERE id = ?6 AND tenant_id = ?7",
+            )
+            .bind::<diesel::sql_types::Text, _>(girdi.baslik.trim())
+            .bind::<diesel::sql_types::Text, _>(girdi.metin.trim())
+            .bind::<diesel::sql_types::Integer, _>(cevap_suresi)
+            .bind::<diesel::sql_types::Bool, _>(girdi.is_active.unwrap_or(true))
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            let id = Uuid::new_v4().to_string();
+            diesel::sql_query(
+                "INSERT INTO ihtar_sablonlari (id, tenant_id, seviye, baslik, metin, cevap_suresi_gun, is_active, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Integer, _>(girdi.seviye)
+            .bind::<diesel::sql_types::Text, _>(girdi.baslik.trim())
+            .bind::<diesel::sql_types::Text, _>(girdi.metin.trim())
+            .bind::<diesel::sql_types::Integer, _>(cevap_suresi)
+            .bind::<diesel::sql_types::Bool, _>(girdi.is_active.unwrap_or(true))
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            denetim.yeni_kayit(tenant_id, "ihtar_sablonlari", &id);
+            id
+        }
+    };
+    Ok(id)
+}
+
+pub fn kurali_getir(conn: &mut SqliteConnection, tenant_id: &str) -> Result<IhracKurali, String> {
+    Ok(diesel::sql_query(
+        "SELECT asgari_gecikme_gun, gerekli_seviye, tuzuk_maddesi FROM ihrac_kurallari WHERE tenant_id = ?1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result::<IhracKurali>(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .unwrap_or_default())
+}
+
+pub fn kurali_kaydet(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, kural: &IhracKurali) -> Result<(), String> {
+    if kural.asgari_gecikme_gun < 1 {
+        return Err("Asgari gecikme süresi en az 1 gün olmalıdır".to_string());
+    }
+    if kural.gerekli_seviye.is_some_and(|s| s < 1) {
+        return Err("Gerekli ihtar seviyesi 1 veya büyük olmalıdır".to_string());
+    }
+
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let mevcut = diesel::sql_query("SELECT id FROM ihrac_kurallari WHERE tenant_id = ?1")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result::<IdRow>(conn)
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let id = match mevcut {
+        Some(r) => {
+            denetim.izle(conn, tenant_id, "ihrac_kurallari", &r.id)?;
+            r.id
+        }
+        None => {
+            let id = Uuid::new_v4().to_string();
+            denetim.yeni_kayit(tenant_id, "ihrac_kurallari", &id);
+            id
+        }
+    };
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO ihrac_kurallari (id, tenant_id, asgari_gecikme_gun, gerekli_seviye, tuzuk_maddesi, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(tenant_id) DO UPDATE SET asgari_gecikme_gun = excluded.asgari_gecikme_gun,
+             gerekli_seviye = excluded.gerekli_seviye, tuzuk_maddesi = excluded.tuzuk_maddesi,
+             updated_at = excluded.updated_at",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(kural.asgari_gecikme_gun)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(kural.gerekli_seviye)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(
+        kural.tuzuk_maddesi.as_deref().map(str::trim).filter(|s| !s.is_empty()),
+    )
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Vadesi geçmiş borcu olan üyeler ve kesilebilecek ihtar seviyesi
+pub fn ihtar_adaylari(conn: &mut SqliteConnection, tenant_id: &str, tarih: NaiveDate) -> Result<Vec<IhtarAdayi>, String> {
+    let seviyeler: Vec<i32> = etkin_sablonlar(conn, tenant_id)?.iter().map(|s| s.seviye).collect();
+    let mut adaylar = Vec::new();
+    for uye in acik_borclar(conn, tenant_id, None)? {
+        let gecikmis: Vec<&AcikBorc> = uye.borclar.iter().filter(|b| b.vade < tarih).collect();
+        if gecikmis.is_empty() {
+            continue;
+        }
+        let onceki = onceki_ihtarlar(conn, tenant_id, &uye.uye_id, uye.en_eski_vade())?;
+        let sonraki = if aktif_taksit_plani_var(conn, tenant_id, &uye.uye_id)? {
+            Err("Aktif taksit planı var".to_string())
+        } else {
+            sonraki_seviye(&onceki, &seviyeler, tarih)
+        };
+        adaylar.push(IhtarAdayi {
+            uye_id: uye.uye_id.clone(),
+            uye_no: uye.uye_no.clone(),
+            ad_soyad: uye.ad_soyad.clone(),
+            borc_tutari: gecikmis.iter().map(|b| b.kalan).sum(),
+            en_eski_vade: tarih_yaz(uye.en_eski_vade()),
+            gecikme_gun: (tarih - uye.en_eski_vade()).num_days(),
+            son_ihtar_seviyesi: onceki.iter().map(|i| i.seviye).max(),
+            sonraki_seviye: sonraki.as_ref().ok().copied(),
+            engel: sonraki.err(),
+        });
+    }
+    adaylar.sort_by(|a, b| b.gecikme_gun.cmp(&a.gecikme_gun).then_with(|| a.uye_no.cmp(&b.uye_no)));
+    Ok(adaylar)
+}
+
+fn dernek_adi(conn: &mut SqliteConnection, tenant_id: &str) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        name: String,
+    }
+
+    diesel::sql_query("SELECT name FROM tenants WHERE id = ?1")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result::<Row>(conn)
+        .map(|r| r.name)
+        .map_err(|_| "Dernek bulunamadı".to_string())
+}
+
+/// Üyelere sıradaki seviyeden ihtar keser; engeli olan üye varsa hiçbiri kesilmez
+pub fn ihtar_olustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_idleri: &[String],
+    tarih: NaiveDate,
+    olusturan: Option<&str>,
+) -> Result<Vec<String>, String> {
+    if uye_idleri.is_empty() {
+        return Err("İhtar kesilecek üye seçilmedi".to_string());
+    }
+    let sablonlar = etkin_sablonlar(conn, tenant_id)?;
+    let seviyeler: Vec<i32> = sablonlar.iter().map(|s| s.seviye).collect();
+    let dernek = dernek_adi(conn, tenant_id)?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let mut idler = Vec::new();
+    for uye_id in uye_idleri {
+        let Some(uye) = acik_borclar(conn, tenant_id, Some(uye_id))?.pop() else {
+            return Err(format!("Üyenin açık aidat borcu yok: {}", uye_id));
+        };
+        let gecikmis: Vec<&AcikBorc> = uye.borclar.iter().filter(|b| b.vade < tarih).collect();
+        if gecikmis.is_empty() {
+            return Err(format!("{} için vadesi geçmiş borç yok", uye.ad_soyad));
+        }
+        if aktif_taksit_plani_var(conn, tenant_id, uye_id)? {
+            return Err(format!("{}: aktif taksit planı var", uye.ad_soyad));
+        }
+        let onceki = onceki_ihtarlar(conn, tenant_id, uye_id, uye.en_eski_vade())?;
+        let seviye = sonraki_seviye(&onceki, &seviyeler, tarih).map_err(|e| format!("{}: {}", uye.ad_soyad, e))?;
+        let sablon = sablonlar.iter().find(|s| s.seviye == seviye).ok_or("İhtar şablonu bulunamadı")?;
+
+        let borc: Para = gecikmis.iter().map(|b| b.kalan).sum();
+        let degerler = [
+            ("ad_soyad", uye.ad_soyad.clone()),
+            ("uye_no", uye.uye_no.clone()),
+            ("borc_tutari", borc.tr_format()),
+            ("en_eski_donem", donem_adi(gecikmis[0].yil, gecikmis[0].ay)),
+            ("tarih", tr_tarih(tarih)),
+            ("cevap_suresi_gun", sablon.cevap_suresi_gun.to_string()),
+            ("dernek_adi", dernek.clone()),
+            ("seviye", seviye.to_string()),
+        ];
+
+        let id = Uuid::new_v4().to_string();
+        diesel::sql_query(
+            "INSERT INTO ihtarlar (id, tenant_id, uye_id, seviye, sablon_id, tarih, baslik, metin, borc_tutari,
+                                   en_eski_vade, cevap_suresi_gun, olusturan, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?13)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Integer, _>(seviye)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(sablon.id.as_deref())
+        .bind::<diesel::sql_types::Text, _>(tarih_yaz(tarih))
+        .bind::<diesel::sql_types::Text, _>(sablon_doldur(&sablon.baslik, &degerler))
+        .bind::<diesel::sql_types::Text, _>(sablon_doldur(&sablon.metin, &degerler))
+        .bind::<diesel::sql_types::Double, _>(borc)
+        .bind::<diesel::sql_types::Text, _>(tarih_yaz(gecikmis[0].vade))
+        .bind::<diesel::sql_types::Integer, _>(sablon.cevap_suresi_gun)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(olusturan)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        denetim.yeni_kayit(tenant_id, "ihtarlar", &id);
+
+        for b in gecikmis {
+            diesel::sql_query(
+                "INSERT INTO ihtar_kalemleri (id, tenant_id, ihtar_id, aidat_id, yil, ay, vade_tarihi, kalan)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )
+            .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&b.aidat_id)
+            .bind::<diesel::sql_types::Integer, _>(b.yil)
+            .bind::<diesel::sql_types::Integer, _>(b.ay)
+            .bind::<diesel::sql_types::Text, _>(tarih_yaz(b.vade))
+            .bind::<diesel::sql_types::Double, _>(b.kalan)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+        }
+        idler.push(id);
+    }
+    Ok(idler)
+}
+
+const IHTAR_SECIMI: &str = "SELECT i.id, i.uye_id, u.uye_no, u.ad_soyad, i.seviye, i.tarih, i.baslik, i.metin,
+            i.borc_tutari, i.en_eski_vade, i.cevap_suresi_gun, i.bildirim_tarihi, i.bildirim_kanali,
+            i.sure_bitis_tarihi, i.belge_yolu, i.olusturan
+     FROM ihtarlar i
+     LEFT JOIN uyeler u ON u.id = i.uye_id AND u.tenant_id = i.tenant_id";
+
+pub fn ihtar_getir(conn: &mut SqliteConnection, tenant_id: &str, ihtar_id: &str) -> Result<Ihtar, String> {
+    diesel::sql_query(format!("{} WHERE i.tenant_id = ?1 AND i.id = ?2", IHTAR_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(ihtar_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "İhtar bulunamadı".to_string())
+}
+
+/// İhtar geçmişi (yeniden eskiye); üye verilirse yalnızca o üyenin
+pub fn ihtarlari_getir(conn: &mut SqliteConnection, tenant_id: &str, uye_id: Option<&str>) -> Result<Vec<Ihtar>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE i.tenant_id = ?1 AND (?2 IS NULL OR i.uye_id = ?2) ORDER BY i.tarih DESC, i.seviye DESC",
+        IHTAR_SECIMI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn ihtar_kalemleri(conn: &mut SqliteConnection, tenant_id: &str, ihtar_id: &str) -> Result<Vec<IhtarKalemi>, String> {
+    diesel::sql_query(
+        "SELECT aidat_id, yil, ay, vade_tarihi, kalan FROM ihtar_kalemleri
+         WHERE tenant_id = ?1 AND ihtar_id = ?2 ORDER BY vade_tarihi",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(ihtar_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Tebliğ kaydı: cevap süresi tebliğ tarihinden başlar
+pub fn bildirim_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    ihtar_id: &str,
+    bildirim_tarihi: NaiveDate,
+    kanal: &str,
+) -> Result<(), String> {
+    if !BILDIRIM_KANALLARI.contains(&kanal) {
+        return Err(format!("Geçersiz bildirim kanalı: {}", kanal));
+    }
+    let ihtar = ihtar_getir(conn, tenant_id, ihtar_id)?;
+    if tarih_oku(&ihtar.tarih).is_some_and(|t| bildirim_tarihi < t) {
+        return Err("Tebliğ tarihi ihtar tarihinden önce olamaz".to_string());
+    }
+    let sure_bitis = bildirim_tarihi + Duration::days(ihtar.cevap_suresi_gun as i64);
+
+    denetim.izle(conn, tenant_id, "ihtarlar", ihtar_id)?;
+    diesel::sql_query(
+        "UPDATE ihtarlar SET bildirim_tarihi = ?1, bildirim_kanali = ?2, sure_bitis_tarihi = ?3, updated_at = ?4
+         WHERE id = ?5 AND tenant_id = ?6",
+    )
+    .bind::<diesel::sql_types::Text, _>(tarih_yaz(bildirim_tarihi))
+    .bind::<diesel::sql_types::Text, _>(kanal)
+    .bind::<diesel::sql_types::Text, _>(tarih_yaz(sure_bitis))
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(ihtar_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn belge_yolu_kaydet(conn: &mut SqliteConnection, tenant_id: &str, ihtar_id: &str, yol: &str) -> Result<(), String> {
+    diesel::sql_query("UPDATE ihtarlar SET belge_yolu = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
+        .bind::<diesel::sql_types::Text, _>(yol)
+        .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(ihtar_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Tüzüğün ödenmeyen aidat hükmüne göre ihraç adayları
+pub fn ihrac_adaylari(conn: &mut SqliteConnection, tenant_id: &str, tarih: NaiveDate) -> Result<Vec<IhracAdayi>, String> {
+    let kural = kurali_getir(conn, tenant_id)?;
+    let gerekli_seviye = match kural.gerekli_seviye {
+        Some(s) => s,
+        None => match etkin_sablonlar(conn, tenant_id)?.iter().map(|s| s.seviye).max() {
+            Some(s) => s,
+            None => return Ok(Vec::new()),
+        },
+    };
+
+    let mut adaylar = Vec::new();
+    for uye in acik_borclar(conn, tenant_id, None)? {
+        let gecikme_gun = (tarih - uye.en_eski_vade()).num_days();
+        if gecikme_gun < kural.asgari_gecikme_gun as i64 {
+            continue;
+        }
+        let ihtarlar = ihtarlari_getir(conn, tenant_id, Some(&uye.uye_id))?;
+        let en_eski = tarih_yaz(uye.en_eski_vade());
+        let dolan = ihtarlar.into_iter().find(|i| {
+            i.tarih >= en_eski
+                && i.seviye >= gerekli_seviye
+                && i.sure_bitis_tarihi.as_deref().and_then(tarih_oku).is_some_and(|s| s < tarih)
+        });
+        let Some(ihtar) = dolan else { continue };
+        adaylar.push(IhracAdayi {
+            uye_id: uye.uye_id.clone(),
+            uye_no: uye.uye_no.clone(),
+            ad_soyad: uye.ad_soyad.clone(),
+            borc_tutari: uye.toplam(),
+            en_eski_vade: en_eski,
+            gecikme_gun,
+            ihtar_id: ihtar.id,
+            ihtar_seviyesi: ihtar.seviye,
+            bildirim_tarihi: ihtar.bildirim_tarihi.unwrap_or_default(),
+            sure_bitis_tarihi: ihtar.sure_bitis_tarihi.unwrap_or_default(),
+            tuzuk_maddesi: kural.tuzuk_maddesi.clone(),
+        });
+    }
+    adaylar.sort_by(|a, b| b.gecikme_gun.cmp(&a.gecikme_gun).then_with(|| a.uye_no.cmp(&b.uye_no)));
+    Ok(adaylar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "001", "Ali Veli");
+        test_db::uye(&mut conn, "t1", "u2", "002", "Ayşe Kaya");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_tanimlari (id, tenant_id, yil, uye_turu, son_odeme_gunu, is_active, updated_at)
+             VALUES ('d25', 't1', 2025, 'Asil', 15, 1, '2025-01-01')",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan) VALUES
+                ('a1', 't1', 'u1', 2025, 1, 100.0, 0, 100.0),
+                ('a2', 't1', 'u1', 2026, 1, 120.0, 20.0, NULL),
+                ('b1', 't1', 'u2', 2026, 9, 50.0, 0, 50.0)",
+        );
+        conn
+    }
+
+    fn tarih(y: i32, a: u32, g: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, a, g).unwrap()
+    }
+
+    #[test]
+    fn dilim_sinirlari() {
+        let mut d = YasDilimleri::default();
+        for (gun, tl) in [(-1, 1.0), (0, 2.0), (30, 4.0), (31, 8.0), (90, 16.0), (91, 32.0), (365, 64.0), (366, 128.0)] {
+            d.ekle(gun, Para::from_tl(tl));
+        }
+        assert_eq!(d.vadesi_gelmemis, Para::from_tl(1.0));
+        assert_eq!(d.gun_0_30, Para::from_tl(6.0));
+        assert_eq!(d.gun_31_90, Para::from_tl(24.0));
+        assert_eq!(d.gun_91_365, Para::from_tl(96.0));
+        assert_eq!(d.yil_ustu, Para::from_tl(128.0));
+        assert_eq!(d.toplam, Para::from_tl(255.0));
+    }
+
+    #[test]
+    fn kademe_cevap_suresini_bekler() {
+        let seviyeler = [1, 2, 3];
+        let bugun = tarih(2026, 5, 1);
+        assert_eq!(sonraki_seviye(&[], &seviyeler, bugun), Ok(1));
+        assert!(sonraki_seviye(&[], &[], bugun).is_err());
+
+        let mut onceki = vec![OncekiIhtar { seviye: 1, tarih: tarih(2026, 3, 1), sure_bitis: None }];
+        assert!(sonraki_seviye(&onceki, &seviyeler, bugun).unwrap_err().contains("tebliğ"));
+        onceki[0].sure_bitis = Some(bugun);
+        assert!(sonraki_seviye(&onceki, &seviyeler, bugun).unwrap_err().contains("01.05.2026"));
+        assert_eq!(sonraki_seviye(&onceki, &seviyeler, tarih(2026, 5, 2)), Ok(2));
+        // Pasif seviye atlanır
+        assert_eq!(sonraki_seviye(&onceki, &[1, 3], tarih(2026, 5, 2)), Ok(3));
+
+        onceki.push(OncekiIhtar { seviye: 3, tarih: tarih(2026, 4, 1), sure_bitis: Some(tarih(2026, 4, 2)) });
+        assert!(sonraki_seviye(&onceki, &seviyeler, bugun).unwrap_err().contains("Son"));
+    }
+
+    #[test]
+    fn sablon_yer_tutuculari_doldurulur() {
+        let metin = sablon_doldur("{ad_soyad}: {borc_tutari} TL {bilinmeyen}", &[
+            ("ad_soyad", "Ali".to_string()),
+            ("borc_tutari", "1.250,00".to_string()),
+        ]);
+        assert_eq!(metin, "Ali: 1.250,00 TL {bilinmeyen}");
+    }
+
+    #[test]
+    fn yaslandirma_ihtar_ve_ihrac_akisi() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut denetim = Denetim::yeni(None, None, "test");
+
+        // a1 vadesi 2025-01-15 (tanımdan), a2 vadesi 2026-01-31 (tanım yok: ay sonu), b1 2026-09-30
+        let rapor = yaslandirma(c, "t1", tarih(2026, 3, 1)).unwrap();
+        assert_eq!(rapor.satirlar[0].uye_id, "u1");
+        assert_eq!(rapor.satirlar[0].dilimler.yil_ustu, Para::from_tl(100.0));
+        assert_eq!(rapor.satirlar[0].dilimler.gun_0_30, Para::from_tl(100.0));
+        assert_eq!(rapor.satirlar[1].dilimler.vadesi_gelmemis, Para::from_tl(50.0));
+        assert_eq!(rapor.toplam.toplam, Para::from_tl(250.0));
+
+        // Vadesi geçmemiş borca ihtar kesilmez
+        let adaylar = ihtar_adaylari(c, "t1", tarih(2026, 3, 1)).unwrap();
+        assert_eq!(adaylar.len(), 1);
+        assert_eq!(adaylar[0].sonraki_seviye, Some(1));
+        assert!(ihtar_olustur(c, &mut denetim, "t1", &["u2".to_string()], tarih(2026, 3, 1), None).is_err());
+
+        let ilk = ihtar_olustur(c, &mut denetim, "t1", &["u1".to_string()], tarih(2026, 3, 1), None).unwrap();
+        let ihtar = ihtar_getir(c, "t1", &ilk[0]).unwrap();
+        assert_eq!(ihtar.borc_tutari, Para::from_tl(200.0));
+        assert!(ihtar.metin.contains("Ali Veli") && ihtar.metin.contains("2025/01") && ihtar.metin.contains("200,00"));
+        assert_eq!(ihtar_kalemleri(c, "t1", &ilk[0]).unwrap().len(), 2);
+        // Tebliğ edilmeden ikinci seviye kesilemez
+        assert!(ihtar_olustur(c, &mut denetim, "t1", &["u1".to_string()], tarih(2026, 3, 5), None).is_err());
+
+        assert!(bildirim_kaydet(c, &mut denetim, "t1", &ilk[0], tarih(2026, 3, 2), "guvercin").is_err());
+        bildirim_kaydet(c, &mut denetim, "t1", &ilk[0], tarih(2026, 3, 2), "iadeli_taahhutlu").unwrap();
+        assert_eq!(ihtar_getir(c, "t1", &ilk[0]).unwrap().sure_bitis_tarihi.as_deref(), Some("2026-04-01"));
+
+        // Kural: 1 yıl gecikme ve 2. seviye ihtar
+        kurali_kaydet(c, &mut denetim, "t1", &IhracKurali {
+            asgari_gecikme_gun: 365,
+            gerekli_seviye: Some(2),
+            tuzuk_maddesi: Some("md. 9".to_string()),
+        })
+        .unwrap();
+        let ikinci = ihtar_olustur(c, &mut denetim, "t1", &["u1".to_string()], tarih(2026, 4, 2), None).unwrap();
+        assert_eq!(ihtar_getir(c, "t1", &ikinci[0]).unwrap().seviye, 2);
+        bildirim_kaydet(c, &mut denetim, "t1", &ikinci[0], tarih(2026, 4, 3), "elden").unwrap();
+        assert!(ihrac_adaylari(c, "t1", tarih(2026, 5, 3)).unwrap().is_empty());
+        let ihrac = ihrac_adaylari(c, "t1", tarih(2026, 5, 4)).unwrap();
+        assert_eq!(ihrac.len(), 1);
+        assert_eq!(ihrac[0].tuzuk_maddesi.as_deref(), Some("md. 9"));
+
+        // Borç kapanıp yeniden oluşursa kademe baştan başlar
+        diesel::sql_query("UPDATE aidat_takip SET kalan = 0, odenen = tutar, durum = 'odendi' WHERE uye_id = 'u1'")
+            .execute(c)
+            .unwrap();
+        diesel::sql_query(
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, kalan) VALUES ('a3', 't1', 'u1', 2026, 6, 80.0, 80.0)",
+        )
+        .execute(c)
+        .unwrap();
+        assert!(ihrac_adaylari(c, "t1", tarih(2026, 8, 1)).unwrap().is_empty());
+        let adaylar = ihtar_adaylari(c, "t1", tarih(2026, 8, 1)).unwrap();
+        assert_eq!(adaylar[0].sonraki_seviye, Some(1));
+        assert_eq!(adaylar[0].son_ihtar_seviyesi, None);
+    }
+}
@@ -0,0 +1,824 @@
+// Karar defteri: organ kararlarının sıra numaralı kaydı.
+//
+// Karar numarası organ ve karar tarihinin yılı içinde 1'den başlar ve boşluksuz artar;
+// defter kronolojik olduğundan yeni karar o sıradaki son karardan önceki bir tarihe
+// yazılamaz. Numara verildikten sonra değişmez: karar silinmez, gerekçesiyle iptal
+// edilir. Oy sayıları katılımcı listesi verildiyse katılımcıların oylarından çıkarılır
+// (genel kurul gibi kalabalık toplantılarda doğrudan sayı girilebilir); karar katılanların
+// salt çoğunluğuyla alınır (bkz. genel_kurul::karar_sonucu). Üyeleri organ_gorevleri'nde
+// kayıtlı kurulların kararlarında oy kullananlar kurulun asil üyesi olmalı ve toplantı yeter
+// sayısını sağlamalıdır. Kabul edilmiş karar, dayanak olduğu gelir, gider, demirbaş ve üye
+// kayıtlarına bağlanabilir.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::genel_kurul;
+use crate::db::organ_gorevi;
+
+pub const ORGANLAR: [&str; 3] = ["yonetim_kurulu", "denetim_kurulu", "genel_kurul"];
+pub const BAGLANTI_TABLOLARI: [&str; 4] = ["gelirler", "giderler", "demirbaslar", "uyeler"];
+pub const OYLAR: [&str; 4] = ["kabul", "ret", "cekimser", "katilmadi"];
+
+/// (kabul, ret, çekimser)
+pub type OySayilari = (i32, i32, i32);
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct Karar {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub organ: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub karar_no: i32,
+    /// "2026/14"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub numara: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub karar_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub toplanti_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub toplanti_basligi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub konu: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub metin: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub kabul: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub ret: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub cekimser: i32,
+    /// "kabul" | "ret"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub sonuc: String,
+    /// "gecerli" | "iptal"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub iptal_nedeni: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct KararKatilimcisi {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_id: Option<String>,
+    /// Üye verildiyse boş bırakılabilir, üye kaydından alınır
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[serde(default)]
+    pub ad_soyad: String,
+    /// "kabul" | "ret" | "cekimser" | "katilmadi"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub oy: String,
+    /// Yalnızca ret oyunda
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub muhalefet_serhi: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct KararBaglantisi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub karar_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tablo: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kayit_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    /// Bağlı kaydın kısa tanımı (tarih, açıklama, tutar ya da ad)
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kayit_ozeti: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KararDetay {
+    #[serde(flatten)]
+    pub karar: Karar,
+    pub katilimcilar: Vec<KararKatilimcisi>,
+    pub baglantilar: Vec<KararBaglantisi>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KararGirdi {
+    pub organ: String,
+    /// Verilmezse toplantı tarihi
+    pub karar_tarihi: Option<String>,
+    pub toplanti_id: Option<String>,
+    pub konu: String,
+    pub metin: String,
+    #[serde(default)]
+    pub katilimcilar: Vec<KararKatilimcisi>,
+    /// Katılımcı listesi yoksa oy sayıları
+    pub kabul: Option<i32>,
+    pub ret: Option<i32>,
+    pub cekimser: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BaglantiGirdi {
+    pub tablo: String,
+    pub kayit_id: String,
+    pub aciklama: Option<String>,
+}
+
+fn bos_degilse(s: &Option<String>) -> Option<&str> {
+    s.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Katılımcı oylarından oy sayıları
+pub fn oy_sayilari(katilimcilar: &[KararKatilimcisi]) -> OySayilari {
+    let say = |oy: &str| katilimcilar.iter().filter(|k| k.oy == oy).count() as i32;
+    (say("kabul"), say("ret"), say("cekimser"))
+}
+
+const KARAR_SECIMI: &str = "SELECT k.id, k.organ, k.yil, k.karar_no, k.yil || '/' || k.karar_no AS numara, k.karar_tarihi,
+        k.toplanti_id, t.baslik AS toplanti_basligi, k.konu, k.metin, k.kabul, k.ret, k.cekimser, k.sonuc, k.durum,
+        k.iptal_nedeni, k.created_at, k.updated_at
+     FROM kararlar k
+     LEFT JOIN toplantilar t ON t.id = k.toplanti_id AND t.tenant_id = k.tenant_id";
+
+pub fn kararlari_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    organ: Option<&str>,
+    yil: Option<i32>,
+) -> Result<Vec<Karar>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE k.tenant_id = ?1 AND (?2 IS NULL OR k.organ = ?2) AND (?3 IS NULL OR k.yil = ?3)
+         ORDER BY k.organ, k.yil, k.karar_no",
+        KARAR_SECIMI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(organ)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(yil)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn karar_getir(conn: &mut SqliteConnection, tenant_id: &str, karar_id: &str) -> Result<Karar, String> {
+    diesel::sql_query(format!("{} WHERE k.tenant_id = ?1 AND k.id = ?2", KARAR_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(karar_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Karar bulunamadı".to_string())
+}
+
+pub fn karar_detay(conn: &mut SqliteConnection, tenant_id: &str, karar_id: &str) -> Result<KararDetay, String> {
+    let karar = karar_getir(conn, tenant_id, karar_id)?;
+    let katilimcilar = diesel::sql_query(
+        "SELECT uye_id, ad_soyad, oy, muhalefet_serhi FROM karar_katilimcilari
+         WHERE tenant_id = ?1 AND karar_id = ?2
+         ORDER BY CASE oy WHEN 'katilmadi' THEN 1 ELSE 0 END, rowid",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(karar_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    let baglantilar = baglantilari_getir(conn, tenant_id, Some(karar_id), None)?;
+    Ok(KararDetay { karar, katilimcilar, baglantilar })
+}
+
+fn baglantilari_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    karar_id: Option<&str>,
+    kayit: Option<(&str, &str)>,
+) -> Result<Vec<KararBaglantisi>, String> {
+    diesel::sql_query(
+        "SELECT b.id, b.karar_id, b.tablo, b.kayit_id, b.aciklama,
+                CASE b.tablo
+                    WHEN 'gelirler' THEN (SELECT substr(x.tarih, 1, 10) || ' ' || COALESCE(x.aciklama, x.gelir_turu, '')
+                                          || ' (' || printf('%.2f', x.tutar) || ')' FROM gelirler x WHERE x.id = b.kayit_id)
+                    WHEN 'giderler' THEN (SELECT substr(x.tarih, 1, 10) || ' ' || COALESCE(x.aciklama, x.gider_turu, '')
+                                          || ' (' || printf('%.2f', x.tutar) || ')' FROM giderler x WHERE x.id = b.kayit_id)
+                    WHEN 'demirbaslar' THEN (SELECT COALESCE(x.demirbas_no || ' ', '') || x.ad FROM demirbaslar x WHERE x.id = b.kayit_id)
+                    WHEN 'uyeler' THEN (SELECT COALESCE(x.uye_no || ' ', '') || x.ad_soyad FROM uyeler x WHERE x.id = b.kayit_id)
+                END AS kayit_ozeti
+         FROM karar_baglantilari b
+         WHERE b.tenant_id = ?1 AND (?2 IS NULL OR b.karar_id = ?2)
+           AND (?3 IS NULL OR (b.tablo = ?3 AND b.kayit_id = ?4))
+         ORDER BY b.created_at",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(karar_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(kayit.map(|k| k.0))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(kayit.map(|k| k.1))
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Bir gelir, gider, demirbaş ya da üye kaydını yetkilendiren kararlar
+pub fn kayit_kararlari(conn: &mut SqliteConnection, tenant_id: &str, tablo: &str, kayit_id: &str) -> Result<Vec<Karar>, String> {
+    let mut kararlar = Vec::new();
+    for b in baglantilari_getir(conn, tenant_id, None, Some((tablo, kayit_id)))? {
+        kararlar.push(karar_getir(conn, tenant_id, &b.karar_id)?);
+    }
+    kararlar.sort_by(|a, b| a.karar_tarihi.cmp(&b.karar_tarihi).then(a.karar_no.cmp(&b.karar_no)));
+    Ok(kararlar)
+}
+
+/// Katılımcıları doğrular, ad soyadları üyeden tamamlar ve oy sayılarını döner
+fn katilimcilari_hazirla(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    girdi: &KararGirdi,
+) -> Result<(Vec<KararKatilimcisi>, OySayilari), String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+    }
+
+    let mut katilimcilar = Vec::new();
+    let mut uyeler = HashSet::new();
+    for k in &girdi.katilimcilar {
+        if !OYLAR.contains(&k.oy.as_str()) {
+            return Err(format!("Geçersiz oy: {}", k.oy));
+        }
+        let serh = bos_degilse(&k.muhalefet_serhi).map(str::to_string);
+        if serh.is_some() && k.oy != "ret" {
+            return Err("Muhalefet şerhi yalnızca ret oyu veren katılımcıya yazılır".to_string());
+        }
+        let uye_id = bos_degilse(&k.uye_id).map(str::to_string);
+        let mut ad_soyad = k.ad_soyad.trim().to_string();
+        if let Some(uye_id) = &uye_id {
+            if !uyeler.insert(uye_id.clone()) {
+                return Err("Katılımcı listesinde tekrar eden üye var".to_string());
+            }
+            let uye: UyeRow = diesel::sql_query("SELECT ad_soyad FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+                .bind::<diesel::sql_types::Text, _>(uye_id)
+                .bind::<diesel::sql_types::Text, _>(tenant_id)
+                .get_result(conn)
+                .optional()
+                .map_err(|e| e.to_string())?
+                .ok_or("Katılımcı üye bulunamadı")?;
+            if ad_soyad.is_empty() {
+                ad_soyad = uye.ad_soyad;
+            }
+        }
+        if ad_soyad.is_empty() {
+            return Err("Katılımcının adı soyadı zorunludur".to_string());
+        }
+        katilimcilar.push(KararKatilimcisi { uye_id, ad_soyad, oy: k.oy.clone(), muhalefet_serhi: serh });
+    }
+
+    let oylar = if katilimcilar.iter().any(|k| k.oy != "katilmadi") {
+        oy_sayilari(&katilimcilar)
+    } else {
+        let oylar = (girdi.kabul.unwrap_or(0), girdi.ret.unwrap_or(0), girdi.cekimser.unwrap_or(0));
+        if oylar.0 < 0 || oylar.1 < 0 || oylar.2 < 0 {
+            return Err("Oy sayıları negatif olamaz".to_string());
+        }
+        if oylar.0 + oylar.1 + oylar.2 == 0 {
+            return Err("Katılımcı oyları ya da oy sayıları girilmelidir".to_string());
+        }
+        oylar
+    };
+    Ok((katilimcilar, oylar))
+}
+
+/// Kurul kararında oy kullananların üyeliğini ve toplantı yeter sayısını denetler
+fn yetersayi_dogrula(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    organ: &str,
+    tarih: &str,
+    katilimcilar: &[KararKatilimcisi],
+) -> Result<(), String> {
+    if organ == "genel_kurul" {
+        return Ok(());
+    }
+    let oy_kullananlar: Vec<Option<&str>> =
+        katilimcilar.iter().filter(|k| k.oy != "katilmadi").map(|k| k.uye_id.as_deref()).collect();
+    organ_gorevi::toplanti_katilimini_dogrula(conn, tenant_id, organ, tarih, &oy_kullananlar)
+}
+
+fn katilimcilari_yaz(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    karar_id: &str,
+    katilimcilar: &[KararKatilimcisi],
+) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let eski: Vec<IdRow> = diesel::sql_query("SELECT id FROM karar_katilimcilari WHERE tenant_id = ?1 AND karar_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(karar_id)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    for r in &eski {
+        denetim.izle(conn, tenant_id, "karar_katilimcilari", &r.id)?;
+    }
+    diesel::sql_query("DELETE FROM karar_katilimcilari WHERE tenant_id = ?1 AND karar_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(karar_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    for k in katilimcilar {
+        let id = Uuid::new_v4().to_string();
+        diesel::sql_query(
+            "INSERT INTO karar_katilimcilari (id, tenant_id, karar_id, uye_id, ad_soyad, oy, muhalefet_serhi)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(karar_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&k.uye_id)
+        .bind::<diesel::sql_types::Text, _>(&k.ad_soyad)
+        .bind::<diesel::sql_types::Text, _>(&k.oy)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&k.muhalefet_serhi)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        denetim.yeni_kayit(tenant_id, "karar_katilimcilari", &id);
+    }
+    Ok(())
+}
+
+/// Karar tarihi: girdide yoksa toplantının tarihi; toplantı verildiyse kiracıya ait olmalı
+fn karar_tarihi_coz(conn: &mut SqliteConnection, tenant_id: &str, girdi: &KararGirdi) -> Result<NaiveDate, String> {
+    #[derive(QueryableByName)]
+    struct ToplantiRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+    }
+
+    let toplanti_tarihi = match bos_degilse(&girdi.toplanti_id) {
+        Some(toplanti_id) => Some(
+            diesel::sql_query(
+                "SELECT tarih FROM toplantilar WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+            )
+            .bind::<diesel::sql_types::Text, _>(toplanti_id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .get_result::<ToplantiRow>(conn)
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or("Toplantı bulunamadı")?
+            .tarih,
+        ),
+        None => None,
+    };
+    let tarih = bos_degilse(&girdi.karar_tarihi)
+        .or(toplanti_tarihi.as_deref())
+        .ok_or("Karar tarihi ya da kararın alındığı toplantı belirtilmelidir")?;
+    tarih
+        .get(..10)
+        .and_then(|t| NaiveDate::parse_from_str(t, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("Geçersiz karar tarihi: {}", tarih))
+}
+
+/// Aynı organ ve yıldaki komşu kararların tarihleri (önceki, sonraki)
+fn komsu_tarihler(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    organ: &str,
+    yil: i32,
+    karar_no: i32,
+) -> Result<(Option<String>, Option<String>), String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        onceki: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        sonraki: Option<String>,
+    }
+
+    let r: Row = diesel::sql_query(
+        "SELECT (SELECT MAX(karar_tarihi) FROM kararlar WHERE tenant_id = ?1 AND organ = ?2 AND yil = ?3 AND karar_no < ?4) AS onceki,
+                (SELECT MIN(karar_tarihi) FROM kararlar WHERE tenant_id = ?1 AND organ = ?2 AND yil = ?3 AND karar_no > ?4) AS sonraki",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(organ)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .bind::<diesel::sql_types::Integer, _>(karar_no)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    Ok((r.onceki, r.sonraki))
+}
+
+fn icerik_dogrula(girdi: &KararGirdi) -> Result<(), String> {
+    if !ORGANLAR.contains(&girdi.organ.as_str()) {
+        return Err(format!("Geçersiz organ: {}", girdi.organ));
+    }
+    if girdi.konu.trim().is_empty() || girdi.metin.trim().is_empty() {
+        return Err("Karar konusu ve metni zorunludur".to_string());
+    }
+    Ok(())
+}
+
+/// Yeni karar: organ ve yıl içindeki sıradaki numarayı alır
+pub fn karar_ekle(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, girdi: &KararGirdi) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct NoRow {
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        son: i32,
+    }
+
+    icerik_dogrula(girdi)?;
+    let tarih = karar_tarihi_coz(conn, tenant_id, girdi)?;
+    let tarih_metni = tarih.format("%Y-%m-%d").to_string();
+    let yil = tarih.year();
+    let (katilimcilar, (kabul, ret, cekimser)) = katilimcilari_hazirla(conn, tenant_id, girdi)?;
+    yetersayi_dogrula(conn, tenant_id, &girdi.organ, &tarih_metni, &katilimcilar)?;
+
+    let son: NoRow = diesel::sql_query(
+        "SELECT COALESCE(MAX(karar_no), 0) AS son FROM kararlar WHERE tenant_id = ?1 AND organ = ?2 AND yil = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&girdi.organ)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if let (Some(onceki), _) = komsu_tarihler(conn, tenant_id, &girdi.organ, yil, son.son + 1)? {
+        if onceki > tarih_metni {
+            return Err(format!("Karar defterinde {} tarihli karardan önceki bir tarihe karar yazılamaz", onceki));
+        }
+    }
+
+    let sonuc = genel_kurul::karar_sonucu((kabul + ret + cekimser) as i64, kabul as i64, false);
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO kararlar (id, tenant_id, organ, yil, karar_no, karar_tarihi, toplanti_id, konu, metin,
+            kabul, ret, cekimser, sonuc, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?14)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&girdi.organ)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .bind::<diesel::sql_types::Integer, _>(son.son + 1)
+    .bind::<diesel::sql_types::Text, _>(&tarih_metni)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.toplanti_id))
+    .bind::<diesel::sql_types::Text, _>(girdi.konu.trim())
+    .bind::<diesel::sql_types::Text, _>(girdi.metin.trim())
+    .bind::<diesel::sql_types::Integer, _>(kabul)
+    .bind::<diesel::sql_types::Integer, _>(ret)
+    .bind::<diesel::sql_types::Integer, _>(cekimser)
+    .bind::<diesel::sql_types::Text, _>(sonuc)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    denetim.yeni_kayit(tenant_id, "kararlar", &id);
+    katilimcilari_yaz(conn, denetim, tenant_id, &id, &katilimcilar)?;
+    Ok(id)
+}
+
+/// Geçerli kararın içeriğini düzeltir; organ, yıl ve numara değişmez, tarih komşu
+/// kararların arasında kalmalıdır
+pub fn karar_guncelle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    karar_id: &str,
+    girdi: &KararGirdi,
+) -> Result<(), String> {
+    let mevcut = karar_getir(conn, tenant_id, karar_id)?;
+    if mevcut.durum != "gecerli" {
+        return Err("İptal edilmiş karar değiştirilemez".to_string());
+    }
+    icerik_dogrula(girdi)?;
+    if girdi.organ != mevcut.organ {
+        return Err("Kararın organı değiştirilemez".to_string());
+    }
+    let tarih = karar_tarihi_coz(conn, tenant_id, girdi)?;
+    let tarih_metni = tarih.format("%Y-%m-%d").to_string();
+    if tarih.year() != mevcut.yil {
+        return Err("Karar tarihi başka bir yıla taşınamaz".to_string());
+    }
+    let (onceki, sonraki) = komsu_tarihler(conn, tenant_id, &mevcut.organ, mevcut.yil, mevcut.karar_no)?;
+    if onceki.is_some_and(|t| t > tarih_metni) || sonraki.is_some_and(|t| t < tarih_metni) {
+        return Err("Karar tarihi defterdeki önceki ve sonraki kararların tarihleri arasında olmalıdır".to_string());
+    }
+    let (katilimcilar, (kabul, ret, cekimser)) = katilimcilari_hazirla(conn, tenant_id, girdi)?;
+    yetersayi_dogrula(conn, tenant_id, &girdi.organ, &tarih_metni, &katilimcilar)?;
+    let sonuc = genel_kurul::karar_sonucu((kabul + ret + cekimser) as i64, kabul as i64, false);
+    if sonuc == "ret" && !baglantilari_getir(conn, tenant_id, Some(karar_id), None)?.is_empty() {
+        return Err("Kayıtlara bağlı karar ret sonucuna çevrilemez; önce bağlantıları kaldırın".to_string());
+    }
+
+    denetim.izle(conn, tenant_id, "kararlar", karar_id)?;
+    diesel::sql_query(
+        "UPDATE kararlar SET karar_tarihi = ?1, toplanti_id = ?2, konu = ?3, metin = ?4, kabul = ?5, ret = ?6,
+            cekimser = ?7, sonuc = ?8, updated_at = ?9
+         WHERE id = ?10 AND tenant_id = ?11",
+    )
+    .bind::<diesel::sql_types::Text, _>(&tarih_metni)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.toplanti_id))
+    .bind::<diesel::sql_types::Text, _>(girdi.konu.trim())
+    .bind::<diesel::sql_types::Text, _>(girdi.metin.trim())
+    .bind::<diesel::sql_types::Integer, _>(kabul)
+    .bind::<diesel::sql_types::Integer, _>(ret)
+    .bind::<diesel::sql_types::Integer, _>(cekimser)
+    .bind::<diesel::sql_types::Text, _>(sonuc)
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(karar_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    katilimcilari_yaz(conn, denetim, tenant_id, karar_id, &katilimcilar)
+}
+
+/// Kararı iptal eder; numarası defterde iptal notuyla kalır
+pub fn karar_iptal(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, karar_id: &str, neden: &str) -> Result<(), String> {
+    let mevcut = karar_getir(conn, tenant_id, karar_id)?;
+    if mevcut.durum == "iptal" {
+        return Err("Karar zaten iptal edilmiş".to_string());
+    }
+    if neden.trim().is_empty() {
+        return Err("İptal nedeni zorunludur".to_string());
+    }
+    denetim.izle(conn, tenant_id, "kararlar", karar_id)?;
+    diesel::sql_query("UPDATE kararlar SET durum = 'iptal', iptal_nedeni = ?1, updated_at = ?2 WHERE id = ?3 AND tenant_id = ?4")
+        .bind::<diesel::sql_types::Text, _>(neden.trim())
+        .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind::<diesel::sql_types::Text, _>(karar_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Kabul edilmiş kararı dayanak olduğu kayda bağlar
+pub fn baglanti_ekle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    karar_id: &str,
+    girdi: &BaglantiGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct SayiRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    let karar = karar_getir(conn, tenant_id, karar_id)?;
+    if karar.durum != "gecerli" || karar.sonuc != "kabul" {
+        return Err("Yalnızca geçerli ve kabul edilmiş karar bir kayda dayanak olabilir".to_string());
+    }
+    let tablo = BAGLANTI_TABLOLARI
+        .iter()
+        .find(|t| **t == girdi.tablo)
+        .ok_or_else(|| format!("Karara bağlanamayan tablo: {}", girdi.tablo))?;
+    // Tablo adı sabit listeden geldiği için sorguya gömülebilir
+    let kayit: SayiRow = diesel::sql_query(format!("SELECT COUNT(*) AS sayi FROM {} WHERE id = ?1 AND tenant_id = ?2", tablo))
+        .bind::<diesel::sql_types::Text, _>(&girdi.kayit_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .map_err(|e| e.to_string())?;
+    if kayit.sayi == 0 {
+        return Err("Bağlanacak kayıt bulunamadı".to_string());
+    }
+    let mevcut: SayiRow = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM karar_baglantilari WHERE karar_id = ?1 AND tablo = ?2 AND kayit_id = ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(karar_id)
+    .bind::<diesel::sql_types::Text, _>(tablo)
+    .bind::<diesel::sql_types::Text, _>(&girdi.kayit_id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if mevcut.sayi > 0 {
+        return Err("Kayıt bu karara zaten bağlı".to_string());
+    }
+
+    let id = Uuid::new_v4().to_string();
+    diesel::sql_query(
+        "INSERT INTO karar_baglantilari (id, tenant_id, karar_id, tablo, kayit_id, aciklama, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(karar_id)
+    .bind::<diesel::sql_types::Text, _>(tablo)
+    .bind::<diesel::sql_types::Text, _>(&girdi.kayit_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.aciklama))
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    denetim.yeni_kayit(tenant_id, "karar_baglantilari", &id);
+    Ok(id)
+}
+
+pub fn baglanti_sil(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, baglanti_id: &str) -> Result<(), String> {
+    denetim.izle(conn, tenant_id, "karar_baglantilari", baglanti_id)?;
+    let silinen = diesel::sql_query("DELETE FROM karar_baglantilari WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(baglanti_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    if silinen == 0 {
+        return Err("Bağlantı bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::uye(&mut conn, "t1", "u2", "2", "Ayşe Kaya");
+        test_db::uye(&mut conn, "t1", "u3", "3", "Can Er");
+        test_db::kasa(&mut conn, "t1", "k1", "Ana Kasa");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO toplantilar (id, tenant_id, baslik, tarih) VALUES ('yk3', 't1', 'YK 3. toplantı', '2026-02-10')",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO giderler (id, tenant_id, kasa_id, tarih, tutar, aciklama)
+             VALUES ('g1', 't1', 'k1', '2026-02-12', 1500.0, 'Yazıcı alımı')",
+        );
+        conn
+    }
+
+    fn katilimci(uye_id: &str, oy: &str) -> KararKatilimcisi {
+        KararKatilimcisi { uye_id: Some(uye_id.to_string()), ad_soyad: String::new(), oy: oy.to_string(), muhalefet_serhi: None }
+    }
+
+    fn girdi(tarih: Option<&str>, katilimcilar: Vec<KararKatilimcisi>) -> KararGirdi {
+        KararGirdi {
+            organ: "yonetim_kurulu".to_string(),
+            karar_tarihi: tarih.map(str::to_string),
+            toplanti_id: Some("yk3".to_string()),
+            konu: "Yazıcı alımı".to_string(),
+            metin: "Dernek merkezi için yazıcı alınmasına karar verildi.".to_string(),
+            katilimcilar,
+            kabul: None,
+            ret: None,
+            cekimser: None,
+        }
+    }
+
+    /// Toplantıya bağlı olmayan, oy sayılarıyla yazılan karar
+    fn sayili(organ: &str, tarih: &str, kabul: i32, ret: i32) -> KararGirdi {
+        KararGirdi {
+            organ: organ.to_string(),
+            toplanti_id: None,
+            kabul: Some(kabul),
+            ret: Some(ret),
+            ..girdi(Some(tarih), vec![])
+        }
+    }
+
+    fn ekle(conn: &mut SqliteConnection, g: &KararGirdi) -> Result<Karar, String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        let id = karar_ekle(conn, &mut d, "t1", g)?;
+        karar_getir(conn, "t1", &id)
+    }
+
+    #[test]
+    fn karar_toplanti_tarihini_alir_ve_oylari_sayar() {
+        let mut conn = test_db();
+        let mut serhli = katilimci("u3", "ret");
+        serhli.muhalefet_serhi = Some("Bütçe yetersiz".to_string());
+        let k = ekle(&mut conn, &girdi(None, vec![katilimci("u1", "kabul"), katilimci("u2", "kabul"), serhli])).unwrap();
+        let detay = karar_detay(&mut conn, "t1", &k.id).unwrap();
+        assert_eq!((detay.karar.numara.as_str(), detay.karar.karar_tarihi.as_str()), ("2026/1", "2026-02-10"));
+        assert_eq!((detay.karar.kabul, detay.karar.ret, detay.karar.sonuc.as_str()), (2, 1, "kabul"));
+        assert_eq!(detay.katilimcilar[0].ad_soyad, "Ali Veli");
+        assert_eq!(detay.katilimcilar[2].muhalefet_serhi.as_deref(), Some("Bütçe yetersiz"));
+    }
+
+    #[test]
+    fn muhalefet_serhi_yalnizca_ret_oyunda_yazilir() {
+        let mut conn = test_db();
+        let mut hatali = katilimci("u1", "kabul");
+        hatali.muhalefet_serhi = Some("?".to_string());
+        assert!(ekle(&mut conn, &girdi(None, vec![hatali])).is_err());
+    }
+
+    #[test]
+    fn katilimci_ya_da_oy_sayisi_zorunlu() {
+        let mut conn = test_db();
+        assert!(ekle(&mut conn, &girdi(None, vec![])).is_err());
+        let k = ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-01", 2, 3)).unwrap();
+        assert_eq!((k.karar_no, k.sonuc.as_str()), (1, "ret"));
+    }
+
+    #[test]
+    fn numara_yil_icinde_artar_yeni_yilda_birden_baslar() {
+        let mut conn = test_db();
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-01", 3, 0)).unwrap().numara, "2026/1");
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-12-31", 3, 0)).unwrap().numara, "2026/2");
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2027-01-05", 3, 0)).unwrap().numara, "2027/1");
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2027-01-06", 3, 0)).unwrap().numara, "2027/2");
+    }
+
+    #[test]
+    fn her_organin_numarasi_ayri_yurur() {
+        let mut conn = test_db();
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-01", 3, 0)).unwrap().numara, "2026/1");
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-02", 3, 0)).unwrap().numara, "2026/2");
+        assert_eq!(ekle(&mut conn, &sayili("denetim_kurulu", "2026-03-03", 2, 0)).unwrap().numara, "2026/1");
+        assert_eq!(ekle(&mut conn, &sayili("genel_kurul", "2026-04-04", 40, 2)).unwrap().numara, "2026/1");
+        // Bir organın yeni yılı diğerinin numarasını etkilemez
+        assert_eq!(ekle(&mut conn, &sayili("genel_kurul", "2027-04-03", 40, 2)).unwrap().numara, "2027/1");
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-05-01", 3, 0)).unwrap().numara, "2026/3");
+        assert_eq!(ekle(&mut conn, &sayili("denetim_kurulu", "2027-01-10", 2, 0)).unwrap().numara, "2027/1");
+    }
+
+    #[test]
+    fn defterde_geriye_tarih_atilamaz() {
+        let mut conn = test_db();
+        ekle(&mut conn, &sayili("yonetim_kurulu", "2026-02-10", 3, 0)).unwrap();
+        assert!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-02-01", 3, 0)).is_err());
+        // Aynı gün ve başka organda önceki tarih serbest
+        ekle(&mut conn, &sayili("yonetim_kurulu", "2026-02-10", 3, 0)).unwrap();
+        ekle(&mut conn, &sayili("denetim_kurulu", "2026-02-01", 2, 0)).unwrap();
+    }
+
+    #[test]
+    fn guncellemede_tarih_komsu_kararlar_arasinda_kalir() {
+        let mut conn = test_db();
+        let mut d = Denetim::yeni(None, None, "test");
+        let k1 = ekle(&mut conn, &girdi(None, vec![katilimci("u1", "kabul")])).unwrap();
+        ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-01", 2, 0)).unwrap();
+
+        let sonra = girdi(Some("2026-03-02"), vec![katilimci("u1", "kabul")]);
+        assert!(karar_guncelle(&mut conn, &mut d, "t1", &k1.id, &sonra).is_err());
+        let arada = girdi(Some("2026-02-11"), vec![katilimci("u1", "kabul")]);
+        karar_guncelle(&mut conn, &mut d, "t1", &k1.id, &arada).unwrap();
+        assert_eq!(karar_getir(&mut conn, "t1", &k1.id).unwrap().karar_no, 1);
+    }
+
+    #[test]
+    fn kabul_edilen_karar_kayda_baglanir() {
+        let mut conn = test_db();
+        let mut d = Denetim::yeni(None, None, "test");
+        let kabul = ekle(&mut conn, &girdi(None, vec![katilimci("u1", "kabul")])).unwrap();
+        let ret = ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-01", 2, 3)).unwrap();
+        let bag = BaglantiGirdi { tablo: "giderler".into(), kayit_id: "g1".into(), aciklama: None };
+
+        assert!(baglanti_ekle(&mut conn, &mut d, "t1", &ret.id, &bag).is_err());
+        baglanti_ekle(&mut conn, &mut d, "t1", &kabul.id, &bag).unwrap();
+        assert!(baglanti_ekle(&mut conn, &mut d, "t1", &kabul.id, &bag).is_err());
+        assert!(baglanti_ekle(&mut conn, &mut d, "t1", &kabul.id, &BaglantiGirdi { tablo: "kasalar".into(), ..bag.clone() }).is_err());
+
+        assert_eq!(kayit_kararlari(&mut conn, "t1", "giderler", "g1").unwrap().len(), 1);
+        let baglanti = &karar_detay(&mut conn, "t1", &kabul.id).unwrap().baglantilar[0];
+        assert_eq!(baglanti.kayit_ozeti.as_deref(), Some("2026-02-12 Yazıcı alımı (1500.00)"));
+    }
+
+    #[test]
+    fn iptal_edilen_kararin_numarasi_yeniden_kullanilmaz() {
+        let mut conn = test_db();
+        let mut d = Denetim::yeni(None, None, "test");
+        ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-01", 3, 0)).unwrap();
+        let iki = ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-02", 3, 0)).unwrap();
+        karar_iptal(&mut conn, &mut d, "t1", &iki.id, "Mükerrer kayıt").unwrap();
+        assert_eq!(ekle(&mut conn, &sayili("yonetim_kurulu", "2026-03-05", 3, 0)).unwrap().karar_no, 3);
+    }
+
+    #[test]
+    fn kayitli_kurulda_oy_kullananlar_yeter_sayiyi_saglamali() {
+        let mut conn = test_db();
+        let mut d = Denetim::yeni(None, None, "test");
+        for uye in ["u1", "u2", "u3"] {
+            let gorev = organ_gorevi::GorevGirdi {
+                organ: "yonetim_kurulu".to_string(),
+                komisyon_adi: None,
+                uye_id: uye.to_string(),
+                gorev: "uye".to_string(),
+                sira: None,
+                baslangic_tarihi: "2027-01-10".to_string(),
+                bitis_tarihi: None,
+                genel_kurul_id: None,
+                tahsilat_yetkisi: false,
+            };
+            organ_gorevi::gorev_kaydet(&mut conn, &mut d, "t1", None, &gorev).unwrap();
+        }
+        // 3 asil üye: yeter sayı 2; katılmayan sayılmaz
+        let eksik = girdi(Some("2027-02-01"), vec![katilimci("u1", "kabul"), katilimci("u2", "katilmadi")]);
+        assert!(ekle(&mut conn, &eksik).unwrap_err().contains("yeter sayısı"));
+        let tam = girdi(Some("2027-02-01"), vec![katilimci("u1", "kabul"), katilimci("u2", "ret")]);
+        assert_eq!(ekle(&mut conn, &tam).unwrap().sonuc, "ret");
+        // Genel kurul kararları kurul üyeliğiyle denetlenmez
+        ekle(&mut conn, &sayili("genel_kurul", "2027-04-03", 10, 0)).unwrap();
+    }
+}
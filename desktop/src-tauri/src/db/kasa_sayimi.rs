@@ -0,0 +1,76 @@
+// Kasa sayımı: sayılan tutarın küpürlerden hesaplanması ve defter bakiyesiyle farkı.
+//
+// Nakit kasada sayım küpür × adet olarak girilir; TRY kasalarında yalnızca
+// tedavüldeki banknot ve madeni paralar kabul edilir (diğer para birimlerinde
+// küpür değeri serbesttir). Banka kasasında sayılan tutar ekstre bakiyesidir.
+// Defter bakiyesi sayım gününün sonundaki bakiyedir (mali::kasa_bakiyesi_tarihte);
+// fark = sayılan − defter (pozitif: sayım fazlası, negatif: sayım açığı).
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::para::Para;
+
+/// TRY küpürleri (kuruş): 200, 100, 50, 20, 10, 5 TL banknot; 1 TL, 50, 25, 10, 5, 1 kuruş
+pub const TRY_KUPURLERI: [i64; 12] = [20000, 10000, 5000, 2000, 1000, 500, 100, 50, 25, 10, 5, 1];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Kupur {
+    pub deger: Para,
+    pub adet: i64,
+}
+
+/// Küpürleri doğrular, aynı değerleri birleştirir (büyükten küçüğe) ve toplamı döner
+pub fn kupur_toplami(para_birimi: &str, kupurler: &[Kupur]) -> Result<(Vec<Kupur>, Para), String> {
+    let mut birlesik: Vec<Kupur> = Vec::new();
+    for k in kupurler {
+        if !k.deger.is_positive() {
+            return Err("Küpür değeri sıfırdan büyük olmalıdır".to_string());
+        }
+        if k.adet < 0 {
+            return Err(format!("{} küpürü için adet negatif olamaz", k.deger.tr_format()));
+        }
+        if para_birimi == "TRY" && !TRY_KUPURLERI.contains(&k.deger.kurus()) {
+            return Err(format!("Geçersiz TL küpürü: {}", k.deger.tr_format()));
+        }
+        match birlesik.iter_mut().find(|b| b.deger == k.deger) {
+            Some(b) => b.adet += k.adet,
+            None => birlesik.push(k.clone()),
+        }
+    }
+    birlesik.retain(|k| k.adet > 0);
+    birlesik.sort_by_key(|k| std::cmp::Reverse(k.deger));
+
+    let toplam = birlesik
+        .iter()
+        .map(|k| Para::from_kurus(k.deger.kurus() * k.adet))
+        .sum();
+    Ok((birlesik, toplam))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn k(tl: f64, adet: i64) -> Kupur {
+        Kupur { deger: Para::from_tl(tl), adet }
+    }
+
+    #[test]
+    fn kupurler_birlesir_ve_toplanir() {
+        let (kupurler, toplam) =
+            kupur_toplami("TRY", &[k(10.0, 3), k(200.0, 2), k(0.25, 4), k(10.0, 1), k(5.0, 0)]).unwrap();
+        assert_eq!(toplam, Para::from_tl(441.0));
+        assert_eq!(kupurler.len(), 3);
+        assert_eq!(kupurler[0].deger, Para::from_tl(200.0));
+        assert_eq!(kupurler[1].adet, 4);
+    }
+
+    #[test]
+    fn gecersiz_kupurler_reddedilir() {
+        assert!(kupur_toplami("TRY", &[k(30.0, 1)]).is_err());
+        assert!(kupur_toplami("TRY", &[k(10.0, -1)]).is_err());
+        assert!(kupur_toplami("EUR", &[k(0.0, 1)]).is_err());
+        // Yabancı para biriminde küpür değeri serbest
+        assert_eq!(kupur_toplami("EUR", &[k(500.0, 1)]).unwrap().1, Para::from_tl(500.0));
+    }
+}
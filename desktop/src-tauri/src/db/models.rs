@@ -1,5 +1,6 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use crate::db::para::Para;
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Selectable)]
 #[diesel(table_name = crate::db::schema::tenants)]
@@ -98,7 +99,7 @@ pub struct Uye {
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub uyelik_tipi: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
-    pub ozel_aidat_tutari: Option<f64>,
+    pub ozel_aidat_tutari: Option<Para>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
     pub aidat_indirimi_yuzde: Option<f64>,
     #[diesel(sql_type = diesel::sql_types::Text)]
@@ -127,13 +128,13 @@ pub struct AidatTakip {
     pub uye_id: String,
     pub yil: i32,
     pub ay: i32,
-    pub tutar: f64,
-    pub odenen: f64,
-    pub kalan: Option<f64>,
+    pub tutar: Para,
+    pub odenen: Para,
+    pub kalan: Option<Para>,
     pub odeme_tarihi: Option<String>,
     pub durum: String,
     pub gecikme_gun: Option<i32>,
-    pub gecikme_faiz: Option<f64>,
+    pub gecikme_faiz: Option<Para>,
     pub tahsilat_turu: Option<String>,
     pub banka_sube: Option<String>,
     pub dekont_no: Option<String>,
@@ -153,19 +154,20 @@ pub struct Kasa {
     pub id: String,
     pub tenant_id: String,
     pub kasa_adi: String,
-    pub bakiye: f64,
+    pub bakiye: Para,
     pub para_birimi: String,
-    pub devir_bakiye: Option<f64>,
-    pub toplam_gelir: Option<f64>,
-    pub toplam_gider: Option<f64>,
-    pub virman_giris: Option<f64>,
-    pub virman_cikis: Option<f64>,
-    pub fiziksel_bakiye: Option<f64>,
-    pub tahakkuk_tutari: Option<f64>,
-    pub serbest_bakiye: Option<f64>,
+    pub devir_bakiye: Option<Para>,
+    pub toplam_gelir: Option<Para>,
+    pub toplam_gider: Option<Para>,
+    pub virman_giris: Option<Para>,
+    pub virman_cikis: Option<Para>,
+    pub fiziksel_bakiye: Option<Para>,
+    pub tahakkuk_tutari: Option<Para>,
+    pub serbest_bakiye: Option<Para>,
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub devir_tarihi: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Selectable)]
@@ -192,7 +194,7 @@ pub struct Gelir {
     pub gelir_turu_id: Option<String>,
     pub alt_kategori: Option<String>,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub makbuz_no: Option<String>,
     pub belge_no: Option<String>,
@@ -233,7 +235,7 @@ pub struct Gider {
     pub gider_turu_id: Option<String>,
     pub alt_kategori: Option<String>,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub fatura_no: Option<String>,
     pub islem_no: Option<String>,
@@ -276,12 +278,12 @@ pub struct Virman {
     pub kaynak_kasa_id: String,
     pub hedef_kasa_id: String,
     pub tarih: String,
-    pub tutar: f64,
+    pub tutar: Para,
     pub aciklama: Option<String>,
     pub kaynak_para_birimi: Option<String>,
     pub hedef_para_birimi: Option<String>,
-    pub kaynak_tutar: Option<f64>,
-    pub hedef_tutar: Option<f64>,
+    pub kaynak_tutar: Option<Para>,
+    pub hedef_tutar: Option<Para>,
     pub uygulanan_kur: Option<f64>,
     pub kur_id: Option<String>,
     pub is_deleted: Option<i32>,
@@ -0,0 +1,703 @@
+// Organ görevleri: yönetim, denetim ve disiplin kurulları ile komisyonlarda görevli üyeler.
+//
+// Her kayıt bir üyenin bir organdaki görev dönemidir; bitis_tarihi görevin son günüdür.
+// Aynı üye aynı organda çakışan iki döneme, başkan/başkan yardımcısı/sayman/sekreter
+// görevlerine aynı anda iki kişi, denetim kurulu üyesi aynı dönemde yönetim kuruluna
+// yazılamaz. Asil üye ayrıldığında sıradaki yedek asil üyeliğe geçer (eski kayıt kapanır,
+// yeni kayıt açılır). Genel kurul seçim sonuçları kurulların yeni dönemini oluşturur.
+//
+// Kayıtlı üyesi olan kurulun toplantısı üye tamsayısının salt çoğunluğuyla toplanır
+// (TMK 84; bkz. karar_defteri), tahsil edeni yazılmamış gelire tahsilat yetkilisi yazılır.
+
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::genel_kurul;
+
+pub const ORGANLAR: [&str; 4] = ["yonetim_kurulu", "denetim_kurulu", "disiplin_kurulu", "komisyon"];
+/// Görevler sunum sırasıyla
+pub const GOREVLER: [&str; 6] = ["baskan", "baskan_yardimcisi", "sayman", "sekreter", "uye", "yedek_uye"];
+/// Organda aynı anda tek kişinin tutabileceği görevler
+const TEKIL_GOREVLER: [&str; 4] = ["baskan", "baskan_yardimcisi", "sayman", "sekreter"];
+
+pub fn gorev_adi(gorev: &str) -> &str {
+    match gorev {
+        "baskan" => "Başkan",
+        "baskan_yardimcisi" => "Başkan Yardımcısı",
+        "sayman" => "Sayman",
+        "sekreter" => "Sekreter",
+        "uye" => "Asil Üye",
+        "yedek_uye" => "Yedek Üye",
+        diger => diger,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct OrganGorevi {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub organ: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub komisyon_adi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub gorev: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub sira: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslangic_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub bitis_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub genel_kurul_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub tahsilat_yetkisi: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ayrilma_nedeni: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GorevGirdi {
+    pub organ: String,
+    /// Yalnızca komisyonlarda
+    pub komisyon_adi: Option<String>,
+    pub uye_id: String,
+    pub gorev: String,
+    pub sira: Option<i32>,
+    /// YYYY-MM-DD
+    pub baslangic_tarihi: String,
+    /// Görevin son günü; boşsa görev sürer
+    pub bitis_tarihi: Option<String>,
+    pub genel_kurul_id: Option<String>,
+    #[serde(default)]
+    pub tahsilat_yetkisi: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrganYetersayisi {
+    pub organ: String,
+    /// Tarihte görevdeki asil üye sayısı
+    pub uye_tamsayisi: i64,
+    /// Toplantı için gereken asgari asil üye (salt çoğunluk)
+    pub gerekli: i64,
+}
+
+fn tarih_coz(tarih: &str, alan: &str) -> Result<NaiveDate, String> {
+    tarih
+        .get(..10)
+        .and_then(|t| NaiveDate::parse_from_str(t, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("Geçersiz {}: {}", alan, tarih))
+}
+
+fn gun(tarih: NaiveDate) -> String {
+    tarih.format("%Y-%m-%d").to_string()
+}
+
+const GOREV_SECIMI: &str = "SELECT g.id, g.organ, g.komisyon_adi, g.uye_id, u.uye_no, COALESCE(u.ad_soyad, '') AS ad_soyad,
+        g.gorev, g.sira, g.baslangic_tarihi, g.bitis_tarihi, g.genel_kurul_id, g.tahsilat_yetkisi, g.ayrilma_nedeni
+     FROM organ_gorevleri g
+     LEFT JOIN uyeler u ON u.id = g.uye_id AND u.tenant_id = g.tenant_id";
+
+const GOREV_SIRASI: &str = "ORDER BY g.organ, g.komisyon_adi,
+        CASE g.gorev WHEN 'baskan' THEN 0 WHEN 'baskan_yardimcisi' THEN 1 WHEN 'sayman' THEN 2
+                     WHEN 'sekreter' THEN 3 WHEN 'uye' THEN 4 ELSE 5 END,
+        g.sira IS NULL, g.sira, g.baslangic_tarihi, ad_soyad";
+
+/// Görevler; tarih verilirse o gün görevde olanlar
+pub fn gorevleri_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    organ: Option<&str>,
+    tarih: Option<&str>,
+) -> Result<Vec<OrganGorevi>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE g.tenant_id = ?1 AND (?2 IS NULL OR g.organ = ?2)
+           AND (?3 IS NULL OR (g.baslangic_tarihi <= ?3 AND (g.bitis_tarihi IS NULL OR g.bitis_tarihi >= ?3)))
+         {}",
+        GOREV_SECIMI, GOREV_SIRASI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(organ)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(tarih.and_then(|t| t.get(..10)))
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn gorev_getir(conn: &mut SqliteConnection, tenant_id: &str, gorev_id: &str) -> Result<OrganGorevi, String> {
+    diesel::sql_query(format!("{} WHERE g.tenant_id = ?1 AND g.id = ?2", GOREV_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(gorev_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Görev kaydı bulunamadı".to_string())
+}
+
+/// Bugünden itibaren `gun` gün içinde görev süresi biten üyeler
+pub fn biten_gorevler(conn: &mut SqliteConnection, tenant_id: &str, bugun: NaiveDate, gun_sayisi: i64) -> Result<Vec<OrganGorevi>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE g.tenant_id = ?1 AND g.bitis_tarihi >= ?2 AND g.bitis_tarihi <= ?3 AND g.ayrilma_nedeni IS NULL {}",
+        GOREV_SECIMI, GOREV_SIRASI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(gun(bugun))
+    .bind::<diesel::sql_types::Text, _>(gun(bugun + Duration::days(gun_sayisi)))
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Girdiyi doğrular; çakışma kontrolünde `haric_id` (güncellenen kayıt) sayılmaz
+fn dogrula(conn: &mut SqliteConnection, tenant_id: &str, haric_id: Option<&str>, g: &GorevGirdi) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct CakismaRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        organ: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        gorev: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+    }
+    #[derive(QueryableByName)]
+    struct SayiRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    if !ORGANLAR.contains(&g.organ.as_str()) {
+        return Err(format!("Geçersiz organ: {}", g.organ));
+    }
+    if !GOREVLER.contains(&g.gorev.as_str()) {
+        return Err(format!("Geçersiz görev: {}", g.gorev));
+    }
+    let komisyon = g.komisyon_adi.as_deref().map(str::trim).filter(|k| !k.is_empty());
+    match (g.organ == "komisyon", komisyon) {
+        (true, None) => return Err("Komisyon adı zorunludur".to_string()),
+        (false, Some(_)) => return Err("Komisyon adı yalnızca komisyon görevlerinde yazılır".to_string()),
+        _ => {}
+    }
+    if g.gorev == "sayman" && g.organ != "yonetim_kurulu" {
+        return Err("Sayman yalnızca yönetim kurulunda görevlendirilir".to_string());
+    }
+    if g.tahsilat_yetkisi && g.gorev == "yedek_uye" {
+        return Err("Yedek üyeye tahsilat yetkisi verilemez".to_string());
+    }
+    let bas = tarih_coz(&g.baslangic_tarihi, "başlangıç tarihi")?;
+    let bitis = match g.bitis_tarihi.as_deref().filter(|t| !t.trim().is_empty()) {
+        Some(t) => Some(tarih_coz(t, "bitiş tarihi")?),
+        None => None,
+    };
+    if bitis.is_some_and(|b| b < bas) {
+        return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+    }
+    let uye: SayiRow = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM uyeler WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&g.uye_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if uye.sayi == 0 {
+        return Err("Üye bulunamadı".to_string());
+    }
+    if let Some(gk) = g.genel_kurul_id.as_deref().filter(|s| !s.is_empty()) {
+        genel_kurul::genel_kurul_getir(conn, tenant_id, gk)?;
+    }
+
+    // Tarih aralığı çakışan görevler (aynı organ/komisyon ya da karşı kurul)
+    let cakisanlar: Vec<CakismaRow> = diesel::sql_query(
+        "SELECT g.organ, g.uye_id, g.gorev, COALESCE(u.ad_soyad, '') AS ad_soyad
+         FROM organ_gorevleri g
+         LEFT JOIN uyeler u ON u.id = g.uye_id AND u.tenant_id = g.tenant_id
+         WHERE g.tenant_id = ?1 AND (?2 IS NULL OR g.id != ?2)
+           AND g.baslangic_tarihi <= COALESCE(?4, '9999-12-31') AND COALESCE(g.bitis_tarihi, '9999-12-31') >= ?3
+           AND ((g.organ = ?5 AND COALESCE(g.komisyon_adi, '') = COALESCE(?6, ''))
+                OR (g.uye_id = ?7 AND g.organ IN ('yonetim_kurulu', 'denetim_kurulu')))",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(haric_id)
+    .bind::<diesel::sql_types::Text, _>(gun(bas))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bitis.map(gun))
+    .bind::<diesel::sql_types::Text, _>(&g.organ)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(komisyon)
+    .bind::<diesel::sql_types::Text, _>(&g.uye_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    for c in &cakisanlar {
+        if c.organ == g.organ && c.uye_id == g.uye_id {
+            return Err("Üyenin bu organda aynı döneme düşen başka bir görev kaydı var".to_string());
+        }
+        if c.organ == g.organ && c.gorev == g.gorev && TEKIL_GOREVLER.contains(&g.gorev.as_str()) {
+            return Err(format!("{} görevinde aynı dönemde {} bulunuyor", gorev_adi(&g.gorev), c.ad_soyad));
+        }
+        if c.uye_id == g.uye_id && c.organ != g.organ && g.organ != "disiplin_kurulu" && g.organ != "komisyon" {
+            return Err("Denetim kurulu üyesi aynı dönemde yönetim kurulunda görev alamaz".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Görev ekler (gorev_id yoksa) ya da günceller
+pub fn gorev_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    gorev_id: Option<&str>,
+    g: &GorevGirdi,
+) -> Result<String, String> {
+    if let Some(id) = gorev_id {
+        gorev_getir(conn, tenant_id, id)?;
+    }
+    dogrula(conn, tenant_id, gorev_id, g)?;
+    let komisyon = g.komisyon_adi.as_deref().map(str::trim).filter(|k| !k.is_empty());
+    let bitis = g.bitis_tarihi.as_deref().map(str::trim).filter(|t| !t.is_empty()).map(|t| &t[..10]);
+    let genel_kurul_id = g.genel_kurul_id.as_deref().filter(|s| !s.is_empty());
+    let sira = if g.gorev == "yedek_uye" { g.sira } else { None };
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let id = match gorev_id {
+        Some(id) => {
+            denetim.izle(conn, tenant_id, "organ_gorevleri", id)?;
+            diesel::sql_query(
+                "UPDATE organ_gorevleri SET organ = ?1, komisyon_adi = ?2, uye_id = ?3, gorev = ?4, sira = ?5,
+                    baslangic_tarihi = ?6, bitis_tarihi = ?7, genel_kurul_id = ?8, tahsilat_yetkisi = ?9, updated_at = ?10
+                 WHERE id = ?11 AND tenant_id = ?12",
+            )
+            .bind::<diesel::sql_types::Text, _>(&g.organ)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(komisyon)
+            .bind::<diesel::sql_types::Text, _>(&g.uye_id)
+            .bind::<diesel::sql_types::Text, _>(&g.gorev)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(sira)
+            .bind::<diesel::sql_types::Text, _>(&g.baslangic_tarihi[..10])
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bitis)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(genel_kurul_id)
+            .bind::<diesel::sql_types::Bool, _>(g.tahsilat_yetkisi)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            id.to_string()
+        }
+        None => {
+            let id = Uuid::new_v4().to_string();
+            diesel::sql_query(
+                "INSERT INTO organ_gorevleri (id, tenant_id, organ, komisyon_adi, uye_id, gorev, sira, baslangic_tarihi,
+                    bitis_tarihi, genel_kurul_id, tahsilat_yetkisi, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(&g.organ)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(komisyon)
+            .bind::<diesel::sql_types::Text, _>(&g.uye_id)
+            .bind::<diesel::sql_types::Text, _>(&g.gorev)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(sira)
+            .bind::<diesel::sql_types::Text, _>(&g.baslangic_tarihi[..10])
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bitis)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(genel_kurul_id)
+            .bind::<diesel::sql_types::Bool, _>(g.tahsilat_yetkisi)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            denetim.yeni_kayit(tenant_id, "organ_gorevleri", &id);
+            id
+        }
+    };
+    Ok(id)
+}
+
+fn gorevi_kapat(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    gorev_id: &str,
+    son_gun: NaiveDate,
+    neden: &str,
+) -> Result<(), String> {
+    denetim.izle(conn, tenant_id, "organ_gorevleri", gorev_id)?;
+    diesel::sql_query(
+        "UPDATE organ_gorevleri SET bitis_tarihi = ?1, ayrilma_nedeni = ?2, updated_at = ?3 WHERE id = ?4 AND tenant_id = ?5",
+    )
+    .bind::<diesel::sql_types::Text, _>(gun(son_gun))
+    .bind::<diesel::sql_types::Text, _>(neden)
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(gorev_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Görevi `son_gun` itibarıyla sonlandırır. Asil üye ayrılıyorsa ve yedek_cagir verildiyse
+/// sıradaki yedek ertesi gün asil üyeliğe geçer; yeni görev kaydının id'si döner.
+pub fn gorevden_ayril(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    gorev_id: &str,
+    son_gun: NaiveDate,
+    neden: &str,
+    yedek_cagir: bool,
+) -> Result<Option<String>, String> {
+    let gorev = gorev_getir(conn, tenant_id, gorev_id)?;
+    if neden.trim().is_empty() {
+        return Err("Ayrılma nedeni zorunludur".to_string());
+    }
+    if gorev.ayrilma_nedeni.is_some() {
+        return Err("Üye bu görevden zaten ayrılmış".to_string());
+    }
+    if gun(son_gun) < gorev.baslangic_tarihi || gorev.bitis_tarihi.as_deref().is_some_and(|b| gun(son_gun).as_str() > b) {
+        return Err("Ayrılma tarihi görev dönemi içinde olmalıdır".to_string());
+    }
+    gorevi_kapat(conn, denetim, tenant_id, gorev_id, son_gun, neden.trim())?;
+    if !yedek_cagir || gorev.gorev == "yedek_uye" {
+        return Ok(None);
+    }
+
+    let ertesi = son_gun + Duration::days(1);
+    let yedek = gorevleri_getir(conn, tenant_id, Some(&gorev.organ), Some(&gun(ertesi)))?
+        .into_iter()
+        .find(|g| g.gorev == "yedek_uye" && g.komisyon_adi == gorev.komisyon_adi)
+        .ok_or("Göreve çağrılacak yedek üye yok")?;
+    gorevi_kapat(conn, denetim, tenant_id, &yedek.id, son_gun, "Asil üyeliğe geçti")?;
+    let yeni = GorevGirdi {
+        organ: gorev.organ.clone(),
+        komisyon_adi: gorev.komisyon_adi.clone(),
+        uye_id: yedek.uye_id.clone(),
+        gorev: "uye".to_string(),
+        sira: None,
+        baslangic_tarihi: gun(ertesi),
+        bitis_tarihi: yedek.bitis_tarihi.clone(),
+        genel_kurul_id: yedek.genel_kurul_id.clone(),
+        tahsilat_yetkisi: false,
+    };
+    gorev_kaydet(conn, denetim, tenant_id, None, &yeni).map(Some)
+}
+
+/// Genel kurulun sonuçlanmış seçimlerinden kurulların yeni dönemini oluşturur: görevdeki
+/// kurul üyelerinin dönemi genel kurulun önceki günü kapanır, kazanan asiller "uye",
+/// yedekler sıralarıyla "yedek_uye" olarak genel kurul tarihinden başlar. Görev dağılımı
+/// (başkan, sayman...) kurulun ilk toplantısından sonra gorev_kaydet ile yapılır.
+pub fn secimden_gorevlendir(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    bitis_tarihi: Option<&str>,
+) -> Result<Vec<String>, String> {
+    #[derive(QueryableByName)]
+    struct SecimRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        organ: String,
+    }
+    #[derive(QueryableByName)]
+    struct SayiRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    let gk = genel_kurul::genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    let baslangic = tarih_coz(&gk.tarih, "genel kurul tarihi")?;
+    let secimler: Vec<SecimRow> = diesel::sql_query(
+        "SELECT id, organ FROM genel_kurul_secimleri
+         WHERE tenant_id = ?1 AND genel_kurul_id = ?2 AND sonuclandi = 1 ORDER BY organ",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    if secimler.is_empty() {
+        return Err("Genel kurulda sonuçlanmış seçim yok".to_string());
+    }
+    let yapilmis: SayiRow =
+        diesel::sql_query("SELECT COUNT(*) AS sayi FROM organ_gorevleri WHERE tenant_id = ?1 AND genel_kurul_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(genel_kurul_id)
+            .get_result(conn)
+            .map_err(|e| e.to_string())?;
+    if yapilmis.sayi > 0 {
+        return Err("Bu genel kurulun seçtiği üyeler zaten görevlendirilmiş".to_string());
+    }
+
+    let mut idler = Vec::new();
+    for secim in secimler.iter().filter(|s| ORGANLAR.contains(&s.organ.as_str())) {
+        // Önceki dönem genel kurulun önceki günü kapanır
+        for eski in gorevleri_getir(conn, tenant_id, Some(&secim.organ), Some(&gun(baslangic)))? {
+            gorevi_kapat(conn, denetim, tenant_id, &eski.id, baslangic - Duration::days(1), "Görev süresi doldu")?;
+        }
+        let mut yedek_sirasi = 0;
+        for aday in genel_kurul::adaylari_getir(conn, tenant_id, &secim.id)? {
+            let gorev = match aday.sonuc.as_deref() {
+                Some("asil") => "uye",
+                Some("yedek") => "yedek_uye",
+                _ => continue,
+            };
+            let sira = if gorev == "yedek_uye" {
+                yedek_sirasi += 1;
+                Some(yedek_sirasi)
+            } else {
+                None
+            };
+            let girdi = GorevGirdi {
+                organ: secim.organ.clone(),
+                komisyon_adi: None,
+                uye_id: aday.uye_id.clone(),
+                gorev: gorev.to_string(),
+                sira,
+                baslangic_tarihi: gun(baslangic),
+                bitis_tarihi: bitis_tarihi.map(str::to_string),
+                genel_kurul_id: Some(genel_kurul_id.to_string()),
+                tahsilat_yetkisi: false,
+            };
+            idler.push(gorev_kaydet(conn, denetim, tenant_id, None, &girdi)?);
+        }
+    }
+    Ok(idler)
+}
+
+/// Kurulun tarihteki üye tamsayısı ve toplantı yeter sayısı; kurulun kayıtlı üyesi yoksa None
+pub fn yetersayi(conn: &mut SqliteConnection, tenant_id: &str, organ: &str, tarih: &str) -> Result<Option<OrganYetersayisi>, String> {
+    let asiller = gorevleri_getir(conn, tenant_id, Some(organ), Some(tarih))?
+        .into_iter()
+        .filter(|g| g.gorev != "yedek_uye" && g.komisyon_adi.is_none())
+        .count() as i64;
+    Ok((asiller > 0).then(|| OrganYetersayisi {
+        organ: organ.to_string(),
+        uye_tamsayisi: asiller,
+        gerekli: asiller / 2 + 1,
+    }))
+}
+
+/// Kurul toplantısında oy kullananları denetler: kurulun kayıtlı üyeleri varsa oy kullanan
+/// herkes o gün kurulun asil üyesi olmalı ve sayıları yeter sayıyı karşılamalıdır.
+/// `oy_kullananlar` katılımcıların üye id'leridir (üyeye bağlı olmayan katılımcı için None).
+pub fn toplanti_katilimini_dogrula(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    organ: &str,
+    tarih: &str,
+    oy_kullananlar: &[Option<&str>],
+) -> Result<(), String> {
+    let Some(ys) = yetersayi(conn, tenant_id, organ, tarih)? else {
+        return Ok(());
+    };
+    let asiller: HashSet<String> = gorevleri_getir(conn, tenant_id, Some(organ), Some(tarih))?
+        .into_iter()
+        .filter(|g| g.gorev != "yedek_uye" && g.komisyon_adi.is_none())
+        .map(|g| g.uye_id)
+        .collect();
+    if oy_kullananlar.iter().any(|u| !u.is_some_and(|u| asiller.contains(u))) {
+        return Err(format!("Oy kullananlardan biri {} tarihinde {} asil üyesi değil", tarih, genel_kurul::organ_adi(organ)));
+    }
+    let katilan = oy_kullananlar.len() as i64;
+    if katilan < ys.gerekli {
+        return Err(format!(
+            "{} toplantı yeter sayısı sağlanmadı: üye tamsayısı {}, gerekli {}, katılan {}",
+            genel_kurul::organ_adi(organ),
+            ys.uye_tamsayisi,
+            ys.gerekli,
+            katilan
+        ));
+    }
+    Ok(())
+}
+
+/// Tarihte tahsilat yetkisi olan görevli; yetki verilmiş kimse yoksa yönetim kurulu saymanı
+pub fn tahsil_yetkilisi(conn: &mut SqliteConnection, tenant_id: &str, tarih: &str) -> Result<Option<String>, String> {
+    #[derive(QueryableByName)]
+    struct AdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+    }
+
+    let gun = tarih.get(..10).unwrap_or(tarih);
+    let yetkili: Option<AdRow> = diesel::sql_query(
+        "SELECT u.ad_soyad FROM organ_gorevleri g
+         JOIN uyeler u ON u.id = g.uye_id AND u.tenant_id = g.tenant_id
+         WHERE g.tenant_id = ?1 AND g.baslangic_tarihi <= ?2 AND (g.bitis_tarihi IS NULL OR g.bitis_tarihi >= ?2)
+           AND g.gorev != 'yedek_uye'
+           AND (g.tahsilat_yetkisi = 1 OR (g.organ = 'yonetim_kurulu' AND g.gorev = 'sayman'))
+         ORDER BY g.tahsilat_yetkisi DESC, g.gorev = 'sayman' DESC, g.baslangic_tarihi
+         LIMIT 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(gun)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+    Ok(yetkili.map(|y| y.ad_soyad))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    const TARIH: &str = "2026-03-01";
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        for (id, no, ad) in [
+            ("u1", "1", "Ali Veli"),
+            ("u2", "2", "Ayşe Kaya"),
+            ("u3", "3", "Can Er"),
+            ("u4", "4", "Deniz Ak"),
+            ("u5", "5", "Ece Su"),
+        ] {
+            test_db::uye(&mut conn, "t1", id, no, ad);
+        }
+        conn
+    }
+
+    fn girdi(organ: &str, uye_id: &str, gorev: &str, sira: Option<i32>) -> GorevGirdi {
+        GorevGirdi {
+            organ: organ.to_string(),
+            komisyon_adi: None,
+            uye_id: uye_id.to_string(),
+            gorev: gorev.to_string(),
+            sira,
+            baslangic_tarihi: "2026-01-10".to_string(),
+            bitis_tarihi: Some("2029-01-09".to_string()),
+            genel_kurul_id: None,
+            tahsilat_yetkisi: false,
+        }
+    }
+
+    fn kaydet(conn: &mut SqliteConnection, organ: &str, uye_id: &str, gorev: &str, sira: Option<i32>) -> Result<String, String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        gorev_kaydet(conn, &mut d, "t1", None, &girdi(organ, uye_id, gorev, sira))
+    }
+
+    /// YK: u1 başkan, u2 sayman (asil), u3 birinci yedek
+    fn yonetim_kurulu(conn: &mut SqliteConnection) -> String {
+        let baskan = kaydet(conn, "yonetim_kurulu", "u1", "baskan", None).unwrap();
+        kaydet(conn, "yonetim_kurulu", "u2", "sayman", None).unwrap();
+        kaydet(conn, "yonetim_kurulu", "u3", "yedek_uye", Some(1)).unwrap();
+        baskan
+    }
+
+    fn katilim(conn: &mut SqliteConnection, uyeler: &[&str]) -> Result<(), String> {
+        let oy: Vec<Option<&str>> = uyeler.iter().map(|u| Some(*u)).collect();
+        toplanti_katilimini_dogrula(conn, "t1", "yonetim_kurulu", TARIH, &oy)
+    }
+
+    #[test]
+    fn tekil_gorev_ayni_donemde_ikinci_kisiye_verilemez() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        assert!(kaydet(&mut conn, "yonetim_kurulu", "u4", "baskan", None).unwrap_err().contains("Başkan"));
+        kaydet(&mut conn, "yonetim_kurulu", "u4", "uye", None).unwrap();
+    }
+
+    #[test]
+    fn uye_ayni_organda_iki_goreve_yazilamaz() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        assert!(kaydet(&mut conn, "yonetim_kurulu", "u1", "uye", None).is_err());
+    }
+
+    #[test]
+    fn yonetim_ve_denetim_kurulu_birlikte_tutulamaz() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        assert!(kaydet(&mut conn, "denetim_kurulu", "u2", "uye", None).is_err());
+        // Disiplin kurulu için bu kısıt yok; sayman yalnızca yönetim kurulunda
+        kaydet(&mut conn, "disiplin_kurulu", "u2", "uye", None).unwrap();
+        assert!(kaydet(&mut conn, "denetim_kurulu", "u4", "sayman", None).is_err());
+    }
+
+    #[test]
+    fn yetersayi_tamsayinin_salt_cogunlugudur() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        // Yedek üye tamsayıya girmez: 2 asil → 2
+        let ys = yetersayi(&mut conn, "t1", "yonetim_kurulu", TARIH).unwrap().unwrap();
+        assert_eq!((ys.uye_tamsayisi, ys.gerekli), (2, 2));
+
+        kaydet(&mut conn, "yonetim_kurulu", "u4", "uye", None).unwrap();
+        let ys = yetersayi(&mut conn, "t1", "yonetim_kurulu", TARIH).unwrap().unwrap();
+        assert_eq!((ys.uye_tamsayisi, ys.gerekli), (3, 2));
+        assert!(katilim(&mut conn, &["u1"]).unwrap_err().contains("yeter sayısı"));
+        katilim(&mut conn, &["u1", "u4"]).unwrap();
+
+        kaydet(&mut conn, "yonetim_kurulu", "u5", "uye", None).unwrap();
+        let ys = yetersayi(&mut conn, "t1", "yonetim_kurulu", TARIH).unwrap().unwrap();
+        assert_eq!((ys.uye_tamsayisi, ys.gerekli), (4, 3));
+        assert!(katilim(&mut conn, &["u1", "u4"]).is_err());
+        katilim(&mut conn, &["u1", "u2", "u5"]).unwrap();
+    }
+
+    #[test]
+    fn oy_kullanan_kurulun_asil_uyesi_olmali() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        assert!(katilim(&mut conn, &["u1", "u3"]).unwrap_err().contains("asil üyesi değil"));
+        katilim(&mut conn, &["u1", "u2"]).unwrap();
+    }
+
+    #[test]
+    fn kayitli_uyesi_olmayan_kurul_denetlenmez() {
+        let mut conn = test_db();
+        assert!(yetersayi(&mut conn, "t1", "denetim_kurulu", TARIH).unwrap().is_none());
+        toplanti_katilimini_dogrula(&mut conn, "t1", "denetim_kurulu", TARIH, &[None]).unwrap();
+    }
+
+    #[test]
+    fn tahsilat_yetkilisi_yoksa_sayman_tahsil_eder() {
+        let mut conn = test_db();
+        assert_eq!(tahsil_yetkilisi(&mut conn, "t1", "2026-03-01 10:00").unwrap(), None);
+        yonetim_kurulu(&mut conn);
+        assert_eq!(tahsil_yetkilisi(&mut conn, "t1", "2026-03-01 10:00").unwrap().as_deref(), Some("Ayşe Kaya"));
+    }
+
+    #[test]
+    fn ayrilan_asilin_yerine_siradaki_yedek_ertesi_gun_gecer() {
+        let mut conn = test_db();
+        let baskan = yonetim_kurulu(&mut conn);
+        let mut d = Denetim::yeni(None, None, "test");
+        let son_gun = NaiveDate::from_ymd_opt(2026, 6, 30).unwrap();
+
+        let yeni = gorevden_ayril(&mut conn, &mut d, "t1", &baskan, son_gun, "İstifa", true).unwrap().unwrap();
+        let yeni = gorev_getir(&mut conn, "t1", &yeni).unwrap();
+        assert_eq!((yeni.uye_id.as_str(), yeni.gorev.as_str(), yeni.baslangic_tarihi.as_str()), ("u3", "uye", "2026-07-01"));
+        assert_eq!(yeni.bitis_tarihi.as_deref(), Some("2029-01-09"));
+        let temmuz: Vec<String> = gorevleri_getir(&mut conn, "t1", Some("yonetim_kurulu"), Some("2026-07-01"))
+            .unwrap()
+            .into_iter()
+            .map(|g| g.uye_id)
+            .collect();
+        assert_eq!(temmuz, ["u2", "u3"]);
+        // Kapanmış görevden ikinci kez ayrılınamaz
+        assert!(gorevden_ayril(&mut conn, &mut d, "t1", &baskan, son_gun, "İstifa", false).is_err());
+
+        // Boşalan başkanlığa atama yapılabilir
+        let mut yeni_baskan = girdi("yonetim_kurulu", "u4", "baskan", None);
+        yeni_baskan.baslangic_tarihi = "2026-07-15".to_string();
+        gorev_kaydet(&mut conn, &mut d, "t1", None, &yeni_baskan).unwrap();
+    }
+
+    #[test]
+    fn donem_sonu_yaklasan_gorevler_listelenir() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        let bugun = NaiveDate::from_ymd_opt(2028, 12, 1).unwrap();
+        assert_eq!(biten_gorevler(&mut conn, "t1", bugun, 60).unwrap().len(), 3);
+        assert!(biten_gorevler(&mut conn, "t1", bugun, 30).unwrap().is_empty());
+    }
+}
@@ -88,12 +88,21 @@ struct OpRow {
     operation: String,
 }
 
-/// Tablonun gerçek kolon listesi. Tablo adı SYNCED_TABLES beyaz listesinden
-/// geldiği için interpolasyon güvenlidir.
+/// Sync yüzeyine yazılacak tablonun gerçek kolon listesi; yalnızca SYNCED_TABLES
+/// tabloları için. Tablo adı doğrulaması tablo_kolonlari içindedir.
 fn table_columns(conn: &mut SqliteConnection, table_name: &str) -> Result<Vec<String>, String> {
     if !is_synced_table(table_name) {
         return Err(format!("Sync dışı tablo: {}", table_name));
     }
+    tablo_kolonlari(conn, table_name)
+}
+
+/// Herhangi bir yerel tablonun kolon listesi (denetim görüntüleri sync dışı
+/// tabloları da kapsar). Tablo adı yalnızca harf/rakam/alt çizgi olabilir.
+fn tablo_kolonlari(conn: &mut SqliteConnection, table_name: &str) -> Result<Vec<String>, String> {
+    if table_name.is_empty() || !table_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Geçersiz tablo adı: {}", table_name));
+    }
     let rows: Vec<ColumnName> = diesel::sql_query(format!(
         "SELECT name FROM pragma_table_info('{}')",
         table_name
@@ -113,15 +122,18 @@ pub fn snapshot_row(
     record_id: &str,
     tenant_id: &str,
 ) -> Result<Option<serde_json::Value>, String> {
-    let cols = table_columns(conn, table_name)?;
+    let cols = tablo_kolonlari(conn, table_name)?;
     let pairs: Vec<String> = cols
         .iter()
         .map(|c| format!("'{}', \"{}\"", c, c))
         .collect();
+    // tenants satırının kiracısı kendisidir; tenant_id kolonu yoktur
+    let kiraci_kolonu = if table_name == "tenants" { "id" } else { "tenant_id" };
     let sql = format!(
-        "SELECT json_object({}) AS json FROM \"{}\" WHERE id = ?1 AND tenant_id = ?2",
+        "SELECT json_object({}) AS json FROM \"{}\" WHERE id = ?1 AND {} = ?2",
         pairs.join(", "),
-        table_name
+        table_name,
+        kiraci_kolonu
     );
     let row: Option<JsonRow> = diesel::sql_query(sql)
         .bind::<diesel::sql_types::Text, _>(record_id)
@@ -504,6 +516,24 @@ mod tests {
         assert_eq!(pending(&mut conn).len(), 0);
     }
 
+    #[test]
+    fn sync_disi_tablonun_goruntusu_alinir() {
+        let mut conn = setup();
+        diesel::sql_query("CREATE TABLE cari_hareketler (id TEXT PRIMARY KEY, tenant_id TEXT NOT NULL, tutar REAL)")
+            .execute(&mut conn)
+            .unwrap();
+        diesel::sql_query("INSERT INTO cari_hareketler VALUES ('h1', 't1', 12.5)")
+            .execute(&mut conn)
+            .unwrap();
+
+        // Denetim görüntüsü sync yüzeyine bağlı değildir; yazım fonksiyonları bağlıdır
+        let snap = snapshot_row(&mut conn, "cari_hareketler", "h1", "t1").unwrap().unwrap();
+        assert_eq!(snap["tutar"], 12.5);
+        assert!(snapshot_row(&mut conn, "cari_hareketler", "h1", "t2").unwrap().is_none());
+        assert!(snapshot_row(&mut conn, "x'; DROP TABLE kasalar; --", "h1", "t1").is_err());
+        assert!(apply_remote_delete(&mut conn, "t1", "cari_hareketler", "h1", Some(2)).is_err());
+    }
+
     #[test]
     fn apply_remote_upsert_turetilmisleri_uygulamaz() {
         let mut conn = setup();
@@ -0,0 +1,615 @@
+// Sabit noktalı para tipi
+//
+// Tüm tutarlar bellekte kuruş cinsinden i64 olarak taşınır; toplama/çıkarma
+// tamsayı aritmetiği ile yapılır, böylece binlerce bakiye yeniden hesaplamasında
+// f64 yuvarlama kayması oluşmaz.
+//
+// Depolama: SQLite kolonları REAL olarak kalır (sync payload'ları ve rapor
+// sorguları TL cinsinden sayı bekliyor), ancak yazılan her değer tam kuruşa
+// yuvarlanmış olur (bkz. migration 034). SQL tarafında toplamlar
+// `SUM(CAST(ROUND(kolon * 100) AS INTEGER))` ile kuruş olarak alınır.
+//
+// JSON: frontend ile sözleşme değişmez — TL cinsinden sayı olarak yazılır,
+// sayı veya "1.234,56" / "1234.56" biçimli metin olarak okunur.
+
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Double;
+use diesel::sqlite::{Sqlite, SqliteValue};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Double)]
+pub struct Para(i64);
+
+impl Para {
+    pub const SIFIR: Para = Para(0);
+
+    pub const fn from_kurus(kurus: i64) -> Self {
+        Para(kurus)
+    }
+
+    /// TL cinsinden f64 değeri en yakın kuruşa yuvarlar (NaN → 0).
+    pub fn from_tl(tl: f64) -> Self {
+        if !tl.is_finite() {
+            return Para(0);
+        }
+        Para((tl * 100.0).round() as i64)
+    }
+
+    pub const fn kurus(self) -> i64 {
+        self.0
+    }
+
+    /// Sadece sınır noktalarında (SQL bind, JSON, kur çarpımı) kullanılmalı.
+    pub fn tl(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub const fn abs(self) -> Self {
+        Para(self.0.abs())
+    }
+
+    /// Negatif sonuçları sıfıra çeker (kalan borç vb. için).
+    pub fn sifirdan_kucukse_sifir(self) -> Self {
+        if self.0 < 0 { Para(0) } else { self }
+    }
+
+    /// Oran/kur ile çarpar ve sonucu kuruşa yuvarlar (ör. kur dönüşümü).
+    pub fn carp(self, katsayi: f64) -> Self {
+        Para::from_kurus((self.0 as f64 * katsayi).round() as i64)
+    }
+
+    /// Yüzde uygular: `tutar.yuzde(18.0)` → tutarın %18'i, kuruşa yuvarlanmış.
+    pub fn yuzde(self, oran: f64) -> Self {
+        self.carp(oran / 100.0)
+    }
+
+    /// Tutarı `parca` eşit parçaya böler; artan kuruşlar ilk parçalara dağıtılır,
+    /// böylece parçaların toplamı her zaman tutara eşittir.
+    pub fn paylastir(self, parca: usize) -> Vec<Para> {
+        if parca == 0 {
+            return Vec::new();
+        }
+        let n = parca as i64;
+        let taban = self.0 / n;
+        let artan = self.0 % n;
+        (0..n)
+            .map(|i| {
+                if i < artan.abs() {
+                    Para(taban + artan.signum())
+                } else {
+                    Para(taban)
+                }
+            })
+            .collect()
+    }
+
+    /// Tutarı ağırlıklar oranında böler (en büyük kalan yöntemi); parçaların
+    /// toplamı her zaman tutara eşittir. Ağırlıklar toplamı sıfırsa eşit bölünür.
+    pub fn agirlikli_paylastir(self, agirliklar: &[i64]) -> Vec<Para> {
+        let toplam: i128 = agirliklar.iter().map(|a| *a as i128).sum();
+        if toplam <= 0 {
+            return self.paylastir(agirliklar.len());
+        }
+        let mut parcalar: Vec<i64> = Vec::with_capacity(agirliklar.len());
+        let mut kalanlar: Vec<(i128, usize)> = Vec::with_capacity(agirliklar.len());
+        for (i, a) in agirliklar.iter().enumerate() {
+            let pay = self.0 as i128 * *a as i128;
+            parcalar.push((pay / toplam) as i64);
+            kalanlar.push(((pay % toplam).abs(), i));
+        }
+        // Artan kuruşlar en büyük kalanı olan parçalara (eşitlikte öndekine)
+        kalanlar.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        let artan = self.0 - parcalar.iter().sum::<i64>();
+        for (_, i) in kalanlar.iter().take(artan.unsigned_abs() as usize) {
+            parcalar[*i] += artan.signum();
+        }
+        parcalar.into_iter().map(Para).collect()
+    }
+
+    /// Türkçe gösterim: 1.234,56
+    pub fn tr_format(self) -> String {
+        let negatif = self.0 < 0;
+        let mutlak = self.0.unsigned_abs();
+        let lira = (mutlak / 100).to_string();
+        let kurus = mutlak % 100;
+
+        let mut gruplu = String::with_capacity(lira.len() + lira.len() / 3);
+        for (i, c) in lira.chars().enumerate() {
+            if i > 0 && (lira.len() - i).is_multiple_of(3) {
+                gruplu.push('.');
+            }
+            gruplu.push(c);
+        }
+
+        format!("{}{},{:02}", if negatif { "-" } else { "" }, gruplu, kurus)
+    }
+
+    /// Makbuzlar için yazıyla tutar: 1.250,30 → "bin iki yüz elli Türk lirası otuz kuruş"
+    pub fn yaziyla(self) -> String {
+        let mutlak = self.0.unsigned_abs();
+        let lira = mutlak / 100;
+        let kurus = mutlak % 100;
+
+        let mut parcalar = Vec::new();
+        if self.0 < 0 {
+            parcalar.push("eksi".to_string());
+        }
+        if lira > 0 || kurus == 0 {
+            parcalar.push(sayi_yaziyla(lira));
+            parcalar.push("Türk lirası".to_string());
+        }
+        if kurus > 0 {
+            parcalar.push(sayi_yaziyla(kurus));
+            parcalar.push("kuruş".to_string());
+        }
+        parcalar.join(" ")
+    }
+}
+
+fn sayi_yaziyla(n: u64) -> String {
+    const BIRLER: [&str; 10] = ["", "bir", "iki", "üç", "dört", "beş", "altı", "yedi", "sekiz", "dokuz"];
+    const ONLAR: [&str; 10] = ["", "on", "yirmi", "otuz", "kırk", "elli", "altmış", "yetmiş", "seksen", "doksan"];
+    const BOLUKLER: [&str; 7] = ["", "bin", "milyon", "milyar", "trilyon", "katrilyon", "kentilyon"];
+
+    if n == 0 {
+        return "sıfır".to_string();
+    }
+
+    let mut gruplar = Vec::new();
+    let mut kalan = n;
+    while kalan > 0 {
+        gruplar.push((kalan % 1000) as usize);
+        kalan /= 1000;
+    }
+
+    let mut kelimeler: Vec<&str> = Vec::new();
+    for (i, &grup) in gruplar.iter().enumerate().rev() {
+        if grup == 0 {
+            continue;
+        }
+        // "bir bin" denmez, yalnızca "bin"
+        if !(i == 1 && grup == 1) {
+            let (yuz, on, bir) = (grup / 100, grup / 10 % 10, grup % 10);
+            if yuz > 1 {
+                kelimeler.push(BIRLER[yuz]);
+            }
+            if yuz > 0 {
+                kelimeler.push("yüz");
+            }
+            if on > 0 {
+                kelimeler.push(ONLAR[on]);
+            }
+            if bir > 0 {
+                kelimeler.push(BIRLER[bir]);
+            }
+        }
+        if i > 0 {
+            kelimeler.push(BOLUKLER[i]);
+        }
+    }
+    kelimeler.join(" ")
+}
+
+impl fmt::Display for Para {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let isaret = if self.0 < 0 { "-" } else { "" };
+        let mutlak = self.0.unsigned_abs();
+        write!(f, "{}{}.{:02}", isaret, mutlak / 100, mutlak % 100)
+    }
+}
+
+impl FromStr for Para {
+    type Err = String;
+
+    /// "1234.56", "1234,56", "1.234,56", "1,234.56", "-12", "₺ 12,5" biçimlerini kabul eder.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let temiz: String = s
+            .trim()
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '₺' && *c != '\'')
+            .collect();
+        let temiz = temiz.trim_end_matches("TL").trim_end_matches("TRY");
+        if temiz.is_empty() {
+            return Err(format!("Geçersiz tutar: '{}'", s));
+        }
+
+        let (negatif, govde) = match temiz.strip_prefix('-') {
+            Some(r) => (true, r),
+            None => (false, temiz.strip_prefix('+').unwrap_or(temiz)),
+        };
+
+        // Ondalık ayırıcı: son görülen '.' veya ',' — ardından 1-2 hane geliyorsa.
+        // Ardından 3 hane geliyorsa binlik ayırıcıdır; başka uzunluk geçersizdir.
+        let gecersiz = || format!("Geçersiz tutar: '{}'", s);
+        let (tam_kisim, ondalik, ondalik_ayirici) = match govde.rfind(['.', ',']) {
+            Some(pos) => match govde.len() - pos - 1 {
+                1 | 2 => (&govde[..pos], &govde[pos + 1..], govde[pos..].chars().next()),
+                3 => (govde, "", None),
+                _ => return Err(gecersiz()),
+            },
+            None => (govde, "", None),
+        };
+
+        // Binlik gruplar: ilk grup sıfırla başlamayan 1-3 hane, diğerleri tam 3 hane;
+        // tek tür ayırıcı ve ondalık ayırıcıdan farklı olmalı ("0.125", "1234.567" reddedilir).
+        let mut binlik_ayiricilar = tam_kisim.chars().filter(|c| *c == '.' || *c == ',');
+        let tam_rakamlar: String = match binlik_ayiricilar.next() {
+            None => tam_kisim.to_string(),
+            Some(ayirici) => {
+                if binlik_ayiricilar.any(|c| c != ayirici) || ondalik_ayirici == Some(ayirici) {
+                    return Err(gecersiz());
+                }
+                let gruplar: Vec<&str> = tam_kisim.split(ayirici).collect();
+                let ilk = gruplar[0];
+                if ilk.is_empty() || ilk.len() > 3 || ilk.starts_with('0') || gruplar[1..].iter().any(|g| g.len() != 3) {
+                    return Err(gecersiz());
+                }
+                gruplar.concat()
+            }
+        };
+        if tam_rakamlar.is_empty() && ondalik.is_empty() {
+            return Err(gecersiz());
+        }
+        if !tam_rakamlar.chars().all(|c| c.is_ascii_digit()) || !ondalik.chars().all(|c| c.is_ascii_digit()) {
+            return Err(gecersiz());
+        }
+
+        let lira: i64 = if tam_rakamlar.is_empty() {
+            0
+        } else {
+            tam_rakamlar.parse().map_err(|_| format!("Tutar çok büyük: '{}'", s))?
+        };
+        let kurus: i64 = match ondalik.len() {
+            0 => 0,
+            1 => ondalik.parse::<i64>().unwrap_or(0) * 10,
+            _ => ondalik.parse::<i64>().unwrap_or(0),
+        };
+
+        let toplam = lira
+            .checked_mul(100)
+            .and_then(|v| v.checked_add(kurus))
+            .ok_or_else(|| format!("Tutar çok büyük: '{}'", s))?;
+
+        Ok(Para(if negatif { -toplam } else { toplam }))
+    }
+}
+
+impl Add for Para {
+    type Output = Para;
+    fn add(self, rhs: Para) -> Para {
+        Para(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Para {
+    type Output = Para;
+    fn sub(self, rhs: Para) -> Para {
+        Para(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Para {
+    type Output = Para;
+    fn neg(self) -> Para {
+        Para(-self.0)
+    }
+}
+
+impl AddAssign for Para {
+    fn add_assign(&mut self, rhs: Para) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Para {
+    fn sub_assign(&mut self, rhs: Para) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Para {
+    fn sum<I: Iterator<Item = Para>>(iter: I) -> Para {
+        Para(iter.map(|p| p.0).sum())
+    }
+}
+
+impl<'a> Sum<&'a Para> for Para {
+    fn sum<I: Iterator<Item = &'a Para>>(iter: I) -> Para {
+        Para(iter.map(|p| p.0).sum())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Diesel: REAL kolonlarla okuma/yazma (yazarken tam kuruş değeri gider)
+// ---------------------------------------------------------------------------
+
+impl ToSql<Double, Sqlite> for Para {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(self.tl());
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Double, Sqlite> for Para {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let tl = <f64 as FromSql<Double, Sqlite>>::from_sql(value)?;
+        Ok(Para::from_tl(tl))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Serde: frontend sözleşmesi TL cinsinden sayı
+// ---------------------------------------------------------------------------
+
+impl Serialize for Para {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.tl())
+    }
+}
+
+impl<'de> Deserialize<'de> for Para {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ParaVisitor;
+
+        impl serde::de::Visitor<'_> for ParaVisitor {
+            type Value = Para;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("TL cinsinden sayı veya metin tutar")
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Para, E> {
+                if !v.is_finite() {
+                    return Err(E::custom("Geçersiz tutar"));
+                }
+                Ok(Para::from_tl(v))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Para, E> {
+                v.checked_mul(100).map(Para).ok_or_else(|| E::custom("Tutar çok büyük"))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Para, E> {
+                i64::try_from(v)
+                    .ok()
+                    .and_then(|v| v.checked_mul(100))
+                    .map(Para)
+                    .ok_or_else(|| E::custom("Tutar çok büyük"))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Para, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(ParaVisitor)
+    }
+}
+
+/// `SELECT COALESCE(SUM(CAST(ROUND(x * 100) AS INTEGER)), 0) AS kurus` sonuçları için.
+#[derive(diesel::QueryableByName)]
+pub struct KurusToplam {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub kurus: i64,
+}
+
+impl KurusToplam {
+    pub fn para(&self) -> Para {
+        Para::from_kurus(self.kurus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binlerce_toplamada_kayma_olmaz() {
+        let mut f = 0.0_f64;
+        let mut p = Para::SIFIR;
+        for _ in 0..100_000 {
+            f += 0.1;
+            p += Para::from_tl(0.1);
+        }
+        assert_ne!(f, 10_000.0); // f64 kayıyor
+        assert_eq!(p, Para::from_kurus(1_000_000));
+        assert_eq!(p.tl(), 10_000.0);
+    }
+
+    #[test]
+    fn metin_ayristirma() {
+        assert_eq!("1234.56".parse::<Para>().unwrap().kurus(), 123_456);
+        assert_eq!("1234,56".parse::<Para>().unwrap().kurus(), 123_456);
+        assert_eq!("1.234,56".parse::<Para>().unwrap().kurus(), 123_456);
+        assert_eq!("1,234.56".parse::<Para>().unwrap().kurus(), 123_456);
+        assert_eq!("1.234.567".parse::<Para>().unwrap().kurus(), 123_456_700);
+        assert_eq!("-12,5".parse::<Para>().unwrap().kurus(), -1_250);
+        assert_eq!("₺ 100 TL".parse::<Para>().unwrap().kurus(), 10_000);
+        assert!("abc".parse::<Para>().is_err());
+        assert!("".parse::<Para>().is_err());
+    }
+
+    #[test]
+    fn parse_binlik_grubu_yalnizca_uc_hane() {
+        assert_eq!("1,234".parse::<Para>().unwrap().kurus(), 123_400);
+        assert_eq!("12.345.678,9".parse::<Para>().unwrap().kurus(), 1_234_567_890);
+        assert_eq!(",5".parse::<Para>().unwrap().kurus(), 50);
+        // Üç haneli kesir tutar değildir; binlik grubu olarak da okunamaz
+        assert!("0.125".parse::<Para>().is_err());
+        assert!("0,125".parse::<Para>().is_err());
+        assert!("1234.567".parse::<Para>().is_err());
+        assert!("1.2345".parse::<Para>().is_err());
+        assert!("12.34.567".parse::<Para>().is_err());
+        assert!("012.345".parse::<Para>().is_err());
+        // Aynı işaret hem binlik hem ondalık olamaz; karışık binlik ayırıcı geçersiz
+        assert!("1.234.56".parse::<Para>().is_err());
+        assert!("1.234,567".parse::<Para>().is_err());
+        assert!("1,234.567,00".parse::<Para>().is_err());
+        assert!("12,".parse::<Para>().is_err());
+    }
+
+    #[test]
+    fn yaziyla_gosterim() {
+        assert_eq!(Para::from_kurus(125_030).yaziyla(), "bin iki yüz elli Türk lirası otuz kuruş");
+        assert_eq!(Para::from_tl(101.0).yaziyla(), "yüz bir Türk lirası");
+        assert_eq!(Para::from_tl(2_001_000.0).yaziyla(), "iki milyon bin Türk lirası");
+        assert_eq!(Para::from_kurus(5).yaziyla(), "beş kuruş");
+        assert_eq!(Para::SIFIR.yaziyla(), "sıfır Türk lirası");
+    }
+
+    #[test]
+    fn gosterim() {
+        assert_eq!(Para::from_kurus(123_456_789).tr_format(), "1.234.567,89");
+        assert_eq!(Para::from_kurus(-5).tr_format(), "-0,05");
+        assert_eq!(Para::from_kurus(-1_050).to_string(), "-10.50");
+    }
+
+    #[test]
+    fn paylastirma_toplami_korur() {
+        let parcalar = Para::from_kurus(10_000).paylastir(3);
+        assert_eq!(parcalar, vec![Para::from_kurus(3_334), Para::from_kurus(3_333), Para::from_kurus(3_333)]);
+        assert_eq!(parcalar.iter().sum::<Para>(), Para::from_kurus(10_000));
+        assert!(Para::from_kurus(5).paylastir(0).is_empty());
+
+        let parcalar = Para::from_kurus(10_000).agirlikli_paylastir(&[2, 1, 1]);
+        assert_eq!(parcalar, vec![Para::from_kurus(5_000), Para::from_kurus(2_500), Para::from_kurus(2_500)]);
+        let parcalar = Para::from_kurus(100).agirlikli_paylastir(&[1, 1, 1]);
+        assert_eq!(parcalar, Para::from_kurus(100).paylastir(3));
+        let parcalar = Para::from_kurus(-1_001).agirlikli_paylastir(&[3, 2]);
+        assert_eq!(parcalar.iter().sum::<Para>(), Para::from_kurus(-1_001));
+        assert_eq!(Para::from_kurus(10).agirlikli_paylastir(&[0, 0]), Para::from_kurus(10).paylastir(2));
+    }
+
+    #[test]
+    fn serde_tl_sayi_sozlesmesi() {
+        let p: Para = serde_json::from_str("12.345").unwrap();
+        assert_eq!(p.kurus(), 1_235);
+        let p: Para = serde_json::from_str("7").unwrap();
+        assert_eq!(p.kurus(), 700);
+        let p: Para = serde_json::from_str("\"1.250,75\"").unwrap();
+        assert_eq!(p.kurus(), 125_075);
+        assert_eq!(serde_json::to_string(&Para::from_kurus(1_050)).unwrap(), "10.5");
+    }
+
+    #[test]
+    fn sqlite_real_kolon_gidis_donus() {
+        use diesel::prelude::*;
+        use diesel::sqlite::SqliteConnection;
+
+        #[derive(QueryableByName)]
+        struct Satir {
+            #[diesel(sql_type = Double)]
+            tutar: Para,
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query("CREATE TABLE t (tutar REAL)").execute(&mut conn).unwrap();
+        for _ in 0..1000 {
+            diesel::sql_query("INSERT INTO t (tutar) VALUES (?1)")
+                .bind::<Double, _>(Para::from_kurus(10))
+                .execute(&mut conn)
+                .unwrap();
+        }
+
+        let toplam = diesel::sql_query(
+            "SELECT COALESCE(SUM(CAST(ROUND(tutar * 100) AS INTEGER)), 0) AS kurus FROM t",
+        )
+        .get_result::<KurusToplam>(&mut conn)
+        .unwrap();
+        assert_eq!(toplam.para(), Para::from_kurus(10_000));
+
+        let satir = diesel::sql_query("SELECT tutar FROM t LIMIT 1")
+            .get_result::<Satir>(&mut conn)
+            .unwrap();
+        assert_eq!(satir.tutar, Para::from_kurus(10));
+    }
+
+    #[test]
+    fn eski_devirli_kasaya_kesim_tarihi_yazilir() {
+        use crate::db::test_db;
+        use diesel::prelude::*;
+
+        #[derive(QueryableByName)]
+        struct Devir {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            id: String,
+            #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+            devir_tarihi: Option<String>,
+            #[diesel(sql_type = Double)]
+            devir_bakiye: Para,
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            hesaplanan: i64,
+        }
+
+        let mut conn = test_db::surumden_once("034");
+        test_db::kiraci(&mut conn, "t1", "Dernek");
+        // k1: 2024 hareketleri (1.000 TL) eski devirle devir_bakiye'ye alınmış, 2025'te +150 TL
+        // k2: devir tutarı hareketlerle açıklanamıyor; k3: devri olmayan kasa
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, devir_bakiye, fiziksel_bakiye, bakiye) VALUES
+                ('k1', 't1', 'Merkez', 1000, 1150, 1150),
+                ('k2', 't1', 'Banka', 500, 700, 700),
+                ('k3', 't1', 'Yeni', 0, 80, 80)",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar) VALUES
+                ('g1', 't1', 'k1', '2024-03-01', 600), ('g2', 't1', 'k1', '2024-06-01', 400),
+                ('g3', 't1', 'k1', '2025-02-01', 200), ('g4', 't1', 'k2', '2024-05-01', 100),
+                ('g5', 't1', 'k3', '2025-01-10', 80)",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO giderler (id, tenant_id, kasa_id, tarih, tutar) VALUES ('d1', 't1', 'k1', '2025-03-01 10:00:00', 50)",
+        );
+        crate::db::connection::run_migrations(&mut conn).unwrap();
+
+        // Uygulamanın bakiye formülü: devir + devir_tarihi sonrası hareketler
+        let satirlar = diesel::sql_query(
+            "SELECT k.id, k.devir_tarihi, COALESCE(k.devir_bakiye, 0) AS devir_bakiye,
+                    CAST(ROUND(COALESCE(k.devir_bakiye, 0) * 100) AS INTEGER)
+                    + (SELECT COALESCE(SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)), 0) FROM gelirler g
+                        WHERE g.kasa_id = k.id AND (k.devir_tarihi IS NULL OR g.tarih >= k.devir_tarihi))
+                    - (SELECT COALESCE(SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)), 0) FROM giderler g
+                        WHERE g.kasa_id = k.id AND (k.devir_tarihi IS NULL OR g.tarih >= k.devir_tarihi)) AS hesaplanan
+             FROM kasalar k ORDER BY k.id",
+        )
+        .load::<Devir>(&mut conn)
+        .unwrap();
+
+        let k1 = &satirlar[0];
+        assert_eq!(k1.id, "k1");
+        assert_eq!(k1.devir_tarihi.as_deref(), Some("2025-02-01"));
+        assert_eq!(k1.devir_bakiye, Para::from_kurus(100_000));
+        assert_eq!(k1.hesaplanan, 115_000);
+
+        let k2 = &satirlar[1];
+        assert_eq!(k2.devir_tarihi.as_deref(), Some("2024-05-02"));
+        assert_eq!(k2.devir_bakiye, Para::from_kurus(70_000));
+        assert_eq!(k2.hesaplanan, 70_000);
+
+        let k3 = &satirlar[2];
+        assert_eq!(k3.devir_tarihi, None);
+        assert_eq!(k3.hesaplanan, 8_000);
+    }
+}
@@ -0,0 +1,600 @@
+// Yazdırılabilir belgeler için küçük PDF yazıcı (makbuz, hesap ekstresi).
+//
+// Harici kütüphane kullanılmaz: PDF'in standart 14 fontundan Helvetica ve
+// Helvetica-Bold gömülmeden kullanılır. Türkçe karakterler WinAnsiEncoding
+// üzerine Differences dizisiyle Latin-5 (ISO-8859-9) konumlarına yerleştirilir:
+// Ğ=0xD0, İ=0xDD, Ş=0xDE, ğ=0xF0, ı=0xFD, ş=0xFE. Ç, Ö, Ü, ç, ö, ü WinAnsi'de
+// zaten aynı konumdadır. Metin genişlikleri Adobe AFM değerleriyle hesaplanır
+// (sağa hizalı tutarlar ve kolon taşması için).
+//
+// Koordinatlar A4 sayfada (595 × 842 pt) sol üst köşeden, aşağı doğru verilir;
+// PDF'in alttan yukarı sistemine yazım sırasında çevrilir.
+//
+// Logo: JPEG (DCTDecode) olduğu gibi gömülür. PNG'de IDAT verisi zaten zlib
+// akışı olduğundan FlateDecode + PNG predictor ile açılmadan gömülür; bunun için
+// 8 bit gri/RGB veya paletli, interlace'sız olmalıdır (alfa kanalı desteklenmez).
+
+pub const SAYFA_GENISLIK: f64 = 595.0;
+pub const SAYFA_YUKSEKLIK: f64 = 842.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Normal,
+    Kalin,
+}
+
+impl Font {
+    fn kaynak_adi(self) -> &'static str {
+        match self {
+            Font::Normal => "F1",
+            Font::Kalin => "F2",
+        }
+    }
+}
+
+/// Helvetica, 32..=126 (1/1000 em)
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+/// Helvetica-Bold, 32..=126 (1/1000 em)
+const HELVETICA_KALIN: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Bir karakteri font kodlamasındaki bayt(lar)a çevirir; karşılığı olmayanlar '?' olur
+fn karakter_kodla(c: char, cikti: &mut Vec<u8>) {
+    let b = match c {
+        ' '..='~' => c as u8,
+        '\t' | '\u{a0}' => b' ',
+        '₺' => {
+            cikti.extend_from_slice(b"TL");
+            return;
+        }
+        'Ğ' => 0xD0,
+        'İ' => 0xDD,
+        'Ş' => 0xDE,
+        'ğ' => 0xF0,
+        'ı' => 0xFD,
+        'ş' => 0xFE,
+        'Ç' => 0xC7,
+        'Ö' => 0xD6,
+        'Ü' => 0xDC,
+        'ç' => 0xE7,
+        'ö' => 0xF6,
+        'ü' => 0xFC,
+        'Â' => 0xC2,
+        'Î' => 0xCE,
+        'Û' => 0xDB,
+        'â' => 0xE2,
+        'î' => 0xEE,
+        'û' => 0xFB,
+        '€' => 0x80,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '–' => 0x96,
+        '—' => 0x97,
+        '°' => 0xB0,
+        '·' => 0xB7,
+        _ => b'?',
+    };
+    cikti.push(b);
+}
+
+/// Metni font kodlamasına çevirir
+pub fn kodla(metin: &str) -> Vec<u8> {
+    let mut cikti = Vec::with_capacity(metin.len());
+    for c in metin.chars() {
+        karakter_kodla(c, &mut cikti);
+    }
+    cikti
+}
+
+fn bayt_genisligi(b: u8, font: Font) -> u16 {
+    let kalin = font == Font::Kalin;
+    match b {
+        32..=126 => {
+            let tablo = if kalin { &HELVETICA_KALIN } else { &HELVETICA };
+            tablo[(b - 32) as usize]
+        }
+        0xC7 | 0xDC | 0xDB => 722,
+        0xC2 if kalin => 722,
+        0xC2 | 0xDE => 667,
+        0xD0 | 0xD6 => 778,
+        0xDD | 0xCE | 0xFD | 0xEE => 278,
+        0xE7 | 0xFE if kalin => 556,
+        0xE7 | 0xFE => 500,
+        0xF0 | 0xF6 | 0xFC | 0xFB if kalin => 611,
+        0xF0 | 0xF6 | 0xFC | 0xFB | 0xE2 | 0x80 | 0x96 => 556,
+        0x85 | 0x97 => 1000,
+        0x91 | 0x92 if kalin => 278,
+        0x91 | 0x92 => 222,
+        0x93 | 0x94 if kalin => 500,
+        0x93 | 0x94 => 333,
+        0xB0 => 400,
+        0xB7 => 278,
+        _ => 556,
+    }
+}
+
+/// Metnin verilen font ve puntodaki genişliği (pt)
+pub fn metin_genisligi(metin: &str, font: Font, boyut: f64) -> f64 {
+    let birim: u32 = kodla(metin).iter().map(|&b| bayt_genisligi(b, font) as u32).sum();
+    birim as f64 * boyut / 1000.0
+}
+
+/// Metni genişliğe sığacak şekilde sondan kısaltır ("…" ekleyerek)
+pub fn kisalt(metin: &str, font: Font, boyut: f64, genislik: f64) -> String {
+    if metin_genisligi(metin, font, boyut) <= genislik {
+        return metin.to_string();
+    }
+    let mut karakterler: Vec<char> = metin.chars().collect();
+    while !karakterler.is_empty() {
+        karakterler.pop();
+        let aday: String = karakterler.iter().collect::<String>().trim_end().to_string() + "…";
+        if metin_genisligi(&aday, font, boyut) <= genislik {
+            return aday;
+        }
+    }
+    String::new()
+}
+
+/// Metni kelime sınırlarından genişliğe sığan satırlara böler; tek başına
+/// sığmayan kelime kısaltılır. Boş satırlar (paragraf arası) korunur.
+pub fn satirlara_bol(metin: &str, font: Font, boyut: f64, genislik: f64) -> Vec<String> {
+    let mut satirlar = Vec::new();
+    for paragraf in metin.lines() {
+        let mut satir = String::new();
+        for kelime in paragraf.split_whitespace() {
+            let aday = if satir.is_empty() { kelime.to_string() } else { format!("{} {}", satir, kelime) };
+            if metin_genisligi(&aday, font, boyut) <= genislik {
+                satir = aday;
+                continue;
+            }
+            if !satir.is_empty() {
+                satirlar.push(std::mem::take(&mut satir));
+            }
+            satir = kisalt(kelime, font, boyut, genislik);
+        }
+        satirlar.push(satir);
+    }
+    satirlar
+}
+
+fn pdf_metni(metin: &str) -> Vec<u8> {
+    let mut cikti = vec![b'('];
+    for b in kodla(metin) {
+        if matches!(b, b'(' | b')' | b'\\') {
+            cikti.push(b'\\');
+        }
+        cikti.push(b);
+    }
+    cikti.push(b')');
+    cikti
+}
+
+fn sayi(v: f64) -> String {
+    let s = format!("{:.2}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+// ---------------------------------------------------------------------------
+// Görseller
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct Gorsel {
+    pub genislik: u32,
+    pub yukseklik: u32,
+    /// Görsel sözlüğünün Width/Height dışındaki girdileri
+    sozluk: String,
+    veri: Vec<u8>,
+}
+
+/// JPEG veya PNG baytlarından gömülebilir görsel hazırlar
+pub fn gorsel_coz(veri: &[u8]) -> Result<Gorsel, String> {
+    if veri.starts_with(&[0xFF, 0xD8]) {
+        jpeg_coz(veri)
+    } else if veri.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png_coz(veri)
+    } else {
+        Err("Logo JPEG veya PNG biçiminde olmalıdır".to_string())
+    }
+}
+
+fn jpeg_coz(veri: &[u8]) -> Result<Gorsel, String> {
+    let bozuk = || "JPEG dosyası okunamadı".to_string();
+    let mut i = 2;
+    while i + 4 <= veri.len() {
+        if veri[i] != 0xFF {
+            return Err(bozuk());
+        }
+        let isaret = veri[i + 1];
+        if isaret == 0xFF {
+            i += 1;
+            continue;
+        }
+        let uzunluk = u16::from_be_bytes([veri[i + 2], veri[i + 3]]) as usize;
+        // SOF0..SOF15 (DHT=C4, JPG=C8, DAC=CC hariç)
+        if (0xC0..=0xCF).contains(&isaret) && !matches!(isaret, 0xC4 | 0xC8 | 0xCC) {
+            if i + 10 > veri.len() {
+                return Err(bozuk());
+            }
+            let yukseklik = u16::from_be_bytes([veri[i + 5], veri[i + 6]]) as u32;
+            let genislik = u16::from_be_bytes([veri[i + 7], veri[i + 8]]) as u32;
+            let sozluk = match veri[i + 9] {
+                1 => "/ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /DCTDecode".to_string(),
+                3 => "/ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode".to_string(),
+                // Adobe CMYK JPEG'leri ters kodlanır
+                4 => "/ColorSpace /DeviceCMYK /BitsPerComponent 8 /Decode [1 0 1 0 1 0 1 0] /Filter /DCTDecode".to_string(),
+                _ => return Err("Desteklenmeyen JPEG renk uzayı".to_string()),
+            };
+            if genislik == 0 || yukseklik == 0 {
+                return Err(bozuk());
+            }
+            return Ok(Gorsel { genislik, yukseklik, sozluk, veri: veri.to_vec() });
+        }
+        i += 2 + uzunluk;
+    }
+    Err(bozuk())
+}
+
+fn png_coz(veri: &[u8]) -> Result<Gorsel, String> {
+    let bozuk = || "PNG dosyası okunamadı".to_string();
+    let mut i = 8;
+    let mut ihdr: Option<(u32, u32, u8, u8, u8)> = None;
+    let mut palet: Vec<u8> = Vec::new();
+    let mut idat: Vec<u8> = Vec::new();
+
+    while i + 8 <= veri.len() {
+        let uzunluk = u32::from_be_bytes([veri[i], veri[i + 1], veri[i + 2], veri[i + 3]]) as usize;
+        let tur = &veri[i + 4..i + 8];
+        let bas = i + 8;
+        let son = bas.checked_add(uzunluk).filter(|&s| s + 4 <= veri.len()).ok_or_else(bozuk)?;
+        let govde = &veri[bas..son];
+        match tur {
+            b"IHDR" if govde.len() >= 13 => {
+                let g = u32::from_be_bytes([govde[0], govde[1], govde[2], govde[3]]);
+                let y = u32::from_be_bytes([govde[4], govde[5], govde[6], govde[7]]);
+                ihdr = Some((g, y, govde[8], govde[9], govde[12]));
+            }
+            b"PLTE" => palet = govde.to_vec(),
+            b"IDAT" => idat.extend_from_slice(govde),
+            b"IEND" => break,
+            _ => {}
+        }
+        i = son + 4;
+    }
+
+    let (genislik, yukseklik, bit, renk, interlace) = ihdr.ok_or_else(bozuk)?;
+    if genislik == 0 || yukseklik == 0 || idat.is_empty() {
+        return Err(bozuk());
+    }
+    if interlace != 0 {
+        return Err("Interlace'lı PNG logolar desteklenmez; dosyayı interlace'sız kaydedin".to_string());
+    }
+    let (renk_uzayi, renk_sayisi) = match (renk, bit) {
+        (0, 8) => ("/DeviceGray".to_string(), 1),
+        (2, 8) => ("/DeviceRGB".to_string(), 3),
+        (3, 1 | 2 | 4 | 8) if !palet.is_empty() && palet.len().is_multiple_of(3) => {
+            let hex: String = palet.iter().map(|b| format!("{:02X}", b)).collect();
+            (format!("[/Indexed /DeviceRGB {} <{}>]", palet.len() / 3 - 1, hex), 1)
+        }
+        (4 | 6, _) => {
+            return Err("Şeffaf (alfa kanallı) PNG logolar desteklenmez; JPEG veya şeffaflıksız PNG kullanın".to_string())
+        }
+        _ => return Err("Desteklenmeyen PNG biçimi; 8 bit RGB/gri veya paletli PNG kullanın".to_string()),
+    };
+
+    let sozluk = format!(
+        "/ColorSpace {} /BitsPerComponent {} /Filter /FlateDecode /DecodeParms << /Predictor 15 /Colors {} /BitsPerComponent {} /Columns {} >>",
+        renk_uzayi, bit, renk_sayisi, bit, genislik
+    );
+    Ok(Gorsel { genislik, yukseklik, sozluk, veri: idat })
+}
+
+// ---------------------------------------------------------------------------
+// Belge
+// ---------------------------------------------------------------------------
+
+pub struct PdfBelge {
+    baslik: String,
+    sayfalar: Vec<Vec<u8>>,
+    aktif: usize,
+    gorseller: Vec<Gorsel>,
+}
+
+impl PdfBelge {
+    /// Tek boş sayfa ile başlar
+    pub fn new(baslik: &str) -> Self {
+        PdfBelge { baslik: baslik.to_string(), sayfalar: vec![Vec::new()], aktif: 0, gorseller: Vec::new() }
+    }
+
+    pub fn yeni_sayfa(&mut self) {
+        self.sayfalar.push(Vec::new());
+        self.aktif = self.sayfalar.len() - 1;
+    }
+
+    pub fn sayfa_sayisi(&self) -> usize {
+        self.sayfalar.len()
+    }
+
+    /// Yazımı önceki bir sayfaya yönlendirir (ör. sonradan "Sayfa 1/3" eklemek için)
+    pub fn sayfa_sec(&mut self, sayfa: usize) {
+        if sayfa < self.sayfalar.len() {
+            self.aktif = sayfa;
+        }
+    }
+
+    fn yaz(&mut self, parca: &[u8]) {
+        self.sayfalar[self.aktif].extend_from_slice(parca);
+    }
+
+    /// (x, y) metnin taban çizgisinin sol ucudur
+    pub fn metin(&mut self, x: f64, y: f64, boyut: f64, font: Font, metin: &str) {
+        let mut komut = format!(
+            "BT /{} {} Tf {} {} Td ",
+            font.kaynak_adi(),
+            sayi(boyut),
+            sayi(x),
+            sayi(SAYFA_YUKSEKLIK - y)
+        )
+        .into_bytes();
+        komut.extend(pdf_metni(metin));
+        komut.extend_from_slice(b" Tj ET\n");
+        self.yaz(&komut);
+    }
+
+    /// Metni sağ ucu x_sag'da olacak şekilde yazar
+    pub fn metin_sag(&mut self, x_sag: f64, y: f64, boyut: f64, font: Font, metin: &str) {
+        let x = x_sag - metin_genisligi(metin, font, boyut);
+        self.metin(x, y, boyut, font, metin);
+    }
+
+    pub fn metin_orta(&mut self, x_orta: f64, y: f64, boyut: f64, font: Font, metin: &str) {
+        let x = x_orta - metin_genisligi(metin, font, boyut) / 2.0;
+        self.metin(x, y, boyut, font, metin);
+    }
+
+    pub fn cizgi(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, kalinlik: f64) {
+        let komut = format!(
+            "{} w {} {} m {} {} l S\n",
+            sayi(kalinlik),
+            sayi(x1),
+            sayi(SAYFA_YUKSEKLIK - y1),
+            sayi(x2),
+            sayi(SAYFA_YUKSEKLIK - y2)
+        );
+        self.yaz(komut.as_bytes());
+    }
+
+    /// (x, y) sol üst köşe; gri dolgu 0 (siyah) ile 1 (beyaz) arasıdır, None ise yalnızca çerçeve
+    pub fn dikdortgen(&mut self, x: f64, y: f64, genislik: f64, yukseklik: f64, gri_dolgu: Option<f64>) {
+        let kutu = format!(
+            "{} {} {} {} re",
+            sayi(x),
+            sayi(SAYFA_YUKSEKLIK - y - yukseklik),
+            sayi(genislik),
+            sayi(yukseklik)
+        );
+        let komut = match gri_dolgu {
+            Some(g) => format!("q {} g {} f Q\n", sayi(g), kutu),
+            None => format!("0.5 w {} S\n", kutu),
+        };
+        self.yaz(komut.as_bytes());
+    }
+
+    /// Görseli belgeye ekler ve çizimde kullanılacak sırasını döner
+    pub fn gorsel_ekle(&mut self, gorsel: Gorsel) -> usize {
+        self.gorseller.push(gorsel);
+        self.gorseller.len() - 1
+    }
+
+    /// Görseli en-boy oranını koruyarak (x, y) sol üst köşeli kutuya sığdırır; çizilen genişliği döner
+    pub fn gorsel_ciz(&mut self, sira: usize, x: f64, y: f64, kutu_genislik: f64, kutu_yukseklik: f64) -> f64 {
+        let Some(g) = self.gorseller.get(sira) else { return 0.0 };
+        let olcek = (kutu_genislik / g.genislik as f64).min(kutu_yukseklik / g.yukseklik as f64);
+        let (w, h) = (g.genislik as f64 * olcek, g.yukseklik as f64 * olcek);
+        let komut = format!(
+            "q {} 0 0 {} {} {} cm /Im{} Do Q\n",
+            sayi(w),
+            sayi(h),
+            sayi(x),
+            sayi(SAYFA_YUKSEKLIK - y - h),
+            sira
+        );
+        self.yaz(komut.as_bytes());
+        w
+    }
+
+    /// PDF dosya baytlarını üretir
+    pub fn bitir(self) -> Vec<u8> {
+        let mut cikti: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut konumlar: Vec<usize> = Vec::new();
+
+        // Nesne numaraları: 1 katalog, 2 sayfa ağacı, 3 kodlama, 4-5 fontlar, 6 bilgi,
+        // ardından görseller, ardından her sayfa için (sayfa, içerik) çifti
+        let ilk_gorsel = 7;
+        let ilk_sayfa = ilk_gorsel + self.gorseller.len();
+        let sayfa_no = |i: usize| ilk_sayfa + i * 2;
+
+        fn nesne(cikti: &mut Vec<u8>, konumlar: &mut Vec<usize>, govde: &[u8]) {
+            konumlar.push(cikti.len());
+            cikti.extend_from_slice(format!("{} 0 obj\n", konumlar.len()).as_bytes());
+            cikti.extend_from_slice(govde);
+            cikti.extend_from_slice(b"\nendobj\n");
+        }
+
+        nesne(&mut cikti, &mut konumlar, b"<< /Type /Catalog /Pages 2 0 R >>");
+        let kids: Vec<String> = (0..self.sayfalar.len()).map(|i| format!("{} 0 R", sayfa_no(i))).collect();
+        nesne(
+            &mut cikti,
+            &mut konumlar,
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.sayfalar.len()).as_bytes(),
+        );
+        nesne(
+            &mut cikti,
+            &mut konumlar,
+            b"<< /Type /Encoding /BaseEncoding /WinAnsiEncoding /Differences [208 /Gbreve 221 /Idotaccent /Scedilla 240 /gbreve 253 /dotlessi /scedilla] >>",
+        );
+        nesne(&mut cikti, &mut konumlar, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding 3 0 R >>");
+        nesne(&mut cikti, &mut konumlar, b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding 3 0 R >>");
+
+        let mut bilgi = b"<< /Title ".to_vec();
+        bilgi.extend(pdf_metni(&self.baslik));
+        bilgi.extend_from_slice(
+            format!(" /CreationDate (D:{}) >>", chrono::Local::now().format("%Y%m%d%H%M%S")).as_bytes(),
+        );
+        nesne(&mut cikti, &mut konumlar, &bilgi);
+
+        for g in &self.gorseller {
+            let mut govde = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} {} /Length {} >>\nstream\n",
+                g.genislik,
+                g.yukseklik,
+                g.sozluk,
+                g.veri.len()
+            )
+            .into_bytes();
+            govde.extend_from_slice(&g.veri);
+            govde.extend_from_slice(b"\nendstream");
+            nesne(&mut cikti, &mut konumlar, &govde);
+        }
+
+        let xobjeler: String = (0..self.gorseller.len())
+            .map(|i| format!("/Im{} {} 0 R ", i, ilk_gorsel + i))
+            .collect();
+        for (i, icerik) in self.sayfalar.iter().enumerate() {
+            nesne(
+                &mut cikti,
+                &mut konumlar,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 4 0 R /F2 5 0 R >> /XObject << {}>> >> /Contents {} 0 R >>",
+                    sayi(SAYFA_GENISLIK),
+                    sayi(SAYFA_YUKSEKLIK),
+                    xobjeler,
+                    sayfa_no(i) + 1
+                )
+                .as_bytes(),
+            );
+            let mut govde = format!("<< /Length {} >>\nstream\n", icerik.len()).into_bytes();
+            govde.extend_from_slice(icerik);
+            govde.extend_from_slice(b"\nendstream");
+            nesne(&mut cikti, &mut konumlar, &govde);
+        }
+
+        let xref = cikti.len();
+        cikti.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", konumlar.len() + 1).as_bytes());
+        for k in &konumlar {
+            cikti.extend_from_slice(format!("{:010} 00000 n \n", k).as_bytes());
+        }
+        cikti.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 6 0 R >>\nstartxref\n{}\n%%EOF\n",
+                konumlar.len() + 1,
+                xref
+            )
+            .as_bytes(),
+        );
+        cikti
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turkce_karakterler_latin5_konumlarina_kodlanir() {
+        assert_eq!(kodla("ĞİŞğış"), vec![0xD0, 0xDD, 0xDE, 0xF0, 0xFD, 0xFE]);
+        assert_eq!(kodla("ÇÖÜçöü"), vec![0xC7, 0xD6, 0xDC, 0xE7, 0xF6, 0xFC]);
+        assert_eq!(kodla("10 ₺"), b"10 TL".to_vec());
+        assert_eq!(kodla("日"), b"?".to_vec());
+        assert_eq!(pdf_metni("a(b)\\"), b"(a\\(b\\)\\\\)".to_vec());
+    }
+
+    #[test]
+    fn genislik_ve_kisaltma() {
+        // "1.234,56" Helvetica 10pt: 6 rakam × 556 + '.' 278 + ',' 278
+        assert!((metin_genisligi("1.234,56", Font::Normal, 10.0) - 38.92).abs() < 1e-9);
+        assert!(metin_genisligi("Ş", Font::Kalin, 10.0) > metin_genisligi("ı", Font::Kalin, 10.0));
+        let kisa = kisalt("Çok uzun bir açıklama metni", Font::Normal, 9.0, 60.0);
+        assert!(kisa.ends_with('…'));
+        assert!(metin_genisligi(&kisa, Font::Normal, 9.0) <= 60.0);
+
+        let satirlar = satirlara_bol("Sayın üye,\n\nBorcunuzu otuz gün içinde ödeyiniz", Font::Normal, 9.0, 80.0);
+        assert_eq!(satirlar[0], "Sayın üye,");
+        assert_eq!(satirlar[1], "");
+        assert!(satirlar.len() > 3);
+        assert!(satirlar.iter().all(|s| metin_genisligi(s, Font::Normal, 9.0) <= 80.0));
+        assert_eq!(satirlar[2..].join(" "), "Borcunuzu otuz gün içinde ödeyiniz");
+    }
+
+    #[test]
+    fn belge_gecerli_xref_uretir() {
+        let mut belge = PdfBelge::new("Makbuz");
+        belge.metin(40.0, 40.0, 12.0, Font::Kalin, "Dernek Yönetim Kurulu");
+        belge.yeni_sayfa();
+        belge.metin_sag(555.0, 60.0, 10.0, Font::Normal, "1.250,00");
+        let pdf = belge.bitir();
+        let metin = String::from_utf8_lossy(&pdf);
+        assert!(metin.starts_with("%PDF-1.4"));
+        assert!(metin.contains("/Count 2"));
+        assert!(metin.ends_with("%%EOF\n"));
+
+        // startxref, "xref" satırını göstermeli; her girdi kendi nesnesini göstermeli
+        let son = String::from_utf8(pdf[pdf.len() - 30..].to_vec()).unwrap();
+        let xref: usize = son.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        let tablo = String::from_utf8(pdf[xref..].to_vec()).unwrap();
+        assert!(tablo.starts_with("xref"));
+        for (i, satir) in tablo.lines().skip(3).take(6).enumerate() {
+            let konum: usize = satir[..10].parse().unwrap();
+            assert!(pdf[konum..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn logo_bicimleri() {
+        // Minimal JPEG başlığı: SOI, APP0 (boş), SOF0 40×20 RGB
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x02, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x14, 0x00, 0x28, 0x03,
+        ];
+        let g = gorsel_coz(&jpeg).unwrap();
+        assert_eq!((g.genislik, g.yukseklik), (40, 20));
+        assert!(g.sozluk.contains("/DeviceRGB"));
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        let parca = |png: &mut Vec<u8>, tur: &[u8], govde: &[u8]| {
+            png.extend_from_slice(&(govde.len() as u32).to_be_bytes());
+            png.extend_from_slice(tur);
+            png.extend_from_slice(govde);
+            png.extend_from_slice(&[0, 0, 0, 0]);
+        };
+        let mut alfali = png.clone();
+        parca(&mut png, b"IHDR", &[0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        parca(&mut png, b"IDAT", &[0x78, 0x9C]);
+        parca(&mut png, b"IEND", &[]);
+        let g = gorsel_coz(&png).unwrap();
+        assert_eq!((g.genislik, g.yukseklik), (3, 2));
+        assert!(g.sozluk.contains("/Predictor 15 /Colors 3"));
+
+        parca(&mut alfali, b"IHDR", &[0, 0, 0, 3, 0, 0, 0, 2, 8, 6, 0, 0, 0]);
+        parca(&mut alfali, b"IDAT", &[0x78, 0x9C]);
+        assert!(gorsel_coz(&alfali).is_err());
+        assert!(gorsel_coz(b"GIF89a").is_err());
+    }
+}
@@ -108,6 +108,7 @@ diesel::table! {
         is_active -> Bool,
         created_at -> Text,
         updated_at -> Text,
+        devir_tarihi -> Nullable<Text>,
     }
 }
 
@@ -259,6 +260,9 @@ diesel::table! {
         updated_at -> Nullable<Text>,
         created_by -> Nullable<Text>,
         is_deleted -> Nullable<Integer>,
+        kapasite -> Nullable<Integer>,
+        bilet_ucreti -> Nullable<Double>,
+        uye_bilet_ucreti -> Nullable<Double>,
     }
 }
 
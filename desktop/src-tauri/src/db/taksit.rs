@@ -0,0 +1,633 @@
+// Aidat borcu taksitlendirme: plan, taksit takvimi, ödeme takibi ve bozuk plan raporu.
+//
+// Plan, üyenin seçilen açık aidatlarının o anki kalan borcunu toplar ve taksitlere böler
+// (Para::paylastir: artan kuruşlar ilk taksitlere). Taksit ödemesi ayrı bir para hareketi
+// değildir: plana bağlı aidata add_aidat_odeme_with_gelir ile yapılan her ödeme
+// `odemeyi_isle` ile planın en eski açık taksitlerine işlenir ve gelir kaydıyla
+// eşleştirilir (taksit_odemeleri). Bütün taksitler ödenince plan tamamlanır.
+// Bozuk plan: vadesinden tolerans_gun sonra hâlâ kalanı olan taksiti bulunan aktif plan.
+
+use chrono::{Months, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::avans;
+use crate::db::denetim::Denetim;
+use crate::db::para::Para;
+
+pub const AZAMI_TAKSIT: i32 = 60;
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct TaksitPlani {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ilk_vade: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub taksit_sayisi: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub aralik_ay: i32,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub toplam_tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub odenen: Para,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub tolerans_gun: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub aciklama: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kapanis_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub kapanis_nedeni: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct Taksit {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub sira: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub vade_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub tutar: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub odenen: Para,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub odeme_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct PlanAidati {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub aidat_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub yil: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub ay: i32,
+    /// Plana alınırken kalan borç
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub plan_tutari: Para,
+    /// Aidatın güncel kalan borcu
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub kalan: Para,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaksitPlaniDetay {
+    pub plan: TaksitPlani,
+    pub aidatlar: Vec<PlanAidati>,
+    pub taksitler: Vec<Taksit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaksitOnizleme {
+    pub sira: i32,
+    pub vade_tarihi: String,
+    pub tutar: Para,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BozukPlan {
+    pub plan_id: String,
+    pub uye_id: String,
+    pub uye_no: Option<String>,
+    pub ad_soyad: Option<String>,
+    pub toplam_tutar: Para,
+    pub odenen: Para,
+    pub geciken_taksit: i32,
+    pub geciken_tutar: Para,
+    pub ilk_geciken_vade: String,
+    pub gecikme_gun: i64,
+}
+
+/// Taksit takvimi: tutar eşit bölünür, vadeler ilk vadeden aralik_ay ay arayla
+/// (ay sonuna denk gelen gün kısa aylarda ayın son gününe çekilir)
+pub fn takvim(toplam: Para, taksit_sayisi: i32, ilk_vade: NaiveDate, aralik_ay: i32) -> Result<Vec<TaksitOnizleme>, String> {
+    if !(1..=AZAMI_TAKSIT).contains(&taksit_sayisi) {
+        return Err(format!("Taksit sayısı 1 ile {} arasında olmalıdır", AZAMI_TAKSIT));
+    }
+    if !(1..=12).contains(&aralik_ay) {
+        return Err("Taksit aralığı 1 ile 12 ay arasında olmalıdır".to_string());
+    }
+    if !toplam.is_positive() {
+        return Err("Taksitlendirilecek borç bulunmuyor".to_string());
+    }
+    toplam
+        .paylastir(taksit_sayisi as usize)
+        .into_iter()
+        .enumerate()
+        .map(|(i, tutar)| {
+            let vade = ilk_vade
+                .checked_add_months(Months::new(i as u32 * aralik_ay as u32))
+                .ok_or("Geçersiz vade tarihi")?;
+            Ok(TaksitOnizleme { sira: i as i32 + 1, vade_tarihi: vade.format("%Y-%m-%d").to_string(), tutar })
+        })
+        .collect()
+}
+
+/// Vadesi tolerans süresini aşmış, kalanı olan taksitler: (adet, tutar, ilk vade)
+pub fn geciken_taksitler(taksitler: &[Taksit], tolerans_gun: i32, bugun: NaiveDate) -> Option<(i32, Para, NaiveDate)> {
+    let mut sonuc: Option<(i32, Para, NaiveDate)> = None;
+    for t in taksitler {
+        let kalan = (t.tutar - t.odenen).sifirdan_kucukse_sifir();
+        let Ok(vade) = NaiveDate::parse_from_str(&t.vade_tarihi, "%Y-%m-%d") else { continue };
+        if !kalan.is_positive() || (bugun - vade).num_days() <= tolerans_gun as i64 {
+            continue;
+        }
+        sonuc = Some(match sonuc {
+            None => (1, kalan, vade),
+            Some((adet, tutar, ilk)) => (adet + 1, tutar + kalan, ilk.min(vade)),
+        });
+    }
+    sonuc
+}
+
+const PLAN_SELECT: &str = "
+    SELECT p.id, p.uye_id, u.uye_no, u.ad_soyad, p.ilk_vade, p.taksit_sayisi, p.aralik_ay,
+           p.toplam_tutar,
+           COALESCE((SELECT SUM(CAST(ROUND(t.odenen * 100) AS INTEGER)) FROM taksitler t WHERE t.plan_id = p.id), 0) / 100.0 AS odenen,
+           p.tolerans_gun, p.durum, p.aciklama, p.kapanis_tarihi, p.kapanis_nedeni, p.created_at
+    FROM taksit_planlari p
+    LEFT JOIN uyeler u ON u.id = p.uye_id AND u.tenant_id = p.tenant_id";
+
+pub fn planlari_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: Option<&str>,
+    durum: Option<&str>,
+) -> Result<Vec<TaksitPlani>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE p.tenant_id = ?1 AND (?2 IS NULL OR p.uye_id = ?2) AND (?3 IS NULL OR p.durum = ?3)
+         ORDER BY p.created_at DESC",
+        PLAN_SELECT
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(durum)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn plan_getir(conn: &mut SqliteConnection, tenant_id: &str, plan_id: &str) -> Result<TaksitPlaniDetay, String> {
+    let plan: TaksitPlani = diesel::sql_query(format!("{} WHERE p.id = ?1 AND p.tenant_id = ?2", PLAN_SELECT))
+        .bind::<diesel::sql_types::Text, _>(plan_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Taksit planı bulunamadı".to_string())?;
+
+    let aidatlar = diesel::sql_query(
+        "SELECT pa.aidat_id, a.yil, a.ay, pa.tutar AS plan_tutari,
+                MAX(COALESCE(a.kalan, a.tutar - a.odenen), 0) AS kalan
+         FROM taksit_plani_aidatlari pa
+         JOIN aidat_takip a ON a.id = pa.aidat_id AND a.tenant_id = pa.tenant_id
+         WHERE pa.plan_id = ?1 AND pa.tenant_id = ?2
+         ORDER BY a.yil, a.ay, a.created_at",
+    )
+    .bind::<diesel::sql_types::Text, _>(plan_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    let taksitler = taksitleri_getir(conn, tenant_id, plan_id)?;
+    Ok(TaksitPlaniDetay { plan, aidatlar, taksitler })
+}
+
+fn taksitleri_getir(conn: &mut SqliteConnection, tenant_id: &str, plan_id: &str) -> Result<Vec<Taksit>, String> {
+    diesel::sql_query(
+        "SELECT id, sira, vade_tarihi, tutar, odenen, odeme_tarihi, durum FROM taksitler
+         WHERE plan_id = ?1 AND tenant_id = ?2 ORDER BY sira",
+    )
+    .bind::<diesel::sql_types::Text, _>(plan_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Plana alınacak aidatların kalan borçları; her biri üyeye ait, açık ve başka aktif
+/// planda olmamalıdır
+pub fn plan_borclari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: &str,
+    aidat_idleri: &[String],
+) -> Result<Vec<(String, Para)>, String> {
+    #[derive(QueryableByName)]
+    struct AidatRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        uye_id: String,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        kalan: Para,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        durum: String,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        planda: bool,
+    }
+
+    let mut idler = aidat_idleri.to_vec();
+    idler.sort();
+    idler.dedup();
+    if idler.is_empty() {
+        return Err("Taksitlendirilecek aidat seçilmedi".to_string());
+    }
+    let mut satirlar = Vec::new();
+    for aidat_id in &idler {
+        let a: AidatRow = diesel::sql_query(
+            "SELECT a.id, a.uye_id, a.yil, a.ay, COALESCE(a.kalan, a.tutar - a.odenen) AS kalan, a.durum,
+                    EXISTS (SELECT 1 FROM taksit_plani_aidatlari pa
+                            JOIN taksit_planlari p ON p.id = pa.plan_id
+                            WHERE pa.aidat_id = a.id AND pa.tenant_id = a.tenant_id AND p.durum = 'aktif') AS planda
+             FROM aidat_takip a
+             WHERE a.id = ?1 AND a.tenant_id = ?2 AND (a.is_deleted IS NULL OR a.is_deleted = 0)",
+        )
+        .bind::<diesel::sql_types::Text, _>(aidat_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Aidat kaydı bulunamadı".to_string())?;
+
+        let donem = if a.ay <= 1 { a.yil.to_string() } else { format!("{}/{:02}", a.yil, a.ay) };
+        if a.uye_id != uye_id {
+            return Err(format!("{} aidatı bu üyeye ait değil", donem));
+        }
+        if a.durum == "iptal" || !a.kalan.is_positive() {
+            return Err(format!("{} aidatının açık borcu yok", donem));
+        }
+        if a.planda {
+            return Err(format!("{} aidatı zaten aktif bir taksit planında", donem));
+        }
+        satirlar.push((a.id, (a.yil, a.ay), a.kalan));
+    }
+    satirlar.sort_by_key(|(_, donem, _)| *donem);
+    Ok(satirlar.into_iter().map(|(id, _, kalan)| (id, kalan)).collect())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn plan_olustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    uye_id: &str,
+    aidat_idleri: &[String],
+    taksit_sayisi: i32,
+    ilk_vade: NaiveDate,
+    aralik_ay: i32,
+    tolerans_gun: i32,
+    aciklama: Option<&str>,
+    olusturan: Option<&str>,
+) -> Result<String, String> {
+    if tolerans_gun < 0 {
+        return Err("Tolerans süresi negatif olamaz".to_string());
+    }
+    let borclar = plan_borclari(conn, tenant_id, uye_id, aidat_idleri)?;
+    let toplam: Para = borclar.iter().map(|(_, kalan)| *kalan).sum();
+    let taksitler = takvim(toplam, taksit_sayisi, ilk_vade, aralik_ay)?;
+
+    let plan_id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    denetim.yeni_kayit(tenant_id, "taksit_planlari", &plan_id);
+    diesel::sql_query(
+        "INSERT INTO taksit_planlari
+            (id, tenant_id, uye_id, ilk_vade, taksit_sayisi, aralik_ay, toplam_tutar, tolerans_gun,
+             durum, aciklama, olusturan, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'aktif', ?9, ?10, ?11, ?11)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&plan_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_id)
+    .bind::<diesel::sql_types::Text, _>(ilk_vade.format("%Y-%m-%d").to_string())
+    .bind::<diesel::sql_types::Integer, _>(taksit_sayisi)
+    .bind::<diesel::sql_types::Integer, _>(aralik_ay)
+    .bind::<diesel::sql_types::Double, _>(toplam)
+    .bind::<diesel::sql_types::Integer, _>(tolerans_gun)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(aciklama)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(olusturan)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+
+    for (aidat_id, kalan) in &borclar {
+        diesel::sql_query(
+            "INSERT INTO taksit_plani_aidatlari (id, tenant_id, plan_id, aidat_id, tutar) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&plan_id)
+        .bind::<diesel::sql_types::Text, _>(aidat_id)
+        .bind::<diesel::sql_types::Double, _>(*kalan)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+
+    for t in &taksitler {
+        diesel::sql_query(
+            "INSERT INTO taksitler (id, tenant_id, plan_id, sira, vade_tarihi, tutar, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+        )
+        .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&plan_id)
+        .bind::<diesel::sql_types::Integer, _>(t.sira)
+        .bind::<diesel::sql_types::Text, _>(&t.vade_tarihi)
+        .bind::<diesel::sql_types::Double, _>(t.tutar)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(plan_id)
+}
+
+/// Aidatın bağlı olduğu aktif plan
+pub fn aktif_plan(conn: &mut SqliteConnection, tenant_id: &str, aidat_id: &str) -> Result<Option<String>, String> {
+    #[derive(QueryableByName)]
+    struct PlanRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    diesel::sql_query(
+        "SELECT p.id FROM taksit_planlari p
+         JOIN taksit_plani_aidatlari pa ON pa.plan_id = p.id
+         WHERE pa.aidat_id = ?1 AND p.tenant_id = ?2 AND p.durum = 'aktif'
+         LIMIT 1",
+    )
+    .bind::<diesel::sql_types::Text, _>(aidat_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result::<PlanRow>(conn)
+    .optional()
+    .map(|r| r.map(|r| r.id))
+    .map_err(|e| e.to_string())
+}
+
+/// Plana bağlı aidata yapılan ödemeyi planın en eski açık taksitlerine işler; aidat
+/// planda değilse bir şey yapmaz. Taksitlere işlenen tutarı döner.
+pub fn odemeyi_isle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    aidat_id: &str,
+    tutar: Para,
+    tarih: &str,
+    gelir_id: &str,
+) -> Result<Para, String> {
+    let Some(plan_id) = aktif_plan(conn, tenant_id, aidat_id)? else {
+        return Ok(Para::SIFIR);
+    };
+    let taksitler = taksitleri_getir(conn, tenant_id, &plan_id)?;
+    let acik: Vec<(String, Para)> = taksitler
+        .iter()
+        .map(|t| (t.id.clone(), (t.tutar - t.odenen).sifirdan_kucukse_sifir()))
+        .collect();
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut islenen = Para::SIFIR;
+    for (taksit_id, uygulanan) in avans::dagit(tutar, &acik) {
+        denetim.izle(conn, tenant_id, "taksitler", &taksit_id)?;
+        diesel::sql_query(
+            "UPDATE taksitler
+             SET odenen = odenen + ?1, odeme_tarihi = ?2,
+                 durum = CASE WHEN tutar - odenen - ?1 <= 0.004 THEN 'odendi' ELSE 'kismi' END,
+                 updated_at = ?3
+             WHERE id = ?4 AND tenant_id = ?5",
+        )
+        .bind::<diesel::sql_types::Double, _>(uygulanan)
+        .bind::<diesel::sql_types::Text, _>(tarih)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&taksit_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+
+        diesel::sql_query(
+            "INSERT INTO taksit_odemeleri (id, tenant_id, taksit_id, aidat_id, gelir_id, tarih, tutar, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )
+        .bind::<diesel::sql_types::Text, _>(Uuid::new_v4().to_string())
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&taksit_id)
+        .bind::<diesel::sql_types::Text, _>(aidat_id)
+        .bind::<diesel::sql_types::Text, _>(gelir_id)
+        .bind::<diesel::sql_types::Text, _>(tarih)
+        .bind::<diesel::sql_types::Double, _>(uygulanan)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+        islenen += uygulanan;
+    }
+
+    let kalan: Para = acik.iter().map(|(_, k)| *k).sum::<Para>() - islenen;
+    if !kalan.is_positive() {
+        denetim.izle(conn, tenant_id, "taksit_planlari", &plan_id)?;
+        diesel::sql_query(
+            "UPDATE taksit_planlari SET durum = 'tamamlandi', kapanis_tarihi = ?1, updated_at = ?2
+             WHERE id = ?3 AND tenant_id = ?4",
+        )
+        .bind::<diesel::sql_types::Text, _>(tarih)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&plan_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(islenen)
+}
+
+/// Aktif planlardan vadesi tolerans süresini aşmış taksiti olanlar (en uzun gecikme önce)
+pub fn bozuk_planlar(conn: &mut SqliteConnection, tenant_id: &str, bugun: NaiveDate) -> Result<Vec<BozukPlan>, String> {
+    let mut sonuc = Vec::new();
+    for plan in planlari_getir(conn, tenant_id, None, Some("aktif"))? {
+        let taksitler = taksitleri_getir(conn, tenant_id, &plan.id)?;
+        let Some((adet, tutar, ilk_vade)) = geciken_taksitler(&taksitler, plan.tolerans_gun, bugun) else {
+            continue;
+        };
+        sonuc.push(BozukPlan {
+            plan_id: plan.id,
+            uye_id: plan.uye_id,
+            uye_no: plan.uye_no,
+            ad_soyad: plan.ad_soyad,
+            toplam_tutar: plan.toplam_tutar,
+            odenen: plan.odenen,
+            geciken_taksit: adet,
+            geciken_tutar: tutar,
+            ilk_geciken_vade: ilk_vade.format("%Y-%m-%d").to_string(),
+            gecikme_gun: (bugun - ilk_vade).num_days(),
+        });
+    }
+    sonuc.sort_by_key(|p| std::cmp::Reverse(p.gecikme_gun));
+    Ok(sonuc)
+}
+
+/// Aktif planı kapatır ('bozuldu' veya 'iptal'); aidatlar yeniden planlanabilir olur
+pub fn plani_kapat(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    plan_id: &str,
+    durum: &str,
+    neden: &str,
+    tarih: &str,
+) -> Result<(), String> {
+    if !matches!(durum, "bozuldu" | "iptal") {
+        return Err("Plan yalnızca 'bozuldu' veya 'iptal' olarak kapatılabilir".to_string());
+    }
+    if neden.trim().is_empty() {
+        return Err("Kapanış nedeni yazılmalıdır".to_string());
+    }
+    denetim.izle(conn, tenant_id, "taksit_planlari", plan_id)?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let guncellenen = diesel::sql_query(
+        "UPDATE taksit_planlari SET durum = ?1, kapanis_nedeni = ?2, kapanis_tarihi = ?3, updated_at = ?4
+         WHERE id = ?5 AND tenant_id = ?6 AND durum = 'aktif'",
+    )
+    .bind::<diesel::sql_types::Text, _>(durum)
+    .bind::<diesel::sql_types::Text, _>(neden.trim())
+    .bind::<diesel::sql_types::Text, _>(tarih)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(plan_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    if guncellenen == 0 {
+        return Err("Yalnızca aktif plan kapatılabilir".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::uye(&mut conn, "t1", "u2", "2", "Ayşe Kaya");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, odenen, kalan, created_at) VALUES
+                ('a24', 't1', 'u1', 2024, 1, 100.0, 40.0, 60.0, '2024-01-01'),
+                ('a25', 't1', 'u1', 2025, 1, 120.0, 0, 120.0, '2025-01-01'),
+                ('b25', 't1', 'u2', 2025, 1, 120.0, 0, 120.0, '2025-01-01')",
+        );
+        conn
+    }
+
+    fn tarih(y: i32, a: u32, g: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, a, g).unwrap()
+    }
+
+    fn taksit(sira: i32, vade: &str, tutar: f64, odenen: f64) -> Taksit {
+        Taksit {
+            id: format!("t{}", sira),
+            sira,
+            vade_tarihi: vade.to_string(),
+            tutar: Para::from_tl(tutar),
+            odenen: Para::from_tl(odenen),
+            odeme_tarihi: None,
+            durum: String::new(),
+        }
+    }
+
+    #[test]
+    fn takvim_kurus_kaybetmeden_aylara_boler() {
+        let t = takvim(Para::from_tl(1000.0), 3, tarih(2026, 1, 31), 1).unwrap();
+        let tutarlar: Vec<Para> = t.iter().map(|x| x.tutar).collect();
+        assert_eq!(tutarlar, vec![Para::from_kurus(33_334), Para::from_kurus(33_333), Para::from_kurus(33_333)]);
+        let vadeler: Vec<&str> = t.iter().map(|x| x.vade_tarihi.as_str()).collect();
+        // Ay sonu kısa ayda son güne çekilir, sonraki ayda yeniden 31'e döner
+        assert_eq!(vadeler, ["2026-01-31", "2026-02-28", "2026-03-31"]);
+
+        let uc_aylik = takvim(Para::from_tl(90.0), 2, tarih(2026, 11, 15), 3).unwrap();
+        assert_eq!(uc_aylik[1].vade_tarihi, "2027-02-15");
+
+        assert!(takvim(Para::from_tl(90.0), 0, tarih(2026, 1, 1), 1).is_err());
+        assert!(takvim(Para::from_tl(90.0), 2, tarih(2026, 1, 1), 0).is_err());
+        assert!(takvim(Para::SIFIR, 2, tarih(2026, 1, 1), 1).is_err());
+    }
+
+    #[test]
+    fn tolerans_asilinca_plan_bozuk() {
+        let taksitler = vec![
+            taksit(1, "2026-01-10", 100.0, 100.0),
+            taksit(2, "2026-02-10", 100.0, 40.0),
+            taksit(3, "2026-03-10", 100.0, 0.0),
+        ];
+        // Ödenmiş taksit gecikme sayılmaz; tolerans içindeki taksit de sayılmaz
+        assert_eq!(geciken_taksitler(&taksitler, 7, tarih(2026, 2, 17)), None);
+        assert_eq!(
+            geciken_taksitler(&taksitler, 7, tarih(2026, 2, 18)),
+            Some((1, Para::from_tl(60.0), tarih(2026, 2, 10)))
+        );
+        assert_eq!(
+            geciken_taksitler(&taksitler, 0, tarih(2026, 3, 11)),
+            Some((2, Para::from_tl(160.0), tarih(2026, 2, 10)))
+        );
+    }
+
+    #[test]
+    fn plan_odemeleri_taksitlere_islenir() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut denetim = Denetim::yeni(None, None, "test");
+        let aidatlar = vec!["a25".to_string(), "a24".to_string()];
+
+        // Başka üyenin aidatı plana alınamaz
+        let hatali = vec!["b25".to_string()];
+        assert!(plan_olustur(c, &mut denetim, "t1", "u1", &hatali, 3, tarih(2026, 1, 15), 1, 5, None, None).is_err());
+
+        let plan_id = plan_olustur(c, &mut denetim, "t1", "u1", &aidatlar, 3, tarih(2026, 1, 15), 1, 5, None, None).unwrap();
+        let detay = plan_getir(c, "t1", &plan_id).unwrap();
+        assert_eq!(detay.plan.toplam_tutar, Para::from_tl(180.0));
+        assert_eq!(detay.aidatlar[0].aidat_id, "a24");
+        assert!(detay.taksitler.iter().all(|t| t.tutar == Para::from_tl(60.0)));
+        // Aynı aidat ikinci aktif plana alınamaz
+        assert!(plan_olustur(c, &mut denetim, "t1", "u1", &aidatlar, 2, tarih(2026, 1, 15), 1, 0, None, None).is_err());
+
+        // 90 TL: 1. taksit kapanır, 2. taksite 30 TL
+        assert_eq!(
+            odemeyi_isle(c, &mut denetim, "t1", "a24", Para::from_tl(90.0), "2026-01-14", "g1").unwrap(),
+            Para::from_tl(90.0)
+        );
+        let taksitler = plan_getir(c, "t1", &plan_id).unwrap().taksitler;
+        assert_eq!(taksitler[0].durum, "odendi");
+        assert_eq!(taksitler[1].durum, "kismi");
+        assert_eq!(taksitler[1].odenen, Para::from_tl(30.0));
+
+        // 2. taksitin vadesi + 5 gün geçti: bozuk
+        let bozuk = bozuk_planlar(c, "t1", tarih(2026, 2, 21)).unwrap();
+        assert_eq!(bozuk.len(), 1);
+        assert_eq!(bozuk[0].geciken_tutar, Para::from_tl(30.0));
+        assert_eq!(bozuk[0].gecikme_gun, 6);
+        assert!(bozuk_planlar(c, "t1", tarih(2026, 2, 20)).unwrap().is_empty());
+
+        // Planda olmayan aidatın ödemesi taksitlere işlenmez; kalan ödenince plan tamamlanır
+        assert_eq!(odemeyi_isle(c, &mut denetim, "t1", "b25", Para::from_tl(10.0), "2026-02-01", "g2").unwrap(), Para::SIFIR);
+        odemeyi_isle(c, &mut denetim, "t1", "a25", Para::from_tl(90.0), "2026-03-01", "g3").unwrap();
+        assert_eq!(plan_getir(c, "t1", &plan_id).unwrap().plan.durum, "tamamlandi");
+        assert!(bozuk_planlar(c, "t1", tarih(2026, 6, 1)).unwrap().is_empty());
+    }
+}
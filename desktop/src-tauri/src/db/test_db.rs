@@ -0,0 +1,82 @@
+// Testler için gerçek şemayla kurulmuş bellek içi veritabanı. Şema, uygulama
+// açılışındaki gibi schema.sql + db::connection migration koşucusuyla kurulur;
+// modüller kendi tablo taslaklarını yazmaz.
+use diesel::prelude::*;
+use crate::db::connection;
+
+/// schema.sql ve tüm migration'lar uygulanmış bağlantı
+pub fn baglanti() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Bellek içi veritabanı açılamadı");
+    connection::init_database(&mut conn).expect("schema.sql uygulanamadı");
+    connection::run_migrations(&mut conn).expect("Migration'lar uygulanamadı");
+    crate::db::denetim::koruma_kur(&mut conn).expect("Denetim koruması kurulamadı");
+    conn
+}
+
+/// Adı `surum` ile başlayan migration'dan önceki şema. Eski veriyi yazıp kalan
+/// migration'ları `connection::run_migrations` ile koşan testler için.
+pub fn surumden_once(surum: &str) -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Bellek içi veritabanı açılamadı");
+    connection::init_database(&mut conn).expect("schema.sql uygulanamadı");
+    let sonrakiler: Vec<String> = std::fs::read_dir("migrations")
+        .expect("migrations dizini okunamadı")
+        .filter_map(|e| e.ok()?.file_name().into_string().ok())
+        .filter(|ad| ad.ends_with(".sql") && ad.as_str() >= surum)
+        .collect();
+    // Sonraki migration'lar uygulanmış gibi işaretlenir, koşucu onları atlar
+    for ad in &sonrakiler {
+        diesel::sql_query("INSERT INTO schema_migrations (version) VALUES (?1)")
+            .bind::<diesel::sql_types::Text, _>(ad)
+            .execute(&mut conn)
+            .unwrap();
+    }
+    connection::run_migrations(&mut conn).expect("Migration'lar uygulanamadı");
+    for ad in &sonrakiler {
+        diesel::sql_query("DELETE FROM schema_migrations WHERE version = ?1")
+            .bind::<diesel::sql_types::Text, _>(ad)
+            .execute(&mut conn)
+            .unwrap();
+    }
+    conn
+}
+
+pub fn kiraci(conn: &mut SqliteConnection, id: &str, ad: &str) {
+    diesel::sql_query("INSERT INTO tenants (id, name, slug) VALUES (?1, ?2, ?1)")
+        .bind::<diesel::sql_types::Text, _>(id)
+        .bind::<diesel::sql_types::Text, _>(ad)
+        .execute(conn)
+        .unwrap();
+}
+
+/// Aktif üye; ad_soyad ilk boşluktan ad/soyad olarak bölünür
+pub fn uye(conn: &mut SqliteConnection, tenant_id: &str, id: &str, uye_no: &str, ad_soyad: &str) {
+    let (ad, soyad) = ad_soyad.split_once(' ').unwrap_or((ad_soyad, ""));
+    diesel::sql_query(
+        "INSERT INTO uyeler (id, tenant_id, uye_no, tc_no, ad, soyad, ad_soyad, giris_tarihi)
+         VALUES (?1, ?2, ?3, '', ?4, ?5, ?6, '2020-01-01')",
+    )
+    .bind::<diesel::sql_types::Text, _>(id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(uye_no)
+    .bind::<diesel::sql_types::Text, _>(ad)
+    .bind::<diesel::sql_types::Text, _>(soyad)
+    .bind::<diesel::sql_types::Text, _>(ad_soyad)
+    .execute(conn)
+    .unwrap();
+}
+
+pub fn kasa(conn: &mut SqliteConnection, tenant_id: &str, id: &str, kasa_adi: &str) {
+    diesel::sql_query("INSERT INTO kasalar (id, tenant_id, kasa_adi) VALUES (?1, ?2, ?3)")
+        .bind::<diesel::sql_types::Text, _>(id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(kasa_adi)
+        .execute(conn)
+        .unwrap();
+}
+
+/// Tek seferlik test SQL'i
+pub fn calistir(conn: &mut SqliteConnection, sql: &str) {
+    diesel::sql_query(sql)
+        .execute(conn)
+        .unwrap_or_else(|e| panic!("{}: {}", e, sql));
+}
@@ -0,0 +1,909 @@
+// Toplantı çağrıları ve katılım: davetli listesi, çağrı süresi denetimi, katılım işaretleme
+// ve üye bazında katılım geçmişi.
+//
+// Davetli listesi toplantı türünden çıkarılır (bkz. toplanti_organi): kurul toplantılarına
+// o gün görevde olan asil kurul üyeleri, genel kurula hazirundaki katılma hakkı olan üyeler
+// (hazirun yoksa o gün asil üye olanlar) çağrılır; misafir ve diğer üyeler elle eklenir.
+// Çağrı toplantıdan en az asgari_bildirim_gun gün önce yapılmalıdır; genel kurul çağrısı
+// gündemsiz yapılamaz (TMK 75). Genel kurulun katılımı hazirun imzasından işlenir
+// (hazirun_imzasini_isle), diğer toplantılarda katilim_kaydet ile toplantı günü ya da sonra.
+
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::db::denetim::Denetim;
+use crate::db::{genel_kurul, organ_gorevi, uyelik_durumu};
+
+pub const BILDIRIM_KANALLARI: [&str; 6] = ["elden", "posta", "iadeli_taahhutlu", "eposta", "sms", "ilan"];
+pub const KATILIM_DURUMLARI: [&str; 4] = ["bekleniyor", "katildi", "katilmadi", "mazeretli"];
+/// Genel kurul çağrısı ile toplantı arasındaki asgari süre (TMK 75)
+pub const GENEL_KURUL_CAGRI_GUN: i64 = 15;
+/// Kurul toplantıları için tüzükte aksi yazmıyorsa uygulanan çağrı süresi
+pub const KURUL_CAGRI_GUN: i64 = 3;
+
+/// toplantilar.toplanti_tipi → organ ("Yönetim Kurulu" → "yonetim_kurulu"); diğer türler için None
+pub fn toplanti_organi(toplanti_tipi: &str) -> Option<&'static str> {
+    let tip = toplanti_tipi.to_lowercase();
+    if tip.contains("genel kurul") {
+        Some("genel_kurul")
+    } else if tip.contains("yönetim") || tip.contains("yonetim") {
+        Some("yonetim_kurulu")
+    } else if tip.contains("denetim") {
+        Some("denetim_kurulu")
+    } else if tip.contains("disiplin") {
+        Some("disiplin_kurulu")
+    } else {
+        None
+    }
+}
+
+pub fn asgari_bildirim_gun(organ: Option<&str>) -> i64 {
+    match organ {
+        Some("genel_kurul") => GENEL_KURUL_CAGRI_GUN,
+        Some(_) => KURUL_CAGRI_GUN,
+        None => 0,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct Davetli {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub toplanti_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gorev: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub davet_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub davet_kanali: Option<String>,
+    /// "bekleniyor" | "katildi" | "katilmadi" | "mazeretli"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub katilim: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub katilim_zamani: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub mazeret: Option<String>,
+}
+
+/// Çağrı ve davetiye için toplantı bilgileri
+#[derive(Debug, Clone, Serialize)]
+pub struct Davetiye {
+    pub toplanti_id: String,
+    pub baslik: String,
+    pub toplanti_tipi: String,
+    pub organ: Option<String>,
+    pub tarih: String,
+    pub saat: Option<String>,
+    pub yer: Option<String>,
+    /// Genel kurulda çoğunluk sağlanamazsa yapılacak ikinci toplantı
+    pub ikinci_toplanti_tarihi: Option<String>,
+    pub gundem: Vec<String>,
+    pub asgari_bildirim_gun: i64,
+    /// Çağrının yapılabileceği son gün
+    pub son_cagri_tarihi: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToplantiKatilimi {
+    pub davetiye: Davetiye,
+    pub davetliler: Vec<Davetli>,
+    pub davet_edilen: usize,
+    pub katilan: usize,
+    pub mazeretli: usize,
+    pub katilmayan: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DavetliGirdi {
+    pub uye_id: Option<String>,
+    /// Misafirde zorunlu; üyede boşsa üye kaydından
+    pub ad_soyad: Option<String>,
+    pub gorev: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DavetGirdi {
+    /// Çağrının yapıldığı gün (YYYY-MM-DD)
+    pub tarih: String,
+    pub kanal: String,
+    /// Boşsa henüz çağrılmamış tüm davetliler
+    pub davetli_idler: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KatilimGirdi {
+    pub davetli_id: String,
+    pub katilim: String,
+    pub mazeret: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct KatilimSatiri {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub toplanti_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub baslik: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub toplanti_tipi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub uye_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad_soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub gorev: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub katilim: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub mazeret: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UyeKatilimOzeti {
+    pub uye_id: String,
+    pub uye_no: Option<String>,
+    pub ad_soyad: String,
+    pub davet: usize,
+    pub katildi: usize,
+    pub mazeretli: usize,
+    pub katilmadi: usize,
+    /// Sonuçlanmış toplantılarda katılım yüzdesi; henüz sonuçlanan toplantı yoksa None
+    pub katilim_orani: Option<f64>,
+}
+
+impl UyeKatilimOzeti {
+    fn ekle(&mut self, satir: &KatilimSatiri) {
+        self.davet += 1;
+        match satir.katilim.as_str() {
+            "katildi" => self.katildi += 1,
+            "mazeretli" => self.mazeretli += 1,
+            "katilmadi" => self.katilmadi += 1,
+            _ => {}
+        }
+        let sonuclanan = self.katildi + self.mazeretli + self.katilmadi;
+        self.katilim_orani = (sonuclanan > 0).then(|| (self.katildi * 1000 / sonuclanan) as f64 / 10.0);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UyeKatilimGecmisi {
+    pub ozet: UyeKatilimOzeti,
+    pub toplantilar: Vec<KatilimSatiri>,
+}
+
+#[derive(QueryableByName)]
+struct ToplantiRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    baslik: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    saat: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    yer: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    toplanti_tipi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    gundem: Option<String>,
+}
+
+fn toplanti_getir(conn: &mut SqliteConnection, tenant_id: &str, toplanti_id: &str) -> Result<ToplantiRow, String> {
+    diesel::sql_query(
+        "SELECT baslik, substr(tarih, 1, 10) AS tarih, saat, yer, COALESCE(toplanti_tipi, '') AS toplanti_tipi,
+                COALESCE(durum, 'planli') AS durum, gundem
+         FROM toplantilar WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(toplanti_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Toplantı bulunamadı".to_string())
+}
+
+fn tarih_coz(tarih: &str) -> Result<NaiveDate, String> {
+    tarih
+        .get(..10)
+        .and_then(|t| NaiveDate::parse_from_str(t, "%Y-%m-%d").ok())
+        .ok_or_else(|| format!("Geçersiz tarih: {}", tarih))
+}
+
+/// Toplantıya bağlı genel kurul kaydı
+fn genel_kurulu(conn: &mut SqliteConnection, tenant_id: &str, toplanti_id: &str) -> Result<Option<genel_kurul::GenelKurul>, String> {
+    #[derive(QueryableByName)]
+    struct IdRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+    }
+
+    let id: Option<IdRow> = diesel::sql_query("SELECT id FROM genel_kurullar WHERE tenant_id = ?1 AND toplanti_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(toplanti_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?;
+    id.map(|r| genel_kurul::genel_kurul_getir(conn, tenant_id, &r.id)).transpose()
+}
+
+pub fn davetiye(conn: &mut SqliteConnection, tenant_id: &str, toplanti_id: &str) -> Result<Davetiye, String> {
+    let t = toplanti_getir(conn, tenant_id, toplanti_id)?;
+    let organ = toplanti_organi(&t.toplanti_tipi);
+    let gk = genel_kurulu(conn, tenant_id, toplanti_id)?;
+    // Genel kurul gündemi kayıtlıysa o, değilse toplantının serbest gündem metni (satır satır)
+    let mut gundem: Vec<String> = match &gk {
+        Some(gk) => genel_kurul::gundem_getir(conn, tenant_id, &gk.id)?.into_iter().map(|m| m.baslik).collect(),
+        None => Vec::new(),
+    };
+    if gundem.is_empty() {
+        gundem = t
+            .gundem
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    let asgari = asgari_bildirim_gun(organ);
+    let son_cagri_tarihi = match tarih_coz(&t.tarih) {
+        Ok(tarih) if asgari > 0 => Some((tarih - chrono::Duration::days(asgari)).format("%Y-%m-%d").to_string()),
+        _ => None,
+    };
+    Ok(Davetiye {
+        toplanti_id: toplanti_id.to_string(),
+        baslik: t.baslik,
+        toplanti_tipi: t.toplanti_tipi,
+        organ: organ.map(str::to_string),
+        tarih: t.tarih,
+        saat: t.saat,
+        yer: t.yer,
+        ikinci_toplanti_tarihi: gk.and_then(|g| g.ikinci_toplanti_tarihi),
+        gundem,
+        asgari_bildirim_gun: asgari,
+        son_cagri_tarihi,
+    })
+}
+
+/// Çağrı metni gövdesi; tarihler GG.AA.YYYY yazılır
+pub fn davet_metni(d: &Davetiye, dernek: &str) -> String {
+    let tr = |t: &str| {
+        NaiveDate::parse_from_str(&t[..10.min(t.len())], "%Y-%m-%d")
+            .map(|t| t.format("%d.%m.%Y").to_string())
+            .unwrap_or_else(|_| t.to_string())
+    };
+    let mut metin = format!("{} {} toplantısı {} tarihinde", dernek, d.baslik, tr(&d.tarih));
+    if let Some(saat) = d.saat.as_deref().filter(|s| !s.is_empty()) {
+        metin.push_str(&format!(" saat {}'da", saat));
+    }
+    if let Some(yer) = d.yer.as_deref().filter(|s| !s.is_empty()) {
+        metin.push_str(&format!(" {} adresinde", yer));
+    }
+    metin.push_str(" aşağıdaki gündemle yapılacaktır.");
+    if let Some(ikinci) = d.ikinci_toplanti_tarihi.as_deref() {
+        metin.push_str(&format!(
+            " Çoğunluk sağlanamadığı takdirde ikinci toplantı {} tarihinde aynı yer ve saatte, aynı gündemle yapılacaktır.",
+            tr(ikinci)
+        ));
+    }
+    metin.push_str(" Katılımınızı rica ederiz.");
+    metin
+}
+
+pub fn davetlileri_getir(conn: &mut SqliteConnection, tenant_id: &str, toplanti_id: &str) -> Result<Vec<Davetli>, String> {
+    diesel::sql_query(
+        "SELECT d.id, d.toplanti_id, d.uye_id, u.uye_no, d.ad_soyad, d.gorev, d.davet_tarihi, d.davet_kanali,
+                d.katilim, d.katilim_zamani, d.mazeret
+         FROM toplanti_davetlileri d
+         LEFT JOIN uyeler u ON u.id = d.uye_id AND u.tenant_id = d.tenant_id
+         WHERE d.tenant_id = ?1 AND d.toplanti_id = ?2
+         ORDER BY d.gorev IS NULL, d.uye_id IS NULL, CAST(u.uye_no AS INTEGER), d.ad_soyad",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(toplanti_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn toplanti_katilimi(conn: &mut SqliteConnection, tenant_id: &str, toplanti_id: &str) -> Result<ToplantiKatilimi, String> {
+    let davetiye = davetiye(conn, tenant_id, toplanti_id)?;
+    let davetliler = davetlileri_getir(conn, tenant_id, toplanti_id)?;
+    let say = |k: &str| davetliler.iter().filter(|d| d.katilim == k).count();
+    Ok(ToplantiKatilimi {
+        davetiye,
+        davet_edilen: davetliler.iter().filter(|d| d.davet_tarihi.is_some()).count(),
+        katilan: say("katildi"),
+        mazeretli: say("mazeretli"),
+        katilmayan: say("katilmadi"),
+        davetliler,
+    })
+}
+
+fn davetli_yaz(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    toplanti_id: &str,
+    uye_id: Option<&str>,
+    ad_soyad: &str,
+    gorev: Option<&str>,
+) -> Result<String, String> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::sql_query(
+        "INSERT INTO toplanti_davetlileri (id, tenant_id, toplanti_id, uye_id, ad_soyad, gorev, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(toplanti_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .bind::<diesel::sql_types::Text, _>(ad_soyad)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(gorev)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    denetim.yeni_kayit(tenant_id, "toplanti_davetlileri", &id);
+    Ok(id)
+}
+
+fn duzenlenebilir(t: &ToplantiRow) -> Result<(), String> {
+    if ["iptal", "İptal", "tamamlandi"].contains(&t.durum.as_str()) {
+        return Err("İptal edilmiş ya da tamamlanmış toplantının davetlileri değiştirilemez".to_string());
+    }
+    Ok(())
+}
+
+/// Toplantı türüne göre çağrılması gerekenleri listeye ekler; listede olanlar korunur
+pub fn davetlileri_olustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    toplanti_id: &str,
+) -> Result<Vec<Davetli>, String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+    }
+
+    let t = toplanti_getir(conn, tenant_id, toplanti_id)?;
+    duzenlenebilir(&t)?;
+    let organ = toplanti_organi(&t.toplanti_tipi)
+        .ok_or("Bu toplantı türü için davetli listesi otomatik çıkarılamaz; davetlileri elle ekleyin")?;
+    let tarih = tarih_coz(&t.tarih)?;
+
+    // (uye_id, ad_soyad, görev)
+    let adaylar: Vec<(String, String, Option<String>)> = if organ == "genel_kurul" {
+        let hazirun = match genel_kurulu(conn, tenant_id, toplanti_id)? {
+            Some(gk) => genel_kurul::hazirun_getir(conn, tenant_id, &gk.id)?,
+            None => Vec::new(),
+        };
+        if hazirun.is_empty() {
+            let asiller = uyelik_durumu::donemde_asil_uyeler(conn, tenant_id, tarih, tarih)?;
+            let uyeler: Vec<UyeRow> = diesel::sql_query(
+                "SELECT id, ad_soyad FROM uyeler WHERE tenant_id = ?1 AND (is_deleted IS NULL OR is_deleted = 0)
+                 ORDER BY CAST(uye_no AS INTEGER), uye_no, ad_soyad",
+            )
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .load(conn)
+            .map_err(|e| e.to_string())?;
+            uyeler.into_iter().filter(|u| asiller.contains(&u.id)).map(|u| (u.id, u.ad_soyad, None)).collect()
+        } else {
+            hazirun.into_iter().filter(|h| h.katilim_hakki).map(|h| (h.uye_id, h.ad_soyad, None)).collect()
+        }
+    } else {
+        organ_gorevi::gorevleri_getir(conn, tenant_id, Some(organ), Some(&t.tarih))?
+            .into_iter()
+            .filter(|g| g.gorev != "yedek_uye" && g.komisyon_adi.is_none())
+            .map(|g| {
+                let gorev = organ_gorevi::gorev_adi(&g.gorev).to_string();
+                (g.uye_id, g.ad_soyad, Some(gorev))
+            })
+            .collect()
+    };
+    if adaylar.is_empty() {
+        return Err(format!("{} tarihinde çağrılacak {} üyesi bulunamadı", t.tarih, genel_kurul::organ_adi(organ)));
+    }
+
+    let mevcut: std::collections::HashSet<String> =
+        davetlileri_getir(conn, tenant_id, toplanti_id)?.into_iter().filter_map(|d| d.uye_id).collect();
+    for (uye_id, ad_soyad, gorev) in adaylar.iter().filter(|(u, _, _)| !mevcut.contains(u)) {
+        davetli_yaz(conn, denetim, tenant_id, toplanti_id, Some(uye_id), ad_soyad, gorev.as_deref())?;
+    }
+    davetlileri_getir(conn, tenant_id, toplanti_id)
+}
+
+/// Üye ya da misafir davetli ekler
+pub fn davetli_ekle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    toplanti_id: &str,
+    girdi: &DavetliGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad_soyad: String,
+    }
+
+    let t = toplanti_getir(conn, tenant_id, toplanti_id)?;
+    duzenlenebilir(&t)?;
+    let uye_id = girdi.uye_id.as_deref().filter(|s| !s.is_empty());
+    let mut ad_soyad = girdi.ad_soyad.as_deref().map(str::trim).unwrap_or_default().to_string();
+    if let Some(uye_id) = uye_id {
+        let uye: UyeRow = diesel::sql_query(
+            "SELECT ad_soyad FROM uyeler WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+        )
+        .bind::<diesel::sql_types::Text, _>(uye_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or("Üye bulunamadı")?;
+        if davetlileri_getir(conn, tenant_id, toplanti_id)?.iter().any(|d| d.uye_id.as_deref() == Some(uye_id)) {
+            return Err("Üye zaten davetli listesinde".to_string());
+        }
+        if ad_soyad.is_empty() {
+            ad_soyad = uye.ad_soyad;
+        }
+    }
+    if ad_soyad.is_empty() {
+        return Err("Misafir davetlinin adı soyadı zorunludur".to_string());
+    }
+    let gorev = girdi.gorev.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    davetli_yaz(conn, denetim, tenant_id, toplanti_id, uye_id, &ad_soyad, gorev)
+}
+
+/// Henüz çağrılmamış davetliyi listeden çıkarır
+pub fn davetli_sil(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, davetli_id: &str) -> Result<(), String> {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        davet_tarihi: Option<String>,
+    }
+
+    let d: Row = diesel::sql_query("SELECT davet_tarihi FROM toplanti_davetlileri WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(davetli_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or("Davetli bulunamadı")?;
+    if d.davet_tarihi.is_some() {
+        return Err("Çağrısı yapılmış davetli listeden çıkarılamaz".to_string());
+    }
+    denetim.izle(conn, tenant_id, "toplanti_davetlileri", davetli_id)?;
+    diesel::sql_query("DELETE FROM toplanti_davetlileri WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(davetli_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Çağrıyı kaydeder; asgari bildirim süresi ve genel kurul gündemi denetlenir.
+/// Çağrı yapılan davetli sayısı döner.
+pub fn davet_gonder(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    toplanti_id: &str,
+    girdi: &DavetGirdi,
+) -> Result<usize, String> {
+    let t = toplanti_getir(conn, tenant_id, toplanti_id)?;
+    duzenlenebilir(&t)?;
+    if !BILDIRIM_KANALLARI.contains(&girdi.kanal.as_str()) {
+        return Err(format!("Geçersiz bildirim kanalı: {}", girdi.kanal));
+    }
+    let d = davetiye(conn, tenant_id, toplanti_id)?;
+    let cagri = tarih_coz(&girdi.tarih)?;
+    let kalan = (tarih_coz(&d.tarih)? - cagri).num_days();
+    if kalan < d.asgari_bildirim_gun {
+        return Err(format!(
+            "{} çağrısı toplantıdan en az {} gün önce yapılmalıdır; çağrı tarihiyle toplantı arasında {} gün var",
+            d.toplanti_tipi, d.asgari_bildirim_gun, kalan
+        ));
+    }
+    if d.organ.as_deref() == Some("genel_kurul") && d.gundem.is_empty() {
+        return Err("Genel kurul çağrısı gündem olmadan yapılamaz".to_string());
+    }
+
+    let hedef: Vec<Davetli> = davetlileri_getir(conn, tenant_id, toplanti_id)?
+        .into_iter()
+        .filter(|d| match &girdi.davetli_idler {
+            Some(idler) => idler.contains(&d.id),
+            None => d.davet_tarihi.is_none(),
+        })
+        .collect();
+    if hedef.is_empty() {
+        return Err("Çağrılacak davetli yok; önce davetli listesini oluşturun".to_string());
+    }
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for davetli in &hedef {
+        denetim.izle(conn, tenant_id, "toplanti_davetlileri", &davetli.id)?;
+        diesel::sql_query(
+            "UPDATE toplanti_davetlileri SET davet_tarihi = ?1, davet_kanali = ?2, updated_at = ?3 WHERE id = ?4 AND tenant_id = ?5",
+        )
+        .bind::<diesel::sql_types::Text, _>(cagri.format("%Y-%m-%d").to_string())
+        .bind::<diesel::sql_types::Text, _>(&girdi.kanal)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&davetli.id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(hedef.len())
+}
+
+fn katilimci_sayisini_guncelle(conn: &mut SqliteConnection, denetim: &mut Denetim, tenant_id: &str, toplanti_id: &str) -> Result<(), String> {
+    denetim.izle(conn, tenant_id, "toplantilar", toplanti_id)?;
+    diesel::sql_query(
+        "UPDATE toplantilar SET updated_at = ?1,
+            katilimci_sayisi = (SELECT COUNT(*) FROM toplanti_davetlileri
+                                WHERE tenant_id = ?2 AND toplanti_id = ?3 AND katilim = 'katildi')
+         WHERE id = ?3 AND tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(toplanti_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Katılımı işaretler (toplantı günü ya da sonrasında) ve katılımcı sayısını günceller
+pub fn katilim_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    toplanti_id: &str,
+    girdiler: &[KatilimGirdi],
+    bugun: NaiveDate,
+) -> Result<(), String> {
+    let t = toplanti_getir(conn, tenant_id, toplanti_id)?;
+    if ["iptal", "İptal"].contains(&t.durum.as_str()) {
+        return Err("İptal edilmiş toplantıya katılım işlenemez".to_string());
+    }
+    if tarih_coz(&t.tarih)? > bugun {
+        return Err("Katılım toplantı gününden önce işlenemez".to_string());
+    }
+    if genel_kurulu(conn, tenant_id, toplanti_id)?.is_some() {
+        return Err("Genel kurul katılımı hazirun imzalarından işlenir".to_string());
+    }
+    let davetliler = davetlileri_getir(conn, tenant_id, toplanti_id)?;
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    for g in girdiler {
+        if !KATILIM_DURUMLARI.contains(&g.katilim.as_str()) {
+            return Err(format!("Geçersiz katılım durumu: {}", g.katilim));
+        }
+        if !davetliler.iter().any(|d| d.id == g.davetli_id) {
+            return Err("Davetli bu toplantıya ait değil".to_string());
+        }
+        let mazeret = g.mazeret.as_deref().map(str::trim).filter(|m| !m.is_empty());
+        if mazeret.is_some() && g.katilim != "mazeretli" {
+            return Err("Mazeret yalnızca mazeretli katılmayanlara yazılır".to_string());
+        }
+        denetim.izle(conn, tenant_id, "toplanti_davetlileri", &g.davetli_id)?;
+        diesel::sql_query(
+            "UPDATE toplanti_davetlileri SET katilim = ?1, mazeret = ?2, katilim_zamani = ?3, updated_at = ?4
+             WHERE id = ?5 AND tenant_id = ?6",
+        )
+        .bind::<diesel::sql_types::Text, _>(&g.katilim)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(mazeret)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>((g.katilim == "katildi").then_some(&now))
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .bind::<diesel::sql_types::Text, _>(&g.davetli_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    katilimci_sayisini_guncelle(conn, denetim, tenant_id, toplanti_id)
+}
+
+/// Hazirun imzasını genel kurul davetlisinin katılımına yansıtır (imza geri alınırsa bekleniyor)
+pub fn hazirun_imzasini_isle(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    genel_kurul_id: &str,
+    uye_id: &str,
+    imza_zamani: Option<&str>,
+) -> Result<(), String> {
+    let gk = genel_kurul::genel_kurul_getir(conn, tenant_id, genel_kurul_id)?;
+    let Some(davetli) = davetlileri_getir(conn, tenant_id, &gk.toplanti_id)?
+        .into_iter()
+        .find(|d| d.uye_id.as_deref() == Some(uye_id))
+    else {
+        return Ok(());
+    };
+    denetim.izle(conn, tenant_id, "toplanti_davetlileri", &davetli.id)?;
+    diesel::sql_query(
+        "UPDATE toplanti_davetlileri SET katilim = ?1, katilim_zamani = ?2, mazeret = NULL, updated_at = ?3
+         WHERE id = ?4 AND tenant_id = ?5",
+    )
+    .bind::<diesel::sql_types::Text, _>(if imza_zamani.is_some() { "katildi" } else { "bekleniyor" })
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(imza_zamani)
+    .bind::<diesel::sql_types::Text, _>(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind::<diesel::sql_types::Text, _>(&davetli.id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Tarih aralığındaki (iptal edilmemiş) toplantılarda üyelerin davet ve katılım satırları
+fn katilim_satirlari(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: Option<&str>,
+    baslangic: &str,
+    bitis: &str,
+) -> Result<Vec<KatilimSatiri>, String> {
+    diesel::sql_query(
+        "SELECT t.id AS toplanti_id, t.baslik, substr(t.tarih, 1, 10) AS tarih, COALESCE(t.toplanti_tipi, '') AS toplanti_tipi,
+                d.uye_id, u.uye_no, COALESCE(u.ad_soyad, d.ad_soyad) AS ad_soyad, d.gorev, d.katilim, d.mazeret
+         FROM toplanti_davetlileri d
+         JOIN toplantilar t ON t.id = d.toplanti_id AND t.tenant_id = d.tenant_id
+         LEFT JOIN uyeler u ON u.id = d.uye_id AND u.tenant_id = d.tenant_id
+         WHERE d.tenant_id = ?1 AND d.uye_id IS NOT NULL AND (?2 IS NULL OR d.uye_id = ?2)
+           AND (t.is_deleted IS NULL OR t.is_deleted = 0) AND COALESCE(t.durum, '') NOT IN ('iptal', 'İptal')
+           AND substr(t.tarih, 1, 10) >= ?3 AND substr(t.tarih, 1, 10) <= ?4
+         ORDER BY t.tarih, t.saat, t.id",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(uye_id)
+    .bind::<diesel::sql_types::Text, _>(baslangic)
+    .bind::<diesel::sql_types::Text, _>(bitis)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+/// Üyenin çağrıldığı toplantılar ve katılım özeti
+pub fn uye_katilim_gecmisi(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    uye_id: &str,
+    baslangic: &str,
+    bitis: &str,
+) -> Result<UyeKatilimGecmisi, String> {
+    let toplantilar = katilim_satirlari(conn, tenant_id, Some(uye_id), baslangic, bitis)?;
+    let mut ozet = UyeKatilimOzeti { uye_id: uye_id.to_string(), ..Default::default() };
+    for s in &toplantilar {
+        ozet.uye_no.clone_from(&s.uye_no);
+        ozet.ad_soyad.clone_from(&s.ad_soyad);
+        ozet.ekle(s);
+    }
+    Ok(UyeKatilimGecmisi { ozet, toplantilar })
+}
+
+/// Üye bazında katılım raporu; organ verilirse yalnızca o organın toplantıları
+pub fn katilim_raporu(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    baslangic: &str,
+    bitis: &str,
+    organ: Option<&str>,
+) -> Result<Vec<UyeKatilimOzeti>, String> {
+    let mut ozetler: BTreeMap<String, UyeKatilimOzeti> = BTreeMap::new();
+    for s in katilim_satirlari(conn, tenant_id, None, baslangic, bitis)? {
+        if organ.is_some_and(|o| toplanti_organi(&s.toplanti_tipi) != Some(o)) {
+            continue;
+        }
+        let ozet = ozetler.entry(s.uye_id.clone()).or_insert_with(|| UyeKatilimOzeti {
+            uye_id: s.uye_id.clone(),
+            uye_no: s.uye_no.clone(),
+            ad_soyad: s.ad_soyad.clone(),
+            ..Default::default()
+        });
+        ozet.ekle(&s);
+    }
+    let mut rapor: Vec<UyeKatilimOzeti> = ozetler.into_values().collect();
+    rapor.sort_by(|a, b| a.ad_soyad.cmp(&b.ad_soyad));
+    Ok(rapor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        test_db::uye(&mut conn, "t1", "u2", "2", "Ayşe Kaya");
+        test_db::uye(&mut conn, "t1", "u3", "3", "Can Er");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO toplantilar (id, tenant_id, baslik, tarih, saat, toplanti_tipi, durum, gundem) VALUES
+                ('yk1', 't1', 'YK 1. toplantı', '2026-03-10', '19:00', 'Yönetim Kurulu', 'planli', 'Açılış\nAidatlar'),
+                ('gk', 't1', '2026 Olağan Genel Kurul', '2026-04-04', '10:00', 'Genel Kurul', 'planli', NULL)",
+        );
+        conn
+    }
+
+    /// YK: u1 başkan, u2 üye, u3 yedek
+    fn yonetim_kurulu(conn: &mut SqliteConnection) {
+        let mut d = Denetim::yeni(None, None, "test");
+        for (uye, gorev) in [("u1", "baskan"), ("u2", "uye"), ("u3", "yedek_uye")] {
+            let g = organ_gorevi::GorevGirdi {
+                organ: "yonetim_kurulu".to_string(),
+                komisyon_adi: None,
+                uye_id: uye.to_string(),
+                gorev: gorev.to_string(),
+                sira: None,
+                baslangic_tarihi: "2026-01-01".to_string(),
+                bitis_tarihi: None,
+                genel_kurul_id: None,
+                tahsilat_yetkisi: false,
+            };
+            organ_gorevi::gorev_kaydet(conn, &mut d, "t1", None, &g).unwrap();
+        }
+    }
+
+    fn davet(tarih: &str) -> DavetGirdi {
+        DavetGirdi { tarih: tarih.into(), kanal: "eposta".into(), davetli_idler: None }
+    }
+
+    fn gonder(conn: &mut SqliteConnection, toplanti_id: &str, tarih: &str) -> Result<usize, String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        davet_gonder(conn, &mut d, "t1", toplanti_id, &davet(tarih))
+    }
+
+    /// Genel kurula tek davetli (u3)
+    fn genel_kurul_davetlisi(conn: &mut SqliteConnection) {
+        let mut d = Denetim::yeni(None, None, "test");
+        let girdi = DavetliGirdi { uye_id: Some("u3".into()), ad_soyad: None, gorev: None };
+        davetli_ekle(conn, &mut d, "t1", "gk", &girdi).unwrap();
+    }
+
+    #[test]
+    fn toplanti_turu_organa_eslenir() {
+        assert_eq!(toplanti_organi("Yönetim Kurulu"), Some("yonetim_kurulu"));
+        assert_eq!(toplanti_organi("Genel Kurul"), Some("genel_kurul"));
+        assert_eq!(toplanti_organi("Komisyon"), None);
+    }
+
+    #[test]
+    fn kurula_asil_uyeler_gorev_adiyla_cagrilir_yedek_cagrilmaz() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        let mut d = Denetim::yeni(None, None, "test");
+        let davetliler = davetlileri_olustur(&mut conn, &mut d, "t1", "yk1").unwrap();
+        assert_eq!(davetliler.iter().map(|d| d.ad_soyad.as_str()).collect::<Vec<_>>(), ["Ali Veli", "Ayşe Kaya"]);
+        assert_eq!(davetliler[0].gorev.as_deref(), Some("Başkan"));
+        // Tekrar oluşturmak mükerrer davetli yazmaz
+        assert_eq!(davetlileri_olustur(&mut conn, &mut d, "t1", "yk1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn kurul_cagrisi_en_az_uc_gun_once_yapilir() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        let mut d = Denetim::yeni(None, None, "test");
+        davetlileri_olustur(&mut conn, &mut d, "t1", "yk1").unwrap();
+        assert!(gonder(&mut conn, "yk1", "2026-03-08").unwrap_err().contains("en az 3 gün"));
+        assert_eq!(gonder(&mut conn, "yk1", "2026-03-07").unwrap(), 2);
+    }
+
+    #[test]
+    fn genel_kurul_cagrisi_tam_on_bes_gun_once_yapilabilir() {
+        let mut conn = test_db();
+        genel_kurul_davetlisi(&mut conn);
+        test_db::calistir(&mut conn, "UPDATE toplantilar SET gundem = 'Açılış' WHERE id = 'gk'");
+        // Toplantı 04.04: 14 gün yetmez, 15 gün yeter
+        assert!(gonder(&mut conn, "gk", "2026-03-21").unwrap_err().contains("15 gün"));
+        assert_eq!(gonder(&mut conn, "gk", "2026-03-20").unwrap(), 1);
+    }
+
+    #[test]
+    fn genel_kurul_cagrisi_gundemsiz_yapilamaz() {
+        let mut conn = test_db();
+        genel_kurul_davetlisi(&mut conn);
+        assert!(gonder(&mut conn, "gk", "2026-03-15").unwrap_err().contains("gündem"));
+    }
+
+    #[test]
+    fn cagrilan_misafir_listeden_silinemez() {
+        let mut conn = test_db();
+        let mut d = Denetim::yeni(None, None, "test");
+        let girdi = DavetliGirdi { uye_id: None, ad_soyad: Some("Mali Müşavir".into()), gorev: None };
+        let misafir = davetli_ekle(&mut conn, &mut d, "t1", "yk1", &girdi).unwrap();
+        let diger = davetli_ekle(&mut conn, &mut d, "t1", "yk1", &DavetliGirdi { ad_soyad: Some("Avukat".into()), ..girdi }).unwrap();
+        davetli_sil(&mut conn, &mut d, "t1", &diger).unwrap();
+
+        gonder(&mut conn, "yk1", "2026-03-05").unwrap();
+        assert!(davetli_sil(&mut conn, &mut d, "t1", &misafir).is_err());
+    }
+
+    #[test]
+    fn davet_metni_gundem_ve_toplanti_zamanini_yazar() {
+        let mut conn = test_db();
+        let davetiye = davetiye(&mut conn, "t1", "yk1").unwrap();
+        assert_eq!(davetiye.gundem, ["Açılış", "Aidatlar"]);
+        assert!(davet_metni(&davetiye, "Örnek Derneği").contains("10.03.2026 tarihinde saat 19:00'da"));
+    }
+
+    fn katilim_isle(conn: &mut SqliteConnection) -> Result<(), String> {
+        let mut d = Denetim::yeni(None, None, "test");
+        let ids: Vec<String> = davetlileri_getir(conn, "t1", "yk1").unwrap().into_iter().map(|d| d.id).collect();
+        let katilim = vec![
+            KatilimGirdi { davetli_id: ids[0].clone(), katilim: "katildi".into(), mazeret: None },
+            KatilimGirdi { davetli_id: ids[1].clone(), katilim: "mazeretli".into(), mazeret: Some("Hastalık".into()) },
+        ];
+        katilim_kaydet(conn, &mut d, "t1", "yk1", &katilim, NaiveDate::from_ymd_opt(2026, 3, 10).unwrap())
+    }
+
+    #[test]
+    fn katilim_toplanti_gununden_once_islenemez() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        let mut d = Denetim::yeni(None, None, "test");
+        davetlileri_olustur(&mut conn, &mut d, "t1", "yk1").unwrap();
+        let ids: Vec<String> = davetlileri_getir(&mut conn, "t1", "yk1").unwrap().into_iter().map(|d| d.id).collect();
+        let katilim = [KatilimGirdi { davetli_id: ids[0].clone(), katilim: "katildi".into(), mazeret: None }];
+        let once = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        assert!(katilim_kaydet(&mut conn, &mut d, "t1", "yk1", &katilim, once).is_err());
+    }
+
+    #[test]
+    fn katilim_islenince_toplanti_sayilari_guncellenir() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        let mut d = Denetim::yeni(None, None, "test");
+        davetlileri_olustur(&mut conn, &mut d, "t1", "yk1").unwrap();
+        let misafir = DavetliGirdi { uye_id: None, ad_soyad: Some("Mali Müşavir".into()), gorev: None };
+        davetli_ekle(&mut conn, &mut d, "t1", "yk1", &misafir).unwrap();
+        gonder(&mut conn, "yk1", "2026-03-05").unwrap();
+        katilim_isle(&mut conn).unwrap();
+
+        let ozet = toplanti_katilimi(&mut conn, "t1", "yk1").unwrap();
+        assert_eq!((ozet.davet_edilen, ozet.katilan, ozet.mazeretli), (3, 1, 1));
+        #[derive(QueryableByName)]
+        struct Sayi {
+            #[diesel(sql_type = diesel::sql_types::Integer)]
+            katilimci_sayisi: i32,
+        }
+        let t: Sayi = diesel::sql_query("SELECT katilimci_sayisi FROM toplantilar WHERE id = 'yk1'")
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(t.katilimci_sayisi, 1);
+    }
+
+    #[test]
+    fn uye_katilim_oranlari_raporlanir() {
+        let mut conn = test_db();
+        yonetim_kurulu(&mut conn);
+        let mut d = Denetim::yeni(None, None, "test");
+        davetlileri_olustur(&mut conn, &mut d, "t1", "yk1").unwrap();
+        katilim_isle(&mut conn).unwrap();
+
+        let gecmis = uye_katilim_gecmisi(&mut conn, "t1", "u2", "2026-01-01", "2026-12-31").unwrap();
+        assert_eq!((gecmis.ozet.davet, gecmis.ozet.mazeretli, gecmis.ozet.katilim_orani), (1, 1, Some(0.0)));
+        let rapor = katilim_raporu(&mut conn, "t1", "2026-01-01", "2026-12-31", Some("yonetim_kurulu")).unwrap();
+        assert_eq!(rapor.iter().map(|r| (r.ad_soyad.as_str(), r.katilim_orani)).collect::<Vec<_>>(), [
+            ("Ali Veli", Some(100.0)),
+            ("Ayşe Kaya", Some(0.0))
+        ]);
+    }
+}
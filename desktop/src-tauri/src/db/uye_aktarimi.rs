@@ -0,0 +1,800 @@
+// Üye aktarımı: XLSX veya CSV dosyasından toplu üye kaydı.
+//
+// Sütunlar UyeAktarimEslestirme ile başlık adı veya 1'den başlayan sütun numarasıyla
+// seçilir; eşleştirme verilmezse başlıklar bilinen adlarla (üye Excel dışa aktarımının
+// başlıkları dahil) otomatik eşlenir. Her satır utils::validators ile doğrulanır
+// (TC kimlik algoritması, telefon, e-posta, tarih) ve mükerrerlik hem mevcut üyelerle
+// hem dosyadaki önceki satırlarla aranır: TC kimlik no aynıysa ya da TC olmayan
+// satırda ad soyad + doğum tarihi aynıysa satır mükerrerdir.
+//
+// Önizleme veritabanına yazmaz. Aktarım raporu aynı transaction içinde yeniden üretir
+// ve yalnızca geçerli satırları outbox üzerinden ekler (satırlar senkronlanır);
+// mükerrerler her zaman atlanır, hatalı satır varsa atlanması açıkça istenmedikçe
+// hiçbir satır eklenmez.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::banka_ekstresi::{ayirici_tahmin_et, csv_satirlari, sutun_bul, tarih_coz};
+use crate::db::denetim::Denetim;
+use crate::db::outbox;
+use crate::db::uyelik_durumu::{self, UyelikDurumu};
+use crate::utils::validators;
+
+/// Bir dosyada aktarılabilecek en fazla satır
+pub const AZAMI_SATIR: usize = 10_000;
+
+/// Sütun eşleştirmesi; sütunlar başlık adıyla (büyük/küçük harf duyarsız) veya
+/// 1'den başlayan sütun numarasıyla verilir
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UyeAktarimEslestirme {
+    /// CSV için; verilmezse başlık satırında ';', ',' ve sekmeden en sık geçeni
+    pub ayirici: Option<char>,
+    /// XLSX için sayfa adı; verilmezse ilk sayfa
+    pub sayfa: Option<String>,
+    /// Başlık satırından önce atlanacak satır sayısı
+    #[serde(default)]
+    pub atlanacak_satir: usize,
+    /// false ise ilk satır da veridir; sütunlar yalnızca numarayla seçilebilir
+    #[serde(default = "varsayilan_baslik")]
+    pub baslik_var: bool,
+    /// chrono biçimi ("%d/%m/%Y"); verilmezse YYYY-MM-DD ve GG.AA.YYYY
+    pub tarih_bicimi: Option<String>,
+    pub uye_no_sutunu: Option<String>,
+    pub tc_no_sutunu: Option<String>,
+    pub ad_sutunu: Option<String>,
+    pub soyad_sutunu: Option<String>,
+    /// Ad ve soyad tek sütundaysa; son kelime soyad sayılır
+    pub ad_soyad_sutunu: Option<String>,
+    pub telefon_sutunu: Option<String>,
+    pub email_sutunu: Option<String>,
+    pub dogum_tarihi_sutunu: Option<String>,
+    pub cinsiyet_sutunu: Option<String>,
+    pub uyelik_tipi_sutunu: Option<String>,
+    pub giris_tarihi_sutunu: Option<String>,
+    pub adres_sutunu: Option<String>,
+    pub il_sutunu: Option<String>,
+    pub ilce_sutunu: Option<String>,
+    pub meslek_sutunu: Option<String>,
+    pub notlar_sutunu: Option<String>,
+    /// Satırda üyelik tipi yoksa
+    pub varsayilan_uyelik_tipi: Option<String>,
+    /// Satırda giriş tarihi yoksa (YYYY-MM-DD); o da yoksa aktarım günü
+    pub varsayilan_giris_tarihi: Option<String>,
+}
+
+fn varsayilan_baslik() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AktarimSatiri {
+    /// Dosyadaki veri satırı sırası (başlık hariç, 1'den)
+    pub satir_no: usize,
+    pub uye_no: Option<String>,
+    pub tc_no: Option<String>,
+    pub ad: String,
+    pub soyad: String,
+    pub telefon: Option<String>,
+    pub email: Option<String>,
+    pub dogum_tarihi: Option<String>,
+    pub cinsiyet: Option<String>,
+    pub uyelik_tipi: Option<String>,
+    pub giris_tarihi: Option<String>,
+    pub adres: Option<String>,
+    pub il: Option<String>,
+    pub ilce: Option<String>,
+    pub meslek: Option<String>,
+    pub notlar: Option<String>,
+    /// "gecerli" | "hatali" | "mukerrer"
+    pub durum: String,
+    pub hatalar: Vec<String>,
+    /// Mükerrer olduğu mevcut üye
+    pub mukerrer_uye_id: Option<String>,
+    pub mukerrer_aciklama: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UyeAktarimRaporu {
+    pub toplam: usize,
+    pub gecerli: usize,
+    pub hatali: usize,
+    pub mukerrer: usize,
+    pub satirlar: Vec<AktarimSatiri>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UyeAktarimSonucu {
+    pub eklenen: usize,
+    pub atlanan_hatali: usize,
+    pub atlanan_mukerrer: usize,
+    pub uye_idleri: Vec<String>,
+}
+
+// ============================================================================
+// DOSYA OKUMA
+// ============================================================================
+
+/// Dosyayı satırlara çevirir (atlanacak satırlar çıkarılmış, başlık dahil).
+/// XLSX, ZIP imzasından ya da uzantısından tanınır; diğerleri UTF-8 CSV okunur.
+pub fn dosya_satirlari(dosya_adi: &str, icerik: &[u8], e: &UyeAktarimEslestirme) -> Result<Vec<Vec<String>>, String> {
+    let xlsx = icerik.starts_with(b"PK\x03\x04") || dosya_adi.to_lowercase().ends_with(".xlsx");
+    let satirlar = if xlsx {
+        let mut satirlar = xlsx_satirlari(icerik, e.sayfa.as_deref())?;
+        satirlar.drain(..e.atlanacak_satir.min(satirlar.len()));
+        satirlar
+    } else {
+        let metin = std::str::from_utf8(icerik).map_err(|_| "CSV dosyası UTF-8 olarak okunamadı".to_string())?;
+        let veri: String = metin
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .skip(e.atlanacak_satir)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let ayirici = e.ayirici.unwrap_or_else(|| ayirici_tahmin_et(veri.lines().next().unwrap_or("")));
+        csv_satirlari(&veri, ayirici)
+    };
+    if satirlar.len() > AZAMI_SATIR + usize::from(e.baslik_var) {
+        return Err(format!("Dosyada en fazla {} satır aktarılabilir", AZAMI_SATIR));
+    }
+    Ok(satirlar)
+}
+
+fn zip_metni(arsiv: &mut zip::ZipArchive<Cursor<&[u8]>>, ad: &str) -> Result<Option<String>, String> {
+    let mut dosya = match arsiv.by_name(ad) {
+        Ok(d) => d,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("XLSX okunamadı: {}", e)),
+    };
+    let mut metin = String::new();
+    dosya.read_to_string(&mut metin).map_err(|e| format!("XLSX okunamadı: {}", e))?;
+    Ok(Some(metin))
+}
+
+fn oznitelik(e: &BytesStart, ad: &str) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == ad)
+        .and_then(|a| a.normalized_value(XmlVersion::Implicit1_0).ok().map(|v| v.into_owned()))
+}
+
+fn referans_karakteri(r: &BytesRef) -> Option<char> {
+    match r.xml10_content().as_ref() {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => r.resolve_char_ref().ok().flatten(),
+    }
+}
+
+/// "AB12" → 27 (0'dan başlayan sütun)
+fn sutun_indeksi(hucre: &str) -> Option<usize> {
+    let harfler: Vec<u8> = hucre.bytes().take_while(|b| b.is_ascii_alphabetic()).collect();
+    if harfler.is_empty() {
+        return None;
+    }
+    Some(harfler.iter().fold(0usize, |t, b| t * 26 + (b.to_ascii_uppercase() - b'A' + 1) as usize) - 1)
+}
+
+fn xlsx_satirlari(icerik: &[u8], sayfa: Option<&str>) -> Result<Vec<Vec<String>>, String> {
+    let mut arsiv = zip::ZipArchive::new(Cursor::new(icerik)).map_err(|e| format!("XLSX okunamadı: {}", e))?;
+
+    // Sayfa adı → ilişki kimliği → dosya yolu
+    let kitap = zip_metni(&mut arsiv, "xl/workbook.xml")?.ok_or("XLSX çalışma kitabı bulunamadı")?;
+    let mut sayfalar: Vec<(String, String)> = Vec::new();
+    let mut okuyucu = Reader::from_str(&kitap);
+    loop {
+        match okuyucu.read_event().map_err(|e| format!("XLSX okunamadı: {}", e))? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == "sheet" => {
+                if let (Some(ad), Some(id)) = (oznitelik(&e, "name"), oznitelik(&e, "id")) {
+                    sayfalar.push((ad, id));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let (_, iliski) = match sayfa {
+        Some(s) => sayfalar
+            .iter()
+            .find(|(ad, _)| ad.trim().to_lowercase() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("XLSX dosyasında '{}' sayfası bulunamadı", s))?,
+        None => sayfalar.first().ok_or("XLSX dosyasında sayfa bulunamadı")?,
+    };
+
+    let iliskiler = zip_metni(&mut arsiv, "xl/_rels/workbook.xml.rels")?.ok_or("XLSX ilişki dosyası bulunamadı")?;
+    let mut sayfa_yolu = None;
+    let mut okuyucu = Reader::from_str(&iliskiler);
+    loop {
+        match okuyucu.read_event().map_err(|e| format!("XLSX okunamadı: {}", e))? {
+            Event::Start(e) | Event::Empty(e)
+                if e.local_name().as_ref() == "Relationship" && oznitelik(&e, "Id").as_ref() == Some(iliski) =>
+            {
+                sayfa_yolu = oznitelik(&e, "Target");
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let sayfa_yolu = sayfa_yolu.ok_or("XLSX sayfa dosyası bulunamadı")?;
+    let sayfa_yolu = match sayfa_yolu.strip_prefix('/') {
+        Some(mutlak) => mutlak.to_string(),
+        None => format!("xl/{}", sayfa_yolu),
+    };
+
+    // Paylaşılan metinler: her <si> bir metin; zengin metin parçaları (<r><t>) birleşir,
+    // fonetik okunuşlar (<rPh>) alınmaz
+    let mut paylasilan: Vec<String> = Vec::new();
+    if let Some(xml) = zip_metni(&mut arsiv, "xl/sharedStrings.xml")? {
+        let mut okuyucu = Reader::from_str(&xml);
+        let mut metin = String::new();
+        let (mut t_icinde, mut fonetik) = (false, false);
+        loop {
+            match okuyucu.read_event().map_err(|e| format!("XLSX okunamadı: {}", e))? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    "si" => metin.clear(),
+                    "t" => t_icinde = true,
+                    "rPh" => fonetik = true,
+                    _ => {}
+                },
+                Event::Text(t) if t_icinde && !fonetik => metin.push_str(&t.xml10_content()),
+                Event::GeneralRef(r) if t_icinde && !fonetik => metin.extend(referans_karakteri(&r)),
+                Event::End(e) => match e.local_name().as_ref() {
+                    "si" => paylasilan.push(std::mem::take(&mut metin)),
+                    "t" => t_icinde = false,
+                    "rPh" => fonetik = false,
+                    _ => {}
+                },
+                Event::Empty(e) if e.local_name().as_ref() == "si" => paylasilan.push(String::new()),
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+    }
+
+    let xml = zip_metni(&mut arsiv, &sayfa_yolu)?.ok_or("XLSX sayfa dosyası bulunamadı")?;
+    let mut okuyucu = Reader::from_str(&xml);
+    let mut satirlar: Vec<Vec<String>> = Vec::new();
+    let mut satir: Vec<String> = Vec::new();
+    let mut hucre: Option<(Option<usize>, Option<String>)> = None;
+    let mut deger = String::new();
+    let mut metin_icinde = false;
+    loop {
+        match okuyucu.read_event().map_err(|e| format!("XLSX okunamadı: {}", e))? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                "row" => satir.clear(),
+                "c" => {
+                    hucre = Some((oznitelik(&e, "r").as_deref().and_then(sutun_indeksi), oznitelik(&e, "t")));
+                    deger.clear();
+                }
+                "v" | "t" => metin_icinde = true,
+                _ => {}
+            },
+            Event::Empty(e) if e.local_name().as_ref() == "row" => satirlar.push(Vec::new()),
+            Event::Text(t) if metin_icinde => deger.push_str(&t.xml10_content()),
+            Event::GeneralRef(r) if metin_icinde => deger.extend(referans_karakteri(&r)),
+            Event::End(e) => match e.local_name().as_ref() {
+                "v" | "t" => metin_icinde = false,
+                "c" => {
+                    if let Some((sutun, tur)) = hucre.take() {
+                        let metin = match tur.as_deref() {
+                            Some("s") => deger
+                                .trim()
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|i| paylasilan.get(i).cloned())
+                                .unwrap_or_default(),
+                            _ => deger.clone(),
+                        };
+                        let sutun = sutun.unwrap_or(satir.len());
+                        if satir.len() <= sutun {
+                            satir.resize(sutun + 1, String::new());
+                        }
+                        satir[sutun] = metin;
+                    }
+                }
+                "row" => satirlar.push(std::mem::take(&mut satir)),
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    satirlar.retain(|s| s.iter().any(|a| !a.trim().is_empty()));
+    Ok(satirlar)
+}
+
+// ============================================================================
+// SÜTUNLAR VE DOĞRULAMA
+// ============================================================================
+
+/// Eşleştirme verilmediğinde başlıkla eşlenen adlar (ilk eşleşen kullanılır)
+const OTOMATIK_BASLIKLAR: [(&str, &[&str]); 16] = [
+    ("uye_no", &["üye no", "uye no", "üye numarası", "sicil no"]),
+    ("tc_no", &["tc no", "tc kimlik no", "t.c. kimlik no", "tc", "tckn"]),
+    ("ad", &["ad", "adı"]),
+    ("soyad", &["soyad", "soyadı"]),
+    ("ad_soyad", &["ad soyad", "adı soyadı"]),
+    ("telefon", &["telefon", "cep telefonu", "gsm", "tel"]),
+    ("email", &["email", "e-posta", "eposta", "e-mail"]),
+    ("dogum_tarihi", &["doğum tarihi", "dogum tarihi"]),
+    ("cinsiyet", &["cinsiyet"]),
+    ("uyelik_tipi", &["üyelik tipi", "üyelik türü", "üye türü"]),
+    ("giris_tarihi", &["giriş tarihi", "giris tarihi", "üyelik tarihi"]),
+    ("adres", &["adres"]),
+    ("il", &["il", "şehir"]),
+    ("ilce", &["ilçe", "ilce"]),
+    ("meslek", &["meslek"]),
+    ("notlar", &["notlar", "not", "açıklama"]),
+];
+
+fn kucuk_harf(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'I' => 'ı',
+            'İ' => 'i',
+            c => c.to_lowercase().next().unwrap_or(c),
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Alan adı → sütun indeksi
+fn sutunlari_coz(baslik: Option<&[String]>, e: &UyeAktarimEslestirme) -> Result<HashMap<&'static str, usize>, String> {
+    let secimler: [(&'static str, &Option<String>); 16] = [
+        ("uye_no", &e.uye_no_sutunu),
+        ("tc_no", &e.tc_no_sutunu),
+        ("ad", &e.ad_sutunu),
+        ("soyad", &e.soyad_sutunu),
+        ("ad_soyad", &e.ad_soyad_sutunu),
+        ("telefon", &e.telefon_sutunu),
+        ("email", &e.email_sutunu),
+        ("dogum_tarihi", &e.dogum_tarihi_sutunu),
+        ("cinsiyet", &e.cinsiyet_sutunu),
+        ("uyelik_tipi", &e.uyelik_tipi_sutunu),
+        ("giris_tarihi", &e.giris_tarihi_sutunu),
+        ("adres", &e.adres_sutunu),
+        ("il", &e.il_sutunu),
+        ("ilce", &e.ilce_sutunu),
+        ("meslek", &e.meslek_sutunu),
+        ("notlar", &e.notlar_sutunu),
+    ];
+
+    let mut sutunlar = HashMap::new();
+    if secimler.iter().any(|(_, s)| s.as_deref().is_some_and(|s| !s.trim().is_empty())) {
+        for (alan, secim) in secimler {
+            if let Some(secim) = secim.as_deref().filter(|s| !s.trim().is_empty()) {
+                sutunlar.insert(alan, sutun_bul(baslik, secim)?);
+            }
+        }
+    } else {
+        let baslik = baslik.ok_or("Başlıksız dosyada sütun eşleştirmesi verilmelidir")?;
+        let basliklar: Vec<String> = baslik.iter().map(|b| kucuk_harf(b)).collect();
+        for (alan, adlar) in OTOMATIK_BASLIKLAR.iter() {
+            if let Some(i) = basliklar.iter().position(|b| adlar.contains(&b.as_str())) {
+                sutunlar.insert(*alan, i);
+            }
+        }
+    }
+
+    let ad_var = sutunlar.contains_key("ad_soyad") || (sutunlar.contains_key("ad") && sutunlar.contains_key("soyad"));
+    if !ad_var {
+        return Err("Ad ve soyad sütunları (ya da tek ad soyad sütunu) seçilmelidir".to_string());
+    }
+    Ok(sutunlar)
+}
+
+/// Tarihi YYYY-MM-DD'ye çevirir; Excel'in gün sayısı olarak tuttuğu tarihler de okunur
+fn tarih_oku(deger: &str, bicim: Option<&str>) -> Result<String, String> {
+    if let Some(b) = bicim {
+        return tarih_coz(deger, Some(b)).ok_or_else(|| format!("Tarih '{}' biçimine uymuyor: {}", b, deger));
+    }
+    if let Ok(gun) = deger.parse::<f64>() {
+        if (1.0..=2_958_465.0).contains(&gun) {
+            let baslangic = NaiveDate::from_ymd_opt(1899, 12, 30).expect("geçerli tarih");
+            return Ok((baslangic + Duration::days(gun.trunc() as i64)).format("%Y-%m-%d").to_string());
+        }
+    }
+    validators::validate_date_format(deger)?;
+    tarih_coz(deger, None).ok_or_else(|| format!("Geçersiz tarih: {}", deger))
+}
+
+fn bos_degilse(s: &str) -> Option<String> {
+    let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!s.is_empty()).then_some(s)
+}
+
+/// Satırı alanlara ayırır ve doğrular (mükerrerlik hariç)
+fn satiri_oku(
+    satir_no: usize,
+    satir: &[String],
+    sutunlar: &HashMap<&'static str, usize>,
+    e: &UyeAktarimEslestirme,
+) -> AktarimSatiri {
+    let al = |alan: &str| -> Option<String> { sutunlar.get(alan).and_then(|i| satir.get(*i)).and_then(|s| bos_degilse(s)) };
+    let mut hatalar = Vec::new();
+
+    let (mut ad, mut soyad) = (al("ad").unwrap_or_default(), al("soyad").unwrap_or_default());
+    if ad.is_empty() && soyad.is_empty() {
+        if let Some(tam) = al("ad_soyad") {
+            match tam.rsplit_once(' ') {
+                Some((a, s)) => (ad, soyad) = (a.to_string(), s.to_string()),
+                None => ad = tam,
+            }
+        }
+    }
+    if ad.is_empty() || soyad.is_empty() {
+        hatalar.push("Ad ve soyad zorunludur".to_string());
+    }
+
+    let tc_no = al("tc_no").map(|t| t.replace(' ', ""));
+    if let Some(tc) = &tc_no {
+        if let Err(h) = validators::validate_tc_no(tc) {
+            hatalar.push(h);
+        }
+    }
+    let telefon = al("telefon");
+    if let Some(t) = &telefon {
+        if let Err(h) = validators::validate_phone(t) {
+            hatalar.push(h);
+        }
+    }
+    let email = al("email");
+    if let Some(m) = &email {
+        if let Err(h) = validators::validate_email(m) {
+            hatalar.push(h);
+        }
+    }
+    let mut tarih = |alan: &str, ad: &str| -> Option<String> {
+        let deger = al(alan)?;
+        match tarih_oku(&deger, e.tarih_bicimi.as_deref()) {
+            Ok(t) => Some(t),
+            Err(h) => {
+                hatalar.push(format!("{}: {}", ad, h));
+                None
+            }
+        }
+    };
+    let dogum_tarihi = tarih("dogum_tarihi", "Doğum tarihi");
+    let giris_tarihi = tarih("giris_tarihi", "Giriş tarihi");
+
+    AktarimSatiri {
+        satir_no,
+        uye_no: al("uye_no"),
+        tc_no,
+        ad,
+        soyad,
+        telefon,
+        email,
+        dogum_tarihi,
+        cinsiyet: al("cinsiyet"),
+        uyelik_tipi: al("uyelik_tipi").or_else(|| e.varsayilan_uyelik_tipi.as_deref().and_then(bos_degilse)),
+        giris_tarihi,
+        adres: al("adres"),
+        il: al("il"),
+        ilce: al("ilce"),
+        meslek: al("meslek"),
+        notlar: al("notlar"),
+        durum: if hatalar.is_empty() { "gecerli" } else { "hatali" }.to_string(),
+        hatalar,
+        mukerrer_uye_id: None,
+        mukerrer_aciklama: None,
+    }
+}
+
+// ============================================================================
+// RAPOR VE AKTARIM
+// ============================================================================
+
+/// Dosyayı okur, satırları doğrular ve mükerrerleri işaretler; veritabanına yazmaz
+pub fn rapor_olustur(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    dosya_adi: &str,
+    icerik: &[u8],
+    e: &UyeAktarimEslestirme,
+) -> Result<UyeAktarimRaporu, String> {
+    #[derive(QueryableByName)]
+    struct MevcutRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        uye_no: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        tc_no: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        ad_soyad: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        dogum_tarihi: Option<String>,
+    }
+
+    let satirlar = dosya_satirlari(dosya_adi, icerik, e)?;
+    let mut satirlar = satirlar.into_iter();
+    let baslik = if e.baslik_var { satirlar.next() } else { None };
+    let sutunlar = sutunlari_coz(baslik.as_deref(), e)?;
+
+    let mevcut: Vec<MevcutRow> =
+        diesel::sql_query("SELECT id, uye_no, tc_no, ad_soyad, dogum_tarihi FROM uyeler WHERE tenant_id = ?1")
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .load(conn)
+            .map_err(|e| e.to_string())?;
+
+    // Anahtar → (mevcut üye id'si ya da dosyadaki satır no, açıklama)
+    enum Kaynak {
+        Uye(String),
+        Satir(usize),
+    }
+    let mut tc_anahtari: HashMap<String, (Kaynak, String)> = HashMap::new();
+    let mut ad_anahtari: HashMap<(String, String), (Kaynak, String)> = HashMap::new();
+    let mut uye_nolari: HashMap<String, Kaynak> = HashMap::new();
+    for m in mevcut {
+        let ad = m.ad_soyad.unwrap_or_default();
+        if let Some(no) = m.uye_no.filter(|n| !n.trim().is_empty()) {
+            uye_nolari.insert(no.trim().to_string(), Kaynak::Uye(m.id.clone()));
+        }
+        if let Some(tc) = m.tc_no.filter(|t| !t.trim().is_empty()) {
+            tc_anahtari.insert(tc.trim().to_string(), (Kaynak::Uye(m.id.clone()), ad.clone()));
+        }
+        if let Some(dogum) = m.dogum_tarihi.as_deref().and_then(|d| tarih_coz(d, None)) {
+            ad_anahtari.insert((kucuk_harf(&ad), dogum), (Kaynak::Uye(m.id), ad));
+        }
+    }
+
+    let mut rapor = UyeAktarimRaporu { toplam: 0, gecerli: 0, hatali: 0, mukerrer: 0, satirlar: Vec::new() };
+    for (n, ham) in satirlar.enumerate() {
+        let mut s = satiri_oku(n + 1, &ham, &sutunlar, e);
+        let tam_ad = format!("{} {}", s.ad, s.soyad);
+
+        let eslesen = match &s.tc_no {
+            Some(tc) => tc_anahtari.get(tc).map(|(k, ad)| (k, format!("TC kimlik no aynı ({})", ad))),
+            None => s.dogum_tarihi.as_ref().and_then(|d| {
+                ad_anahtari
+                    .get(&(kucuk_harf(&tam_ad), d.clone()))
+                    .map(|(k, ad)| (k, format!("Ad soyad ve doğum tarihi aynı ({})", ad)))
+            }),
+        };
+        if let Some((kaynak, aciklama)) = eslesen {
+            match kaynak {
+                Kaynak::Uye(id) => {
+                    s.mukerrer_uye_id = Some(id.clone());
+                    s.mukerrer_aciklama = Some(format!("Mevcut üye: {}", aciklama));
+                }
+                Kaynak::Satir(no) => s.mukerrer_aciklama = Some(format!("Dosyada {}. satır: {}", no, aciklama)),
+            }
+        }
+        if let Some(no) = &s.uye_no {
+            match uye_nolari.get(no) {
+                Some(Kaynak::Uye(id)) if s.mukerrer_uye_id.as_ref() != Some(id) => {
+                    s.hatalar.push(format!("Üye no {} başka bir üyede kayıtlı", no))
+                }
+                Some(Kaynak::Satir(k)) if s.mukerrer_aciklama.is_none() => {
+                    s.hatalar.push(format!("Üye no {} dosyada {}. satırda da var", no, k))
+                }
+                _ => {}
+            }
+        }
+
+        s.durum = if !s.hatalar.is_empty() {
+            "hatali"
+        } else if s.mukerrer_aciklama.is_some() {
+            "mukerrer"
+        } else {
+            "gecerli"
+        }
+        .to_string();
+
+        // Yalnızca eklenecek satırlar sonraki satırların mükerrerlik kontrolüne girer
+        if s.durum == "gecerli" {
+            if let Some(tc) = &s.tc_no {
+                tc_anahtari.insert(tc.clone(), (Kaynak::Satir(s.satir_no), tam_ad.clone()));
+            }
+            if let Some(d) = &s.dogum_tarihi {
+                ad_anahtari.insert((kucuk_harf(&tam_ad), d.clone()), (Kaynak::Satir(s.satir_no), tam_ad.clone()));
+            }
+            if let Some(no) = &s.uye_no {
+                uye_nolari.insert(no.clone(), Kaynak::Satir(s.satir_no));
+            }
+        }
+
+        match s.durum.as_str() {
+            "gecerli" => rapor.gecerli += 1,
+            "mukerrer" => rapor.mukerrer += 1,
+            _ => rapor.hatali += 1,
+        }
+        rapor.satirlar.push(s);
+    }
+    rapor.toplam = rapor.satirlar.len();
+    if rapor.toplam == 0 {
+        return Err("Dosyada aktarılacak satır bulunamadı".to_string());
+    }
+    Ok(rapor)
+}
+
+/// Raporun geçerli satırlarını üye olarak ekler; çağıranın transaction'ı içinde.
+/// Hatalı satır varsa ve hatalilari_atla verilmemişse hiçbir satır eklenmez.
+pub fn aktar(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    rapor: &UyeAktarimRaporu,
+    hatalilari_atla: bool,
+    varsayilan_giris_tarihi: &str,
+) -> Result<UyeAktarimSonucu, String> {
+    if rapor.hatali > 0 && !hatalilari_atla {
+        let ilk = rapor.satirlar.iter().find(|s| s.durum == "hatali").expect("hatalı satır");
+        return Err(format!(
+            "{} satırda hata var (ilki {}. satır: {}); düzeltin ya da hatalı satırları atlayarak aktarın",
+            rapor.hatali,
+            ilk.satir_no,
+            ilk.hatalar.join(", ")
+        ));
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut uye_idleri = Vec::new();
+    for s in rapor.satirlar.iter().filter(|s| s.durum == "gecerli") {
+        let id = Uuid::new_v4().to_string();
+        let giris_tarihi = s.giris_tarihi.as_deref().unwrap_or(varsayilan_giris_tarihi);
+        diesel::sql_query(
+            "INSERT INTO uyeler (
+                id, tenant_id, uye_no, tc_no, ad, soyad, ad_soyad,
+                telefon, email, adres, cinsiyet, dogum_tarihi, meslek, il, ilce,
+                uyelik_tipi, giris_tarihi, durum, notlar, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, 'Aktif', ?18, ?19, ?19)",
+        )
+        .bind::<diesel::sql_types::Text, _>(&id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(s.uye_no.as_deref().unwrap_or(&id))
+        .bind::<diesel::sql_types::Text, _>(s.tc_no.as_deref().unwrap_or(""))
+        .bind::<diesel::sql_types::Text, _>(&s.ad)
+        .bind::<diesel::sql_types::Text, _>(&s.soyad)
+        .bind::<diesel::sql_types::Text, _>(format!("{} {}", s.ad, s.soyad))
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.telefon)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.email)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.adres)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.cinsiyet)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.dogum_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.meslek)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.il)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.ilce)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.uyelik_tipi)
+        .bind::<diesel::sql_types::Text, _>(giris_tarihi)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&s.notlar)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| format!("{}. satır eklenemedi: {}", s.satir_no, e))?;
+
+        denetim.yeni_kayit(tenant_id, "uyeler", &id);
+        uyelik_durumu::ilk_durumu_yaz(conn, denetim, tenant_id, &id, UyelikDurumu::Asil, giris_tarihi, None)?;
+        outbox::queue_change(conn, tenant_id, "uyeler", &id, "create")?;
+        uye_idleri.push(id);
+    }
+
+    Ok(UyeAktarimSonucu {
+        eklenen: uye_idleri.len(),
+        atlanan_hatali: rapor.hatali,
+        atlanan_mukerrer: rapor.mukerrer,
+        uye_idleri,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO uyeler (id, tenant_id, uye_no, tc_no, ad, soyad, ad_soyad, dogum_tarihi, giris_tarihi) VALUES
+                ('u1', 't1', '1', '10000000146', 'Ali', 'Veli', 'Ali Veli', '1980-01-01', '2020-01-01'),
+                ('u2', 't1', '2', '', 'Ayşe', 'Işık', 'Ayşe Işık', '1990-05-06', '2020-01-01')",
+        );
+        conn
+    }
+
+    #[test]
+    fn csv_dogrulama_ve_mukerrer() {
+        let mut conn = test_db();
+        let csv = "Üye No;Ad Soyad;TC No;Telefon;Email;Doğum Tarihi\n\
+                   10;Mehmet Can Öz;10000000146;05321234567;;\n\
+                   11;AYŞE IŞIK;;0532 123 45 67;ayse@ornek.org;06.05.1990\n\
+                   12;Zeynep Kaya;12345678901;123;zeynep@;32.13.2000\n\
+                   13;Deniz Ak;;;;1995-02-03\n\
+                   14;Deniz Ak;;;;03.02.1995\n\
+                   1;Yeni Üye;;;;\n";
+        let e = UyeAktarimEslestirme { baslik_var: true, ..Default::default() };
+        let rapor = rapor_olustur(&mut conn, "t1", "uyeler.csv", csv.as_bytes(), &e).unwrap();
+        let durumlar: Vec<&str> = rapor.satirlar.iter().map(|s| s.durum.as_str()).collect();
+        assert_eq!(durumlar, ["mukerrer", "mukerrer", "hatali", "gecerli", "mukerrer", "hatali"]);
+        assert_eq!(rapor.satirlar[0].mukerrer_uye_id.as_deref(), Some("u1"));
+        // Türkçe büyük harf farkı gözetilmez, GG.AA.YYYY çevrilir
+        assert_eq!(rapor.satirlar[1].mukerrer_uye_id.as_deref(), Some("u2"));
+        assert_eq!(rapor.satirlar[2].hatalar.len(), 4);
+        assert_eq!((rapor.satirlar[3].ad.as_str(), rapor.satirlar[3].soyad.as_str()), ("Deniz", "Ak"));
+        assert!(rapor.satirlar[4].mukerrer_aciklama.as_deref().unwrap().starts_with("Dosyada 4. satır"));
+        assert!(rapor.satirlar[5].hatalar[0].contains("Üye no 1"));
+
+        let mut denetim = Denetim::yeni(None, None, "test");
+        assert!(aktar(&mut conn, &mut denetim, "t1", &rapor, false, "2026-10-18").is_err());
+        let sonuc = aktar(&mut conn, &mut denetim, "t1", &rapor, true, "2026-10-18").unwrap();
+        assert_eq!((sonuc.eklenen, sonuc.atlanan_hatali, sonuc.atlanan_mukerrer), (1, 2, 3));
+
+        #[derive(QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            uye_no: String,
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            giris_tarihi: String,
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            outbox: i64,
+        }
+        let r: Row = diesel::sql_query(
+            "SELECT uye_no, giris_tarihi, (SELECT COUNT(*) FROM sync_changes WHERE table_name = 'uyeler') AS outbox
+             FROM uyeler WHERE ad_soyad = 'Deniz Ak'",
+        )
+        .get_result(&mut conn)
+        .unwrap();
+        assert_eq!((r.uye_no.as_str(), r.giris_tarihi.as_str(), r.outbox), ("13", "2026-10-18", 1));
+
+        // Aynı dosya ikinci kez: eklenen satır artık mevcut üye
+        let rapor = rapor_olustur(&mut conn, "t1", "uyeler.csv", csv.as_bytes(), &e).unwrap();
+        assert_eq!(rapor.satirlar[3].durum, "mukerrer");
+    }
+
+    #[test]
+    fn xlsx_okuma_ve_eslestirme() {
+        let mut kitap = rust_xlsxwriter::Workbook::new();
+        let sayfa = kitap.add_worksheet();
+        sayfa.set_name("Üyeler").unwrap();
+        for (i, b) in ["Sıra", "Adı", "Soyadı", "Kimlik", "Giriş"].iter().enumerate() {
+            sayfa.write_string(0, i as u16, *b).unwrap();
+        }
+        sayfa.write_number(1, 0, 1).unwrap();
+        sayfa.write_string(1, 1, "Ece & Su").unwrap();
+        sayfa.write_string(1, 2, "Yılmaz").unwrap();
+        sayfa.write_string(1, 3, "10000000146").unwrap();
+        // Excel tarih seri numarası: 45292 = 2024-01-01
+        sayfa.write_number(1, 4, 45292).unwrap();
+        sayfa.write_string(3, 1, "Can").unwrap();
+        sayfa.write_string(3, 2, "Er").unwrap();
+        let icerik = kitap.save_to_buffer().unwrap();
+
+        let satirlar = dosya_satirlari("x.xlsx", &icerik, &UyeAktarimEslestirme::default()).unwrap();
+        assert_eq!(satirlar.len(), 3);
+        assert_eq!(satirlar[1], ["1", "Ece & Su", "Yılmaz", "10000000146", "45292"]);
+        assert_eq!(satirlar[2], ["", "Can", "Er"]);
+        assert!(dosya_satirlari("x.xlsx", &icerik, &UyeAktarimEslestirme { sayfa: Some("Yok".into()), ..Default::default() }).is_err());
+
+        // Sütunlar başlık adı ya da numarasıyla seçilir
+        let mut conn = test_db();
+        let e = UyeAktarimEslestirme {
+            baslik_var: true,
+            ad_sutunu: Some("adı".into()),
+            soyad_sutunu: Some("3".into()),
+            tc_no_sutunu: Some("Kimlik".into()),
+            giris_tarihi_sutunu: Some("Giriş".into()),
+            ..Default::default()
+        };
+        let rapor = rapor_olustur(&mut conn, "t1", "x.xlsx", &icerik, &e).unwrap();
+        assert_eq!(rapor.satirlar[0].giris_tarihi.as_deref(), Some("2024-01-01"));
+        assert_eq!(rapor.satirlar[0].durum, "mukerrer");
+        assert_eq!(rapor.satirlar[1].durum, "gecerli");
+        let basliksiz = UyeAktarimEslestirme { baslik_var: false, ..Default::default() };
+        assert!(rapor_olustur(&mut conn, "t1", "x.xlsx", &icerik, &basliksiz).is_err());
+    }
+}
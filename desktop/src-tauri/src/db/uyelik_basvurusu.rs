@@ -0,0 +1,597 @@
+// Üyelik başvurusu ve yönetim kurulu kararı.
+//
+// Tüzük gereği yeni üye doğrudan kaydedilmez: başvuru 'bekliyor' durumunda açılır ve
+// yönetim kurulu en geç KARAR_SURESI_GUN içinde karar verir (karar_son_tarihi). Karar,
+// alındığı toplantıya (toplantilar) ve karar numarasına bağlanır. Onayda üye kaydı açılır,
+// durum geçmişine başvuru tarihinden aday ve karar tarihinden asil satırı yazılır (bkz.
+// db::uyelik_durumu) ve giriş döneminin aidatı tahakkuk eder. Ayrılmış ya da ihraç edilmiş
+// eski üyenin başvurusu o üyeye bağlanır; onayda aynı kayıt yeniden kabul edilir.
+
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::aidat_tahakkuku::{self, IlkAidat};
+use crate::db::denetim::Denetim;
+use crate::db::outbox;
+use crate::db::uyelik_durumu::{self, DurumDegisikligi, UyelikDurumu};
+use crate::utils::validators;
+
+/// Başvurunun karara bağlanması gereken süre
+pub const KARAR_SURESI_GUN: i64 = 30;
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct UyelikBasvurusu {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tc_no: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ad: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub soyad: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub telefon: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub email: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub dogum_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub cinsiyet: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub meslek: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub adres: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub il: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ilce: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uyelik_tipi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub referans_uye_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub referans_ad_soyad: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub basvuru_tarihi: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub karar_son_tarihi: String,
+    /// Bekleyen başvuruda karar süresinin bitmesine kalan gün (geçtiyse negatif)
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub kalan_gun: Option<i64>,
+    /// Karar verilmiş başvuruda karar süresi içinde verildi mi
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Bool>)]
+    pub suresinde: Option<bool>,
+    /// "bekliyor" | "onaylandi" | "reddedildi"
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub durum: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub toplanti_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub toplanti_basligi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karar_no: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub karar_tarihi: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub red_nedeni: Option<String>,
+    /// Onayda açılan üye; eski üyenin başvurusunda baştan dolu
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub uye_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub notlar: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BasvuruGirdi {
+    pub tc_no: String,
+    pub ad: String,
+    pub soyad: String,
+    pub telefon: Option<String>,
+    pub email: Option<String>,
+    pub dogum_tarihi: Option<String>,
+    pub cinsiyet: Option<String>,
+    pub meslek: Option<String>,
+    pub adres: Option<String>,
+    pub il: Option<String>,
+    pub ilce: Option<String>,
+    pub uyelik_tipi: Option<String>,
+    pub referans_uye_id: Option<String>,
+    /// YYYY-MM-DD
+    pub basvuru_tarihi: String,
+    pub notlar: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BasvuruKarari {
+    pub onay: bool,
+    /// Kararın alındığı yönetim kurulu toplantısı
+    pub toplanti_id: Option<String>,
+    pub karar_no: String,
+    /// Verilmezse toplantı tarihi
+    pub karar_tarihi: Option<String>,
+    /// Redde zorunlu
+    pub red_nedeni: Option<String>,
+    /// Onayda giriş döneminin aidatı açılsın mı (varsayılan evet)
+    pub ilk_aidat: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BasvuruSonucu {
+    pub basvuru: UyelikBasvurusu,
+    pub ilk_aidatlar: Vec<IlkAidat>,
+}
+
+const BASVURU_SECIMI: &str = "SELECT b.id, b.tc_no, b.ad, b.soyad, b.telefon, b.email, b.dogum_tarihi, b.cinsiyet,
+        b.meslek, b.adres, b.il, b.ilce, b.uyelik_tipi, b.referans_uye_id, r.ad_soyad AS referans_ad_soyad,
+        b.basvuru_tarihi, b.karar_son_tarihi,
+        CASE WHEN b.durum = 'bekliyor'
+             THEN CAST(julianday(b.karar_son_tarihi) - julianday(?2) AS INTEGER) END AS kalan_gun,
+        CASE WHEN b.durum != 'bekliyor' THEN b.karar_tarihi <= b.karar_son_tarihi END AS suresinde,
+        b.durum, b.toplanti_id, t.baslik AS toplanti_basligi, b.karar_no, b.karar_tarihi, b.red_nedeni,
+        b.uye_id, b.notlar, b.created_at, b.updated_at
+     FROM uyelik_basvurulari b
+     LEFT JOIN uyeler r ON r.id = b.referans_uye_id AND r.tenant_id = b.tenant_id
+     LEFT JOIN toplantilar t ON t.id = b.toplanti_id AND t.tenant_id = b.tenant_id";
+
+fn tarih_dogrula(tarih: &str, alan: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(tarih.trim(), "%Y-%m-%d").map_err(|_| format!("Geçersiz {}: {}", alan, tarih))
+}
+
+fn bos_degilse(s: &Option<String>) -> Option<&str> {
+    s.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// durum: "bekliyor", "onaylandi", "reddedildi" ya da süresi geçmiş bekleyenler için "suresi_gecen"
+pub fn basvurulari_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    durum: Option<&str>,
+    bugun: NaiveDate,
+) -> Result<Vec<UyelikBasvurusu>, String> {
+    diesel::sql_query(format!(
+        "{} WHERE b.tenant_id = ?1
+           AND (?3 IS NULL OR b.durum = ?3 OR (?3 = 'suresi_gecen' AND b.durum = 'bekliyor' AND b.karar_son_tarihi < ?2))
+         ORDER BY CASE WHEN b.durum = 'bekliyor' THEN 0 ELSE 1 END, b.karar_son_tarihi, b.basvuru_tarihi DESC",
+        BASVURU_SECIMI
+    ))
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(bugun.format("%Y-%m-%d").to_string())
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(durum)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+pub fn basvuru_getir(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    basvuru_id: &str,
+    bugun: NaiveDate,
+) -> Result<UyelikBasvurusu, String> {
+    diesel::sql_query(format!("{} WHERE b.tenant_id = ?1 AND b.id = ?3", BASVURU_SECIMI))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(bugun.format("%Y-%m-%d").to_string())
+        .bind::<diesel::sql_types::Text, _>(basvuru_id)
+        .get_result(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Başvuru bulunamadı".to_string())
+}
+
+/// Başvuru açar (basvuru_id yoksa) ya da bekleyen başvuruyu günceller
+pub fn basvuru_kaydet(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    basvuru_id: Option<&str>,
+    girdi: &BasvuruGirdi,
+) -> Result<String, String> {
+    #[derive(QueryableByName)]
+    struct UyeRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        durum: Option<String>,
+    }
+    #[derive(QueryableByName)]
+    struct SayiRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        sayi: i64,
+    }
+
+    let tc_no = girdi.tc_no.trim();
+    validators::validate_tc_no(tc_no)?;
+    if girdi.ad.trim().is_empty() || girdi.soyad.trim().is_empty() {
+        return Err("Ad ve soyad zorunludur".to_string());
+    }
+    if let Some(t) = bos_degilse(&girdi.telefon) {
+        validators::validate_phone(t)?;
+    }
+    if let Some(e) = bos_degilse(&girdi.email) {
+        validators::validate_email(e)?;
+    }
+    if let Some(d) = bos_degilse(&girdi.dogum_tarihi) {
+        tarih_dogrula(d, "doğum tarihi")?;
+    }
+    let basvuru_tarihi = tarih_dogrula(&girdi.basvuru_tarihi, "başvuru tarihi")?;
+    let karar_son_tarihi = basvuru_tarihi + Duration::days(KARAR_SURESI_GUN);
+
+    // Aynı kişinin bekleyen başka başvurusu ya da sürmekte olan üyeliği olamaz
+    let bekleyen: SayiRow = diesel::sql_query(
+        "SELECT COUNT(*) AS sayi FROM uyelik_basvurulari
+         WHERE tenant_id = ?1 AND tc_no = ?2 AND durum = 'bekliyor' AND id != ?3",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(tc_no)
+    .bind::<diesel::sql_types::Text, _>(basvuru_id.unwrap_or(""))
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if bekleyen.sayi > 0 {
+        return Err("Bu TC kimlik numarasıyla bekleyen bir başvuru var".to_string());
+    }
+    let uyeler: Vec<UyeRow> = diesel::sql_query("SELECT id, durum FROM uyeler WHERE tenant_id = ?1 AND tc_no = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(tc_no)
+        .load(conn)
+        .map_err(|e| e.to_string())?;
+    let mut eski_uye = None;
+    for u in uyeler {
+        match u.durum.as_deref().and_then(UyelikDurumu::coz) {
+            Some(d) if d.ayrilmis() => eski_uye = Some(u.id),
+            _ => return Err("Bu TC kimlik numarasıyla kayıtlı bir üye var".to_string()),
+        }
+    }
+
+    if let Some(referans) = bos_degilse(&girdi.referans_uye_id) {
+        let r: Option<UyeRow> = diesel::sql_query("SELECT id, durum FROM uyeler WHERE id = ?1 AND tenant_id = ?2")
+            .bind::<diesel::sql_types::Text, _>(referans)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .get_result(conn)
+            .optional()
+            .map_err(|e| e.to_string())?;
+        match r.and_then(|r| r.durum).as_deref().and_then(UyelikDurumu::coz) {
+            Some(UyelikDurumu::Asil) => {}
+            Some(_) => return Err("Referans üye asil üye olmalıdır".to_string()),
+            None => return Err("Referans üye bulunamadı".to_string()),
+        }
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let id = match basvuru_id {
+        Some(id) => {
+            let mevcut = basvuru_getir(conn, tenant_id, id, basvuru_tarihi)?;
+            if mevcut.durum != "bekliyor" {
+                return Err("Karara bağlanmış başvuru değiştirilemez".to_string());
+            }
+            denetim.izle(conn, tenant_id, "uyelik_basvurulari", id)?;
+            id.to_string()
+        }
+        None => {
+            let id = Uuid::new_v4().to_string();
+            diesel::sql_query(
+                "INSERT INTO uyelik_basvurulari (id, tenant_id, tc_no, ad, soyad, basvuru_tarihi, karar_son_tarihi,
+                    durum, created_at, updated_at)
+                 VALUES (?1, ?2, '', '', '', '', '', 'bekliyor', ?3, ?3)",
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+            denetim.yeni_kayit(tenant_id, "uyelik_basvurulari", &id);
+            id
+        }
+    };
+
+    diesel::sql_query(
+        "UPDATE uyelik_basvurulari SET tc_no = ?1, ad = ?2, soyad = ?3, telefon = ?4, email = ?5, dogum_tarihi = ?6,
+            cinsiyet = ?7, meslek = ?8, adres = ?9, il = ?10, ilce = ?11, uyelik_tipi = ?12, referans_uye_id = ?13,
+            basvuru_tarihi = ?14, karar_son_tarihi = ?15, uye_id = ?16, notlar = ?17, updated_at = ?18
+         WHERE id = ?19 AND tenant_id = ?20",
+    )
+    .bind::<diesel::sql_types::Text, _>(tc_no)
+    .bind::<diesel::sql_types::Text, _>(girdi.ad.trim())
+    .bind::<diesel::sql_types::Text, _>(girdi.soyad.trim())
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.telefon))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.email))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.dogum_tarihi))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.cinsiyet))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.meslek))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.adres))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.il))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.ilce))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.uyelik_tipi))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.referans_uye_id))
+    .bind::<diesel::sql_types::Text, _>(basvuru_tarihi.format("%Y-%m-%d").to_string())
+    .bind::<diesel::sql_types::Text, _>(karar_son_tarihi.format("%Y-%m-%d").to_string())
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&eski_uye)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&girdi.notlar))
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+/// Yönetim kurulu kararını işler. Onayda üye açılır (ya da eski üye yeniden kabul edilir),
+/// üye için outbox kaydı yazılır ve istenirse ilk aidat tahakkuk eder.
+pub fn karar_ver(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    basvuru_id: &str,
+    karar: &BasvuruKarari,
+    bugun: NaiveDate,
+) -> Result<Vec<IlkAidat>, String> {
+    #[derive(QueryableByName)]
+    struct ToplantiRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+    }
+
+    let basvuru = basvuru_getir(conn, tenant_id, basvuru_id, bugun)?;
+    if basvuru.durum != "bekliyor" {
+        return Err("Başvuru zaten karara bağlanmış".to_string());
+    }
+    let karar_no = karar.karar_no.trim();
+    if karar_no.is_empty() {
+        return Err("Karar numarası zorunludur".to_string());
+    }
+    let toplanti_tarihi = match bos_degilse(&karar.toplanti_id) {
+        Some(toplanti_id) => {
+            let t: ToplantiRow = diesel::sql_query(
+                "SELECT tarih FROM toplantilar
+                 WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+            )
+            .bind::<diesel::sql_types::Text, _>(toplanti_id)
+            .bind::<diesel::sql_types::Text, _>(tenant_id)
+            .get_result(conn)
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or("Toplantı bulunamadı")?;
+            Some(t.tarih.get(..10).unwrap_or(&t.tarih).to_string())
+        }
+        None => None,
+    };
+    let karar_tarihi = match (bos_degilse(&karar.karar_tarihi), toplanti_tarihi.as_deref()) {
+        (Some(t), _) | (None, Some(t)) => tarih_dogrula(t, "karar tarihi")?,
+        (None, None) => return Err("Karar tarihi ya da kararın alındığı toplantı belirtilmelidir".to_string()),
+    };
+    let karar_tarihi_metni = karar_tarihi.format("%Y-%m-%d").to_string();
+    if karar_tarihi_metni < basvuru.basvuru_tarihi {
+        return Err("Karar tarihi başvuru tarihinden önce olamaz".to_string());
+    }
+    let red_nedeni = bos_degilse(&karar.red_nedeni);
+    if !karar.onay && red_nedeni.is_none() {
+        return Err("Ret kararında gerekçe zorunludur".to_string());
+    }
+
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut uye_id = basvuru.uye_id.clone();
+    if karar.onay {
+        uye_id = Some(uyeye_donustur(conn, denetim, tenant_id, &basvuru, karar_no, &karar_tarihi_metni, &now)?);
+    }
+
+    denetim.izle(conn, tenant_id, "uyelik_basvurulari", basvuru_id)?;
+    diesel::sql_query(
+        "UPDATE uyelik_basvurulari SET durum = ?1, toplanti_id = ?2, karar_no = ?3, karar_tarihi = ?4,
+            red_nedeni = ?5, uye_id = ?6, updated_at = ?7
+         WHERE id = ?8 AND tenant_id = ?9",
+    )
+    .bind::<diesel::sql_types::Text, _>(if karar.onay { "onaylandi" } else { "reddedildi" })
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bos_degilse(&karar.toplanti_id))
+    .bind::<diesel::sql_types::Text, _>(karar_no)
+    .bind::<diesel::sql_types::Text, _>(&karar_tarihi_metni)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(if karar.onay { None } else { red_nedeni })
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&uye_id)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .bind::<diesel::sql_types::Text, _>(basvuru_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+
+    match uye_id {
+        Some(uye_id) if karar.onay && karar.ilk_aidat.unwrap_or(true) => {
+            aidat_tahakkuku::ilk_aidatlari_olustur(conn, denetim, tenant_id, &uye_id, karar_tarihi)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Başvurudan üye kaydı açar ya da eski üyeyi yeniden kabul eder; üye id'si döner
+fn uyeye_donustur(
+    conn: &mut SqliteConnection,
+    denetim: &mut Denetim,
+    tenant_id: &str,
+    basvuru: &UyelikBasvurusu,
+    karar_no: &str,
+    karar_tarihi: &str,
+    now: &str,
+) -> Result<String, String> {
+    let kabul = DurumDegisikligi {
+        durum: UyelikDurumu::Asil.kod().to_string(),
+        tarih: karar_tarihi.to_string(),
+        karar_no: Some(karar_no.to_string()),
+        karar_tarihi: Some(karar_tarihi.to_string()),
+        neden: None,
+        aciklama: Some("Üyelik başvurusu kabulü".to_string()),
+    };
+
+    if let Some(uye_id) = &basvuru.uye_id {
+        // Eski üye: başvuru tarihinden aday, karar tarihinden asil
+        let aday = DurumDegisikligi {
+            durum: UyelikDurumu::Aday.kod().to_string(),
+            tarih: basvuru.basvuru_tarihi.clone(),
+            karar_no: None,
+            karar_tarihi: None,
+            neden: None,
+            aciklama: Some("Yeniden üyelik başvurusu".to_string()),
+        };
+        uyelik_durumu::durum_degistir(conn, denetim, tenant_id, uye_id, &aday)?;
+        uyelik_durumu::durum_degistir(conn, denetim, tenant_id, uye_id, &kabul)?;
+        outbox::queue_change(conn, tenant_id, "uyeler", uye_id, "update")?;
+        return Ok(uye_id.clone());
+    }
+
+    let id = Uuid::new_v4().to_string();
+    diesel::sql_query(
+        "INSERT INTO uyeler (
+            id, tenant_id, uye_no, tc_no, ad, soyad, ad_soyad, telefon, email, cinsiyet, dogum_tarihi,
+            meslek, adres, il, ilce, uyelik_tipi, giris_tarihi, durum, referans_uye_id, created_at, updated_at
+        ) VALUES (?1, ?2, ?1, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?19)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&basvuru.tc_no)
+    .bind::<diesel::sql_types::Text, _>(&basvuru.ad)
+    .bind::<diesel::sql_types::Text, _>(&basvuru.soyad)
+    .bind::<diesel::sql_types::Text, _>(format!("{} {}", basvuru.ad, basvuru.soyad))
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.telefon)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.email)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.cinsiyet)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.dogum_tarihi)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.meslek)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.adres)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.il)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.ilce)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.uyelik_tipi)
+    .bind::<diesel::sql_types::Text, _>(karar_tarihi)
+    .bind::<diesel::sql_types::Text, _>(UyelikDurumu::Aday.uye_durumu())
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&basvuru.referans_uye_id)
+    .bind::<diesel::sql_types::Text, _>(now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+    denetim.yeni_kayit(tenant_id, "uyeler", &id);
+
+    uyelik_durumu::ilk_durumu_yaz(conn, denetim, tenant_id, &id, UyelikDurumu::Aday, &basvuru.basvuru_tarihi, None)?;
+    uyelik_durumu::durum_degistir(conn, denetim, tenant_id, &id, &kabul)?;
+    outbox::queue_change(conn, tenant_id, "uyeler", &id, "create")?;
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{connection, test_db};
+
+    fn test_db() -> SqliteConnection {
+        let mut conn = test_db::surumden_once("049");
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO uyeler (id, tenant_id, uye_no, tc_no, ad, soyad, ad_soyad, giris_tarihi, cikis_tarihi, durum) VALUES
+                ('u1', 't1', '1', '10000000146', 'Ali', 'Veli', 'Ali Veli', '2020-01-01', NULL, 'Aktif'),
+                ('u2', 't1', '2', '12345678950', 'Eski', 'Üye', 'Eski Üye', '2018-01-01', '2023-12-31', 'Ayrıldı')",
+        );
+        test_db::calistir(
+            &mut conn,
+            "INSERT INTO toplantilar (id, tenant_id, baslik, tarih) VALUES ('yk7', 't1', 'Yönetim Kurulu 7. toplantı', '2026-03-10')",
+        );
+        connection::run_migrations(&mut conn).unwrap();
+        conn
+    }
+
+    fn girdi(tc_no: &str, basvuru_tarihi: &str) -> BasvuruGirdi {
+        BasvuruGirdi {
+            tc_no: tc_no.to_string(),
+            ad: "Zeynep".to_string(),
+            soyad: "Kaya".to_string(),
+            telefon: Some("0532 111 22 33".to_string()),
+            email: None,
+            dogum_tarihi: Some("1990-04-05".to_string()),
+            cinsiyet: None,
+            meslek: None,
+            adres: None,
+            il: None,
+            ilce: None,
+            uyelik_tipi: Some("Asil".to_string()),
+            referans_uye_id: Some("u1".to_string()),
+            basvuru_tarihi: basvuru_tarihi.to_string(),
+            notlar: None,
+        }
+    }
+
+    fn karar(onay: bool, karar_no: &str) -> BasvuruKarari {
+        BasvuruKarari {
+            onay,
+            toplanti_id: Some("yk7".to_string()),
+            karar_no: karar_no.to_string(),
+            karar_tarihi: None,
+            red_nedeni: None,
+            ilk_aidat: Some(false),
+        }
+    }
+
+    fn d(y: i32, a: u32, g: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, a, g).unwrap()
+    }
+
+    #[test]
+    fn basvuru_onaylaninca_uye_acilir() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut denetim = Denetim::yeni(None, None, "test");
+
+        // Geçersiz TC, kayıtlı üye, yanlış referans
+        assert!(basvuru_kaydet(c, &mut denetim, "t1", None, &girdi("12345678901", "2026-02-20")).is_err());
+        assert!(basvuru_kaydet(c, &mut denetim, "t1", None, &girdi("10000000146", "2026-02-20")).is_err());
+        let mut yanlis = girdi("10000000078", "2026-02-20");
+        yanlis.referans_uye_id = Some("u2".to_string());
+        assert!(basvuru_kaydet(c, &mut denetim, "t1", None, &yanlis).is_err());
+
+        let id = basvuru_kaydet(c, &mut denetim, "t1", None, &girdi("10000000078", "2026-02-20")).unwrap();
+        assert!(basvuru_kaydet(c, &mut denetim, "t1", None, &girdi("10000000078", "2026-02-21")).is_err());
+        let b = basvuru_getir(c, "t1", &id, d(2026, 3, 15)).unwrap();
+        assert_eq!((b.karar_son_tarihi.as_str(), b.kalan_gun), ("2026-03-22", Some(7)));
+        assert_eq!(b.referans_ad_soyad.as_deref(), Some("Ali Veli"));
+        assert!(basvurulari_getir(c, "t1", Some("suresi_gecen"), d(2026, 3, 15)).unwrap().is_empty());
+        assert_eq!(basvurulari_getir(c, "t1", Some("suresi_gecen"), d(2026, 3, 23)).unwrap().len(), 1);
+
+        // Karar tarihi toplantıdan alınır; üye başvuru tarihinden aday, kararla asil
+        assert!(karar_ver(c, &mut denetim, "t1", &id, &karar(true, " "), d(2026, 3, 15)).is_err());
+        karar_ver(c, &mut denetim, "t1", &id, &karar(true, "2026/21"), d(2026, 3, 15)).unwrap();
+        let b = basvuru_getir(c, "t1", &id, d(2026, 3, 15)).unwrap();
+        assert_eq!((b.durum.as_str(), b.karar_tarihi.as_deref(), b.suresinde), ("onaylandi", Some("2026-03-10"), Some(true)));
+        assert_eq!(b.toplanti_basligi.as_deref(), Some("Yönetim Kurulu 7. toplantı"));
+        let uye_id = b.uye_id.unwrap();
+        let gecmis: Vec<(String, String)> = uyelik_durumu::gecmis(c, "t1", &uye_id)
+            .unwrap()
+            .into_iter()
+            .map(|k| (k.durum, k.baslangic))
+            .collect();
+        assert_eq!(gecmis, [("aday".into(), "2026-02-20".into()), ("asil".into(), "2026-03-10".into())]);
+        assert!(karar_ver(c, &mut denetim, "t1", &id, &karar(false, "2026/22"), d(2026, 3, 15)).is_err());
+    }
+
+    #[test]
+    fn eski_uye_yeniden_kabul_ve_ret() {
+        let mut conn = test_db();
+        let c = &mut conn;
+        let mut denetim = Denetim::yeni(None, None, "test");
+
+        let id = basvuru_kaydet(c, &mut denetim, "t1", None, &girdi("12345678950", "2026-02-01")).unwrap();
+        assert_eq!(basvuru_getir(c, "t1", &id, d(2026, 2, 1)).unwrap().uye_id.as_deref(), Some("u2"));
+        karar_ver(c, &mut denetim, "t1", &id, &karar(true, "2026/5"), d(2026, 3, 15)).unwrap();
+        let durumlar: Vec<String> = uyelik_durumu::gecmis(c, "t1", "u2").unwrap().into_iter().map(|k| k.durum).collect();
+        assert_eq!(durumlar, ["asil", "ayrildi", "aday", "asil"]);
+
+        // Ret gerekçe ister, üye açılmaz
+        let id = basvuru_kaydet(c, &mut denetim, "t1", None, &girdi("10000000078", "2026-03-01")).unwrap();
+        assert!(karar_ver(c, &mut denetim, "t1", &id, &karar(false, "2026/6"), d(2026, 3, 15)).is_err());
+        let mut ret = karar(false, "2026/6");
+        ret.red_nedeni = Some("Tüzük şartları sağlanmıyor".to_string());
+        ret.toplanti_id = None;
+        assert!(karar_ver(c, &mut denetim, "t1", &id, &ret, d(2026, 3, 15)).is_err());
+        ret.karar_tarihi = Some("2026-04-15".to_string());
+        karar_ver(c, &mut denetim, "t1", &id, &ret, d(2026, 4, 15)).unwrap();
+        let b = basvuru_getir(c, "t1", &id, d(2026, 4, 15)).unwrap();
+        assert_eq!((b.durum.as_str(), b.uye_id, b.suresinde), ("reddedildi", None, Some(false)));
+    }
+}
@@ -0,0 +1,1370 @@
+// Çift taraflı kayıt (yevmiye) motoru
+//
+// Her mali kaynak kaydı (gelir, gider, virman, aidat tahakkuku, gecikme faizi,
+// cari hareket, kasa açılışı) hesap planındaki hesaplara borç/alacak satırları olarak işlenir.
+// Fişler kaynak kaydın GÜNCEL halinden türetilir ve yazımla aynı transaction
+// içinde `kaydi_isle` ile eşitlenir:
+//   - kaynak yoksa / silinmişse → aktif fiş 'iptal' edilir,
+//   - fiş içeriği değişmişse → eski fiş 'iptal', yenisi 'aktif' yazılır,
+//   - değişiklik yoksa → hiçbir şey yazılmaz (idempotent).
+// Böylece iptal edilen fişler denetim izi olarak kalır; mizan yalnızca aktif
+// fişlerden hesaplanır. Dengesiz (borç ≠ alacak) fiş yazılamaz.
+//
+// Yevmiye sync yüzeyinde değildir: sunucudan gelen baz kayıtlar uygulandıktan
+// sonra sync aynı fonksiyonla yerel fişleri üretir.
+
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::db::para::Para;
+
+/// Yevmiyeye kaynak olan tablolar (`kaydi_isle` bunları kabul eder).
+pub const KAYNAK_TABLOLAR: [&str; 7] = [
+    "kasalar",
+    "gelirler",
+    "giderler",
+    "virmanlar",
+    "aidat_takip",
+    "gecikme_faizleri",
+    "cari_hareketler",
+];
+
+pub const HESAP_KASA: &str = "100";
+pub const HESAP_BANKA: &str = "102";
+pub const HESAP_CARI: &str = "120";
+pub const HESAP_AIDAT_ALACAKLARI: &str = "127";
+pub const HESAP_OZ_KAYNAK: &str = "500";
+pub const HESAP_GELIRLER: &str = "600";
+pub const HESAP_KAMBIYO_KARI: &str = "646";
+pub const HESAP_CARI_GELIRLERI: &str = "649";
+pub const HESAP_KAMBIYO_ZARARI: &str = "656";
+pub const HESAP_GIDERLER: &str = "770";
+
+/// Ana hesaplar (Tekdüzen Hesap Planı'nın dernek muhasebesinde kullanılan kısmı).
+/// Kasa, banka, cari, gelir ve gider hesaplarının alt hesapları ilk kullanımda açılır.
+const ANA_HESAPLAR: [(&str, &str, &str); 10] = [
+    (HESAP_KASA, "Kasa", "aktif"),
+    (HESAP_BANKA, "Bankalar", "aktif"),
+    (HESAP_CARI, "Cari Hesaplar", "aktif"),
+    (HESAP_AIDAT_ALACAKLARI, "Üye Aidat Alacakları", "aktif"),
+    (HESAP_OZ_KAYNAK, "Dernek Öz Kaynakları", "ozkaynak"),
+    (HESAP_GELIRLER, "Gelirler", "gelir"),
+    (HESAP_KAMBIYO_KARI, "Kambiyo Kârları", "gelir"),
+    (HESAP_CARI_GELIRLERI, "Cari Hesap Gelirleri", "gelir"),
+    (HESAP_KAMBIYO_ZARARI, "Kambiyo Zararları", "gider"),
+    (HESAP_GIDERLER, "Giderler", "gider"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HesapRef {
+    pub id: String,
+    pub kod: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FisSatiri {
+    pub hesap: HesapRef,
+    pub borc: Para,
+    pub alacak: Para,
+}
+
+/// Fiş karşılaştırma anahtarı: (tarih, sıralı (hesap_id, borç kuruş, alacak kuruş) satırları)
+type FisImzasi = (String, Vec<(String, i64, i64)>);
+
+#[derive(Debug, Clone)]
+pub struct FisTaslagi {
+    pub tarih: String,
+    pub aciklama: String,
+    pub satirlar: Vec<FisSatiri>,
+}
+
+impl FisTaslagi {
+    /// İki satırlı fiş: `borclu` hesaba borç, `alacakli` hesaba alacak.
+    /// Negatif tutarda taraflar yer değiştirir (ters kayıt).
+    fn iki_tarafli(tarih: String, aciklama: String, borclu: HesapRef, alacakli: HesapRef, tutar: Para) -> Self {
+        let (borclu, alacakli, tutar) = if tutar.is_negative() {
+            (alacakli, borclu, tutar.abs())
+        } else {
+            (borclu, alacakli, tutar)
+        };
+        FisTaslagi {
+            tarih,
+            aciklama,
+            satirlar: vec![
+                FisSatiri { hesap: borclu, borc: tutar, alacak: Para::SIFIR },
+                FisSatiri { hesap: alacakli, borc: Para::SIFIR, alacak: tutar },
+            ],
+        }
+    }
+
+    pub fn toplam_borc(&self) -> Para {
+        self.satirlar.iter().map(|s| s.borc).sum()
+    }
+
+    pub fn toplam_alacak(&self) -> Para {
+        self.satirlar.iter().map(|s| s.alacak).sum()
+    }
+
+    pub fn dengeli_mi(&self) -> bool {
+        self.toplam_borc() == self.toplam_alacak()
+    }
+
+    /// Karşılaştırma anahtarı: tarih + (hesap, borç, alacak) satırları sıralı.
+    fn imza(&self) -> FisImzasi {
+        let mut satirlar: Vec<(String, i64, i64)> = self
+            .satirlar
+            .iter()
+            .map(|s| (s.hesap.id.clone(), s.borc.kurus(), s.alacak.kurus()))
+            .collect();
+        satirlar.sort();
+        (self.tarih.clone(), satirlar)
+    }
+}
+
+/// `kaydi_isle` sonucunda yevmiyede ne değiştiği.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FisDegisikligi {
+    Yok,
+    Olusturuldu,
+    Yenilendi,
+    IptalEdildi,
+}
+
+#[derive(QueryableByName)]
+struct IdRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+}
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    count: i64,
+}
+
+fn simdi() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Gelir/gider türü adlarını hesap anahtarına çevirir ("Aidat" ve "AİDAT" aynı hesap).
+pub(crate) fn hesap_anahtari(ad: &str) -> String {
+    ad.trim()
+        .chars()
+        .map(|c| match c {
+            'i' => 'İ',
+            'ı' => 'I',
+            _ => c,
+        })
+        .collect::<String>()
+        .to_uppercase()
+}
+
+// ============================================================================
+// HESAP PLANI
+// ============================================================================
+
+/// Tenant'ın ana hesaplarını açar (eksik olanları ekler).
+pub fn hesap_plani_hazirla(conn: &mut SqliteConnection, tenant_id: &str) -> Result<(), String> {
+    let mevcut: CountRow = diesel::sql_query(
+        "SELECT COUNT(*) AS count FROM hesap_plani WHERE tenant_id = ?1 AND ust_kod IS NULL",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+    if mevcut.count >= ANA_HESAPLAR.len() as i64 {
+        return Ok(());
+    }
+
+    let now = simdi();
+    for (kod, ad, tur) in ANA_HESAPLAR {
+        diesel::sql_query(
+            "INSERT OR IGNORE INTO hesap_plani (id, tenant_id, kod, ad, hesap_turu, ust_kod, is_active, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, NULL, 1, ?6, ?6)",
+        )
+        .bind::<diesel::sql_types::Text, _>(uuid::Uuid::new_v4().to_string())
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(kod)
+        .bind::<diesel::sql_types::Text, _>(ad)
+        .bind::<diesel::sql_types::Text, _>(tur)
+        .bind::<diesel::sql_types::Text, _>(&now)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn ana_hesap(conn: &mut SqliteConnection, tenant_id: &str, kod: &str) -> Result<HesapRef, String> {
+    hesap_plani_hazirla(conn, tenant_id)?;
+    let row: IdRow = diesel::sql_query("SELECT id FROM hesap_plani WHERE tenant_id = ?1 AND kod = ?2")
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(kod)
+        .get_result(conn)
+        .map_err(|e| format!("Hesap planında {} bulunamadı: {}", kod, e))?;
+    Ok(HesapRef { id: row.id, kod: kod.to_string() })
+}
+
+/// Ana hesabın altında, bağlı kayda ait alt hesabı bulur; yoksa sıradaki kodla açar
+/// (ör. 100.001). Bağlı kaydın adı değiştiyse hesap adı da güncellenir.
+fn alt_hesap(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    ust_kod: &str,
+    ad: &str,
+    bagli_tablo: &str,
+    bagli_id: &str,
+) -> Result<HesapRef, String> {
+    #[derive(QueryableByName)]
+    struct AltHesapRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kod: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad: String,
+    }
+
+    let ust = ana_hesap(conn, tenant_id, ust_kod)?;
+
+    let mevcut: Option<AltHesapRow> = diesel::sql_query(
+        "SELECT id, kod, ad FROM hesap_plani \
+         WHERE tenant_id = ?1 AND ust_kod = ?2 AND bagli_tablo = ?3 AND bagli_id = ?4",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&ust.kod)
+    .bind::<diesel::sql_types::Text, _>(bagli_tablo)
+    .bind::<diesel::sql_types::Text, _>(bagli_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let now = simdi();
+    if let Some(h) = mevcut {
+        if h.ad != ad {
+            diesel::sql_query("UPDATE hesap_plani SET ad = ?1, updated_at = ?2 WHERE id = ?3")
+                .bind::<diesel::sql_types::Text, _>(ad)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&h.id)
+                .execute(conn)
+                .map_err(|e| e.to_string())?;
+        }
+        return Ok(HesapRef { id: h.id, kod: h.kod });
+    }
+
+    let son: CountRow = diesel::sql_query(
+        "SELECT COALESCE(MAX(CAST(substr(kod, length(?2) + 2) AS INTEGER)), 0) AS count \
+         FROM hesap_plani WHERE tenant_id = ?1 AND ust_kod = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&ust.kod)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+
+    let hesap_id = uuid::Uuid::new_v4().to_string();
+    let kod = format!("{}.{:03}", ust.kod, son.count + 1);
+    let hesap_turu = ANA_HESAPLAR
+        .iter()
+        .find(|(k, _, _)| *k == ust.kod)
+        .map(|(_, _, t)| *t)
+        .unwrap_or("aktif");
+
+    diesel::sql_query(
+        "INSERT INTO hesap_plani (id, tenant_id, kod, ad, hesap_turu, ust_kod, bagli_tablo, bagli_id, is_active, created_at, updated_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1, ?9, ?9)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&hesap_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(&kod)
+    .bind::<diesel::sql_types::Text, _>(ad)
+    .bind::<diesel::sql_types::Text, _>(hesap_turu)
+    .bind::<diesel::sql_types::Text, _>(&ust.kod)
+    .bind::<diesel::sql_types::Text, _>(bagli_tablo)
+    .bind::<diesel::sql_types::Text, _>(bagli_id)
+    .bind::<diesel::sql_types::Text, _>(&now)
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+
+    Ok(HesapRef { id: hesap_id, kod })
+}
+
+/// Kasanın hesabı: kasa_tipi 'Banka' ise 102 altında, değilse 100 altında.
+pub fn kasa_hesabi(conn: &mut SqliteConnection, tenant_id: &str, kasa_id: &str) -> Result<HesapRef, String> {
+    #[derive(QueryableByName)]
+    struct KasaRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_adi: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        kasa_tipi: Option<String>,
+    }
+
+    let kasa: Option<KasaRow> = diesel::sql_query(
+        "SELECT kasa_adi, kasa_tipi FROM kasalar WHERE id = ?1 AND tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    // Sync sırasında kasa henüz gelmemiş olabilir; hesap yine açılır, ad sonradan düzelir.
+    let (ad, ust) = match kasa {
+        Some(k) if k.kasa_tipi.as_deref() == Some("Banka") => (k.kasa_adi, HESAP_BANKA),
+        Some(k) => (k.kasa_adi, HESAP_KASA),
+        None => (format!("Kasa {}", kasa_id.chars().take(8).collect::<String>()), HESAP_KASA),
+    };
+    alt_hesap(conn, tenant_id, ust, &ad, "kasalar", kasa_id)
+}
+
+fn cari_hesabi(conn: &mut SqliteConnection, tenant_id: &str, cari_id: &str) -> Result<HesapRef, String> {
+    #[derive(QueryableByName)]
+    struct CariRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        unvan: String,
+    }
+
+    let ad = diesel::sql_query("SELECT unvan FROM cariler WHERE id = ?1 AND tenant_id = ?2")
+        .bind::<diesel::sql_types::Text, _>(cari_id)
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .get_result::<CariRow>(conn)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .map(|r| r.unvan)
+        .unwrap_or_else(|| "Cari".to_string());
+    alt_hesap(conn, tenant_id, HESAP_CARI, &ad, "cariler", cari_id)
+}
+
+fn gelir_hesabi(conn: &mut SqliteConnection, tenant_id: &str, gelir_turu: Option<&str>) -> Result<HesapRef, String> {
+    let ad = gelir_turu.filter(|t| !t.trim().is_empty()).unwrap_or("Diğer Gelirler");
+    alt_hesap(conn, tenant_id, HESAP_GELIRLER, ad.trim(), "gelir_turu", &hesap_anahtari(ad))
+}
+
+fn gider_hesabi(conn: &mut SqliteConnection, tenant_id: &str, gider_turu: Option<&str>) -> Result<HesapRef, String> {
+    let ad = gider_turu.filter(|t| !t.trim().is_empty()).unwrap_or("Diğer Giderler");
+    alt_hesap(conn, tenant_id, HESAP_GIDERLER, ad.trim(), "gider_turu", &hesap_anahtari(ad))
+}
+
+fn aidat_geliri_mi(gelir_turu: Option<&str>) -> bool {
+    gelir_turu.map(|t| hesap_anahtari(t) == "AİDAT").unwrap_or(false)
+}
+
+fn gun(tarih: &str) -> String {
+    tarih.chars().take(10).collect()
+}
+
+// ============================================================================
+// KAYNAK KAYITLARDAN FİŞ TASLAKLARI
+// ============================================================================
+
+/// Gelir: Kasa (B) / karşı hesap (A).
+/// Karşı hesap: cari tahsilatıysa cari, aidat, gecikme faizi veya üye avansı
+/// tahsilatıysa 127, değilse gelir türü hesabı.
+fn gelir_fisi(conn: &mut SqliteConnection, tenant_id: &str, gelir_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct GelirRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        gelir_turu: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        aciklama: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        aidat_id: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        uye_id: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        cari_id: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        gecikme_tahsilati: bool,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        avans_tahsilati: bool,
+    }
+
+    let row: Option<GelirRow> = diesel::sql_query(
+        "SELECT g.kasa_id, g.gelir_turu, g.tarih, g.tutar, g.aciklama, g.aidat_id, g.uye_id, \
+                (SELECT ch.cari_id FROM cari_hareketler ch \
+                  WHERE ch.gelir_id = g.id AND ch.tenant_id = g.tenant_id AND COALESCE(ch.is_active, 1) = 1 \
+                  LIMIT 1) AS cari_id, \
+                EXISTS (SELECT 1 FROM gecikme_faizleri gf \
+                  WHERE gf.gelir_id = g.id AND gf.tenant_id = g.tenant_id) AS gecikme_tahsilati, \
+                EXISTS (SELECT 1 FROM uye_avans_hareketleri ua \
+                  WHERE ua.gelir_id = g.id AND ua.tenant_id = g.tenant_id) AS avans_tahsilati \
+         FROM gelirler g \
+         WHERE g.id = ?1 AND g.tenant_id = ?2 AND (g.is_deleted IS NULL OR g.is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(gelir_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(g) = row else { return Ok(None) };
+    if g.tutar.is_zero() {
+        return Ok(None);
+    }
+
+    let kasa = kasa_hesabi(conn, tenant_id, &g.kasa_id)?;
+    let karsi = if let Some(cari_id) = &g.cari_id {
+        cari_hesabi(conn, tenant_id, cari_id)?
+    } else if g.aidat_id.is_some()
+        || g.gecikme_tahsilati
+        || g.avans_tahsilati
+        || (g.uye_id.is_some() && aidat_geliri_mi(g.gelir_turu.as_deref()))
+    {
+        ana_hesap(conn, tenant_id, HESAP_AIDAT_ALACAKLARI)?
+    } else {
+        gelir_hesabi(conn, tenant_id, g.gelir_turu.as_deref())?
+    };
+
+    let aciklama = g.aciklama.unwrap_or_else(|| format!("Gelir: {}", g.gelir_turu.as_deref().unwrap_or("-")));
+    Ok(Some(FisTaslagi::iki_tarafli(gun(&g.tarih), aciklama, kasa, karsi, g.tutar)))
+}
+
+/// Gider: karşı hesap (B) / Kasa (A). Cariye yapılan ödemede karşı hesap caridir;
+/// üye avansı iadesinde 127 (fazla ödeme gelirle bu hesaba alacak yazılmıştı).
+fn gider_fisi(conn: &mut SqliteConnection, tenant_id: &str, gider_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct GiderRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_id: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        gider_turu: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        aciklama: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        cari_id: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        avans_iadesi: bool,
+    }
+
+    let row: Option<GiderRow> = diesel::sql_query(
+        "SELECT g.kasa_id, g.gider_turu, g.tarih, g.tutar, g.aciklama, \
+                (SELECT ch.cari_id FROM cari_hareketler ch \
+                  WHERE ch.gider_id = g.id AND ch.tenant_id = g.tenant_id AND COALESCE(ch.is_active, 1) = 1 \
+                  LIMIT 1) AS cari_id, \
+                EXISTS (SELECT 1 FROM uye_avans_hareketleri ua \
+                  WHERE ua.gider_id = g.id AND ua.tenant_id = g.tenant_id) AS avans_iadesi \
+         FROM giderler g \
+         WHERE g.id = ?1 AND g.tenant_id = ?2 AND (g.is_deleted IS NULL OR g.is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(gider_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(g) = row else { return Ok(None) };
+    if g.tutar.is_zero() {
+        return Ok(None);
+    }
+
+    let kasa = kasa_hesabi(conn, tenant_id, &g.kasa_id)?;
+    let karsi = match &g.cari_id {
+        Some(cari_id) => cari_hesabi(conn, tenant_id, cari_id)?,
+        None if g.avans_iadesi => ana_hesap(conn, tenant_id, HESAP_AIDAT_ALACAKLARI)?,
+        None => gider_hesabi(conn, tenant_id, g.gider_turu.as_deref())?,
+    };
+
+    let aciklama = g.aciklama.unwrap_or_else(|| format!("Gider: {}", g.gider_turu.as_deref().unwrap_or("-")));
+    Ok(Some(FisTaslagi::iki_tarafli(gun(&g.tarih), aciklama, karsi, kasa, g.tutar)))
+}
+
+/// Virman: Hedef kasa (B, hedef_tutar) / Kaynak kasa (A, tutar).
+/// Dövizli virmanda iki taraf farklıysa fark kambiyo kâr/zarar hesabına gider
+/// (tutarlar kasaların kendi para biriminde izlenir; fiş her zaman dengelidir).
+fn virman_fisi(conn: &mut SqliteConnection, tenant_id: &str, virman_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct VirmanRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kaynak_kasa_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hedef_kasa_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        hedef_tutar: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        aciklama: Option<String>,
+    }
+
+    let row: Option<VirmanRow> = diesel::sql_query(
+        "SELECT kaynak_kasa_id, hedef_kasa_id, tarih, tutar, COALESCE(hedef_tutar, tutar) AS hedef_tutar, aciklama \
+         FROM virmanlar \
+         WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0)",
+    )
+    .bind::<diesel::sql_types::Text, _>(virman_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(v) = row else { return Ok(None) };
+    if v.tutar.is_zero() && v.hedef_tutar.is_zero() {
+        return Ok(None);
+    }
+
+    let kaynak = kasa_hesabi(conn, tenant_id, &v.kaynak_kasa_id)?;
+    let hedef = kasa_hesabi(conn, tenant_id, &v.hedef_kasa_id)?;
+    let aciklama = v.aciklama.unwrap_or_else(|| "Virman".to_string());
+
+    let fark = v.hedef_tutar - v.tutar;
+    if fark.is_zero() {
+        return Ok(Some(FisTaslagi::iki_tarafli(gun(&v.tarih), aciklama, hedef, kaynak, v.tutar)));
+    }
+
+    let mut satirlar = vec![
+        FisSatiri { hesap: hedef, borc: v.hedef_tutar, alacak: Para::SIFIR },
+        FisSatiri { hesap: kaynak, borc: Para::SIFIR, alacak: v.tutar },
+    ];
+    if fark.is_positive() {
+        let kar = ana_hesap(conn, tenant_id, HESAP_KAMBIYO_KARI)?;
+        satirlar.push(FisSatiri { hesap: kar, borc: Para::SIFIR, alacak: fark });
+    } else {
+        let zarar = ana_hesap(conn, tenant_id, HESAP_KAMBIYO_ZARARI)?;
+        satirlar.push(FisSatiri { hesap: zarar, borc: fark.abs(), alacak: Para::SIFIR });
+    }
+    Ok(Some(FisTaslagi { tarih: gun(&v.tarih), aciklama, satirlar }))
+}
+
+/// Aidat tahakkuku: 127 Üye Aidat Alacakları (B) / Aidat gelirleri (A).
+/// İptal edilmiş veya silinmiş tahakkuk fiş üretmez.
+fn aidat_fisi(conn: &mut SqliteConnection, tenant_id: &str, aidat_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct AidatRow {
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        yil: i32,
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        ay: i32,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+    }
+
+    let row: Option<AidatRow> = diesel::sql_query(
+        "SELECT yil, ay, tutar FROM aidat_takip \
+         WHERE id = ?1 AND tenant_id = ?2 AND (is_deleted IS NULL OR is_deleted = 0) AND durum != 'iptal'",
+    )
+    .bind::<diesel::sql_types::Text, _>(aidat_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(a) = row else { return Ok(None) };
+    if a.tutar.is_zero() {
+        return Ok(None);
+    }
+
+    let alacak_hesabi = ana_hesap(conn, tenant_id, HESAP_AIDAT_ALACAKLARI)?;
+    let gelir = gelir_hesabi(conn, tenant_id, Some("AİDAT"))?;
+    let tarih = format!("{:04}-{:02}-01", a.yil, a.ay.clamp(1, 12));
+    let aciklama = format!("{} yılı aidat tahakkuku", a.yil);
+    Ok(Some(FisTaslagi::iki_tarafli(tarih, aciklama, alacak_hesabi, gelir, a.tutar)))
+}
+
+/// Gecikme faizi alacağı: 127 Üye Aidat Alacakları (B) / Gecikme Faizi geliri (A).
+/// Tutar feragat sonrası nettir; tamamından feragat edilmişse fiş üretmez.
+fn gecikme_faizi_fisi(conn: &mut SqliteConnection, tenant_id: &str, faiz_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct FaizRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hesap_tarihi: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        net: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        aciklama: Option<String>,
+    }
+
+    let row: Option<FaizRow> = diesel::sql_query(
+        "SELECT hesap_tarihi, tutar - feragat_tutari AS net, aciklama FROM gecikme_faizleri \
+         WHERE id = ?1 AND tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(faiz_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(f) = row else { return Ok(None) };
+    if !f.net.is_positive() {
+        return Ok(None);
+    }
+
+    let alacak_hesabi = ana_hesap(conn, tenant_id, HESAP_AIDAT_ALACAKLARI)?;
+    let gelir = gelir_hesabi(conn, tenant_id, Some("Gecikme Faizi"))?;
+    let aciklama = match f.aciklama {
+        Some(a) => format!("Gecikme faizi: {}", a),
+        None => "Gecikme faizi tahakkuku".to_string(),
+    };
+    Ok(Some(FisTaslagi::iki_tarafli(gun(&f.hesap_tarihi), aciklama, alacak_hesabi, gelir, f.net)))
+}
+
+/// Cari hareket: kasa bacağı bir gelir/gider kaydıysa fiş o kayıttan üretilir.
+/// Kasasız "Borç": Cari (B) / 649 Cari Hesap Gelirleri (A).
+/// Kasasız "Alacak": Cari hesap giderleri (B) / Cari (A).
+fn cari_hareket_fisi(conn: &mut SqliteConnection, tenant_id: &str, hareket_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct HareketRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        cari_id: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hareket_tipi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        tarih: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        tutar: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        aciklama: Option<String>,
+    }
+
+    let row: Option<HareketRow> = diesel::sql_query(
+        "SELECT cari_id, hareket_tipi, tarih, tutar, aciklama FROM cari_hareketler \
+         WHERE id = ?1 AND tenant_id = ?2 AND COALESCE(is_active, 1) = 1 \
+           AND gelir_id IS NULL AND gider_id IS NULL",
+    )
+    .bind::<diesel::sql_types::Text, _>(hareket_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(h) = row else { return Ok(None) };
+    if h.tutar.is_zero() {
+        return Ok(None);
+    }
+
+    let cari = cari_hesabi(conn, tenant_id, &h.cari_id)?;
+    let aciklama = h.aciklama.unwrap_or_else(|| format!("Cari hareket: {}", h.hareket_tipi));
+    let fis = if h.hareket_tipi == "Borç" {
+        let gelir = ana_hesap(conn, tenant_id, HESAP_CARI_GELIRLERI)?;
+        FisTaslagi::iki_tarafli(gun(&h.tarih), aciklama, cari, gelir, h.tutar)
+    } else {
+        let gider = gider_hesabi(conn, tenant_id, Some("Cari Hesap Giderleri"))?;
+        FisTaslagi::iki_tarafli(gun(&h.tarih), aciklama, gider, cari, h.tutar)
+    };
+    Ok(Some(fis))
+}
+
+/// Kasa açılışı: ilk devir bakiyesi Kasa (B) / 500 Öz Kaynaklar (A).
+/// Yıl sonu devri yeni bir hareket değildir; devir_bakiye önceki yılların net
+/// hareketini de içerdiğinden açılış = devir_bakiye − devir_tarihi öncesi net hareket.
+fn kasa_acilis_fisi(conn: &mut SqliteConnection, tenant_id: &str, kasa_id: &str) -> Result<Option<FisTaslagi>, String> {
+    #[derive(QueryableByName)]
+    struct AcilisRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kasa_adi: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        created_at: String,
+        #[diesel(sql_type = diesel::sql_types::Double)]
+        devir: Para,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        devir_tarihi: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        devir_oncesi_net: i64,
+    }
+
+    let row: Option<AcilisRow> = diesel::sql_query(
+        "SELECT k.kasa_adi, k.created_at, COALESCE(k.devir_bakiye, 0.0) AS devir, k.devir_tarihi,
+            CASE WHEN k.devir_tarihi IS NULL THEN 0 ELSE
+              (SELECT COALESCE(SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)), 0) FROM gelirler g
+                WHERE g.kasa_id = k.id AND (g.is_deleted IS NULL OR g.is_deleted = 0) AND g.tarih < k.devir_tarihi)
+            - (SELECT COALESCE(SUM(CAST(ROUND(g.tutar * 100) AS INTEGER)), 0) FROM giderler g
+                WHERE g.kasa_id = k.id AND (g.is_deleted IS NULL OR g.is_deleted = 0) AND g.tarih < k.devir_tarihi)
+            + (SELECT COALESCE(SUM(CAST(ROUND(COALESCE(v.hedef_tutar, v.tutar) * 100) AS INTEGER)), 0) FROM virmanlar v
+                WHERE v.hedef_kasa_id = k.id AND (v.is_deleted IS NULL OR v.is_deleted = 0) AND v.tarih < k.devir_tarihi)
+            - (SELECT COALESCE(SUM(CAST(ROUND(v.tutar * 100) AS INTEGER)), 0) FROM virmanlar v
+                WHERE v.kaynak_kasa_id = k.id AND (v.is_deleted IS NULL OR v.is_deleted = 0) AND v.tarih < k.devir_tarihi)
+            END AS devir_oncesi_net
+         FROM kasalar k
+         WHERE k.id = ?1 AND k.tenant_id = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(kasa_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .get_result(conn)
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let Some(k) = row else { return Ok(None) };
+    // Kesim tarihi olmayan devir hangi hareketleri kapsadığını söylemez; açılış
+    // devir_bakiye'den yazılırsa önceki hareketler yevmiyede ikinci kez sayılır.
+    if k.devir_tarihi.is_none() && !k.devir.is_zero() {
+        return Err(format!("{} kasasının devir tarihi eksik, açılış fişi hesaplanamaz", k.kasa_adi));
+    }
+    let acilis = k.devir - Para::from_kurus(k.devir_oncesi_net);
+    if acilis.is_zero() {
+        return Ok(None);
+    }
+
+    let kasa = kasa_hesabi(conn, tenant_id, kasa_id)?;
+    let oz_kaynak = ana_hesap(conn, tenant_id, HESAP_OZ_KAYNAK)?;
+    let aciklama = format!("{} açılış bakiyesi", k.kasa_adi);
+    Ok(Some(FisTaslagi::iki_tarafli(gun(&k.created_at), aciklama, kasa, oz_kaynak, acilis)))
+}
+
+// ============================================================================
+// FİŞ YAZIMI
+// ============================================================================
+
+#[derive(QueryableByName)]
+struct AktifFisRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    tarih: String,
+}
+
+#[derive(QueryableByName)]
+struct SatirImzaRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    hesap_id: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    borc: Para,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    alacak: Para,
+}
+
+fn aktif_fisler(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kaynak_tablo: &str,
+    kaynak_id: &str,
+) -> Result<Vec<AktifFisRow>, String> {
+    diesel::sql_query(
+        "SELECT id, tarih FROM yevmiye_fisleri \
+         WHERE tenant_id = ?1 AND kaynak_tablo = ?2 AND kaynak_id = ?3 AND durum = 'aktif'",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Text, _>(kaynak_tablo)
+    .bind::<diesel::sql_types::Text, _>(kaynak_id)
+    .load(conn)
+    .map_err(|e| e.to_string())
+}
+
+fn fis_imzasi(conn: &mut SqliteConnection, fis: &AktifFisRow) -> Result<FisImzasi, String> {
+    let satirlar: Vec<SatirImzaRow> = diesel::sql_query(
+        "SELECT hesap_id, borc, alacak FROM yevmiye_satirlari WHERE fis_id = ?1",
+    )
+    .bind::<diesel::sql_types::Text, _>(&fis.id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+    let mut imza: Vec<(String, i64, i64)> = satirlar
+        .into_iter()
+        .map(|s| (s.hesap_id, s.borc.kurus(), s.alacak.kurus()))
+        .collect();
+    imza.sort();
+    Ok((fis.tarih.clone(), imza))
+}
+
+fn fisleri_iptal_et(conn: &mut SqliteConnection, fisler: &[AktifFisRow]) -> Result<(), String> {
+    let now = simdi();
+    for f in fisler {
+        diesel::sql_query("UPDATE yevmiye_fisleri SET durum = 'iptal', iptal_tarihi = ?1 WHERE id = ?2")
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&f.id)
+            .execute(conn)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Fişi yazar. Dengesiz veya boş fiş reddedilir; fiş numarası tenant + yıl bazında sıralıdır.
+pub fn fis_yaz(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kaynak_tablo: &str,
+    kaynak_id: &str,
+    taslak: &FisTaslagi,
+) -> Result<String, String> {
+    let satirlar: Vec<&FisSatiri> = taslak
+        .satirlar
+        .iter()
+        .filter(|s| !(s.borc.is_zero() && s.alacak.is_zero()))
+        .collect();
+    if satirlar.len() < 2 {
+        return Err(format!("Yevmiye fişi en az iki satır içermeli ({} / {})", kaynak_tablo, kaynak_id));
+    }
+    if satirlar.iter().any(|s| s.borc.is_negative() || s.alacak.is_negative()) {
+        return Err(format!("Yevmiye fişinde negatif tutar olamaz ({} / {})", kaynak_tablo, kaynak_id));
+    }
+    if !taslak.dengeli_mi() {
+        return Err(format!(
+            "Dengesiz yevmiye fişi ({} / {}): borç {} ≠ alacak {}",
+            kaynak_tablo,
+            kaynak_id,
+            taslak.toplam_borc(),
+            taslak.toplam_alacak()
+        ));
+    }
+
+    let yil: i32 = taslak.tarih.get(0..4).and_then(|y| y.parse().ok()).unwrap_or(0);
+    let son: CountRow = diesel::sql_query(
+        "SELECT COALESCE(MAX(fis_no), 0) AS count FROM yevmiye_fisleri WHERE tenant_id = ?1 AND yil = ?2",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .get_result(conn)
+    .map_err(|e| e.to_string())?;
+
+    let fis_id = uuid::Uuid::new_v4().to_string();
+    diesel::sql_query(
+        "INSERT INTO yevmiye_fisleri (id, tenant_id, yil, fis_no, tarih, aciklama, kaynak_tablo, kaynak_id, durum, created_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'aktif', ?9)",
+    )
+    .bind::<diesel::sql_types::Text, _>(&fis_id)
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Integer, _>(yil)
+    .bind::<diesel::sql_types::BigInt, _>(son.count + 1)
+    .bind::<diesel::sql_types::Text, _>(&taslak.tarih)
+    .bind::<diesel::sql_types::Text, _>(&taslak.aciklama)
+    .bind::<diesel::sql_types::Text, _>(kaynak_tablo)
+    .bind::<diesel::sql_types::Text, _>(kaynak_id)
+    .bind::<diesel::sql_types::Text, _>(simdi())
+    .execute(conn)
+    .map_err(|e| e.to_string())?;
+
+    for (sira, s) in satirlar.iter().enumerate() {
+        diesel::sql_query(
+            "INSERT INTO yevmiye_satirlari (id, tenant_id, fis_id, sira, hesap_id, hesap_kodu, borc, alacak) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )
+        .bind::<diesel::sql_types::Text, _>(uuid::Uuid::new_v4().to_string())
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .bind::<diesel::sql_types::Text, _>(&fis_id)
+        .bind::<diesel::sql_types::Integer, _>(sira as i32 + 1)
+        .bind::<diesel::sql_types::Text, _>(&s.hesap.id)
+        .bind::<diesel::sql_types::Text, _>(&s.hesap.kod)
+        .bind::<diesel::sql_types::Double, _>(s.borc)
+        .bind::<diesel::sql_types::Double, _>(s.alacak)
+        .execute(conn)
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(fis_id)
+}
+
+/// Kaynak kaydın yevmiye fişini kaydın güncel haliyle eşitler.
+/// ÇAĞRI KURALI: kaynak yazımıyla aynı transaction içinde, yazımdan SONRA çağrılmalıdır.
+pub fn kaydi_isle(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    kaynak_tablo: &str,
+    kaynak_id: &str,
+) -> Result<FisDegisikligi, String> {
+    let taslak = match kaynak_tablo {
+        "gelirler" => gelir_fisi(conn, tenant_id, kaynak_id)?,
+        "giderler" => gider_fisi(conn, tenant_id, kaynak_id)?,
+        "virmanlar" => virman_fisi(conn, tenant_id, kaynak_id)?,
+        "aidat_takip" => aidat_fisi(conn, tenant_id, kaynak_id)?,
+        "gecikme_faizleri" => gecikme_faizi_fisi(conn, tenant_id, kaynak_id)?,
+        "cari_hareketler" => cari_hareket_fisi(conn, tenant_id, kaynak_id)?,
+        "kasalar" => kasa_acilis_fisi(conn, tenant_id, kaynak_id)?,
+        _ => return Err(format!("Yevmiye kaynağı değil: {}", kaynak_tablo)),
+    };
+
+    let mevcut = aktif_fisler(conn, tenant_id, kaynak_tablo, kaynak_id)?;
+
+    match taslak {
+        None => {
+            if mevcut.is_empty() {
+                return Ok(FisDegisikligi::Yok);
+            }
+            fisleri_iptal_et(conn, &mevcut)?;
+            Ok(FisDegisikligi::IptalEdildi)
+        }
+        Some(taslak) => {
+            if mevcut.len() == 1 && fis_imzasi(conn, &mevcut[0])? == taslak.imza() {
+                return Ok(FisDegisikligi::Yok);
+            }
+            let yenileme = !mevcut.is_empty();
+            fisleri_iptal_et(conn, &mevcut)?;
+            fis_yaz(conn, tenant_id, kaynak_tablo, kaynak_id, &taslak)?;
+            Ok(if yenileme { FisDegisikligi::Yenilendi } else { FisDegisikligi::Olusturuldu })
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct YenidenOlusturmaSonucu {
+    pub islenen: i64,
+    pub olusturulan: i64,
+    pub yenilenen: i64,
+    pub iptal_edilen: i64,
+}
+
+/// Tenant'ın tüm kaynak kayıtlarını yevmiyeye işler (ilk kurulum, sync sonrası
+/// veya denetim için). Kaynağı kaybolmuş aktif fişler de iptal edilir.
+pub fn tumunu_yeniden_olustur(conn: &mut SqliteConnection, tenant_id: &str) -> Result<YenidenOlusturmaSonucu, String> {
+    hesap_plani_hazirla(conn, tenant_id)?;
+    let mut sonuc = YenidenOlusturmaSonucu::default();
+
+    for tablo in KAYNAK_TABLOLAR {
+        // Tablo adı sabit listeden geldiği için interpolasyon güvenlidir.
+        let mut idler: Vec<String> = diesel::sql_query(format!(
+            "SELECT id FROM \"{}\" WHERE tenant_id = ?1 \
+             UNION SELECT kaynak_id AS id FROM yevmiye_fisleri \
+             WHERE tenant_id = ?1 AND kaynak_tablo = '{}' AND durum = 'aktif'",
+            tablo, tablo
+        ))
+        .bind::<diesel::sql_types::Text, _>(tenant_id)
+        .load::<IdRow>(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+        idler.sort();
+
+        for id in idler {
+            sonuc.islenen += 1;
+            match kaydi_isle(conn, tenant_id, tablo, &id)? {
+                FisDegisikligi::Olusturuldu => sonuc.olusturulan += 1,
+                FisDegisikligi::Yenilendi => sonuc.yenilenen += 1,
+                FisDegisikligi::IptalEdildi => sonuc.iptal_edilen += 1,
+                FisDegisikligi::Yok => {}
+            }
+        }
+    }
+
+    Ok(sonuc)
+}
+
+// ============================================================================
+// MİZAN
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MizanSatiri {
+    pub hesap_kodu: String,
+    pub hesap_adi: String,
+    pub hesap_turu: String,
+    pub ust_kod: Option<String>,
+    pub borc_toplam: Para,
+    pub alacak_toplam: Para,
+    pub borc_bakiye: Para,
+    pub alacak_bakiye: Para,
+}
+
+impl MizanSatiri {
+    fn bakiyeleri_hesapla(&mut self) {
+        let net = self.borc_toplam - self.alacak_toplam;
+        if net.is_negative() {
+            self.borc_bakiye = Para::SIFIR;
+            self.alacak_bakiye = net.abs();
+        } else {
+            self.borc_bakiye = net;
+            self.alacak_bakiye = Para::SIFIR;
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Mizan {
+    /// Ana hesaplar ve altlarında alt hesaplar, kod sırasıyla.
+    pub satirlar: Vec<MizanSatiri>,
+    pub toplam_borc: Para,
+    pub toplam_alacak: Para,
+    pub toplam_borc_bakiye: Para,
+    pub toplam_alacak_bakiye: Para,
+    pub dengeli: bool,
+}
+
+/// Aktif fişlerden mizan. Tarihler dahil (YYYY-MM-DD); None sınırsız.
+pub fn mizan_hesapla(
+    conn: &mut SqliteConnection,
+    tenant_id: &str,
+    baslangic: Option<&str>,
+    bitis: Option<&str>,
+) -> Result<Mizan, String> {
+    #[derive(QueryableByName)]
+    struct HesapToplamRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kod: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hesap_turu: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+        ust_kod: Option<String>,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        borc: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        alacak: i64,
+    }
+
+    hesap_plani_hazirla(conn, tenant_id)?;
+
+    let rows: Vec<HesapToplamRow> = diesel::sql_query(
+        "SELECT h.kod, h.ad, h.hesap_turu, h.ust_kod,
+                COALESCE(SUM(CAST(ROUND(s.borc * 100) AS INTEGER)), 0) AS borc,
+                COALESCE(SUM(CAST(ROUND(s.alacak * 100) AS INTEGER)), 0) AS alacak
+         FROM yevmiye_satirlari s
+         JOIN yevmiye_fisleri f ON f.id = s.fis_id
+         JOIN hesap_plani h ON h.id = s.hesap_id
+         WHERE s.tenant_id = ?1 AND f.durum = 'aktif'
+           AND (?2 IS NULL OR f.tarih >= ?2)
+           AND (?3 IS NULL OR f.tarih <= ?3)
+         GROUP BY h.id
+         ORDER BY h.kod",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(baslangic)
+    .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(bitis)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    #[derive(QueryableByName)]
+    struct AnaHesapRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        kod: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        ad: String,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        hesap_turu: String,
+    }
+
+    let ana_hesaplar: Vec<AnaHesapRow> = diesel::sql_query(
+        "SELECT kod, ad, hesap_turu FROM hesap_plani WHERE tenant_id = ?1 AND ust_kod IS NULL",
+    )
+    .bind::<diesel::sql_types::Text, _>(tenant_id)
+    .load(conn)
+    .map_err(|e| e.to_string())?;
+
+    // Ana hesap satırı = kendisine doğrudan işlenen + alt hesaplarının toplamı
+    let mut anlar: BTreeMap<String, MizanSatiri> = BTreeMap::new();
+    let mut altlar: Vec<MizanSatiri> = Vec::new();
+    for r in rows {
+        let ana_kod = r.ust_kod.clone().unwrap_or_else(|| r.kod.clone());
+        let ana = anlar.entry(ana_kod.clone()).or_insert_with(|| {
+            let bilgi = ana_hesaplar.iter().find(|h| h.kod == ana_kod);
+            MizanSatiri {
+                hesap_kodu: ana_kod.clone(),
+                hesap_adi: bilgi.map(|h| h.ad.clone()).unwrap_or_default(),
+                hesap_turu: bilgi.map(|h| h.hesap_turu.clone()).unwrap_or_default(),
+                ust_kod: None,
+                borc_toplam: Para::SIFIR,
+                alacak_toplam: Para::SIFIR,
+                borc_bakiye: Para::SIFIR,
+                alacak_bakiye: Para::SIFIR,
+            }
+        });
+        ana.borc_toplam += Para::from_kurus(r.borc);
+        ana.alacak_toplam += Para::from_kurus(r.alacak);
+
+        if r.ust_kod.is_some() {
+            let mut alt = MizanSatiri {
+                hesap_kodu: r.kod,
+                hesap_adi: r.ad,
+                hesap_turu: r.hesap_turu,
+                ust_kod: r.ust_kod,
+                borc_toplam: Para::from_kurus(r.borc),
+                alacak_toplam: Para::from_kurus(r.alacak),
+                borc_bakiye: Para::SIFIR,
+                alacak_bakiye: Para::SIFIR,
+            };
+            alt.bakiyeleri_hesapla();
+            altlar.push(alt);
+        }
+    }
+
+    let mut toplam_borc = Para::SIFIR;
+    let mut toplam_alacak = Para::SIFIR;
+    let mut toplam_borc_bakiye = Para::SIFIR;
+    let mut toplam_alacak_bakiye = Para::SIFIR;
+    let mut satirlar = Vec::new();
+    for (kod, mut ana) in anlar {
+        ana.bakiyeleri_hesapla();
+        toplam_borc += ana.borc_toplam;
+        toplam_alacak += ana.alacak_toplam;
+        toplam_borc_bakiye += ana.borc_bakiye;
+        toplam_alacak_bakiye += ana.alacak_bakiye;
+        satirlar.push(ana);
+        satirlar.extend(altlar.iter().filter(|a| a.ust_kod.as_deref() == Some(kod.as_str())).cloned());
+    }
+
+    Ok(Mizan {
+        satirlar,
+        toplam_borc,
+        toplam_alacak,
+        toplam_borc_bakiye,
+        toplam_alacak_bakiye,
+        dengeli: toplam_borc == toplam_alacak && toplam_borc_bakiye == toplam_alacak_bakiye,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db::{self, calistir as exec};
+
+    fn setup() -> SqliteConnection {
+        let mut conn = test_db::baglanti();
+        test_db::kiraci(&mut conn, "t1", "Örnek Derneği");
+        test_db::uye(&mut conn, "t1", "u1", "1", "Ali Veli");
+        exec(
+            &mut conn,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, kasa_tipi, para_birimi, devir_bakiye, devir_tarihi, created_at) VALUES
+                ('k1', 't1', 'Ana Kasa', 'Nakit', 'TRY', 100.0, '2026-01-01', '2026-01-01 09:00:00'),
+                ('k2', 't1', 'Euro Hesabı', 'Banka', 'EUR', 0.0, NULL, '2026-01-01 09:00:00')",
+        );
+        exec(&mut conn, "INSERT INTO cariler (id, tenant_id, unvan) VALUES ('c1', 't1', 'Tedarikçi A')");
+        conn
+    }
+
+    #[derive(QueryableByName)]
+    struct FisToplamRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        fis_id: String,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        borc: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        alacak: i64,
+    }
+
+    fn aktif_fis_toplamlari(conn: &mut SqliteConnection) -> Vec<FisToplamRow> {
+        diesel::sql_query(
+            "SELECT s.fis_id,
+                    SUM(CAST(ROUND(s.borc * 100) AS INTEGER)) AS borc,
+                    SUM(CAST(ROUND(s.alacak * 100) AS INTEGER)) AS alacak
+             FROM yevmiye_satirlari s JOIN yevmiye_fisleri f ON f.id = s.fis_id
+             WHERE f.durum = 'aktif' GROUP BY s.fis_id",
+        )
+        .load(conn)
+        .unwrap()
+    }
+
+    fn fis_sayisi(conn: &mut SqliteConnection, durum: &str) -> i64 {
+        diesel::sql_query("SELECT COUNT(*) AS count FROM yevmiye_fisleri WHERE durum = ?1")
+            .bind::<diesel::sql_types::Text, _>(durum)
+            .get_result::<CountRow>(conn)
+            .unwrap()
+            .count
+    }
+
+    fn hesap_neti(mizan: &Mizan, kod: &str) -> Para {
+        mizan
+            .satirlar
+            .iter()
+            .find(|s| s.hesap_kodu == kod)
+            .map(|s| s.borc_toplam - s.alacak_toplam)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn mevcut_akislar_dengeli_fis_uretir() {
+        use crate::commands::{aidat, cariler, mali};
+        use crate::db::denetim::Denetim;
+
+        let mut conn = setup();
+        let c = &mut conn;
+        let mut denetim = Denetim::yeni(None, None, "test");
+        test_db::kasa(c, "t1", "k3", "Banka TL");
+
+        // Kasa açılışları (devir bakiyesi öz kaynaktan)
+        kaydi_isle(c, "t1", "kasalar", "k1").unwrap();
+        kaydi_isle(c, "t1", "kasalar", "k2").unwrap();
+
+        // create_gelir / create_gider
+        mali::gelir_ekle(c, "t1", "g1", &mali::CreateGelirRequest {
+            kasa_id: "k1".into(),
+            tarih: "2026-02-01".into(),
+            tutar: Para::from_kurus(25_050),
+            ..Default::default()
+        }).unwrap();
+        mali::gider_ekle(c, "t1", "d1", &mali::CreateGiderRequest {
+            kasa_id: "k1".into(),
+            tarih: "2026-02-02".into(),
+            tutar: Para::from_kurus(8_025),
+            ..Default::default()
+        }).unwrap();
+        mali::update_kasa_bakiye(c, "k1").unwrap();
+
+        // virman_yap: aynı para birimi ve dövizli (kaynak 100 TRY → hedef 3,10 EUR)
+        mali::virman_ekle(c, &mut denetim, "t1", &mali::VirmanRequest {
+            kaynak_kasa_id: "k1".into(),
+            hedef_kasa_id: "k3".into(),
+            tutar: Para::from_kurus(5_000),
+            aciklama: None,
+            uygulanan_kur: None,
+        }).unwrap();
+        mali::virman_ekle(c, &mut denetim, "t1", &mali::VirmanRequest {
+            kaynak_kasa_id: "k1".into(),
+            hedef_kasa_id: "k2".into(),
+            tutar: Para::from_kurus(10_000),
+            aciklama: None,
+            uygulanan_kur: Some(0.031),
+        }).unwrap();
+
+        // Aidat tahakkuku + add_aidat_odeme_with_gelir
+        exec(c, "INSERT INTO aidat_takip (id, tenant_id, uye_id, yil, ay, tutar, kalan) VALUES ('a1', 't1', 'u1', 2026, 1, 120.0, 120.0)");
+        kaydi_isle(c, "t1", "aidat_takip", "a1").unwrap();
+        aidat::aidat_odemesi_gelirle(c, &mut denetim, "t1", "a1", Para::from_kurus(12_000), "k1", None).unwrap();
+
+        // Cari borçlandırma + odeme_kaydet_cari (kasalı tahsilat gelir kaydıyla bağlı)
+        cariler::cari_hareket_ekle(c, &mut denetim, "t1", "c1", "Borç", Para::from_kurus(30_000), None, None, Some("2026-02-06".into()), None).unwrap();
+        cariler::cari_hareket_ekle(c, &mut denetim, "t1", "c1", "Alacak", Para::from_kurus(20_000), None, None, Some("2026-02-07".into()), Some("k2".into())).unwrap();
+
+        // update_gelir → eski fiş iptal, yenisi aktif; delete_gider (soft) → fiş iptal
+        mali::gelir_guncelle(c, &mut denetim, "t1", "g1", mali::UpdateGelirRequest {
+            tutar: Some(Para::from_kurus(26_000)),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(fis_sayisi(c, "iptal"), 1);
+        mali::gider_sil(c, &mut denetim, "t1", "d1").unwrap();
+        assert_eq!(fis_sayisi(c, "iptal"), 2);
+
+        let toplamlar = aktif_fis_toplamlari(c);
+        assert_eq!(toplamlar.len(), 8); // 1 açılış (k2 sıfır) + g1 + 2 virman + a1 + aidat geliri + cari borç + cari tahsilat
+        for f in &toplamlar {
+            assert_eq!(f.borc, f.alacak, "fiş {} dengesiz", f.fis_id);
+        }
+
+        let mizan = mizan_hesapla(c, "t1", None, None).unwrap();
+        assert!(mizan.dengeli);
+        assert_eq!(mizan.toplam_borc, mizan.toplam_alacak);
+
+        // Kasa hesapları kasanın fiziksel bakiyesine eşit:
+        // Ana Kasa = 100 + 260 + 120 − 50 − 100; Euro Hesabı = 3,10 + 200
+        assert_eq!(hesap_neti(&mizan, "100.001"), Para::from_kurus(33_000));
+        assert_eq!(hesap_neti(&mizan, "102.001"), Para::from_kurus(20_310));
+        // Tahsil edilen aidat alacağı kapanır, cari kalan borç 100
+        assert_eq!(hesap_neti(&mizan, HESAP_AIDAT_ALACAKLARI), Para::SIFIR);
+        assert_eq!(hesap_neti(&mizan, "120.001"), Para::from_kurus(10_000));
+        // Dövizli virman farkı kambiyo zararında
+        assert_eq!(hesap_neti(&mizan, HESAP_KAMBIYO_ZARARI), Para::from_kurus(9_690));
+    }
+
+    #[test]
+    fn gecikme_faizi_ayri_alacak() {
+        let mut conn = setup();
+        let c = &mut conn;
+
+        exec(c, "INSERT INTO gecikme_faizleri (id, tenant_id, aidat_id, uye_id, hesap_tarihi, vade_tarihi, gecikme_gun, anapara, tutar, created_at, updated_at)
+                 VALUES ('f1', 't1', 'a1', 'u1', '2026-03-01', '2026-01-31', 29, 120.0, 10.0, '2026-03-01', '2026-03-01')");
+        assert_eq!(kaydi_isle(c, "t1", "gecikme_faizleri", "f1").unwrap(), FisDegisikligi::Olusturuldu);
+
+        // Kısmi feragat: fiş net tutarla yenilenir
+        exec(c, "UPDATE gecikme_faizleri SET feragat_tutari = 4.0 WHERE id = 'f1'");
+        assert_eq!(kaydi_isle(c, "t1", "gecikme_faizleri", "f1").unwrap(), FisDegisikligi::Yenilendi);
+
+        // Tahsilat geliri alacağı kapatır, ikinci kez gelir yazmaz
+        exec(c, "UPDATE gecikme_faizleri SET gelir_id = 'g1' WHERE id = 'f1'");
+        exec(c, "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar, uye_id) VALUES ('g1', 't1', 'k1', '2026-03-05', 6.0, 'u1')");
+        kaydi_isle(c, "t1", "gelirler", "g1").unwrap();
+
+        let mizan = mizan_hesapla(c, "t1", None, None).unwrap();
+        assert!(mizan.dengeli);
+        assert_eq!(hesap_neti(&mizan, HESAP_AIDAT_ALACAKLARI), Para::SIFIR);
+        assert_eq!(hesap_neti(&mizan, HESAP_GELIRLER), Para::from_kurus(-600));
+
+        // Tamamından feragat edilen faiz fiş üretmez
+        exec(c, "INSERT INTO gecikme_faizleri (id, tenant_id, aidat_id, uye_id, hesap_tarihi, vade_tarihi, gecikme_gun, anapara, tutar, feragat_tutari, created_at, updated_at)
+                 VALUES ('f2', 't1', 'a1', 'u1', '2026-03-01', '2026-01-31', 29, 120.0, 5.0, 5.0, '2026-03-01', '2026-03-01')");
+        assert_eq!(kaydi_isle(c, "t1", "gecikme_faizleri", "f2").unwrap(), FisDegisikligi::Yok);
+    }
+
+    #[test]
+    fn avans_iadesi_aidat_alacagindan_duser() {
+        let mut conn = setup();
+        let c = &mut conn;
+
+        // Peşin ödeme (türü ne olursa olsun) 127'ye alacak yazılır, iadesi aynı hesabı kapatır
+        exec(c, "INSERT INTO uye_avans_hareketleri (id, tenant_id, uye_id, tarih, tur, tutar, gelir_id, created_at)
+                 VALUES ('a0', 't1', 'u1', '2026-03-01', 'giris', 50.0, 'g1', '2026-03-01')");
+        exec(c, "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, tarih, tutar, uye_id) VALUES ('g1', 't1', 'k1', 'Bağış', '2026-03-01', 50.0, 'u1')");
+        kaydi_isle(c, "t1", "gelirler", "g1").unwrap();
+        exec(c, "INSERT INTO giderler (id, tenant_id, kasa_id, gider_turu, tarih, tutar) VALUES ('d1', 't1', 'k1', 'Diğer', '2026-03-10', 50.0)");
+        exec(c, "INSERT INTO uye_avans_hareketleri (id, tenant_id, uye_id, tarih, tur, tutar, gider_id, created_at)
+                 VALUES ('a1', 't1', 'u1', '2026-03-10', 'iade', -50.0, 'd1', '2026-03-10')");
+        kaydi_isle(c, "t1", "giderler", "d1").unwrap();
+
+        let mizan = mizan_hesapla(c, "t1", None, None).unwrap();
+        assert!(mizan.dengeli);
+        assert_eq!(hesap_neti(&mizan, HESAP_AIDAT_ALACAKLARI), Para::SIFIR);
+        assert!(!mizan.satirlar.iter().any(|s| s.hesap_kodu.starts_with(HESAP_GIDERLER)));
+    }
+
+    #[test]
+    fn devredilmis_kasanin_acilisi_devir_oncesi_hareketi_icermez() {
+        let mut conn = setup();
+        let c = &mut conn;
+        // 2025 devri: 400 TL açılış + 2025'te net 600 TL → devir_bakiye 1.000 TL
+        exec(
+            c,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, devir_bakiye, devir_tarihi, created_at) VALUES
+                ('k3', 't1', 'Şube Kasası', 1000.0, '2026-01-01', '2025-01-01 09:00:00')",
+        );
+        exec(c, "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar) VALUES ('g1', 't1', 'k3', '2025-04-01', 700.0), ('g2', 't1', 'k3', '2026-02-01', 50.0)");
+        exec(c, "INSERT INTO giderler (id, tenant_id, kasa_id, tarih, tutar) VALUES ('d1', 't1', 'k3', '2025-09-01', 100.0)");
+        for (tablo, id) in [("kasalar", "k3"), ("gelirler", "g1"), ("gelirler", "g2"), ("giderler", "d1")] {
+            kaydi_isle(c, "t1", tablo, id).unwrap();
+        }
+
+        let kasa = kasa_hesabi(c, "t1", "k3").unwrap();
+        let mizan = mizan_hesapla(c, "t1", None, None).unwrap();
+        assert_eq!(hesap_neti(&mizan, &kasa.kod), Para::from_kurus(105_000));
+    }
+
+    #[test]
+    fn devir_tarihi_eksik_kasanin_acilisi_reddedilir() {
+        let mut conn = setup();
+        let c = &mut conn;
+        exec(
+            c,
+            "INSERT INTO kasalar (id, tenant_id, kasa_adi, devir_bakiye, created_at) VALUES
+                ('k3', 't1', 'Şube Kasası', 1000.0, '2025-01-01 09:00:00')",
+        );
+        exec(c, "INSERT INTO gelirler (id, tenant_id, kasa_id, tarih, tutar) VALUES ('g1', 't1', 'k3', '2025-04-01', 1000.0)");
+
+        let hata = kaydi_isle(c, "t1", "kasalar", "k3").unwrap_err();
+        assert!(hata.contains("devir tarihi eksik"), "{}", hata);
+        assert_eq!(fis_sayisi(c, "aktif"), 0);
+    }
+
+    #[test]
+    fn kaydi_isle_idempotent() {
+        let mut conn = setup();
+        exec(&mut conn, "INSERT INTO gelirler (id, tenant_id, kasa_id, gelir_turu, tarih, tutar) VALUES ('g1', 't1', 'k1', 'Bağış', '2026-02-01', 10.0)");
+
+        assert_eq!(kaydi_isle(&mut conn, "t1", "gelirler", "g1").unwrap(), FisDegisikligi::Olusturuldu);
+        assert_eq!(kaydi_isle(&mut conn, "t1", "gelirler", "g1").unwrap(), FisDegisikligi::Yok);
+
+        // "bağış" yazımı aynı gelir hesabına düşer, fiş değişmez
+        exec(&mut conn, "UPDATE gelirler SET gelir_turu = 'bağış' WHERE id = 'g1'");
+        assert_eq!(kaydi_isle(&mut conn, "t1", "gelirler", "g1").unwrap(), FisDegisikligi::Yok);
+
+        let sonuc = tumunu_yeniden_olustur(&mut conn, "t1").unwrap();
+        assert_eq!(sonuc.olusturulan, 1); // k1 açılışı
+        assert_eq!(sonuc.yenilenen, 0);
+        assert_eq!(sonuc.iptal_edilen, 0);
+
+        let tekrar = tumunu_yeniden_olustur(&mut conn, "t1").unwrap();
+        assert_eq!(tekrar.olusturulan + tekrar.yenilenen + tekrar.iptal_edilen, 0);
+        assert_eq!(fis_sayisi(&mut conn, "aktif"), 2);
+    }
+
+    #[test]
+    fn dengesiz_fis_reddedilir() {
+        let mut conn = setup();
+        let kasa = kasa_hesabi(&mut conn, "t1", "k1").unwrap();
+        let gelir = ana_hesap(&mut conn, "t1", HESAP_GELIRLER).unwrap();
+
+        let taslak = FisTaslagi {
+            tarih: "2026-03-01".to_string(),
+            aciklama: "Hatalı".to_string(),
+            satirlar: vec![
+                FisSatiri { hesap: kasa, borc: Para::from_kurus(1_000), alacak: Para::SIFIR },
+                FisSatiri { hesap: gelir, borc: Para::SIFIR, alacak: Para::from_kurus(999) },
+            ],
+        };
+        assert!(fis_yaz(&mut conn, "t1", "gelirler", "x", &taslak).is_err());
+        assert_eq!(fis_sayisi(&mut conn, "aktif"), 0);
+    }
+}
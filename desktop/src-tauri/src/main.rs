@@ -4,6 +4,7 @@
 mod commands;
 mod db;
 mod state;
+mod utils;
 
 use state::AppState;
 use std::sync::Mutex;
@@ -64,11 +65,17 @@ fn main() {
                 if let Err(e) = db::connection::run_migrations(&mut conn) {
                      eprintln!("❌ Migration warning: {:?}", e);
                 }
+                if let Err(e) = db::denetim::koruma_kur(&mut conn) {
+                     eprintln!("❌ Denetim koruması kurulamadı: {:?}", e);
+                }
                 
                 println!("✅ Saving state...");
                 let state = app.state::<AppState>();
                 *state.db.lock().unwrap() = Some(pool);
                 *state.db_path.lock().unwrap() = Some(db_path);
+
+                // Aidat tipine göre dönem başı tahakkuk (kaçırılan dönemler dahil)
+                commands::aidat_tahakkuku::zamanlayiciyi_baslat(app.handle().clone());
                 
                 Ok(())
             })();
@@ -109,6 +116,8 @@ fn main() {
             commands::tenant::update_tenant,
             commands::tenant::list_tenants,
             commands::tenant::check_slug_available,
+            commands::tenant::get_tenant_logo,
+            commands::tenant::set_tenant_logo,
             // Üyeler
             commands::uyeler::get_uyeler,
             commands::uyeler::get_uye_by_id,
@@ -181,6 +190,155 @@ fn main() {
             commands::mali::get_devir_onizleme,
             commands::mali::uygula_yil_sonu_devir,
             commands::mali::get_kasa_ozet,
+            commands::mali::recalculate_kasa_bakiye,
+            commands::mali::check_kasa_bakiye_tutarliligi,
+            commands::yevmiye::get_hesap_plani,
+            commands::yevmiye::get_yevmiye_fisleri,
+            commands::yevmiye::get_mizan,
+            commands::yevmiye::yevmiye_yeniden_olustur,
+            commands::resmi_defterler::get_isletme_hesabi_defteri,
+            commands::resmi_defterler::get_gelir_gider_tablosu,
+            commands::beyanname::get_dernek_beyannamesi,
+            commands::beyanname::get_beyanname_alan_kaynaklari,
+            commands::donem_kilidi::get_donem_kilitleri,
+            commands::donem_kilidi::donem_kilitle,
+            commands::donem_kilidi::donem_kilidi_ac,
+            commands::donem_kilidi::get_donem_kilidi_ihlalleri,
+            commands::donem_kilidi::donem_kilidi_ihlali_sonuclandir,
+            commands::denetim::get_kayit_gecmisi,
+            commands::denetim::get_kullanici_islemleri,
+            // Banka ekstresi aktarımı
+            commands::banka_ekstresi::banka_ekstresi_yukle,
+            commands::banka_ekstresi::get_banka_ekstre_aktarimlari,
+            commands::banka_ekstresi::get_banka_ekstre_aktarimi,
+            commands::banka_ekstresi::get_son_csv_eslestirmesi,
+            commands::banka_ekstresi::get_banka_ekstre_satiri_adaylari,
+            commands::banka_ekstresi::banka_ekstre_satiri_karar,
+            commands::banka_ekstresi::banka_ekstresi_uygula,
+            commands::banka_ekstresi::banka_ekstresi_iptal,
+            // Kasa sayımı
+            commands::kasa_sayimi::get_kasa_defter_bakiyesi,
+            commands::kasa_sayimi::kasa_sayimi_kaydet,
+            commands::kasa_sayimi::kasa_sayimi_onayla,
+            commands::kasa_sayimi::kasa_sayimi_iptal,
+            commands::kasa_sayimi::get_kasa_sayimlari,
+            // Alındı belgesi ciltleri
+            commands::alindi_belgesi::get_alindi_belgesi_ciltleri,
+            commands::alindi_belgesi::alindi_belgesi_cildi_ekle,
+            commands::alindi_belgesi::alindi_belgesi_cildi_guncelle,
+            commands::alindi_belgesi::alindi_belgesi_iptal,
+            commands::alindi_belgesi::get_alindi_belgesi_kontrolu,
+            commands::alindi_belgesi::get_alindi_belgesi_kayit_defteri,
+            // Aidat fiyat kuralları
+            commands::aidat_fiyat::get_aidat_fiyat_kurallari,
+            commands::aidat_fiyat::aidat_fiyat_kurali_kaydet,
+            commands::aidat_fiyat::aidat_fiyat_kurali_sil,
+            commands::aidat_fiyat::aidat_fiyat_hesapla,
+            // Zamanlanmış aidat tahakkuku
+            commands::aidat_tahakkuku::get_aidat_tahakkuk_calismalari,
+            commands::aidat_tahakkuku::get_bekleyen_aidat_donemleri,
+            commands::aidat_tahakkuku::aidat_tahakkukunu_calistir,
+            commands::aidat_tahakkuku::get_otomatik_aidat_tahakkuku,
+            commands::aidat_tahakkuku::set_otomatik_aidat_tahakkuku,
+            // Gecikme faizi
+            commands::gecikme::get_gecikme_politikalari,
+            commands::gecikme::gecikme_politikasi_kaydet,
+            commands::gecikme::gecikme_politikasi_sil,
+            commands::gecikme::gecikme_faizi_onizle,
+            commands::gecikme::gecikme_faizlerini_tahakkuk_et,
+            commands::gecikme::get_gecikme_faizleri,
+            commands::gecikme::gecikme_faizi_feragat_et,
+            commands::gecikme::gecikme_faizi_tahsil_et,
+            // Üye avans
+            commands::avans::get_uye_avans,
+            commands::avans::get_avans_bakiyeleri,
+            commands::avans::uye_avans_tahsil_et,
+            commands::avans::uye_avans_iade_et,
+            // Taksitlendirme
+            commands::taksit::get_taksit_planlari,
+            commands::taksit::get_taksit_plani,
+            commands::taksit::taksit_plani_onizle,
+            commands::taksit::taksit_plani_olustur,
+            commands::taksit::taksit_odemesi_al,
+            commands::taksit::get_bozuk_taksit_planlari,
+            commands::taksit::taksit_plani_kapat,
+            // Borç yaşlandırma ve ihtar
+            commands::ihtar::get_borc_yaslandirma,
+            commands::ihtar::get_ihtar_sablonlari,
+            commands::ihtar::ihtar_sablonu_kaydet,
+            commands::ihtar::get_ihrac_kurali,
+            commands::ihtar::ihrac_kurali_kaydet,
+            commands::ihtar::get_ihtar_adaylari,
+            commands::ihtar::ihtar_olustur,
+            commands::ihtar::get_ihtarlar,
+            commands::ihtar::get_ihtar,
+            commands::ihtar::ihtar_bildirimi_kaydet,
+            commands::ihtar::get_ihrac_adaylari,
+            // Aile (hane) üyelikleri
+            commands::hane::get_haneler,
+            commands::hane::get_hane,
+            commands::hane::get_uye_hanesi,
+            commands::hane::hane_kaydet,
+            commands::hane::hane_kapat,
+            commands::hane::get_hane_aidat_paylari,
+            // Üye aktarımı
+            commands::uye_aktarimi::uye_aktarimi_onizle,
+            commands::uye_aktarimi::uye_aktarimi_uygula,
+            // Üyelik durum geçmişi
+            commands::uyelik_durumu::get_uyelik_durum_gecmisi,
+            commands::uyelik_durumu::uyelik_durumu_degistir,
+            // Üyelik başvuruları
+            commands::uyelik_basvurusu::get_uyelik_basvurulari,
+            commands::uyelik_basvurusu::get_uyelik_basvurusu,
+            commands::uyelik_basvurusu::uyelik_basvurusu_kaydet,
+            commands::uyelik_basvurusu::uyelik_basvurusu_karar,
+            // Genel kurul
+            commands::genel_kurul::get_genel_kurullar,
+            commands::genel_kurul::get_genel_kurul,
+            commands::genel_kurul::genel_kurul_kaydet,
+            commands::genel_kurul::get_genel_kurul_hazirun,
+            commands::genel_kurul::genel_kurul_hazirun_olustur,
+            commands::genel_kurul::genel_kurul_imza,
+            commands::genel_kurul::genel_kurul_gundem_kaydet,
+            commands::genel_kurul::genel_kurul_ac,
+            commands::genel_kurul::genel_kurul_oylama,
+            commands::genel_kurul::genel_kurul_secim_kaydet,
+            commands::genel_kurul::genel_kurul_secim_sonucu,
+            commands::genel_kurul::genel_kurul_tamamla,
+            // Karar defteri
+            commands::karar_defteri::get_kararlar,
+            commands::karar_defteri::get_karar,
+            commands::karar_defteri::karar_ekle,
+            commands::karar_defteri::karar_guncelle,
+            commands::karar_defteri::karar_iptal,
+            commands::karar_defteri::karar_baglanti_ekle,
+            commands::karar_defteri::karar_baglanti_sil,
+            commands::karar_defteri::get_kayit_kararlari,
+            // Kurul ve komisyon görevleri
+            commands::organ_gorevi::get_organ_gorevleri,
+            commands::organ_gorevi::organ_gorevi_kaydet,
+            commands::organ_gorevi::organ_gorevinden_ayril,
+            commands::organ_gorevi::organ_secimden_gorevlendir,
+            commands::organ_gorevi::get_biten_organ_gorevleri,
+            commands::organ_gorevi::get_organ_yetersayisi,
+            // Toplantı davet ve katılım
+            commands::toplanti_katilimi::get_toplanti_katilimi,
+            commands::toplanti_katilimi::toplanti_davetlileri_olustur,
+            commands::toplanti_katilimi::toplanti_davetli_ekle,
+            commands::toplanti_katilimi::toplanti_davetli_sil,
+            commands::toplanti_katilimi::toplanti_davet_gonder,
+            commands::toplanti_katilimi::toplanti_katilim_kaydet,
+            commands::toplanti_katilimi::get_uye_katilim_gecmisi,
+            commands::toplanti_katilimi::get_katilim_raporu,
+            // PDF belgeler
+            commands::belge_pdf::aidat_makbuzu_pdf,
+            commands::belge_pdf::uye_hesap_ekstresi_pdf,
+            commands::belge_pdf::cari_ekstre_pdf,
+            commands::belge_pdf::ihtar_mektubu_pdf,
+            commands::belge_pdf::hazirun_listesi_pdf,
+            commands::belge_pdf::genel_kurul_tutanagi_pdf,
+            commands::belge_pdf::karar_defteri_pdf,
+            commands::belge_pdf::toplanti_davetiyesi_pdf,
             // Sync
             commands::sync::get_sync_status,
             commands::sync::get_pending_changes,
@@ -214,6 +372,10 @@ fn main() {
             commands::export::export_giderler_excel,
             commands::export::export_uyeler_excel,
             commands::export::export_demirbaslar_excel,
+            commands::export::export_isletme_hesabi_defteri_excel,
+            commands::export::export_gelir_gider_tablosu_excel,
+            commands::export::export_dernek_beyannamesi_excel,
+            commands::export::export_alindi_belgesi_kayit_defteri_excel,
             // Etkinlikler
             commands::etkinlikler::get_etkinlikler,
             commands::etkinlikler::get_etkinlik_mali_ozet,
@@ -221,6 +383,13 @@ fn main() {
             commands::etkinlikler::create_etkinlik,
             commands::etkinlikler::update_etkinlik,
             commands::etkinlikler::delete_etkinlik,
+            // Etkinlik kayıtları ve giriş
+            commands::etkinlik_kaydi::get_etkinlik_kayitlari,
+            commands::etkinlik_kaydi::etkinlik_kaydi_ekle,
+            commands::etkinlik_kaydi::etkinlik_kaydi_iptal,
+            commands::etkinlik_kaydi::etkinlik_bileti_tahsil_et,
+            commands::etkinlik_kaydi::etkinlik_girisi,
+            commands::etkinlik_kaydi::get_etkinlik_katilim_raporu,
             // Toplantılar
             commands::toplantilar::get_toplantilar,
             commands::toplantilar::get_toplanti,  // YENİ!
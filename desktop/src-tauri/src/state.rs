@@ -157,6 +157,26 @@ impl AppState {
             }
         }
     }
+
+    /// Yazım komutları için denetim izi; oturumdaki kullanıcıyla komut adını taşır
+    pub fn denetim(&self, komut: &str) -> crate::db::denetim::Denetim {
+        let user = self.current_user.lock().unwrap();
+        crate::db::denetim::Denetim::yeni(
+            user.as_ref().map(|u| u.id.clone()),
+            user.as_ref().map(|u| u.full_name.clone()),
+            komut,
+        )
+    }
+
+    /// Yönetici yetkisi gerektiren işlemler için oturumdaki kullanıcıyı döner
+    /// (check_permission("admin") ile aynı kural; rol büyük/küçük harf duyarsız)
+    pub fn verify_admin(&self) -> Result<CurrentUser, String> {
+        match self.current_user.lock().unwrap().as_ref() {
+            Some(user) if user.role.eq_ignore_ascii_case("admin") || user.is_superuser => Ok(user.clone()),
+            Some(_) => Err("Bu işlem için yönetici yetkisi gerekir".to_string()),
+            None => Err("Unauthorized: No active session. Please login first.".to_string()),
+        }
+    }
 }
 
 // ============================================================================
@@ -109,6 +109,7 @@ pub fn validate_date_format(date: &str) -> Result<(), String> {
 }
 
 /// Tutar validasyonu (pozitif sayı)
+#[allow(dead_code)]
 pub fn validate_amount(amount: f64, field_name: &str) -> Result<(), String> {
     if amount < 0.0 {
         return Err(format!("{} negatif olamaz", field_name));
@@ -122,6 +123,7 @@ pub fn validate_amount(amount: f64, field_name: &str) -> Result<(), String> {
 }
 
 /// String uzunluk validasyonu
+#[allow(dead_code)]
 pub fn validate_length(text: &str, field_name: &str, min: usize, max: usize) -> Result<(), String> {
     let len = text.len();
 
@@ -137,6 +139,7 @@ pub fn validate_length(text: &str, field_name: &str, min: usize, max: usize) ->
 }
 
 /// IBAN validasyonu
+#[allow(dead_code)]
 pub fn validate_iban(iban: &str) -> Result<(), String> {
     if iban.is_empty() {
         return Err("IBAN boş olamaz".to_string());
@@ -156,6 +159,7 @@ pub fn validate_iban(iban: &str) -> Result<(), String> {
 }
 
 /// Şifre güvenlik validasyonu
+#[allow(dead_code)]
 pub fn validate_password_strength(password: &str) -> Result<(), String> {
     if password.len() < 8 {
         return Err("Şifre en az 8 karakter olmalıdır".to_string());
@@ -177,6 +181,7 @@ pub fn validate_password_strength(password: &str) -> Result<(), String> {
 }
 
 /// URL validasyonu
+#[allow(dead_code)]
 pub fn validate_url(url: &str) -> Result<(), String> {
     if url.is_empty() {
         return Err("URL boş olamaz".to_string());